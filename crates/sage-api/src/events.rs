@@ -19,4 +19,10 @@ pub enum SyncEvent {
     CatInfo,
     DidInfo,
     NftData,
+    OfferGroupUpdated {
+        offer_group_id: u32,
+    },
+    MintJobUpdated {
+        mint_job_id: u32,
+    },
 }
@@ -1,27 +1,41 @@
 mod coin;
+mod contact;
 mod derivation;
 mod did;
+mod mint_job;
 mod nft;
 mod nft_collection;
+mod nft_integrity;
 mod offer;
+mod offer_group;
 mod offer_summary;
 mod option;
+mod payout;
 mod peer;
 mod pending_transaction;
+mod scheduled_payment;
 mod token;
+mod trade;
 mod transaction;
 mod transaction_summary;
 
 pub use coin::*;
+pub use contact::*;
 pub use derivation::*;
 pub use did::*;
+pub use mint_job::*;
 pub use nft::*;
 pub use nft_collection::*;
+pub use nft_integrity::*;
 pub use offer::*;
+pub use offer_group::*;
 pub use offer_summary::*;
 pub use option::*;
+pub use payout::*;
 pub use peer::*;
 pub use pending_transaction::*;
+pub use scheduled_payment::*;
 pub use token::*;
+pub use trade::*;
 pub use transaction::*;
 pub use transaction_summary::*;
@@ -0,0 +1,21 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct ContactRecord {
+    pub id: u32,
+    pub name: String,
+    pub notes: Option<String>,
+    pub did_id: Option<String>,
+    pub addresses: Vec<ContactAddressRecord>,
+    pub created_timestamp: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct ContactAddressRecord {
+    pub address: String,
+    pub label: Option<String>,
+}
@@ -13,5 +13,17 @@ pub struct DidRecord {
     pub address: String,
     pub amount: Amount,
     pub recovery_hash: Option<String>,
+    /// The DIDs which can recover the DID, if the recovery list is known
+    pub recovery_did_ids: Option<Vec<String>>,
+    pub num_verifications_required: u64,
+    pub metadata: Vec<DidMetadataEntry>,
     pub created_height: Option<u32>,
 }
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct DidMetadataEntry {
+    pub key: String,
+    pub value: String,
+}
@@ -0,0 +1,41 @@
+use serde::{Deserialize, Serialize};
+
+use crate::Amount;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct MintJobRecord {
+    pub id: u32,
+    pub name: String,
+    pub did_id: String,
+    pub fee: Amount,
+    pub max_cost: u64,
+    pub is_paused: bool,
+    pub error: Option<String>,
+    pub created_timestamp: u64,
+    pub item_count: u32,
+    pub submitted_count: u32,
+    pub confirmed_count: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct MintJobItemRecord {
+    pub row_index: u32,
+    pub nft_id: Option<String>,
+    pub address: Option<String>,
+    pub edition_number: u64,
+    pub edition_total: u64,
+    pub data_uris: Vec<String>,
+    pub data_hash: Option<String>,
+    pub metadata_uris: Vec<String>,
+    pub metadata_hash: Option<String>,
+    pub license_uris: Vec<String>,
+    pub license_hash: Option<String>,
+    pub royalty_address: Option<String>,
+    pub royalty_ten_thousandths: u16,
+    pub transaction_id: Option<String>,
+    pub is_confirmed: bool,
+}
@@ -50,3 +50,19 @@ pub struct NftData {
     pub metadata_json: Option<String>,
     pub metadata_hash_matches: bool,
 }
+
+/// How the preview images of an NFT were made from its data. Vector images are rasterized,
+/// animations are previewed as a strip of frames, and placeholders are generated with a badge for
+/// file types that can't be rendered. Videos aren't decoded, so they're previewed by the cover art
+/// embedded in the file, and videos without any get a placeholder instead.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum NftPreviewSource {
+    Image,
+    Vector,
+    Animation,
+    CoverArt,
+    Placeholder,
+}
@@ -0,0 +1,74 @@
+use serde::{Deserialize, Serialize};
+
+/// Whether the content of an NFT file matches its on-chain hash. A file is partially verified if
+/// only some of its URIs match, and unhashed NFTs can't be verified at all. The variants are
+/// ordered from best to worst, so the status of an NFT is the worst status of its files.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum IntegrityStatus {
+    Verified,
+    Pending,
+    Unhashed,
+    Partial,
+    Unavailable,
+    Mismatch,
+}
+
+/// The result of the last check of a single URI
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum UriIntegrityStatus {
+    Verified,
+    Pending,
+    Failed,
+    Mismatch,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct NftIntegrityRecord {
+    pub nft_id: String,
+    pub name: Option<String>,
+    pub status: IntegrityStatus,
+    pub data: Option<FileIntegrityRecord>,
+    pub metadata: Option<FileIntegrityRecord>,
+    pub license: Option<FileIntegrityRecord>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct FileIntegrityRecord {
+    pub hash: String,
+    pub status: IntegrityStatus,
+    pub uris: Vec<UriIntegrityRecord>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct UriIntegrityRecord {
+    pub uri: String,
+    pub status: UriIntegrityStatus,
+    pub error: Option<String>,
+    pub last_checked_timestamp: Option<u64>,
+    pub failed_attempts: u32,
+}
+
+/// The number of NFTs with each integrity status
+#[derive(Debug, Default, Clone, Copy, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct NftIntegritySummary {
+    pub verified: u32,
+    pub pending: u32,
+    pub unhashed: u32,
+    pub partial: u32,
+    pub unavailable: u32,
+    pub mismatch: u32,
+}
@@ -1,6 +1,6 @@
 use serde::{Deserialize, Serialize};
 
-use super::OfferSummary;
+use crate::{Amount, OfferSummary};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[cfg_attr(feature = "tauri", derive(specta::Type))]
@@ -11,6 +11,22 @@ pub struct OfferRecord {
     pub status: OfferRecordStatus,
     pub creation_timestamp: u64,
     pub summary: OfferSummary,
+    pub blocks_until_expiration: Option<u32>,
+    pub seconds_until_expiration: Option<u64>,
+    pub publications: Vec<OfferPublicationRecord>,
+    pub invalid_reason: Option<String>,
+    pub auto_recreate: bool,
+    pub recreated_offer_id: Option<String>,
+    pub recreate_error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct OfferPublicationRecord {
+    pub exchange: String,
+    pub code: String,
+    pub published_timestamp: u64,
 }
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize)]
@@ -23,4 +39,25 @@ pub enum OfferRecordStatus {
     Completed = 2,
     Cancelled = 3,
     Expired = 4,
+    Invalid = 5,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct OfferCoinState {
+    pub coin_id: String,
+    pub amount: Amount,
+    pub status: OfferCoinStatus,
+    pub spent_height: Option<u32>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum OfferCoinStatus {
+    Unspent,
+    Spent,
+    Missing,
 }
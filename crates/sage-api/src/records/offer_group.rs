@@ -0,0 +1,29 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{Amount, OfferRecord};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct OfferGroupRecord {
+    pub id: u32,
+    pub offered_asset_id: Option<String>,
+    pub requested_asset_id: Option<String>,
+    pub created_timestamp: u64,
+    pub offer_count: u32,
+    pub active_count: u32,
+    pub auto_repost: bool,
+    pub price_spread: String,
+    pub stop_price: Option<String>,
+    pub max_inventory: Option<Amount>,
+    pub stop_reason: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct OfferGroupOfferRecord {
+    pub price: String,
+    pub is_reversed: bool,
+    pub offer: OfferRecord,
+}
@@ -0,0 +1,32 @@
+use serde::{Deserialize, Serialize};
+
+use crate::Amount;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct PayoutRecord {
+    pub id: u32,
+    pub name: String,
+    pub fee: Amount,
+    pub max_cost: u64,
+    pub is_paused: bool,
+    pub error: Option<String>,
+    pub created_timestamp: u64,
+    pub payment_count: u32,
+    pub submitted_count: u32,
+    pub confirmed_count: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct PayoutPaymentRecord {
+    pub row_index: u32,
+    pub asset_id: Option<String>,
+    pub address: String,
+    pub amount: Amount,
+    pub memos: Vec<String>,
+    pub transaction_id: Option<String>,
+    pub is_confirmed: bool,
+}
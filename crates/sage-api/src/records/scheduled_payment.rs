@@ -0,0 +1,27 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{Amount, Payment};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct ScheduledPaymentRecord {
+    pub id: u32,
+    pub name: String,
+    pub schedule: String,
+    pub payments: Vec<Payment>,
+    pub fee: Amount,
+    pub is_paused: bool,
+    pub next_run_timestamp: u64,
+    pub created_timestamp: u64,
+    pub runs: Vec<ScheduledPaymentRunRecord>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct ScheduledPaymentRunRecord {
+    pub transaction_id: Option<String>,
+    pub error: Option<String>,
+    pub run_timestamp: u64,
+}
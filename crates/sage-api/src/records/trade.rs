@@ -0,0 +1,53 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{Amount, Asset};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct TradeRecord {
+    pub offer_id: String,
+    pub is_maker: bool,
+    pub transaction_id: Option<String>,
+    pub fee: Amount,
+    pub fill_height: Option<u32>,
+    pub fill_timestamp: Option<u64>,
+    pub inserted_timestamp: u64,
+    pub given: Vec<TradeAmount>,
+    pub received: Vec<TradeAmount>,
+    pub prices: Vec<TradePrice>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct TradeAmount {
+    pub asset: Asset,
+    pub amount: Amount,
+    pub royalty: Amount,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct TradePrice {
+    pub base_asset_id: Option<String>,
+    pub quote_asset_id: Option<String>,
+    pub price: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct TradeAssetPnl {
+    pub asset: Asset,
+    pub received: Amount,
+    pub given: Amount,
+    pub royalties_paid: Amount,
+    pub net: String,
+    pub bought: Amount,
+    pub sold: Amount,
+    pub average_buy_price: Option<String>,
+    pub average_sell_price: Option<String>,
+    pub realized_pnl: Option<String>,
+}
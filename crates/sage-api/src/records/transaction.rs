@@ -1,6 +1,6 @@
 use serde::{Deserialize, Serialize};
 
-use crate::{AddressKind, Amount, Asset};
+use crate::{AddressKind, Amount, Asset, AssetKind};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[cfg_attr(feature = "tauri", derive(specta::Type))]
@@ -21,4 +21,25 @@ pub struct TransactionCoinRecord {
     pub address: Option<String>,
     pub address_kind: AddressKind,
     pub asset: Asset,
+    pub contact: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct TransactionExportRecord {
+    pub height: u32,
+    pub timestamp: Option<u64>,
+    pub transaction_id: Option<String>,
+    pub asset_kind: AssetKind,
+    pub asset_id: Option<String>,
+    pub ticker: Option<String>,
+    pub name: Option<String>,
+    pub precision: u8,
+    pub amount: String,
+    pub fee: Amount,
+    pub counterparty: Option<String>,
+    pub memo: Option<String>,
+    pub offer_id: Option<String>,
+    pub label: Option<String>,
 }
@@ -8,6 +8,7 @@ use crate::{Amount, Asset};
 pub struct TransactionSummary {
     pub fee: Amount,
     pub inputs: Vec<TransactionInput>,
+    pub nft_metadata_diffs: Vec<NftMetadataDiff>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -56,4 +57,29 @@ pub struct TransactionOutput {
     pub address: String,
     pub receiving: bool,
     pub burning: bool,
+    pub contact: Option<String>,
+    pub is_new_address: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct NftMetadataDiff {
+    pub nft_id: String,
+    pub old: NftMetadataJson,
+    pub new: NftMetadataJson,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct NftMetadataJson {
+    pub edition_number: u64,
+    pub edition_total: u64,
+    pub data_uris: Vec<String>,
+    pub data_hash: Option<String>,
+    pub metadata_uris: Vec<String>,
+    pub metadata_hash: Option<String>,
+    pub license_uris: Vec<String>,
+    pub license_hash: Option<String>,
 }
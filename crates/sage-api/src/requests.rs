@@ -1,15 +1,37 @@
 mod actions;
+mod contacts;
 mod data;
+mod dids;
+mod exchanges;
 mod keys;
+mod mint_jobs;
+mod nft_integrity;
+mod offer_groups;
 mod offers;
+mod payouts;
+mod scheduled_payments;
+mod search;
 mod settings;
+mod spending_policy;
+mod trades;
 mod transactions;
 
 pub use actions::*;
+pub use contacts::*;
 pub use data::*;
+pub use dids::*;
+pub use exchanges::*;
 pub use keys::*;
+pub use mint_jobs::*;
+pub use nft_integrity::*;
+pub use offer_groups::*;
 pub use offers::*;
+pub use payouts::*;
+pub use scheduled_payments::*;
+pub use search::*;
 pub use settings::*;
+pub use spending_policy::*;
+pub use trades::*;
 pub use transactions::*;
 
 pub mod wallet_connect;
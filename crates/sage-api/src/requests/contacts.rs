@@ -0,0 +1,169 @@
+use serde::{Deserialize, Serialize};
+
+use crate::ContactRecord;
+
+/// List contacts in the address book
+#[cfg_attr(
+    feature = "openapi",
+    crate::openapi_attr(
+        tag = "Contacts",
+        description = "List all contacts in the address book along with their addresses."
+    )
+)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct GetContacts {}
+
+/// Response with address book contacts
+#[cfg_attr(feature = "openapi", crate::openapi_attr(tag = "Contacts"))]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct GetContactsResponse {
+    /// List of contacts
+    pub contacts: Vec<ContactRecord>,
+}
+
+/// Get a specific contact
+#[cfg_attr(
+    feature = "openapi",
+    crate::openapi_attr(
+        tag = "Contacts",
+        description = "Get a single contact from the address book by its ID."
+    )
+)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct GetContact {
+    /// Contact ID
+    #[cfg_attr(feature = "openapi", schema(example = 1))]
+    pub id: u32,
+}
+
+/// Response with contact details
+#[cfg_attr(feature = "openapi", crate::openapi_attr(tag = "Contacts"))]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct GetContactResponse {
+    /// Contact details
+    #[cfg_attr(feature = "openapi", schema(nullable = true))]
+    pub contact: Option<ContactRecord>,
+}
+
+/// Address to store for a contact
+#[cfg_attr(feature = "openapi", crate::openapi_attr(tag = "Contacts"))]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct ContactAddress {
+    /// Address on the current network
+    #[cfg_attr(feature = "openapi", schema(example = "xch1..."))]
+    pub address: String,
+    /// Optional label such as "Deposit" or "Cold storage"
+    #[serde(default)]
+    #[cfg_attr(feature = "openapi", schema(nullable = true))]
+    pub label: Option<String>,
+}
+
+/// Add a contact to the address book
+#[cfg_attr(
+    feature = "openapi",
+    crate::openapi_attr(
+        tag = "Contacts",
+        description = "Add a new contact with one or more validated addresses to the address book."
+    )
+)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct AddContact {
+    /// Display name of the contact
+    #[cfg_attr(feature = "openapi", schema(example = "Exchange deposit"))]
+    pub name: String,
+    /// Addresses belonging to the contact
+    pub addresses: Vec<ContactAddress>,
+    /// Optional notes
+    #[serde(default)]
+    #[cfg_attr(feature = "openapi", schema(nullable = true))]
+    pub notes: Option<String>,
+    /// Optional DID ID associated with the contact
+    #[serde(default)]
+    #[cfg_attr(feature = "openapi", schema(nullable = true, example = "did:chia:..."))]
+    pub did_id: Option<String>,
+}
+
+/// Response with the created contact
+#[cfg_attr(feature = "openapi", crate::openapi_attr(tag = "Contacts"))]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct AddContactResponse {
+    /// Created contact
+    pub contact: ContactRecord,
+}
+
+/// Update an existing contact
+#[cfg_attr(
+    feature = "openapi",
+    crate::openapi_attr(
+        tag = "Contacts",
+        description = "Replace the name, notes, DID and addresses of an existing contact."
+    )
+)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct UpdateContact {
+    /// Contact ID
+    #[cfg_attr(feature = "openapi", schema(example = 1))]
+    pub id: u32,
+    /// Display name of the contact
+    pub name: String,
+    /// Addresses belonging to the contact
+    pub addresses: Vec<ContactAddress>,
+    /// Optional notes
+    #[serde(default)]
+    #[cfg_attr(feature = "openapi", schema(nullable = true))]
+    pub notes: Option<String>,
+    /// Optional DID ID associated with the contact
+    #[serde(default)]
+    #[cfg_attr(feature = "openapi", schema(nullable = true))]
+    pub did_id: Option<String>,
+}
+
+/// Response with the updated contact
+#[cfg_attr(feature = "openapi", crate::openapi_attr(tag = "Contacts"))]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct UpdateContactResponse {
+    /// Updated contact
+    pub contact: ContactRecord,
+}
+
+/// Delete a contact
+#[cfg_attr(
+    feature = "openapi",
+    crate::openapi_attr(
+        tag = "Contacts",
+        description = "Delete a contact and all of its addresses from the address book."
+    )
+)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct DeleteContact {
+    /// Contact ID to delete
+    #[cfg_attr(feature = "openapi", schema(example = 1))]
+    pub id: u32,
+}
+
+/// Response for contact deletion
+#[cfg_attr(feature = "openapi", crate::openapi_attr(tag = "Contacts"))]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct DeleteContactResponse {}
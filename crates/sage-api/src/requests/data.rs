@@ -1,8 +1,9 @@
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    Amount, CoinRecord, DerivationRecord, DidRecord, NftCollectionRecord, NftData, NftRecord,
-    OptionRecord, PendingTransactionRecord, TokenRecord, TransactionRecord, Unit,
+    Amount, CoinRecord, DerivationRecord, DidRecord, NftCollectionRecord, NftData,
+    NftPreviewSource, NftRecord, OptionRecord, PendingTransactionRecord, TokenRecord,
+    TransactionRecord, Unit,
 };
 
 /// Validate and check an address
@@ -10,7 +11,7 @@ use crate::{
     feature = "openapi",
     crate::openapi_attr(
         tag = "Addresses",
-        description = "Validate a Chia address and check if it belongs to this wallet."
+        description = "Validate a Chia address for the current network and check if it belongs to this wallet."
     )
 )]
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -28,7 +29,7 @@ pub struct CheckAddress {
 #[cfg_attr(feature = "tauri", derive(specta::Type))]
 #[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct CheckAddressResponse {
-    /// Whether the address is valid and belongs to this wallet
+    /// Whether the address is valid for the current network and belongs to this wallet
     #[cfg_attr(feature = "openapi", schema(example = true))]
     pub valid: bool,
 }
@@ -141,6 +142,160 @@ pub struct GetDatabaseStatsResponse {
     pub wal_pages: i64,
 }
 
+/// Retrieve media cache statistics
+#[cfg_attr(
+    feature = "openapi",
+    crate::openapi_attr(
+        tag = "System & Sync",
+        description = "Retrieve how much space downloaded NFT files take up, along with the media cache policy."
+    )
+)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct GetMediaCacheStats {}
+
+/// Response with media cache statistics
+#[cfg_attr(feature = "openapi", crate::openapi_attr(tag = "System & Sync"))]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct GetMediaCacheStatsResponse {
+    /// Maximum size of the cache in bytes, or unlimited if not set
+    #[cfg_attr(feature = "openapi", schema(nullable = true))]
+    pub max_bytes: Option<u64>,
+    /// Whether new files are stored in a directory next to the database
+    pub store_externally: bool,
+    /// Number of files whose data is cached
+    pub cached_files: u64,
+    /// Total size of cached files in bytes
+    pub cached_bytes: u64,
+    /// Number of cached files stored outside of the database
+    pub external_files: u64,
+    /// Size of cached files stored outside of the database in bytes
+    pub external_bytes: u64,
+    /// Number of cached files belonging to pinned NFTs or collections
+    pub pinned_files: u64,
+    /// Size of pinned files in bytes
+    pub pinned_bytes: u64,
+    /// Size of icons, thumbnails and previews in bytes, which are never evicted
+    pub resized_image_bytes: u64,
+}
+
+/// Configure the media cache
+#[cfg_attr(
+    feature = "openapi",
+    crate::openapi_attr(
+        tag = "System & Sync",
+        description = "Set the size budget of the media cache and where files are stored. Least recently viewed files are evicted immediately if the cache is over budget, and existing files are moved to match the storage location."
+    )
+)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct SetMediaCachePolicy {
+    /// Maximum size of the cache in bytes, or unlimited if not set
+    #[serde(default)]
+    #[cfg_attr(feature = "openapi", schema(nullable = true))]
+    pub max_bytes: Option<u64>,
+    /// Store files in a content-addressed directory next to the database
+    #[serde(default)]
+    pub store_externally: bool,
+}
+
+/// Response after configuring the media cache
+#[cfg_attr(feature = "openapi", crate::openapi_attr(tag = "System & Sync"))]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct SetMediaCachePolicyResponse {
+    /// Number of files evicted to fit in the budget
+    pub evicted_files: u64,
+    /// Number of bytes evicted to fit in the budget
+    pub evicted_bytes: u64,
+    /// Number of files moved into or out of the database
+    pub relocated_files: u64,
+}
+
+/// Pin or unpin NFT media
+#[cfg_attr(
+    feature = "openapi",
+    crate::openapi_attr(
+        tag = "System & Sync",
+        description = "Pin NFTs or whole collections so that their files are never evicted from the media cache, or unpin them."
+    )
+)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct PinNftMedia {
+    /// NFT IDs to pin or unpin
+    #[serde(default)]
+    pub nft_ids: Vec<String>,
+    /// Collection IDs to pin or unpin
+    #[serde(default)]
+    pub collection_ids: Vec<String>,
+    /// Whether to pin or unpin them
+    pub pinned: bool,
+}
+
+/// Response after pinning or unpinning NFT media
+#[cfg_attr(feature = "openapi", crate::openapi_attr(tag = "System & Sync"))]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct PinNftMediaResponse {}
+
+/// List pinned NFT media
+#[cfg_attr(
+    feature = "openapi",
+    crate::openapi_attr(
+        tag = "System & Sync",
+        description = "List the NFTs and collections whose files are pinned in the media cache."
+    )
+)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct GetMediaPins {}
+
+/// Response with pinned NFT media
+#[cfg_attr(feature = "openapi", crate::openapi_attr(tag = "System & Sync"))]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct GetMediaPinsResponse {
+    /// Pinned NFT IDs
+    pub nft_ids: Vec<String>,
+    /// Pinned collection IDs
+    pub collection_ids: Vec<String>,
+}
+
+/// Clear the media cache
+#[cfg_attr(
+    feature = "openapi",
+    crate::openapi_attr(
+        tag = "System & Sync",
+        description = "Evict every downloaded NFT file that isn't pinned. Icons, thumbnails, previews and metadata are kept, and evicted files are downloaded again when they're viewed."
+    )
+)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct ClearMediaCache {}
+
+/// Response after clearing the media cache
+#[cfg_attr(feature = "openapi", crate::openapi_attr(tag = "System & Sync"))]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct ClearMediaCacheResponse {
+    /// Number of files evicted
+    pub evicted_files: u64,
+    /// Number of bytes evicted
+    pub evicted_bytes: u64,
+}
+
 /// Get the current synchronization status
 #[cfg_attr(
     feature = "openapi",
@@ -660,6 +815,57 @@ pub struct GetTransactionsResponse {
     pub total: u32,
 }
 
+#[derive(Debug, Default, Clone, Copy, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum ExportFormat {
+    #[default]
+    Csv,
+    Json,
+}
+
+/// Export the transaction history
+#[cfg_attr(
+    feature = "openapi",
+    crate::openapi_attr(
+        tag = "Transactions",
+        description = "Export the transaction history for bookkeeping, with one row per asset movement in each block. Amounts are signed and in the asset's base units, and the fee paid by the wallet is split between the rows of the block it was paid in. The counterparty and memo are only known for payments sent by this wallet. The offer ID is set on rows that are part of the transaction which took an offer, or that move an asset which an offer made or taken by this wallet traded in the block it was filled in."
+    )
+)]
+#[derive(Debug, Default, Clone, Copy, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct ExportTransactions {
+    /// Format of the exported data
+    #[serde(default)]
+    pub format: ExportFormat,
+    /// First block height to include
+    #[serde(default)]
+    pub start_height: Option<u32>,
+    /// Last block height to include
+    #[serde(default)]
+    pub end_height: Option<u32>,
+    /// Earliest block timestamp to include, in seconds since the UNIX epoch
+    #[serde(default)]
+    pub start_timestamp: Option<u64>,
+    /// Latest block timestamp to include, in seconds since the UNIX epoch
+    #[serde(default)]
+    pub end_timestamp: Option<u64>,
+}
+
+/// Response with the exported transaction history
+#[cfg_attr(feature = "openapi", crate::openapi_attr(tag = "Transactions"))]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct ExportTransactionsResponse {
+    /// The exported rows in the requested format
+    pub data: String,
+    /// Number of rows exported
+    pub count: u32,
+}
+
 /// List NFT collections
 #[cfg_attr(
     feature = "openapi",
@@ -720,6 +926,48 @@ pub struct GetNftCollectionResponse {
     pub collection: Option<NftCollectionRecord>,
 }
 
+/// Count the traits of owned NFTs in a collection
+#[cfg_attr(
+    feature = "openapi",
+    crate::openapi_attr(
+        tag = "NFTs",
+        description = "Count how many owned NFTs in a collection have each trait, which can be used to work out their rarity."
+    )
+)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct GetNftCollectionTraits {
+    /// Collection ID
+    pub collection_id: String,
+}
+
+/// Response with trait counts for an NFT collection
+#[cfg_attr(feature = "openapi", crate::openapi_attr(tag = "NFTs"))]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct GetNftCollectionTraitsResponse {
+    /// Number of owned NFTs with each trait
+    pub traits: Vec<NftTraitCount>,
+    /// Total number of owned NFTs in the collection
+    pub total: u32,
+}
+
+/// The number of owned NFTs in a collection with a trait
+#[cfg_attr(feature = "openapi", crate::openapi_attr(tag = "NFTs"))]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct NftTraitCount {
+    /// Trait type
+    pub trait_type: String,
+    /// Value of the trait
+    pub value: String,
+    /// Number of NFTs with this trait
+    pub count: u32,
+}
+
 /// List NFTs with filtering
 #[cfg_attr(
     feature = "openapi",
@@ -759,6 +1007,32 @@ pub struct GetNfts {
     /// Include hidden NFTs
     #[cfg_attr(feature = "openapi", schema(example = false))]
     pub include_hidden: bool,
+    /// Only include NFTs which match these traits, where NFTs need one of the traits given for each trait type
+    #[serde(default)]
+    pub traits: Vec<NftTraitFilter>,
+}
+
+/// A trait which NFTs must have, either with an exact value or a numeric value within a range
+#[cfg_attr(feature = "openapi", crate::openapi_attr(tag = "NFTs"))]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct NftTraitFilter {
+    /// Trait type
+    #[cfg_attr(feature = "openapi", schema(example = "Background"))]
+    pub trait_type: String,
+    /// Exact value of the trait
+    #[serde(default)]
+    #[cfg_attr(feature = "openapi", schema(nullable = true))]
+    pub value: Option<String>,
+    /// Minimum numeric value of the trait, inclusive
+    #[serde(default)]
+    #[cfg_attr(feature = "openapi", schema(nullable = true))]
+    pub min: Option<f64>,
+    /// Maximum numeric value of the trait, inclusive
+    #[serde(default)]
+    #[cfg_attr(feature = "openapi", schema(nullable = true))]
+    pub max: Option<f64>,
 }
 
 /// Response with NFTs list
@@ -863,6 +1137,36 @@ pub struct GetNftThumbnailResponse {
     pub thumbnail: Option<String>,
 }
 
+/// Get NFT preview image
+#[cfg_attr(
+    feature = "openapi",
+    crate::openapi_attr(
+        tag = "NFTs",
+        description = "Retrieve the size-limited preview image for an NFT, which is larger than the thumbnail. Animated images are previewed as a strip of frames. Videos are only previewed by the cover art embedded in them, and otherwise by a placeholder."
+    )
+)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct GetNftPreview {
+    /// NFT coin ID
+    pub nft_id: String,
+}
+
+/// Response with NFT preview
+#[cfg_attr(feature = "openapi", crate::openapi_attr(tag = "NFTs"))]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct GetNftPreviewResponse {
+    /// Base64-encoded PNG preview image
+    #[cfg_attr(feature = "openapi", schema(nullable = true))]
+    pub preview: Option<String>,
+    /// How the preview was made from the NFT data
+    #[cfg_attr(feature = "openapi", schema(nullable = true))]
+    pub source: Option<NftPreviewSource>,
+}
+
 /// Get NFT data file
 #[cfg_attr(
     feature = "openapi",
@@ -0,0 +1,234 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{Amount, DidMetadataEntry, SpendBundleJson, TransactionResponse, TransactionSummary};
+
+/// Update the metadata of a DID
+#[cfg_attr(
+    feature = "openapi",
+    crate::openapi_attr(
+        tag = "DIDs",
+        description = "Replace the on-chain metadata of a DID with a list of key value pairs.",
+        response_type = "TransactionResponse"
+    )
+)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct UpdateDidMetadata {
+    /// DID ID to update
+    pub did_id: String,
+    /// New metadata, replacing the existing metadata
+    pub metadata: Vec<DidMetadataEntry>,
+    /// Transaction fee
+    pub fee: Amount,
+    /// Whether to automatically submit the transaction
+    #[serde(default)]
+    #[cfg_attr(feature = "openapi", schema(default = false))]
+    pub auto_submit: bool,
+}
+
+/// Set the recovery list of a DID
+#[cfg_attr(
+    feature = "openapi",
+    crate::openapi_attr(
+        tag = "DIDs",
+        description = "Set the DIDs which can recover a DID, and how many of them are required. Between one and all of them must be required. An empty list disables recovery.",
+        response_type = "TransactionResponse"
+    )
+)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct SetDidRecoveryList {
+    /// DID ID to update
+    pub did_id: String,
+    /// DID IDs which can recover the DID
+    pub recovery_did_ids: Vec<String>,
+    /// Number of recovery DIDs which must attest to a recovery
+    #[cfg_attr(feature = "openapi", schema(example = 1))]
+    pub num_verifications_required: u64,
+    /// Transaction fee
+    pub fee: Amount,
+    /// Whether to automatically submit the transaction
+    #[serde(default)]
+    #[cfg_attr(feature = "openapi", schema(default = false))]
+    pub auto_submit: bool,
+}
+
+/// Sign a message with a DID
+#[cfg_attr(
+    feature = "openapi",
+    crate::openapi_attr(
+        tag = "DIDs",
+        description = "Sign a message with the key which owns a DID."
+    )
+)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct SignMessageByDid {
+    /// DID ID whose key to use
+    pub did_id: String,
+    /// Message to sign
+    pub message: String,
+}
+
+/// Response with signed message
+#[cfg_attr(feature = "openapi", crate::openapi_attr(tag = "DIDs"))]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct SignMessageByDidResponse {
+    /// Public key used
+    pub public_key: String,
+    /// Signature
+    pub signature: String,
+}
+
+/// Verify a message signed with a DID
+#[cfg_attr(
+    feature = "openapi",
+    crate::openapi_attr(
+        tag = "DIDs",
+        description = "Verify that a message was signed by the key which currently owns a DID. DIDs which aren't in the wallet are looked up on-chain."
+    )
+)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct VerifyDidSignature {
+    /// DID ID which signed the message
+    pub did_id: String,
+    /// Message which was signed
+    pub message: String,
+    /// Public key which signed the message
+    pub public_key: String,
+    /// Signature
+    pub signature: String,
+}
+
+/// Response with the verification result
+#[cfg_attr(feature = "openapi", crate::openapi_attr(tag = "DIDs"))]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct VerifyDidSignatureResponse {
+    /// Whether the signature is valid and the public key owns the DID
+    pub is_valid: bool,
+}
+
+/// Get the information needed to recover a DID
+#[cfg_attr(
+    feature = "openapi",
+    crate::openapi_attr(
+        tag = "DIDs",
+        description = "Look up the latest coin and recovery list hash of a DID on-chain, and the inner puzzle hash it would have once recovered to a public key. Recovery DIDs attest to these."
+    )
+)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct GetDidRecoveryInfo {
+    /// DID ID to recover
+    pub did_id: String,
+    /// Public key to recover the DID to, defaults to one of the wallet's keys
+    #[serde(default)]
+    pub public_key: Option<String>,
+}
+
+/// Response with the information needed to recover a DID
+#[cfg_attr(feature = "openapi", crate::openapi_attr(tag = "DIDs"))]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct GetDidRecoveryInfoResponse {
+    /// Coin ID of the latest DID coin
+    pub coin_id: String,
+    /// Inner puzzle hash of the DID once recovered
+    pub new_inner_puzzle_hash: String,
+    /// Public key the DID will be recovered to
+    pub public_key: String,
+    /// Hash of the recovery list, if there is one
+    pub recovery_list_hash: Option<String>,
+    /// Recovery DIDs, if the recovery list was set by this wallet
+    pub recovery_did_ids: Option<Vec<String>>,
+    /// Number of recovery DIDs which must attest to a recovery
+    pub num_verifications_required: u64,
+}
+
+/// Attest to the recovery of a DID
+#[cfg_attr(
+    feature = "openapi",
+    crate::openapi_attr(
+        tag = "DIDs",
+        description = "Create a signed attestation from one of the wallet's DIDs, which approves the recovery of another DID's coin to a new public key. The attestation is given to whoever is recovering the DID."
+    )
+)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct CreateDidAttestation {
+    /// DID ID of the wallet which attests to the recovery
+    pub did_id: String,
+    /// Coin ID of the DID being recovered
+    pub coin_id: String,
+    /// Inner puzzle hash of the DID once recovered
+    pub new_inner_puzzle_hash: String,
+    /// Public key the DID will be recovered to
+    pub public_key: String,
+}
+
+/// Response with a signed attestation
+#[cfg_attr(feature = "openapi", crate::openapi_attr(tag = "DIDs"))]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct CreateDidAttestationResponse {
+    /// Hex encoded spend bundle of the attestation
+    pub attestation: String,
+}
+
+/// Recover a DID with attestations
+#[cfg_attr(
+    feature = "openapi",
+    crate::openapi_attr(
+        tag = "DIDs",
+        description = "Recover a DID to a public key owned by the wallet, with an attestation from every DID in its recovery list."
+    )
+)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct RecoverDid {
+    /// DID ID to recover
+    pub did_id: String,
+    /// Recovery DIDs, in the order they were set
+    pub recovery_did_ids: Vec<String>,
+    /// Hex encoded attestations from the recovery DIDs
+    pub attestations: Vec<String>,
+    /// Public key the attestations approve, which must belong to the wallet
+    pub public_key: String,
+    /// Transaction fee
+    pub fee: Amount,
+    /// Whether to automatically submit the transaction
+    #[serde(default)]
+    #[cfg_attr(feature = "openapi", schema(default = false))]
+    pub auto_submit: bool,
+}
+
+/// Response with the recovery transaction
+#[cfg_attr(feature = "openapi", crate::openapi_attr(tag = "DIDs"))]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct RecoverDidResponse {
+    /// Transaction summary
+    pub summary: TransactionSummary,
+    /// Spend bundle, including the attestations
+    pub spend_bundle: SpendBundleJson,
+    /// Transaction ID
+    pub transaction_id: String,
+}
+
+pub type UpdateDidMetadataResponse = TransactionResponse;
+pub type SetDidRecoveryListResponse = TransactionResponse;
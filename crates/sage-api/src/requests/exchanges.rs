@@ -0,0 +1,109 @@
+use serde::{Deserialize, Serialize};
+
+/// Publish an offer to an exchange
+#[cfg_attr(
+    feature = "openapi",
+    crate::openapi_attr(
+        tag = "Offers",
+        description = "Publish one of the wallet's offers to a configured exchange, and record the code it was given."
+    )
+)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct PublishOffer {
+    /// Offer ID
+    pub offer_id: String,
+    /// Name of the exchange to publish to
+    pub exchange: String,
+}
+
+/// Response with the code of a published offer
+#[cfg_attr(feature = "openapi", crate::openapi_attr(tag = "Offers"))]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct PublishOfferResponse {
+    /// Code the offer can be fetched by from the exchange
+    pub code: String,
+}
+
+/// Fetch an offer from an exchange
+#[cfg_attr(
+    feature = "openapi",
+    crate::openapi_attr(
+        tag = "Offers",
+        description = "Fetch an offer from a configured exchange by the code it was published under."
+    )
+)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct FetchOfferByCode {
+    /// Code of the offer
+    pub code: String,
+    /// Name of the exchange, which defaults to the first offer code service
+    #[serde(default)]
+    #[cfg_attr(feature = "openapi", schema(nullable = true))]
+    pub exchange: Option<String>,
+}
+
+/// Response with the fetched offer
+#[cfg_attr(feature = "openapi", crate::openapi_attr(tag = "Offers"))]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct FetchOfferByCodeResponse {
+    /// Offer string
+    pub offer: String,
+}
+
+/// Search the offers listed on an exchange
+#[cfg_attr(
+    feature = "openapi",
+    crate::openapi_attr(
+        tag = "Offers",
+        description = "Search the open offers listed on a configured exchange for a pair of assets. Offer code services can't be searched."
+    )
+)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct SearchExchangeOffers {
+    /// Name of the exchange to search
+    pub exchange: String,
+    /// Offered asset, as identified by the exchange (such as an asset ID or "xch")
+    #[serde(default)]
+    #[cfg_attr(feature = "openapi", schema(nullable = true))]
+    pub offered: Option<String>,
+    /// Requested asset, as identified by the exchange (such as an asset ID or "xch")
+    #[serde(default)]
+    #[cfg_attr(feature = "openapi", schema(nullable = true))]
+    pub requested: Option<String>,
+    /// Page of results, starting from 1
+    pub page: u32,
+    /// Number of offers per page
+    pub page_size: u32,
+}
+
+/// Response with the offers found on an exchange
+#[cfg_attr(feature = "openapi", crate::openapi_attr(tag = "Offers"))]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct SearchExchangeOffersResponse {
+    /// Offers in the order they were listed
+    pub offers: Vec<ExchangeOfferRecord>,
+}
+
+/// An offer listed on an exchange
+#[cfg_attr(feature = "openapi", crate::openapi_attr(tag = "Offers"))]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct ExchangeOfferRecord {
+    /// Code the offer can be fetched by from the exchange
+    pub code: String,
+    /// Offer string
+    pub offer: String,
+}
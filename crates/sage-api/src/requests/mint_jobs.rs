@@ -0,0 +1,165 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{Amount, MintJobItemRecord, MintJobRecord};
+
+#[derive(Debug, Default, Clone, Copy, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum MintJobFormat {
+    #[default]
+    Csv,
+    Json,
+}
+
+/// List mint jobs
+#[cfg_attr(
+    feature = "openapi",
+    crate::openapi_attr(
+        tag = "Mint Jobs",
+        description = "List all mint jobs along with how many of their NFTs have been submitted and confirmed."
+    )
+)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct GetMintJobs {}
+
+/// Response with mint jobs
+#[cfg_attr(feature = "openapi", crate::openapi_attr(tag = "Mint Jobs"))]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct GetMintJobsResponse {
+    /// List of mint jobs
+    pub mint_jobs: Vec<MintJobRecord>,
+}
+
+/// Get a mint job
+#[cfg_attr(
+    feature = "openapi",
+    crate::openapi_attr(
+        tag = "Mint Jobs",
+        description = "Get a mint job along with the status and NFT ID of each of its items."
+    )
+)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct GetMintJob {
+    /// Mint job ID
+    #[cfg_attr(feature = "openapi", schema(example = 1))]
+    pub id: u32,
+}
+
+/// Response with a mint job and its items
+#[cfg_attr(feature = "openapi", crate::openapi_attr(tag = "Mint Jobs"))]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct GetMintJobResponse {
+    /// The mint job
+    pub mint_job: MintJobRecord,
+    /// Items in the order they were provided
+    pub items: Vec<MintJobItemRecord>,
+}
+
+/// Create a mint job
+#[cfg_attr(
+    feature = "openapi",
+    crate::openapi_attr(
+        tag = "Mint Jobs",
+        description = "Create a mint job from a manifest of NFTs, which are minted from the DID in transactions that stay below the maximum cost and are submitted one after another while the wallet is synced, if background signing is enabled. Each transaction is checked against the spending policy. The manifest has the columns address, edition_number, edition_total, data_uris, data_hash, metadata_uris, metadata_hash, license_uris, license_hash, royalty_address and royalty_ten_thousandths, all of which are optional. URIs are separated by whitespace. Every URI is fetched up front, and missing hashes are calculated from the content. CSV data must start with a header row, and JSON data is an array of objects with the same fields, where URIs can also be given as arrays."
+    )
+)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct CreateMintJob {
+    /// Display name for the mint job
+    #[cfg_attr(feature = "openapi", schema(example = "Collection drop"))]
+    pub name: String,
+    /// DID ID to mint the NFTs from
+    pub did_id: String,
+    /// Format of the manifest
+    #[serde(default)]
+    pub format: MintJobFormat,
+    /// The manifest data
+    pub data: String,
+    /// Fee paid on each transaction
+    pub fee: Amount,
+    /// Maximum cost of each transaction, defaults to the maximum cost allowed by the mempool
+    #[serde(default)]
+    pub max_cost: Option<u64>,
+    /// Whether to check the hashes in the manifest against the content of the URIs
+    #[serde(default = "yes")]
+    #[cfg_attr(feature = "openapi", schema(default = true))]
+    pub verify_hashes: bool,
+}
+
+fn yes() -> bool {
+    true
+}
+
+/// Response with the created mint job
+#[cfg_attr(feature = "openapi", crate::openapi_attr(tag = "Mint Jobs"))]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct CreateMintJobResponse {
+    /// The created mint job
+    pub mint_job: MintJobRecord,
+}
+
+/// Pause or resume a mint job
+#[cfg_attr(
+    feature = "openapi",
+    crate::openapi_attr(
+        tag = "Mint Jobs",
+        description = "Pause or resume a mint job. Mint jobs are paused automatically if a transaction fails or would break the spending policy, and resuming one clears the error and retries the NFTs that weren't confirmed."
+    )
+)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct PauseMintJob {
+    /// Mint job ID
+    #[cfg_attr(feature = "openapi", schema(example = 1))]
+    pub id: u32,
+    /// Whether the mint job should be paused
+    pub paused: bool,
+}
+
+/// Response with the updated mint job
+#[cfg_attr(feature = "openapi", crate::openapi_attr(tag = "Mint Jobs"))]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct PauseMintJobResponse {
+    /// The updated mint job
+    pub mint_job: MintJobRecord,
+}
+
+/// Delete a mint job
+#[cfg_attr(
+    feature = "openapi",
+    crate::openapi_attr(
+        tag = "Mint Jobs",
+        description = "Delete a mint job and its history. NFTs that were already minted are not affected."
+    )
+)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct DeleteMintJob {
+    /// Mint job ID
+    #[cfg_attr(feature = "openapi", schema(example = 1))]
+    pub id: u32,
+}
+
+/// Response for deleting a mint job
+#[cfg_attr(feature = "openapi", crate::openapi_attr(tag = "Mint Jobs"))]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct DeleteMintJobResponse {}
@@ -0,0 +1,103 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{NftIntegrityRecord, NftIntegritySummary};
+
+/// Get the integrity of an NFT's files
+#[cfg_attr(
+    feature = "openapi",
+    crate::openapi_attr(
+        tag = "NFTs",
+        description = "Check whether each of the data, metadata and license URIs of an NFT serves content that matches its on-chain hash."
+    )
+)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct GetNftIntegrity {
+    /// NFT ID
+    #[cfg_attr(feature = "openapi", schema(example = "nft1..."))]
+    pub nft_id: String,
+}
+
+/// Response with the integrity of an NFT's files
+#[cfg_attr(feature = "openapi", crate::openapi_attr(tag = "NFTs"))]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct GetNftIntegrityResponse {
+    /// Integrity of the NFT
+    pub integrity: NftIntegrityRecord,
+}
+
+/// Get an integrity report for owned NFTs
+#[cfg_attr(
+    feature = "openapi",
+    crate::openapi_attr(
+        tag = "NFTs",
+        description = "List owned NFTs whose content is missing or doesn't match its on-chain hash, either in a collection or across the whole wallet."
+    )
+)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct GetNftIntegrityReport {
+    /// Only include NFTs in this collection
+    #[serde(default)]
+    #[cfg_attr(feature = "openapi", schema(nullable = true))]
+    pub collection_id: Option<String>,
+    /// Whether to include NFTs that have been fully verified
+    #[serde(default)]
+    #[cfg_attr(feature = "openapi", schema(default = false))]
+    pub include_verified: bool,
+    /// Starting offset for pagination
+    #[cfg_attr(feature = "openapi", schema(example = 0))]
+    pub offset: u32,
+    /// Number of NFTs to return
+    #[cfg_attr(feature = "openapi", schema(example = 50))]
+    pub limit: u32,
+}
+
+/// Response with an integrity report for owned NFTs
+#[cfg_attr(feature = "openapi", crate::openapi_attr(tag = "NFTs"))]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct GetNftIntegrityReportResponse {
+    /// NFTs in the report
+    pub nfts: Vec<NftIntegrityRecord>,
+    /// Total number of NFTs in the report
+    pub total: u32,
+    /// Number of owned NFTs with each status, including verified ones
+    pub summary: NftIntegritySummary,
+}
+
+/// Check the failing URIs of NFTs again
+#[cfg_attr(
+    feature = "openapi",
+    crate::openapi_attr(
+        tag = "NFTs",
+        description = "Queue the URIs of NFTs that failed to load or didn't match their hash to be checked again. URIs that have already been verified are left alone."
+    )
+)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct ReverifyNfts {
+    /// NFT IDs to check again, or every owned NFT if empty
+    #[serde(default)]
+    pub nft_ids: Vec<String>,
+    /// Only check owned NFTs in this collection, if no NFT IDs are provided
+    #[serde(default)]
+    #[cfg_attr(feature = "openapi", schema(nullable = true))]
+    pub collection_id: Option<String>,
+}
+
+/// Response after queueing URIs to be checked again
+#[cfg_attr(feature = "openapi", crate::openapi_attr(tag = "NFTs"))]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct ReverifyNftsResponse {
+    /// Number of URIs that will be checked again
+    pub uris: u32,
+}
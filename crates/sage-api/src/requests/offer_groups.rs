@@ -0,0 +1,228 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{Amount, OfferGroupOfferRecord, OfferGroupRecord};
+
+use super::TransactionResponse;
+
+/// How the total size of an offer ladder is spread over its rungs
+#[derive(Debug, Default, Clone, Copy, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum LadderDistribution {
+    #[default]
+    Uniform,
+    Increasing,
+    Decreasing,
+}
+
+/// Create an offer ladder
+#[cfg_attr(
+    feature = "openapi",
+    crate::openapi_attr(
+        tag = "Offers",
+        description = "Create a group of offers for the same asset pair at stepped prices. Prices are the amount of the requested asset per unit of the offered asset, and either a step or a count of rungs must be provided. The offered asset is split into a separate coin for each rung in a single transaction which pays the fee, so every offer can be taken independently. All of the offers are imported together."
+    )
+)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct MakeOfferLadder {
+    /// Asset ID being offered (null for XCH)
+    #[serde(default)]
+    #[cfg_attr(feature = "openapi", schema(nullable = true))]
+    pub offered_asset_id: Option<String>,
+    /// Asset ID being requested (null for XCH)
+    #[serde(default)]
+    #[cfg_attr(feature = "openapi", schema(nullable = true))]
+    pub requested_asset_id: Option<String>,
+    /// Price of the first rung
+    #[cfg_attr(feature = "openapi", schema(example = "0.01"))]
+    pub min_price: String,
+    /// Price of the last rung
+    #[cfg_attr(feature = "openapi", schema(example = "0.02"))]
+    pub max_price: String,
+    /// Price difference between rungs
+    #[serde(default)]
+    #[cfg_attr(feature = "openapi", schema(nullable = true))]
+    pub step: Option<String>,
+    /// Number of rungs, spread evenly over the price range
+    #[serde(default)]
+    #[cfg_attr(feature = "openapi", schema(nullable = true))]
+    pub count: Option<u32>,
+    /// Total amount of the offered asset
+    pub total_amount: Amount,
+    /// How the total amount is spread over the rungs
+    #[serde(default)]
+    pub distribution: LadderDistribution,
+    /// Fee for the transaction which splits the offered asset
+    pub fee: Amount,
+    /// Optional receive address
+    #[serde(default)]
+    #[cfg_attr(feature = "openapi", schema(nullable = true))]
+    pub receive_address: Option<String>,
+    /// Optional expiration timestamp
+    #[serde(default)]
+    #[cfg_attr(feature = "openapi", schema(nullable = true))]
+    pub expires_at_second: Option<u64>,
+}
+
+/// Response with the created offer group
+#[cfg_attr(feature = "openapi", crate::openapi_attr(tag = "Offers"))]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct MakeOfferLadderResponse {
+    /// Offer group ID
+    pub group_id: u32,
+    /// IDs of the offers, from the lowest price to the highest
+    pub offer_ids: Vec<String>,
+}
+
+/// List offer groups
+#[cfg_attr(
+    feature = "openapi",
+    crate::openapi_attr(
+        tag = "Offers",
+        description = "List all offer groups along with how many of their offers are still active."
+    )
+)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct GetOfferGroups {}
+
+/// Response with offer groups
+#[cfg_attr(feature = "openapi", crate::openapi_attr(tag = "Offers"))]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct GetOfferGroupsResponse {
+    /// List of offer groups
+    pub groups: Vec<OfferGroupRecord>,
+}
+
+/// Get an offer group
+#[cfg_attr(
+    feature = "openapi",
+    crate::openapi_attr(
+        tag = "Offers",
+        description = "Get an offer group along with each of its offers and the price it was made at."
+    )
+)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct GetOfferGroup {
+    /// Offer group ID
+    #[cfg_attr(feature = "openapi", schema(example = 1))]
+    pub id: u32,
+}
+
+/// Response with an offer group and its offers
+#[cfg_attr(feature = "openapi", crate::openapi_attr(tag = "Offers"))]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct GetOfferGroupResponse {
+    /// The offer group
+    pub group: OfferGroupRecord,
+    /// Offers in the order they were created
+    pub offers: Vec<OfferGroupOfferRecord>,
+}
+
+/// Cancel an offer group
+#[cfg_attr(
+    feature = "openapi",
+    crate::openapi_attr(
+        tag = "Offers",
+        description = "Cancel all of the active offers in a group in a single transaction.",
+        response_type = "TransactionResponse"
+    )
+)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct CancelOfferGroup {
+    /// Offer group ID
+    #[cfg_attr(feature = "openapi", schema(example = 1))]
+    pub id: u32,
+    /// Transaction fee
+    pub fee: Amount,
+    /// Whether to automatically submit the transaction
+    #[serde(default)]
+    #[cfg_attr(feature = "openapi", schema(default = false))]
+    pub auto_submit: bool,
+}
+
+pub type CancelOfferGroupResponse = TransactionResponse;
+
+/// Re-price an offer group
+#[cfg_attr(
+    feature = "openapi",
+    crate::openapi_attr(
+        tag = "Offers",
+        description = "Replace the active offers in a group with new ones spread evenly over a new price range. The rungs keep their sizes, and the coins of the old offers are spent to create the new ones, which cancels the old offers in the same transaction.",
+        response_type = "MakeOfferLadderResponse"
+    )
+)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct RepriceOfferGroup {
+    /// Offer group ID
+    #[cfg_attr(feature = "openapi", schema(example = 1))]
+    pub id: u32,
+    /// Price of the first rung
+    #[cfg_attr(feature = "openapi", schema(example = "0.01"))]
+    pub min_price: String,
+    /// Price of the last rung
+    #[cfg_attr(feature = "openapi", schema(example = "0.02"))]
+    pub max_price: String,
+    /// Transaction fee
+    pub fee: Amount,
+    /// Optional expiration timestamp
+    #[serde(default)]
+    #[cfg_attr(feature = "openapi", schema(nullable = true))]
+    pub expires_at_second: Option<u64>,
+}
+
+pub type RepriceOfferGroupResponse = MakeOfferLadderResponse;
+
+/// Set the repost policy of an offer group
+#[cfg_attr(
+    feature = "openapi",
+    crate::openapi_attr(
+        tag = "Offers",
+        description = "Set whether offers in a group are replaced once they are taken, and the limits for doing so. A replacement offers the proceeds back the other way, priced the spread below the taken offer if it sold the group's offered asset, or the spread above it if it bought it back. Reposting stops once a replacement would be priced below the stop price, or would take the offered asset held by the group's active offers over the max inventory. A replacement only spends the coin which the taken offer paid its proceeds to, so no other coins are locked up by reposting. Replacements are signed in the background if background signing is enabled, and reposting also stops if one would break the spending policy. Only offers taken after the policy is set are replaced."
+    )
+)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct SetOfferGroupPolicy {
+    /// Offer group ID
+    #[cfg_attr(feature = "openapi", schema(example = 1))]
+    pub id: u32,
+    /// Whether to replace offers once they are taken
+    pub auto_repost: bool,
+    /// Price difference between a taken offer and its replacement
+    #[serde(default)]
+    #[cfg_attr(feature = "openapi", schema(nullable = true, example = "0.001"))]
+    pub price_spread: Option<String>,
+    /// Lowest price a replacement can be made at
+    #[serde(default)]
+    #[cfg_attr(feature = "openapi", schema(nullable = true))]
+    pub stop_price: Option<String>,
+    /// Most of the offered asset that the group's active offers can hold
+    #[serde(default)]
+    #[cfg_attr(feature = "openapi", schema(nullable = true))]
+    pub max_inventory: Option<Amount>,
+}
+
+/// Response for setting the repost policy of an offer group
+#[cfg_attr(feature = "openapi", crate::openapi_attr(tag = "Offers"))]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct SetOfferGroupPolicyResponse {}
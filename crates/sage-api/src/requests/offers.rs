@@ -1,7 +1,8 @@
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    Amount, OfferRecord, OfferRecordStatus, OfferSummary, SpendBundleJson, TransactionSummary,
+    Amount, OfferCoinState, OfferRecord, OfferRecordStatus, OfferSummary, SpendBundleJson,
+    TransactionSummary,
 };
 
 use super::TransactionResponse;
@@ -32,6 +33,18 @@ pub struct MakeOffer {
     #[serde(default)]
     #[cfg_attr(feature = "openapi", schema(nullable = true))]
     pub expires_at_second: Option<u64>,
+    /// Optional block height at which the offer expires
+    #[serde(default)]
+    #[cfg_attr(feature = "openapi", schema(nullable = true))]
+    pub expires_at_height: Option<u32>,
+    /// Optional number of blocks from the current peak after which the offer expires
+    #[serde(default)]
+    #[cfg_attr(feature = "openapi", schema(nullable = true))]
+    pub expires_in_blocks: Option<u32>,
+    /// Optional number of seconds from now after which the offer expires
+    #[serde(default)]
+    #[cfg_attr(feature = "openapi", schema(nullable = true))]
+    pub expires_in_seconds: Option<u64>,
     /// Whether to automatically import the offer
     #[serde(default = "yes")]
     #[cfg_attr(feature = "openapi", schema(default = true))]
@@ -68,6 +81,143 @@ pub struct MakeOfferResponse {
     pub offer_id: String,
 }
 
+/// Preview the royalties for an offer
+#[cfg_attr(
+    feature = "openapi",
+    crate::openapi_attr(
+        tag = "Offers",
+        description = "Calculate the royalties which would be paid for each NFT in an offer before it's created. The maker pays royalties for requested NFTs out of the offered assets, and the taker pays royalties for offered NFTs out of the requested assets, split evenly between the NFTs which have royalties."
+    )
+)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct PreviewOfferRoyalties {
+    /// Assets requested in the offer
+    pub requested_assets: Vec<OfferAmount>,
+    /// Assets offered in exchange
+    pub offered_assets: Vec<OfferAmount>,
+}
+
+/// Response with the royalty breakdown of an offer
+#[cfg_attr(feature = "openapi", crate::openapi_attr(tag = "Offers"))]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct PreviewOfferRoyaltiesResponse {
+    /// Royalty owed for each NFT in each asset it's traded for
+    pub royalties: Vec<NftRoyaltyPayment>,
+    /// Trade prices revealed by each offered NFT which has royalties
+    pub trade_prices: Vec<OfferTradePrice>,
+}
+
+/// Royalty paid to the creator of an NFT in one asset
+#[cfg_attr(feature = "openapi", crate::openapi_attr(tag = "Offers"))]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct NftRoyaltyPayment {
+    /// NFT ID
+    pub nft_id: String,
+    /// Address the royalty is paid to
+    pub royalty_address: String,
+    /// Royalty percentage in basis points
+    pub royalty_basis_points: u16,
+    /// Asset ID the royalty is paid in (null for XCH)
+    #[cfg_attr(feature = "openapi", schema(nullable = true))]
+    pub asset_id: Option<String>,
+    /// Share of the asset the NFT is traded for
+    pub trade_price: Amount,
+    /// Royalty amount
+    pub amount: Amount,
+    /// Whether the NFT is requested, in which case the maker pays the royalty
+    pub is_requested: bool,
+}
+
+/// Trade price revealed when an NFT is transferred
+#[cfg_attr(feature = "openapi", crate::openapi_attr(tag = "Offers"))]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct OfferTradePrice {
+    /// Asset ID (null for XCH)
+    #[cfg_attr(feature = "openapi", schema(nullable = true))]
+    pub asset_id: Option<String>,
+    /// Amount of the asset
+    pub amount: Amount,
+    /// Settlement puzzle hash of the asset
+    pub puzzle_hash: String,
+}
+
+/// Create a separate offer for each of several NFTs
+#[cfg_attr(
+    feature = "openapi",
+    crate::openapi_attr(
+        tag = "Offers",
+        description = "Create one offer per NFT, each requesting the same assets. The NFTs are either listed by ID or taken from a collection, in which case NFTs that are already locked in an offer are skipped. Every offer is imported as it's made, so that each pays its fee from different coins. If one of the offers can't be made, the ones made before it are kept."
+    )
+)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct MakeNftOffers {
+    /// NFT IDs to offer
+    #[serde(default)]
+    pub nft_ids: Vec<String>,
+    /// Collection ID to offer every owned NFT from
+    #[serde(default)]
+    #[cfg_attr(feature = "openapi", schema(nullable = true))]
+    pub collection_id: Option<String>,
+    /// Assets requested for each NFT
+    pub requested_assets: Vec<OfferAmount>,
+    /// Transaction fee for each offer
+    pub fee: Amount,
+    /// Optional receive address
+    #[serde(default)]
+    #[cfg_attr(feature = "openapi", schema(nullable = true))]
+    pub receive_address: Option<String>,
+    /// Optional expiration timestamp
+    #[serde(default)]
+    #[cfg_attr(feature = "openapi", schema(nullable = true))]
+    pub expires_at_second: Option<u64>,
+    /// Optional block height at which the offers expire
+    #[serde(default)]
+    #[cfg_attr(feature = "openapi", schema(nullable = true))]
+    pub expires_at_height: Option<u32>,
+    /// Optional number of blocks from the current peak after which the offers expire
+    #[serde(default)]
+    #[cfg_attr(feature = "openapi", schema(nullable = true))]
+    pub expires_in_blocks: Option<u32>,
+    /// Optional number of seconds from now after which the offers expire
+    #[serde(default)]
+    #[cfg_attr(feature = "openapi", schema(nullable = true))]
+    pub expires_in_seconds: Option<u64>,
+}
+
+/// Response with the created offers
+#[cfg_attr(feature = "openapi", crate::openapi_attr(tag = "Offers"))]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct MakeNftOffersResponse {
+    /// Created offers, in the same order as the NFTs
+    pub offers: Vec<NftOffer>,
+}
+
+/// Offer created for a single NFT
+#[cfg_attr(feature = "openapi", crate::openapi_attr(tag = "Offers"))]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct NftOffer {
+    /// NFT ID
+    pub nft_id: String,
+    /// Offer string (bech32 encoded)
+    pub offer: String,
+    /// Offer ID
+    pub offer_id: String,
+}
+
 /// Accept an offer
 #[cfg_attr(
     feature = "openapi",
@@ -104,6 +254,42 @@ pub struct TakeOfferResponse {
     pub transaction_id: String,
 }
 
+/// Accept multiple offers at once
+#[cfg_attr(
+    feature = "openapi",
+    crate::openapi_attr(
+        tag = "Offers",
+        description = "Accept several offers in a single transaction, paying the fee only once. Fails if the offers conflict with each other or any of their coins have already been spent."
+    )
+)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct TakeOffers {
+    /// Offer strings to accept
+    pub offers: Vec<String>,
+    /// Transaction fee
+    pub fee: Amount,
+    /// Whether to automatically submit the transaction
+    #[serde(default)]
+    #[cfg_attr(feature = "openapi", schema(default = false))]
+    pub auto_submit: bool,
+}
+
+/// Response with accepted offers details
+#[cfg_attr(feature = "openapi", crate::openapi_attr(tag = "Offers"))]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct TakeOffersResponse {
+    /// Summary of the combined transaction
+    pub summary: TransactionSummary,
+    /// Spend bundle
+    pub spend_bundle: SpendBundleJson,
+    /// Transaction ID
+    pub transaction_id: String,
+}
+
 /// Combine multiple offers
 #[cfg_attr(
     feature = "openapi",
@@ -158,6 +344,48 @@ pub struct ViewOfferResponse {
     pub status: OfferRecordStatus,
 }
 
+/// Check whether an offer can be taken
+#[cfg_attr(
+    feature = "openapi",
+    crate::openapi_attr(
+        tag = "Offers",
+        description = "Check the offered coins on chain, the expiration, and a dry run of the taker's spend before accepting an offer."
+    )
+)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct PreflightOffer {
+    /// Offer string to check
+    pub offer: String,
+    /// Transaction fee the offer would be taken with
+    pub fee: Amount,
+}
+
+/// Response with the preflight verdict for an offer
+#[cfg_attr(feature = "openapi", crate::openapi_attr(tag = "Offers"))]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct PreflightOfferResponse {
+    /// Whether the offer can currently be taken
+    pub takeable: bool,
+    /// The state of each of the offered coins on chain
+    pub coins: Vec<OfferCoinState>,
+    /// Peak height of the peer the coins were fetched from
+    pub peak_height: Option<u32>,
+    /// Whether the offer has expired
+    pub expired: bool,
+    /// Block height at which the offer expires
+    pub expiration_height: Option<u32>,
+    /// Timestamp at which the offer expires
+    pub expiration_timestamp: Option<u64>,
+    /// Cost of the taker's spend, if it could be built and run
+    pub cost: Option<u64>,
+    /// Why the taker's spend couldn't be built or run
+    pub error: Option<String>,
+}
+
 /// Import an offer
 #[cfg_attr(
     feature = "openapi",
@@ -184,6 +412,97 @@ pub struct ImportOfferResponse {
     pub offer_id: String,
 }
 
+/// Import several offers at once
+#[cfg_attr(
+    feature = "openapi",
+    crate::openapi_attr(
+        tag = "Offers",
+        description = "Import a batch of offers, such as the contents of several offer files. Each offer is imported separately, so one that is invalid doesn't prevent the rest from being imported. Offers can optionally be verified against the current state of their coins on chain."
+    )
+)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct ImportOffers {
+    /// Offer strings to import
+    pub offers: Vec<String>,
+    /// Whether to check that the offered coins are unspent and the offers haven't expired
+    #[serde(default)]
+    #[cfg_attr(feature = "openapi", schema(default = false))]
+    pub verify: bool,
+}
+
+/// Response with the result of importing each offer
+#[cfg_attr(feature = "openapi", crate::openapi_attr(tag = "Offers"))]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct ImportOffersResponse {
+    /// Result for each offer, in the same order they were provided
+    pub results: Vec<ImportOfferResult>,
+}
+
+/// Import offers from a URL
+#[cfg_attr(
+    feature = "openapi",
+    crate::openapi_attr(
+        tag = "Offers",
+        description = "Download a single offer or a newline separated list of offers from an HTTP(S), IPFS, IPNS or Arweave URL and import each of them. Content-addressed URLs are fetched through the configured gateways."
+    )
+)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct ImportOffersFromUrl {
+    /// HTTP(S), IPFS, IPNS or Arweave URL to download the offers from
+    pub url: String,
+    /// Whether to check that the offered coins are unspent and the offers haven't expired
+    #[serde(default)]
+    #[cfg_attr(feature = "openapi", schema(default = false))]
+    pub verify: bool,
+}
+
+/// Response with the result of importing each downloaded offer
+#[cfg_attr(feature = "openapi", crate::openapi_attr(tag = "Offers"))]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct ImportOffersFromUrlResponse {
+    /// Result for each offer, in the order they were listed
+    pub results: Vec<ImportOfferResult>,
+}
+
+/// Result of importing a single offer
+#[cfg_attr(feature = "openapi", crate::openapi_attr(tag = "Offers"))]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct ImportOfferResult {
+    /// ID of the offer, if it could be decoded
+    #[cfg_attr(feature = "openapi", schema(nullable = true))]
+    pub offer_id: Option<String>,
+    /// Whether the offer was imported
+    pub status: ImportOfferStatus,
+    /// Why the offer is invalid or couldn't be verified
+    #[cfg_attr(feature = "openapi", schema(nullable = true))]
+    pub error: Option<String>,
+    /// Whether the offered coins are unspent and the offer hasn't expired, if it was verified.
+    /// This is left empty if its coins couldn't be fetched.
+    #[cfg_attr(feature = "openapi", schema(nullable = true))]
+    pub live: Option<bool>,
+}
+
+/// Outcome of importing an offer
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum ImportOfferStatus {
+    Imported,
+    Duplicate,
+    Invalid,
+}
+
 /// List all offers
 #[cfg_attr(
     feature = "openapi",
@@ -282,6 +601,31 @@ pub struct DeleteOffer {
 #[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct DeleteOfferResponse {}
 
+/// Set whether an offer is made again if it becomes invalid
+#[cfg_attr(
+    feature = "openapi",
+    crate::openapi_attr(
+        tag = "Offers",
+        description = "Set whether an offer is made again from other coins if it becomes invalid, because a coin locked in it was spent by another transaction. Only confirmed spends are detected, since the wallet doesn't watch the mempool, and spends by the wallet's own pending transactions are treated as cancellations rather than conflicts. The replacement is signed in the background if background signing is enabled, and isn't made if it would break the spending policy."
+    )
+)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct SetOfferAutoRecreate {
+    /// Offer ID to update
+    pub offer_id: String,
+    /// Whether to make the offer again if it becomes invalid
+    pub auto_recreate: bool,
+}
+
+/// Response for setting whether an offer is made again
+#[cfg_attr(feature = "openapi", crate::openapi_attr(tag = "Offers"))]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct SetOfferAutoRecreateResponse {}
+
 /// Cancel an offer on-chain
 #[cfg_attr(
     feature = "openapi",
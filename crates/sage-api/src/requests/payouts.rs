@@ -0,0 +1,155 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{Amount, PayoutPaymentRecord, PayoutRecord};
+
+#[derive(Debug, Default, Clone, Copy, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum PayoutFormat {
+    #[default]
+    Csv,
+    Json,
+}
+
+/// List payouts
+#[cfg_attr(
+    feature = "openapi",
+    crate::openapi_attr(
+        tag = "Payouts",
+        description = "List all payouts along with how many of their payments have been submitted and confirmed."
+    )
+)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct GetPayouts {}
+
+/// Response with payouts
+#[cfg_attr(feature = "openapi", crate::openapi_attr(tag = "Payouts"))]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct GetPayoutsResponse {
+    /// List of payouts
+    pub payouts: Vec<PayoutRecord>,
+}
+
+/// Get a payout
+#[cfg_attr(
+    feature = "openapi",
+    crate::openapi_attr(
+        tag = "Payouts",
+        description = "Get a payout along with the status of each of its payments."
+    )
+)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct GetPayout {
+    /// Payout ID
+    #[cfg_attr(feature = "openapi", schema(example = 1))]
+    pub id: u32,
+}
+
+/// Response with a payout and its payments
+#[cfg_attr(feature = "openapi", crate::openapi_attr(tag = "Payouts"))]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct GetPayoutResponse {
+    /// The payout
+    pub payout: PayoutRecord,
+    /// Payments in the order they were provided
+    pub payments: Vec<PayoutPaymentRecord>,
+}
+
+/// Create a payout
+#[cfg_attr(
+    feature = "openapi",
+    crate::openapi_attr(
+        tag = "Payouts",
+        description = "Create a payout from a list of payments. Every row is validated up front, then the payments are split into transactions that stay below the maximum cost and submitted one after another while the wallet is synced, if background signing is enabled. Each transaction is checked against the spending policy. The data has the columns address, asset (empty or xch for XCH, otherwise the CAT asset ID), amount (in mojos) and an optional hex encoded memo. CSV data must start with a header row, and JSON data is an array of objects with the same fields."
+    )
+)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct CreatePayout {
+    /// Display name for the payout
+    #[cfg_attr(feature = "openapi", schema(example = "Airdrop"))]
+    pub name: String,
+    /// Format of the payment data
+    #[serde(default)]
+    pub format: PayoutFormat,
+    /// The payment data
+    pub data: String,
+    /// Fee paid on each transaction
+    pub fee: Amount,
+    /// Maximum cost of each transaction, defaults to the maximum cost allowed by the mempool
+    #[serde(default)]
+    pub max_cost: Option<u64>,
+}
+
+/// Response with the created payout
+#[cfg_attr(feature = "openapi", crate::openapi_attr(tag = "Payouts"))]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct CreatePayoutResponse {
+    /// The created payout
+    pub payout: PayoutRecord,
+}
+
+/// Pause or resume a payout
+#[cfg_attr(
+    feature = "openapi",
+    crate::openapi_attr(
+        tag = "Payouts",
+        description = "Pause or resume a payout. Payouts are paused automatically if a transaction fails or would break the spending policy, and resuming one clears the error and retries the payments that weren't confirmed."
+    )
+)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct PausePayout {
+    /// Payout ID
+    #[cfg_attr(feature = "openapi", schema(example = 1))]
+    pub id: u32,
+    /// Whether the payout should be paused
+    pub paused: bool,
+}
+
+/// Response with the updated payout
+#[cfg_attr(feature = "openapi", crate::openapi_attr(tag = "Payouts"))]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct PausePayoutResponse {
+    /// The updated payout
+    pub payout: PayoutRecord,
+}
+
+/// Delete a payout
+#[cfg_attr(
+    feature = "openapi",
+    crate::openapi_attr(
+        tag = "Payouts",
+        description = "Delete a payout and its history. Transactions that were already submitted are not affected."
+    )
+)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct DeletePayout {
+    /// Payout ID
+    #[cfg_attr(feature = "openapi", schema(example = 1))]
+    pub id: u32,
+}
+
+/// Response for deleting a payout
+#[cfg_attr(feature = "openapi", crate::openapi_attr(tag = "Payouts"))]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct DeletePayoutResponse {}
@@ -0,0 +1,113 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{Amount, Payment, ScheduledPaymentRecord};
+
+/// List scheduled payments
+#[cfg_attr(
+    feature = "openapi",
+    crate::openapi_attr(
+        tag = "Scheduled Payments",
+        description = "List all scheduled payments along with the outcome of their most recent runs."
+    )
+)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct GetScheduledPayments {}
+
+/// Response with scheduled payments
+#[cfg_attr(feature = "openapi", crate::openapi_attr(tag = "Scheduled Payments"))]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct GetScheduledPaymentsResponse {
+    /// List of scheduled payments
+    pub scheduled_payments: Vec<ScheduledPaymentRecord>,
+}
+
+/// Create a scheduled payment
+#[cfg_attr(
+    feature = "openapi",
+    crate::openapi_attr(
+        tag = "Scheduled Payments",
+        description = "Create a recurring payment that is sent automatically while the wallet is synced, if background signing is enabled. Each run is checked against the spending policy, and runs that would break it are recorded as failed. The schedule is a five field cron expression evaluated in UTC."
+    )
+)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct CreateScheduledPayment {
+    /// Display name for the scheduled payment
+    #[cfg_attr(feature = "openapi", schema(example = "Rent"))]
+    pub name: String,
+    /// Cron expression (minute, hour, day of month, month, day of week)
+    #[cfg_attr(feature = "openapi", schema(example = "0 9 1 * *"))]
+    pub schedule: String,
+    /// Payments to make on each run
+    pub payments: Vec<Payment>,
+    /// Fee paid on each run
+    pub fee: Amount,
+}
+
+/// Response with the created scheduled payment
+#[cfg_attr(feature = "openapi", crate::openapi_attr(tag = "Scheduled Payments"))]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct CreateScheduledPaymentResponse {
+    /// The created scheduled payment
+    pub scheduled_payment: ScheduledPaymentRecord,
+}
+
+/// Pause or resume a scheduled payment
+#[cfg_attr(
+    feature = "openapi",
+    crate::openapi_attr(
+        tag = "Scheduled Payments",
+        description = "Pause or resume a scheduled payment. Resuming skips any runs that were missed while paused."
+    )
+)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct PauseScheduledPayment {
+    /// Scheduled payment ID
+    #[cfg_attr(feature = "openapi", schema(example = 1))]
+    pub id: u32,
+    /// Whether the scheduled payment should be paused
+    pub paused: bool,
+}
+
+/// Response with the updated scheduled payment
+#[cfg_attr(feature = "openapi", crate::openapi_attr(tag = "Scheduled Payments"))]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct PauseScheduledPaymentResponse {
+    /// The updated scheduled payment
+    pub scheduled_payment: ScheduledPaymentRecord,
+}
+
+/// Delete a scheduled payment
+#[cfg_attr(
+    feature = "openapi",
+    crate::openapi_attr(
+        tag = "Scheduled Payments",
+        description = "Delete a scheduled payment and its run history."
+    )
+)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct DeleteScheduledPayment {
+    /// Scheduled payment ID
+    #[cfg_attr(feature = "openapi", schema(example = 1))]
+    pub id: u32,
+}
+
+/// Response for deleting a scheduled payment
+#[cfg_attr(feature = "openapi", crate::openapi_attr(tag = "Scheduled Payments"))]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct DeleteScheduledPaymentResponse {}
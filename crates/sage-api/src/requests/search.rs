@@ -0,0 +1,74 @@
+use serde::{Deserialize, Serialize};
+
+/// Search the wallet
+#[cfg_attr(
+    feature = "openapi",
+    crate::openapi_attr(
+        tag = "Search",
+        description = "Search the names, tickers and descriptions of tokens, NFTs, DIDs and collections, the traits of NFTs, the names, notes, addresses and DIDs of contacts, the ids of offers and of transactions submitted by this wallet, and the addresses and memos of the payments they make. Incoming payments aren't indexed, since the wallet doesn't keep their memos or the address they were sent from. Each word of the query matches words which start with it, and addresses and NFT, DID and collection ids match the puzzle hash or id they encode. Results are ranked by relevance."
+    )
+)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct Search {
+    /// Search query
+    #[cfg_attr(feature = "openapi", schema(example = "monkey blue"))]
+    pub query: String,
+    /// Only include results of these kinds (all kinds if empty)
+    #[serde(default)]
+    pub kinds: Vec<SearchKind>,
+    /// Starting offset for pagination
+    #[cfg_attr(feature = "openapi", schema(example = 0))]
+    pub offset: u32,
+    /// Number of results to return
+    #[cfg_attr(feature = "openapi", schema(example = 50))]
+    pub limit: u32,
+}
+
+/// Response with search results
+#[cfg_attr(feature = "openapi", crate::openapi_attr(tag = "Search"))]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct SearchResponse {
+    /// Results, the most relevant first
+    pub results: Vec<SearchResult>,
+    /// Total number of results
+    pub total: u32,
+}
+
+/// Something in the wallet which matched a search
+#[cfg_attr(feature = "openapi", crate::openapi_attr(tag = "Search"))]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct SearchResult {
+    /// Kind of result
+    pub kind: SearchKind,
+    /// Asset ID, NFT ID, DID ID, option ID, collection ID, offer ID, transaction ID or contact ID
+    pub id: String,
+    /// Name, if it has one
+    #[cfg_attr(feature = "openapi", schema(nullable = true))]
+    pub name: Option<String>,
+    /// Ticker of a token
+    #[cfg_attr(feature = "openapi", schema(nullable = true))]
+    pub ticker: Option<String>,
+    /// Relevance of the result, where higher is more relevant
+    pub score: f64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum SearchKind {
+    Token,
+    Nft,
+    Did,
+    Option,
+    Collection,
+    Offer,
+    Transaction,
+    Contact,
+}
@@ -248,6 +248,28 @@ pub struct SetChangeAddress {
     pub change_address: Option<String>,
 }
 
+/// Allow background jobs to sign transactions for a wallet
+#[cfg_attr(
+    feature = "openapi",
+    crate::openapi_attr(
+        tag = "Authentication & Keys",
+        description = "Opt in to unattended signing for scheduled payments, payouts, mint jobs, offer reposts and recreated offers. The spending policy still applies to everything signed in the background.",
+        response_type = "EmptyResponse"
+    )
+)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct SetBackgroundSigning {
+    /// Wallet fingerprint
+    #[cfg_attr(feature = "openapi", schema(example = 1_234_567_890))]
+    pub fingerprint: u32,
+    /// Whether scheduled payments, payouts, mint jobs, offer reposts and recreated offers are
+    /// signed without the wallet being unlocked
+    #[cfg_attr(feature = "openapi", schema(example = false))]
+    pub enabled: bool,
+}
+
 #[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 #[cfg_attr(feature = "tauri", derive(specta::Type))]
 #[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
@@ -262,3 +284,4 @@ pub type SetNetworkOverrideResponse = EmptyResponse;
 pub type SetDeltaSyncResponse = EmptyResponse;
 pub type SetDeltaSyncOverrideResponse = EmptyResponse;
 pub type SetChangeAddressResponse = EmptyResponse;
+pub type SetBackgroundSigningResponse = EmptyResponse;
@@ -0,0 +1,92 @@
+use serde::{Deserialize, Serialize};
+
+use crate::Amount;
+
+/// Rules that every transaction must satisfy before it is signed
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct SpendingPolicy {
+    /// Outflow limits for individual assets
+    #[serde(default)]
+    pub limits: Vec<SpendingLimit>,
+    /// If not empty, the only addresses that funds can be sent to
+    #[serde(default)]
+    pub allowed_addresses: Vec<String>,
+    /// Maximum fee for a single transaction
+    #[serde(default)]
+    pub max_fee: Option<Amount>,
+    /// Whether NFTs can be transferred out of the wallet
+    #[serde(default)]
+    pub block_nft_transfers: bool,
+    /// Whether DIDs can be transferred out of the wallet
+    #[serde(default)]
+    pub block_did_transfers: bool,
+}
+
+/// Outflow limits for an asset
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct SpendingLimit {
+    /// Asset ID of the CAT, or null for XCH
+    #[serde(default)]
+    pub asset_id: Option<String>,
+    /// Maximum amount sent in a single transaction
+    #[serde(default)]
+    pub max_per_transaction: Option<Amount>,
+    /// Maximum amount sent in any 24 hour period
+    #[serde(default)]
+    pub max_per_day: Option<Amount>,
+}
+
+/// Get the spending policy
+#[cfg_attr(
+    feature = "openapi",
+    crate::openapi_attr(
+        tag = "Spending Policy",
+        description = "Get the rules that transactions must satisfy before the wallet will sign them."
+    )
+)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct GetSpendingPolicy {}
+
+/// Response with the spending policy
+#[cfg_attr(feature = "openapi", crate::openapi_attr(tag = "Spending Policy"))]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct GetSpendingPolicyResponse {
+    /// The current spending policy
+    pub policy: SpendingPolicy,
+}
+
+/// Set the spending policy
+#[cfg_attr(
+    feature = "openapi",
+    crate::openapi_attr(
+        tag = "Spending Policy",
+        description = "Replace the spending policy. Transactions which violate it are rejected with a policy error, including those signed by background jobs such as scheduled payments, payouts, offer reposts and mint jobs. Requires the wallet password."
+    )
+)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct SetSpendingPolicy {
+    /// Password used to unlock the wallet's keys
+    pub password: String,
+    /// The new spending policy
+    pub policy: SpendingPolicy,
+}
+
+/// Response with the updated spending policy
+#[cfg_attr(feature = "openapi", crate::openapi_attr(tag = "Spending Policy"))]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct SetSpendingPolicyResponse {
+    /// The updated spending policy
+    pub policy: SpendingPolicy,
+}
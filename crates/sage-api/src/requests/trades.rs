@@ -0,0 +1,38 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{Amount, TradeAssetPnl, TradeRecord};
+
+/// List the trade history
+#[cfg_attr(
+    feature = "openapi",
+    crate::openapi_attr(
+        tag = "Offers",
+        description = "List the offers the wallet has made which were taken, and the offers it took, with the amounts and prices they were filled at. The profit and loss of each asset is totalled over the filled trades. Prices are the amount of the quote asset per unit of the base asset, and XCH is always the quote asset when it's traded. Realized profit and loss is in XCH mojos, using the average buy and sell prices."
+    )
+)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct GetTrades {
+    /// Only include trades of this asset (null for all trades)
+    #[serde(default)]
+    #[cfg_attr(feature = "openapi", schema(nullable = true))]
+    pub asset_id: Option<String>,
+    /// Whether to include taken offers which haven't been filled yet
+    #[serde(default)]
+    pub include_unfilled: bool,
+}
+
+/// Response with the trade history
+#[cfg_attr(feature = "openapi", crate::openapi_attr(tag = "Offers"))]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct GetTradesResponse {
+    /// Trades, the most recently filled first
+    pub trades: Vec<TradeRecord>,
+    /// Profit and loss of each traded asset
+    pub assets: Vec<TradeAssetPnl>,
+    /// Total fees paid for the filled trades
+    pub fees_paid: Amount,
+}
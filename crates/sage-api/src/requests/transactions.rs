@@ -513,6 +513,48 @@ pub enum NftUriKind {
     License,
 }
 
+/// Update the metadata of NFTs
+#[cfg_attr(
+    feature = "openapi",
+    crate::openapi_attr(
+        tag = "NFTs",
+        description = "Update the on-chain metadata of one or more NFTs in a single transaction. The default metadata updater can only prepend URIs to lists which already exist, since hashes, edition numbers and royalties are fixed at mint.",
+        response_type = "TransactionResponse"
+    )
+)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct UpdateNftMetadata {
+    /// Metadata updates to apply
+    pub updates: Vec<NftMetadataUpdate>,
+    /// Transaction fee
+    pub fee: Amount,
+    /// Whether to automatically submit the transaction
+    #[serde(default)]
+    #[cfg_attr(feature = "openapi", schema(default = false))]
+    pub auto_submit: bool,
+}
+
+/// Metadata changes for a single NFT
+#[cfg_attr(feature = "openapi", crate::openapi_attr(tag = "NFTs"))]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct NftMetadataUpdate {
+    /// NFT ID
+    pub nft_id: String,
+    /// Data URIs to add
+    #[serde(default)]
+    pub data_uris: Vec<String>,
+    /// Metadata URIs to add
+    #[serde(default)]
+    pub metadata_uris: Vec<String>,
+    /// License URIs to add
+    #[serde(default)]
+    pub license_uris: Vec<String>,
+}
+
 /// Assign NFTs to a DID
 #[cfg_attr(
     feature = "openapi",
@@ -825,6 +867,7 @@ pub type MultiSendResponse = TransactionResponse;
 pub type CreateDidResponse = TransactionResponse;
 pub type TransferNftsResponse = TransactionResponse;
 pub type AddNftUriResponse = TransactionResponse;
+pub type UpdateNftMetadataResponse = TransactionResponse;
 pub type AssignNftsToDidResponse = TransactionResponse;
 pub type TransferDidsResponse = TransactionResponse;
 pub type NormalizeDidsResponse = TransactionResponse;
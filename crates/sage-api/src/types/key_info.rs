@@ -11,6 +11,7 @@ pub struct KeyInfo {
     pub has_secrets: bool,
     pub network_id: String,
     pub emoji: Option<String>,
+    pub background_signing: bool,
 }
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize)]
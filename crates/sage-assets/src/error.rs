@@ -20,6 +20,9 @@ pub enum UriError {
     #[error("No URIs provided")]
     NoUris,
 
+    #[error("No gateways are configured for {0}")]
+    NoGateways(String),
+
     #[error("Failed to create thumbnail: {0}")]
     Thumbnail(#[from] ThumbnailError),
 }
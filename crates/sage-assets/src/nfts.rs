@@ -2,8 +2,10 @@ mod chip0007_metadata;
 mod data_uri;
 mod fetch_nft_uri;
 mod thumbnail;
+mod uri_resolver;
 
 pub use chip0007_metadata::*;
 pub use data_uri::*;
 pub use fetch_nft_uri::*;
 pub use thumbnail::*;
+pub use uri_resolver::*;
@@ -1,17 +1,16 @@
 use std::time::{Duration, Instant};
 
 use chia::protocol::Bytes32;
-use chia::sha2::Sha256;
 use futures_lite::StreamExt;
 use futures_util::stream::FuturesUnordered;
 use mime_sniffer::MimeTypeSniffer;
-use reqwest::{header::CONTENT_TYPE, StatusCode};
+use reqwest::{header::CONTENT_TYPE, Client, StatusCode};
 use tokio::task::spawn_blocking;
 use tracing::debug;
 
 use crate::UriError;
 
-use super::{thumbnail as make_thumbnail, Thumbnail};
+use super::{thumbnail as make_thumbnail, Thumbnail, UriResolver};
 
 #[derive(Debug, Clone)]
 pub struct Data {
@@ -21,8 +20,10 @@ pub struct Data {
     pub thumbnail: Option<Thumbnail>,
 }
 
-pub async fn fetch_uri(uri: String, testnet: bool) -> Result<Data, UriError> {
-    let response = reqwest::get(&uri).await?;
+/// Fetches the contents of a URI along with its mime type, which is sniffed from the contents
+/// if the server doesn't provide one.
+pub(crate) async fn fetch_bytes(client: &Client, uri: &str) -> Result<(Vec<u8>, String), UriError> {
+    let response = client.get(uri).send().await?.error_for_status()?;
 
     let mime_type = match response.headers().get(CONTENT_TYPE) {
         Some(header) => Some(
@@ -45,9 +46,21 @@ pub async fn fetch_uri(uri: String, testnet: bool) -> Result<Data, UriError> {
             .to_string()
     };
 
-    let mut hasher = Sha256::new();
-    hasher.update(&blob);
-    let hash = Bytes32::new(hasher.finalize());
+    Ok((blob, mime_type))
+}
+
+/// Fetches a URI through the resolver, which tries other gateways for content-addressed URIs
+/// if the content doesn't match the expected hash, and creates a thumbnail for it.
+pub async fn fetch_uri(
+    resolver: &UriResolver,
+    uri: String,
+    expected: Option<Bytes32>,
+    testnet: bool,
+) -> Result<Data, UriError> {
+    let content = resolver.fetch(&uri, expected).await?;
+    let blob = content.blob;
+    let mime_type = content.mime_type;
+    let hash = content.hash;
 
     let mut thumbnail = match mintgarden_thumbnail(hash, testnet).await {
         Ok(thumbnail) => thumbnail,
@@ -91,11 +104,15 @@ pub async fn fetch_uri(uri: String, testnet: bool) -> Result<Data, UriError> {
     })
 }
 
-pub async fn fetch_uris_without_hash(uris: Vec<String>, testnet: bool) -> Result<Data, UriError> {
+pub async fn fetch_uris_without_hash(
+    resolver: &UriResolver,
+    uris: Vec<String>,
+    testnet: bool,
+) -> Result<Data, UriError> {
     let mut futures = FuturesUnordered::new();
 
     for uri in uris {
-        futures.push(fetch_uri(uri, testnet));
+        futures.push(fetch_uri(resolver, uri, None, testnet));
     }
 
     let mut data = None;
@@ -128,11 +145,21 @@ pub async fn fetch_uris_without_hash(uris: Vec<String>, testnet: bool) -> Result
     data.ok_or(UriError::NoUris)
 }
 
-pub async fn fetch_uris_with_hash(uris: Vec<String>, hash: Bytes32, testnet: bool) -> Option<Data> {
+pub async fn fetch_uris_with_hash(
+    resolver: &UriResolver,
+    uris: Vec<String>,
+    hash: Bytes32,
+    testnet: bool,
+) -> Option<Data> {
     let mut futures = FuturesUnordered::new();
 
     for uri in uris {
-        futures.push(async move { (uri.clone(), fetch_uri(uri, testnet).await) });
+        futures.push(async move {
+            (
+                uri.clone(),
+                fetch_uri(resolver, uri, Some(hash), testnet).await,
+            )
+        });
     }
 
     while let Some((uri, result)) = futures.next().await {
@@ -151,6 +178,36 @@ pub async fn fetch_uris_with_hash(uris: Vec<String>, hash: Bytes32, testnet: boo
     None
 }
 
+/// Fetches every URI and checks that they all have the same content, which also has to match
+/// the expected hash if there is one. Unlike [`fetch_uris_without_hash`], no thumbnail is created.
+pub async fn verify_uris(
+    resolver: &UriResolver,
+    uris: Vec<String>,
+    expected: Option<Bytes32>,
+) -> Result<Bytes32, UriError> {
+    let mut futures = FuturesUnordered::new();
+
+    for uri in uris {
+        futures.push(async move { resolver.fetch(&uri, expected).await });
+    }
+
+    let mut hash = expected;
+
+    while let Some(result) = futures.next().await {
+        let found = result?.hash;
+
+        match hash {
+            Some(expected) if expected != found => {
+                return Err(UriError::HashMismatch { expected, found });
+            }
+            Some(_) => {}
+            None => hash = Some(found),
+        }
+    }
+
+    hash.ok_or(UriError::NoUris)
+}
+
 pub async fn mintgarden_thumbnail(
     data_hash: Bytes32,
     testnet: bool,
@@ -1,9 +1,32 @@
 use std::io::{self, Cursor};
 
-use image::{DynamicImage, ImageFormat, ImageReader};
+use image::{
+    codecs::{gif::GifDecoder, webp::WebPDecoder},
+    AnimationDecoder, DynamicImage, ImageFormat, ImageReader, ImageResult, Rgba, RgbaImage,
+};
+use resvg::{tiny_skia, usvg};
 use thiserror::Error;
 use webp::Decoder;
 
+mod cover_art;
+mod placeholder;
+
+/// The largest width or height of a preview, before it's scaled down further to fit in
+/// [`MAX_PREVIEW_BYTES`].
+pub const PREVIEW_SIZE: u32 = 1024;
+
+/// Previews are halved in size until the encoded image is no larger than this.
+pub const MAX_PREVIEW_BYTES: usize = 1024 * 1024;
+
+/// Previews aren't scaled down below this size, even if they don't fit in the byte limit.
+const MIN_PREVIEW_SIZE: u32 = 256;
+
+/// The number of frames included in the preview strip of an animation.
+const STRIP_FRAMES: usize = 4;
+
+/// Only the start of long animations is sampled for the preview strip.
+const MAX_ANIMATION_FRAMES: usize = 120;
+
 #[derive(Debug, Error)]
 pub enum ThumbnailError {
     #[error("Failed to load image: {0}")]
@@ -14,54 +37,262 @@ pub enum ThumbnailError {
 
     #[error("Failed to decode webp image")]
     Webp,
+
+    #[error("Failed to parse svg image: {0}")]
+    Svg(#[from] usvg::Error),
+
+    #[error("Invalid svg image size")]
+    SvgSize,
+
+    #[error("Animated image has no frames")]
+    NoFrames,
+}
+
+/// How the resized images were made from the original file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ThumbnailSource {
+    /// The file is a still image which was scaled down.
+    Image,
+    /// The file is an SVG image which was rasterized.
+    Vector,
+    /// The file is an animated image, and the preview is a strip of its frames.
+    Animation,
+    /// The file is a video, and the image is the cover art embedded in it.
+    CoverArt,
+    /// The file can't be rendered, so a placeholder with a badge for its type was generated.
+    Placeholder,
 }
 
 #[derive(Debug, Clone)]
 pub struct Thumbnail {
     pub icon: Vec<u8>,
     pub thumbnail: Vec<u8>,
+    pub preview: Vec<u8>,
+    pub source: ThumbnailSource,
 }
 
 pub fn thumbnail(bytes: &[u8], mime: &str) -> Result<Option<Thumbnail>, ThumbnailError> {
-    let Some(image) = load_image(bytes, mime)? else {
+    let Some(rendered) = render(bytes, mime)? else {
         return Ok(None);
     };
 
-    let icon = image.thumbnail(64, 64);
-    let thumbnail = image.thumbnail(256, 256);
+    let icon = rendered.image.thumbnail(64, 64);
+    let thumbnail = rendered.image.thumbnail(256, 256);
+    let preview = rendered.preview.as_ref().unwrap_or(&rendered.image);
 
-    let mut icon_bytes = Vec::new();
-    let mut thumbnail_bytes = Vec::new();
+    Ok(Some(Thumbnail {
+        icon: encode_png(&icon)?,
+        thumbnail: encode_png(&thumbnail)?,
+        preview: encode_preview(preview)?,
+        source: rendered.source,
+    }))
+}
 
-    let mut icon_writer = Cursor::new(&mut icon_bytes);
-    let mut thumbnail_writer = Cursor::new(&mut thumbnail_bytes);
+struct Rendered {
+    image: DynamicImage,
+    preview: Option<DynamicImage>,
+    source: ThumbnailSource,
+}
 
-    icon.write_to(&mut icon_writer, ImageFormat::Png)?;
-    thumbnail.write_to(&mut thumbnail_writer, ImageFormat::Png)?;
+impl Rendered {
+    fn new(image: DynamicImage, source: ThumbnailSource) -> Self {
+        Self {
+            image,
+            preview: None,
+            source,
+        }
+    }
+}
 
-    Ok(Some(Thumbnail {
-        icon: icon_bytes,
-        thumbnail: thumbnail_bytes,
-    }))
+fn render(bytes: &[u8], mime: &str) -> Result<Option<Rendered>, ThumbnailError> {
+    let mime = mime.split(';').next().unwrap_or_default().trim();
+
+    let rendered = match mime {
+        "image/svg+xml" => Rendered::new(rasterize_svg(bytes)?, ThumbnailSource::Vector),
+        "image/gif" => animation(GifDecoder::new(Cursor::new(bytes))?)?,
+        "image/webp" => {
+            let decoder = WebPDecoder::new(Cursor::new(bytes))?;
+
+            if decoder.has_animation() {
+                animation(decoder)?
+            } else {
+                Rendered::new(decode_webp(bytes)?, ThumbnailSource::Image)
+            }
+        }
+        _ if mime.starts_with("video/") => match cover_art::cover_art(bytes) {
+            Some(cover_art) => Rendered::new(
+                image::load_from_memory(cover_art)?,
+                ThumbnailSource::CoverArt,
+            ),
+            None => Rendered::new(
+                placeholder::placeholder("VIDEO"),
+                ThumbnailSource::Placeholder,
+            ),
+        },
+        _ => {
+            if let Some(format) = mime_to_image_format(mime) {
+                let image = ImageReader::with_format(Cursor::new(bytes), format).decode()?;
+                Rendered::new(image, ThumbnailSource::Image)
+            } else if let Some(badge) = placeholder_badge(mime) {
+                Rendered::new(
+                    placeholder::placeholder(badge),
+                    ThumbnailSource::Placeholder,
+                )
+            } else {
+                return Ok(None);
+            }
+        }
+    };
+
+    Ok(Some(rendered))
+}
+
+fn decode_webp(bytes: &[u8]) -> Result<DynamicImage, ThumbnailError> {
+    Ok(Decoder::new(bytes)
+        .decode()
+        .ok_or(ThumbnailError::Webp)?
+        .to_image())
 }
 
-fn load_image(bytes: &[u8], mime: &str) -> Result<Option<DynamicImage>, ThumbnailError> {
-    if mime == "image/webp" {
-        return Ok(Some(
-            Decoder::new(bytes)
-                .decode()
-                .ok_or(ThumbnailError::Webp)?
-                .to_image(),
-        ));
+/// The first frame is used for the icon and thumbnail, and a few frames spread across the
+/// animation are laid out side by side for the preview.
+fn animation<'a>(decoder: impl AnimationDecoder<'a>) -> Result<Rendered, ThumbnailError> {
+    let frame_size = PREVIEW_SIZE / STRIP_FRAMES as u32;
+
+    let mut first = None;
+    let mut frames = Vec::new();
+
+    for frame in decoder.into_frames().take(MAX_ANIMATION_FRAMES) {
+        let frame = DynamicImage::ImageRgba8(frame?.into_buffer());
+        frames.push(fit(&frame, frame_size));
+        first.get_or_insert(frame);
     }
 
-    let Some(format) = mime_to_image_format(mime) else {
-        return Ok(None);
+    let first = first.ok_or(ThumbnailError::NoFrames)?;
+
+    if frames.len() == 1 {
+        return Ok(Rendered::new(first, ThumbnailSource::Image));
+    }
+
+    let frames = sample_frames(&frames, STRIP_FRAMES);
+
+    Ok(Rendered {
+        image: first,
+        preview: Some(strip(&frames)),
+        source: ThumbnailSource::Animation,
+    })
+}
+
+/// Picks frames spread evenly across the animation, starting with the first one.
+fn sample_frames(frames: &[DynamicImage], count: usize) -> Vec<DynamicImage> {
+    let len = frames.len();
+
+    if len <= count {
+        return frames.to_vec();
+    }
+
+    (0..count)
+        .map(|i| frames[i * len / count].clone())
+        .collect()
+}
+
+fn strip(frames: &[DynamicImage]) -> DynamicImage {
+    let width = frames.iter().map(DynamicImage::width).sum();
+    let height = frames
+        .iter()
+        .map(DynamicImage::height)
+        .max()
+        .unwrap_or_default();
+
+    let mut strip = RgbaImage::new(width, height);
+    let mut x = 0;
+
+    for frame in frames {
+        image::imageops::overlay(&mut strip, &frame.to_rgba8(), x.into(), 0);
+        x += frame.width();
+    }
+
+    DynamicImage::ImageRgba8(strip)
+}
+
+/// Renders an SVG image at preview size. External images and text aren't rendered, since
+/// neither local files nor system fonts should be read on behalf of an NFT.
+fn rasterize_svg(bytes: &[u8]) -> Result<DynamicImage, ThumbnailError> {
+    let options = usvg::Options {
+        image_href_resolver: usvg::ImageHrefResolver {
+            resolve_data: usvg::ImageHrefResolver::default_data_resolver(),
+            resolve_string: Box::new(|_, _| None),
+        },
+        ..Default::default()
     };
 
-    Ok(Some(
-        ImageReader::with_format(Cursor::new(bytes), format).decode()?,
-    ))
+    let tree = usvg::Tree::from_data(bytes, &options)?;
+    let size = tree.size();
+
+    #[allow(clippy::cast_precision_loss)]
+    let scale = (PREVIEW_SIZE as f32 / size.width()).min(PREVIEW_SIZE as f32 / size.height());
+    let width = (size.width() * scale).round() as u32;
+    let height = (size.height() * scale).round() as u32;
+
+    let mut pixmap = tiny_skia::Pixmap::new(width, height).ok_or(ThumbnailError::SvgSize)?;
+
+    resvg::render(
+        &tree,
+        tiny_skia::Transform::from_scale(scale, scale),
+        &mut pixmap.as_mut(),
+    );
+
+    let mut image = RgbaImage::new(width, height);
+
+    for (pixel, color) in image.pixels_mut().zip(pixmap.pixels()) {
+        let color = color.demultiply();
+        *pixel = Rgba([color.red(), color.green(), color.blue(), color.alpha()]);
+    }
+
+    Ok(DynamicImage::ImageRgba8(image))
+}
+
+fn placeholder_badge(mime: &str) -> Option<&'static str> {
+    if mime.starts_with("audio/") {
+        Some("AUDIO")
+    } else if mime.starts_with("model/") {
+        Some("3D")
+    } else if mime == "text/html" {
+        Some("HTML")
+    } else {
+        None
+    }
+}
+
+fn encode_png(image: &DynamicImage) -> ImageResult<Vec<u8>> {
+    let mut bytes = Vec::new();
+    image.write_to(&mut Cursor::new(&mut bytes), ImageFormat::Png)?;
+    Ok(bytes)
+}
+
+/// Scales an image down to fit in a square of the given size, but unlike
+/// [`DynamicImage::thumbnail`] never scales it up.
+fn fit(image: &DynamicImage, size: u32) -> DynamicImage {
+    if image.width() <= size && image.height() <= size {
+        image.clone()
+    } else {
+        image.thumbnail(size, size)
+    }
+}
+
+/// Encodes the largest preview that fits in [`MAX_PREVIEW_BYTES`].
+fn encode_preview(image: &DynamicImage) -> ImageResult<Vec<u8>> {
+    let mut size = PREVIEW_SIZE;
+
+    loop {
+        let bytes = encode_png(&fit(image, size))?;
+
+        if bytes.len() <= MAX_PREVIEW_BYTES || size <= MIN_PREVIEW_SIZE {
+            return Ok(bytes);
+        }
+
+        size /= 2;
+    }
 }
 
 fn mime_to_image_format(mime: &str) -> Option<ImageFormat> {
@@ -69,7 +300,74 @@ fn mime_to_image_format(mime: &str) -> Option<ImageFormat> {
         "image/png" => Some(ImageFormat::Png),
         "image/jpeg" => Some(ImageFormat::Jpeg),
         "image/bmp" => Some(ImageFormat::Bmp),
-        "image/gif" => Some(ImageFormat::Gif),
         _ => None,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use image::{codecs::gif::GifEncoder, Delay, Frame};
+
+    use super::*;
+
+    fn dimensions(png: &[u8]) -> anyhow::Result<(u32, u32)> {
+        let image = image::load_from_memory_with_format(png, ImageFormat::Png)?;
+        Ok((image.width(), image.height()))
+    }
+
+    #[test]
+    fn test_svg_thumbnail() -> anyhow::Result<()> {
+        let svg = br##"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 20 10">
+            <rect width="20" height="10" fill="#ff0000" />
+        </svg>"##;
+
+        let thumbnail = thumbnail(svg, "image/svg+xml")?.expect("missing thumbnail");
+
+        assert_eq!(thumbnail.source, ThumbnailSource::Vector);
+        assert_eq!(dimensions(&thumbnail.icon)?, (64, 32));
+        assert_eq!(dimensions(&thumbnail.thumbnail)?, (256, 128));
+        assert_eq!(dimensions(&thumbnail.preview)?, (1024, 512));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_animation_strip() -> anyhow::Result<()> {
+        let mut gif = Vec::new();
+
+        {
+            let mut encoder = GifEncoder::new(&mut gif);
+
+            for shade in [0, 50, 100, 150, 200, 250] {
+                let image = RgbaImage::from_pixel(40, 20, Rgba([shade, shade, shade, 255]));
+                encoder.encode_frame(Frame::from_parts(
+                    image,
+                    0,
+                    0,
+                    Delay::from_numer_denom_ms(100, 1),
+                ))?;
+            }
+        }
+
+        let thumbnail = thumbnail(&gif, "image/gif")?.expect("missing thumbnail");
+
+        assert_eq!(thumbnail.source, ThumbnailSource::Animation);
+        assert_eq!(dimensions(&thumbnail.icon)?, (64, 32));
+        assert_eq!(dimensions(&thumbnail.preview)?, (160, 20));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_placeholders() -> anyhow::Result<()> {
+        for mime in ["audio/mpeg", "model/gltf-binary", "video/mp4"] {
+            let thumbnail = thumbnail(b"not a real file", mime)?.expect("missing thumbnail");
+            assert_eq!(thumbnail.source, ThumbnailSource::Placeholder);
+            assert_eq!(dimensions(&thumbnail.preview)?, (512, 512));
+        }
+
+        assert!(thumbnail(b"{}", "application/json")?.is_none());
+
+        Ok(())
+    }
+}
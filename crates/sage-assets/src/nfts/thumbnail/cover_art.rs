@@ -0,0 +1,220 @@
+//! Video frames can't be decoded without bundling codecs, so videos are only previewed by the
+//! cover art embedded in their container. This is the `covr` item of MP4 files, or the first
+//! image attachment of Matroska files.
+
+const MATROSKA_MAGIC: [u8; 4] = [0x1A, 0x45, 0xDF, 0xA3];
+
+const SEGMENT: u32 = 0x1853_8067;
+const ATTACHMENTS: u32 = 0x1941_A469;
+const ATTACHED_FILE: u32 = 0x61A7;
+const FILE_MIME_TYPE: u32 = 0x4660;
+const FILE_DATA: u32 = 0x465C;
+
+/// Finds the cover art embedded in a video, if there is any.
+pub fn cover_art(bytes: &[u8]) -> Option<&[u8]> {
+    if bytes.starts_with(&MATROSKA_MAGIC) {
+        matroska_cover_art(bytes)
+    } else {
+        mp4_cover_art(bytes)
+    }
+}
+
+fn mp4_cover_art(bytes: &[u8]) -> Option<&[u8]> {
+    let udta = mp4_box(mp4_box(bytes, *b"moov")?, *b"udta")?;
+    let meta = mp4_box(udta, *b"meta")?;
+
+    // The meta box is a full box with a version and flags, except in some QuickTime files.
+    let meta = if meta.get(4..8) == Some(b"hdlr".as_slice()) {
+        meta
+    } else {
+        meta.get(4..)?
+    };
+
+    let data = mp4_box(mp4_box(mp4_box(meta, *b"ilst")?, *b"covr")?, *b"data")?;
+
+    // The image follows the type indicator and locale.
+    data.get(8..)
+}
+
+/// Finds the contents of the first box of a given type in a sequence of boxes.
+fn mp4_box(mut bytes: &[u8], kind: [u8; 4]) -> Option<&[u8]> {
+    while bytes.len() >= 8 {
+        let size = u32::from_be_bytes(bytes[..4].try_into().ok()?);
+
+        let (header, size) = match size {
+            0 => (8, bytes.len()),
+            1 => (
+                16,
+                usize::try_from(u64::from_be_bytes(bytes.get(8..16)?.try_into().ok()?)).ok()?,
+            ),
+            size => (8, size as usize),
+        };
+
+        let contents = bytes.get(header..size)?;
+
+        if bytes[4..8] == kind {
+            return Some(contents);
+        }
+
+        bytes = &bytes[size..];
+    }
+
+    None
+}
+
+fn matroska_cover_art(bytes: &[u8]) -> Option<&[u8]> {
+    let segment = ebml_element(bytes, SEGMENT)?;
+    let attachments = ebml_element(segment, ATTACHMENTS)?;
+
+    let mut files = attachments;
+
+    while let Some((id, contents, rest)) = ebml_next(files) {
+        files = rest;
+
+        if id != ATTACHED_FILE {
+            continue;
+        }
+
+        if ebml_element(contents, FILE_MIME_TYPE)
+            .is_some_and(|mime_type| mime_type.starts_with(b"image/"))
+        {
+            return ebml_element(contents, FILE_DATA);
+        }
+    }
+
+    None
+}
+
+/// Finds the contents of the first element with a given ID in a sequence of elements.
+fn ebml_element(mut bytes: &[u8], target: u32) -> Option<&[u8]> {
+    while let Some((id, contents, rest)) = ebml_next(bytes) {
+        if id == target {
+            return Some(contents);
+        }
+
+        bytes = rest;
+    }
+
+    None
+}
+
+/// Splits the next element off of a sequence, returning its ID, contents and the remaining
+/// bytes. An element of unknown size, which is only allowed for the segment and clusters of
+/// live streams, is assumed to extend to the end.
+fn ebml_next(bytes: &[u8]) -> Option<(u32, &[u8], &[u8])> {
+    let (id, id_len) = ebml_vint(bytes, false)?;
+    let (size, size_len) = ebml_vint(bytes.get(id_len..)?, true)?;
+
+    let start = id_len + size_len;
+    let end = match size {
+        Some(size) => start.checked_add(usize::try_from(size).ok()?)?,
+        None => bytes.len(),
+    };
+
+    Some((
+        u32::try_from(id?).ok()?,
+        bytes.get(start..end)?,
+        &bytes[end..],
+    ))
+}
+
+/// Reads a variable length integer, whose length is the number of leading zero bits of the first
+/// byte plus one. The length marker is kept for IDs but removed from sizes, and a size with all
+/// of its value bits set means that it's unknown.
+fn ebml_vint(bytes: &[u8], is_size: bool) -> Option<(Option<u64>, usize)> {
+    let first = *bytes.first()?;
+    let len = first.leading_zeros() as usize + 1;
+
+    if len > 8 || (!is_size && len > 4) {
+        return None;
+    }
+
+    let mut value = u64::from(first);
+
+    if is_size {
+        value &= (1 << (8 - len)) - 1;
+    }
+
+    for &byte in bytes.get(1..len)? {
+        value = (value << 8) | u64::from(byte);
+    }
+
+    if is_size && value == (1 << (7 * len)) - 1 {
+        return Some((None, len));
+    }
+
+    Some((Some(value), len))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mp4(kind: [u8; 4], contents: &[u8]) -> Vec<u8> {
+        let size = contents.len() as u32 + 8;
+        [&size.to_be_bytes(), &kind, contents].concat()
+    }
+
+    fn ebml(id: &[u8], contents: &[u8]) -> Vec<u8> {
+        let size = contents.len() as u8 | 0x80;
+        [id, &[size], contents].concat()
+    }
+
+    #[test]
+    fn test_mp4_cover_art() {
+        let data = mp4(
+            *b"data",
+            &[[0, 0, 0, 14].as_slice(), &[0; 4], b"cover"].concat(),
+        );
+        let ilst = mp4(*b"ilst", &mp4(*b"covr", &data));
+        let meta = mp4(
+            *b"meta",
+            &[[0; 4].as_slice(), &mp4(*b"hdlr", &[0; 4]), &ilst].concat(),
+        );
+        let moov = mp4(
+            *b"moov",
+            &[mp4(*b"mvhd", &[0; 8]), mp4(*b"udta", &meta)].concat(),
+        );
+        let file = [mp4(*b"ftyp", b"isom"), moov, mp4(*b"mdat", &[1; 16])].concat();
+
+        assert_eq!(cover_art(&file), Some(b"cover".as_slice()));
+        assert_eq!(cover_art(&mp4(*b"ftyp", b"isom")), None);
+    }
+
+    #[test]
+    fn test_matroska_cover_art() {
+        let font = ebml(
+            &[0x61, 0xA7],
+            &[
+                ebml(&[0x46, 0x60], b"font/ttf"),
+                ebml(&[0x46, 0x5C], b"font"),
+            ]
+            .concat(),
+        );
+        let cover = ebml(
+            &[0x61, 0xA7],
+            &[
+                ebml(&[0x46, 0x60], b"image/png"),
+                ebml(&[0x46, 0x5C], b"cover"),
+            ]
+            .concat(),
+        );
+        let attachments = ebml(&[0x19, 0x41, 0xA4, 0x69], &[font, cover].concat());
+
+        // The segment has an unknown size, like a file that is still being written.
+        let segment = [
+            [0x18, 0x53, 0x80, 0x67, 0xFF].as_slice(),
+            &ebml(&[0x1F, 0x43, 0xB6, 0x75], &[0; 4]),
+            &attachments,
+        ]
+        .concat();
+
+        let file = [
+            ebml(&MATROSKA_MAGIC, &[0x42, 0x82, 0x84, b'w', b'e', b'b', b'm']),
+            segment,
+        ]
+        .concat();
+
+        assert_eq!(cover_art(&file), Some(b"cover".as_slice()));
+    }
+}
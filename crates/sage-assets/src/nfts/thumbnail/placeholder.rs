@@ -0,0 +1,116 @@
+use image::{DynamicImage, Rgba, RgbaImage};
+
+const SIZE: u32 = 512;
+
+const BACKGROUND: Rgba<u8> = Rgba([39, 39, 42, 255]);
+const BADGE: Rgba<u8> = Rgba([82, 82, 91, 255]);
+const TEXT: Rgba<u8> = Rgba([250, 250, 250, 255]);
+
+const GLYPH_WIDTH: u32 = 5;
+const GLYPH_HEIGHT: u32 = 7;
+
+/// Generates a square image with the badge text centered on it, so that files which can't be
+/// rendered still have an icon which says what they are.
+pub fn placeholder(badge: &str) -> DynamicImage {
+    let mut image = RgbaImage::from_pixel(SIZE, SIZE, BACKGROUND);
+
+    let glyphs: Vec<[u8; GLYPH_HEIGHT as usize]> = badge.chars().filter_map(glyph).collect();
+
+    // Each glyph is followed by a column of spacing, except for the last one.
+    let columns = (glyphs.len() as u32 * (GLYPH_WIDTH + 1)).saturating_sub(1);
+
+    if columns == 0 {
+        return DynamicImage::ImageRgba8(image);
+    }
+
+    let scale = (SIZE / 2 / columns).clamp(1, SIZE / 6 / GLYPH_HEIGHT);
+    let padding = scale * 3;
+
+    let text_width = columns * scale;
+    let text_height = GLYPH_HEIGHT * scale;
+    let left = (SIZE - text_width) / 2;
+    let top = (SIZE - text_height) / 2;
+
+    fill(
+        &mut image,
+        left - padding,
+        top - padding,
+        text_width + padding * 2,
+        text_height + padding * 2,
+        BADGE,
+    );
+
+    for (index, rows) in glyphs.iter().enumerate() {
+        let glyph_left = left + index as u32 * (GLYPH_WIDTH + 1) * scale;
+
+        for (y, row) in rows.iter().enumerate() {
+            for x in 0..GLYPH_WIDTH {
+                if row & (1 << (GLYPH_WIDTH - 1 - x)) == 0 {
+                    continue;
+                }
+
+                fill(
+                    &mut image,
+                    glyph_left + x * scale,
+                    top + y as u32 * scale,
+                    scale,
+                    scale,
+                    TEXT,
+                );
+            }
+        }
+    }
+
+    DynamicImage::ImageRgba8(image)
+}
+
+fn fill(image: &mut RgbaImage, left: u32, top: u32, width: u32, height: u32, color: Rgba<u8>) {
+    for y in top..top + height {
+        for x in left..left + width {
+            image.put_pixel(x, y, color);
+        }
+    }
+}
+
+/// A 5x7 bitmap of the characters used in badges, where each row is stored in the low bits.
+fn glyph(c: char) -> Option<[u8; GLYPH_HEIGHT as usize]> {
+    Some(match c {
+        '3' => [
+            0b11110, 0b00001, 0b00001, 0b01110, 0b00001, 0b00001, 0b11110,
+        ],
+        'A' => [
+            0b01110, 0b10001, 0b10001, 0b11111, 0b10001, 0b10001, 0b10001,
+        ],
+        'D' => [
+            0b11110, 0b10001, 0b10001, 0b10001, 0b10001, 0b10001, 0b11110,
+        ],
+        'E' => [
+            0b11111, 0b10000, 0b10000, 0b11110, 0b10000, 0b10000, 0b11111,
+        ],
+        'H' => [
+            0b10001, 0b10001, 0b10001, 0b11111, 0b10001, 0b10001, 0b10001,
+        ],
+        'I' => [
+            0b01110, 0b00100, 0b00100, 0b00100, 0b00100, 0b00100, 0b01110,
+        ],
+        'L' => [
+            0b10000, 0b10000, 0b10000, 0b10000, 0b10000, 0b10000, 0b11111,
+        ],
+        'M' => [
+            0b10001, 0b11011, 0b10101, 0b10101, 0b10001, 0b10001, 0b10001,
+        ],
+        'O' => [
+            0b01110, 0b10001, 0b10001, 0b10001, 0b10001, 0b10001, 0b01110,
+        ],
+        'T' => [
+            0b11111, 0b00100, 0b00100, 0b00100, 0b00100, 0b00100, 0b00100,
+        ],
+        'U' => [
+            0b10001, 0b10001, 0b10001, 0b10001, 0b10001, 0b10001, 0b01110,
+        ],
+        'V' => [
+            0b10001, 0b10001, 0b10001, 0b10001, 0b10001, 0b01010, 0b00100,
+        ],
+        _ => return None,
+    })
+}
@@ -0,0 +1,403 @@
+use std::time::Duration;
+
+use chia::{protocol::Bytes32, sha2::Sha256};
+use reqwest::Client;
+use sage_config::GatewayConfig;
+use tracing::debug;
+
+use crate::UriError;
+
+use super::fetch_bytes;
+
+/// How long a single gateway has to respond before the next one is tried.
+const GATEWAY_TIMEOUT: Duration = Duration::from_secs(15);
+
+/// A URI in its canonical form. Content-addressed URIs are identified by their CID, name or
+/// transaction id (along with an optional path), regardless of which gateway they came from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ContentUri {
+    Http(String),
+    Ipfs(String),
+    Ipns(String),
+    Arweave(String),
+}
+
+impl ContentUri {
+    pub fn canonical(&self) -> String {
+        match self {
+            Self::Http(uri) => uri.clone(),
+            Self::Ipfs(path) => format!("ipfs://{path}"),
+            Self::Ipns(path) => format!("ipns://{path}"),
+            Self::Arweave(path) => format!("ar://{path}"),
+        }
+    }
+}
+
+/// The content of a URI, along with the URL it was fetched from.
+#[derive(Debug, Clone)]
+pub struct UriContent {
+    pub blob: Vec<u8>,
+    pub mime_type: String,
+    pub hash: Bytes32,
+    pub url: String,
+}
+
+/// Turns the URIs found in NFT metadata into URLs that can be fetched. Content-addressed URIs
+/// can be served by any gateway, so each of the configured ones is tried until the content
+/// matches the expected hash.
+#[derive(Debug, Clone)]
+pub struct UriResolver {
+    gateways: GatewayConfig,
+    client: Client,
+}
+
+impl Default for UriResolver {
+    fn default() -> Self {
+        Self::new(GatewayConfig::default())
+    }
+}
+
+impl UriResolver {
+    pub fn new(gateways: GatewayConfig) -> Self {
+        let client = Client::builder()
+            .timeout(GATEWAY_TIMEOUT)
+            .build()
+            .unwrap_or_default();
+
+        Self { gateways, client }
+    }
+
+    pub fn parse(&self, uri: &str) -> ContentUri {
+        let uri = uri.trim();
+
+        if let Some(path) = uri.strip_prefix("ipfs://") {
+            let path = path.strip_prefix("ipfs/").unwrap_or(path);
+            return ContentUri::Ipfs(path.to_string());
+        }
+
+        if let Some(path) = uri.strip_prefix("ipns://") {
+            let path = path.strip_prefix("ipns/").unwrap_or(path);
+            return ContentUri::Ipns(path.to_string());
+        }
+
+        if let Some(path) = uri.strip_prefix("ar://") {
+            return ContentUri::Arweave(path.to_string());
+        }
+
+        let Some(rest) = uri
+            .strip_prefix("https://")
+            .or_else(|| uri.strip_prefix("http://"))
+        else {
+            return ContentUri::Http(uri.to_string());
+        };
+
+        let (host, path) = rest.split_at(rest.find('/').unwrap_or(rest.len()));
+
+        // Path gateways, such as `https://ipfs.io/ipfs/{cid}/{path}`.
+        if let Some(path) = path.strip_prefix("/ipfs/") {
+            if is_cid(first_segment(path)) {
+                return ContentUri::Ipfs(path.to_string());
+            }
+        }
+
+        if let Some(path) = path.strip_prefix("/ipns/") {
+            if !first_segment(path).is_empty() {
+                return ContentUri::Ipns(path.to_string());
+            }
+        }
+
+        // Subdomain gateways, such as `https://{cid}.ipfs.dweb.link/{path}`.
+        let mut labels = host.split('.');
+
+        if let (Some(id), Some(kind)) = (labels.next(), labels.next()) {
+            let path = path.trim_start_matches('/');
+            let path = if path.is_empty() {
+                id.to_string()
+            } else {
+                format!("{id}/{path}")
+            };
+
+            if kind == "ipfs" && is_cid(id) {
+                return ContentUri::Ipfs(path);
+            }
+
+            if kind == "ipns" && !id.is_empty() {
+                return ContentUri::Ipns(path);
+            }
+        }
+
+        if self
+            .gateways
+            .arweave
+            .iter()
+            .any(|gateway| gateway_host(gateway) == Some(host))
+        {
+            let path = path.trim_start_matches('/');
+
+            if is_arweave_id(first_segment(path)) {
+                return ContentUri::Arweave(path.to_string());
+            }
+        }
+
+        ContentUri::Http(uri.to_string())
+    }
+
+    /// The URLs to try in order for a URI. If a content-addressed URI was provided as a gateway
+    /// URL, that gateway is tried after the configured ones.
+    pub fn candidates(&self, uri: &str) -> Vec<String> {
+        let (gateways, path) = match self.parse(uri) {
+            ContentUri::Http(uri) => return vec![uri],
+            ContentUri::Ipfs(path) => (&self.gateways.ipfs, format!("ipfs/{path}")),
+            ContentUri::Ipns(path) => (&self.gateways.ipns, format!("ipns/{path}")),
+            ContentUri::Arweave(path) => (&self.gateways.arweave, path),
+        };
+
+        let mut urls: Vec<String> = gateways
+            .iter()
+            .map(|gateway| format!("{}/{path}", gateway.trim_end_matches('/')))
+            .collect();
+
+        let uri = uri.trim();
+
+        if (uri.starts_with("https://") || uri.starts_with("http://"))
+            && !urls.iter().any(|url| url == uri)
+        {
+            urls.push(uri.to_string());
+        }
+
+        urls
+    }
+
+    /// Fetches a URI, falling back to the next gateway if one fails or returns content that
+    /// doesn't match the expected hash. If none of them match, the first content that was
+    /// found is returned so that the mismatch can be recorded.
+    pub async fn fetch(
+        &self,
+        uri: &str,
+        expected: Option<Bytes32>,
+    ) -> Result<UriContent, UriError> {
+        let mut mismatch = None;
+        let mut error = UriError::NoGateways(uri.to_string());
+
+        for url in self.candidates(uri) {
+            match fetch_bytes(&self.client, &url).await {
+                Ok((blob, mime_type)) => {
+                    let mut hasher = Sha256::new();
+                    hasher.update(&blob);
+                    let hash = Bytes32::new(hasher.finalize());
+
+                    let content = UriContent {
+                        blob,
+                        mime_type,
+                        hash,
+                        url,
+                    };
+
+                    if expected.is_none_or(|expected| expected == hash) {
+                        return Ok(content);
+                    }
+
+                    debug!("Hash mismatch for {uri} from {}", content.url);
+
+                    mismatch.get_or_insert(content);
+                }
+                Err(fetch_error) => {
+                    debug!("Failed to fetch {uri} from {url}: {fetch_error}");
+                    error = fetch_error;
+                }
+            }
+        }
+
+        mismatch.ok_or(error)
+    }
+}
+
+fn first_segment(path: &str) -> &str {
+    path.split(['/', '?', '#']).next().unwrap_or_default()
+}
+
+fn gateway_host(gateway: &str) -> Option<&str> {
+    let rest = gateway
+        .strip_prefix("https://")
+        .or_else(|| gateway.strip_prefix("http://"))?;
+
+    rest.split('/').next()
+}
+
+/// Both version 0 CIDs (base58, starting with `Qm`) and version 1 CIDs (usually base32) are
+/// at least 46 alphanumeric characters long.
+fn is_cid(value: &str) -> bool {
+    value.len() >= 46 && value.chars().all(|c| c.is_ascii_alphanumeric())
+}
+
+/// Arweave transaction ids are 32 bytes encoded as unpadded base64url.
+fn is_arweave_id(value: &str) -> bool {
+    value.len() == 43
+        && value
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::{
+        io::{AsyncReadExt, AsyncWriteExt},
+        net::TcpListener,
+    };
+
+    use super::*;
+
+    const CID: &str = "bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi";
+    const ARWEAVE_ID: &str = "bNbA3TEQVL60xlgCcqdz4ZPHFZ711cZ3hmkpGttDt_U";
+
+    fn test_resolver(gateways: &[&str]) -> UriResolver {
+        let gateways: Vec<String> = gateways.iter().map(ToString::to_string).collect();
+
+        UriResolver::new(GatewayConfig {
+            ipfs: gateways.clone(),
+            ipns: gateways.clone(),
+            arweave: gateways,
+        })
+    }
+
+    fn hash(data: &[u8]) -> Bytes32 {
+        let mut hasher = Sha256::new();
+        hasher.update(data);
+        Bytes32::new(hasher.finalize())
+    }
+
+    /// Serves `/good/...` with the given body, `/bad/...` with different content and anything
+    /// else as not found, so that each prefix acts as a separate gateway.
+    async fn serve(body: &'static str) -> anyhow::Result<String> {
+        let listener = TcpListener::bind("127.0.0.1:0").await?;
+        let base = format!("http://{}", listener.local_addr()?);
+
+        tokio::spawn(async move {
+            while let Ok((mut stream, _)) = listener.accept().await {
+                let mut request = vec![0; 4096];
+                let Ok(len) = stream.read(&mut request).await else {
+                    continue;
+                };
+
+                let request = String::from_utf8_lossy(&request[..len]);
+                let path = request.split(' ').nth(1).unwrap_or_default();
+
+                let (status, body) = if path.starts_with("/good/") {
+                    ("200 OK", body)
+                } else if path.starts_with("/bad/") {
+                    ("200 OK", "tampered")
+                } else {
+                    ("404 Not Found", "")
+                };
+
+                let response = format!(
+                    "HTTP/1.1 {status}\r\nContent-Type: text/plain\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                    body.len()
+                );
+
+                stream.write_all(response.as_bytes()).await.ok();
+            }
+        });
+
+        Ok(base)
+    }
+
+    #[test]
+    fn test_parse_uris() {
+        let resolver = test_resolver(&["https://arweave.net"]);
+
+        for uri in [
+            format!("ipfs://{CID}/1.png"),
+            format!("ipfs://ipfs/{CID}/1.png"),
+            format!("https://ipfs.io/ipfs/{CID}/1.png"),
+            format!("https://{CID}.ipfs.dweb.link/1.png"),
+        ] {
+            assert_eq!(
+                resolver.parse(&uri).canonical(),
+                format!("ipfs://{CID}/1.png")
+            );
+        }
+
+        assert_eq!(
+            resolver.parse("https://gateway.example/ipns/example.com/1.png"),
+            ContentUri::Ipns("example.com/1.png".to_string())
+        );
+        assert_eq!(
+            resolver.parse(&format!("https://arweave.net/{ARWEAVE_ID}")),
+            ContentUri::Arweave(ARWEAVE_ID.to_string())
+        );
+        assert_eq!(
+            resolver.parse(&format!("https://example.com/{ARWEAVE_ID}")),
+            ContentUri::Http(format!("https://example.com/{ARWEAVE_ID}"))
+        );
+        assert_eq!(
+            resolver.parse("https://example.com/ipfs/not-a-cid"),
+            ContentUri::Http("https://example.com/ipfs/not-a-cid".to_string())
+        );
+    }
+
+    #[test]
+    fn test_candidates() {
+        let resolver = test_resolver(&["https://a.example/", "https://b.example"]);
+
+        assert_eq!(
+            resolver.candidates(&format!("https://dead.example/ipfs/{CID}")),
+            vec![
+                format!("https://a.example/ipfs/{CID}"),
+                format!("https://b.example/ipfs/{CID}"),
+                format!("https://dead.example/ipfs/{CID}"),
+            ]
+        );
+        assert_eq!(
+            resolver.candidates(&format!("ar://{ARWEAVE_ID}")),
+            vec![
+                format!("https://a.example/{ARWEAVE_ID}"),
+                format!("https://b.example/{ARWEAVE_ID}"),
+            ]
+        );
+        assert_eq!(
+            resolver.candidates("https://example.com/1.png"),
+            vec!["https://example.com/1.png".to_string()]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_fetch_falls_back_to_matching_gateway() -> anyhow::Result<()> {
+        let base = serve("hello").await?;
+        let resolver = test_resolver(&[
+            &format!("{base}/missing"),
+            &format!("{base}/bad"),
+            &format!("{base}/good"),
+        ]);
+
+        let content = resolver
+            .fetch(&format!("ipfs://{CID}"), Some(hash(b"hello")))
+            .await?;
+
+        assert_eq!(content.blob, b"hello");
+        assert_eq!(content.url, format!("{base}/good/ipfs/{CID}"));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_fetch_returns_mismatch() -> anyhow::Result<()> {
+        let base = serve("hello").await?;
+        let resolver = test_resolver(&[&format!("{base}/missing"), &format!("{base}/bad")]);
+
+        let content = resolver
+            .fetch(&format!("ar://{ARWEAVE_ID}"), Some(hash(b"hello")))
+            .await?;
+
+        assert_eq!(content.hash, hash(b"tampered"));
+
+        let resolver = test_resolver(&[&format!("{base}/missing")]);
+
+        assert!(resolver
+            .fetch(&format!("ipfs://{CID}"), None)
+            .await
+            .is_err());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,75 @@
+use std::path::PathBuf;
+
+use anyhow::Result;
+use clap::Parser;
+use sage_api::{ExportFormat, ExportTransactions};
+use sage_client::{Client, SageRpcError};
+
+/// Export the transaction history as CSV or JSON
+#[derive(Debug, Parser)]
+pub struct ExportTransactionsCommand {
+    /// Output file path, the format is based on its extension (prints CSV to stdout if not provided)
+    #[clap(short, long)]
+    output: Option<PathBuf>,
+    /// Export as JSON rather than CSV
+    #[clap(long)]
+    json: bool,
+    /// First block height to include
+    #[clap(long)]
+    start_height: Option<u32>,
+    /// Last block height to include
+    #[clap(long)]
+    end_height: Option<u32>,
+    /// Earliest block timestamp to include, in seconds since the UNIX epoch
+    #[clap(long)]
+    start_timestamp: Option<u64>,
+    /// Latest block timestamp to include, in seconds since the UNIX epoch
+    #[clap(long)]
+    end_timestamp: Option<u64>,
+}
+
+impl ExportTransactionsCommand {
+    pub async fn handle(self) -> Result<()> {
+        let is_json = self.json
+            || self.output.as_ref().is_some_and(|path| {
+                path.extension()
+                    .is_some_and(|extension| extension.eq_ignore_ascii_case("json"))
+            });
+
+        let client = Client::new()?;
+
+        let response = match client
+            .export_transactions(ExportTransactions {
+                format: if is_json {
+                    ExportFormat::Json
+                } else {
+                    ExportFormat::Csv
+                },
+                start_height: self.start_height,
+                end_height: self.end_height,
+                start_timestamp: self.start_timestamp,
+                end_timestamp: self.end_timestamp,
+            })
+            .await
+        {
+            Ok(response) => response,
+            Err(SageRpcError::Api(_, message)) => {
+                eprintln!("{message}");
+                return Ok(());
+            }
+            Err(error) => {
+                eprintln!("{error}");
+                return Ok(());
+            }
+        };
+
+        if let Some(path) = self.output {
+            std::fs::write(&path, response.data)?;
+            eprintln!("Exported {} rows to {}", response.count, path.display());
+        } else {
+            print!("{}", response.data);
+        }
+
+        Ok(())
+    }
+}
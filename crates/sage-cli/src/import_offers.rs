@@ -0,0 +1,153 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+use clap::Parser;
+use sage_api::{ImportOffers, ImportOffersFromUrl, ImportOffersResponse};
+use sage_client::Client;
+
+use crate::rpc::handle;
+
+/// Import offers from offer files, directories of offer files, or URLs
+#[derive(Debug, Parser)]
+pub struct ImportOffersCommand {
+    /// Files containing one or more offers on separate lines, directories of `.offer` files, or
+    /// HTTP(S), IPFS, IPNS or Arweave URLs to download offers from
+    #[clap(required = true)]
+    sources: Vec<String>,
+    /// Check that the offered coins are unspent and the offers haven't expired
+    #[clap(long)]
+    verify: bool,
+}
+
+impl ImportOffersCommand {
+    pub async fn handle(self) -> Result<()> {
+        let mut offers = Vec::new();
+        let mut urls = Vec::new();
+
+        for source in self.sources {
+            if is_url(&source) {
+                urls.push(source);
+            } else {
+                read_offers(Path::new(&source), &mut offers)?;
+            }
+        }
+
+        let client = Client::new()?;
+
+        let mut results = Vec::new();
+
+        if !offers.is_empty() {
+            match client
+                .import_offers(ImportOffers {
+                    offers,
+                    verify: self.verify,
+                })
+                .await
+            {
+                Ok(response) => results.extend(response.results),
+                Err(error) => {
+                    handle::<()>(Err(error));
+                    return Ok(());
+                }
+            }
+        }
+
+        for url in urls {
+            match client
+                .import_offers_from_url(ImportOffersFromUrl {
+                    url,
+                    verify: self.verify,
+                })
+                .await
+            {
+                Ok(response) => results.extend(response.results),
+                Err(error) => {
+                    handle::<()>(Err(error));
+                    return Ok(());
+                }
+            }
+        }
+
+        handle(Ok(ImportOffersResponse { results }));
+
+        Ok(())
+    }
+}
+
+fn is_url(source: &str) -> bool {
+    ["http://", "https://", "ipfs://", "ipns://", "ar://"]
+        .iter()
+        .any(|scheme| source.starts_with(scheme))
+}
+
+/// Reads the offers in a file, one per line, or in each `.offer` file of a directory.
+fn read_offers(path: &Path, offers: &mut Vec<String>) -> Result<()> {
+    if !path.is_dir() {
+        let data = std::fs::read_to_string(path)?;
+
+        offers.extend(
+            data.lines()
+                .map(str::trim)
+                .filter(|line| !line.is_empty())
+                .map(ToString::to_string),
+        );
+
+        return Ok(());
+    }
+
+    let mut paths = std::fs::read_dir(path)?
+        .map(|entry| Ok(entry?.path()))
+        .collect::<Result<Vec<PathBuf>>>()?;
+
+    paths.sort();
+
+    for path in paths {
+        if path
+            .extension()
+            .is_some_and(|extension| extension.eq_ignore_ascii_case("offer"))
+        {
+            read_offers(&path, offers)?;
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_offers() -> Result<()> {
+        let dir = std::env::temp_dir().join(format!("sage-import-offers-{}", std::process::id()));
+        let offers_dir = dir.join("offers");
+        std::fs::create_dir_all(&offers_dir)?;
+
+        let file = dir.join("offers.txt");
+        std::fs::write(&file, "offer1abc\r\n\n  offer1def  \n")?;
+        std::fs::write(offers_dir.join("b.offer"), "offer1bad")?;
+        std::fs::write(offers_dir.join("a.OFFER"), "offer1ghi\n")?;
+        std::fs::write(offers_dir.join("notes.txt"), "offer1ignored")?;
+
+        let mut offers = Vec::new();
+        read_offers(&file, &mut offers)?;
+        assert_eq!(offers, vec!["offer1abc", "offer1def"]);
+
+        // Only `.offer` files are read from directories, in order of their names
+        let mut offers = Vec::new();
+        read_offers(&offers_dir, &mut offers)?;
+        assert_eq!(offers, vec!["offer1ghi", "offer1bad"]);
+
+        std::fs::remove_dir_all(&dir)?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_is_url() {
+        assert!(is_url("https://example.com/offers.txt"));
+        assert!(is_url("ipfs://bafy/offers.txt"));
+        assert!(is_url("ar://abc"));
+        assert!(!is_url("offers/http.offer"));
+    }
+}
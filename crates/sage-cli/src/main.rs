@@ -1,7 +1,17 @@
+mod export;
+mod import_offers;
+mod mint_job;
+mod payout;
+mod recover_did;
 mod rpc;
 
 use anyhow::Result;
 use clap::Parser;
+use export::ExportTransactionsCommand;
+use import_offers::ImportOffersCommand;
+use mint_job::MintJobCommand;
+use payout::PayoutCommand;
+use recover_did::RecoverDidCommand;
 use rpc::RpcCommand;
 use rustls::crypto::aws_lc_rs::default_provider;
 
@@ -17,6 +27,11 @@ enum Command {
         #[clap(subcommand)]
         command: RpcCommand,
     },
+    Payout(PayoutCommand),
+    ExportTransactions(ExportTransactionsCommand),
+    ImportOffers(ImportOffersCommand),
+    RecoverDid(RecoverDidCommand),
+    MintJob(MintJobCommand),
 }
 
 #[tokio::main]
@@ -33,6 +48,11 @@ async fn main() -> Result<()> {
 
     match args.command {
         Command::Rpc { command } => command.handle(path).await?,
+        Command::Payout(command) => command.handle().await?,
+        Command::ExportTransactions(command) => command.handle().await?,
+        Command::ImportOffers(command) => command.handle().await?,
+        Command::RecoverDid(command) => command.handle().await?,
+        Command::MintJob(command) => command.handle().await?,
     }
 
     Ok(())
@@ -0,0 +1,71 @@
+use std::path::PathBuf;
+
+use anyhow::Result;
+use clap::Parser;
+use sage_api::{Amount, CreateMintJob, MintJobFormat};
+use sage_client::Client;
+
+use crate::rpc::handle;
+
+/// Create a mint job from a CSV or JSON manifest of NFTs
+#[derive(Debug, Parser)]
+pub struct MintJobCommand {
+    /// Path to the manifest, the format is based on its extension
+    file: PathBuf,
+    /// DID ID to mint the NFTs from
+    #[clap(short, long)]
+    did_id: String,
+    /// Display name for the mint job, defaults to the file name
+    #[clap(short, long)]
+    name: Option<String>,
+    /// Fee paid on each transaction, in mojos
+    #[clap(short, long, default_value_t = 0)]
+    fee: u64,
+    /// Maximum cost of each transaction
+    #[clap(long)]
+    max_cost: Option<u64>,
+    /// Trust the hashes in the manifest instead of checking them against the URIs
+    #[clap(long)]
+    skip_hash_verification: bool,
+}
+
+impl MintJobCommand {
+    pub async fn handle(self) -> Result<()> {
+        let format = if self
+            .file
+            .extension()
+            .is_some_and(|extension| extension.eq_ignore_ascii_case("json"))
+        {
+            MintJobFormat::Json
+        } else {
+            MintJobFormat::Csv
+        };
+
+        let name = self.name.unwrap_or_else(|| {
+            self.file
+                .file_stem()
+                .map(|stem| stem.to_string_lossy().to_string())
+                .unwrap_or_default()
+        });
+
+        let data = std::fs::read_to_string(&self.file)?;
+
+        let client = Client::new()?;
+
+        handle(
+            client
+                .create_mint_job(CreateMintJob {
+                    name,
+                    did_id: self.did_id,
+                    format,
+                    data,
+                    fee: Amount::u64(self.fee),
+                    max_cost: self.max_cost,
+                    verify_hashes: !self.skip_hash_verification,
+                })
+                .await,
+        );
+
+        Ok(())
+    }
+}
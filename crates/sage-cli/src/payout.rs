@@ -0,0 +1,63 @@
+use std::path::PathBuf;
+
+use anyhow::Result;
+use clap::Parser;
+use sage_api::{Amount, CreatePayout, PayoutFormat};
+use sage_client::Client;
+
+use crate::rpc::handle;
+
+/// Create a payout from a CSV or JSON file of payments
+#[derive(Debug, Parser)]
+pub struct PayoutCommand {
+    /// Path to the payment file, the format is based on its extension
+    file: PathBuf,
+    /// Display name for the payout, defaults to the file name
+    #[clap(short, long)]
+    name: Option<String>,
+    /// Fee paid on each transaction, in mojos
+    #[clap(short, long, default_value_t = 0)]
+    fee: u64,
+    /// Maximum cost of each transaction
+    #[clap(long)]
+    max_cost: Option<u64>,
+}
+
+impl PayoutCommand {
+    pub async fn handle(self) -> Result<()> {
+        let format = if self
+            .file
+            .extension()
+            .is_some_and(|extension| extension.eq_ignore_ascii_case("json"))
+        {
+            PayoutFormat::Json
+        } else {
+            PayoutFormat::Csv
+        };
+
+        let name = self.name.unwrap_or_else(|| {
+            self.file
+                .file_stem()
+                .map(|stem| stem.to_string_lossy().to_string())
+                .unwrap_or_default()
+        });
+
+        let data = std::fs::read_to_string(&self.file)?;
+
+        let client = Client::new()?;
+
+        handle(
+            client
+                .create_payout(CreatePayout {
+                    name,
+                    format,
+                    data,
+                    fee: Amount::u64(self.fee),
+                    max_cost: self.max_cost,
+                })
+                .await,
+        );
+
+        Ok(())
+    }
+}
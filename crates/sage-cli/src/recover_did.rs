@@ -0,0 +1,65 @@
+use std::path::Path;
+
+use anyhow::Result;
+use clap::Parser;
+use sage_api::{Amount, RecoverDid};
+use sage_client::Client;
+
+use crate::rpc::handle;
+
+/// Recover a DID with attestations from the DIDs in its recovery list
+#[derive(Debug, Parser)]
+pub struct RecoverDidCommand {
+    /// DID ID to recover
+    did_id: String,
+    /// Public key the attestations approve, which must belong to the wallet
+    #[clap(long)]
+    public_key: String,
+    /// Recovery DIDs, in the order they were set
+    #[clap(long = "recovery-did", required = true)]
+    recovery_did_ids: Vec<String>,
+    /// Files containing an attestation, or the hex encoded attestations themselves
+    #[clap(required = true)]
+    attestations: Vec<String>,
+    /// Transaction fee, in mojos
+    #[clap(short, long, default_value_t = 0)]
+    fee: u64,
+    /// Submit the transaction instead of only returning it
+    #[clap(long)]
+    auto_submit: bool,
+}
+
+impl RecoverDidCommand {
+    pub async fn handle(self) -> Result<()> {
+        let attestations = self
+            .attestations
+            .into_iter()
+            .map(|source| {
+                let path = Path::new(&source);
+
+                if path.is_file() {
+                    Ok(std::fs::read_to_string(path)?.trim().to_string())
+                } else {
+                    Ok(source)
+                }
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let client = Client::new()?;
+
+        handle(
+            client
+                .recover_did(RecoverDid {
+                    did_id: self.did_id,
+                    recovery_did_ids: self.recovery_did_ids,
+                    attestations,
+                    public_key: self.public_key,
+                    fee: Amount::u64(self.fee),
+                    auto_submit: self.auto_submit,
+                })
+                .await,
+        );
+
+        Ok(())
+    }
+}
@@ -54,7 +54,7 @@ impl_endpoints! {
     }
 }
 
-fn handle<T: Serialize>(result: Result<T, SageRpcError>) {
+pub(crate) fn handle<T: Serialize>(result: Result<T, SageRpcError>) {
     match result {
         Ok(result) => println!(
             "{}",
@@ -8,6 +8,8 @@ pub struct Config {
     pub global: GlobalConfig,
     pub network: NetworkConfig,
     pub rpc: RpcConfig,
+    pub exchanges: Vec<ExchangeConfig>,
+    pub gateways: GatewayConfig,
 }
 
 impl Default for Config {
@@ -17,6 +19,8 @@ impl Default for Config {
             global: GlobalConfig::default(),
             network: NetworkConfig::default(),
             rpc: RpcConfig::default(),
+            exchanges: default_exchanges(),
+            gateways: GatewayConfig::default(),
         }
     }
 }
@@ -70,3 +74,67 @@ impl Default for RpcConfig {
         }
     }
 }
+
+/// A service that offers can be published to and fetched from.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Type)]
+pub struct ExchangeConfig {
+    pub name: String,
+    pub kind: ExchangeKind,
+    pub base_url: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum ExchangeKind {
+    /// Offers are shared by short codes, as with offercodes.chia.net.
+    OfferCode,
+    /// Offers are posted to, searched and fetched by id from a Dexie compatible API.
+    Dexie,
+    /// Offers are posted to and searched at `/offers`, and fetched from `/offers/{code}`.
+    Http,
+}
+
+pub fn default_exchanges() -> Vec<ExchangeConfig> {
+    vec![
+        ExchangeConfig {
+            name: "offercodes".to_string(),
+            kind: ExchangeKind::OfferCode,
+            base_url: "https://offercodes.chia.net".to_string(),
+        },
+        ExchangeConfig {
+            name: "dexie".to_string(),
+            kind: ExchangeKind::Dexie,
+            base_url: "https://api.dexie.space".to_string(),
+        },
+    ]
+}
+
+/// HTTP gateways used to fetch content-addressed URIs, in the order they are tried. Each entry
+/// is a base URL, such that IPFS content is fetched from `{base}/ipfs/{cid}`, IPNS names from
+/// `{base}/ipns/{name}` and Arweave transactions from `{base}/{id}`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(default)]
+pub struct GatewayConfig {
+    pub ipfs: Vec<String>,
+    pub ipns: Vec<String>,
+    pub arweave: Vec<String>,
+}
+
+impl Default for GatewayConfig {
+    fn default() -> Self {
+        let ipfs = vec![
+            "https://ipfs.io".to_string(),
+            "https://dweb.link".to_string(),
+            "https://w3s.link".to_string(),
+        ];
+
+        Self {
+            ipns: ipfs.clone(),
+            ipfs,
+            arweave: vec![
+                "https://arweave.net".to_string(),
+                "https://ar-io.net".to_string(),
+            ],
+        }
+    }
+}
@@ -7,8 +7,8 @@ use serde_with::{hex::Hex, serde_as};
 use specta::Type;
 
 use crate::{
-    Config, GlobalConfig, InheritedNetwork, Network, NetworkConfig, NetworkList, RpcConfig, Wallet,
-    WalletConfig, WalletDefaults,
+    default_exchanges, Config, GatewayConfig, GlobalConfig, InheritedNetwork, Network,
+    NetworkConfig, NetworkList, RpcConfig, Wallet, WalletConfig, WalletDefaults,
 };
 
 #[derive(Debug, Clone, PartialEq, Eq, Deserialize, Type)]
@@ -140,6 +140,8 @@ pub fn migrate_config(old: OldConfig) -> Result<(Config, WalletConfig), ParseInt
             enabled: old.rpc.run_on_startup,
             port: old.rpc.server_port,
         },
+        exchanges: default_exchanges(),
+        gateways: GatewayConfig::default(),
     };
 
     let mut wallet_config = WalletConfig {
@@ -155,6 +157,7 @@ pub fn migrate_config(old: OldConfig) -> Result<(Config, WalletConfig), ParseInt
             delta_sync: None,
             emoji: None,
             change_address: None,
+            background_signing: false,
         });
     }
 
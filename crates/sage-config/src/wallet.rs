@@ -31,6 +31,8 @@ pub struct Wallet {
     pub emoji: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub change_address: Option<String>,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub background_signing: bool,
 }
 
 impl Wallet {
@@ -48,6 +50,7 @@ impl Default for Wallet {
             delta_sync: None,
             emoji: None,
             change_address: None,
+            background_signing: false,
         }
     }
 }
@@ -68,6 +71,7 @@ mod tests {
             change_address: Some(
                 "xch1dtfukqqka3ftqtdlhmc5spc5vd44h7ejrtnjcewxlueam5yrnnqqyczg8t".to_string(),
             ),
+            background_signing: false,
         }
     }
 
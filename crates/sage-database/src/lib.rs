@@ -1,4 +1,5 @@
 mod maintenance;
+mod media_store;
 mod serialized_primitives;
 mod tables;
 mod utils;
@@ -7,27 +8,47 @@ pub use maintenance::*;
 pub use serialized_primitives::*;
 pub use tables::*;
 
+pub(crate) use media_store::*;
 pub(crate) use utils::*;
 
-use std::num::TryFromIntError;
+#[cfg(test)]
+mod test;
 
+#[cfg(test)]
+pub(crate) use test::*;
+
+use std::{io, num::TryFromIntError, path::PathBuf};
+
+use chia::protocol::Bytes32;
 use sqlx::{Sqlite, SqlitePool, Transaction as SqliteTransaction};
 use thiserror::Error;
-use tracing::info;
+use tracing::{info, warn};
 
 #[derive(Debug, Clone)]
 pub struct Database {
     pub(crate) pool: SqlitePool,
+    pub(crate) media_dir: Option<PathBuf>,
 }
 
 impl Database {
     pub fn new(pool: SqlitePool) -> Self {
-        Self { pool }
+        Self {
+            pool,
+            media_dir: None,
+        }
+    }
+
+    /// Sets the directory that file data is stored in when the media cache policy keeps it
+    /// outside of the database.
+    #[must_use]
+    pub fn with_media_dir(mut self, media_dir: PathBuf) -> Self {
+        self.media_dir = Some(media_dir);
+        self
     }
 
     pub async fn tx(&self) -> Result<DatabaseTx<'_>> {
         let tx = self.pool.begin().await?;
-        Ok(DatabaseTx::new(tx))
+        Ok(DatabaseTx::new(tx, self.media_dir.clone()))
     }
 
     pub async fn run_rust_migrations(&self, ticker: String) -> Result<()> {
@@ -55,15 +76,31 @@ impl Database {
 #[derive(Debug)]
 pub struct DatabaseTx<'a> {
     pub(crate) tx: SqliteTransaction<'a, Sqlite>,
+    pub(crate) media_dir: Option<PathBuf>,
+    pub(crate) removed_media: Vec<Bytes32>,
 }
 
 impl<'a> DatabaseTx<'a> {
-    pub fn new(tx: SqliteTransaction<'a, Sqlite>) -> Self {
-        Self { tx }
+    pub fn new(tx: SqliteTransaction<'a, Sqlite>, media_dir: Option<PathBuf>) -> Self {
+        Self {
+            tx,
+            media_dir,
+            removed_media: Vec::new(),
+        }
     }
 
+    /// Commits the transaction, and only then removes the files of any data it deleted from the
+    /// media directory, so that rolling back never leaves a file without its data.
     pub async fn commit(self) -> Result<()> {
-        Ok(self.tx.commit().await?)
+        self.tx.commit().await?;
+
+        for hash in self.removed_media {
+            if let Err(error) = remove_media(self.media_dir.as_deref(), hash).await {
+                warn!("Failed to remove the data of file {hash} from the media directory: {error}");
+            }
+        }
+
+        Ok(())
     }
 
     pub async fn rollback(self) -> Result<()> {
@@ -101,6 +138,9 @@ pub enum DatabaseError {
     #[error("BLS error: {0}")]
     Bls(#[from] chia::bls::Error),
 
+    #[error("Streamable error: {0}")]
+    Streamable(#[from] chia::traits::Error),
+
     #[error("Invalid enum variant")]
     InvalidEnumVariant,
 
@@ -112,6 +152,9 @@ pub enum DatabaseError {
 
     #[error("Public key not found for puzzle hash")]
     PublicKeyNotFound,
+
+    #[error("IO error: {0}")]
+    Io(#[from] io::Error),
 }
 
 pub(crate) type Result<T> = std::result::Result<T, DatabaseError>;
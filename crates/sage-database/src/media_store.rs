@@ -0,0 +1,96 @@
+use std::{
+    io::ErrorKind,
+    path::{Path, PathBuf},
+};
+
+use chia::protocol::Bytes32;
+use tempfile::NamedTempFile;
+use tokio::{fs, io::AsyncWriteExt};
+
+use crate::Result;
+
+/// Files are stored by hash, in a subdirectory named after the first byte of the hash so that
+/// no single directory gets too large.
+fn media_path(dir: &Path, hash: Bytes32) -> PathBuf {
+    let hash = hex::encode(hash);
+    dir.join(&hash[..2]).join(hash)
+}
+
+pub(crate) async fn read_media(dir: Option<&Path>, hash: Bytes32) -> Result<Option<Vec<u8>>> {
+    let Some(dir) = dir else {
+        return Ok(None);
+    };
+
+    match fs::read(media_path(dir, hash)).await {
+        Ok(data) => Ok(Some(data)),
+        Err(error) if error.kind() == ErrorKind::NotFound => Ok(None),
+        Err(error) => Err(error.into()),
+    }
+}
+
+pub(crate) async fn write_media(dir: &Path, hash: Bytes32, data: &[u8]) -> Result<()> {
+    let path = media_path(dir, hash);
+    let parent = path.parent().unwrap_or(dir);
+
+    fs::create_dir_all(parent).await?;
+
+    // The data is written to a temporary file first, so that a partially written file is never
+    // mistaken for the real one. Each writer gets its own temporary file, which is removed if
+    // the write fails, since the same file can be written by more than one download at a time.
+    let (file, temp_path) = NamedTempFile::new_in(parent)?.into_parts();
+
+    let mut file = fs::File::from_std(file);
+    file.write_all(data).await?;
+    file.flush().await?;
+    drop(file);
+
+    temp_path.persist(&path).map_err(|error| error.error)?;
+
+    Ok(())
+}
+
+pub(crate) async fn remove_media(dir: Option<&Path>, hash: Bytes32) -> Result<()> {
+    let Some(dir) = dir else {
+        return Ok(());
+    };
+
+    match fs::remove_file(media_path(dir, hash)).await {
+        Ok(()) => Ok(()),
+        Err(error) if error.kind() == ErrorKind::NotFound => Ok(()),
+        Err(error) => Err(error.into()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_concurrent_writes() -> anyhow::Result<()> {
+        let dir = std::env::temp_dir().join(format!("sage-media-store-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+
+        let hash = Bytes32::new([1; 32]);
+        let data = vec![42; 1024 * 1024];
+
+        tokio::try_join!(
+            write_media(&dir, hash, &data),
+            write_media(&dir, hash, &data),
+            write_media(&dir, hash, &data),
+        )?;
+
+        assert_eq!(read_media(Some(&dir), hash).await?, Some(data));
+
+        // Only the file itself is left behind, without any of the temporary files.
+        let path = media_path(&dir, hash);
+        let parent = path.parent().expect("missing parent directory");
+        assert_eq!(std::fs::read_dir(parent)?.count(), 1);
+
+        remove_media(Some(&dir), hash).await?;
+        assert_eq!(read_media(Some(&dir), hash).await?, None);
+
+        std::fs::remove_dir_all(&dir)?;
+
+        Ok(())
+    }
+}
@@ -2,17 +2,37 @@ mod assets;
 mod blocks;
 mod coins;
 mod collections;
+mod contacts;
 mod files;
+mod media_cache;
 mod mempool_items;
+mod mint_jobs;
+mod offer_groups;
+mod offer_publications;
 mod offers;
 mod p2_puzzles;
+mod payouts;
+mod scheduled_payments;
+mod search_index;
+mod spending_policy;
+mod trades;
 mod transactions;
 
 pub use assets::*;
 pub use coins::*;
 pub use collections::*;
+pub use contacts::*;
 pub use files::*;
+pub use media_cache::*;
 pub use mempool_items::*;
+pub use mint_jobs::*;
+pub use offer_groups::*;
+pub use offer_publications::*;
 pub use offers::*;
 pub use p2_puzzles::*;
+pub use payouts::*;
+pub use scheduled_payments::*;
+pub use search_index::*;
+pub use spending_policy::*;
+pub use trades::*;
 pub use transactions::*;
@@ -76,6 +76,48 @@ impl Database {
         })
         .collect()
     }
+
+    /// The DIDs in a recovery list which was set by the wallet, or an empty list if it's unknown.
+    pub async fn did_recovery_list(&self, hash: Bytes32) -> Result<Vec<Bytes32>> {
+        let hash = hash.as_ref();
+
+        query!(
+            "SELECT did_hash FROM did_recovery_lists WHERE hash = ? ORDER BY position ASC",
+            hash
+        )
+        .fetch_all(&self.pool)
+        .await?
+        .into_iter()
+        .map(|row| row.did_hash.convert())
+        .collect()
+    }
+
+    pub async fn insert_did_recovery_list(&self, hash: Bytes32, dids: &[Bytes32]) -> Result<()> {
+        let hash = hash.as_ref();
+
+        let mut tx = self.pool.begin().await?;
+
+        for (position, did) in dids.iter().enumerate() {
+            let position: i64 = position.try_into()?;
+            let did = did.as_ref();
+
+            query!(
+                "
+                INSERT OR IGNORE INTO did_recovery_lists (hash, position, did_hash)
+                VALUES (?, ?, ?)
+                ",
+                hash,
+                position,
+                did
+            )
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        tx.commit().await?;
+
+        Ok(())
+    }
 }
 
 impl DatabaseTx<'_> {
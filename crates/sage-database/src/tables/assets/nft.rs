@@ -19,6 +19,43 @@ pub enum NftGroupSearch {
     NoOwnerDid,
 }
 
+/// A CHIP-0007 attribute of an NFT. Numeric values are also kept as a number, so that they can
+/// be filtered by range.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NftTrait {
+    pub trait_type: String,
+    pub value: String,
+    pub numeric_value: Option<f64>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum NftTraitFilter {
+    Value {
+        trait_type: String,
+        value: String,
+    },
+    Range {
+        trait_type: String,
+        min: Option<f64>,
+        max: Option<f64>,
+    },
+}
+
+impl NftTraitFilter {
+    pub fn trait_type(&self) -> &str {
+        match self {
+            Self::Value { trait_type, .. } | Self::Range { trait_type, .. } => trait_type,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NftTraitCount {
+    pub trait_type: String,
+    pub value: String,
+    pub count: u32,
+}
+
 #[derive(Debug, Clone)]
 pub struct NftCoinInfo {
     pub collection_hash: Bytes32,
@@ -133,10 +170,12 @@ impl Database {
         .transpose()
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub async fn owned_nfts(
         &self,
         name_search: Option<String>,
         group_search: Option<NftGroupSearch>,
+        trait_filters: &[NftTraitFilter],
         sort_mode: NftSortMode,
         include_hidden: bool,
         limit: u32,
@@ -194,6 +233,61 @@ impl Database {
                 }
             }
         }
+
+        // Filters on the same trait type are alternatives, such as either of two backgrounds,
+        // whereas filters on different trait types must all match.
+        let mut trait_types: Vec<&str> = Vec::new();
+
+        for filter in trait_filters {
+            if !trait_types.contains(&filter.trait_type()) {
+                trait_types.push(filter.trait_type());
+            }
+        }
+
+        for trait_type in trait_types {
+            query.push(
+                " AND EXISTS (
+                    SELECT 1 FROM nft_traits
+                    WHERE nft_traits.asset_id = owned_nfts.asset_id AND nft_traits.trait_type = ",
+            );
+            query.push_bind(trait_type.to_string());
+            query.push(" AND (");
+
+            let filters = trait_filters
+                .iter()
+                .filter(|filter| filter.trait_type() == trait_type);
+
+            for (i, filter) in filters.enumerate() {
+                if i > 0 {
+                    query.push(" OR ");
+                }
+
+                match filter {
+                    NftTraitFilter::Value { value, .. } => {
+                        query.push("nft_traits.value = ");
+                        query.push_bind(value.clone());
+                    }
+                    NftTraitFilter::Range { min, max, .. } => {
+                        query.push("(nft_traits.numeric_value IS NOT NULL");
+
+                        if let Some(min) = min {
+                            query.push(" AND nft_traits.numeric_value >= ");
+                            query.push_bind(*min);
+                        }
+
+                        if let Some(max) = max {
+                            query.push(" AND nft_traits.numeric_value <= ");
+                            query.push_bind(*max);
+                        }
+
+                        query.push(")");
+                    }
+                }
+            }
+
+            query.push("))");
+        }
+
         // Add ORDER BY clause based on sort_mode
         query.push(" ORDER BY ");
 
@@ -322,6 +416,57 @@ impl Database {
         Ok((dids, total_count))
     }
 
+    /// The number of owned NFTs in a collection with each trait, along with the total number of
+    /// owned NFTs in the collection, which is enough to work out how rare each of them is.
+    pub async fn collection_trait_counts(
+        &self,
+        collection_id: Bytes32,
+    ) -> Result<(Vec<NftTraitCount>, u32)> {
+        let collection_id = collection_id.as_ref();
+
+        let rows = query!(
+            "
+            SELECT nft_traits.trait_type, nft_traits.value, COUNT(DISTINCT nft_traits.asset_id) AS \"count!: i64\"
+            FROM owned_nfts
+            INNER JOIN collections ON collections.id = owned_nfts.collection_id
+            INNER JOIN nft_traits ON nft_traits.asset_id = owned_nfts.asset_id
+            WHERE collections.hash = ?
+            GROUP BY nft_traits.trait_type, nft_traits.value
+            ORDER BY nft_traits.trait_type ASC, COUNT(DISTINCT nft_traits.asset_id) DESC, nft_traits.value ASC
+            ",
+            collection_id
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        let total = query!(
+            "
+            SELECT COUNT(DISTINCT owned_nfts.asset_id) AS total
+            FROM owned_nfts
+            INNER JOIN collections ON collections.id = owned_nfts.collection_id
+            WHERE collections.hash = ?
+            ",
+            collection_id
+        )
+        .fetch_one(&self.pool)
+        .await?
+        .total
+        .convert()?;
+
+        let counts = rows
+            .into_iter()
+            .map(|row| {
+                Ok(NftTraitCount {
+                    trait_type: row.trait_type,
+                    value: row.value,
+                    count: row.count.convert()?,
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok((counts, total))
+    }
+
     pub async fn offer_nft_info(&self, hash: Bytes32) -> Result<Option<NftOfferInfo>> {
         let hash = hash.as_ref();
 
@@ -477,6 +622,35 @@ impl DatabaseTx<'_> {
         Ok(())
     }
 
+    /// Replaces the traits of an NFT with the ones from its latest metadata.
+    pub async fn set_nft_traits(&mut self, hash: Bytes32, traits: &[NftTrait]) -> Result<()> {
+        let hash = hash.as_ref();
+
+        query!(
+            "DELETE FROM nft_traits WHERE asset_id = (SELECT id FROM assets WHERE hash = ?)",
+            hash
+        )
+        .execute(&mut *self.tx)
+        .await?;
+
+        for nft_trait in traits {
+            query!(
+                "
+                INSERT INTO nft_traits (asset_id, trait_type, value, numeric_value)
+                VALUES ((SELECT id FROM assets WHERE hash = ?), ?, ?, ?)
+                ",
+                hash,
+                nft_trait.trait_type,
+                nft_trait.value,
+                nft_trait.numeric_value
+            )
+            .execute(&mut *self.tx)
+            .await?;
+        }
+
+        Ok(())
+    }
+
     pub async fn update_nft_metadata(
         &mut self,
         hash: Bytes32,
@@ -515,3 +689,268 @@ impl DatabaseTx<'_> {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use chia::{bls::PublicKey, protocol::CoinState};
+
+    use crate::{test_database, CollectionRow, Derivation};
+
+    use super::*;
+
+    const P2_PUZZLE_HASH: Bytes32 = Bytes32::new([1; 32]);
+
+    fn value(trait_type: &str, value: &str) -> NftTrait {
+        NftTrait {
+            trait_type: trait_type.to_string(),
+            value: value.to_string(),
+            numeric_value: value.parse().ok(),
+        }
+    }
+
+    fn value_filter(trait_type: &str, value: &str) -> NftTraitFilter {
+        NftTraitFilter::Value {
+            trait_type: trait_type.to_string(),
+            value: value.to_string(),
+        }
+    }
+
+    fn count(trait_type: &str, value: &str, count: u32) -> NftTraitCount {
+        NftTraitCount {
+            trait_type: trait_type.to_string(),
+            value: value.to_string(),
+            count,
+        }
+    }
+
+    async fn insert_nft(
+        tx: &mut DatabaseTx<'_>,
+        name: &str,
+        collection_hash: Bytes32,
+        traits: &[NftTrait],
+    ) -> anyhow::Result<()> {
+        let hash = Bytes32::new([name.as_bytes()[0]; 32]);
+
+        tx.insert_asset(Asset {
+            hash,
+            name: Some(name.to_string()),
+            ticker: None,
+            precision: 1,
+            icon_url: None,
+            description: None,
+            is_sensitive_content: false,
+            is_visible: true,
+            hidden_puzzle_hash: None,
+            kind: AssetKind::Nft,
+        })
+        .await?;
+
+        tx.insert_nft(
+            hash,
+            &NftCoinInfo {
+                collection_hash,
+                collection_name: None,
+                minter_hash: None,
+                owner_hash: None,
+                metadata: Program::default(),
+                metadata_updater_puzzle_hash: Bytes32::default(),
+                royalty_puzzle_hash: Bytes32::default(),
+                royalty_basis_points: 0,
+                data_hash: None,
+                metadata_hash: None,
+                license_hash: None,
+                edition_number: None,
+                edition_total: None,
+            },
+        )
+        .await?;
+
+        let coin = Coin::new(hash, P2_PUZZLE_HASH, 1);
+        tx.insert_coin(CoinState::new(coin, None, None)).await?;
+        tx.update_coin(coin.coin_id(), hash, P2_PUZZLE_HASH).await?;
+        tx.set_nft_traits(hash, traits).await?;
+
+        Ok(())
+    }
+
+    async fn filtered_names(
+        db: &Database,
+        filters: &[NftTraitFilter],
+    ) -> anyhow::Result<Vec<String>> {
+        let (nfts, total) = db
+            .owned_nfts(None, None, filters, NftSortMode::Name, true, 100, 0)
+            .await?;
+
+        assert_eq!(nfts.len(), total as usize);
+
+        Ok(nfts.into_iter().filter_map(|nft| nft.asset.name).collect())
+    }
+
+    #[tokio::test]
+    async fn test_nft_traits() -> anyhow::Result<()> {
+        let db = test_database().await?;
+
+        let collection = Bytes32::new([2; 32]);
+        let other_collection = Bytes32::new([3; 32]);
+
+        let mut tx = db.tx().await?;
+
+        tx.insert_custody_p2_puzzle(
+            P2_PUZZLE_HASH,
+            PublicKey::default(),
+            Derivation {
+                derivation_index: 0,
+                is_hardened: false,
+            },
+        )
+        .await?;
+
+        for hash in [collection, other_collection] {
+            tx.insert_collection(CollectionRow {
+                hash,
+                uuid: hex::encode(hash),
+                minter_hash: Bytes32::default(),
+                name: None,
+                icon_url: None,
+                banner_url: None,
+                description: None,
+                is_visible: true,
+            })
+            .await?;
+        }
+
+        let red = [value("Background", "Red"), value("Level", "5")];
+        let blue = [value("Background", "Blue"), value("Level", "10")];
+        let green = [value("Background", "Green"), value("Hat", "Cap")];
+
+        insert_nft(&mut tx, "A", collection, &red).await?;
+        insert_nft(&mut tx, "B", collection, &blue).await?;
+        insert_nft(&mut tx, "C", collection, &green).await?;
+        insert_nft(&mut tx, "D", collection, &[]).await?;
+        insert_nft(
+            &mut tx,
+            "E",
+            other_collection,
+            &[value("Background", "Red")],
+        )
+        .await?;
+
+        tx.commit().await?;
+
+        assert_eq!(filtered_names(&db, &[]).await?, ["A", "B", "C", "D", "E"]);
+        assert_eq!(
+            filtered_names(&db, &[value_filter("Background", "Red")]).await?,
+            ["A", "E"]
+        );
+
+        // Either of the values of a trait type can match
+        assert_eq!(
+            filtered_names(
+                &db,
+                &[
+                    value_filter("Background", "Red"),
+                    value_filter("Background", "Blue")
+                ]
+            )
+            .await?,
+            ["A", "B", "E"]
+        );
+
+        // But each of the trait types has to match
+        assert_eq!(
+            filtered_names(
+                &db,
+                &[
+                    value_filter("Background", "Red"),
+                    value_filter("Background", "Blue"),
+                    NftTraitFilter::Range {
+                        trait_type: "Level".to_string(),
+                        min: Some(6.0),
+                        max: None,
+                    },
+                ]
+            )
+            .await?,
+            ["B"]
+        );
+        assert_eq!(
+            filtered_names(
+                &db,
+                &[NftTraitFilter::Range {
+                    trait_type: "Level".to_string(),
+                    min: Some(1.0),
+                    max: Some(5.0),
+                }]
+            )
+            .await?,
+            ["A"]
+        );
+
+        // Values are only matched within their own trait type
+        assert!(filtered_names(&db, &[value_filter("Background", "Cap")])
+            .await?
+            .is_empty());
+        assert!(filtered_names(&db, &[value_filter("Mouth", "Smile")])
+            .await?
+            .is_empty());
+
+        let (nfts, _) = db
+            .owned_nfts(
+                None,
+                Some(NftGroupSearch::Collection(collection)),
+                &[value_filter("Background", "Red")],
+                NftSortMode::Name,
+                true,
+                100,
+                0,
+            )
+            .await?;
+        assert_eq!(nfts.len(), 1);
+        assert_eq!(nfts[0].asset.name.as_deref(), Some("A"));
+
+        // NFTs without traits are still counted towards the total of the collection
+        assert_eq!(
+            db.collection_trait_counts(collection).await?,
+            (
+                vec![
+                    count("Background", "Blue", 1),
+                    count("Background", "Green", 1),
+                    count("Background", "Red", 1),
+                    count("Hat", "Cap", 1),
+                    count("Level", "10", 1),
+                    count("Level", "5", 1),
+                ],
+                4
+            )
+        );
+
+        // Traits are replaced when the metadata of an NFT changes
+        let mut tx = db.tx().await?;
+        tx.set_nft_traits(Bytes32::new([b'A'; 32]), &[value("Background", "Blue")])
+            .await?;
+        tx.commit().await?;
+
+        assert_eq!(
+            filtered_names(&db, &[value_filter("Background", "Red")]).await?,
+            ["E"]
+        );
+        assert_eq!(
+            db.collection_trait_counts(collection).await?,
+            (
+                vec![
+                    count("Background", "Blue", 2),
+                    count("Background", "Green", 1),
+                    count("Hat", "Cap", 1),
+                    count("Level", "10", 1),
+                ],
+                4
+            )
+        );
+        assert_eq!(
+            db.collection_trait_counts(other_collection).await?,
+            (vec![count("Background", "Red", 1)], 1)
+        );
+
+        Ok(())
+    }
+}
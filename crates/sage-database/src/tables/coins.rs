@@ -152,6 +152,10 @@ impl Database {
         selectable_cat_coins(&self.pool, asset_id).await
     }
 
+    pub async fn is_coin_spent(&self, coin_id: Bytes32) -> Result<bool> {
+        is_coin_spent(&self.pool, coin_id).await
+    }
+
     pub async fn coin_kind(&self, coin_id: Bytes32) -> Result<Option<CoinKind>> {
         coin_kind(&self.pool, coin_id).await
     }
@@ -247,6 +251,10 @@ impl DatabaseTx<'_> {
         is_known_coin(&mut *self.tx, coin_id).await
     }
 
+    pub async fn coin_children(&mut self, coin_id: Bytes32) -> Result<Vec<Bytes32>> {
+        coin_children(&mut *self.tx, coin_id).await
+    }
+
     pub async fn update_coin(
         &mut self,
         coin_id: Bytes32,
@@ -348,6 +356,33 @@ async fn is_known_coin(conn: impl SqliteExecutor<'_>, coin_id: Bytes32) -> Resul
     Ok(row.count > 0)
 }
 
+async fn is_coin_spent(conn: impl SqliteExecutor<'_>, coin_id: Bytes32) -> Result<bool> {
+    let coin_id_ref = coin_id.as_ref();
+
+    let row = query!(
+        "SELECT COUNT(*) AS count FROM coins WHERE hash = ? AND spent_height IS NOT NULL",
+        coin_id_ref
+    )
+    .fetch_one(conn)
+    .await?;
+
+    Ok(row.count > 0)
+}
+
+async fn coin_children(conn: impl SqliteExecutor<'_>, coin_id: Bytes32) -> Result<Vec<Bytes32>> {
+    let coin_id_ref = coin_id.as_ref();
+
+    query!(
+        "SELECT hash FROM coins WHERE parent_coin_hash = ?",
+        coin_id_ref
+    )
+    .fetch_all(conn)
+    .await?
+    .into_iter()
+    .map(|row| row.hash.convert())
+    .collect()
+}
+
 async fn unsynced_coins(conn: impl SqliteExecutor<'_>, limit: usize) -> Result<Vec<UnsyncedCoin>> {
     let limit = i64::try_from(limit)?;
 
@@ -0,0 +1,402 @@
+use std::collections::HashMap;
+
+use chia::protocol::Bytes32;
+use sqlx::SqliteExecutor;
+
+use crate::{Convert, Database, DatabaseTx, Result};
+
+#[derive(Debug, Clone)]
+pub struct ContactRow {
+    pub id: u32,
+    pub name: String,
+    pub notes: Option<String>,
+    pub did_id: Option<Bytes32>,
+    pub created_timestamp: u64,
+}
+
+#[derive(Debug, Clone)]
+pub struct ContactAddressRow {
+    pub contact_id: u32,
+    pub puzzle_hash: Bytes32,
+    pub label: Option<String>,
+}
+
+impl Database {
+    pub async fn contacts(&self) -> Result<Vec<ContactRow>> {
+        contacts(&self.pool).await
+    }
+
+    pub async fn contact(&self, id: u32) -> Result<Option<ContactRow>> {
+        contact(&self.pool, id).await
+    }
+
+    pub async fn contact_addresses(&self, contact_id: u32) -> Result<Vec<ContactAddressRow>> {
+        contact_addresses(&self.pool, contact_id).await
+    }
+
+    pub async fn all_contact_addresses(&self) -> Result<Vec<ContactAddressRow>> {
+        all_contact_addresses(&self.pool).await
+    }
+
+    pub async fn contact_names(&self) -> Result<HashMap<Bytes32, String>> {
+        contact_names(&self.pool).await
+    }
+
+    pub async fn delete_contact(&self, id: u32) -> Result<()> {
+        delete_contact(&self.pool, id).await
+    }
+
+    pub async fn is_known_puzzle_hash(&self, puzzle_hash: Bytes32) -> Result<bool> {
+        is_known_puzzle_hash(&self.pool, puzzle_hash).await
+    }
+}
+
+impl DatabaseTx<'_> {
+    pub async fn contact(&mut self, id: u32) -> Result<Option<ContactRow>> {
+        contact(&mut *self.tx, id).await
+    }
+
+    pub async fn insert_contact(
+        &mut self,
+        name: String,
+        notes: Option<String>,
+        did_id: Option<Bytes32>,
+    ) -> Result<u32> {
+        insert_contact(&mut *self.tx, name, notes, did_id).await
+    }
+
+    pub async fn update_contact(
+        &mut self,
+        id: u32,
+        name: String,
+        notes: Option<String>,
+        did_id: Option<Bytes32>,
+    ) -> Result<()> {
+        update_contact(&mut *self.tx, id, name, notes, did_id).await
+    }
+
+    pub async fn insert_contact_address(
+        &mut self,
+        contact_id: u32,
+        puzzle_hash: Bytes32,
+        label: Option<String>,
+    ) -> Result<()> {
+        insert_contact_address(&mut *self.tx, contact_id, puzzle_hash, label).await
+    }
+
+    pub async fn delete_contact_addresses(&mut self, contact_id: u32) -> Result<()> {
+        delete_contact_addresses(&mut *self.tx, contact_id).await
+    }
+}
+
+async fn contacts(conn: impl SqliteExecutor<'_>) -> Result<Vec<ContactRow>> {
+    sqlx::query!(
+        "
+        SELECT id, name, notes, did_hash, created_timestamp
+        FROM contacts
+        ORDER BY name ASC, id ASC
+        "
+    )
+    .fetch_all(conn)
+    .await?
+    .into_iter()
+    .map(|row| {
+        Ok(ContactRow {
+            id: row.id.convert()?,
+            name: row.name,
+            notes: row.notes,
+            did_id: row.did_hash.convert()?,
+            created_timestamp: row.created_timestamp.convert()?,
+        })
+    })
+    .collect()
+}
+
+async fn contact(conn: impl SqliteExecutor<'_>, id: u32) -> Result<Option<ContactRow>> {
+    sqlx::query!(
+        "SELECT id, name, notes, did_hash, created_timestamp FROM contacts WHERE id = ?",
+        id
+    )
+    .fetch_optional(conn)
+    .await?
+    .map(|row| {
+        Ok(ContactRow {
+            id: row.id.convert()?,
+            name: row.name,
+            notes: row.notes,
+            did_id: row.did_hash.convert()?,
+            created_timestamp: row.created_timestamp.convert()?,
+        })
+    })
+    .transpose()
+}
+
+async fn contact_addresses(
+    conn: impl SqliteExecutor<'_>,
+    contact_id: u32,
+) -> Result<Vec<ContactAddressRow>> {
+    sqlx::query!(
+        "
+        SELECT contact_id, puzzle_hash, label
+        FROM contact_addresses
+        WHERE contact_id = ?
+        ORDER BY id ASC
+        ",
+        contact_id
+    )
+    .fetch_all(conn)
+    .await?
+    .into_iter()
+    .map(|row| {
+        Ok(ContactAddressRow {
+            contact_id: row.contact_id.convert()?,
+            puzzle_hash: row.puzzle_hash.convert()?,
+            label: row.label,
+        })
+    })
+    .collect()
+}
+
+async fn all_contact_addresses(conn: impl SqliteExecutor<'_>) -> Result<Vec<ContactAddressRow>> {
+    sqlx::query!("SELECT contact_id, puzzle_hash, label FROM contact_addresses ORDER BY id ASC")
+        .fetch_all(conn)
+        .await?
+        .into_iter()
+        .map(|row| {
+            Ok(ContactAddressRow {
+                contact_id: row.contact_id.convert()?,
+                puzzle_hash: row.puzzle_hash.convert()?,
+                label: row.label,
+            })
+        })
+        .collect()
+}
+
+async fn contact_names(conn: impl SqliteExecutor<'_>) -> Result<HashMap<Bytes32, String>> {
+    sqlx::query!(
+        "
+        SELECT contact_addresses.puzzle_hash, contacts.name
+        FROM contact_addresses
+        INNER JOIN contacts ON contacts.id = contact_addresses.contact_id
+        ORDER BY contact_addresses.id ASC
+        "
+    )
+    .fetch_all(conn)
+    .await?
+    .into_iter()
+    .map(|row| Ok((row.puzzle_hash.convert()?, row.name)))
+    .collect()
+}
+
+async fn insert_contact(
+    conn: impl SqliteExecutor<'_>,
+    name: String,
+    notes: Option<String>,
+    did_id: Option<Bytes32>,
+) -> Result<u32> {
+    let did_id = did_id.as_deref();
+
+    sqlx::query_scalar!(
+        "INSERT INTO contacts (name, notes, did_hash) VALUES (?, ?, ?) RETURNING id",
+        name,
+        notes,
+        did_id
+    )
+    .fetch_one(conn)
+    .await?
+    .convert()
+}
+
+async fn update_contact(
+    conn: impl SqliteExecutor<'_>,
+    id: u32,
+    name: String,
+    notes: Option<String>,
+    did_id: Option<Bytes32>,
+) -> Result<()> {
+    let did_id = did_id.as_deref();
+
+    sqlx::query!(
+        "UPDATE contacts SET name = ?, notes = ?, did_hash = ? WHERE id = ?",
+        name,
+        notes,
+        did_id,
+        id
+    )
+    .execute(conn)
+    .await?;
+
+    Ok(())
+}
+
+async fn delete_contact(conn: impl SqliteExecutor<'_>, id: u32) -> Result<()> {
+    sqlx::query!("DELETE FROM contacts WHERE id = ?", id)
+        .execute(conn)
+        .await?;
+
+    Ok(())
+}
+
+async fn insert_contact_address(
+    conn: impl SqliteExecutor<'_>,
+    contact_id: u32,
+    puzzle_hash: Bytes32,
+    label: Option<String>,
+) -> Result<()> {
+    let puzzle_hash = puzzle_hash.as_ref();
+
+    sqlx::query!(
+        "
+        INSERT INTO contact_addresses (contact_id, puzzle_hash, label) VALUES (?, ?, ?)
+        ON CONFLICT (contact_id, puzzle_hash) DO UPDATE SET label = excluded.label
+        ",
+        contact_id,
+        puzzle_hash,
+        label
+    )
+    .execute(conn)
+    .await?;
+
+    Ok(())
+}
+
+async fn delete_contact_addresses(conn: impl SqliteExecutor<'_>, contact_id: u32) -> Result<()> {
+    sqlx::query!(
+        "DELETE FROM contact_addresses WHERE contact_id = ?",
+        contact_id
+    )
+    .execute(conn)
+    .await?;
+
+    Ok(())
+}
+
+async fn is_known_puzzle_hash(conn: impl SqliteExecutor<'_>, puzzle_hash: Bytes32) -> Result<bool> {
+    let puzzle_hash = puzzle_hash.as_ref();
+
+    let row = sqlx::query!(
+        "
+        SELECT EXISTS (SELECT 1 FROM p2_puzzles WHERE hash = ?1)
+            OR EXISTS (SELECT 1 FROM contact_addresses WHERE puzzle_hash = ?1)
+            OR EXISTS (SELECT 1 FROM coins WHERE puzzle_hash = ?1)
+            OR EXISTS (SELECT 1 FROM clawbacks WHERE receiver_puzzle_hash = ?1)
+        AS is_known
+        ",
+        puzzle_hash
+    )
+    .fetch_one(conn)
+    .await?;
+
+    Ok(row.is_known != 0)
+}
+
+#[cfg(test)]
+mod tests {
+    use chia::bls::PublicKey;
+
+    use crate::{test_database, Derivation};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_contacts() -> anyhow::Result<()> {
+        let db = test_database().await?;
+
+        let alice_hash = Bytes32::new([1; 32]);
+        let bob_hash = Bytes32::new([2; 32]);
+
+        let mut tx = db.tx().await?;
+        let bob = tx.insert_contact("Bob".to_string(), None, None).await?;
+        let alice = tx
+            .insert_contact(
+                "Alice".to_string(),
+                Some("Friend".to_string()),
+                Some(Bytes32::new([3; 32])),
+            )
+            .await?;
+        tx.insert_contact_address(alice, alice_hash, None).await?;
+        tx.insert_contact_address(alice, alice_hash, Some("Main".to_string()))
+            .await?;
+        tx.insert_contact_address(bob, bob_hash, None).await?;
+        tx.commit().await?;
+
+        let contacts = db.contacts().await?;
+        assert_eq!(
+            contacts
+                .iter()
+                .map(|contact| contact.name.as_str())
+                .collect::<Vec<_>>(),
+            ["Alice", "Bob"]
+        );
+
+        let row = db.contact(alice).await?.expect("missing contact");
+        assert_eq!(row.notes.as_deref(), Some("Friend"));
+        assert_eq!(row.did_id, Some(Bytes32::new([3; 32])));
+
+        let addresses = db.contact_addresses(alice).await?;
+        assert_eq!(addresses.len(), 1);
+        assert_eq!(addresses[0].puzzle_hash, alice_hash);
+        assert_eq!(addresses[0].label.as_deref(), Some("Main"));
+
+        let names = db.contact_names().await?;
+        assert_eq!(names.get(&alice_hash).map(String::as_str), Some("Alice"));
+        assert_eq!(names.get(&bob_hash).map(String::as_str), Some("Bob"));
+
+        let mut tx = db.tx().await?;
+        tx.update_contact(alice, "Alicia".to_string(), None, None)
+            .await?;
+        tx.delete_contact_addresses(alice).await?;
+        tx.commit().await?;
+
+        let row = db.contact(alice).await?.expect("missing contact");
+        assert_eq!(row.name, "Alicia");
+        assert_eq!(row.notes, None);
+        assert_eq!(row.did_id, None);
+        assert!(db.contact_addresses(alice).await?.is_empty());
+
+        db.delete_contact(bob).await?;
+
+        assert!(db.contact(bob).await?.is_none());
+        assert!(db.all_contact_addresses().await?.is_empty());
+        assert!(db.contact_names().await?.is_empty());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_known_puzzle_hashes() -> anyhow::Result<()> {
+        let db = test_database().await?;
+
+        let contact_hash = Bytes32::new([1; 32]);
+        let custody_hash = Bytes32::new([2; 32]);
+        let unknown_hash = Bytes32::new([3; 32]);
+
+        assert!(!db.is_known_puzzle_hash(contact_hash).await?);
+        assert!(!db.is_known_puzzle_hash(custody_hash).await?);
+
+        let mut tx = db.tx().await?;
+        let contact = tx.insert_contact("Alice".to_string(), None, None).await?;
+        tx.insert_contact_address(contact, contact_hash, None)
+            .await?;
+        tx.insert_custody_p2_puzzle(
+            custody_hash,
+            PublicKey::default(),
+            Derivation {
+                derivation_index: 0,
+                is_hardened: false,
+            },
+        )
+        .await?;
+        tx.commit().await?;
+
+        assert!(db.is_known_puzzle_hash(contact_hash).await?);
+        assert!(db.is_known_puzzle_hash(custody_hash).await?);
+        assert!(!db.is_known_puzzle_hash(unknown_hash).await?);
+
+        db.delete_contact(contact).await?;
+
+        assert!(!db.is_known_puzzle_hash(contact_hash).await?);
+
+        Ok(())
+    }
+}
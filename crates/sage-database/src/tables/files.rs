@@ -1,7 +1,7 @@
 use chia::protocol::Bytes32;
-use sqlx::{query, SqliteExecutor};
+use sqlx::{query, SqliteConnection, SqliteExecutor};
 
-use crate::{Convert, Database, DatabaseTx, Result};
+use crate::{read_media, write_media, Convert, Database, DatabaseError, DatabaseTx, Result};
 
 #[derive(Debug, Clone)]
 pub struct FileUri {
@@ -11,6 +11,34 @@ pub struct FileUri {
     pub failed_attempts: u32,
 }
 
+/// The result of the last check of a URI, where the hash match is only known once it has been
+/// fetched successfully.
+#[derive(Debug, Clone)]
+pub struct FileUriStatus {
+    pub hash: Bytes32,
+    pub uri: String,
+    pub is_hash_match: Option<bool>,
+    pub error: Option<String>,
+    pub last_checked_timestamp: Option<u64>,
+    pub failed_attempts: u32,
+}
+
+/// A file whose data is already stored, but which has no resized images yet.
+#[derive(Debug, Clone)]
+pub struct UnresizedFile {
+    pub hash: Bytes32,
+    pub mime_type: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct NftFileHashes {
+    pub hash: Bytes32,
+    pub name: Option<String>,
+    pub data_hash: Option<Bytes32>,
+    pub metadata_hash: Option<Bytes32>,
+    pub license_hash: Option<Bytes32>,
+}
+
 #[derive(Debug, Clone, Copy)]
 pub struct UpdateableNft {
     pub hash: Bytes32,
@@ -21,6 +49,17 @@ pub struct UpdateableNft {
 pub enum ResizedImageKind {
     Icon,
     Thumbnail,
+    Preview,
+}
+
+/// How a resized image was made from the file it belongs to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ResizedImageSource {
+    Image,
+    Vector,
+    Animation,
+    CoverArt,
+    Placeholder,
 }
 
 #[derive(Debug, Clone)]
@@ -29,12 +68,14 @@ pub struct FileData {
     pub data: Vec<u8>,
     pub mime_type: String,
     pub is_hash_match: bool,
+    pub is_external: bool,
 }
 
 #[derive(Debug, Clone)]
 pub struct ResizedImage {
     pub data: Vec<u8>,
     pub mime_type: Option<String>,
+    pub source: ResizedImageSource,
 }
 
 impl Database {
@@ -47,6 +88,35 @@ impl Database {
         candidates_for_download(&self.pool, check_every_seconds, max_failed_attempts, limit).await
     }
 
+    pub async fn candidates_for_verification(
+        &self,
+        check_every_seconds: i64,
+        max_failed_attempts: u32,
+        limit: u32,
+    ) -> Result<Vec<FileUri>> {
+        candidates_for_verification(&self.pool, check_every_seconds, max_failed_attempts, limit)
+            .await
+    }
+
+    pub async fn file_uri_statuses(&self, hash: Bytes32) -> Result<Vec<FileUriStatus>> {
+        file_uri_statuses(&self.pool, hash).await
+    }
+
+    pub async fn owned_nft_file_uri_statuses(&self) -> Result<Vec<FileUriStatus>> {
+        owned_nft_file_uri_statuses(&self.pool).await
+    }
+
+    pub async fn nft_file_hashes(&self, hash: Bytes32) -> Result<Option<NftFileHashes>> {
+        nft_file_hashes(&self.pool, hash).await
+    }
+
+    pub async fn owned_nft_file_hashes(
+        &self,
+        collection_id: Option<Bytes32>,
+    ) -> Result<Vec<NftFileHashes>> {
+        owned_nft_file_hashes(&self.pool, collection_id).await
+    }
+
     pub async fn thumbnail(&self, hash: Bytes32) -> Result<Option<ResizedImage>> {
         resized_image(&self.pool, hash, ResizedImageKind::Thumbnail).await
     }
@@ -55,8 +125,32 @@ impl Database {
         resized_image(&self.pool, hash, ResizedImageKind::Icon).await
     }
 
+    pub async fn preview(&self, hash: Bytes32) -> Result<Option<ResizedImage>> {
+        resized_image(&self.pool, hash, ResizedImageKind::Preview).await
+    }
+
     pub async fn full_file_data(&self, hash: Bytes32) -> Result<Option<FileData>> {
-        full_file_data(&self.pool, hash).await
+        let Some(mut file) = full_file_data(&self.pool, hash).await? else {
+            return Ok(None);
+        };
+
+        if file.is_external {
+            file.data = read_media(self.media_dir.as_deref(), hash)
+                .await?
+                .unwrap_or_default();
+        }
+
+        Ok(Some(file))
+    }
+
+    /// Marks the URIs of a file whose data was evicted as unchecked, so that it's downloaded
+    /// again. Returns whether the file had been evicted.
+    pub async fn requeue_evicted_file(&self, hash: Bytes32) -> Result<bool> {
+        requeue_evicted_file(&self.pool, hash).await
+    }
+
+    pub async fn files_needing_resized_images(&self, limit: u32) -> Result<Vec<UnresizedFile>> {
+        files_needing_resized_images(&self.pool, limit).await
     }
 
     pub async fn checked_files(&self) -> Result<u64> {
@@ -78,15 +172,29 @@ impl DatabaseTx<'_> {
     }
 
     pub async fn file_data(&mut self, hash: Bytes32) -> Result<Option<Vec<u8>>> {
+        if is_external_file(&mut *self.tx, hash).await? {
+            return read_media(self.media_dir.as_deref(), hash).await;
+        }
+
         file_data(&mut *self.tx, hash).await
     }
 
-    pub async fn update_checked_uri(&mut self, hash: Bytes32, uri: String) -> Result<()> {
-        update_checked_uri(&mut *self.tx, hash, uri).await
+    pub async fn update_checked_uri(
+        &mut self,
+        hash: Bytes32,
+        uri: String,
+        is_hash_match: bool,
+    ) -> Result<()> {
+        update_checked_uri(&mut *self.tx, hash, uri, is_hash_match).await
     }
 
-    pub async fn update_failed_uri(&mut self, hash: Bytes32, uri: String) -> Result<()> {
-        update_failed_uri(&mut *self.tx, hash, uri).await
+    pub async fn update_failed_uri(
+        &mut self,
+        hash: Bytes32,
+        uri: String,
+        error: String,
+    ) -> Result<()> {
+        update_failed_uri(&mut *self.tx, hash, uri, error).await
     }
 
     pub async fn update_file(
@@ -96,16 +204,92 @@ impl DatabaseTx<'_> {
         mime_type: String,
         is_hash_match: bool,
     ) -> Result<()> {
-        update_file(&mut *self.tx, hash, data, mime_type, is_hash_match).await
+        let size = data.len().try_into()?;
+
+        // Only data that matches its hash can be content-addressed, so anything else is always
+        // kept in the database.
+        let media_dir = if is_hash_match {
+            match self.media_cache_policy().await? {
+                Some(policy) if policy.is_external => self.media_dir.clone(),
+                _ => None,
+            }
+        } else {
+            None
+        };
+
+        if let Some(media_dir) = media_dir {
+            self.removed_media.retain(|removed| *removed != hash);
+            write_media(&media_dir, hash, &data).await?;
+            update_file(
+                &mut *self.tx,
+                hash,
+                None,
+                size,
+                true,
+                mime_type,
+                is_hash_match,
+            )
+            .await
+        } else {
+            update_file(
+                &mut *self.tx,
+                hash,
+                Some(data),
+                size,
+                false,
+                mime_type,
+                is_hash_match,
+            )
+            .await
+        }
+    }
+
+    /// Moves the data of files which match their hash into or out of the media directory,
+    /// returning how many files were moved.
+    pub async fn relocate_file_data(&mut self, is_external: bool) -> Result<u64> {
+        let Some(media_dir) = self.media_dir.clone() else {
+            return Ok(0);
+        };
+
+        let mut relocated = 0;
+
+        for hash in files_to_relocate(&mut *self.tx, !is_external).await? {
+            if is_external {
+                let Some(data) = file_data(&mut *self.tx, hash).await? else {
+                    continue;
+                };
+
+                self.removed_media.retain(|removed| *removed != hash);
+                write_media(&media_dir, hash, &data).await?;
+                set_file_location(&mut *self.tx, hash, None, true).await?;
+            } else {
+                let Some(data) = read_media(Some(&media_dir), hash).await? else {
+                    continue;
+                };
+
+                set_file_location(&mut *self.tx, hash, Some(data), false).await?;
+                self.removed_media.push(hash);
+            }
+
+            relocated += 1;
+        }
+
+        Ok(relocated)
     }
 
     pub async fn insert_resized_image(
         &mut self,
         file_hash: Bytes32,
         kind: ResizedImageKind,
+        source: ResizedImageSource,
         data: Vec<u8>,
     ) -> Result<()> {
-        insert_resized_image(&mut *self.tx, file_hash, kind, data).await
+        insert_resized_image(&mut self.tx, file_hash, kind, source, data).await
+    }
+
+    /// Stops a file from being picked up for resized images again, even if none could be made.
+    pub async fn clear_needs_resized_images(&mut self, hash: Bytes32) -> Result<()> {
+        clear_needs_resized_images(&mut *self.tx, hash).await
     }
 
     pub async fn icon(&mut self, hash: Bytes32) -> Result<Option<ResizedImage>> {
@@ -117,12 +301,23 @@ impl DatabaseTx<'_> {
     }
 
     pub async fn delete_file_data(&mut self, hash: Bytes32) -> Result<()> {
+        // The file is only removed once the transaction has been committed.
+        if is_external_file(&mut *self.tx, hash).await? {
+            self.removed_media.push(hash);
+        }
+
         delete_file_data(&mut *self.tx, hash).await
     }
 
     pub async fn set_uri_unchecked(&mut self, uri: String) -> Result<()> {
         set_uri_unchecked(&mut *self.tx, uri).await
     }
+
+    /// Marks the URIs of a file that failed or didn't match its hash as unchecked, returning how
+    /// many of them will be checked again.
+    pub async fn set_failing_uris_unchecked(&mut self, hash: Bytes32) -> Result<u64> {
+        set_failing_uris_unchecked(&mut *self.tx, hash).await
+    }
 }
 
 async fn insert_file(conn: impl SqliteExecutor<'_>, hash: Bytes32) -> Result<()> {
@@ -163,7 +358,7 @@ async fn full_file_data(conn: impl SqliteExecutor<'_>, hash: Bytes32) -> Result<
     let hash = hash.as_ref();
 
     let row = query!(
-        "SELECT hash, data, mime_type, is_hash_match FROM files WHERE hash = ?",
+        "SELECT hash, data, mime_type, is_hash_match, is_external FROM files WHERE hash = ?",
         hash
     )
     .fetch_optional(conn)
@@ -175,11 +370,61 @@ async fn full_file_data(conn: impl SqliteExecutor<'_>, hash: Bytes32) -> Result<
             data: row.data.unwrap_or_default(),
             mime_type: row.mime_type.unwrap_or_default(),
             is_hash_match: row.is_hash_match.unwrap_or_default(),
+            is_external: row.is_external,
         })
     })
     .transpose()
 }
 
+async fn is_external_file(conn: impl SqliteExecutor<'_>, hash: Bytes32) -> Result<bool> {
+    let hash = hash.as_ref();
+
+    let row = query!("SELECT is_external FROM files WHERE hash = ?", hash)
+        .fetch_optional(conn)
+        .await?;
+
+    Ok(row.is_some_and(|row| row.is_external))
+}
+
+async fn files_to_relocate(
+    conn: impl SqliteExecutor<'_>,
+    is_external: bool,
+) -> Result<Vec<Bytes32>> {
+    query!(
+        "
+        SELECT hash FROM files
+        WHERE is_external = ? AND is_hash_match
+        AND (data IS NOT NULL OR is_external)
+        ",
+        is_external
+    )
+    .fetch_all(conn)
+    .await?
+    .into_iter()
+    .map(|row| row.hash.convert())
+    .collect()
+}
+
+async fn set_file_location(
+    conn: impl SqliteExecutor<'_>,
+    hash: Bytes32,
+    data: Option<Vec<u8>>,
+    is_external: bool,
+) -> Result<()> {
+    let hash = hash.as_ref();
+
+    query!(
+        "UPDATE files SET data = ?, is_external = ? WHERE hash = ?",
+        data,
+        is_external,
+        hash
+    )
+    .execute(conn)
+    .await?;
+
+    Ok(())
+}
+
 async fn candidates_for_download(
     conn: impl SqliteExecutor<'_>,
     check_every_seconds: i64,
@@ -191,7 +436,43 @@ async fn candidates_for_download(
         SELECT hash, uri, last_checked_timestamp, failed_attempts
         FROM file_uris
         INNER JOIN files ON files.id = file_uris.file_id
-        WHERE data IS NULL
+        WHERE ((data IS NULL AND NOT is_external) OR NOT files.is_hash_match)
+        AND file_uris.is_hash_match IS NULL
+        AND (last_checked_timestamp IS NULL OR unixepoch() - last_checked_timestamp >= ?)
+        AND failed_attempts < ?
+        LIMIT ?
+        ",
+        check_every_seconds,
+        max_failed_attempts,
+        limit
+    )
+    .fetch_all(conn)
+    .await?
+    .into_iter()
+    .map(|row| {
+        Ok(FileUri {
+            hash: row.hash.convert()?,
+            uri: row.uri,
+            last_checked_timestamp: row.last_checked_timestamp.convert()?,
+            failed_attempts: row.failed_attempts.convert()?,
+        })
+    })
+    .collect()
+}
+
+async fn candidates_for_verification(
+    conn: impl SqliteExecutor<'_>,
+    check_every_seconds: i64,
+    max_failed_attempts: u32,
+    limit: u32,
+) -> Result<Vec<FileUri>> {
+    query!(
+        "
+        SELECT hash, uri, last_checked_timestamp, failed_attempts
+        FROM file_uris
+        INNER JOIN files ON files.id = file_uris.file_id
+        WHERE (data IS NOT NULL OR is_external) AND files.is_hash_match
+        AND file_uris.is_hash_match IS NULL
         AND (last_checked_timestamp IS NULL OR unixepoch() - last_checked_timestamp >= ?)
         AND failed_attempts < ?
         LIMIT ?
@@ -214,19 +495,144 @@ async fn candidates_for_download(
     .collect()
 }
 
+async fn file_uri_statuses(
+    conn: impl SqliteExecutor<'_>,
+    hash: Bytes32,
+) -> Result<Vec<FileUriStatus>> {
+    let hash = hash.as_ref();
+
+    query!(
+        "
+        SELECT hash, uri, file_uris.is_hash_match, error, last_checked_timestamp, failed_attempts
+        FROM file_uris
+        INNER JOIN files ON files.id = file_uris.file_id
+        WHERE hash = ?
+        ORDER BY file_uris.id ASC
+        ",
+        hash
+    )
+    .fetch_all(conn)
+    .await?
+    .into_iter()
+    .map(|row| {
+        Ok(FileUriStatus {
+            hash: row.hash.convert()?,
+            uri: row.uri,
+            is_hash_match: row.is_hash_match,
+            error: row.error,
+            last_checked_timestamp: row.last_checked_timestamp.convert()?,
+            failed_attempts: row.failed_attempts.convert()?,
+        })
+    })
+    .collect()
+}
+
+async fn owned_nft_file_uri_statuses(conn: impl SqliteExecutor<'_>) -> Result<Vec<FileUriStatus>> {
+    query!(
+        "
+        SELECT hash, uri, file_uris.is_hash_match, error, last_checked_timestamp, failed_attempts
+        FROM file_uris
+        INNER JOIN files ON files.id = file_uris.file_id
+        WHERE hash IN (
+            SELECT data_hash FROM owned_nfts
+            UNION SELECT metadata_hash FROM owned_nfts
+            UNION SELECT license_hash FROM owned_nfts
+        )
+        ORDER BY file_uris.id ASC
+        "
+    )
+    .fetch_all(conn)
+    .await?
+    .into_iter()
+    .map(|row| {
+        Ok(FileUriStatus {
+            hash: row.hash.convert()?,
+            uri: row.uri,
+            is_hash_match: row.is_hash_match,
+            error: row.error,
+            last_checked_timestamp: row.last_checked_timestamp.convert()?,
+            failed_attempts: row.failed_attempts.convert()?,
+        })
+    })
+    .collect()
+}
+
+async fn nft_file_hashes(
+    conn: impl SqliteExecutor<'_>,
+    hash: Bytes32,
+) -> Result<Option<NftFileHashes>> {
+    let hash = hash.as_ref();
+
+    query!(
+        "
+        SELECT assets.hash, assets.name, data_hash, metadata_hash, license_hash
+        FROM nfts
+        INNER JOIN assets ON assets.id = nfts.asset_id
+        WHERE assets.hash = ?
+        ",
+        hash
+    )
+    .fetch_optional(conn)
+    .await?
+    .map(|row| {
+        Ok(NftFileHashes {
+            hash: row.hash.convert()?,
+            name: row.name,
+            data_hash: row.data_hash.convert()?,
+            metadata_hash: row.metadata_hash.convert()?,
+            license_hash: row.license_hash.convert()?,
+        })
+    })
+    .transpose()
+}
+
+async fn owned_nft_file_hashes(
+    conn: impl SqliteExecutor<'_>,
+    collection_id: Option<Bytes32>,
+) -> Result<Vec<NftFileHashes>> {
+    let collection_id = collection_id.as_deref();
+
+    query!(
+        "
+        SELECT asset_hash, asset_name, data_hash, metadata_hash, license_hash
+        FROM owned_nfts
+        LEFT JOIN collections ON collections.id = owned_nfts.collection_id
+        WHERE ? IS NULL OR collections.hash = ?
+        ORDER BY asset_name ASC, asset_hash ASC
+        ",
+        collection_id,
+        collection_id
+    )
+    .fetch_all(conn)
+    .await?
+    .into_iter()
+    .map(|row| {
+        Ok(NftFileHashes {
+            hash: row.asset_hash.convert()?,
+            name: row.asset_name,
+            data_hash: row.data_hash.convert()?,
+            metadata_hash: row.metadata_hash.convert()?,
+            license_hash: row.license_hash.convert()?,
+        })
+    })
+    .collect()
+}
+
 async fn update_failed_uri(
     conn: impl SqliteExecutor<'_>,
     hash: Bytes32,
     uri: String,
+    error: String,
 ) -> Result<()> {
     let hash = hash.as_ref();
 
     query!(
         "
         UPDATE file_uris
-        SET failed_attempts = failed_attempts + 1, last_checked_timestamp = unixepoch()
+        SET failed_attempts = failed_attempts + 1, last_checked_timestamp = unixepoch(), error = ?
         WHERE file_id = (SELECT id FROM files WHERE hash = ?) AND uri = ?
         ",
+        error,
         hash,
         uri
     )
@@ -240,15 +646,17 @@ async fn update_checked_uri(
     conn: impl SqliteExecutor<'_>,
     hash: Bytes32,
     uri: String,
+    is_hash_match: bool,
 ) -> Result<()> {
     let hash = hash.as_ref();
 
     query!(
         "
         UPDATE file_uris
-        SET last_checked_timestamp = unixepoch()
+        SET last_checked_timestamp = unixepoch(), is_hash_match = ?, error = NULL
         WHERE file_id = (SELECT id FROM files WHERE hash = ?) AND uri = ?
         ",
+        is_hash_match,
         hash,
         uri
     )
@@ -261,19 +669,26 @@ async fn update_checked_uri(
 async fn update_file(
     conn: impl SqliteExecutor<'_>,
     hash: Bytes32,
-    data: Vec<u8>,
+    data: Option<Vec<u8>>,
+    size: u64,
+    is_external: bool,
     mime_type: String,
     is_hash_match: bool,
 ) -> Result<()> {
     let hash = hash.as_ref();
+    let size: i64 = size.try_into()?;
 
     query!(
         "
-        UPDATE files SET data = ?, mime_type = ?, is_hash_match = ?
+        UPDATE files
+        SET data = ?, size = ?, is_external = ?, mime_type = ?, is_hash_match = ?,
+            last_accessed_timestamp = unixepoch(), needs_resized_images = FALSE
         WHERE hash = ?
-        AND (data IS NULL OR NOT is_hash_match)
+        AND ((data IS NULL AND NOT is_external) OR NOT is_hash_match)
         ",
         data,
+        size,
+        is_external,
         mime_type,
         is_hash_match,
         hash
@@ -305,20 +720,73 @@ async fn nfts_with_metadata_hash(
 }
 
 async fn insert_resized_image(
-    conn: impl SqliteExecutor<'_>,
+    conn: &mut SqliteConnection,
     file_hash: Bytes32,
     kind: ResizedImageKind,
+    source: ResizedImageSource,
     data: Vec<u8>,
 ) -> Result<()> {
     let file_hash = file_hash.as_ref();
     let kind = kind as i64;
+    let source = source as i64;
 
+    // The file is downloaded again if its data didn't match the hash, in which case the previous
+    // image is replaced.
     query!(
-        "INSERT INTO resized_images (file_id, kind, data) VALUES ((SELECT id FROM files WHERE hash = ?), ?, ?)",
+        "DELETE FROM resized_images WHERE file_id = (SELECT id FROM files WHERE hash = ?) AND kind = ?",
+        file_hash,
+        kind
+    )
+    .execute(&mut *conn)
+    .await?;
+
+    query!(
+        "INSERT INTO resized_images (file_id, kind, source, data) VALUES ((SELECT id FROM files WHERE hash = ?), ?, ?, ?)",
         file_hash,
         kind,
+        source,
         data
     )
+    .execute(&mut *conn)
+    .await?;
+
+    Ok(())
+}
+
+async fn files_needing_resized_images(
+    conn: impl SqliteExecutor<'_>,
+    limit: u32,
+) -> Result<Vec<UnresizedFile>> {
+    query!(
+        "
+        SELECT hash, mime_type AS 'mime_type!'
+        FROM files
+        WHERE needs_resized_images
+        AND (data IS NOT NULL OR is_external)
+        AND mime_type IS NOT NULL
+        LIMIT ?
+        ",
+        limit
+    )
+    .fetch_all(conn)
+    .await?
+    .into_iter()
+    .map(|row| {
+        Ok(UnresizedFile {
+            hash: row.hash.convert()?,
+            mime_type: row.mime_type,
+        })
+    })
+    .collect()
+}
+
+async fn clear_needs_resized_images(conn: impl SqliteExecutor<'_>, hash: Bytes32) -> Result<()> {
+    let hash = hash.as_ref();
+
+    query!(
+        "UPDATE files SET needs_resized_images = FALSE WHERE hash = ?",
+        hash
+    )
     .execute(conn)
     .await?;
 
@@ -334,7 +802,7 @@ async fn resized_image(
     let kind = kind as i64;
 
     let row = query!(
-        "SELECT resized_images.data, mime_type
+        "SELECT resized_images.data, mime_type, source
         FROM resized_images 
         INNER JOIN files ON files.id = resized_images.file_id
         WHERE files.hash = ? AND kind = ?",
@@ -344,26 +812,62 @@ async fn resized_image(
     .fetch_optional(conn)
     .await?;
 
-    Ok(row.map(|row| ResizedImage {
+    let Some(row) = row else {
+        return Ok(None);
+    };
+
+    Ok(Some(ResizedImage {
         data: row.data,
         mime_type: row.mime_type,
+        source: match row.source {
+            0 => ResizedImageSource::Image,
+            1 => ResizedImageSource::Vector,
+            2 => ResizedImageSource::Animation,
+            3 => ResizedImageSource::CoverArt,
+            4 => ResizedImageSource::Placeholder,
+            _ => return Err(DatabaseError::InvalidEnumVariant),
+        },
     }))
 }
 
 async fn delete_file_data(conn: impl SqliteExecutor<'_>, hash: Bytes32) -> Result<()> {
     let hash = hash.as_ref();
 
-    query!("UPDATE files SET data = NULL WHERE hash = ?", hash)
-        .execute(conn)
-        .await?;
+    query!(
+        "UPDATE files SET data = NULL, size = NULL, is_external = FALSE WHERE hash = ?",
+        hash
+    )
+    .execute(conn)
+    .await?;
 
     Ok(())
 }
 
+async fn requeue_evicted_file(conn: impl SqliteExecutor<'_>, hash: Bytes32) -> Result<bool> {
+    let hash = hash.as_ref();
+
+    // Only evicted files have no data even though their URIs were verified.
+    let result = query!(
+        "
+        UPDATE file_uris
+        SET failed_attempts = 0, last_checked_timestamp = NULL, is_hash_match = NULL, error = NULL
+        WHERE file_id = (
+            SELECT id FROM files WHERE hash = ? AND data IS NULL AND NOT is_external
+        )
+        AND is_hash_match
+        ",
+        hash
+    )
+    .execute(conn)
+    .await?;
+
+    Ok(result.rows_affected() > 0)
+}
+
 async fn set_uri_unchecked(conn: impl SqliteExecutor<'_>, uri: String) -> Result<()> {
     query!(
         "UPDATE file_uris 
-        SET failed_attempts = 0, last_checked_timestamp = NULL
+        SET failed_attempts = 0, last_checked_timestamp = NULL, is_hash_match = NULL, error = NULL
         FROM files
         WHERE file_uris.file_id = files.id AND file_uris.uri = ?",
         uri
@@ -374,6 +878,24 @@ async fn set_uri_unchecked(conn: impl SqliteExecutor<'_>, uri: String) -> Result
     Ok(())
 }
 
+async fn set_failing_uris_unchecked(conn: impl SqliteExecutor<'_>, hash: Bytes32) -> Result<u64> {
+    let hash = hash.as_ref();
+
+    let result = query!(
+        "
+        UPDATE file_uris
+        SET failed_attempts = 0, last_checked_timestamp = NULL, is_hash_match = NULL, error = NULL
+        WHERE file_id = (SELECT id FROM files WHERE hash = ?)
+        AND (NOT is_hash_match OR (is_hash_match IS NULL AND failed_attempts > 0))
+        ",
+        hash
+    )
+    .execute(conn)
+    .await?;
+
+    Ok(result.rows_affected())
+}
+
 async fn checked_files(conn: impl SqliteExecutor<'_>) -> Result<u64> {
     query!(
         "
@@ -0,0 +1,530 @@
+use chia::protocol::Bytes32;
+use sqlx::{query, SqliteExecutor};
+
+use crate::{Convert, Database, DatabaseError, DatabaseTx, Result};
+
+/// Limits how much space downloaded NFT files take up. Icons, thumbnails and previews aren't
+/// counted, since they're kept when the full data is evicted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MediaCachePolicy {
+    pub max_bytes: Option<u64>,
+    pub is_external: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MediaPinKind {
+    Nft,
+    Collection,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct MediaPin {
+    pub kind: MediaPinKind,
+    pub hash: Bytes32,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MediaCacheStats {
+    pub cached_files: u64,
+    pub cached_bytes: u64,
+    pub external_files: u64,
+    pub external_bytes: u64,
+    pub pinned_files: u64,
+    pub pinned_bytes: u64,
+    pub resized_image_bytes: u64,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MediaEviction {
+    pub files: u64,
+    pub bytes: u64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CachedFile {
+    pub hash: Bytes32,
+    pub size: u64,
+}
+
+impl Database {
+    pub async fn media_cache_policy(&self) -> Result<Option<MediaCachePolicy>> {
+        media_cache_policy(&self.pool).await
+    }
+
+    pub async fn media_pins(&self) -> Result<Vec<MediaPin>> {
+        media_pins(&self.pool).await
+    }
+
+    pub async fn media_cache_stats(&self) -> Result<MediaCacheStats> {
+        media_cache_stats(&self.pool).await
+    }
+
+    pub async fn touch_file(&self, hash: Bytes32) -> Result<()> {
+        touch_file(&self.pool, hash).await
+    }
+}
+
+impl DatabaseTx<'_> {
+    pub async fn media_cache_policy(&mut self) -> Result<Option<MediaCachePolicy>> {
+        media_cache_policy(&mut *self.tx).await
+    }
+
+    pub async fn set_media_cache_policy(&mut self, policy: MediaCachePolicy) -> Result<()> {
+        set_media_cache_policy(&mut *self.tx, policy).await
+    }
+
+    pub async fn insert_media_pin(&mut self, pin: MediaPin) -> Result<()> {
+        insert_media_pin(&mut *self.tx, pin).await
+    }
+
+    pub async fn delete_media_pin(&mut self, pin: MediaPin) -> Result<()> {
+        delete_media_pin(&mut *self.tx, pin).await
+    }
+
+    pub async fn cached_file_bytes(&mut self) -> Result<u64> {
+        cached_file_bytes(&mut *self.tx).await
+    }
+
+    /// Files that can be evicted, starting with the least recently accessed. The metadata of
+    /// NFTs is never evicted, since it's small and needed to display them.
+    pub async fn evictable_files(&mut self) -> Result<Vec<CachedFile>> {
+        evictable_files(&mut *self.tx).await
+    }
+
+    /// Deletes the data of the least recently accessed files until the cache fits in the
+    /// given number of bytes, or there is nothing left that can be evicted.
+    pub async fn evict_media(&mut self, max_bytes: u64) -> Result<MediaEviction> {
+        let mut cached_bytes = self.cached_file_bytes().await?;
+        let mut eviction = MediaEviction::default();
+
+        if cached_bytes <= max_bytes {
+            return Ok(eviction);
+        }
+
+        for file in self.evictable_files().await? {
+            if cached_bytes <= max_bytes {
+                break;
+            }
+
+            self.delete_file_data(file.hash).await?;
+
+            cached_bytes = cached_bytes.saturating_sub(file.size);
+            eviction.files += 1;
+            eviction.bytes += file.size;
+        }
+
+        Ok(eviction)
+    }
+
+    /// Applies the size limit of the media cache policy, if there is one.
+    pub async fn enforce_media_cache_policy(&mut self) -> Result<MediaEviction> {
+        match self
+            .media_cache_policy()
+            .await?
+            .and_then(|policy| policy.max_bytes)
+        {
+            Some(max_bytes) => self.evict_media(max_bytes).await,
+            None => Ok(MediaEviction::default()),
+        }
+    }
+}
+
+async fn media_cache_policy(conn: impl SqliteExecutor<'_>) -> Result<Option<MediaCachePolicy>> {
+    query!("SELECT max_bytes, is_external FROM media_cache_policy WHERE id = 1")
+        .fetch_optional(conn)
+        .await?
+        .map(|row| {
+            Ok(MediaCachePolicy {
+                max_bytes: row.max_bytes.convert()?,
+                is_external: row.is_external,
+            })
+        })
+        .transpose()
+}
+
+async fn set_media_cache_policy(
+    conn: impl SqliteExecutor<'_>,
+    policy: MediaCachePolicy,
+) -> Result<()> {
+    let max_bytes: Option<i64> = policy.max_bytes.map(TryInto::try_into).transpose()?;
+
+    query!(
+        "
+        INSERT OR REPLACE INTO media_cache_policy (id, max_bytes, is_external)
+        VALUES (1, ?, ?)
+        ",
+        max_bytes,
+        policy.is_external
+    )
+    .execute(conn)
+    .await?;
+
+    Ok(())
+}
+
+async fn media_pins(conn: impl SqliteExecutor<'_>) -> Result<Vec<MediaPin>> {
+    query!("SELECT kind, hash FROM media_pins ORDER BY id ASC")
+        .fetch_all(conn)
+        .await?
+        .into_iter()
+        .map(|row| {
+            Ok(MediaPin {
+                kind: match row.kind {
+                    0 => MediaPinKind::Nft,
+                    1 => MediaPinKind::Collection,
+                    _ => return Err(DatabaseError::InvalidEnumVariant),
+                },
+                hash: row.hash.convert()?,
+            })
+        })
+        .collect()
+}
+
+async fn insert_media_pin(conn: impl SqliteExecutor<'_>, pin: MediaPin) -> Result<()> {
+    let kind = pin.kind as i64;
+    let hash = pin.hash.as_ref();
+
+    query!(
+        "INSERT OR IGNORE INTO media_pins (kind, hash) VALUES (?, ?)",
+        kind,
+        hash
+    )
+    .execute(conn)
+    .await?;
+
+    Ok(())
+}
+
+async fn delete_media_pin(conn: impl SqliteExecutor<'_>, pin: MediaPin) -> Result<()> {
+    let kind = pin.kind as i64;
+    let hash = pin.hash.as_ref();
+
+    query!(
+        "DELETE FROM media_pins WHERE kind = ? AND hash = ?",
+        kind,
+        hash
+    )
+    .execute(conn)
+    .await?;
+
+    Ok(())
+}
+
+async fn touch_file(conn: impl SqliteExecutor<'_>, hash: Bytes32) -> Result<()> {
+    let hash = hash.as_ref();
+
+    query!(
+        "UPDATE files SET last_accessed_timestamp = unixepoch() WHERE hash = ?",
+        hash
+    )
+    .execute(conn)
+    .await?;
+
+    Ok(())
+}
+
+async fn cached_file_bytes(conn: impl SqliteExecutor<'_>) -> Result<u64> {
+    query!(
+        "
+        SELECT COALESCE(SUM(size), 0) AS bytes FROM files
+        WHERE data IS NOT NULL OR is_external
+        "
+    )
+    .fetch_one(conn)
+    .await?
+    .bytes
+    .convert()
+}
+
+async fn evictable_files(conn: impl SqliteExecutor<'_>) -> Result<Vec<CachedFile>> {
+    query!(
+        "
+        SELECT hash, size FROM files
+        WHERE (data IS NOT NULL OR is_external)
+        AND NOT EXISTS (SELECT 1 FROM nfts WHERE nfts.metadata_hash = files.hash)
+        AND NOT EXISTS (
+            SELECT 1 FROM nfts
+            INNER JOIN assets ON assets.id = nfts.asset_id
+            LEFT JOIN collections ON collections.id = nfts.collection_id
+            INNER JOIN media_pins ON (media_pins.kind = 0 AND media_pins.hash = assets.hash)
+                OR (media_pins.kind = 1 AND media_pins.hash = collections.hash)
+            WHERE files.hash IN (nfts.data_hash, nfts.license_hash)
+        )
+        ORDER BY last_accessed_timestamp ASC, id ASC
+        "
+    )
+    .fetch_all(conn)
+    .await?
+    .into_iter()
+    .map(|row| {
+        Ok(CachedFile {
+            hash: row.hash.convert()?,
+            size: row.size.unwrap_or_default().convert()?,
+        })
+    })
+    .collect()
+}
+
+async fn media_cache_stats(conn: impl SqliteExecutor<'_>) -> Result<MediaCacheStats> {
+    let row = query!(
+        "
+        SELECT
+            COUNT(*) AS cached_files,
+            COALESCE(SUM(size), 0) AS cached_bytes,
+            COALESCE(SUM(is_external), 0) AS external_files,
+            COALESCE(SUM(CASE WHEN is_external THEN size ELSE 0 END), 0) AS external_bytes,
+            COALESCE(SUM(is_pinned), 0) AS pinned_files,
+            COALESCE(SUM(CASE WHEN is_pinned THEN size ELSE 0 END), 0) AS pinned_bytes,
+            (SELECT COALESCE(SUM(LENGTH(data)), 0) FROM resized_images) AS resized_image_bytes
+        FROM (
+            SELECT size, is_external, EXISTS (
+                SELECT 1 FROM nfts
+                INNER JOIN assets ON assets.id = nfts.asset_id
+                LEFT JOIN collections ON collections.id = nfts.collection_id
+                INNER JOIN media_pins ON (media_pins.kind = 0 AND media_pins.hash = assets.hash)
+                    OR (media_pins.kind = 1 AND media_pins.hash = collections.hash)
+                WHERE files.hash IN (nfts.data_hash, nfts.license_hash)
+            ) AS is_pinned
+            FROM files
+            WHERE data IS NOT NULL OR is_external
+        )
+        "
+    )
+    .fetch_one(conn)
+    .await?;
+
+    Ok(MediaCacheStats {
+        cached_files: row.cached_files.convert()?,
+        cached_bytes: row.cached_bytes.convert()?,
+        external_files: row.external_files.convert()?,
+        external_bytes: row.external_bytes.convert()?,
+        pinned_files: row.pinned_files.convert()?,
+        pinned_bytes: row.pinned_bytes.convert()?,
+        resized_image_bytes: row.resized_image_bytes.convert()?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use chia::protocol::Program;
+
+    use crate::{read_media, test_database, Asset, AssetKind, NftCoinInfo};
+
+    use super::*;
+
+    async fn insert_file(db: &Database, hash: Bytes32, size: usize, accessed: i64) -> Result<()> {
+        let mut tx = db.tx().await?;
+        tx.insert_file(hash).await?;
+        tx.update_file(hash, vec![0; size], "image/png".to_string(), true)
+            .await?;
+        tx.commit().await?;
+
+        let hash = hash.as_ref();
+
+        query!(
+            "UPDATE files SET last_accessed_timestamp = ? WHERE hash = ?",
+            accessed,
+            hash
+        )
+        .execute(&db.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn evictable_hashes(db: &Database) -> Result<Vec<Bytes32>> {
+        let mut tx = db.tx().await?;
+        let files = tx.evictable_files().await?;
+        tx.commit().await?;
+
+        Ok(files.into_iter().map(|file| file.hash).collect())
+    }
+
+    #[tokio::test]
+    async fn test_evict_media() -> anyhow::Result<()> {
+        let db = test_database().await?;
+
+        let a = Bytes32::new([1; 32]);
+        let b = Bytes32::new([2; 32]);
+        let c = Bytes32::new([3; 32]);
+
+        insert_file(&db, a, 10, 300).await?;
+        insert_file(&db, b, 20, 100).await?;
+        insert_file(&db, c, 30, 200).await?;
+
+        // The least recently accessed files come first.
+        assert_eq!(evictable_hashes(&db).await?, [b, c, a]);
+
+        let mut tx = db.tx().await?;
+        assert_eq!(tx.evict_media(60).await?, MediaEviction::default());
+
+        let eviction = tx.evict_media(35).await?;
+        assert_eq!(
+            eviction,
+            MediaEviction {
+                files: 2,
+                bytes: 50
+            }
+        );
+        assert_eq!(tx.cached_file_bytes().await?, 10);
+        tx.commit().await?;
+
+        assert_eq!(evictable_hashes(&db).await?, [a]);
+
+        let mut tx = db.tx().await?;
+        assert!(tx.file_data(a).await?.is_some());
+        assert!(tx.file_data(b).await?.is_none());
+        assert!(tx.file_data(c).await?.is_none());
+
+        let eviction = tx.evict_media(0).await?;
+        assert_eq!(
+            eviction,
+            MediaEviction {
+                files: 1,
+                bytes: 10
+            }
+        );
+        tx.commit().await?;
+
+        assert_eq!(db.media_cache_stats().await?, MediaCacheStats::default());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_evictable_file_pins() -> anyhow::Result<()> {
+        let db = test_database().await?;
+
+        let nft_hash = Bytes32::new([1; 32]);
+        let data_hash = Bytes32::new([2; 32]);
+        let metadata_hash = Bytes32::new([3; 32]);
+        let license_hash = Bytes32::new([4; 32]);
+        let other_hash = Bytes32::new([5; 32]);
+
+        let mut tx = db.tx().await?;
+        tx.insert_asset(Asset {
+            hash: nft_hash,
+            name: None,
+            ticker: None,
+            precision: 1,
+            icon_url: None,
+            description: None,
+            is_sensitive_content: false,
+            is_visible: true,
+            hidden_puzzle_hash: None,
+            kind: AssetKind::Nft,
+        })
+        .await?;
+        tx.insert_nft(
+            nft_hash,
+            &NftCoinInfo {
+                collection_hash: Bytes32::default(),
+                collection_name: None,
+                minter_hash: None,
+                owner_hash: None,
+                metadata: Program::default(),
+                metadata_updater_puzzle_hash: Bytes32::default(),
+                royalty_puzzle_hash: Bytes32::default(),
+                royalty_basis_points: 0,
+                data_hash: Some(data_hash),
+                metadata_hash: Some(metadata_hash),
+                license_hash: Some(license_hash),
+                edition_number: None,
+                edition_total: None,
+            },
+        )
+        .await?;
+        tx.commit().await?;
+
+        insert_file(&db, data_hash, 10, 100).await?;
+        insert_file(&db, metadata_hash, 10, 200).await?;
+        insert_file(&db, license_hash, 10, 300).await?;
+        insert_file(&db, other_hash, 10, 400).await?;
+
+        // NFT metadata is never evicted.
+        assert_eq!(
+            evictable_hashes(&db).await?,
+            [data_hash, license_hash, other_hash]
+        );
+
+        let nft_pin = MediaPin {
+            kind: MediaPinKind::Nft,
+            hash: nft_hash,
+        };
+        let collection_pin = MediaPin {
+            kind: MediaPinKind::Collection,
+            hash: Bytes32::default(),
+        };
+
+        for pin in [nft_pin, collection_pin] {
+            let mut tx = db.tx().await?;
+            tx.insert_media_pin(pin).await?;
+            tx.commit().await?;
+
+            assert_eq!(evictable_hashes(&db).await?, [other_hash]);
+
+            let stats = db.media_cache_stats().await?;
+            assert_eq!(stats.pinned_files, 2);
+            assert_eq!(stats.pinned_bytes, 20);
+
+            let mut tx = db.tx().await?;
+            assert_eq!(
+                tx.evict_media(0).await?,
+                MediaEviction {
+                    files: 1,
+                    bytes: 10
+                }
+            );
+            tx.rollback().await?;
+
+            let mut tx = db.tx().await?;
+            tx.delete_media_pin(pin).await?;
+            tx.commit().await?;
+
+            assert_eq!(
+                evictable_hashes(&db).await?,
+                [data_hash, license_hash, other_hash]
+            );
+        }
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_evicted_files_removed_after_commit() -> anyhow::Result<()> {
+        let media_dir = std::env::temp_dir().join(format!("sage-media-{}", std::process::id()));
+        let db = test_database().await?.with_media_dir(media_dir.clone());
+
+        let hash = Bytes32::new([1; 32]);
+
+        let mut tx = db.tx().await?;
+        tx.set_media_cache_policy(MediaCachePolicy {
+            max_bytes: None,
+            is_external: true,
+        })
+        .await?;
+        tx.insert_file(hash).await?;
+        tx.update_file(hash, vec![1, 2, 3], "image/png".to_string(), true)
+            .await?;
+        tx.commit().await?;
+
+        assert!(read_media(Some(&media_dir), hash).await?.is_some());
+
+        // The file is kept if the eviction is rolled back.
+        let mut tx = db.tx().await?;
+        assert_eq!(tx.evict_media(0).await?.files, 1);
+        assert!(read_media(Some(&media_dir), hash).await?.is_some());
+        tx.rollback().await?;
+
+        let mut tx = db.tx().await?;
+        assert_eq!(tx.file_data(hash).await?, Some(vec![1, 2, 3]));
+        assert_eq!(tx.evict_media(0).await?.files, 1);
+        tx.commit().await?;
+
+        assert!(read_media(Some(&media_dir), hash).await?.is_none());
+
+        std::fs::remove_dir_all(&media_dir)?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,578 @@
+use chia::{protocol::Bytes32, puzzles::nft::NftMetadata, traits::Streamable};
+use sqlx::SqliteExecutor;
+
+use crate::{Convert, Database, DatabaseTx, Result};
+
+#[derive(Debug, Clone)]
+pub struct MintJobRow {
+    pub id: u32,
+    pub name: String,
+    pub did_id: Bytes32,
+    pub fee: u64,
+    pub max_cost: u64,
+    pub is_paused: bool,
+    pub error: Option<String>,
+    pub created_timestamp: u64,
+    pub item_count: u32,
+    pub submitted_count: u32,
+    pub confirmed_count: u32,
+}
+
+#[derive(Debug, Clone)]
+pub struct MintJobItemRow {
+    pub row_index: u32,
+    pub metadata: NftMetadata,
+    pub p2_puzzle_hash: Option<Bytes32>,
+    pub royalty_puzzle_hash: Option<Bytes32>,
+    pub royalty_basis_points: u16,
+}
+
+#[derive(Debug, Clone)]
+pub struct MintJobItemStatusRow {
+    pub item: MintJobItemRow,
+    pub launcher_id: Option<Bytes32>,
+    pub transaction_id: Option<Bytes32>,
+    pub is_confirmed: bool,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct MintJobBatchRow {
+    pub id: u32,
+    pub transaction_id: Bytes32,
+    pub input_coin_id: Bytes32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MintJobBatchStatus {
+    Pending,
+    Confirmed,
+    Failed,
+}
+
+impl Database {
+    pub async fn mint_jobs(&self) -> Result<Vec<MintJobRow>> {
+        mint_jobs(&self.pool).await
+    }
+
+    pub async fn mint_job(&self, id: u32) -> Result<Option<MintJobRow>> {
+        mint_job(&self.pool, id).await
+    }
+
+    pub async fn active_mint_job_ids(&self) -> Result<Vec<u32>> {
+        active_mint_job_ids(&self.pool).await
+    }
+
+    pub async fn mint_job_items(&self, mint_job_id: u32) -> Result<Vec<MintJobItemStatusRow>> {
+        mint_job_items(&self.pool, mint_job_id).await
+    }
+
+    pub async fn unsubmitted_mint_job_items(
+        &self,
+        mint_job_id: u32,
+    ) -> Result<Vec<MintJobItemRow>> {
+        unsubmitted_mint_job_items(&self.pool, mint_job_id).await
+    }
+
+    pub async fn pending_mint_job_batch(
+        &self,
+        mint_job_id: u32,
+    ) -> Result<Option<MintJobBatchRow>> {
+        pending_mint_job_batch(&self.pool, mint_job_id).await
+    }
+
+    pub async fn mint_job_batch_status(
+        &self,
+        batch: MintJobBatchRow,
+    ) -> Result<MintJobBatchStatus> {
+        mint_job_batch_status(&self.pool, batch).await
+    }
+
+    pub async fn confirm_mint_job_batch(&self, batch_id: u32) -> Result<()> {
+        confirm_mint_job_batch(&self.pool, batch_id).await
+    }
+
+    pub async fn update_mint_job_status(
+        &self,
+        id: u32,
+        is_paused: bool,
+        error: Option<String>,
+    ) -> Result<()> {
+        update_mint_job_status(&self.pool, id, is_paused, error).await
+    }
+
+    pub async fn delete_mint_job(&self, id: u32) -> Result<()> {
+        delete_mint_job(&self.pool, id).await
+    }
+}
+
+impl DatabaseTx<'_> {
+    pub async fn insert_mint_job(
+        &mut self,
+        name: String,
+        did_id: Bytes32,
+        fee: u64,
+        max_cost: u64,
+    ) -> Result<u32> {
+        insert_mint_job(&mut *self.tx, name, did_id, fee, max_cost).await
+    }
+
+    pub async fn insert_mint_job_item(
+        &mut self,
+        mint_job_id: u32,
+        item: MintJobItemRow,
+    ) -> Result<()> {
+        insert_mint_job_item(&mut *self.tx, mint_job_id, item).await
+    }
+
+    pub async fn insert_mint_job_batch(
+        &mut self,
+        mint_job_id: u32,
+        transaction_id: Bytes32,
+        input_coin_id: Bytes32,
+    ) -> Result<u32> {
+        insert_mint_job_batch(&mut *self.tx, mint_job_id, transaction_id, input_coin_id).await
+    }
+
+    pub async fn set_mint_job_item_batch(
+        &mut self,
+        mint_job_id: u32,
+        row_index: u32,
+        batch_id: u32,
+        launcher_id: Bytes32,
+    ) -> Result<()> {
+        set_mint_job_item_batch(&mut *self.tx, mint_job_id, row_index, batch_id, launcher_id).await
+    }
+
+    pub async fn fail_mint_job_batch(&mut self, batch_id: u32) -> Result<()> {
+        fail_mint_job_batch(&mut *self.tx, batch_id).await?;
+        release_mint_job_items(&mut *self.tx, batch_id).await
+    }
+}
+
+async fn mint_jobs(conn: impl SqliteExecutor<'_>) -> Result<Vec<MintJobRow>> {
+    sqlx::query!(
+        "
+        SELECT
+            id, name, did_hash, fee, max_cost, is_paused, error, created_timestamp,
+            (
+                SELECT COUNT(*) FROM mint_job_items
+                WHERE mint_job_items.mint_job_id = mint_jobs.id
+            ) AS item_count,
+            (
+                SELECT COUNT(*) FROM mint_job_items
+                WHERE mint_job_items.mint_job_id = mint_jobs.id AND batch_id IS NOT NULL
+            ) AS submitted_count,
+            (
+                SELECT COUNT(*) FROM mint_job_items
+                INNER JOIN mint_job_batches ON mint_job_batches.id = mint_job_items.batch_id
+                WHERE mint_job_items.mint_job_id = mint_jobs.id AND is_confirmed = TRUE
+            ) AS confirmed_count
+        FROM mint_jobs
+        ORDER BY id ASC
+        "
+    )
+    .fetch_all(conn)
+    .await?
+    .into_iter()
+    .map(|row| {
+        Ok(MintJobRow {
+            id: row.id.convert()?,
+            name: row.name,
+            did_id: row.did_hash.convert()?,
+            fee: row.fee.convert()?,
+            max_cost: row.max_cost.convert()?,
+            is_paused: row.is_paused,
+            error: row.error,
+            created_timestamp: row.created_timestamp.convert()?,
+            item_count: row.item_count.convert()?,
+            submitted_count: row.submitted_count.convert()?,
+            confirmed_count: row.confirmed_count.convert()?,
+        })
+    })
+    .collect()
+}
+
+async fn mint_job(conn: impl SqliteExecutor<'_>, id: u32) -> Result<Option<MintJobRow>> {
+    sqlx::query!(
+        "
+        SELECT
+            id, name, did_hash, fee, max_cost, is_paused, error, created_timestamp,
+            (
+                SELECT COUNT(*) FROM mint_job_items
+                WHERE mint_job_items.mint_job_id = mint_jobs.id
+            ) AS item_count,
+            (
+                SELECT COUNT(*) FROM mint_job_items
+                WHERE mint_job_items.mint_job_id = mint_jobs.id AND batch_id IS NOT NULL
+            ) AS submitted_count,
+            (
+                SELECT COUNT(*) FROM mint_job_items
+                INNER JOIN mint_job_batches ON mint_job_batches.id = mint_job_items.batch_id
+                WHERE mint_job_items.mint_job_id = mint_jobs.id AND is_confirmed = TRUE
+            ) AS confirmed_count
+        FROM mint_jobs
+        WHERE id = ?
+        ",
+        id
+    )
+    .fetch_optional(conn)
+    .await?
+    .map(|row| {
+        Ok(MintJobRow {
+            id: row.id.convert()?,
+            name: row.name,
+            did_id: row.did_hash.convert()?,
+            fee: row.fee.convert()?,
+            max_cost: row.max_cost.convert()?,
+            is_paused: row.is_paused,
+            error: row.error,
+            created_timestamp: row.created_timestamp.convert()?,
+            item_count: row.item_count.convert()?,
+            submitted_count: row.submitted_count.convert()?,
+            confirmed_count: row.confirmed_count.convert()?,
+        })
+    })
+    .transpose()
+}
+
+async fn active_mint_job_ids(conn: impl SqliteExecutor<'_>) -> Result<Vec<u32>> {
+    sqlx::query_scalar!(
+        "
+        SELECT id FROM mint_jobs
+        WHERE is_paused = FALSE AND EXISTS (
+            SELECT 1 FROM mint_job_items
+            LEFT JOIN mint_job_batches ON mint_job_batches.id = mint_job_items.batch_id
+            WHERE mint_job_items.mint_job_id = mint_jobs.id
+            AND (mint_job_batches.id IS NULL OR mint_job_batches.is_confirmed = FALSE)
+        )
+        ORDER BY id ASC
+        "
+    )
+    .fetch_all(conn)
+    .await?
+    .into_iter()
+    .map(Convert::convert)
+    .collect()
+}
+
+async fn mint_job_items(
+    conn: impl SqliteExecutor<'_>,
+    mint_job_id: u32,
+) -> Result<Vec<MintJobItemStatusRow>> {
+    sqlx::query!(
+        "
+        SELECT
+            row_index, edition_number, edition_total, data_uris, data_hash,
+            metadata_uris, metadata_hash, license_uris, license_hash,
+            p2_puzzle_hash, royalty_puzzle_hash, royalty_basis_points, launcher_hash,
+            mint_job_batches.transaction_id AS \"transaction_id?\",
+            mint_job_batches.is_confirmed AS \"is_confirmed?\"
+        FROM mint_job_items
+        LEFT JOIN mint_job_batches ON mint_job_batches.id = mint_job_items.batch_id
+        WHERE mint_job_items.mint_job_id = ?
+        ORDER BY row_index ASC
+        ",
+        mint_job_id
+    )
+    .fetch_all(conn)
+    .await?
+    .into_iter()
+    .map(|row| {
+        Ok(MintJobItemStatusRow {
+            item: MintJobItemRow {
+                row_index: row.row_index.convert()?,
+                metadata: NftMetadata {
+                    edition_number: row.edition_number.convert()?,
+                    edition_total: row.edition_total.convert()?,
+                    data_uris: Vec::<String>::from_bytes(&row.data_uris)?,
+                    data_hash: row.data_hash.convert()?,
+                    metadata_uris: Vec::<String>::from_bytes(&row.metadata_uris)?,
+                    metadata_hash: row.metadata_hash.convert()?,
+                    license_uris: Vec::<String>::from_bytes(&row.license_uris)?,
+                    license_hash: row.license_hash.convert()?,
+                },
+                p2_puzzle_hash: row.p2_puzzle_hash.convert()?,
+                royalty_puzzle_hash: row.royalty_puzzle_hash.convert()?,
+                royalty_basis_points: row.royalty_basis_points.convert()?,
+            },
+            launcher_id: row.launcher_hash.convert()?,
+            transaction_id: row.transaction_id.convert()?,
+            is_confirmed: row.is_confirmed.unwrap_or(false),
+        })
+    })
+    .collect()
+}
+
+async fn unsubmitted_mint_job_items(
+    conn: impl SqliteExecutor<'_>,
+    mint_job_id: u32,
+) -> Result<Vec<MintJobItemRow>> {
+    sqlx::query!(
+        "
+        SELECT
+            row_index, edition_number, edition_total, data_uris, data_hash,
+            metadata_uris, metadata_hash, license_uris, license_hash,
+            p2_puzzle_hash, royalty_puzzle_hash, royalty_basis_points
+        FROM mint_job_items
+        WHERE mint_job_id = ? AND batch_id IS NULL
+        ORDER BY row_index ASC
+        ",
+        mint_job_id
+    )
+    .fetch_all(conn)
+    .await?
+    .into_iter()
+    .map(|row| {
+        Ok(MintJobItemRow {
+            row_index: row.row_index.convert()?,
+            metadata: NftMetadata {
+                edition_number: row.edition_number.convert()?,
+                edition_total: row.edition_total.convert()?,
+                data_uris: Vec::<String>::from_bytes(&row.data_uris)?,
+                data_hash: row.data_hash.convert()?,
+                metadata_uris: Vec::<String>::from_bytes(&row.metadata_uris)?,
+                metadata_hash: row.metadata_hash.convert()?,
+                license_uris: Vec::<String>::from_bytes(&row.license_uris)?,
+                license_hash: row.license_hash.convert()?,
+            },
+            p2_puzzle_hash: row.p2_puzzle_hash.convert()?,
+            royalty_puzzle_hash: row.royalty_puzzle_hash.convert()?,
+            royalty_basis_points: row.royalty_basis_points.convert()?,
+        })
+    })
+    .collect()
+}
+
+async fn pending_mint_job_batch(
+    conn: impl SqliteExecutor<'_>,
+    mint_job_id: u32,
+) -> Result<Option<MintJobBatchRow>> {
+    sqlx::query!(
+        "
+        SELECT id, transaction_id, input_coin_hash
+        FROM mint_job_batches
+        WHERE mint_job_id = ? AND is_confirmed = FALSE AND is_failed = FALSE
+        ORDER BY id ASC
+        LIMIT 1
+        ",
+        mint_job_id
+    )
+    .fetch_optional(conn)
+    .await?
+    .map(|row| {
+        Ok(MintJobBatchRow {
+            id: row.id.convert()?,
+            transaction_id: row.transaction_id.convert()?,
+            input_coin_id: row.input_coin_hash.convert()?,
+        })
+    })
+    .transpose()
+}
+
+async fn mint_job_batch_status(
+    conn: impl SqliteExecutor<'_>,
+    batch: MintJobBatchRow,
+) -> Result<MintJobBatchStatus> {
+    let transaction_id = batch.transaction_id.as_ref();
+    let input_coin_id = batch.input_coin_id.as_ref();
+
+    // Transactions are removed from the mempool once they are either confirmed or
+    // rejected, and the input coin is marked as spent in the former case.
+    let row = sqlx::query!(
+        "
+        SELECT
+            EXISTS (SELECT 1 FROM mempool_items WHERE hash = ?) AS is_pending,
+            EXISTS (
+                SELECT 1 FROM coins WHERE hash = ? AND spent_height IS NOT NULL
+            ) AS is_spent
+        ",
+        transaction_id,
+        input_coin_id
+    )
+    .fetch_one(conn)
+    .await?;
+
+    Ok(if row.is_pending != 0 {
+        MintJobBatchStatus::Pending
+    } else if row.is_spent != 0 {
+        MintJobBatchStatus::Confirmed
+    } else {
+        MintJobBatchStatus::Failed
+    })
+}
+
+async fn confirm_mint_job_batch(conn: impl SqliteExecutor<'_>, batch_id: u32) -> Result<()> {
+    sqlx::query!(
+        "UPDATE mint_job_batches SET is_confirmed = TRUE WHERE id = ?",
+        batch_id
+    )
+    .execute(conn)
+    .await?;
+
+    Ok(())
+}
+
+async fn update_mint_job_status(
+    conn: impl SqliteExecutor<'_>,
+    id: u32,
+    is_paused: bool,
+    error: Option<String>,
+) -> Result<()> {
+    sqlx::query!(
+        "UPDATE mint_jobs SET is_paused = ?, error = ? WHERE id = ?",
+        is_paused,
+        error,
+        id
+    )
+    .execute(conn)
+    .await?;
+
+    Ok(())
+}
+
+async fn delete_mint_job(conn: impl SqliteExecutor<'_>, id: u32) -> Result<()> {
+    sqlx::query!("DELETE FROM mint_jobs WHERE id = ?", id)
+        .execute(conn)
+        .await?;
+
+    Ok(())
+}
+
+async fn insert_mint_job(
+    conn: impl SqliteExecutor<'_>,
+    name: String,
+    did_id: Bytes32,
+    fee: u64,
+    max_cost: u64,
+) -> Result<u32> {
+    let did_id = did_id.as_ref();
+    let fee = fee.to_be_bytes().to_vec();
+    let max_cost: i64 = max_cost.try_into()?;
+
+    sqlx::query_scalar!(
+        "INSERT INTO mint_jobs (name, did_hash, fee, max_cost) VALUES (?, ?, ?, ?) RETURNING id",
+        name,
+        did_id,
+        fee,
+        max_cost
+    )
+    .fetch_one(conn)
+    .await?
+    .convert()
+}
+
+async fn insert_mint_job_item(
+    conn: impl SqliteExecutor<'_>,
+    mint_job_id: u32,
+    item: MintJobItemRow,
+) -> Result<()> {
+    let edition_number: i64 = item.metadata.edition_number.try_into()?;
+    let edition_total: i64 = item.metadata.edition_total.try_into()?;
+    let data_uris = item.metadata.data_uris.to_bytes()?;
+    let data_hash = item.metadata.data_hash.as_deref();
+    let metadata_uris = item.metadata.metadata_uris.to_bytes()?;
+    let metadata_hash = item.metadata.metadata_hash.as_deref();
+    let license_uris = item.metadata.license_uris.to_bytes()?;
+    let license_hash = item.metadata.license_hash.as_deref();
+    let p2_puzzle_hash = item.p2_puzzle_hash.as_deref();
+    let royalty_puzzle_hash = item.royalty_puzzle_hash.as_deref();
+
+    sqlx::query!(
+        "
+        INSERT INTO mint_job_items (
+            mint_job_id, row_index, edition_number, edition_total, data_uris, data_hash,
+            metadata_uris, metadata_hash, license_uris, license_hash,
+            p2_puzzle_hash, royalty_puzzle_hash, royalty_basis_points
+        )
+        VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+        ",
+        mint_job_id,
+        item.row_index,
+        edition_number,
+        edition_total,
+        data_uris,
+        data_hash,
+        metadata_uris,
+        metadata_hash,
+        license_uris,
+        license_hash,
+        p2_puzzle_hash,
+        royalty_puzzle_hash,
+        item.royalty_basis_points
+    )
+    .execute(conn)
+    .await?;
+
+    Ok(())
+}
+
+async fn insert_mint_job_batch(
+    conn: impl SqliteExecutor<'_>,
+    mint_job_id: u32,
+    transaction_id: Bytes32,
+    input_coin_id: Bytes32,
+) -> Result<u32> {
+    let transaction_id = transaction_id.as_ref();
+    let input_coin_id = input_coin_id.as_ref();
+
+    sqlx::query_scalar!(
+        "
+        INSERT INTO mint_job_batches (mint_job_id, transaction_id, input_coin_hash)
+        VALUES (?, ?, ?)
+        RETURNING id
+        ",
+        mint_job_id,
+        transaction_id,
+        input_coin_id
+    )
+    .fetch_one(conn)
+    .await?
+    .convert()
+}
+
+async fn set_mint_job_item_batch(
+    conn: impl SqliteExecutor<'_>,
+    mint_job_id: u32,
+    row_index: u32,
+    batch_id: u32,
+    launcher_id: Bytes32,
+) -> Result<()> {
+    let launcher_id = launcher_id.as_ref();
+
+    sqlx::query!(
+        "
+        UPDATE mint_job_items SET batch_id = ?, launcher_hash = ?
+        WHERE mint_job_id = ? AND row_index = ?
+        ",
+        batch_id,
+        launcher_id,
+        mint_job_id,
+        row_index
+    )
+    .execute(conn)
+    .await?;
+
+    Ok(())
+}
+
+async fn fail_mint_job_batch(conn: impl SqliteExecutor<'_>, batch_id: u32) -> Result<()> {
+    sqlx::query!(
+        "UPDATE mint_job_batches SET is_failed = TRUE WHERE id = ?",
+        batch_id
+    )
+    .execute(conn)
+    .await?;
+
+    Ok(())
+}
+
+async fn release_mint_job_items(conn: impl SqliteExecutor<'_>, batch_id: u32) -> Result<()> {
+    sqlx::query!(
+        "UPDATE mint_job_items SET batch_id = NULL, launcher_hash = NULL WHERE batch_id = ?",
+        batch_id
+    )
+    .execute(conn)
+    .await?;
+
+    Ok(())
+}
@@ -0,0 +1,407 @@
+use chia::protocol::Bytes32;
+use sqlx::SqliteExecutor;
+
+use crate::{Convert, Database, DatabaseError, DatabaseTx, OfferRow, OfferStatus, Result};
+
+#[derive(Debug, Clone)]
+pub struct OfferGroupRow {
+    pub id: u32,
+    pub offered_asset_id: Option<Bytes32>,
+    pub requested_asset_id: Option<Bytes32>,
+    pub created_timestamp: u64,
+    pub offer_count: u32,
+    pub active_count: u32,
+    pub policy: OfferGroupPolicy,
+    pub stop_reason: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct OfferGroupPolicy {
+    pub auto_repost: bool,
+    pub price_spread: String,
+    pub stop_price: Option<String>,
+    pub max_inventory: Option<u64>,
+}
+
+#[derive(Debug, Clone)]
+pub struct OfferGroupOfferRow {
+    pub offer_group_id: u32,
+    pub offer: OfferRow,
+    pub price: String,
+    pub is_reversed: bool,
+}
+
+impl Database {
+    pub async fn offer_groups(&self) -> Result<Vec<OfferGroupRow>> {
+        offer_groups(&self.pool).await
+    }
+
+    pub async fn offer_group(&self, id: u32) -> Result<Option<OfferGroupRow>> {
+        offer_group(&self.pool, id).await
+    }
+
+    pub async fn offer_group_offers(&self, id: u32) -> Result<Vec<OfferGroupOfferRow>> {
+        offer_group_offers(&self.pool, id).await
+    }
+
+    /// Offers which have been taken from groups that repost them, but haven't been handled yet.
+    pub async fn taken_offer_group_offers(&self) -> Result<Vec<OfferGroupOfferRow>> {
+        taken_offer_group_offers(&self.pool).await
+    }
+
+    pub async fn set_offer_group_offer_reposted(&self, offer_id: Bytes32) -> Result<()> {
+        set_offer_group_offer_reposted(&self.pool, offer_id).await
+    }
+
+    pub async fn stop_offer_group(&self, id: u32, reason: String) -> Result<()> {
+        stop_offer_group(&self.pool, id, reason).await
+    }
+}
+
+impl DatabaseTx<'_> {
+    pub async fn insert_offer_group(
+        &mut self,
+        offered_asset_id: Option<Bytes32>,
+        requested_asset_id: Option<Bytes32>,
+    ) -> Result<u32> {
+        insert_offer_group(&mut *self.tx, offered_asset_id, requested_asset_id).await
+    }
+
+    pub async fn delete_offer_group(&mut self, id: u32) -> Result<()> {
+        delete_offer_group(&mut *self.tx, id).await
+    }
+
+    pub async fn insert_offer_group_offer(
+        &mut self,
+        group_id: u32,
+        offer_id: Bytes32,
+        price: String,
+        is_reversed: bool,
+    ) -> Result<()> {
+        insert_offer_group_offer(&mut *self.tx, group_id, offer_id, price, is_reversed).await
+    }
+
+    /// Replaces the repost policy of a group and clears its stop reason. Offers which have
+    /// already been taken are marked as reposted, so that only offers taken from now on are
+    /// replaced.
+    pub async fn set_offer_group_policy(
+        &mut self,
+        id: u32,
+        policy: OfferGroupPolicy,
+    ) -> Result<()> {
+        set_offer_group_policy(&mut *self.tx, id, policy).await?;
+        mark_taken_offer_group_offers_reposted(&mut *self.tx, id).await
+    }
+}
+
+async fn offer_groups(conn: impl SqliteExecutor<'_>) -> Result<Vec<OfferGroupRow>> {
+    sqlx::query!(
+        "
+        SELECT
+            offer_groups.id, offered_asset_hash, requested_asset_hash, created_timestamp,
+            auto_repost, price_spread, stop_price, max_inventory, stop_reason,
+            COUNT(offers.id) AS offer_count,
+            COALESCE(SUM(offers.status = 1), 0) AS active_count
+        FROM offer_groups
+        LEFT JOIN offer_group_offers ON offer_group_offers.offer_group_id = offer_groups.id
+        LEFT JOIN offers ON offers.id = offer_group_offers.offer_id
+        GROUP BY offer_groups.id
+        ORDER BY offer_groups.id DESC
+        "
+    )
+    .fetch_all(conn)
+    .await?
+    .into_iter()
+    .map(|row| {
+        Ok(OfferGroupRow {
+            id: row.id.convert()?,
+            offered_asset_id: row.offered_asset_hash.convert()?,
+            requested_asset_id: row.requested_asset_hash.convert()?,
+            created_timestamp: row.created_timestamp.convert()?,
+            offer_count: row.offer_count.convert()?,
+            active_count: row.active_count.convert()?,
+            policy: OfferGroupPolicy {
+                auto_repost: row.auto_repost,
+                price_spread: row.price_spread,
+                stop_price: row.stop_price,
+                max_inventory: row.max_inventory.convert()?,
+            },
+            stop_reason: row.stop_reason,
+        })
+    })
+    .collect()
+}
+
+async fn offer_group(conn: impl SqliteExecutor<'_>, id: u32) -> Result<Option<OfferGroupRow>> {
+    sqlx::query!(
+        "
+        SELECT
+            offer_groups.id, offered_asset_hash, requested_asset_hash, created_timestamp,
+            auto_repost, price_spread, stop_price, max_inventory, stop_reason,
+            COUNT(offers.id) AS offer_count,
+            COALESCE(SUM(offers.status = 1), 0) AS active_count
+        FROM offer_groups
+        LEFT JOIN offer_group_offers ON offer_group_offers.offer_group_id = offer_groups.id
+        LEFT JOIN offers ON offers.id = offer_group_offers.offer_id
+        WHERE offer_groups.id = ?
+        GROUP BY offer_groups.id
+        ",
+        id
+    )
+    .fetch_optional(conn)
+    .await?
+    .map(|row| {
+        Ok(OfferGroupRow {
+            id: row.id.convert()?,
+            offered_asset_id: row.offered_asset_hash.convert()?,
+            requested_asset_id: row.requested_asset_hash.convert()?,
+            created_timestamp: row.created_timestamp.convert()?,
+            offer_count: row.offer_count.convert()?,
+            active_count: row.active_count.convert()?,
+            policy: OfferGroupPolicy {
+                auto_repost: row.auto_repost,
+                price_spread: row.price_spread,
+                stop_price: row.stop_price,
+                max_inventory: row.max_inventory.convert()?,
+            },
+            stop_reason: row.stop_reason,
+        })
+    })
+    .transpose()
+}
+
+async fn offer_group_offers(
+    conn: impl SqliteExecutor<'_>,
+    id: u32,
+) -> Result<Vec<OfferGroupOfferRow>> {
+    sqlx::query!(
+        "
+        SELECT
+            offer_group_id, offers.hash AS offer_id, offers.encoded_offer, offers.fee,
+            offers.status, offers.expiration_height, offers.expiration_timestamp,
+            offers.inserted_timestamp, offers.invalid_reason, offers.auto_recreate,
+            recreated.hash AS \"recreated_offer_id?\", offers.recreate_error, price, is_reversed
+        FROM offer_group_offers
+        INNER JOIN offers ON offers.id = offer_group_offers.offer_id
+        LEFT JOIN offers AS recreated ON recreated.id = offers.recreated_offer_id
+        WHERE offer_group_id = ?
+        ORDER BY offer_group_offers.id ASC
+        ",
+        id
+    )
+    .fetch_all(conn)
+    .await?
+    .into_iter()
+    .map(|row| {
+        Ok(OfferGroupOfferRow {
+            offer_group_id: row.offer_group_id.convert()?,
+            offer: OfferRow {
+                offer_id: row.offer_id.convert()?,
+                encoded_offer: row.encoded_offer,
+                expiration_height: row.expiration_height.convert()?,
+                expiration_timestamp: row.expiration_timestamp.convert()?,
+                fee: row.fee.convert()?,
+                status: match row.status {
+                    0 => OfferStatus::Pending,
+                    1 => OfferStatus::Active,
+                    2 => OfferStatus::Completed,
+                    3 => OfferStatus::Cancelled,
+                    4 => OfferStatus::Expired,
+                    5 => OfferStatus::Invalid,
+                    _ => return Err(DatabaseError::InvalidEnumVariant),
+                },
+                inserted_timestamp: row.inserted_timestamp.convert()?,
+                invalid_reason: row.invalid_reason,
+                auto_recreate: row.auto_recreate,
+                recreated_offer_id: row.recreated_offer_id.convert()?,
+                recreate_error: row.recreate_error,
+            },
+            price: row.price,
+            is_reversed: row.is_reversed,
+        })
+    })
+    .collect()
+}
+
+async fn taken_offer_group_offers(
+    conn: impl SqliteExecutor<'_>,
+) -> Result<Vec<OfferGroupOfferRow>> {
+    sqlx::query!(
+        "
+        SELECT
+            offer_group_id, offers.hash AS offer_id, offers.encoded_offer, offers.fee,
+            offers.status, offers.expiration_height, offers.expiration_timestamp,
+            offers.inserted_timestamp, offers.invalid_reason, offers.auto_recreate,
+            recreated.hash AS \"recreated_offer_id?\", offers.recreate_error, price, is_reversed
+        FROM offer_group_offers
+        INNER JOIN offers ON offers.id = offer_group_offers.offer_id
+        LEFT JOIN offers AS recreated ON recreated.id = offers.recreated_offer_id
+        INNER JOIN offer_groups ON offer_groups.id = offer_group_offers.offer_group_id
+        WHERE offers.status = 2 AND is_reposted = FALSE AND auto_repost = TRUE
+        ORDER BY offer_group_offers.id ASC
+        "
+    )
+    .fetch_all(conn)
+    .await?
+    .into_iter()
+    .map(|row| {
+        Ok(OfferGroupOfferRow {
+            offer_group_id: row.offer_group_id.convert()?,
+            offer: OfferRow {
+                offer_id: row.offer_id.convert()?,
+                encoded_offer: row.encoded_offer,
+                expiration_height: row.expiration_height.convert()?,
+                expiration_timestamp: row.expiration_timestamp.convert()?,
+                fee: row.fee.convert()?,
+                status: match row.status {
+                    0 => OfferStatus::Pending,
+                    1 => OfferStatus::Active,
+                    2 => OfferStatus::Completed,
+                    3 => OfferStatus::Cancelled,
+                    4 => OfferStatus::Expired,
+                    5 => OfferStatus::Invalid,
+                    _ => return Err(DatabaseError::InvalidEnumVariant),
+                },
+                inserted_timestamp: row.inserted_timestamp.convert()?,
+                invalid_reason: row.invalid_reason,
+                auto_recreate: row.auto_recreate,
+                recreated_offer_id: row.recreated_offer_id.convert()?,
+                recreate_error: row.recreate_error,
+            },
+            price: row.price,
+            is_reversed: row.is_reversed,
+        })
+    })
+    .collect()
+}
+
+async fn set_offer_group_offer_reposted(
+    conn: impl SqliteExecutor<'_>,
+    offer_id: Bytes32,
+) -> Result<()> {
+    let offer_id = offer_id.as_ref();
+
+    sqlx::query!(
+        "
+        UPDATE offer_group_offers SET is_reposted = TRUE
+        WHERE offer_id = (SELECT id FROM offers WHERE hash = ?)
+        ",
+        offer_id
+    )
+    .execute(conn)
+    .await?;
+
+    Ok(())
+}
+
+async fn mark_taken_offer_group_offers_reposted(
+    conn: impl SqliteExecutor<'_>,
+    id: u32,
+) -> Result<()> {
+    sqlx::query!(
+        "
+        UPDATE offer_group_offers SET is_reposted = TRUE
+        WHERE offer_group_id = ?
+        AND offer_id IN (SELECT id FROM offers WHERE status = 2)
+        ",
+        id
+    )
+    .execute(conn)
+    .await?;
+
+    Ok(())
+}
+
+async fn set_offer_group_policy(
+    conn: impl SqliteExecutor<'_>,
+    id: u32,
+    policy: OfferGroupPolicy,
+) -> Result<()> {
+    let max_inventory = policy
+        .max_inventory
+        .map(|amount| amount.to_be_bytes().to_vec());
+
+    sqlx::query!(
+        "
+        UPDATE offer_groups
+        SET auto_repost = ?, price_spread = ?, stop_price = ?, max_inventory = ?, stop_reason = NULL
+        WHERE id = ?
+        ",
+        policy.auto_repost,
+        policy.price_spread,
+        policy.stop_price,
+        max_inventory,
+        id
+    )
+    .execute(conn)
+    .await?;
+
+    Ok(())
+}
+
+async fn stop_offer_group(conn: impl SqliteExecutor<'_>, id: u32, reason: String) -> Result<()> {
+    sqlx::query!(
+        "UPDATE offer_groups SET auto_repost = FALSE, stop_reason = ? WHERE id = ?",
+        reason,
+        id
+    )
+    .execute(conn)
+    .await?;
+
+    Ok(())
+}
+
+async fn insert_offer_group(
+    conn: impl SqliteExecutor<'_>,
+    offered_asset_id: Option<Bytes32>,
+    requested_asset_id: Option<Bytes32>,
+) -> Result<u32> {
+    let offered_asset_id = offered_asset_id.as_deref();
+    let requested_asset_id = requested_asset_id.as_deref();
+
+    sqlx::query_scalar!(
+        "
+        INSERT INTO offer_groups (offered_asset_hash, requested_asset_hash)
+        VALUES (?, ?)
+        RETURNING id
+        ",
+        offered_asset_id,
+        requested_asset_id
+    )
+    .fetch_one(conn)
+    .await?
+    .convert()
+}
+
+async fn delete_offer_group(conn: impl SqliteExecutor<'_>, id: u32) -> Result<()> {
+    sqlx::query!("DELETE FROM offer_groups WHERE id = ?", id)
+        .execute(conn)
+        .await?;
+
+    Ok(())
+}
+
+async fn insert_offer_group_offer(
+    conn: impl SqliteExecutor<'_>,
+    group_id: u32,
+    offer_id: Bytes32,
+    price: String,
+    is_reversed: bool,
+) -> Result<()> {
+    let offer_id = offer_id.as_ref();
+
+    sqlx::query!(
+        "
+        INSERT INTO offer_group_offers (offer_group_id, offer_id, price, is_reversed)
+        VALUES (?, (SELECT id FROM offers WHERE hash = ?), ?, ?)
+        ",
+        group_id,
+        offer_id,
+        price,
+        is_reversed
+    )
+    .execute(conn)
+    .await?;
+
+    Ok(())
+}
@@ -0,0 +1,80 @@
+use chia::protocol::Bytes32;
+use sqlx::SqliteExecutor;
+
+use crate::{Convert, Database, Result};
+
+#[derive(Debug, Clone)]
+pub struct OfferPublicationRow {
+    pub exchange: String,
+    pub code: String,
+    pub published_timestamp: u64,
+}
+
+impl Database {
+    pub async fn offer_publications(&self, offer_id: Bytes32) -> Result<Vec<OfferPublicationRow>> {
+        offer_publications(&self.pool, offer_id).await
+    }
+
+    pub async fn insert_offer_publication(
+        &self,
+        offer_id: Bytes32,
+        exchange: String,
+        code: String,
+    ) -> Result<()> {
+        insert_offer_publication(&self.pool, offer_id, exchange, code).await
+    }
+}
+
+async fn offer_publications(
+    conn: impl SqliteExecutor<'_>,
+    offer_id: Bytes32,
+) -> Result<Vec<OfferPublicationRow>> {
+    let offer_id = offer_id.as_ref();
+
+    sqlx::query!(
+        "
+        SELECT exchange, code, published_timestamp
+        FROM offer_publications
+        WHERE offer_id = (SELECT id FROM offers WHERE hash = ?)
+        ORDER BY offer_publications.id ASC
+        ",
+        offer_id
+    )
+    .fetch_all(conn)
+    .await?
+    .into_iter()
+    .map(|row| {
+        Ok(OfferPublicationRow {
+            exchange: row.exchange,
+            code: row.code,
+            published_timestamp: row.published_timestamp.convert()?,
+        })
+    })
+    .collect()
+}
+
+async fn insert_offer_publication(
+    conn: impl SqliteExecutor<'_>,
+    offer_id: Bytes32,
+    exchange: String,
+    code: String,
+) -> Result<()> {
+    let offer_id = offer_id.as_ref();
+
+    sqlx::query!(
+        "
+        INSERT INTO offer_publications (offer_id, exchange, code)
+        VALUES ((SELECT id FROM offers WHERE hash = ?), ?, ?)
+        ON CONFLICT (offer_id, exchange) DO UPDATE SET
+            code = excluded.code,
+            published_timestamp = unixepoch()
+        ",
+        offer_id,
+        exchange,
+        code
+    )
+    .execute(conn)
+    .await?;
+
+    Ok(())
+}
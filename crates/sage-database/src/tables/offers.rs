@@ -10,6 +10,7 @@ pub enum OfferStatus {
     Completed = 2,
     Cancelled = 3,
     Expired = 4,
+    Invalid = 5,
 }
 
 #[derive(Debug, Clone)]
@@ -21,6 +22,10 @@ pub struct OfferRow {
     pub fee: u64,
     pub status: OfferStatus,
     pub inserted_timestamp: u64,
+    pub invalid_reason: Option<String>,
+    pub auto_recreate: bool,
+    pub recreated_offer_id: Option<Bytes32>,
+    pub recreate_error: Option<String>,
 }
 
 #[derive(Debug, Clone)]
@@ -60,6 +65,22 @@ impl Database {
     ) -> Result<Vec<OfferRow>> {
         offers_for_asset(&self.pool, asset_id, status).await
     }
+
+    pub async fn set_offer_auto_recreate(
+        &self,
+        offer_id: Bytes32,
+        auto_recreate: bool,
+    ) -> Result<()> {
+        set_offer_auto_recreate(&self.pool, offer_id, auto_recreate).await
+    }
+
+    pub async fn offers_to_recreate(&self) -> Result<Vec<OfferRow>> {
+        offers_to_recreate(&self.pool).await
+    }
+
+    pub async fn set_offer_recreate_error(&self, offer_id: Bytes32, error: String) -> Result<()> {
+        set_offer_recreate_error(&self.pool, offer_id, error).await
+    }
 }
 
 impl DatabaseTx<'_> {
@@ -71,6 +92,10 @@ impl DatabaseTx<'_> {
         insert_offered_coin(&mut *self.tx, offer_id, coin_id).await
     }
 
+    pub async fn delete_offer(&mut self, offer_id: Bytes32) -> Result<()> {
+        delete_offer(&mut *self.tx, offer_id).await
+    }
+
     pub async fn insert_offer_asset(
         &mut self,
         offer_id: Bytes32,
@@ -105,6 +130,22 @@ impl DatabaseTx<'_> {
     ) -> Result<Vec<OfferRow>> {
         offers_for_asset(&mut *self.tx, asset_id, status).await
     }
+
+    pub async fn active_offers_for_coin(&mut self, coin_id: Bytes32) -> Result<Vec<OfferRow>> {
+        active_offers_for_coin(&mut *self.tx, coin_id).await
+    }
+
+    pub async fn invalidate_offer(&mut self, offer_id: Bytes32, reason: String) -> Result<()> {
+        invalidate_offer(&mut *self.tx, offer_id, reason).await
+    }
+
+    pub async fn set_offer_recreated(
+        &mut self,
+        offer_id: Bytes32,
+        recreated_offer_id: Bytes32,
+    ) -> Result<()> {
+        set_offer_recreated(&mut *self.tx, offer_id, recreated_offer_id).await
+    }
 }
 
 async fn offers_for_asset(
@@ -118,17 +159,22 @@ async fn offers_for_asset(
     let rows = sqlx::query!(
         "SELECT
             offers.hash as offer_id,
-            encoded_offer,
-            fee,
-            status,
-            expiration_height,
-            expiration_timestamp,
-            inserted_timestamp
+            offers.encoded_offer,
+            offers.fee,
+            offers.status,
+            offers.expiration_height,
+            offers.expiration_timestamp,
+            offers.inserted_timestamp,
+            offers.invalid_reason,
+            offers.auto_recreate,
+            recreated.hash AS \"recreated_offer_id?\",
+            offers.recreate_error
         FROM offers
+        LEFT JOIN offers AS recreated ON recreated.id = offers.recreated_offer_id
         INNER JOIN offer_assets ON offers.id = offer_assets.offer_id
         INNER JOIN assets ON offer_assets.asset_id = assets.id
         WHERE assets.hash = ? AND offers.status = ? OR ? IS NULL
-        ORDER BY inserted_timestamp DESC",
+        ORDER BY offers.inserted_timestamp DESC",
         asset_id_ref,
         status_value,
         status_value
@@ -150,9 +196,14 @@ async fn offers_for_asset(
                     2 => OfferStatus::Completed,
                     3 => OfferStatus::Cancelled,
                     4 => OfferStatus::Expired,
+                    5 => OfferStatus::Invalid,
                     _ => return Err(crate::DatabaseError::InvalidEnumVariant),
                 },
                 inserted_timestamp: row.inserted_timestamp as u64,
+                invalid_reason: row.invalid_reason,
+                auto_recreate: row.auto_recreate,
+                recreated_offer_id: row.recreated_offer_id.convert()?,
+                recreate_error: row.recreate_error,
             })
         })
         .collect()
@@ -222,9 +273,9 @@ async fn insert_offer(conn: impl SqliteExecutor<'_>, offer: OfferRow) -> Result<
         "
         INSERT OR IGNORE INTO offers (
             hash, encoded_offer, fee, status,
-            expiration_height, expiration_timestamp, inserted_timestamp
+            expiration_height, expiration_timestamp, inserted_timestamp, auto_recreate
         )
-        VALUES (?, ?, ?, ?, ?, ?, ?)
+        VALUES (?, ?, ?, ?, ?, ?, ?, ?)
         ",
     )
     .bind(offer_id_ref)
@@ -234,6 +285,7 @@ async fn insert_offer(conn: impl SqliteExecutor<'_>, offer: OfferRow) -> Result<
     .bind(expiration_height)
     .bind(expiration_timestamp)
     .bind(inserted_timestamp)
+    .bind(offer.auto_recreate)
     .execute(conn)
     .await?;
     Ok(())
@@ -297,14 +349,20 @@ async fn offer(conn: impl SqliteExecutor<'_>, offer_id: Bytes32) -> Result<Optio
     let offer_id_ref = offer_id.as_ref();
     let row = sqlx::query!(
         "SELECT
-            hash as offer_id,
-            encoded_offer,
-            fee,
-            status,
-            expiration_height,
-            expiration_timestamp,
-            inserted_timestamp
-        FROM offers WHERE hash = ?",
+            offers.hash as offer_id,
+            offers.encoded_offer,
+            offers.fee,
+            offers.status,
+            offers.expiration_height,
+            offers.expiration_timestamp,
+            offers.inserted_timestamp,
+            offers.invalid_reason,
+            offers.auto_recreate,
+            recreated.hash AS \"recreated_offer_id?\",
+            offers.recreate_error
+        FROM offers
+        LEFT JOIN offers AS recreated ON recreated.id = offers.recreated_offer_id
+        WHERE offers.hash = ?",
         offer_id_ref
     )
     .fetch_optional(conn)
@@ -323,9 +381,14 @@ async fn offer(conn: impl SqliteExecutor<'_>, offer_id: Bytes32) -> Result<Optio
                 2 => OfferStatus::Completed,
                 3 => OfferStatus::Cancelled,
                 4 => OfferStatus::Expired,
+                5 => OfferStatus::Invalid,
                 _ => return Err(crate::DatabaseError::InvalidEnumVariant),
             },
             inserted_timestamp: row.inserted_timestamp as u64,
+            invalid_reason: row.invalid_reason,
+            auto_recreate: row.auto_recreate,
+            recreated_offer_id: row.recreated_offer_id.convert()?,
+            recreate_error: row.recreate_error,
         })
     })
     .transpose()
@@ -338,16 +401,21 @@ async fn offers(
     let status_value = status.map(|s| s as u8);
     let rows = sqlx::query!(
         "SELECT
-            hash as offer_id,
-            encoded_offer,
-            fee,
-            status,
-            expiration_height,
-            expiration_timestamp,
-            inserted_timestamp
-        FROM offers 
-        WHERE status = ? OR ? IS NULL
-        ORDER BY inserted_timestamp DESC",
+            offers.hash as offer_id,
+            offers.encoded_offer,
+            offers.fee,
+            offers.status,
+            offers.expiration_height,
+            offers.expiration_timestamp,
+            offers.inserted_timestamp,
+            offers.invalid_reason,
+            offers.auto_recreate,
+            recreated.hash AS \"recreated_offer_id?\",
+            offers.recreate_error
+        FROM offers
+        LEFT JOIN offers AS recreated ON recreated.id = offers.recreated_offer_id
+        WHERE offers.status = ? OR ? IS NULL
+        ORDER BY offers.inserted_timestamp DESC",
         status_value,
         status_value
     )
@@ -368,9 +436,14 @@ async fn offers(
                     2 => OfferStatus::Completed,
                     3 => OfferStatus::Cancelled,
                     4 => OfferStatus::Expired,
+                    5 => OfferStatus::Invalid,
                     _ => return Err(crate::DatabaseError::InvalidEnumVariant),
                 },
                 inserted_timestamp: row.inserted_timestamp as u64,
+                invalid_reason: row.invalid_reason,
+                auto_recreate: row.auto_recreate,
+                recreated_offer_id: row.recreated_offer_id.convert()?,
+                recreate_error: row.recreate_error,
             })
         })
         .collect()
@@ -398,3 +471,186 @@ async fn update_offer_status(
         .await?;
     Ok(())
 }
+
+/// The offers which are still active and have the coin locked in them.
+async fn active_offers_for_coin(
+    conn: impl SqliteExecutor<'_>,
+    coin_id: Bytes32,
+) -> Result<Vec<OfferRow>> {
+    let coin_id_ref = coin_id.as_ref();
+
+    let rows = sqlx::query!(
+        "SELECT
+            offers.hash as offer_id,
+            offers.encoded_offer,
+            offers.fee,
+            offers.status,
+            offers.expiration_height,
+            offers.expiration_timestamp,
+            offers.inserted_timestamp,
+            offers.invalid_reason,
+            offers.auto_recreate,
+            recreated.hash AS \"recreated_offer_id?\",
+            offers.recreate_error
+        FROM offers
+        LEFT JOIN offers AS recreated ON recreated.id = offers.recreated_offer_id
+        INNER JOIN offer_coins ON offer_coins.offer_id = offers.id
+        INNER JOIN coins ON coins.id = offer_coins.coin_id
+        WHERE coins.hash = ? AND offers.status <= 1",
+        coin_id_ref
+    )
+    .fetch_all(conn)
+    .await?;
+
+    rows.into_iter()
+        .map(|row| {
+            Ok(OfferRow {
+                offer_id: row.offer_id.convert()?,
+                encoded_offer: row.encoded_offer,
+                expiration_height: row.expiration_height.map(|h| h as u32),
+                expiration_timestamp: row.expiration_timestamp.map(|t| t as u64),
+                fee: row.fee.convert()?,
+                status: match row.status {
+                    0 => OfferStatus::Pending,
+                    1 => OfferStatus::Active,
+                    2 => OfferStatus::Completed,
+                    3 => OfferStatus::Cancelled,
+                    4 => OfferStatus::Expired,
+                    5 => OfferStatus::Invalid,
+                    _ => return Err(crate::DatabaseError::InvalidEnumVariant),
+                },
+                inserted_timestamp: row.inserted_timestamp as u64,
+                invalid_reason: row.invalid_reason,
+                auto_recreate: row.auto_recreate,
+                recreated_offer_id: row.recreated_offer_id.convert()?,
+                recreate_error: row.recreate_error,
+            })
+        })
+        .collect()
+}
+
+/// The invalid offers with auto recreate turned on, which haven't been made again yet.
+async fn offers_to_recreate(conn: impl SqliteExecutor<'_>) -> Result<Vec<OfferRow>> {
+    let rows = sqlx::query!(
+        "SELECT
+            offers.hash as offer_id,
+            offers.encoded_offer,
+            offers.fee,
+            offers.status,
+            offers.expiration_height,
+            offers.expiration_timestamp,
+            offers.inserted_timestamp,
+            offers.invalid_reason,
+            offers.auto_recreate,
+            recreated.hash AS \"recreated_offer_id?\",
+            offers.recreate_error
+        FROM offers
+        LEFT JOIN offers AS recreated ON recreated.id = offers.recreated_offer_id
+        WHERE offers.status = 5
+        AND offers.auto_recreate = TRUE
+        AND offers.recreated_offer_id IS NULL
+        ORDER BY offers.inserted_timestamp ASC"
+    )
+    .fetch_all(conn)
+    .await?;
+
+    rows.into_iter()
+        .map(|row| {
+            Ok(OfferRow {
+                offer_id: row.offer_id.convert()?,
+                encoded_offer: row.encoded_offer,
+                expiration_height: row.expiration_height.map(|h| h as u32),
+                expiration_timestamp: row.expiration_timestamp.map(|t| t as u64),
+                fee: row.fee.convert()?,
+                status: match row.status {
+                    0 => OfferStatus::Pending,
+                    1 => OfferStatus::Active,
+                    2 => OfferStatus::Completed,
+                    3 => OfferStatus::Cancelled,
+                    4 => OfferStatus::Expired,
+                    5 => OfferStatus::Invalid,
+                    _ => return Err(crate::DatabaseError::InvalidEnumVariant),
+                },
+                inserted_timestamp: row.inserted_timestamp as u64,
+                invalid_reason: row.invalid_reason,
+                auto_recreate: row.auto_recreate,
+                recreated_offer_id: row.recreated_offer_id.convert()?,
+                recreate_error: row.recreate_error,
+            })
+        })
+        .collect()
+}
+
+async fn invalidate_offer(
+    conn: impl SqliteExecutor<'_>,
+    offer_id: Bytes32,
+    reason: String,
+) -> Result<()> {
+    let offer_id_ref = offer_id.as_ref();
+
+    sqlx::query!(
+        "UPDATE offers SET status = 5, invalid_reason = ? WHERE hash = ?",
+        reason,
+        offer_id_ref
+    )
+    .execute(conn)
+    .await?;
+
+    Ok(())
+}
+
+async fn set_offer_auto_recreate(
+    conn: impl SqliteExecutor<'_>,
+    offer_id: Bytes32,
+    auto_recreate: bool,
+) -> Result<()> {
+    let offer_id_ref = offer_id.as_ref();
+
+    sqlx::query!(
+        "UPDATE offers SET auto_recreate = ?, recreate_error = NULL WHERE hash = ?",
+        auto_recreate,
+        offer_id_ref
+    )
+    .execute(conn)
+    .await?;
+
+    Ok(())
+}
+
+async fn set_offer_recreated(
+    conn: impl SqliteExecutor<'_>,
+    offer_id: Bytes32,
+    recreated_offer_id: Bytes32,
+) -> Result<()> {
+    let offer_id_ref = offer_id.as_ref();
+    let recreated_offer_id_ref = recreated_offer_id.as_ref();
+
+    sqlx::query!(
+        "UPDATE offers SET recreated_offer_id = (SELECT id FROM offers WHERE hash = ?)
+        WHERE hash = ?",
+        recreated_offer_id_ref,
+        offer_id_ref
+    )
+    .execute(conn)
+    .await?;
+
+    Ok(())
+}
+
+async fn set_offer_recreate_error(
+    conn: impl SqliteExecutor<'_>,
+    offer_id: Bytes32,
+    error: String,
+) -> Result<()> {
+    let offer_id_ref = offer_id.as_ref();
+
+    sqlx::query!(
+        "UPDATE offers SET auto_recreate = FALSE, recreate_error = ? WHERE hash = ?",
+        error,
+        offer_id_ref
+    )
+    .execute(conn)
+    .await?;
+
+    Ok(())
+}
@@ -0,0 +1,492 @@
+use chia::{
+    protocol::{Bytes, Bytes32},
+    traits::Streamable,
+};
+use sqlx::SqliteExecutor;
+
+use crate::{Convert, Database, DatabaseTx, Result};
+
+#[derive(Debug, Clone)]
+pub struct PayoutRow {
+    pub id: u32,
+    pub name: String,
+    pub fee: u64,
+    pub max_cost: u64,
+    pub is_paused: bool,
+    pub error: Option<String>,
+    pub created_timestamp: u64,
+    pub payment_count: u32,
+    pub submitted_count: u32,
+    pub confirmed_count: u32,
+}
+
+#[derive(Debug, Clone)]
+pub struct PayoutPaymentRow {
+    pub row_index: u32,
+    pub asset_id: Option<Bytes32>,
+    pub puzzle_hash: Bytes32,
+    pub amount: u64,
+    pub memos: Vec<Bytes>,
+}
+
+#[derive(Debug, Clone)]
+pub struct PayoutPaymentStatusRow {
+    pub payment: PayoutPaymentRow,
+    pub transaction_id: Option<Bytes32>,
+    pub is_confirmed: bool,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct PayoutBatchRow {
+    pub id: u32,
+    pub transaction_id: Bytes32,
+    pub output_coin_id: Bytes32,
+}
+
+impl Database {
+    pub async fn payouts(&self) -> Result<Vec<PayoutRow>> {
+        payouts(&self.pool).await
+    }
+
+    pub async fn payout(&self, id: u32) -> Result<Option<PayoutRow>> {
+        payout(&self.pool, id).await
+    }
+
+    pub async fn active_payout_ids(&self) -> Result<Vec<u32>> {
+        active_payout_ids(&self.pool).await
+    }
+
+    pub async fn payout_payments(&self, payout_id: u32) -> Result<Vec<PayoutPaymentStatusRow>> {
+        payout_payments(&self.pool, payout_id).await
+    }
+
+    pub async fn unsubmitted_payout_payments(
+        &self,
+        payout_id: u32,
+    ) -> Result<Vec<PayoutPaymentRow>> {
+        unsubmitted_payout_payments(&self.pool, payout_id).await
+    }
+
+    pub async fn pending_payout_batch(&self, payout_id: u32) -> Result<Option<PayoutBatchRow>> {
+        pending_payout_batch(&self.pool, payout_id).await
+    }
+
+    pub async fn is_payout_batch_pending(&self, batch: PayoutBatchRow) -> Result<bool> {
+        is_payout_batch_pending(&self.pool, batch).await
+    }
+
+    pub async fn confirm_payout_batch(&self, batch_id: u32) -> Result<()> {
+        confirm_payout_batch(&self.pool, batch_id).await
+    }
+
+    pub async fn update_payout_status(
+        &self,
+        id: u32,
+        is_paused: bool,
+        error: Option<String>,
+    ) -> Result<()> {
+        update_payout_status(&self.pool, id, is_paused, error).await
+    }
+
+    pub async fn delete_payout(&self, id: u32) -> Result<()> {
+        delete_payout(&self.pool, id).await
+    }
+}
+
+impl DatabaseTx<'_> {
+    pub async fn insert_payout(&mut self, name: String, fee: u64, max_cost: u64) -> Result<u32> {
+        insert_payout(&mut *self.tx, name, fee, max_cost).await
+    }
+
+    pub async fn insert_payout_payment(
+        &mut self,
+        payout_id: u32,
+        payment: PayoutPaymentRow,
+    ) -> Result<()> {
+        insert_payout_payment(&mut *self.tx, payout_id, payment).await
+    }
+
+    pub async fn insert_payout_batch(
+        &mut self,
+        payout_id: u32,
+        transaction_id: Bytes32,
+        output_coin_id: Bytes32,
+    ) -> Result<u32> {
+        insert_payout_batch(&mut *self.tx, payout_id, transaction_id, output_coin_id).await
+    }
+
+    pub async fn set_payout_payment_batch(
+        &mut self,
+        payout_id: u32,
+        row_index: u32,
+        batch_id: u32,
+    ) -> Result<()> {
+        set_payout_payment_batch(&mut *self.tx, payout_id, row_index, batch_id).await
+    }
+
+    pub async fn fail_payout_batch(&mut self, batch_id: u32) -> Result<()> {
+        fail_payout_batch(&mut *self.tx, batch_id).await?;
+        release_payout_payments(&mut *self.tx, batch_id).await
+    }
+}
+
+async fn payouts(conn: impl SqliteExecutor<'_>) -> Result<Vec<PayoutRow>> {
+    sqlx::query!(
+        "
+        SELECT
+            id, name, fee, max_cost, is_paused, error, created_timestamp,
+            (
+                SELECT COUNT(*) FROM payout_payments
+                WHERE payout_payments.payout_id = payouts.id
+            ) AS payment_count,
+            (
+                SELECT COUNT(*) FROM payout_payments
+                WHERE payout_payments.payout_id = payouts.id AND batch_id IS NOT NULL
+            ) AS submitted_count,
+            (
+                SELECT COUNT(*) FROM payout_payments
+                INNER JOIN payout_batches ON payout_batches.id = payout_payments.batch_id
+                WHERE payout_payments.payout_id = payouts.id AND is_confirmed = TRUE
+            ) AS confirmed_count
+        FROM payouts
+        ORDER BY id ASC
+        "
+    )
+    .fetch_all(conn)
+    .await?
+    .into_iter()
+    .map(|row| {
+        Ok(PayoutRow {
+            id: row.id.convert()?,
+            name: row.name,
+            fee: row.fee.convert()?,
+            max_cost: row.max_cost.convert()?,
+            is_paused: row.is_paused,
+            error: row.error,
+            created_timestamp: row.created_timestamp.convert()?,
+            payment_count: row.payment_count.convert()?,
+            submitted_count: row.submitted_count.convert()?,
+            confirmed_count: row.confirmed_count.convert()?,
+        })
+    })
+    .collect()
+}
+
+async fn payout(conn: impl SqliteExecutor<'_>, id: u32) -> Result<Option<PayoutRow>> {
+    sqlx::query!(
+        "
+        SELECT
+            id, name, fee, max_cost, is_paused, error, created_timestamp,
+            (
+                SELECT COUNT(*) FROM payout_payments
+                WHERE payout_payments.payout_id = payouts.id
+            ) AS payment_count,
+            (
+                SELECT COUNT(*) FROM payout_payments
+                WHERE payout_payments.payout_id = payouts.id AND batch_id IS NOT NULL
+            ) AS submitted_count,
+            (
+                SELECT COUNT(*) FROM payout_payments
+                INNER JOIN payout_batches ON payout_batches.id = payout_payments.batch_id
+                WHERE payout_payments.payout_id = payouts.id AND is_confirmed = TRUE
+            ) AS confirmed_count
+        FROM payouts
+        WHERE id = ?
+        ",
+        id
+    )
+    .fetch_optional(conn)
+    .await?
+    .map(|row| {
+        Ok(PayoutRow {
+            id: row.id.convert()?,
+            name: row.name,
+            fee: row.fee.convert()?,
+            max_cost: row.max_cost.convert()?,
+            is_paused: row.is_paused,
+            error: row.error,
+            created_timestamp: row.created_timestamp.convert()?,
+            payment_count: row.payment_count.convert()?,
+            submitted_count: row.submitted_count.convert()?,
+            confirmed_count: row.confirmed_count.convert()?,
+        })
+    })
+    .transpose()
+}
+
+async fn active_payout_ids(conn: impl SqliteExecutor<'_>) -> Result<Vec<u32>> {
+    sqlx::query_scalar!(
+        "
+        SELECT id FROM payouts
+        WHERE is_paused = FALSE AND EXISTS (
+            SELECT 1 FROM payout_payments
+            LEFT JOIN payout_batches ON payout_batches.id = payout_payments.batch_id
+            WHERE payout_payments.payout_id = payouts.id
+            AND (payout_batches.id IS NULL OR payout_batches.is_confirmed = FALSE)
+        )
+        ORDER BY id ASC
+        "
+    )
+    .fetch_all(conn)
+    .await?
+    .into_iter()
+    .map(Convert::convert)
+    .collect()
+}
+
+async fn payout_payments(
+    conn: impl SqliteExecutor<'_>,
+    payout_id: u32,
+) -> Result<Vec<PayoutPaymentStatusRow>> {
+    sqlx::query!(
+        "
+        SELECT
+            row_index, asset_hash, puzzle_hash, amount, memos,
+            payout_batches.transaction_id AS \"transaction_id?\",
+            payout_batches.is_confirmed AS \"is_confirmed?\"
+        FROM payout_payments
+        LEFT JOIN payout_batches ON payout_batches.id = payout_payments.batch_id
+        WHERE payout_payments.payout_id = ?
+        ORDER BY row_index ASC
+        ",
+        payout_id
+    )
+    .fetch_all(conn)
+    .await?
+    .into_iter()
+    .map(|row| {
+        Ok(PayoutPaymentStatusRow {
+            payment: PayoutPaymentRow {
+                row_index: row.row_index.convert()?,
+                asset_id: row.asset_hash.convert()?,
+                puzzle_hash: row.puzzle_hash.convert()?,
+                amount: row.amount.convert()?,
+                memos: Vec::<Bytes>::from_bytes(&row.memos)?,
+            },
+            transaction_id: row.transaction_id.convert()?,
+            is_confirmed: row.is_confirmed.unwrap_or(false),
+        })
+    })
+    .collect()
+}
+
+async fn unsubmitted_payout_payments(
+    conn: impl SqliteExecutor<'_>,
+    payout_id: u32,
+) -> Result<Vec<PayoutPaymentRow>> {
+    sqlx::query!(
+        "
+        SELECT row_index, asset_hash, puzzle_hash, amount, memos
+        FROM payout_payments
+        WHERE payout_id = ? AND batch_id IS NULL
+        ORDER BY row_index ASC
+        ",
+        payout_id
+    )
+    .fetch_all(conn)
+    .await?
+    .into_iter()
+    .map(|row| {
+        Ok(PayoutPaymentRow {
+            row_index: row.row_index.convert()?,
+            asset_id: row.asset_hash.convert()?,
+            puzzle_hash: row.puzzle_hash.convert()?,
+            amount: row.amount.convert()?,
+            memos: Vec::<Bytes>::from_bytes(&row.memos)?,
+        })
+    })
+    .collect()
+}
+
+async fn pending_payout_batch(
+    conn: impl SqliteExecutor<'_>,
+    payout_id: u32,
+) -> Result<Option<PayoutBatchRow>> {
+    sqlx::query!(
+        "
+        SELECT id, transaction_id, output_coin_hash
+        FROM payout_batches
+        WHERE payout_id = ? AND is_confirmed = FALSE AND is_failed = FALSE
+        ORDER BY id ASC
+        LIMIT 1
+        ",
+        payout_id
+    )
+    .fetch_optional(conn)
+    .await?
+    .map(|row| {
+        Ok(PayoutBatchRow {
+            id: row.id.convert()?,
+            transaction_id: row.transaction_id.convert()?,
+            output_coin_id: row.output_coin_hash.convert()?,
+        })
+    })
+    .transpose()
+}
+
+async fn is_payout_batch_pending(
+    conn: impl SqliteExecutor<'_>,
+    batch: PayoutBatchRow,
+) -> Result<bool> {
+    let transaction_id = batch.transaction_id.as_ref();
+
+    // Transactions are removed from the mempool once they are either confirmed or rejected.
+    let is_pending = sqlx::query_scalar!(
+        "SELECT EXISTS (SELECT 1 FROM mempool_items WHERE hash = ?)",
+        transaction_id
+    )
+    .fetch_one(conn)
+    .await?;
+
+    Ok(is_pending != 0)
+}
+
+async fn confirm_payout_batch(conn: impl SqliteExecutor<'_>, batch_id: u32) -> Result<()> {
+    sqlx::query!(
+        "UPDATE payout_batches SET is_confirmed = TRUE WHERE id = ?",
+        batch_id
+    )
+    .execute(conn)
+    .await?;
+
+    Ok(())
+}
+
+async fn update_payout_status(
+    conn: impl SqliteExecutor<'_>,
+    id: u32,
+    is_paused: bool,
+    error: Option<String>,
+) -> Result<()> {
+    sqlx::query!(
+        "UPDATE payouts SET is_paused = ?, error = ? WHERE id = ?",
+        is_paused,
+        error,
+        id
+    )
+    .execute(conn)
+    .await?;
+
+    Ok(())
+}
+
+async fn delete_payout(conn: impl SqliteExecutor<'_>, id: u32) -> Result<()> {
+    sqlx::query!("DELETE FROM payouts WHERE id = ?", id)
+        .execute(conn)
+        .await?;
+
+    Ok(())
+}
+
+async fn insert_payout(
+    conn: impl SqliteExecutor<'_>,
+    name: String,
+    fee: u64,
+    max_cost: u64,
+) -> Result<u32> {
+    let fee = fee.to_be_bytes().to_vec();
+    let max_cost: i64 = max_cost.try_into()?;
+
+    sqlx::query_scalar!(
+        "INSERT INTO payouts (name, fee, max_cost) VALUES (?, ?, ?) RETURNING id",
+        name,
+        fee,
+        max_cost
+    )
+    .fetch_one(conn)
+    .await?
+    .convert()
+}
+
+async fn insert_payout_payment(
+    conn: impl SqliteExecutor<'_>,
+    payout_id: u32,
+    payment: PayoutPaymentRow,
+) -> Result<()> {
+    let asset_id = payment.asset_id.as_deref();
+    let puzzle_hash = payment.puzzle_hash.as_ref();
+    let amount = payment.amount.to_be_bytes().to_vec();
+    let memos = payment.memos.to_bytes()?;
+
+    sqlx::query!(
+        "
+        INSERT INTO payout_payments
+            (payout_id, row_index, asset_hash, puzzle_hash, amount, memos)
+        VALUES (?, ?, ?, ?, ?, ?)
+        ",
+        payout_id,
+        payment.row_index,
+        asset_id,
+        puzzle_hash,
+        amount,
+        memos
+    )
+    .execute(conn)
+    .await?;
+
+    Ok(())
+}
+
+async fn insert_payout_batch(
+    conn: impl SqliteExecutor<'_>,
+    payout_id: u32,
+    transaction_id: Bytes32,
+    output_coin_id: Bytes32,
+) -> Result<u32> {
+    let transaction_id = transaction_id.as_ref();
+    let output_coin_id = output_coin_id.as_ref();
+
+    sqlx::query_scalar!(
+        "
+        INSERT INTO payout_batches (payout_id, transaction_id, output_coin_hash)
+        VALUES (?, ?, ?)
+        RETURNING id
+        ",
+        payout_id,
+        transaction_id,
+        output_coin_id
+    )
+    .fetch_one(conn)
+    .await?
+    .convert()
+}
+
+async fn set_payout_payment_batch(
+    conn: impl SqliteExecutor<'_>,
+    payout_id: u32,
+    row_index: u32,
+    batch_id: u32,
+) -> Result<()> {
+    sqlx::query!(
+        "UPDATE payout_payments SET batch_id = ? WHERE payout_id = ? AND row_index = ?",
+        batch_id,
+        payout_id,
+        row_index
+    )
+    .execute(conn)
+    .await?;
+
+    Ok(())
+}
+
+async fn fail_payout_batch(conn: impl SqliteExecutor<'_>, batch_id: u32) -> Result<()> {
+    sqlx::query!(
+        "UPDATE payout_batches SET is_failed = TRUE WHERE id = ?",
+        batch_id
+    )
+    .execute(conn)
+    .await?;
+
+    Ok(())
+}
+
+async fn release_payout_payments(conn: impl SqliteExecutor<'_>, batch_id: u32) -> Result<()> {
+    sqlx::query!(
+        "UPDATE payout_payments SET batch_id = NULL WHERE batch_id = ?",
+        batch_id
+    )
+    .execute(conn)
+    .await?;
+
+    Ok(())
+}
@@ -0,0 +1,374 @@
+use chia::{
+    protocol::{Bytes, Bytes32},
+    traits::Streamable,
+};
+use sqlx::SqliteExecutor;
+
+use crate::{Convert, Database, DatabaseTx, Result};
+
+#[derive(Debug, Clone)]
+pub struct ScheduledPaymentRow {
+    pub id: u32,
+    pub name: String,
+    pub schedule: String,
+    pub fee: u64,
+    pub is_paused: bool,
+    pub next_run_timestamp: u64,
+    pub created_timestamp: u64,
+}
+
+#[derive(Debug, Clone)]
+pub struct ScheduledPaymentRecipientRow {
+    pub asset_id: Option<Bytes32>,
+    pub puzzle_hash: Bytes32,
+    pub amount: u64,
+    pub memos: Vec<Bytes>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ScheduledPaymentRunRow {
+    pub scheduled_payment_id: u32,
+    pub transaction_id: Option<Bytes32>,
+    pub error: Option<String>,
+    pub run_timestamp: u64,
+}
+
+impl Database {
+    pub async fn scheduled_payments(&self) -> Result<Vec<ScheduledPaymentRow>> {
+        scheduled_payments(&self.pool).await
+    }
+
+    pub async fn scheduled_payment(&self, id: u32) -> Result<Option<ScheduledPaymentRow>> {
+        scheduled_payment(&self.pool, id).await
+    }
+
+    pub async fn due_scheduled_payments(&self, timestamp: u64) -> Result<Vec<ScheduledPaymentRow>> {
+        due_scheduled_payments(&self.pool, timestamp).await
+    }
+
+    pub async fn scheduled_payment_recipients(
+        &self,
+        scheduled_payment_id: u32,
+    ) -> Result<Vec<ScheduledPaymentRecipientRow>> {
+        scheduled_payment_recipients(&self.pool, scheduled_payment_id).await
+    }
+
+    pub async fn scheduled_payment_runs(
+        &self,
+        scheduled_payment_id: u32,
+        limit: u32,
+    ) -> Result<Vec<ScheduledPaymentRunRow>> {
+        scheduled_payment_runs(&self.pool, scheduled_payment_id, limit).await
+    }
+
+    pub async fn update_scheduled_payment_status(
+        &self,
+        id: u32,
+        is_paused: bool,
+        next_run_timestamp: u64,
+    ) -> Result<()> {
+        update_scheduled_payment_status(&self.pool, id, is_paused, next_run_timestamp).await
+    }
+
+    pub async fn set_next_run_timestamp(&self, id: u32, next_run_timestamp: u64) -> Result<()> {
+        set_next_run_timestamp(&self.pool, id, next_run_timestamp).await
+    }
+
+    pub async fn insert_scheduled_payment_run(
+        &self,
+        scheduled_payment_id: u32,
+        transaction_id: Option<Bytes32>,
+        error: Option<String>,
+    ) -> Result<()> {
+        insert_scheduled_payment_run(&self.pool, scheduled_payment_id, transaction_id, error).await
+    }
+
+    pub async fn delete_scheduled_payment(&self, id: u32) -> Result<()> {
+        delete_scheduled_payment(&self.pool, id).await
+    }
+}
+
+impl DatabaseTx<'_> {
+    pub async fn insert_scheduled_payment(
+        &mut self,
+        name: String,
+        schedule: String,
+        fee: u64,
+        next_run_timestamp: u64,
+    ) -> Result<u32> {
+        insert_scheduled_payment(&mut *self.tx, name, schedule, fee, next_run_timestamp).await
+    }
+
+    pub async fn insert_scheduled_payment_recipient(
+        &mut self,
+        scheduled_payment_id: u32,
+        recipient: ScheduledPaymentRecipientRow,
+    ) -> Result<()> {
+        insert_scheduled_payment_recipient(&mut *self.tx, scheduled_payment_id, recipient).await
+    }
+}
+
+async fn scheduled_payments(conn: impl SqliteExecutor<'_>) -> Result<Vec<ScheduledPaymentRow>> {
+    sqlx::query!(
+        "
+        SELECT id, name, schedule, fee, is_paused, next_run_timestamp, created_timestamp
+        FROM scheduled_payments
+        ORDER BY id ASC
+        "
+    )
+    .fetch_all(conn)
+    .await?
+    .into_iter()
+    .map(|row| {
+        Ok(ScheduledPaymentRow {
+            id: row.id.convert()?,
+            name: row.name,
+            schedule: row.schedule,
+            fee: row.fee.convert()?,
+            is_paused: row.is_paused,
+            next_run_timestamp: row.next_run_timestamp.convert()?,
+            created_timestamp: row.created_timestamp.convert()?,
+        })
+    })
+    .collect()
+}
+
+async fn scheduled_payment(
+    conn: impl SqliteExecutor<'_>,
+    id: u32,
+) -> Result<Option<ScheduledPaymentRow>> {
+    sqlx::query!(
+        "
+        SELECT id, name, schedule, fee, is_paused, next_run_timestamp, created_timestamp
+        FROM scheduled_payments
+        WHERE id = ?
+        ",
+        id
+    )
+    .fetch_optional(conn)
+    .await?
+    .map(|row| {
+        Ok(ScheduledPaymentRow {
+            id: row.id.convert()?,
+            name: row.name,
+            schedule: row.schedule,
+            fee: row.fee.convert()?,
+            is_paused: row.is_paused,
+            next_run_timestamp: row.next_run_timestamp.convert()?,
+            created_timestamp: row.created_timestamp.convert()?,
+        })
+    })
+    .transpose()
+}
+
+async fn due_scheduled_payments(
+    conn: impl SqliteExecutor<'_>,
+    timestamp: u64,
+) -> Result<Vec<ScheduledPaymentRow>> {
+    let timestamp: i64 = timestamp.try_into()?;
+
+    sqlx::query!(
+        "
+        SELECT id, name, schedule, fee, is_paused, next_run_timestamp, created_timestamp
+        FROM scheduled_payments
+        WHERE is_paused = FALSE AND next_run_timestamp <= ?
+        ORDER BY next_run_timestamp ASC, id ASC
+        ",
+        timestamp
+    )
+    .fetch_all(conn)
+    .await?
+    .into_iter()
+    .map(|row| {
+        Ok(ScheduledPaymentRow {
+            id: row.id.convert()?,
+            name: row.name,
+            schedule: row.schedule,
+            fee: row.fee.convert()?,
+            is_paused: row.is_paused,
+            next_run_timestamp: row.next_run_timestamp.convert()?,
+            created_timestamp: row.created_timestamp.convert()?,
+        })
+    })
+    .collect()
+}
+
+async fn scheduled_payment_recipients(
+    conn: impl SqliteExecutor<'_>,
+    scheduled_payment_id: u32,
+) -> Result<Vec<ScheduledPaymentRecipientRow>> {
+    sqlx::query!(
+        "
+        SELECT asset_hash, puzzle_hash, amount, memos
+        FROM scheduled_payment_recipients
+        WHERE scheduled_payment_id = ?
+        ORDER BY id ASC
+        ",
+        scheduled_payment_id
+    )
+    .fetch_all(conn)
+    .await?
+    .into_iter()
+    .map(|row| {
+        Ok(ScheduledPaymentRecipientRow {
+            asset_id: row.asset_hash.convert()?,
+            puzzle_hash: row.puzzle_hash.convert()?,
+            amount: row.amount.convert()?,
+            memos: Vec::<Bytes>::from_bytes(&row.memos)?,
+        })
+    })
+    .collect()
+}
+
+async fn scheduled_payment_runs(
+    conn: impl SqliteExecutor<'_>,
+    scheduled_payment_id: u32,
+    limit: u32,
+) -> Result<Vec<ScheduledPaymentRunRow>> {
+    sqlx::query!(
+        "
+        SELECT scheduled_payment_id, transaction_id, error, run_timestamp
+        FROM scheduled_payment_runs
+        WHERE scheduled_payment_id = ?
+        ORDER BY run_timestamp DESC, id DESC
+        LIMIT ?
+        ",
+        scheduled_payment_id,
+        limit
+    )
+    .fetch_all(conn)
+    .await?
+    .into_iter()
+    .map(|row| {
+        Ok(ScheduledPaymentRunRow {
+            scheduled_payment_id: row.scheduled_payment_id.convert()?,
+            transaction_id: row.transaction_id.convert()?,
+            error: row.error,
+            run_timestamp: row.run_timestamp.convert()?,
+        })
+    })
+    .collect()
+}
+
+async fn insert_scheduled_payment(
+    conn: impl SqliteExecutor<'_>,
+    name: String,
+    schedule: String,
+    fee: u64,
+    next_run_timestamp: u64,
+) -> Result<u32> {
+    let fee = fee.to_be_bytes().to_vec();
+    let next_run_timestamp: i64 = next_run_timestamp.try_into()?;
+
+    sqlx::query_scalar!(
+        "
+        INSERT INTO scheduled_payments (name, schedule, fee, next_run_timestamp)
+        VALUES (?, ?, ?, ?)
+        RETURNING id
+        ",
+        name,
+        schedule,
+        fee,
+        next_run_timestamp
+    )
+    .fetch_one(conn)
+    .await?
+    .convert()
+}
+
+async fn insert_scheduled_payment_recipient(
+    conn: impl SqliteExecutor<'_>,
+    scheduled_payment_id: u32,
+    recipient: ScheduledPaymentRecipientRow,
+) -> Result<()> {
+    let asset_id = recipient.asset_id.as_deref();
+    let puzzle_hash = recipient.puzzle_hash.as_ref();
+    let amount = recipient.amount.to_be_bytes().to_vec();
+    let memos = recipient.memos.to_bytes()?;
+
+    sqlx::query!(
+        "
+        INSERT INTO scheduled_payment_recipients
+            (scheduled_payment_id, asset_hash, puzzle_hash, amount, memos)
+        VALUES (?, ?, ?, ?, ?)
+        ",
+        scheduled_payment_id,
+        asset_id,
+        puzzle_hash,
+        amount,
+        memos
+    )
+    .execute(conn)
+    .await?;
+
+    Ok(())
+}
+
+async fn update_scheduled_payment_status(
+    conn: impl SqliteExecutor<'_>,
+    id: u32,
+    is_paused: bool,
+    next_run_timestamp: u64,
+) -> Result<()> {
+    let next_run_timestamp: i64 = next_run_timestamp.try_into()?;
+
+    sqlx::query!(
+        "UPDATE scheduled_payments SET is_paused = ?, next_run_timestamp = ? WHERE id = ?",
+        is_paused,
+        next_run_timestamp,
+        id
+    )
+    .execute(conn)
+    .await?;
+
+    Ok(())
+}
+
+async fn set_next_run_timestamp(
+    conn: impl SqliteExecutor<'_>,
+    id: u32,
+    next_run_timestamp: u64,
+) -> Result<()> {
+    let next_run_timestamp: i64 = next_run_timestamp.try_into()?;
+
+    sqlx::query!(
+        "UPDATE scheduled_payments SET next_run_timestamp = ? WHERE id = ?",
+        next_run_timestamp,
+        id
+    )
+    .execute(conn)
+    .await?;
+
+    Ok(())
+}
+
+async fn insert_scheduled_payment_run(
+    conn: impl SqliteExecutor<'_>,
+    scheduled_payment_id: u32,
+    transaction_id: Option<Bytes32>,
+    error: Option<String>,
+) -> Result<()> {
+    let transaction_id = transaction_id.as_deref();
+
+    sqlx::query!(
+        "
+        INSERT INTO scheduled_payment_runs (scheduled_payment_id, transaction_id, error)
+        VALUES (?, ?, ?)
+        ",
+        scheduled_payment_id,
+        transaction_id,
+        error
+    )
+    .execute(conn)
+    .await?;
+
+    Ok(())
+}
+
+async fn delete_scheduled_payment(conn: impl SqliteExecutor<'_>, id: u32) -> Result<()> {
+    sqlx::query!("DELETE FROM scheduled_payments WHERE id = ?", id)
+        .execute(conn)
+        .await?;
+
+    Ok(())
+}
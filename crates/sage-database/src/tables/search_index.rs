@@ -0,0 +1,414 @@
+use chia::protocol::{Bytes, Bytes32};
+use sqlx::{Row, SqliteExecutor};
+
+use crate::{Convert, Database, DatabaseError, Result};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SearchKind {
+    Token = 0,
+    Nft = 1,
+    Did = 2,
+    Option = 3,
+    Collection = 4,
+    Offer = 5,
+    Transaction = 6,
+    Contact = 7,
+}
+
+impl Convert<SearchKind> for i64 {
+    fn convert(self) -> Result<SearchKind> {
+        Ok(match self {
+            0 => SearchKind::Token,
+            1 => SearchKind::Nft,
+            2 => SearchKind::Did,
+            3 => SearchKind::Option,
+            4 => SearchKind::Collection,
+            5 => SearchKind::Offer,
+            6 => SearchKind::Transaction,
+            7 => SearchKind::Contact,
+            _ => return Err(DatabaseError::InvalidEnumVariant),
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SearchHit {
+    pub kind: SearchKind,
+    /// The id of the row the hit was made from, such as the id of a contact.
+    pub row_id: u32,
+    /// The hash of everything other than contacts, which don't have one.
+    pub hash: Option<Bytes32>,
+    pub name: Option<String>,
+    pub ticker: Option<String>,
+    /// The BM25 score of the match, where lower is better.
+    pub rank: f64,
+}
+
+impl Database {
+    /// Finds everything which contains each of the terms, or a word starting with it, ranked by
+    /// relevance. Names and tickers are weighted the most, followed by ids and addresses.
+    pub async fn search(
+        &self,
+        terms: &[String],
+        kinds: &[SearchKind],
+        limit: u32,
+        offset: u32,
+    ) -> Result<(Vec<SearchHit>, u32)> {
+        if terms.is_empty() {
+            return Ok((Vec::new(), 0));
+        }
+
+        let mut query = sqlx::QueryBuilder::new(
+            "
+            SELECT *, COUNT(*) OVER() AS total_count FROM (
+                SELECT
+                    rowid / 8 AS row_id, kind, hash, name, ticker,
+                    bm25(search_index, 0.0, 0.0, 10.0, 10.0, 1.0, 2.0, 5.0, 1.0) AS rank
+                FROM search_index
+                WHERE search_index MATCH ",
+        );
+
+        query.push_bind(match_expression(terms));
+
+        if !kinds.is_empty() {
+            query.push(" AND kind IN (");
+            let mut separated = query.separated(", ");
+            for kind in kinds {
+                separated.push_bind(*kind as i64);
+            }
+            separated.push_unseparated(")");
+        }
+
+        query.push(") ORDER BY rank ASC LIMIT ");
+        query.push_bind(limit);
+        query.push(" OFFSET ");
+        query.push_bind(offset);
+
+        let rows = query.build().fetch_all(&self.pool).await?;
+
+        let total_count = rows
+            .first()
+            .map_or(Ok(0), |row| row.get::<i64, _>("total_count").try_into())?;
+
+        let hits = rows
+            .into_iter()
+            .map(|row| {
+                Ok(SearchHit {
+                    kind: row.get::<i64, _>("kind").convert()?,
+                    row_id: row.get::<i64, _>("row_id").convert()?,
+                    hash: row.get::<Option<Vec<u8>>, _>("hash").convert()?,
+                    name: row.get::<Option<String>, _>("name"),
+                    ticker: row.get::<Option<String>, _>("ticker"),
+                    rank: row.get::<f64, _>("rank"),
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok((hits, total_count))
+    }
+}
+
+/// Adds the memos of a submitted payment to the search index entry of its transaction. Memos
+/// which are text are indexed as is, and the rest as hex.
+pub(crate) async fn index_submitted_memos(
+    conn: impl SqliteExecutor<'_>,
+    submitted_transaction_id: u32,
+    memos: &[Bytes],
+) -> Result<()> {
+    if memos.is_empty() {
+        return Ok(());
+    }
+
+    let text = memos
+        .iter()
+        .map(|memo| match std::str::from_utf8(memo) {
+            Ok(text) if !text.chars().any(char::is_control) => text.to_string(),
+            _ => hex::encode(memo),
+        })
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    sqlx::query(
+        "
+        UPDATE search_index SET memos = ltrim(coalesce(memos, '') || ' ' || ?)
+        WHERE rowid = ? * 8 + 6
+        ",
+    )
+    .bind(text)
+    .bind(submitted_transaction_id)
+    .execute(conn)
+    .await?;
+
+    Ok(())
+}
+
+/// Each term is quoted so that it's matched literally, and matches words which start with it.
+fn match_expression(terms: &[String]) -> String {
+    terms
+        .iter()
+        .map(|term| format!("\"{}\"*", term.replace('"', "\"\"")))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        test_database, Asset, AssetKind, NftTrait, OfferRow, OfferStatus, SubmittedPaymentRow,
+    };
+
+    use super::*;
+
+    fn asset(hash: Bytes32, kind: AssetKind, name: &str, description: Option<&str>) -> Asset {
+        Asset {
+            hash,
+            name: Some(name.to_string()),
+            ticker: None,
+            precision: 3,
+            icon_url: None,
+            description: description.map(ToString::to_string),
+            is_sensitive_content: false,
+            is_visible: true,
+            hidden_puzzle_hash: None,
+            kind,
+        }
+    }
+
+    async fn search(db: &Database, terms: &[&str]) -> anyhow::Result<Vec<(SearchKind, String)>> {
+        let terms = terms.iter().map(ToString::to_string).collect::<Vec<_>>();
+        let (hits, total) = db.search(&terms, &[], 50, 0).await?;
+
+        assert_eq!(hits.len(), total as usize);
+
+        Ok(hits
+            .into_iter()
+            .map(|hit| {
+                let id = hit
+                    .name
+                    .unwrap_or_else(|| hex::encode(hit.hash.expect("hash")));
+                (hit.kind, id)
+            })
+            .collect())
+    }
+
+    #[tokio::test]
+    async fn test_search_index() -> anyhow::Result<()> {
+        let db = test_database().await?;
+
+        let token_hash = Bytes32::new([1; 32]);
+        let nft_hash = Bytes32::new([2; 32]);
+        let offer_id = Bytes32::new([3; 32]);
+        let transaction_id = Bytes32::new([4; 32]);
+        let address = Bytes32::new([5; 32]);
+        let did_id = Bytes32::new([6; 32]);
+
+        let mut tx = db.tx().await?;
+
+        tx.insert_asset(Asset {
+            ticker: Some("SBX".to_string()),
+            ..asset(token_hash, AssetKind::Token, "Spacebucks", None)
+        })
+        .await?;
+
+        tx.insert_asset(asset(
+            nft_hash,
+            AssetKind::Nft,
+            "Rocket #1",
+            Some("Fan art of spacebucks"),
+        ))
+        .await?;
+
+        tx.set_nft_traits(
+            nft_hash,
+            &[NftTrait {
+                trait_type: "Background".to_string(),
+                value: "Nebula".to_string(),
+                numeric_value: None,
+            }],
+        )
+        .await?;
+
+        let contact_id = tx
+            .insert_contact(
+                "Alice".to_string(),
+                Some("Met at the meetup".to_string()),
+                Some(did_id),
+            )
+            .await?;
+        tx.insert_contact_address(contact_id, address, None).await?;
+
+        tx.insert_offer(OfferRow {
+            offer_id,
+            encoded_offer: "offer1".to_string(),
+            expiration_height: None,
+            expiration_timestamp: None,
+            fee: 0,
+            status: OfferStatus::Active,
+            inserted_timestamp: 0,
+            invalid_reason: None,
+            auto_recreate: false,
+            recreated_offer_id: None,
+            recreate_error: None,
+        })
+        .await?;
+
+        let submitted_id = tx
+            .insert_submitted_transaction(transaction_id, Bytes32::default(), 0)
+            .await?
+            .expect("submitted transaction");
+
+        tx.insert_submitted_payment(
+            submitted_id,
+            SubmittedPaymentRow {
+                asset_hash: Bytes32::default(),
+                p2_puzzle_hash: address,
+                amount: 1000,
+                memos: vec![
+                    Bytes::new(b"rent for october".to_vec()),
+                    Bytes::new(vec![0xff, 0x00]),
+                ],
+            },
+        )
+        .await?;
+
+        tx.commit().await?;
+
+        // A match in the name or ticker ranks above a match in the description.
+        assert_eq!(
+            search(&db, &["spacebuck"]).await?,
+            vec![
+                (SearchKind::Token, "Spacebucks".to_string()),
+                (SearchKind::Nft, "Rocket #1".to_string()),
+            ]
+        );
+        assert_eq!(
+            search(&db, &["sbx"]).await?,
+            vec![(SearchKind::Token, "Spacebucks".to_string())]
+        );
+        assert_eq!(
+            search(&db, &["nebula"]).await?,
+            vec![(SearchKind::Nft, "Rocket #1".to_string())]
+        );
+
+        // Ids are found by their hex, and addresses by the hex of their puzzle hash.
+        assert_eq!(
+            search(&db, &["0303"]).await?,
+            vec![(SearchKind::Offer, hex::encode(offer_id))]
+        );
+        assert_eq!(
+            search(&db, &["0606"]).await?,
+            vec![(SearchKind::Contact, "Alice".to_string())]
+        );
+
+        let mut hits = search(&db, &["0505"]).await?;
+        hits.sort_by_key(|(kind, _)| *kind as i64);
+        assert_eq!(
+            hits,
+            vec![
+                (SearchKind::Transaction, hex::encode(transaction_id)),
+                (SearchKind::Contact, "Alice".to_string()),
+            ]
+        );
+
+        // Text memos are indexed as is, and the rest as hex.
+        assert_eq!(
+            search(&db, &["rent", "october"]).await?,
+            vec![(SearchKind::Transaction, hex::encode(transaction_id))]
+        );
+        assert_eq!(
+            search(&db, &["ff00"]).await?,
+            vec![(SearchKind::Transaction, hex::encode(transaction_id))]
+        );
+
+        // Every term has to match.
+        assert!(search(&db, &["spacebucks", "nebula", "alice"])
+            .await?
+            .is_empty());
+
+        let (hits, _) = db
+            .search(&["alice".to_string()], &[SearchKind::Contact], 50, 0)
+            .await?;
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].row_id, contact_id);
+        assert_eq!(hits[0].hash, None);
+
+        // Updates to the rows are reflected in the index.
+        db.update_asset(asset(token_hash, AssetKind::Token, "Moonbucks", None))
+            .await?;
+
+        let mut tx = db.tx().await?;
+        tx.set_nft_traits(
+            nft_hash,
+            &[NftTrait {
+                trait_type: "Background".to_string(),
+                value: "Galaxy".to_string(),
+                numeric_value: None,
+            }],
+        )
+        .await?;
+        tx.update_contact(contact_id, "Bob".to_string(), None, None)
+            .await?;
+        tx.delete_contact_addresses(contact_id).await?;
+        tx.commit().await?;
+
+        assert_eq!(
+            search(&db, &["spacebuck"]).await?,
+            vec![(SearchKind::Nft, "Rocket #1".to_string())]
+        );
+        assert_eq!(
+            search(&db, &["moonbucks"]).await?,
+            vec![(SearchKind::Token, "Moonbucks".to_string())]
+        );
+        assert!(search(&db, &["sbx"]).await?.is_empty());
+        assert!(search(&db, &["nebula"]).await?.is_empty());
+        assert_eq!(
+            search(&db, &["galaxy"]).await?,
+            vec![(SearchKind::Nft, "Rocket #1".to_string())]
+        );
+        assert!(search(&db, &["alice"]).await?.is_empty());
+        assert!(search(&db, &["meetup"]).await?.is_empty());
+        assert!(search(&db, &["0606"]).await?.is_empty());
+        assert_eq!(
+            search(&db, &["0505"]).await?,
+            vec![(SearchKind::Transaction, hex::encode(transaction_id))]
+        );
+
+        let mut tx = db.tx().await?;
+        tx.insert_contact_address(contact_id, address, None).await?;
+        tx.commit().await?;
+
+        let mut hits = search(&db, &["0505"]).await?;
+        hits.sort_by_key(|(kind, _)| *kind as i64);
+        assert_eq!(
+            hits,
+            vec![
+                (SearchKind::Transaction, hex::encode(transaction_id)),
+                (SearchKind::Contact, "Bob".to_string()),
+            ]
+        );
+
+        // Deleted rows are removed from the index.
+        db.delete_contact(contact_id).await?;
+        db.delete_offer(offer_id).await?;
+
+        sqlx::query("DELETE FROM assets WHERE hash = ?")
+            .bind(nft_hash.as_ref())
+            .execute(&db.pool)
+            .await?;
+
+        assert!(search(&db, &["bob"]).await?.is_empty());
+        assert!(search(&db, &["0303"]).await?.is_empty());
+        assert!(search(&db, &["rocket"]).await?.is_empty());
+        assert!(search(&db, &["galaxy"]).await?.is_empty());
+        assert_eq!(
+            search(&db, &["0505"]).await?,
+            vec![(SearchKind::Transaction, hex::encode(transaction_id))]
+        );
+        assert_eq!(
+            search(&db, &["moonbucks"]).await?,
+            vec![(SearchKind::Token, "Moonbucks".to_string())]
+        );
+
+        Ok(())
+    }
+}
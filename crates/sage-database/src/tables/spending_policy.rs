@@ -0,0 +1,424 @@
+use chia::protocol::Bytes32;
+use sqlx::SqliteExecutor;
+
+use crate::{Convert, Database, DatabaseTx, Result};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SpendingPolicyRow {
+    pub max_fee: Option<u64>,
+    pub is_nft_transfer_blocked: bool,
+    pub is_did_transfer_blocked: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SpendingLimitRow {
+    pub asset_hash: Bytes32,
+    pub max_per_transaction: Option<u64>,
+    pub max_per_day: Option<u64>,
+}
+
+impl Database {
+    pub async fn spending_policy(&self) -> Result<Option<SpendingPolicyRow>> {
+        spending_policy(&self.pool).await
+    }
+
+    pub async fn spending_limits(&self) -> Result<Vec<SpendingLimitRow>> {
+        spending_limits(&self.pool).await
+    }
+
+    pub async fn allowed_destinations(&self) -> Result<Vec<Bytes32>> {
+        allowed_destinations(&self.pool).await
+    }
+
+    pub async fn policy_spent_since(&self, asset_hash: Bytes32, timestamp: u64) -> Result<u128> {
+        policy_spent_since(&self.pool, asset_hash, timestamp).await
+    }
+
+    /// Removes the outflows of a transaction that was signed but couldn't be submitted.
+    pub async fn release_unsubmitted_policy_spends(&self, transaction_id: Bytes32) -> Result<()> {
+        release_unsubmitted_policy_spends(&self.pool, transaction_id).await
+    }
+}
+
+impl DatabaseTx<'_> {
+    pub async fn clear_spending_policy(&mut self) -> Result<()> {
+        clear_spending_policy(&mut *self.tx).await?;
+        clear_spending_limits(&mut *self.tx).await?;
+        clear_allowed_destinations(&mut *self.tx).await
+    }
+
+    pub async fn set_spending_policy(&mut self, policy: SpendingPolicyRow) -> Result<()> {
+        set_spending_policy(&mut *self.tx, policy).await
+    }
+
+    pub async fn insert_spending_limit(&mut self, limit: SpendingLimitRow) -> Result<()> {
+        insert_spending_limit(&mut *self.tx, limit).await
+    }
+
+    pub async fn insert_allowed_destination(&mut self, puzzle_hash: Bytes32) -> Result<()> {
+        insert_allowed_destination(&mut *self.tx, puzzle_hash).await
+    }
+
+    pub async fn insert_policy_spend(
+        &mut self,
+        transaction_id: Bytes32,
+        asset_hash: Bytes32,
+        amount: u64,
+    ) -> Result<()> {
+        insert_policy_spend(&mut *self.tx, transaction_id, asset_hash, amount).await
+    }
+
+    pub async fn submit_policy_spends(&mut self, transaction_id: Bytes32) -> Result<()> {
+        submit_policy_spends(&mut *self.tx, transaction_id).await
+    }
+
+    /// Removes the outflows of a transaction that was rejected by the mempool.
+    pub async fn remove_policy_spends(&mut self, transaction_id: Bytes32) -> Result<()> {
+        remove_policy_spends(&mut *self.tx, transaction_id).await
+    }
+}
+
+async fn spending_policy(conn: impl SqliteExecutor<'_>) -> Result<Option<SpendingPolicyRow>> {
+    sqlx::query!(
+        "
+        SELECT max_fee, is_nft_transfer_blocked, is_did_transfer_blocked
+        FROM spending_policy
+        WHERE id = 1
+        "
+    )
+    .fetch_optional(conn)
+    .await?
+    .map(|row| {
+        Ok(SpendingPolicyRow {
+            max_fee: row.max_fee.convert()?,
+            is_nft_transfer_blocked: row.is_nft_transfer_blocked,
+            is_did_transfer_blocked: row.is_did_transfer_blocked,
+        })
+    })
+    .transpose()
+}
+
+async fn spending_limits(conn: impl SqliteExecutor<'_>) -> Result<Vec<SpendingLimitRow>> {
+    sqlx::query!(
+        "
+        SELECT asset_hash, max_per_transaction, max_per_day
+        FROM spending_limits
+        ORDER BY id ASC
+        "
+    )
+    .fetch_all(conn)
+    .await?
+    .into_iter()
+    .map(|row| {
+        Ok(SpendingLimitRow {
+            asset_hash: row.asset_hash.convert()?,
+            max_per_transaction: row.max_per_transaction.convert()?,
+            max_per_day: row.max_per_day.convert()?,
+        })
+    })
+    .collect()
+}
+
+async fn allowed_destinations(conn: impl SqliteExecutor<'_>) -> Result<Vec<Bytes32>> {
+    sqlx::query_scalar!("SELECT puzzle_hash FROM allowed_destinations ORDER BY id ASC")
+        .fetch_all(conn)
+        .await?
+        .into_iter()
+        .map(Convert::convert)
+        .collect()
+}
+
+async fn policy_spent_since(
+    conn: impl SqliteExecutor<'_>,
+    asset_hash: Bytes32,
+    timestamp: u64,
+) -> Result<u128> {
+    let asset_hash = asset_hash.as_ref();
+    let timestamp: i64 = timestamp.try_into()?;
+
+    let amounts = sqlx::query_scalar!(
+        "SELECT amount FROM policy_spends WHERE asset_hash = ? AND spent_timestamp >= ?",
+        asset_hash,
+        timestamp
+    )
+    .fetch_all(conn)
+    .await?;
+
+    let mut total = 0;
+
+    for amount in amounts {
+        let amount: u64 = amount.convert()?;
+        total += u128::from(amount);
+    }
+
+    Ok(total)
+}
+
+async fn insert_policy_spend(
+    conn: impl SqliteExecutor<'_>,
+    transaction_id: Bytes32,
+    asset_hash: Bytes32,
+    amount: u64,
+) -> Result<()> {
+    let transaction_id = transaction_id.as_ref();
+    let asset_hash = asset_hash.as_ref();
+    let amount = amount.to_be_bytes().to_vec();
+
+    sqlx::query!(
+        "INSERT INTO policy_spends (transaction_hash, asset_hash, amount) VALUES (?, ?, ?)",
+        transaction_id,
+        asset_hash,
+        amount
+    )
+    .execute(conn)
+    .await?;
+
+    Ok(())
+}
+
+async fn submit_policy_spends(
+    conn: impl SqliteExecutor<'_>,
+    transaction_id: Bytes32,
+) -> Result<()> {
+    let transaction_id = transaction_id.as_ref();
+
+    sqlx::query!(
+        "UPDATE policy_spends SET is_submitted = TRUE WHERE transaction_hash = ?",
+        transaction_id
+    )
+    .execute(conn)
+    .await?;
+
+    Ok(())
+}
+
+async fn release_unsubmitted_policy_spends(
+    conn: impl SqliteExecutor<'_>,
+    transaction_id: Bytes32,
+) -> Result<()> {
+    let transaction_id = transaction_id.as_ref();
+
+    sqlx::query!(
+        "DELETE FROM policy_spends WHERE transaction_hash = ? AND is_submitted = FALSE",
+        transaction_id
+    )
+    .execute(conn)
+    .await?;
+
+    Ok(())
+}
+
+async fn remove_policy_spends(
+    conn: impl SqliteExecutor<'_>,
+    transaction_id: Bytes32,
+) -> Result<()> {
+    let transaction_id = transaction_id.as_ref();
+
+    sqlx::query!(
+        "DELETE FROM policy_spends WHERE transaction_hash = ?",
+        transaction_id
+    )
+    .execute(conn)
+    .await?;
+
+    Ok(())
+}
+
+async fn clear_spending_policy(conn: impl SqliteExecutor<'_>) -> Result<()> {
+    sqlx::query!("DELETE FROM spending_policy")
+        .execute(conn)
+        .await?;
+
+    Ok(())
+}
+
+async fn clear_spending_limits(conn: impl SqliteExecutor<'_>) -> Result<()> {
+    sqlx::query!("DELETE FROM spending_limits")
+        .execute(conn)
+        .await?;
+
+    Ok(())
+}
+
+async fn clear_allowed_destinations(conn: impl SqliteExecutor<'_>) -> Result<()> {
+    sqlx::query!("DELETE FROM allowed_destinations")
+        .execute(conn)
+        .await?;
+
+    Ok(())
+}
+
+async fn set_spending_policy(
+    conn: impl SqliteExecutor<'_>,
+    policy: SpendingPolicyRow,
+) -> Result<()> {
+    let max_fee = policy.max_fee.map(|fee| fee.to_be_bytes().to_vec());
+
+    sqlx::query!(
+        "
+        REPLACE INTO spending_policy (id, max_fee, is_nft_transfer_blocked, is_did_transfer_blocked)
+        VALUES (1, ?, ?, ?)
+        ",
+        max_fee,
+        policy.is_nft_transfer_blocked,
+        policy.is_did_transfer_blocked
+    )
+    .execute(conn)
+    .await?;
+
+    Ok(())
+}
+
+async fn insert_spending_limit(
+    conn: impl SqliteExecutor<'_>,
+    limit: SpendingLimitRow,
+) -> Result<()> {
+    let asset_hash = limit.asset_hash.as_ref();
+    let max_per_transaction = limit
+        .max_per_transaction
+        .map(|amount| amount.to_be_bytes().to_vec());
+    let max_per_day = limit
+        .max_per_day
+        .map(|amount| amount.to_be_bytes().to_vec());
+
+    sqlx::query!(
+        "
+        REPLACE INTO spending_limits (asset_hash, max_per_transaction, max_per_day)
+        VALUES (?, ?, ?)
+        ",
+        asset_hash,
+        max_per_transaction,
+        max_per_day
+    )
+    .execute(conn)
+    .await?;
+
+    Ok(())
+}
+
+async fn insert_allowed_destination(
+    conn: impl SqliteExecutor<'_>,
+    puzzle_hash: Bytes32,
+) -> Result<()> {
+    let puzzle_hash = puzzle_hash.as_ref();
+
+    sqlx::query!(
+        "INSERT OR IGNORE INTO allowed_destinations (puzzle_hash) VALUES (?)",
+        puzzle_hash
+    )
+    .execute(conn)
+    .await?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_database;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_spending_policy() -> anyhow::Result<()> {
+        let db = test_database().await?;
+
+        assert_eq!(db.spending_policy().await?, None);
+        assert!(db.spending_limits().await?.is_empty());
+        assert!(db.allowed_destinations().await?.is_empty());
+
+        let policy = SpendingPolicyRow {
+            max_fee: Some(u64::MAX),
+            is_nft_transfer_blocked: true,
+            is_did_transfer_blocked: false,
+        };
+
+        let limit = SpendingLimitRow {
+            asset_hash: Bytes32::default(),
+            max_per_transaction: Some(1000),
+            max_per_day: None,
+        };
+
+        let destination = Bytes32::new([1; 32]);
+
+        let mut tx = db.tx().await?;
+        tx.set_spending_policy(policy).await?;
+        tx.insert_spending_limit(limit).await?;
+        tx.insert_allowed_destination(destination).await?;
+        tx.insert_allowed_destination(destination).await?;
+        tx.commit().await?;
+
+        assert_eq!(db.spending_policy().await?, Some(policy));
+        assert_eq!(db.spending_limits().await?, vec![limit]);
+        assert_eq!(db.allowed_destinations().await?, vec![destination]);
+
+        // Limits are replaced rather than duplicated for the same asset.
+        let replaced = SpendingLimitRow {
+            max_per_transaction: None,
+            max_per_day: Some(5000),
+            ..limit
+        };
+
+        let mut tx = db.tx().await?;
+        tx.insert_spending_limit(replaced).await?;
+        tx.commit().await?;
+
+        assert_eq!(db.spending_limits().await?, vec![replaced]);
+
+        let mut tx = db.tx().await?;
+        tx.clear_spending_policy().await?;
+        tx.commit().await?;
+
+        assert_eq!(db.spending_policy().await?, None);
+        assert!(db.spending_limits().await?.is_empty());
+        assert!(db.allowed_destinations().await?.is_empty());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_policy_spends() -> anyhow::Result<()> {
+        let db = test_database().await?;
+
+        let xch = Bytes32::default();
+        let cat = Bytes32::new([2; 32]);
+
+        let first = Bytes32::new([3; 32]);
+        let second = Bytes32::new([4; 32]);
+
+        let mut tx = db.tx().await?;
+        tx.insert_policy_spend(first, xch, 100).await?;
+        tx.insert_policy_spend(second, xch, u64::MAX).await?;
+        tx.insert_policy_spend(second, cat, 7).await?;
+        tx.commit().await?;
+
+        // The total can exceed a u64, since it's the sum of many transactions.
+        assert_eq!(
+            db.policy_spent_since(xch, 0).await?,
+            u128::from(u64::MAX) + 100
+        );
+        assert_eq!(db.policy_spent_since(cat, 0).await?, 7);
+
+        // Spends from before the start of the window aren't counted.
+        let future = i64::MAX.try_into()?;
+        assert_eq!(db.policy_spent_since(xch, future).await?, 0);
+
+        // Only the outflows of transactions that weren't submitted are released.
+        let mut tx = db.tx().await?;
+        tx.submit_policy_spends(first).await?;
+        tx.commit().await?;
+
+        db.release_unsubmitted_policy_spends(first).await?;
+        db.release_unsubmitted_policy_spends(second).await?;
+        assert_eq!(db.policy_spent_since(xch, 0).await?, 100);
+        assert_eq!(db.policy_spent_since(cat, 0).await?, 0);
+
+        // Rejected transactions are removed even once they've been submitted.
+        let mut tx = db.tx().await?;
+        tx.remove_policy_spends(first).await?;
+        tx.commit().await?;
+
+        assert_eq!(db.policy_spent_since(xch, 0).await?, 0);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,484 @@
+use chia::protocol::Bytes32;
+use sqlx::SqliteExecutor;
+
+use crate::{Asset, Convert, Database, DatabaseTx, Result};
+
+#[derive(Debug, Clone, Copy)]
+pub struct TradeRow {
+    pub id: u32,
+    pub offer_id: Bytes32,
+    pub is_maker: bool,
+    pub transaction_id: Option<Bytes32>,
+    pub fee: u64,
+    pub fill_height: Option<u32>,
+    pub fill_timestamp: Option<u64>,
+    pub inserted_timestamp: u64,
+}
+
+#[derive(Debug, Clone)]
+pub struct TradeAssetRow {
+    pub trade_id: u32,
+    pub asset: Asset,
+    pub is_received: bool,
+    pub amount: u64,
+    pub royalty: u64,
+}
+
+/// An asset traded in a filled trade, which links the movements of that asset in the fill's
+/// block to the offer.
+#[derive(Debug, Clone, Copy)]
+pub struct FilledTradeAsset {
+    pub fill_height: u32,
+    pub offer_id: Bytes32,
+    pub transaction_id: Option<Bytes32>,
+    pub asset_hash: Bytes32,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct UnfilledTrade {
+    pub id: u32,
+    pub settlement_coin_id: Bytes32,
+}
+
+impl Database {
+    pub async fn trades(&self) -> Result<Vec<TradeRow>> {
+        trades(&self.pool).await
+    }
+
+    pub async fn trade_assets(&self) -> Result<Vec<TradeAssetRow>> {
+        trade_assets(&self.pool).await
+    }
+
+    pub async fn filled_trade_assets(
+        &self,
+        start_height: u32,
+        end_height: u32,
+    ) -> Result<Vec<FilledTradeAsset>> {
+        filled_trade_assets(&self.pool, start_height, end_height).await
+    }
+
+    pub async fn unfilled_trades(&self) -> Result<Vec<UnfilledTrade>> {
+        unfilled_trades(&self.pool).await
+    }
+
+    pub async fn fill_trade(&self, id: u32, height: u32) -> Result<()> {
+        fill_trade(&self.pool, id, height).await
+    }
+
+    /// Removes taker trades which were recorded before the given timestamp and haven't been
+    /// filled, if their transaction isn't pending. These were either never submitted or failed.
+    pub async fn delete_stale_taker_trades(&self, before_timestamp: u64) -> Result<u64> {
+        delete_stale_taker_trades(&self.pool, before_timestamp).await
+    }
+}
+
+impl DatabaseTx<'_> {
+    /// Inserts a trade and returns its id, unless it has already been recorded. A taker trade which
+    /// hasn't been filled yet is replaced instead, since the offer has been taken again.
+    pub async fn insert_trade(
+        &mut self,
+        offer_id: Bytes32,
+        is_maker: bool,
+        settlement_coin_id: Bytes32,
+        transaction_id: Option<Bytes32>,
+        fee: u64,
+    ) -> Result<Option<u32>> {
+        insert_trade(
+            &mut *self.tx,
+            offer_id,
+            is_maker,
+            settlement_coin_id,
+            transaction_id,
+            fee,
+        )
+        .await
+    }
+
+    pub async fn insert_trade_asset(
+        &mut self,
+        trade_id: u32,
+        asset_id: Bytes32,
+        is_received: bool,
+        amount: u64,
+        royalty: u64,
+    ) -> Result<()> {
+        insert_trade_asset(
+            &mut *self.tx,
+            trade_id,
+            asset_id,
+            is_received,
+            amount,
+            royalty,
+        )
+        .await
+    }
+
+    pub async fn fill_trade(&mut self, id: u32, height: u32) -> Result<()> {
+        fill_trade(&mut *self.tx, id, height).await
+    }
+}
+
+async fn trades(conn: impl SqliteExecutor<'_>) -> Result<Vec<TradeRow>> {
+    sqlx::query!(
+        "
+        SELECT
+            trades.id, offer_hash, is_maker, transaction_hash, fee, fill_height,
+            blocks.timestamp AS fill_timestamp, inserted_timestamp
+        FROM trades
+        LEFT JOIN blocks ON blocks.height = trades.fill_height
+        ORDER BY fill_height IS NULL DESC, fill_height DESC, trades.id DESC
+        "
+    )
+    .fetch_all(conn)
+    .await?
+    .into_iter()
+    .map(|row| {
+        Ok(TradeRow {
+            id: row.id.convert()?,
+            offer_id: row.offer_hash.convert()?,
+            is_maker: row.is_maker,
+            transaction_id: row.transaction_hash.convert()?,
+            fee: row.fee.convert()?,
+            fill_height: row.fill_height.convert()?,
+            fill_timestamp: row.fill_timestamp.convert()?,
+            inserted_timestamp: row.inserted_timestamp.convert()?,
+        })
+    })
+    .collect()
+}
+
+async fn trade_assets(conn: impl SqliteExecutor<'_>) -> Result<Vec<TradeAssetRow>> {
+    sqlx::query!(
+        "
+        SELECT
+            trade_id, is_received, amount, royalty,
+            assets.hash, assets.description, assets.is_sensitive_content,
+            assets.is_visible, assets.icon_url, assets.name,
+            assets.ticker, assets.precision, assets.kind,
+            assets.hidden_puzzle_hash
+        FROM trade_assets
+        INNER JOIN assets ON assets.id = trade_assets.asset_id
+        ORDER BY trade_assets.id ASC
+        "
+    )
+    .fetch_all(conn)
+    .await?
+    .into_iter()
+    .map(|row| {
+        Ok(TradeAssetRow {
+            trade_id: row.trade_id.convert()?,
+            asset: Asset {
+                hash: row.hash.convert()?,
+                description: row.description,
+                is_sensitive_content: row.is_sensitive_content,
+                is_visible: row.is_visible,
+                icon_url: row.icon_url,
+                kind: row.kind.convert()?,
+                name: row.name,
+                ticker: row.ticker,
+                precision: row.precision.convert()?,
+                hidden_puzzle_hash: row.hidden_puzzle_hash.convert()?,
+            },
+            is_received: row.is_received,
+            amount: row.amount.convert()?,
+            royalty: row.royalty.convert()?,
+        })
+    })
+    .collect()
+}
+
+async fn filled_trade_assets(
+    conn: impl SqliteExecutor<'_>,
+    start_height: u32,
+    end_height: u32,
+) -> Result<Vec<FilledTradeAsset>> {
+    sqlx::query!(
+        "
+        SELECT
+            trades.fill_height AS \"fill_height!\", trades.offer_hash,
+            trades.transaction_hash, assets.hash AS asset_hash
+        FROM trades
+        INNER JOIN trade_assets ON trade_assets.trade_id = trades.id
+        INNER JOIN assets ON assets.id = trade_assets.asset_id
+        WHERE trades.fill_height BETWEEN ? AND ?
+        ORDER BY trades.id ASC
+        ",
+        start_height,
+        end_height
+    )
+    .fetch_all(conn)
+    .await?
+    .into_iter()
+    .map(|row| {
+        Ok(FilledTradeAsset {
+            fill_height: row.fill_height.convert()?,
+            offer_id: row.offer_hash.convert()?,
+            transaction_id: row.transaction_hash.convert()?,
+            asset_hash: row.asset_hash.convert()?,
+        })
+    })
+    .collect()
+}
+
+async fn unfilled_trades(conn: impl SqliteExecutor<'_>) -> Result<Vec<UnfilledTrade>> {
+    sqlx::query!("SELECT id, settlement_coin_hash FROM trades WHERE fill_height IS NULL")
+        .fetch_all(conn)
+        .await?
+        .into_iter()
+        .map(|row| {
+            Ok(UnfilledTrade {
+                id: row.id.convert()?,
+                settlement_coin_id: row.settlement_coin_hash.convert()?,
+            })
+        })
+        .collect()
+}
+
+async fn insert_trade(
+    conn: impl SqliteExecutor<'_>,
+    offer_id: Bytes32,
+    is_maker: bool,
+    settlement_coin_id: Bytes32,
+    transaction_id: Option<Bytes32>,
+    fee: u64,
+) -> Result<Option<u32>> {
+    let offer_id = offer_id.as_ref();
+    let settlement_coin_id = settlement_coin_id.as_ref();
+    let transaction_id = transaction_id.as_deref();
+    let fee = fee.to_be_bytes().to_vec();
+
+    sqlx::query!(
+        "
+        INSERT INTO trades (offer_hash, is_maker, settlement_coin_hash, transaction_hash, fee)
+        VALUES (?, ?, ?, ?, ?)
+        ON CONFLICT (offer_hash, is_maker) DO UPDATE SET
+            settlement_coin_hash = excluded.settlement_coin_hash,
+            transaction_hash = excluded.transaction_hash,
+            fee = excluded.fee,
+            inserted_timestamp = unixepoch()
+        WHERE NOT trades.is_maker AND trades.fill_height IS NULL
+        RETURNING id
+        ",
+        offer_id,
+        is_maker,
+        settlement_coin_id,
+        transaction_id,
+        fee
+    )
+    .fetch_optional(conn)
+    .await?
+    .map(|row| row.id.convert())
+    .transpose()
+}
+
+async fn insert_trade_asset(
+    conn: impl SqliteExecutor<'_>,
+    trade_id: u32,
+    asset_id: Bytes32,
+    is_received: bool,
+    amount: u64,
+    royalty: u64,
+) -> Result<()> {
+    let asset_id = asset_id.as_ref();
+    let amount = amount.to_be_bytes().to_vec();
+    let royalty = royalty.to_be_bytes().to_vec();
+
+    sqlx::query!(
+        "
+        INSERT OR IGNORE INTO trade_assets (trade_id, asset_id, is_received, amount, royalty)
+        VALUES (?, (SELECT id FROM assets WHERE hash = ?), ?, ?, ?)
+        ",
+        trade_id,
+        asset_id,
+        is_received,
+        amount,
+        royalty
+    )
+    .execute(conn)
+    .await?;
+
+    Ok(())
+}
+
+async fn fill_trade(conn: impl SqliteExecutor<'_>, id: u32, height: u32) -> Result<()> {
+    sqlx::query!("UPDATE trades SET fill_height = ? WHERE id = ?", height, id)
+        .execute(conn)
+        .await?;
+
+    Ok(())
+}
+
+async fn delete_stale_taker_trades(
+    conn: impl SqliteExecutor<'_>,
+    before_timestamp: u64,
+) -> Result<u64> {
+    let before_timestamp: i64 = before_timestamp.try_into()?;
+
+    Ok(sqlx::query!(
+        "
+        DELETE FROM trades
+        WHERE NOT is_maker AND fill_height IS NULL AND inserted_timestamp < ?
+        AND NOT EXISTS (
+            SELECT 1 FROM mempool_items WHERE mempool_items.hash = trades.transaction_hash
+        )
+        ",
+        before_timestamp
+    )
+    .execute(conn)
+    .await?
+    .rows_affected())
+}
+
+#[cfg(test)]
+mod tests {
+    use chia::bls::Signature;
+
+    use crate::test_database;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_filled_trade_assets() -> anyhow::Result<()> {
+        let db = test_database().await?;
+
+        let maker_offer_id = Bytes32::new([1; 32]);
+        let taker_offer_id = Bytes32::new([2; 32]);
+        let transaction_id = Bytes32::new([3; 32]);
+
+        let mut tx = db.tx().await?;
+
+        let maker_trade_id = tx
+            .insert_trade(maker_offer_id, true, Bytes32::new([4; 32]), None, 0)
+            .await?
+            .expect("trade wasn't inserted");
+        tx.insert_trade_asset(maker_trade_id, Bytes32::default(), false, 100, 0)
+            .await?;
+        tx.fill_trade(maker_trade_id, 10).await?;
+
+        let taker_trade_id = tx
+            .insert_trade(
+                taker_offer_id,
+                false,
+                Bytes32::new([5; 32]),
+                Some(transaction_id),
+                0,
+            )
+            .await?
+            .expect("trade wasn't inserted");
+        tx.insert_trade_asset(taker_trade_id, Bytes32::default(), true, 200, 0)
+            .await?;
+        tx.fill_trade(taker_trade_id, 20).await?;
+
+        tx.commit().await?;
+
+        let assets = db.filled_trade_assets(0, 15).await?;
+        assert_eq!(assets.len(), 1);
+        assert_eq!(assets[0].fill_height, 10);
+        assert_eq!(assets[0].offer_id, maker_offer_id);
+        assert_eq!(assets[0].transaction_id, None);
+        assert_eq!(assets[0].asset_hash, Bytes32::default());
+
+        let assets = db.filled_trade_assets(15, 20).await?;
+        assert_eq!(assets.len(), 1);
+        assert_eq!(assets[0].offer_id, taker_offer_id);
+        assert_eq!(assets[0].transaction_id, Some(transaction_id));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_taker_trades() -> anyhow::Result<()> {
+        let db = test_database().await?;
+
+        let offer_id = Bytes32::new([1; 32]);
+        let pending_offer_id = Bytes32::new([2; 32]);
+        let settlement_coin_id = Bytes32::new([3; 32]);
+        let first_transaction_id = Bytes32::new([4; 32]);
+        let second_transaction_id = Bytes32::new([5; 32]);
+
+        let mut tx = db.tx().await?;
+
+        let trade_id = tx
+            .insert_trade(
+                offer_id,
+                false,
+                settlement_coin_id,
+                Some(first_transaction_id),
+                10,
+            )
+            .await?
+            .expect("trade wasn't inserted");
+
+        // Taking the offer again replaces the trade, since the first take was never filled.
+        let retaken_trade_id = tx
+            .insert_trade(
+                offer_id,
+                false,
+                settlement_coin_id,
+                Some(second_transaction_id),
+                20,
+            )
+            .await?;
+        assert_eq!(retaken_trade_id, Some(trade_id));
+
+        // Once the trade has been filled, it's only recorded once.
+        tx.fill_trade(trade_id, 10).await?;
+        let retaken_trade_id = tx
+            .insert_trade(offer_id, false, settlement_coin_id, None, 0)
+            .await?;
+        assert_eq!(retaken_trade_id, None);
+
+        // Maker trades are never replaced.
+        tx.insert_trade(offer_id, true, settlement_coin_id, None, 0)
+            .await?
+            .expect("trade wasn't inserted");
+        let maker_trade_id = tx
+            .insert_trade(offer_id, true, settlement_coin_id, None, 0)
+            .await?;
+        assert_eq!(maker_trade_id, None);
+
+        tx.insert_trade(
+            pending_offer_id,
+            false,
+            settlement_coin_id,
+            Some(first_transaction_id),
+            0,
+        )
+        .await?
+        .expect("trade wasn't inserted");
+        tx.insert_mempool_item(first_transaction_id, Signature::default(), 0)
+            .await?;
+
+        tx.commit().await?;
+
+        let trades = db.trades().await?;
+        let trade = trades
+            .iter()
+            .find(|trade| trade.id == trade_id)
+            .expect("missing trade");
+        assert_eq!(trade.transaction_id, Some(second_transaction_id));
+        assert_eq!(trade.fee, 20);
+
+        // Only unfilled taker trades without a pending transaction are stale.
+        assert_eq!(db.delete_stale_taker_trades(0).await?, 0);
+        assert_eq!(db.delete_stale_taker_trades(u32::MAX.into()).await?, 0);
+        assert_eq!(db.trades().await?.len(), 3);
+
+        let mut tx = db.tx().await?;
+        tx.insert_trade(
+            Bytes32::new([6; 32]),
+            false,
+            settlement_coin_id,
+            Some(second_transaction_id),
+            0,
+        )
+        .await?
+        .expect("trade wasn't inserted");
+        tx.commit().await?;
+
+        assert_eq!(db.delete_stale_taker_trades(u32::MAX.into()).await?, 1);
+        assert_eq!(db.trades().await?.len(), 3);
+
+        Ok(())
+    }
+}
@@ -1,7 +1,10 @@
-use chia::protocol::{Bytes32, Coin};
+use chia::{
+    protocol::{Bytes, Bytes32, Coin},
+    traits::Streamable,
+};
 use sqlx::{Row, SqliteExecutor};
 
-use crate::{Asset, Convert, Database, Result};
+use crate::{index_submitted_memos, Asset, Convert, Database, DatabaseTx, Result};
 
 #[derive(Debug, Clone)]
 pub struct Transaction {
@@ -18,6 +21,28 @@ pub struct TransactionCoin {
     pub p2_puzzle_hash: Option<Bytes32>,
 }
 
+#[derive(Debug, Clone, Copy)]
+pub struct SubmittedTransactionRow {
+    pub height: u32,
+    pub hash: Bytes32,
+    pub fee: u64,
+}
+
+#[derive(Debug, Clone)]
+pub struct SubmittedPaymentRow {
+    pub asset_hash: Bytes32,
+    pub p2_puzzle_hash: Bytes32,
+    pub amount: u64,
+    pub memos: Vec<Bytes>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ConfirmedPaymentRow {
+    pub height: u32,
+    pub transaction_id: Bytes32,
+    pub payment: SubmittedPaymentRow,
+}
+
 impl Database {
     pub async fn transaction(&self, height: u32) -> Result<Option<Transaction>> {
         transaction(&self.pool, height).await
@@ -32,6 +57,62 @@ impl Database {
     ) -> Result<(Vec<Transaction>, u32)> {
         transactions(&self.pool, find_value, sort_ascending, limit, offset).await
     }
+
+    pub async fn transactions_in_range(
+        &self,
+        start_height: Option<u32>,
+        end_height: Option<u32>,
+        start_timestamp: Option<u64>,
+        end_timestamp: Option<u64>,
+    ) -> Result<Vec<Transaction>> {
+        transactions_in_range(
+            &self.pool,
+            start_height,
+            end_height,
+            start_timestamp,
+            end_timestamp,
+        )
+        .await
+    }
+
+    pub async fn confirmed_submitted_transactions(
+        &self,
+        start_height: u32,
+        end_height: u32,
+    ) -> Result<Vec<SubmittedTransactionRow>> {
+        confirmed_submitted_transactions(&self.pool, start_height, end_height).await
+    }
+
+    pub async fn confirmed_submitted_payments(
+        &self,
+        start_height: u32,
+        end_height: u32,
+    ) -> Result<Vec<ConfirmedPaymentRow>> {
+        confirmed_submitted_payments(&self.pool, start_height, end_height).await
+    }
+}
+
+impl DatabaseTx<'_> {
+    pub async fn insert_submitted_transaction(
+        &mut self,
+        hash: Bytes32,
+        input_coin_id: Bytes32,
+        fee: u64,
+    ) -> Result<Option<u32>> {
+        insert_submitted_transaction(&mut *self.tx, hash, input_coin_id, fee).await
+    }
+
+    pub async fn insert_submitted_payment(
+        &mut self,
+        submitted_transaction_id: u32,
+        payment: SubmittedPaymentRow,
+    ) -> Result<()> {
+        let memos = payment.memos.clone();
+
+        insert_submitted_payment(&mut *self.tx, submitted_transaction_id, payment).await?;
+
+        index_submitted_memos(&mut *self.tx, submitted_transaction_id, &memos).await
+    }
 }
 
 // Helper function to create a TransactionCoin from a database row
@@ -207,6 +288,173 @@ async fn transactions(
     Ok((transactions, total_count as u32))
 }
 
+async fn transactions_in_range(
+    conn: impl SqliteExecutor<'_>,
+    start_height: Option<u32>,
+    end_height: Option<u32>,
+    start_timestamp: Option<u64>,
+    end_timestamp: Option<u64>,
+) -> Result<Vec<Transaction>> {
+    let mut query = sqlx::QueryBuilder::new(
+        "SELECT
+            height, timestamp, coin_id, puzzle_hash, parent_coin_hash, amount,
+            is_created_in_block, is_spent_in_block, asset_hash, asset_description,
+            asset_is_visible, asset_is_sensitive_content, asset_name, asset_icon_url,
+            asset_kind, p2_puzzle_hash, asset_ticker, asset_precision, asset_hidden_puzzle_hash
+        FROM transaction_coins
+        WHERE 1=1",
+    );
+
+    if let Some(start_height) = start_height {
+        query.push(" AND height >= ");
+        query.push_bind(start_height);
+    }
+
+    if let Some(end_height) = end_height {
+        query.push(" AND height <= ");
+        query.push_bind(end_height);
+    }
+
+    if let Some(start_timestamp) = start_timestamp {
+        query.push(" AND timestamp >= ");
+        query.push_bind(i64::try_from(start_timestamp)?);
+    }
+
+    if let Some(end_timestamp) = end_timestamp {
+        query.push(" AND timestamp <= ");
+        query.push_bind(i64::try_from(end_timestamp)?);
+    }
+
+    query.push(" ORDER BY height ASC");
+
+    let rows = query.build().fetch_all(conn).await?;
+
+    group_rows_into_transactions(rows, true)
+}
+
+async fn confirmed_submitted_transactions(
+    conn: impl SqliteExecutor<'_>,
+    start_height: u32,
+    end_height: u32,
+) -> Result<Vec<SubmittedTransactionRow>> {
+    sqlx::query!(
+        "
+        SELECT coins.spent_height AS \"height!\", submitted_transactions.hash, fee
+        FROM submitted_transactions
+        INNER JOIN coins ON coins.hash = submitted_transactions.input_coin_hash
+        WHERE coins.spent_height BETWEEN ? AND ?
+        ORDER BY submitted_transactions.id ASC
+        ",
+        start_height,
+        end_height
+    )
+    .fetch_all(conn)
+    .await?
+    .into_iter()
+    .map(|row| {
+        Ok(SubmittedTransactionRow {
+            height: row.height.convert()?,
+            hash: row.hash.convert()?,
+            fee: row.fee.convert()?,
+        })
+    })
+    .collect()
+}
+
+async fn confirmed_submitted_payments(
+    conn: impl SqliteExecutor<'_>,
+    start_height: u32,
+    end_height: u32,
+) -> Result<Vec<ConfirmedPaymentRow>> {
+    sqlx::query!(
+        "
+        SELECT
+            coins.spent_height AS \"height!\", submitted_transactions.hash,
+            submitted_payments.asset_hash, submitted_payments.p2_puzzle_hash,
+            submitted_payments.amount, submitted_payments.memos
+        FROM submitted_payments
+        INNER JOIN submitted_transactions
+            ON submitted_transactions.id = submitted_payments.submitted_transaction_id
+        INNER JOIN coins ON coins.hash = submitted_transactions.input_coin_hash
+        WHERE coins.spent_height BETWEEN ? AND ?
+        ORDER BY submitted_payments.id ASC
+        ",
+        start_height,
+        end_height
+    )
+    .fetch_all(conn)
+    .await?
+    .into_iter()
+    .map(|row| {
+        Ok(ConfirmedPaymentRow {
+            height: row.height.convert()?,
+            transaction_id: row.hash.convert()?,
+            payment: SubmittedPaymentRow {
+                asset_hash: row.asset_hash.convert()?,
+                p2_puzzle_hash: row.p2_puzzle_hash.convert()?,
+                amount: row.amount.convert()?,
+                memos: Vec::<Bytes>::from_bytes(&row.memos)?,
+            },
+        })
+    })
+    .collect()
+}
+
+async fn insert_submitted_transaction(
+    conn: impl SqliteExecutor<'_>,
+    hash: Bytes32,
+    input_coin_id: Bytes32,
+    fee: u64,
+) -> Result<Option<u32>> {
+    let hash = hash.as_ref();
+    let input_coin_id = input_coin_id.as_ref();
+    let fee = fee.to_be_bytes().to_vec();
+
+    sqlx::query_scalar!(
+        "
+        INSERT INTO submitted_transactions (hash, input_coin_hash, fee)
+        VALUES (?, ?, ?)
+        ON CONFLICT (hash) DO NOTHING
+        RETURNING id
+        ",
+        hash,
+        input_coin_id,
+        fee
+    )
+    .fetch_optional(conn)
+    .await?
+    .map(Convert::convert)
+    .transpose()
+}
+
+async fn insert_submitted_payment(
+    conn: impl SqliteExecutor<'_>,
+    submitted_transaction_id: u32,
+    payment: SubmittedPaymentRow,
+) -> Result<()> {
+    let asset_hash = payment.asset_hash.as_ref();
+    let p2_puzzle_hash = payment.p2_puzzle_hash.as_ref();
+    let amount = payment.amount.to_be_bytes().to_vec();
+    let memos = payment.memos.to_bytes()?;
+
+    sqlx::query!(
+        "
+        INSERT INTO submitted_payments
+            (submitted_transaction_id, asset_hash, p2_puzzle_hash, amount, memos)
+        VALUES (?, ?, ?, ?, ?)
+        ",
+        submitted_transaction_id,
+        asset_hash,
+        p2_puzzle_hash,
+        amount,
+        memos
+    )
+    .execute(conn)
+    .await?;
+
+    Ok(())
+}
+
 pub fn is_valid_asset_id(asset_id: &str) -> bool {
     asset_id.len() == 64 && asset_id.chars().all(|c| c.is_ascii_hexdigit())
 }
@@ -0,0 +1,16 @@
+use sqlx::{migrate, sqlite::SqlitePoolOptions};
+
+use crate::Database;
+
+/// An in-memory database with every migration applied. It only has a single connection, so a
+/// transaction has to be committed before the database can be queried outside of it.
+pub async fn test_database() -> anyhow::Result<Database> {
+    let pool = SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await?;
+
+    migrate!("../../migrations").run(&pool).await?;
+
+    Ok(Database::new(pool))
+}
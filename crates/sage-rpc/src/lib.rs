@@ -49,6 +49,7 @@ where
                 ErrorKind::Api => StatusCode::BAD_REQUEST,
                 ErrorKind::NotFound => StatusCode::NOT_FOUND,
                 ErrorKind::Unauthorized => StatusCode::UNAUTHORIZED,
+                ErrorKind::Policy => StatusCode::FORBIDDEN,
                 ErrorKind::DatabaseMigration
                 | ErrorKind::Wallet
                 | ErrorKind::Internal
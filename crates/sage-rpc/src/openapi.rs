@@ -61,18 +61,41 @@ pub fn generate_openapi() -> OpenApi {
         .schema_from::<sage_api::CoinRecord>()
         .schema_from::<sage_api::TokenRecord>()
         .schema_from::<sage_api::DidRecord>()
+        .schema_from::<sage_api::DidMetadataEntry>()
         .schema_from::<sage_api::NftRecord>()
         .schema_from::<sage_api::NftCollectionRecord>()
         .schema_from::<sage_api::OptionRecord>()
         .schema_from::<sage_api::TransactionRecord>()
+        .schema_from::<sage_api::TransactionExportRecord>()
+        .schema_from::<sage_api::ExportFormat>()
         .schema_from::<sage_api::PendingTransactionRecord>()
         .schema_from::<sage_api::DerivationRecord>()
+        .schema_from::<sage_api::ContactRecord>()
+        .schema_from::<sage_api::ContactAddressRecord>()
+        .schema_from::<sage_api::ContactAddress>()
+        .schema_from::<sage_api::ScheduledPaymentRecord>()
+        .schema_from::<sage_api::ScheduledPaymentRunRecord>()
+        .schema_from::<sage_api::SpendingPolicy>()
+        .schema_from::<sage_api::SpendingLimit>()
+        .schema_from::<sage_api::PayoutRecord>()
+        .schema_from::<sage_api::PayoutPaymentRecord>()
+        .schema_from::<sage_api::PayoutFormat>()
+        .schema_from::<sage_api::MintJobRecord>()
+        .schema_from::<sage_api::MintJobItemRecord>()
+        .schema_from::<sage_api::MintJobFormat>()
+        .schema_from::<sage_api::NftIntegrityRecord>()
+        .schema_from::<sage_api::FileIntegrityRecord>()
+        .schema_from::<sage_api::UriIntegrityRecord>()
+        .schema_from::<sage_api::NftIntegritySummary>()
+        .schema_from::<sage_api::IntegrityStatus>()
+        .schema_from::<sage_api::UriIntegrityStatus>()
         .schema_from::<sage_api::PeerRecord>()
         .schema_from::<sage_api::KeyInfo>()
         .schema_from::<sage_api::SecretKeyInfo>()
         .schema_from::<sage_api::KeyKind>()
         .schema_from::<sage_api::NftData>()
         .schema_from::<sage_api::NftSpecialUseType>()
+        .schema_from::<sage_api::NftPreviewSource>()
         .schema_from::<sage_api::Asset>()
         .schema_from::<sage_api::AssetKind>()
         .schema_from::<sage_api::AddressKind>()
@@ -80,16 +103,33 @@ pub fn generate_openapi() -> OpenApi {
         .schema_from::<sage_api::CoinFilterMode>()
         .schema_from::<sage_api::OptionSortMode>()
         .schema_from::<sage_api::NftSortMode>()
+        .schema_from::<sage_api::NftTraitFilter>()
+        .schema_from::<sage_api::NftTraitCount>()
+        .schema_from::<sage_api::SearchResult>()
+        .schema_from::<sage_api::SearchKind>()
         .schema_from::<sage_api::NftUriKind>()
         .schema_from::<sage_api::TransactionSummary>()
         .schema_from::<sage_api::TransactionInput>()
         .schema_from::<sage_api::TransactionOutput>()
+        .schema_from::<sage_api::NftMetadataDiff>()
+        .schema_from::<sage_api::NftMetadataJson>()
         .schema_from::<sage_api::TransactionCoinRecord>()
         .schema_from::<sage_api::CoinSpendJson>()
         .schema_from::<sage_api::SpendBundleJson>()
         .schema_from::<sage_api::CoinJson>()
         .schema_from::<sage_api::OfferRecord>()
         .schema_from::<sage_api::OfferRecordStatus>()
+        .schema_from::<sage_api::OfferCoinState>()
+        .schema_from::<sage_api::OfferCoinStatus>()
+        .schema_from::<sage_api::OfferPublicationRecord>()
+        .schema_from::<sage_api::ExchangeOfferRecord>()
+        .schema_from::<sage_api::TradeRecord>()
+        .schema_from::<sage_api::TradeAmount>()
+        .schema_from::<sage_api::TradePrice>()
+        .schema_from::<sage_api::TradeAssetPnl>()
+        .schema_from::<sage_api::OfferGroupRecord>()
+        .schema_from::<sage_api::OfferGroupOfferRecord>()
+        .schema_from::<sage_api::LadderDistribution>()
         .schema_from::<sage_api::OfferSummary>()
         .schema_from::<sage_api::OfferAsset>()
         .schema_from::<sage_api::NftRoyalty>()
@@ -97,6 +137,11 @@ pub fn generate_openapi() -> OpenApi {
         .schema_from::<sage_api::Payment>()
         .schema_from::<sage_api::NftMint>()
         .schema_from::<sage_api::OfferAmount>()
+        .schema_from::<sage_api::NftRoyaltyPayment>()
+        .schema_from::<sage_api::OfferTradePrice>()
+        .schema_from::<sage_api::NftOffer>()
+        .schema_from::<sage_api::ImportOfferResult>()
+        .schema_from::<sage_api::ImportOfferStatus>()
         .schema_from::<sage_api::OptionAsset>()
         .schema_from::<sage_api::NetworkKind>();
 
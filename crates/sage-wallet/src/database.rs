@@ -6,16 +6,18 @@ use chia::{
     protocol::{Bytes32, CoinState},
     puzzles::{nft::NftMetadata, LineageProof},
 };
+use chia_puzzles::SETTLEMENT_PAYMENT_HASH;
 use chia_wallet_sdk::driver::{OptionInfo, OptionType, OptionUnderlying};
 use sage_assets::base64_data_uri;
 use sage_database::{
     Asset, AssetKind, Database, DatabaseTx, DidCoinInfo, NftCoinInfo, OptionCoinInfo,
-    SerializedNftInfo,
+    SerializedNftInfo, SubmittedPaymentRow,
 };
 use tracing::{error, warn};
 
 use crate::{
-    compute_nft_info, ChildKind, OptionContext, PuzzleContext, Transaction, WalletError, WalletPeer,
+    compute_nft_info, ChildKind, CoinKind, OptionContext, PuzzleContext, Transaction, WalletError,
+    WalletPeer,
 };
 
 pub async fn validate_wallet_coin(
@@ -302,6 +304,8 @@ pub async fn insert_nft(
         edition_total: metadata.as_ref().map(|m| m.edition_total),
     };
 
+    let mut traits = None;
+
     if let Some(metadata_hash) = &metadata.as_ref().and_then(|m| m.metadata_hash) {
         if let Some(blob) = tx.file_data(*metadata_hash).await? {
             let computed = compute_nft_info(coin_info.minter_hash, &blob);
@@ -313,6 +317,8 @@ pub async fn insert_nft(
                 coin_info.collection_hash = collection.hash;
                 tx.insert_collection(collection).await?;
             }
+
+            traits = Some(computed.traits);
         }
     }
 
@@ -320,6 +326,10 @@ pub async fn insert_nft(
 
     tx.insert_nft(info.launcher_id, &coin_info).await?;
 
+    if let Some(traits) = traits {
+        tx.set_nft_traits(info.launcher_id, &traits).await?;
+    }
+
     if coin_state.spent_height.is_none() || lineage_proof.is_none() {
         tx.update_nft(info.launcher_id, &coin_info).await?;
     }
@@ -515,6 +525,33 @@ pub async fn insert_transaction(
     transaction_id: Bytes32,
     transaction: Transaction,
     aggregated_signature: Signature,
+) -> Result<Vec<Bytes32>, WalletError> {
+    let result = insert_mempool_transaction(
+        db,
+        peer,
+        genesis_challenge,
+        transaction_id,
+        transaction,
+        aggregated_signature,
+    )
+    .await;
+
+    // The outflows recorded when the transaction was signed don't count if it never made it
+    // into the mempool queue.
+    if result.is_err() {
+        db.release_unsubmitted_policy_spends(transaction_id).await?;
+    }
+
+    result
+}
+
+async fn insert_mempool_transaction(
+    db: &Database,
+    peer: &WalletPeer,
+    genesis_challenge: Bytes32,
+    transaction_id: Bytes32,
+    transaction: Transaction,
+    aggregated_signature: Signature,
 ) -> Result<Vec<Bytes32>, WalletError> {
     // Make lookups faster for inputs and outputs, and prepare pending coin spends.
     let mut coin_spends = HashMap::new();
@@ -550,6 +587,10 @@ pub async fn insert_transaction(
     tx.insert_mempool_item(transaction_id, aggregated_signature, transaction.fee)
         .await?;
 
+    tx.submit_policy_spends(transaction_id).await?;
+
+    insert_submitted_transaction(&mut tx, transaction_id, &transaction, &output_coin_ids).await?;
+
     let mut subscriptions = Vec::new();
 
     for (index, input) in transaction.inputs.iter().enumerate() {
@@ -658,3 +699,91 @@ pub async fn insert_transaction(
 
     Ok(subscriptions)
 }
+
+/// Keeps the fee and the payments made to other wallets, since they can't be recovered from the
+/// synced coins once the transaction is confirmed.
+async fn insert_submitted_transaction(
+    tx: &mut DatabaseTx<'_>,
+    transaction_id: Bytes32,
+    transaction: &Transaction,
+    output_coin_ids: &HashSet<Bytes32>,
+) -> Result<(), WalletError> {
+    let mut input_coin_id = None;
+
+    for input in &transaction.inputs {
+        let coin_id = input.coin_spend.coin.coin_id();
+
+        if !output_coin_ids.contains(&coin_id) && tx.is_known_coin(coin_id).await? {
+            input_coin_id = Some(coin_id);
+            break;
+        }
+    }
+
+    // Without a coin of our own being spent, there's no way to tell when it's confirmed.
+    let Some(input_coin_id) = input_coin_id else {
+        return Ok(());
+    };
+
+    let Some(submitted_transaction_id) = tx
+        .insert_submitted_transaction(transaction_id, input_coin_id, transaction.fee)
+        .await?
+    else {
+        return Ok(());
+    };
+
+    let input_coin_ids: HashSet<Bytes32> = transaction
+        .inputs
+        .iter()
+        .map(|input| input.coin_spend.coin.coin_id())
+        .collect();
+
+    for input in &transaction.inputs {
+        for output in &input.outputs {
+            // Ephemeral coins are spent by the same transaction, so they aren't payments.
+            if input_coin_ids.contains(&output.coin.coin_id()) {
+                continue;
+            }
+
+            let asset_hash = match (&input.kind, &output.kind) {
+                (CoinKind::Unknown, ChildKind::Unknown | ChildKind::Clawback { .. }) => {
+                    Bytes32::default()
+                }
+                (_, ChildKind::Cat { info, .. }) => info.asset_id,
+                (_, ChildKind::Did { info, .. }) => info.launcher_id,
+                (_, ChildKind::Nft { info, .. }) => info.launcher_id,
+                (_, ChildKind::Option { info, .. }) => info.launcher_id,
+                _ => continue,
+            };
+
+            let p2_puzzle_hash = output
+                .kind
+                .receiver_custody_p2_puzzle_hash()
+                .unwrap_or(output.coin.puzzle_hash);
+
+            if p2_puzzle_hash == SETTLEMENT_PAYMENT_HASH.into()
+                || tx.is_custody_p2_puzzle_hash(p2_puzzle_hash).await?
+            {
+                continue;
+            }
+
+            let mut memos = output.memos.clone();
+
+            if memos.first().map(AsRef::as_ref) == Some(p2_puzzle_hash.as_ref()) {
+                memos.remove(0);
+            }
+
+            tx.insert_submitted_payment(
+                submitted_transaction_id,
+                SubmittedPaymentRow {
+                    asset_hash,
+                    p2_puzzle_hash,
+                    amount: output.coin.amount,
+                    memos,
+                },
+            )
+            .await?;
+        }
+    }
+
+    Ok(())
+}
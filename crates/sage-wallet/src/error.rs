@@ -2,6 +2,7 @@ use std::{num::TryFromIntError, time::SystemTimeError};
 
 use chia::{
     clvm_traits::{FromClvmError, ToClvmError},
+    consensus::validation_error::ErrorCode,
     protocol::Bytes32,
 };
 use chia_wallet_sdk::{
@@ -69,6 +70,9 @@ pub enum WalletError {
     #[error("Insufficient funds")]
     InsufficientFunds,
 
+    #[error("Spending policy violation: {0}")]
+    PolicyViolation(String),
+
     #[error("Insufficient derivations")]
     InsufficientDerivations,
 
@@ -108,6 +112,12 @@ pub enum WalletError {
     #[error("Uncancellable offer")]
     UncancellableOffer,
 
+    #[error("No offers to take")]
+    NoOffers,
+
+    #[error("The offers spend the same coins or request the same NFT")]
+    ConflictingOffers,
+
     #[error("Cannot import offer that doesn't belong to this wallet")]
     CannotImportOffer,
 
@@ -120,9 +130,51 @@ pub enum WalletError {
     #[error("Unsupported clawback coin kind: {0:?}")]
     UnsupportedClawbackCoinKind(CoinKind),
 
+    #[error("The metadata updater of NFT {0} isn't supported")]
+    UnsupportedMetadataUpdater(Bytes32),
+
+    #[error("NFT {0} has no {1} URIs, so its metadata updater can't add any")]
+    MissingNftUriList(Bytes32, &'static str),
+
     #[error("Cannot find clawback info for coin with id {0}")]
     MissingClawbackInfo(Bytes32),
 
+    #[error("Invalid schedule: {0}")]
+    InvalidSchedule(String),
+
+    #[error("Invalid price: {0}")]
+    InvalidPrice(String),
+
+    #[error("Validation error: {0:?}")]
+    Validation(ErrorCode),
+
+    #[error("A single payment or mint exceeds the maximum transaction cost of {0}")]
+    CostExceeded(u64),
+
+    #[error("Missing coin for offer ladder rung {0}")]
+    MissingLadderCoin(usize),
+
+    #[error("The replacement offer would be priced below the stop price of {0}")]
+    StopPriceReached(String),
+
+    #[error("The replacement offer would exceed the max inventory of {0}")]
+    MaxInventoryReached(u64),
+
+    #[error("DID {0} can't be recovered, since it has no recovery list")]
+    RecoveryDisabled(Bytes32),
+
+    #[error("The recovery list doesn't match the recovery list hash of the DID")]
+    RecoveryListMismatch,
+
+    #[error("Missing attestation from DID {0}")]
+    MissingAttestation(Bytes32),
+
+    #[error("Invalid attestation: {0}")]
+    InvalidAttestation(String),
+
+    #[error("The puzzle of DID {0} hasn't been revealed on-chain, so it can't be recovered")]
+    UnknownDidPuzzle(Bytes32),
+
     #[error("Try from int error: {0}")]
     TryFromInt(#[from] TryFromIntError),
 }
@@ -1,13 +1,23 @@
 mod blocktime_queue;
 mod cat_queue;
+mod mint_job_queue;
 mod nft_uri_queue;
+mod offer_group_queue;
 mod offer_queue;
+mod offer_recreate_queue;
+mod payout_queue;
 mod puzzle_queue;
+mod scheduled_payment_queue;
 mod transaction_queue;
 
 pub use blocktime_queue::*;
 pub use cat_queue::*;
+pub use mint_job_queue::*;
 pub use nft_uri_queue::*;
+pub use offer_group_queue::*;
 pub use offer_queue::*;
+pub use offer_recreate_queue::*;
+pub use payout_queue::*;
 pub use puzzle_queue::*;
+pub use scheduled_payment_queue::*;
 pub use transaction_queue::*;
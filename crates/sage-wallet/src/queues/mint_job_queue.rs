@@ -0,0 +1,396 @@
+use std::{collections::HashSet, sync::Arc, time::Duration};
+
+use chia::{
+    bls::{SecretKey, Signature},
+    protocol::{Bytes32, SpendBundle},
+};
+use sage_database::{MintJobBatchStatus, MintJobRow};
+use tokio::{
+    sync::{mpsc, Mutex},
+    time::sleep,
+};
+use tracing::{info, warn};
+
+use crate::{
+    insert_transaction, PeerState, SyncCommand, SyncEvent, Transaction, Wallet, WalletError,
+    WalletNftMint, WalletPeer,
+};
+
+#[derive(Debug)]
+pub struct MintJobQueue {
+    wallet: Arc<Wallet>,
+    master_sk: SecretKey,
+    state: Arc<Mutex<PeerState>>,
+    sync_sender: mpsc::Sender<SyncEvent>,
+    command_sender: mpsc::Sender<SyncCommand>,
+}
+
+impl MintJobQueue {
+    pub fn new(
+        wallet: Arc<Wallet>,
+        master_sk: SecretKey,
+        state: Arc<Mutex<PeerState>>,
+        sync_sender: mpsc::Sender<SyncEvent>,
+        command_sender: mpsc::Sender<SyncCommand>,
+    ) -> Self {
+        Self {
+            wallet,
+            master_sk,
+            state,
+            sync_sender,
+            command_sender,
+        }
+    }
+
+    pub async fn start(self, delay: Duration) -> Result<(), WalletError> {
+        loop {
+            self.process_batch().await?;
+            sleep(delay).await;
+        }
+    }
+
+    async fn process_batch(&self) -> Result<(), WalletError> {
+        let mint_job_ids = self.wallet.db.active_mint_job_ids().await?;
+
+        if mint_job_ids.is_empty() {
+            return Ok(());
+        }
+
+        let Some(peer) = self.state.lock().await.acquire_peer() else {
+            return Ok(());
+        };
+
+        for mint_job_id in mint_job_ids {
+            // Every batch spends the DID, so the next one can't be created until the previous
+            // one has been confirmed.
+            if let Some(batch) = self.wallet.db.pending_mint_job_batch(mint_job_id).await? {
+                match self.wallet.db.mint_job_batch_status(batch).await? {
+                    MintJobBatchStatus::Pending => continue,
+                    MintJobBatchStatus::Confirmed => {
+                        info!(
+                            "Mint job {mint_job_id} batch with transaction id {} was confirmed",
+                            batch.transaction_id
+                        );
+
+                        self.wallet.db.confirm_mint_job_batch(batch.id).await?;
+                    }
+                    MintJobBatchStatus::Failed => {
+                        warn!(
+                            "Mint job {mint_job_id} batch with transaction id {} failed",
+                            batch.transaction_id
+                        );
+
+                        let mut tx = self.wallet.db.tx().await?;
+                        tx.fail_mint_job_batch(batch.id).await?;
+                        tx.commit().await?;
+
+                        self.wallet
+                            .db
+                            .update_mint_job_status(
+                                mint_job_id,
+                                true,
+                                Some(format!("Transaction {} failed", batch.transaction_id)),
+                            )
+                            .await?;
+                    }
+                }
+
+                self.sync_sender
+                    .send(SyncEvent::MintJobUpdated { mint_job_id })
+                    .await
+                    .ok();
+
+                continue;
+            }
+
+            let Some(mint_job) = self.wallet.db.mint_job(mint_job_id).await? else {
+                continue;
+            };
+
+            match self.submit(&peer, &mint_job).await {
+                Ok(Some(transaction_id)) => {
+                    info!(
+                        "Submitted mint job {mint_job_id} batch with transaction id {transaction_id}"
+                    );
+                }
+                Ok(None) => continue,
+                Err(error) => {
+                    warn!("Mint job {mint_job_id} batch could not be submitted: {error}");
+
+                    self.wallet
+                        .db
+                        .update_mint_job_status(mint_job_id, true, Some(error.to_string()))
+                        .await?;
+                }
+            }
+
+            self.sync_sender
+                .send(SyncEvent::MintJobUpdated { mint_job_id })
+                .await
+                .ok();
+        }
+
+        Ok(())
+    }
+
+    async fn submit(
+        &self,
+        peer: &WalletPeer,
+        mint_job: &MintJobRow,
+    ) -> Result<Option<Bytes32>, WalletError> {
+        let rows = self
+            .wallet
+            .db
+            .unsubmitted_mint_job_items(mint_job.id)
+            .await?;
+
+        if rows.is_empty() {
+            return Ok(None);
+        }
+
+        let mints: Vec<WalletNftMint> = rows
+            .iter()
+            .map(|row| WalletNftMint {
+                metadata: row.metadata.clone(),
+                p2_puzzle_hash: row.p2_puzzle_hash,
+                royalty_puzzle_hash: row.royalty_puzzle_hash,
+                royalty_basis_points: row.royalty_basis_points,
+            })
+            .collect();
+
+        let (coin_spends, nfts, count) = self
+            .wallet
+            .bulk_mint_nfts_within_cost(mint_job.fee, mint_job.did_id, &mints, mint_job.max_cost)
+            .await?;
+
+        let spend_bundle = self
+            .wallet
+            .sign_transaction(
+                SpendBundle::new(coin_spends, Signature::default()),
+                &self.wallet.agg_sig_constants,
+                self.master_sk.clone(),
+                false,
+            )
+            .await?;
+
+        let transaction_id = spend_bundle.name();
+
+        // Launchers are created and spent in the same transaction, so they can't be used to
+        // tell whether it was confirmed.
+        let spent_coin_ids: HashSet<Bytes32> = spend_bundle
+            .coin_spends
+            .iter()
+            .map(|coin_spend| coin_spend.coin.coin_id())
+            .collect();
+
+        let input_coin_id = spend_bundle
+            .coin_spends
+            .iter()
+            .find(|coin_spend| !spent_coin_ids.contains(&coin_spend.coin.parent_coin_info))
+            .map_or(spend_bundle.coin_spends[0].coin.coin_id(), |coin_spend| {
+                coin_spend.coin.coin_id()
+            });
+
+        // The batch is recorded before the transaction, so that if the wallet stops in
+        // between, the batch is treated as failed rather than the NFTs being minted twice.
+        let mut tx = self.wallet.db.tx().await?;
+
+        let batch_id = tx
+            .insert_mint_job_batch(mint_job.id, transaction_id, input_coin_id)
+            .await?;
+
+        for (row, nft) in rows[..count].iter().zip(&nfts) {
+            tx.set_mint_job_item_batch(mint_job.id, row.row_index, batch_id, nft.info.launcher_id)
+                .await?;
+        }
+
+        tx.commit().await?;
+
+        let subscriptions = insert_transaction(
+            &self.wallet.db,
+            peer,
+            self.wallet.genesis_challenge,
+            transaction_id,
+            Transaction::from_coin_spends(spend_bundle.coin_spends)?,
+            spend_bundle.aggregated_signature,
+        )
+        .await?;
+
+        self.command_sender
+            .send(SyncCommand::SubscribeCoins {
+                coin_ids: subscriptions,
+            })
+            .await
+            .ok();
+
+        Ok(Some(transaction_id))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use chia::puzzles::nft::NftMetadata;
+    use sage_database::{MintJobItemRow, SpendingPolicyRow};
+    use test_log::test;
+
+    use crate::{
+        spend_cost, SyncCommand, SyncEvent, TestWallet, WalletNftMint, MAX_TRANSACTION_COST,
+    };
+
+    fn item(row_index: u32) -> MintJobItemRow {
+        MintJobItemRow {
+            row_index,
+            metadata: NftMetadata {
+                edition_number: u64::from(row_index) + 1,
+                edition_total: 5,
+                data_uris: vec![format!("https://example.com/{row_index}.png")],
+                ..Default::default()
+            },
+            p2_puzzle_hash: None,
+            royalty_puzzle_hash: None,
+            royalty_basis_points: 300,
+        }
+    }
+
+    #[test(tokio::test)]
+    async fn test_mint_job_batches() -> anyhow::Result<()> {
+        let mut alice = TestWallet::new(10).await?;
+
+        let (coin_spends, did) = alice.wallet.create_did(0).await?;
+        alice.transact(coin_spends).await?;
+        alice.wait_for_coins().await;
+
+        let did_id = did.info.launcher_id;
+
+        // Limit the cost to what two NFTs need, so that five NFTs take three batches.
+        let max_cost = spend_cost(
+            &alice
+                .wallet
+                .bulk_mint_nfts(
+                    0,
+                    did_id,
+                    (0..2)
+                        .map(|row_index| WalletNftMint {
+                            metadata: item(row_index).metadata,
+                            p2_puzzle_hash: None,
+                            royalty_puzzle_hash: None,
+                            royalty_basis_points: 300,
+                        })
+                        .collect(),
+                )
+                .await?
+                .0,
+        )?;
+
+        let mut tx = alice.wallet.db.tx().await?;
+        let id = tx
+            .insert_mint_job("Test".to_string(), did_id, 0, max_cost)
+            .await?;
+        for row_index in 0..5 {
+            tx.insert_mint_job_item(id, item(row_index)).await?;
+        }
+        tx.commit().await?;
+
+        alice
+            .sender
+            .send(SyncCommand::SetSigningKey(Some(alice.master_sk.clone())))
+            .await?;
+
+        loop {
+            alice
+                .consume_until(|event| matches!(event, SyncEvent::MintJobUpdated { .. }))
+                .await;
+
+            let mint_job = alice
+                .wallet
+                .db
+                .mint_job(id)
+                .await?
+                .expect("missing mint job");
+            assert_eq!(mint_job.error, None);
+
+            if mint_job.confirmed_count == 5 {
+                break;
+            }
+        }
+
+        assert!(alice.wallet.db.active_mint_job_ids().await?.is_empty());
+
+        let items = alice.wallet.db.mint_job_items(id).await?;
+        assert!(items.iter().all(|item| item.is_confirmed));
+
+        let transaction_ids: HashSet<_> = items
+            .iter()
+            .filter_map(|item| item.transaction_id)
+            .collect();
+        assert_eq!(transaction_ids.len(), 3);
+
+        // Batches are only confirmed once the wallet has synced the spent coins, which is also
+        // when the minted NFTs are synced.
+        for item in items {
+            let launcher_id = item.launcher_id.expect("missing launcher id");
+            let nft = alice
+                .wallet
+                .db
+                .nft(launcher_id)
+                .await?
+                .expect("missing nft");
+            assert_eq!(nft.info.current_owner, Some(did_id));
+        }
+
+        Ok(())
+    }
+
+    #[test(tokio::test)]
+    async fn test_mint_job_policy_violation() -> anyhow::Result<()> {
+        let mut alice = TestWallet::new(10).await?;
+
+        let (coin_spends, did) = alice.wallet.create_did(0).await?;
+        alice.transact(coin_spends).await?;
+        alice.wait_for_coins().await;
+
+        let did_id = did.info.launcher_id;
+
+        alice
+            .set_policy(
+                Some(SpendingPolicyRow {
+                    max_fee: Some(1),
+                    is_nft_transfer_blocked: false,
+                    is_did_transfer_blocked: false,
+                }),
+                vec![],
+                vec![],
+            )
+            .await?;
+
+        let mut tx = alice.wallet.db.tx().await?;
+        let id = tx
+            .insert_mint_job("Test".to_string(), did_id, 5, MAX_TRANSACTION_COST)
+            .await?;
+        tx.insert_mint_job_item(id, item(0)).await?;
+        tx.commit().await?;
+
+        alice
+            .sender
+            .send(SyncCommand::SetSigningKey(Some(alice.master_sk.clone())))
+            .await?;
+
+        alice
+            .consume_until(|event| matches!(event, SyncEvent::MintJobUpdated { .. }))
+            .await;
+
+        let mint_job = alice
+            .wallet
+            .db
+            .mint_job(id)
+            .await?
+            .expect("missing mint job");
+        assert!(mint_job.is_paused);
+        assert!(mint_job.error.is_some_and(|error| error.contains("policy")));
+        assert_eq!(mint_job.submitted_count, 0);
+
+        Ok(())
+    }
+}
@@ -2,10 +2,13 @@ use std::time::Duration;
 
 use futures_lite::StreamExt;
 use futures_util::stream::FuturesUnordered;
-use sage_assets::{base64_data_uri, fetch_uri};
-use sage_database::{Database, NftMetadataInfo, ResizedImageKind};
+use sage_assets::{
+    base64_data_uri, fetch_uri, thumbnail as make_thumbnail, ThumbnailSource, UriResolver,
+};
+use sage_database::{Database, NftMetadataInfo, ResizedImageKind, ResizedImageSource};
 use tokio::{
     sync::mpsc,
+    task::spawn_blocking,
     time::{sleep, timeout},
 };
 use tracing::{debug, info, warn};
@@ -18,14 +21,21 @@ pub struct NftUriQueue {
     db: Database,
     sync_sender: mpsc::Sender<SyncEvent>,
     network: Network,
+    resolver: UriResolver,
 }
 
 impl NftUriQueue {
-    pub fn new(db: Database, sync_sender: mpsc::Sender<SyncEvent>, network: Network) -> Self {
+    pub fn new(
+        db: Database,
+        sync_sender: mpsc::Sender<SyncEvent>,
+        network: Network,
+        resolver: UriResolver,
+    ) -> Self {
         Self {
             db,
             sync_sender,
             network,
+            resolver,
         }
     }
 
@@ -37,6 +47,13 @@ impl NftUriQueue {
     }
 
     async fn process_batch(&self) -> Result<(), WalletError> {
+        self.download_batch().await?;
+        self.verify_batch().await?;
+        self.resize_batch().await?;
+        Ok(())
+    }
+
+    async fn download_batch(&self) -> Result<(), WalletError> {
         let batch = self.db.candidates_for_download(60 * 60 * 24, 3, 25).await?;
 
         if batch.is_empty() {
@@ -47,11 +64,12 @@ impl NftUriQueue {
 
         let mut futures = FuturesUnordered::new();
         let testnet = self.network.genesis_challenge == TESTNET11_CONSTANTS.genesis_challenge;
+        let resolver = &self.resolver;
         for item in batch {
             futures.push(async move {
                 let result = timeout(
-                    Duration::from_secs(15),
-                    fetch_uri(item.uri.clone(), testnet),
+                    Duration::from_secs(60),
+                    fetch_uri(resolver, item.uri.clone(), Some(item.hash), testnet),
                 )
                 .await;
                 (item, result)
@@ -72,6 +90,14 @@ impl NftUriQueue {
                         );
                     }
 
+                    // Other URIs are tried if the data didn't match, but mismatched data is
+                    // only replaced with data that does.
+                    if !is_hash_match && tx.file_data(item.hash).await?.is_some() {
+                        tx.update_checked_uri(item.hash, item.uri, false).await?;
+                        tx.commit().await?;
+                        continue;
+                    }
+
                     let icon_url = data
                         .thumbnail
                         .as_ref()
@@ -100,40 +126,192 @@ impl NftUriQueue {
                             },
                         )
                         .await?;
+
+                        tx.set_nft_traits(nft.hash, &info.traits).await?;
                     }
 
                     tx.update_file(item.hash, data.blob, data.mime_type, is_hash_match)
                         .await?;
 
                     if let Some(thumbnail) = data.thumbnail {
-                        tx.insert_resized_image(item.hash, ResizedImageKind::Icon, thumbnail.icon)
-                            .await?;
+                        let source = resized_image_source(thumbnail.source);
 
-                        tx.insert_resized_image(
-                            item.hash,
-                            ResizedImageKind::Thumbnail,
-                            thumbnail.thumbnail,
-                        )
-                        .await?;
+                        for (kind, image) in [
+                            (ResizedImageKind::Icon, thumbnail.icon),
+                            (ResizedImageKind::Thumbnail, thumbnail.thumbnail),
+                            (ResizedImageKind::Preview, thumbnail.preview),
+                        ] {
+                            tx.insert_resized_image(item.hash, kind, source, image)
+                                .await?;
+                        }
                     }
 
-                    tx.update_checked_uri(item.hash, item.uri).await?;
+                    tx.update_checked_uri(item.hash, item.uri, is_hash_match)
+                        .await?;
                 }
                 Ok(Err(error)) => {
                     debug!("Error fetching URI {}: {error}", item.uri);
-                    tx.update_failed_uri(item.hash, item.uri).await?;
+                    tx.update_failed_uri(item.hash, item.uri, error.to_string())
+                        .await?;
                 }
                 Err(_error) => {
                     debug!("Timed out fetching URI {}", item.uri);
-                    tx.update_failed_uri(item.hash, item.uri).await?;
+                    tx.update_failed_uri(item.hash, item.uri, "Timed out".to_string())
+                        .await?;
                 }
             }
 
             tx.commit().await?;
         }
 
+        let mut tx = self.db.tx().await?;
+        let eviction = tx.enforce_media_cache_policy().await?;
+        tx.commit().await?;
+
+        if eviction.files > 0 {
+            debug!(
+                "Evicted {} NFT files ({} bytes) from the media cache",
+                eviction.files, eviction.bytes
+            );
+        }
+
         self.sync_sender.send(SyncEvent::NftData).await.ok();
 
         Ok(())
     }
+
+    /// Once a file has been downloaded from one of its URIs, the rest of them are checked to
+    /// make sure they serve the same content, without creating thumbnails again.
+    async fn verify_batch(&self) -> Result<(), WalletError> {
+        let batch = self
+            .db
+            .candidates_for_verification(60 * 60 * 24, 3, 25)
+            .await?;
+
+        if batch.is_empty() {
+            return Ok(());
+        }
+
+        info!("Verifying batch of {} NFT URIs", batch.len());
+
+        let mut futures = FuturesUnordered::new();
+        let resolver = &self.resolver;
+
+        for item in batch {
+            futures.push(async move {
+                let result = timeout(
+                    Duration::from_secs(60),
+                    resolver.fetch(&item.uri, Some(item.hash)),
+                )
+                .await;
+                (item, result)
+            });
+        }
+
+        while let Some((item, result)) = futures.next().await {
+            let mut tx = self.db.tx().await?;
+
+            match result {
+                Ok(Ok(content)) => {
+                    let is_hash_match = content.hash == item.hash;
+
+                    if !is_hash_match {
+                        warn!(
+                            "Hash mismatch for URI {} (expected {} but found {})",
+                            item.uri, item.hash, content.hash
+                        );
+                    }
+
+                    tx.update_checked_uri(item.hash, item.uri, is_hash_match)
+                        .await?;
+                }
+                Ok(Err(error)) => {
+                    debug!("Error verifying URI {}: {error}", item.uri);
+                    tx.update_failed_uri(item.hash, item.uri, error.to_string())
+                        .await?;
+                }
+                Err(_error) => {
+                    debug!("Timed out verifying URI {}", item.uri);
+                    tx.update_failed_uri(item.hash, item.uri, "Timed out".to_string())
+                        .await?;
+                }
+            }
+
+            tx.commit().await?;
+        }
+
+        Ok(())
+    }
+
+    /// Creates resized images for files that are already stored but don't have any yet, such as
+    /// those of types which previously had no thumbnail, without downloading them again.
+    async fn resize_batch(&self) -> Result<(), WalletError> {
+        let batch = self.db.files_needing_resized_images(25).await?;
+
+        if batch.is_empty() {
+            return Ok(());
+        }
+
+        info!(
+            "Creating resized images for batch of {} NFT files",
+            batch.len()
+        );
+
+        for file in batch {
+            let mut tx = self.db.tx().await?;
+
+            if let Some(data) = tx.file_data(file.hash).await? {
+                let mime_type = file.mime_type;
+
+                let thumbnail =
+                    match spawn_blocking(move || make_thumbnail(&data, &mime_type)).await {
+                        Ok(Ok(thumbnail)) => thumbnail,
+                        Ok(Err(error)) => {
+                            debug!("No thumbnail created for {}: {error}", file.hash);
+                            None
+                        }
+                        Err(error) => {
+                            debug!("Failed to create thumbnail for {}: {error}", file.hash);
+                            None
+                        }
+                    };
+
+                if let Some(thumbnail) = thumbnail {
+                    tx.update_nft_data_hash_urls(
+                        file.hash,
+                        base64_data_uri(&thumbnail.icon, "image/png"),
+                    )
+                    .await?;
+
+                    let source = resized_image_source(thumbnail.source);
+
+                    for (kind, image) in [
+                        (ResizedImageKind::Icon, thumbnail.icon),
+                        (ResizedImageKind::Thumbnail, thumbnail.thumbnail),
+                        (ResizedImageKind::Preview, thumbnail.preview),
+                    ] {
+                        tx.insert_resized_image(file.hash, kind, source, image)
+                            .await?;
+                    }
+                }
+            }
+
+            tx.clear_needs_resized_images(file.hash).await?;
+            tx.commit().await?;
+        }
+
+        self.sync_sender.send(SyncEvent::NftData).await.ok();
+
+        Ok(())
+    }
+}
+
+fn resized_image_source(source: ThumbnailSource) -> ResizedImageSource {
+    match source {
+        ThumbnailSource::Image => ResizedImageSource::Image,
+        ThumbnailSource::Vector => ResizedImageSource::Vector,
+        ThumbnailSource::Animation => ResizedImageSource::Animation,
+        ThumbnailSource::CoverArt => ResizedImageSource::CoverArt,
+        ThumbnailSource::Placeholder => ResizedImageSource::Placeholder,
+    }
 }
@@ -0,0 +1,613 @@
+use std::{
+    sync::Arc,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use chia::{
+    bls::SecretKey,
+    protocol::{Bytes32, SpendBundle},
+};
+use chia_wallet_sdk::{
+    driver::{decode_offer, encode_offer, Offer, SpendContext},
+    utils::CoinSelectionError,
+};
+use indexmap::indexmap;
+use sage_database::{Database, OfferGroupOfferRow, OfferGroupRow, OfferRow, OfferStatus};
+use tokio::{sync::mpsc, time::sleep};
+use tracing::{info, warn};
+
+use crate::{
+    sort_offer, OfferExpiry, OfferInput, Offered, Price, Requested, RequestedCat, SyncEvent,
+    Wallet, WalletError,
+};
+
+/// Replaces offers which have been taken from groups with auto repost turned on, by offering the
+/// proceeds back the other way. Offers which were made to sell the group's offered asset are
+/// replaced with offers to buy it back for the spread less, and vice versa. A replacement only
+/// spends the coin which the proceeds were paid to, and is signed subject to the spending policy.
+#[derive(Debug)]
+pub struct OfferGroupQueue {
+    wallet: Arc<Wallet>,
+    master_sk: SecretKey,
+    sync_sender: mpsc::Sender<SyncEvent>,
+}
+
+impl OfferGroupQueue {
+    pub fn new(
+        wallet: Arc<Wallet>,
+        master_sk: SecretKey,
+        sync_sender: mpsc::Sender<SyncEvent>,
+    ) -> Self {
+        Self {
+            wallet,
+            master_sk,
+            sync_sender,
+        }
+    }
+
+    pub async fn start(self, delay: Duration) -> Result<(), WalletError> {
+        loop {
+            self.process_batch().await?;
+            sleep(delay).await;
+        }
+    }
+
+    async fn process_batch(&self) -> Result<(), WalletError> {
+        let rows = self.wallet.db.taken_offer_group_offers().await?;
+
+        for row in rows {
+            let Some(group) = self.wallet.db.offer_group(row.offer_group_id).await? else {
+                continue;
+            };
+
+            // An earlier offer in this batch may have stopped the group.
+            if !group.policy.auto_repost {
+                continue;
+            }
+
+            match self.repost(&group, &row).await {
+                Ok(offer_id) => {
+                    info!(
+                        "Reposted offer {} from group {} as {offer_id}",
+                        row.offer.offer_id, group.id
+                    );
+                }
+                // The proceeds may not have been synced yet, so this is tried again later.
+                Err(
+                    WalletError::InsufficientFunds
+                    | WalletError::CoinSelection(
+                        CoinSelectionError::NoSpendableCoins
+                        | CoinSelectionError::InsufficientBalance(..),
+                    ),
+                ) => continue,
+                Err(error) => {
+                    warn!("Stopped reposting offers in group {}: {error}", group.id);
+
+                    self.wallet
+                        .db
+                        .stop_offer_group(group.id, error.to_string())
+                        .await?;
+                }
+            }
+
+            self.wallet
+                .db
+                .set_offer_group_offer_reposted(row.offer.offer_id)
+                .await?;
+
+            self.sync_sender
+                .send(SyncEvent::OfferGroupUpdated {
+                    offer_group_id: group.id,
+                })
+                .await
+                .ok();
+        }
+
+        Ok(())
+    }
+
+    async fn repost(
+        &self,
+        group: &OfferGroupRow,
+        row: &OfferGroupOfferRow,
+    ) -> Result<Bytes32, WalletError> {
+        let price: Price = row.price.parse()?;
+        let spread: Price = group.policy.price_spread.parse()?;
+        let stop_price = group
+            .policy
+            .stop_price
+            .as_deref()
+            .map(str::parse::<Price>)
+            .transpose()?;
+
+        let base_precision = self.wallet.asset_precision(group.offered_asset_id).await?;
+        let quote_precision = self
+            .wallet
+            .asset_precision(group.requested_asset_id)
+            .await?;
+
+        // The proceeds are whatever the taken offer requested.
+        let proceeds = self
+            .wallet
+            .db
+            .offer_assets(row.offer.offer_id)
+            .await?
+            .into_iter()
+            .find(|asset| asset.is_requested)
+            .map_or(0, |asset| asset.amount);
+
+        let (new_price, requested_amount) = if row.is_reversed {
+            let new_price = price
+                .checked_add(spread)
+                .ok_or_else(|| WalletError::InvalidPrice(row.price.clone()))?;
+
+            let requested_amount =
+                new_price.quote_amount(proceeds, base_precision, quote_precision);
+
+            (new_price, requested_amount)
+        } else {
+            let new_price = price
+                .checked_sub(spread)
+                .filter(|price| !price.is_zero())
+                .ok_or_else(|| WalletError::InvalidPrice(row.price.clone()))?;
+
+            let requested_amount = new_price.base_amount(proceeds, base_precision, quote_precision);
+
+            (new_price, requested_amount)
+        };
+
+        if let Some(stop_price) = stop_price {
+            if new_price < stop_price {
+                return Err(WalletError::StopPriceReached(stop_price.to_string()));
+            }
+        }
+
+        let requested_amount = requested_amount
+            .filter(|&amount| amount > 0)
+            .ok_or_else(|| WalletError::InvalidPrice(new_price.to_string()))?;
+
+        if row.is_reversed {
+            if let Some(max_inventory) = group.policy.max_inventory {
+                let inventory = self.active_inventory(group.id).await? + proceeds;
+
+                if inventory > max_inventory {
+                    return Err(WalletError::MaxInventoryReached(max_inventory));
+                }
+            }
+        }
+
+        // A replacement for a reversed offer sells the offered asset again.
+        let (offered_asset_id, requested_asset_id) = if row.is_reversed {
+            (group.offered_asset_id, group.requested_asset_id)
+        } else {
+            (group.requested_asset_id, group.offered_asset_id)
+        };
+
+        let offer = GroupOffer {
+            offered_asset_id,
+            offered_amount: proceeds,
+            requested_asset_id,
+            requested_amount,
+            price: new_price,
+            is_reversed: !row.is_reversed,
+        };
+
+        let input = self.proceeds_input(row, offered_asset_id, proceeds).await?;
+
+        let spend_bundle = self
+            .wallet
+            .make_offer_with_inputs(
+                offer.offered(),
+                offer.requested(&self.wallet).await?,
+                OfferExpiry::default(),
+                vec![input],
+            )
+            .await?;
+
+        let spend_bundle = self
+            .wallet
+            .sign_transaction(
+                spend_bundle,
+                &self.wallet.agg_sig_constants,
+                self.master_sk.clone(),
+                false,
+            )
+            .await?;
+
+        insert_group_offer(&self.wallet.db, group.id, spend_bundle, offer).await
+    }
+
+    /// The coin which the taken offer paid its proceeds to. Replacements only ever spend this
+    /// coin, so that reposting can't lock up the rest of the wallet's coins.
+    async fn proceeds_input(
+        &self,
+        row: &OfferGroupOfferRow,
+        asset_id: Option<Bytes32>,
+        amount: u64,
+    ) -> Result<OfferInput, WalletError> {
+        let spend_bundle = decode_offer(&row.offer.encoded_offer)?;
+        let mut ctx = SpendContext::new();
+        let offer = Offer::from_spend_bundle(&mut ctx, &spend_bundle)?;
+
+        let notarized_payments = match asset_id {
+            Some(asset_id) => offer.requested_payments().cats.get(&asset_id),
+            None => Some(&offer.requested_payments().xch),
+        };
+
+        let p2_puzzle_hashes: Vec<Bytes32> = notarized_payments
+            .into_iter()
+            .flatten()
+            .flat_map(|notarized_payment| &notarized_payment.payments)
+            .filter(|payment| payment.amount == amount)
+            .map(|payment| payment.puzzle_hash)
+            .collect();
+
+        let input = match asset_id {
+            Some(asset_id) => self
+                .wallet
+                .db
+                .selectable_cat_coins(asset_id)
+                .await?
+                .into_iter()
+                .find(|cat| {
+                    cat.coin.amount == amount && p2_puzzle_hashes.contains(&cat.info.p2_puzzle_hash)
+                })
+                .map(OfferInput::Cat),
+            None => self
+                .wallet
+                .db
+                .selectable_xch_coins()
+                .await?
+                .into_iter()
+                .find(|coin| coin.amount == amount && p2_puzzle_hashes.contains(&coin.puzzle_hash))
+                .map(OfferInput::Xch),
+        };
+
+        // The proceeds may not have been synced yet, in which case this is tried again later.
+        input.ok_or(WalletError::InsufficientFunds)
+    }
+
+    /// The amount of the group's offered asset which its active offers are selling.
+    async fn active_inventory(&self, offer_group_id: u32) -> Result<u64, WalletError> {
+        let mut inventory = 0;
+
+        for row in self.wallet.db.offer_group_offers(offer_group_id).await? {
+            if row.is_reversed || row.offer.status != OfferStatus::Active {
+                continue;
+            }
+
+            inventory += self
+                .wallet
+                .db
+                .offer_assets(row.offer.offer_id)
+                .await?
+                .into_iter()
+                .filter(|asset| !asset.is_requested)
+                .map(|asset| asset.amount)
+                .sum::<u64>();
+        }
+
+        Ok(inventory)
+    }
+}
+
+/// An offer of one fungible asset for another, which is part of an offer group.
+#[derive(Debug, Clone, Copy)]
+struct GroupOffer {
+    offered_asset_id: Option<Bytes32>,
+    offered_amount: u64,
+    requested_asset_id: Option<Bytes32>,
+    requested_amount: u64,
+    price: Price,
+    is_reversed: bool,
+}
+
+impl GroupOffer {
+    fn offered(&self) -> Offered {
+        match self.offered_asset_id {
+            Some(asset_id) => Offered {
+                cats: indexmap! { asset_id => self.offered_amount },
+                ..Default::default()
+            },
+            None => Offered {
+                xch: self.offered_amount,
+                ..Default::default()
+            },
+        }
+    }
+
+    async fn requested(&self, wallet: &Wallet) -> Result<Requested, WalletError> {
+        Ok(match self.requested_asset_id {
+            Some(asset_id) => Requested {
+                cats: indexmap! {
+                    asset_id => RequestedCat {
+                        amount: self.requested_amount,
+                        hidden_puzzle_hash: wallet.fetch_offer_cat_hidden_puzzle_hash(asset_id).await?,
+                    },
+                },
+                ..Default::default()
+            },
+            None => Requested {
+                xch: self.requested_amount,
+                ..Default::default()
+            },
+        })
+    }
+}
+
+/// Imports a signed offer into the group. The assets are already known, since the group was
+/// made from offers for the same pair.
+async fn insert_group_offer(
+    db: &Database,
+    offer_group_id: u32,
+    spend_bundle: SpendBundle,
+    group_offer: GroupOffer,
+) -> Result<Bytes32, WalletError> {
+    let encoded_offer = encode_offer(&spend_bundle)?;
+    let spend_bundle = sort_offer(spend_bundle);
+    let offer_id = spend_bundle.name();
+
+    let mut ctx = SpendContext::new();
+    let offer = Offer::from_spend_bundle(&mut ctx, &spend_bundle)?;
+
+    let mut tx = db.tx().await?;
+
+    tx.insert_offer(OfferRow {
+        offer_id,
+        encoded_offer,
+        expiration_height: None,
+        expiration_timestamp: None,
+        fee: 0,
+        status: OfferStatus::Active,
+        inserted_timestamp: SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs(),
+        invalid_reason: None,
+        auto_recreate: false,
+        recreated_offer_id: None,
+        recreate_error: None,
+    })
+    .await?;
+
+    for coin_spend in offer.cancellable_coin_spends()? {
+        tx.insert_offered_coin(offer_id, coin_spend.coin.coin_id())
+            .await?;
+    }
+
+    tx.insert_offer_asset(
+        offer_id,
+        group_offer.offered_asset_id.unwrap_or_default(),
+        group_offer.offered_amount,
+        0,
+        false,
+    )
+    .await?;
+
+    tx.insert_offer_asset(
+        offer_id,
+        group_offer.requested_asset_id.unwrap_or_default(),
+        group_offer.requested_amount,
+        0,
+        true,
+    )
+    .await?;
+
+    tx.insert_offer_group_offer(
+        offer_group_id,
+        offer_id,
+        group_offer.price.to_string(),
+        group_offer.is_reversed,
+    )
+    .await?;
+
+    tx.commit().await?;
+
+    Ok(offer_id)
+}
+
+#[cfg(test)]
+mod tests {
+    use indexmap::indexmap;
+    use sage_database::{Asset, AssetKind, OfferGroupPolicy, OfferStatus, SpendingLimitRow};
+    use test_log::test;
+
+    use crate::{Offered, Requested, RequestedCat, SyncCommand, SyncEvent, TestWallet};
+
+    use super::*;
+
+    /// Sells 100 mojos for 10 CAT mojos, which is a price of 100 million CATs per XCH, and waits
+    /// for the offer to be taken and handled by the queue. The CAT limit is a per transaction
+    /// spending limit which applies to the replacement.
+    async fn take_group_offer(
+        policy: OfferGroupPolicy,
+        cat_limit: Option<u64>,
+    ) -> anyhow::Result<(TestWallet, u32)> {
+        let mut alice = TestWallet::new(1000).await?;
+        let mut bob = alice.next(1000).await?;
+
+        let (coin_spends, asset_id) = bob.wallet.issue_cat(1000, 0, None).await?;
+        bob.transact(coin_spends).await?;
+        bob.wait_for_coins().await;
+
+        if let Some(limit) = cat_limit {
+            alice
+                .set_policy(
+                    None,
+                    vec![SpendingLimitRow {
+                        asset_hash: asset_id,
+                        max_per_transaction: Some(limit),
+                        max_per_day: None,
+                    }],
+                    vec![],
+                )
+                .await?;
+        }
+
+        let group_offer = GroupOffer {
+            offered_asset_id: None,
+            offered_amount: 100,
+            requested_asset_id: Some(asset_id),
+            requested_amount: 10,
+            price: "100000000".parse()?,
+            is_reversed: false,
+        };
+
+        let offer = alice
+            .wallet
+            .make_offer(
+                Offered {
+                    xch: 100,
+                    ..Default::default()
+                },
+                Requested {
+                    cats: indexmap! { asset_id => RequestedCat { amount: 10, hidden_puzzle_hash: None } },
+                    ..Default::default()
+                },
+                OfferExpiry::default(),
+            )
+            .await?;
+        let offer = alice
+            .wallet
+            .sign_transaction(offer, &alice.agg_sig, alice.master_sk.clone(), true)
+            .await?;
+
+        // Normally the CAT is cached when the offers in a group are first imported.
+        let mut tx = alice.wallet.db.tx().await?;
+        tx.insert_asset(Asset {
+            hash: asset_id,
+            name: None,
+            ticker: None,
+            precision: 3,
+            icon_url: None,
+            description: None,
+            is_sensitive_content: false,
+            is_visible: true,
+            hidden_puzzle_hash: None,
+            kind: AssetKind::Token,
+        })
+        .await?;
+        let group_id = tx.insert_offer_group(None, Some(asset_id)).await?;
+        tx.set_offer_group_policy(group_id, policy).await?;
+        tx.commit().await?;
+
+        insert_group_offer(&alice.wallet.db, group_id, offer.clone(), group_offer).await?;
+
+        let offer = bob.wallet.take_offer(offer, 0).await?;
+        let spend_bundle = bob
+            .wallet
+            .sign_transaction(offer, &bob.agg_sig, bob.master_sk.clone(), true)
+            .await?;
+        bob.push_bundle(spend_bundle).await?;
+
+        alice
+            .sender
+            .send(SyncCommand::SetSigningKey(Some(alice.master_sk.clone())))
+            .await?;
+
+        alice
+            .consume_until(|event| matches!(event, SyncEvent::OfferGroupUpdated { .. }))
+            .await;
+
+        Ok((alice, group_id))
+    }
+
+    #[test(tokio::test)]
+    async fn test_offer_group_repost() -> anyhow::Result<()> {
+        let (alice, group_id) = take_group_offer(
+            OfferGroupPolicy {
+                auto_repost: true,
+                price_spread: "10000000".to_string(),
+                stop_price: None,
+                max_inventory: None,
+            },
+            None,
+        )
+        .await?;
+
+        let offers = alice.wallet.db.offer_group_offers(group_id).await?;
+        assert_eq!(offers.len(), 2);
+        assert_eq!(offers[0].offer.status, OfferStatus::Completed);
+
+        // The proceeds are offered to buy back the XCH for 10% less.
+        let replacement = &offers[1];
+        assert!(replacement.is_reversed);
+        assert_eq!(replacement.price, "90000000");
+        assert_eq!(replacement.offer.status, OfferStatus::Active);
+
+        let assets = alice
+            .wallet
+            .db
+            .offer_assets(replacement.offer.offer_id)
+            .await?;
+        let offered = assets.iter().find(|asset| !asset.is_requested);
+        let requested = assets.iter().find(|asset| asset.is_requested);
+        assert_eq!(offered.map(|asset| asset.amount), Some(10));
+        assert_eq!(requested.map(|asset| asset.amount), Some(111));
+
+        // Only the coin which the proceeds were paid to is spent.
+        let spend_bundle = decode_offer(&replacement.offer.encoded_offer)?;
+        let inputs: Vec<_> = spend_bundle
+            .coin_spends
+            .iter()
+            .filter(|coin_spend| coin_spend.coin.parent_coin_info != Bytes32::default())
+            .collect();
+        assert_eq!(inputs.len(), 1);
+        assert_eq!(inputs[0].coin.amount, 10);
+
+        Ok(())
+    }
+
+    #[test(tokio::test)]
+    async fn test_offer_group_stop_price() -> anyhow::Result<()> {
+        let (alice, group_id) = take_group_offer(
+            OfferGroupPolicy {
+                auto_repost: true,
+                price_spread: "10000000".to_string(),
+                stop_price: Some("95000000".to_string()),
+                max_inventory: None,
+            },
+            None,
+        )
+        .await?;
+
+        assert_eq!(alice.wallet.db.offer_group_offers(group_id).await?.len(), 1);
+
+        let group = alice
+            .wallet
+            .db
+            .offer_group(group_id)
+            .await?
+            .expect("missing offer group");
+        assert!(!group.policy.auto_repost);
+        assert!(group.stop_reason.is_some());
+
+        Ok(())
+    }
+
+    #[test(tokio::test)]
+    async fn test_offer_group_policy_violation() -> anyhow::Result<()> {
+        let (alice, group_id) = take_group_offer(
+            OfferGroupPolicy {
+                auto_repost: true,
+                price_spread: "10000000".to_string(),
+                stop_price: None,
+                max_inventory: None,
+            },
+            Some(5),
+        )
+        .await?;
+
+        assert_eq!(alice.wallet.db.offer_group_offers(group_id).await?.len(), 1);
+
+        let group = alice
+            .wallet
+            .db
+            .offer_group(group_id)
+            .await?
+            .expect("missing offer group");
+        assert!(!group.policy.auto_repost);
+        assert!(group
+            .stop_reason
+            .is_some_and(|reason| reason.contains("policy")));
+
+        Ok(())
+    }
+}
@@ -7,7 +7,7 @@ use std::{
 use chia::protocol::Bytes32;
 use chia_wallet_sdk::driver::{decode_offer, Offer};
 use clvmr::Allocator;
-use sage_database::{Database, OfferStatus};
+use sage_database::{AssetKind, Database, OfferStatus};
 use tokio::{
     sync::{mpsc, Mutex},
     time::sleep,
@@ -16,6 +16,9 @@ use tracing::warn;
 
 use crate::{PeerState, SyncEvent, WalletError};
 
+/// How long an unfilled taker trade is kept without its transaction being pending.
+const STALE_TAKER_TRADE_SECONDS: u64 = 24 * 60 * 60;
+
 #[derive(Debug)]
 pub struct OfferQueue {
     db: Database,
@@ -52,13 +55,22 @@ impl OfferQueue {
         }
 
         let offers = self.db.offers(Some(OfferStatus::Active)).await?;
+        let unfilled_trades = self.db.unfilled_trades().await?;
 
-        if offers.is_empty() {
+        if offers.is_empty() && unfilled_trades.is_empty() {
             return Ok(());
         }
 
         let mut settlement_coin_ids = HashMap::new();
         let mut input_coin_ids = HashMap::new();
+        let mut trade_coin_ids = HashMap::new();
+
+        for trade in unfilled_trades {
+            trade_coin_ids
+                .entry(trade.settlement_coin_id)
+                .or_insert(HashSet::new())
+                .insert(trade.id);
+        }
 
         for row in &offers {
             let mut allocator = Allocator::new();
@@ -91,6 +103,7 @@ impl OfferQueue {
                     .keys()
                     .copied()
                     .chain(input_coin_ids.keys().copied())
+                    .chain(trade_coin_ids.keys().copied())
                     .collect(),
                 self.genesis_challenge,
             )
@@ -109,15 +122,34 @@ impl OfferQueue {
         };
 
         let mut new_offer_statuses = HashMap::new();
+        let mut maker_fills = HashMap::new();
+        let mut trade_fills = HashMap::new();
 
         for coin_state in coin_states {
             if let Some(offer_ids) = settlement_coin_ids.get(&coin_state.coin.coin_id()) {
                 for &offer_id in offer_ids {
                     new_offer_statuses.insert(offer_id, OfferStatus::Completed);
+                    maker_fills.insert(
+                        offer_id,
+                        (coin_state.coin.coin_id(), coin_state.created_height),
+                    );
                 }
             }
 
-            if coin_state.spent_height.is_none() {
+            if let (Some(trade_ids), Some(height)) = (
+                trade_coin_ids.get(&coin_state.coin.coin_id()),
+                coin_state.created_height,
+            ) {
+                for &trade_id in trade_ids {
+                    trade_fills.insert(trade_id, height);
+                }
+            }
+
+            // The spend is synced into the wallet first, so that an offer it conflicts with is
+            // marked as invalid rather than cancelled.
+            if coin_state.spent_height.is_none()
+                || !self.db.is_coin_spent(coin_state.coin.coin_id()).await?
+            {
                 continue;
             }
 
@@ -145,9 +177,34 @@ impl OfferQueue {
             }
         }
 
+        for (trade_id, height) in trade_fills {
+            self.db.fill_trade(trade_id, height).await?;
+        }
+
+        // Taker trades are recorded when the offer is taken, even if the transaction is never
+        // submitted, so they're kept for a while in case it is and removed afterwards.
+        self.db
+            .delete_stale_taker_trades(timestamp.saturating_sub(STALE_TAKER_TRADE_SECONDS))
+            .await?;
+
         for (offer_id, status) in new_offer_statuses {
+            // The offer may have been marked as invalid since it was fetched.
+            if self
+                .db
+                .offer(offer_id)
+                .await?
+                .is_none_or(|row| row.status != OfferStatus::Active)
+            {
+                continue;
+            }
+
             self.db.update_offer_status(offer_id, status).await?;
 
+            if let Some(&(settlement_coin_id, height)) = maker_fills.get(&offer_id) {
+                self.insert_maker_trade(offer_id, settlement_coin_id, height)
+                    .await?;
+            }
+
             self.sync_sender
                 .send(SyncEvent::OfferUpdated { offer_id, status })
                 .await
@@ -156,4 +213,173 @@ impl OfferQueue {
 
         Ok(())
     }
+
+    /// Records the trade for an offer made by the wallet, which has been taken.
+    async fn insert_maker_trade(
+        &self,
+        offer_id: Bytes32,
+        settlement_coin_id: Bytes32,
+        fill_height: Option<u32>,
+    ) -> Result<(), WalletError> {
+        let Some(offer) = self.db.offer(offer_id).await? else {
+            return Ok(());
+        };
+
+        let assets = self.db.offer_assets(offer_id).await?;
+
+        let mut tx = self.db.tx().await?;
+
+        let Some(trade_id) = tx
+            .insert_trade(offer_id, true, settlement_coin_id, None, offer.fee)
+            .await?
+        else {
+            return Ok(());
+        };
+
+        for asset in assets {
+            // NFTs and options are stored with their royalty basis points rather than an amount
+            // of royalties paid, and are requested without an amount
+            let (amount, royalty) =
+                if matches!(asset.asset.kind, AssetKind::Nft | AssetKind::Option) {
+                    (1, 0)
+                } else {
+                    (asset.amount, asset.royalty)
+                };
+
+            tx.insert_trade_asset(
+                trade_id,
+                asset.asset.hash,
+                asset.is_requested,
+                amount,
+                royalty,
+            )
+            .await?;
+        }
+
+        if let Some(height) = fill_height {
+            tx.fill_trade(trade_id, height).await?;
+        }
+
+        tx.commit().await?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chia_wallet_sdk::driver::encode_offer;
+    use indexmap::indexmap;
+    use sage_database::{Asset, OfferRow};
+    use test_log::test;
+
+    use crate::{
+        sort_offer, OfferExpiry, Offered, Requested, RequestedCat, SyncCommand, TestWallet,
+    };
+
+    use super::*;
+
+    #[test(tokio::test)]
+    async fn test_maker_trade() -> anyhow::Result<()> {
+        let mut alice = TestWallet::new(1000).await?;
+        let mut bob = alice.next(1000).await?;
+
+        let (coin_spends, asset_id) = bob.wallet.issue_cat(1000, 0, None).await?;
+        bob.transact(coin_spends).await?;
+        bob.wait_for_coins().await;
+
+        let offer = alice
+            .wallet
+            .make_offer(
+                Offered {
+                    xch: 100,
+                    ..Default::default()
+                },
+                Requested {
+                    cats: indexmap! { asset_id => RequestedCat { amount: 10, hidden_puzzle_hash: None } },
+                    ..Default::default()
+                },
+                OfferExpiry::default(),
+            )
+            .await?;
+        let offer = alice
+            .wallet
+            .sign_transaction(offer, &alice.agg_sig, alice.master_sk.clone(), true)
+            .await?;
+
+        let offer_id = sort_offer(offer.clone()).name();
+
+        let mut tx = alice.wallet.db.tx().await?;
+        tx.insert_asset(Asset {
+            hash: asset_id,
+            name: None,
+            ticker: None,
+            precision: 3,
+            icon_url: None,
+            description: None,
+            is_sensitive_content: false,
+            is_visible: true,
+            hidden_puzzle_hash: None,
+            kind: AssetKind::Token,
+        })
+        .await?;
+        tx.insert_offer(OfferRow {
+            offer_id,
+            encoded_offer: encode_offer(&offer)?,
+            expiration_height: None,
+            expiration_timestamp: None,
+            fee: 0,
+            status: OfferStatus::Active,
+            inserted_timestamp: SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs(),
+            invalid_reason: None,
+            auto_recreate: false,
+            recreated_offer_id: None,
+            recreate_error: None,
+        })
+        .await?;
+        tx.insert_offer_asset(offer_id, Bytes32::default(), 100, 0, false)
+            .await?;
+        tx.insert_offer_asset(offer_id, asset_id, 10, 0, true)
+            .await?;
+        tx.commit().await?;
+
+        let offer = bob.wallet.take_offer(offer, 0).await?;
+        let spend_bundle = bob
+            .wallet
+            .sign_transaction(offer, &bob.agg_sig, bob.master_sk.clone(), true)
+            .await?;
+        bob.push_bundle(spend_bundle).await?;
+
+        alice
+            .sender
+            .send(SyncCommand::SetSigningKey(Some(alice.master_sk.clone())))
+            .await?;
+
+        alice
+            .consume_until(|event| {
+                matches!(
+                    event,
+                    SyncEvent::OfferUpdated {
+                        status: OfferStatus::Completed,
+                        ..
+                    }
+                )
+            })
+            .await;
+
+        let trades = alice.wallet.db.trades().await?;
+        assert_eq!(trades.len(), 1);
+        assert_eq!(trades[0].offer_id, offer_id);
+        assert!(trades[0].is_maker);
+        assert!(trades[0].fill_height.is_some());
+
+        let assets = alice.wallet.db.trade_assets().await?;
+        let given = assets.iter().find(|asset| !asset.is_received);
+        let received = assets.iter().find(|asset| asset.is_received);
+        assert_eq!(given.map(|asset| asset.amount), Some(100));
+        assert_eq!(received.map(|asset| asset.asset.hash), Some(asset_id));
+        assert_eq!(received.map(|asset| asset.amount), Some(10));
+
+        Ok(())
+    }
 }
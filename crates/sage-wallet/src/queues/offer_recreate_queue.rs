@@ -0,0 +1,469 @@
+use std::{
+    sync::Arc,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use chia::{
+    bls::SecretKey,
+    protocol::{Bytes32, SpendBundle},
+};
+use chia_wallet_sdk::{
+    driver::{encode_offer, Offer, SpendContext},
+    utils::CoinSelectionError,
+};
+use sage_database::{AssetKind, Database, OfferRow, OfferStatus, OfferedAsset};
+use tokio::{sync::mpsc, time::sleep};
+use tracing::{info, warn};
+
+use crate::{
+    sort_offer, OfferExpiry, Offered, Requested, RequestedCat, SyncEvent, Wallet, WalletError,
+};
+
+/// Makes offers which became invalid again from other coins, if they have auto recreate turned
+/// on. The replacement trades the same assets with the same expiration, and has auto recreate
+/// turned on as well.
+#[derive(Debug)]
+pub struct OfferRecreateQueue {
+    wallet: Arc<Wallet>,
+    master_sk: SecretKey,
+    sync_sender: mpsc::Sender<SyncEvent>,
+}
+
+impl OfferRecreateQueue {
+    pub fn new(
+        wallet: Arc<Wallet>,
+        master_sk: SecretKey,
+        sync_sender: mpsc::Sender<SyncEvent>,
+    ) -> Self {
+        Self {
+            wallet,
+            master_sk,
+            sync_sender,
+        }
+    }
+
+    pub async fn start(self, delay: Duration) -> Result<(), WalletError> {
+        loop {
+            self.process_batch().await?;
+            sleep(delay).await;
+        }
+    }
+
+    async fn process_batch(&self) -> Result<(), WalletError> {
+        let rows = self.wallet.db.offers_to_recreate().await?;
+
+        if rows.is_empty() {
+            return Ok(());
+        }
+
+        let peak_height = self
+            .wallet
+            .db
+            .latest_peak()
+            .await?
+            .map(|(height, _)| height);
+        let timestamp = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+
+        for row in rows {
+            if row
+                .expiration_height
+                .is_some_and(|height| peak_height.is_some_and(|peak| height <= peak))
+                || row.expiration_timestamp.is_some_and(|ts| ts <= timestamp)
+            {
+                self.wallet
+                    .db
+                    .set_offer_recreate_error(row.offer_id, "The offer has expired".to_string())
+                    .await?;
+
+                continue;
+            }
+
+            match self.recreate(&row).await {
+                Ok(offer_id) => {
+                    info!("Recreated invalid offer {} as {offer_id}", row.offer_id);
+
+                    self.sync_sender
+                        .send(SyncEvent::OfferUpdated {
+                            offer_id,
+                            status: OfferStatus::Active,
+                        })
+                        .await
+                        .ok();
+                }
+                // The coins which replace the spent ones may not have been synced yet, so this is
+                // tried again later.
+                Err(
+                    WalletError::InsufficientFunds
+                    | WalletError::CoinSelection(
+                        CoinSelectionError::NoSpendableCoins
+                        | CoinSelectionError::InsufficientBalance(..),
+                    ),
+                ) => {}
+                // This includes replacements which would break the spending policy.
+                Err(error) => {
+                    warn!("Could not recreate invalid offer {}: {error}", row.offer_id);
+
+                    self.wallet
+                        .db
+                        .set_offer_recreate_error(row.offer_id, error.to_string())
+                        .await?;
+
+                    self.sync_sender
+                        .send(SyncEvent::OfferUpdated {
+                            offer_id: row.offer_id,
+                            status: OfferStatus::Invalid,
+                        })
+                        .await
+                        .ok();
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn recreate(&self, row: &OfferRow) -> Result<Bytes32, WalletError> {
+        let assets = self.wallet.db.offer_assets(row.offer_id).await?;
+
+        let mut offered = Offered {
+            fee: row.fee,
+            ..Default::default()
+        };
+        let mut requested = Requested::default();
+
+        for asset in &assets {
+            let asset_id = asset.asset.hash;
+
+            match (asset.asset.kind, asset.is_requested) {
+                (AssetKind::Token, false) if asset_id == Bytes32::default() => {
+                    offered.xch = asset.amount;
+                }
+                (AssetKind::Token, true) if asset_id == Bytes32::default() => {
+                    requested.xch = asset.amount;
+                }
+                (AssetKind::Token, false) => {
+                    offered.cats.insert(asset_id, asset.amount);
+                }
+                (AssetKind::Token, true) => {
+                    requested.cats.insert(
+                        asset_id,
+                        RequestedCat {
+                            amount: asset.amount,
+                            hidden_puzzle_hash: asset.asset.hidden_puzzle_hash,
+                        },
+                    );
+                }
+                (AssetKind::Nft, false) => {
+                    offered.nfts.push(asset_id);
+                }
+                (AssetKind::Nft, true) => {
+                    let info = self
+                        .wallet
+                        .fetch_offer_nft_info(None, asset_id)
+                        .await?
+                        .ok_or(WalletError::MissingNft(asset_id))?;
+
+                    requested.nfts.insert(asset_id, info);
+                }
+                (AssetKind::Option, false) => {
+                    offered.options.push(asset_id);
+                }
+                (AssetKind::Option, true) => {
+                    let info = self
+                        .wallet
+                        .fetch_offer_option_info(None, asset_id)
+                        .await?
+                        .ok_or(WalletError::MissingOption(asset_id))?;
+
+                    requested.options.insert(asset_id, info);
+                }
+                (AssetKind::Did, _) => return Err(WalletError::MissingAsset(asset_id)),
+            }
+        }
+
+        let spend_bundle = self
+            .wallet
+            .make_offer(
+                offered,
+                requested,
+                OfferExpiry {
+                    height: row.expiration_height,
+                    seconds: row.expiration_timestamp,
+                },
+            )
+            .await?;
+
+        let spend_bundle = self
+            .wallet
+            .sign_transaction(
+                spend_bundle,
+                &self.wallet.agg_sig_constants,
+                self.master_sk.clone(),
+                false,
+            )
+            .await?;
+
+        insert_recreated_offer(&self.wallet.db, row, spend_bundle, assets).await
+    }
+}
+
+/// Imports the replacement for an invalid offer, which trades the same assets.
+async fn insert_recreated_offer(
+    db: &Database,
+    row: &OfferRow,
+    spend_bundle: SpendBundle,
+    assets: Vec<OfferedAsset>,
+) -> Result<Bytes32, WalletError> {
+    let encoded_offer = encode_offer(&spend_bundle)?;
+    let spend_bundle = sort_offer(spend_bundle);
+    let offer_id = spend_bundle.name();
+
+    let mut ctx = SpendContext::new();
+    let offer = Offer::from_spend_bundle(&mut ctx, &spend_bundle)?;
+
+    let mut tx = db.tx().await?;
+
+    tx.insert_offer(OfferRow {
+        offer_id,
+        encoded_offer,
+        expiration_height: row.expiration_height,
+        expiration_timestamp: row.expiration_timestamp,
+        fee: row.fee,
+        status: OfferStatus::Active,
+        inserted_timestamp: SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs(),
+        invalid_reason: None,
+        auto_recreate: true,
+        recreated_offer_id: None,
+        recreate_error: None,
+    })
+    .await?;
+
+    for coin_spend in offer.cancellable_coin_spends()? {
+        tx.insert_offered_coin(offer_id, coin_spend.coin.coin_id())
+            .await?;
+    }
+
+    for asset in assets {
+        tx.insert_offer_asset(
+            offer_id,
+            asset.asset.hash,
+            asset.amount,
+            asset.royalty,
+            asset.is_requested,
+        )
+        .await?;
+    }
+
+    tx.set_offer_recreated(row.offer_id, offer_id).await?;
+
+    tx.commit().await?;
+
+    Ok(offer_id)
+}
+
+#[cfg(test)]
+mod tests {
+    use chia::{bls::Signature, protocol::SpendBundle};
+    use sage_database::SpendingLimitRow;
+    use test_log::test;
+
+    use crate::{SyncCommand, TestWallet};
+
+    use super::*;
+
+    /// Makes an offer with auto recreate turned on and spends its coin from outside of the
+    /// wallet, then waits for it to be marked as invalid.
+    async fn invalidate_offer(alice: &mut TestWallet) -> anyhow::Result<Bytes32> {
+        let offer = alice
+            .wallet
+            .make_offer(
+                Offered {
+                    xch: 100,
+                    ..Default::default()
+                },
+                Requested {
+                    xch: 200,
+                    ..Default::default()
+                },
+                OfferExpiry::default(),
+            )
+            .await?;
+        let offer = alice
+            .wallet
+            .sign_transaction(offer, &alice.agg_sig, alice.master_sk.clone(), true)
+            .await?;
+
+        // Spending the offered coin outside of the wallet is the same as doing so from another
+        // device, since there's no pending transaction for it.
+        let coin_spends = alice.wallet.cancel_offer(offer.clone(), 0).await?;
+        let spend_bundle = alice
+            .wallet
+            .sign_transaction(
+                SpendBundle::new(coin_spends, Signature::default()),
+                &alice.agg_sig,
+                alice.master_sk.clone(),
+                false,
+            )
+            .await?;
+
+        // The offer is imported the same way as a replacement would be, with auto recreate on.
+        let row = OfferRow {
+            offer_id: Bytes32::default(),
+            encoded_offer: String::new(),
+            expiration_height: None,
+            expiration_timestamp: None,
+            fee: 0,
+            status: OfferStatus::Invalid,
+            inserted_timestamp: 0,
+            invalid_reason: None,
+            auto_recreate: true,
+            recreated_offer_id: None,
+            recreate_error: None,
+        };
+
+        let assets = [
+            (Bytes32::default(), 100, false),
+            (Bytes32::default(), 200, true),
+        ]
+        .into_iter()
+        .map(|(hash, amount, is_requested)| OfferedAsset {
+            offer_id: Bytes32::default(),
+            asset: sage_database::Asset {
+                hash,
+                name: None,
+                ticker: None,
+                precision: 12,
+                icon_url: None,
+                description: None,
+                is_sensitive_content: false,
+                is_visible: true,
+                hidden_puzzle_hash: None,
+                kind: AssetKind::Token,
+            },
+            is_requested,
+            amount,
+            royalty: 0,
+        })
+        .collect();
+
+        let offer_id = insert_recreated_offer(&alice.wallet.db, &row, offer, assets).await?;
+
+        alice.peer.send_transaction(spend_bundle).await?;
+
+        alice
+            .consume_until(|event| {
+                matches!(
+                    event,
+                    SyncEvent::OfferUpdated {
+                        status: OfferStatus::Invalid,
+                        ..
+                    }
+                )
+            })
+            .await;
+
+        let invalid = alice
+            .wallet
+            .db
+            .offer(offer_id)
+            .await?
+            .expect("missing offer");
+        assert_eq!(invalid.status, OfferStatus::Invalid);
+        assert!(invalid.invalid_reason.is_some());
+
+        Ok(offer_id)
+    }
+
+    #[test(tokio::test)]
+    async fn test_recreate_invalid_offer() -> anyhow::Result<()> {
+        let mut alice = TestWallet::new(1000).await?;
+        let offer_id = invalidate_offer(&mut alice).await?;
+
+        alice
+            .sender
+            .send(SyncCommand::SetSigningKey(Some(alice.master_sk.clone())))
+            .await?;
+
+        alice
+            .consume_until(|event| {
+                matches!(
+                    event,
+                    SyncEvent::OfferUpdated {
+                        status: OfferStatus::Active,
+                        ..
+                    }
+                )
+            })
+            .await;
+
+        let invalid = alice
+            .wallet
+            .db
+            .offer(offer_id)
+            .await?
+            .expect("missing offer");
+        let recreated_offer_id = invalid.recreated_offer_id.expect("offer wasn't recreated");
+
+        let recreated = alice
+            .wallet
+            .db
+            .offer(recreated_offer_id)
+            .await?
+            .expect("missing offer");
+        assert_eq!(recreated.status, OfferStatus::Active);
+        assert!(recreated.auto_recreate);
+
+        let assets = alice.wallet.db.offer_assets(recreated_offer_id).await?;
+        assert_eq!(assets.len(), 2);
+
+        Ok(())
+    }
+
+    #[test(tokio::test)]
+    async fn test_recreate_policy_violation() -> anyhow::Result<()> {
+        let mut alice = TestWallet::new(1000).await?;
+        let offer_id = invalidate_offer(&mut alice).await?;
+
+        alice
+            .set_policy(
+                None,
+                vec![SpendingLimitRow {
+                    asset_hash: Bytes32::default(),
+                    max_per_transaction: Some(50),
+                    max_per_day: None,
+                }],
+                vec![],
+            )
+            .await?;
+
+        alice
+            .sender
+            .send(SyncCommand::SetSigningKey(Some(alice.master_sk.clone())))
+            .await?;
+
+        alice
+            .consume_until(|event| {
+                matches!(
+                    event,
+                    SyncEvent::OfferUpdated {
+                        status: OfferStatus::Invalid,
+                        ..
+                    }
+                )
+            })
+            .await;
+
+        let invalid = alice
+            .wallet
+            .db
+            .offer(offer_id)
+            .await?
+            .expect("missing offer");
+        assert_eq!(invalid.recreated_offer_id, None);
+        assert!(invalid
+            .recreate_error
+            .is_some_and(|error| error.contains("policy")));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,521 @@
+use std::{sync::Arc, time::Duration};
+
+use chia::{
+    bls::{SecretKey, Signature},
+    protocol::{Bytes32, SpendBundle},
+};
+use sage_database::{PayoutBatchRow, PayoutRow};
+use tokio::{
+    sync::{mpsc, Mutex},
+    time::sleep,
+};
+use tracing::{info, warn};
+
+use crate::{
+    insert_transaction, MultiSendPayment, PeerState, SyncCommand, SyncEvent, Transaction, Wallet,
+    WalletError, WalletPeer,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PayoutBatchStatus {
+    Pending,
+    Confirmed,
+    Failed,
+}
+
+#[derive(Debug)]
+pub struct PayoutQueue {
+    wallet: Arc<Wallet>,
+    master_sk: SecretKey,
+    state: Arc<Mutex<PeerState>>,
+    sync_sender: mpsc::Sender<SyncEvent>,
+    command_sender: mpsc::Sender<SyncCommand>,
+}
+
+impl PayoutQueue {
+    pub fn new(
+        wallet: Arc<Wallet>,
+        master_sk: SecretKey,
+        state: Arc<Mutex<PeerState>>,
+        sync_sender: mpsc::Sender<SyncEvent>,
+        command_sender: mpsc::Sender<SyncCommand>,
+    ) -> Self {
+        Self {
+            wallet,
+            master_sk,
+            state,
+            sync_sender,
+            command_sender,
+        }
+    }
+
+    pub async fn start(self, delay: Duration) -> Result<(), WalletError> {
+        loop {
+            self.process_batch().await?;
+            sleep(delay).await;
+        }
+    }
+
+    async fn process_batch(&self) -> Result<(), WalletError> {
+        let payout_ids = self.wallet.db.active_payout_ids().await?;
+
+        if payout_ids.is_empty() {
+            return Ok(());
+        }
+
+        let Some(peer) = self.state.lock().await.acquire_peer() else {
+            return Ok(());
+        };
+
+        for payout_id in payout_ids {
+            // Batches are submitted one at a time, so that each one can spend the change
+            // from the previous one and a failure never affects more than one batch.
+            if let Some(batch) = self.wallet.db.pending_payout_batch(payout_id).await? {
+                let status = match self.batch_status(&peer, batch).await {
+                    Ok(status) => status,
+                    Err(error) => {
+                        warn!("Payout {payout_id} batch status could not be checked: {error}");
+                        continue;
+                    }
+                };
+
+                match status {
+                    PayoutBatchStatus::Pending => continue,
+                    PayoutBatchStatus::Confirmed => {
+                        info!(
+                            "Payout {payout_id} batch with transaction id {} was confirmed",
+                            batch.transaction_id
+                        );
+
+                        self.wallet.db.confirm_payout_batch(batch.id).await?;
+                    }
+                    PayoutBatchStatus::Failed => {
+                        warn!(
+                            "Payout {payout_id} batch with transaction id {} failed",
+                            batch.transaction_id
+                        );
+
+                        let mut tx = self.wallet.db.tx().await?;
+                        tx.fail_payout_batch(batch.id).await?;
+                        tx.commit().await?;
+
+                        self.wallet
+                            .db
+                            .update_payout_status(
+                                payout_id,
+                                true,
+                                Some(format!("Transaction {} failed", batch.transaction_id)),
+                            )
+                            .await?;
+                    }
+                }
+
+                self.sync_sender
+                    .send(SyncEvent::PayoutUpdated { payout_id })
+                    .await
+                    .ok();
+
+                continue;
+            }
+
+            let Some(payout) = self.wallet.db.payout(payout_id).await? else {
+                continue;
+            };
+
+            match self.submit(&peer, &payout).await {
+                Ok(Some(transaction_id)) => {
+                    info!(
+                        "Submitted payout {payout_id} batch with transaction id {transaction_id}"
+                    );
+                }
+                Ok(None) => continue,
+                Err(error) => {
+                    warn!("Payout {payout_id} batch could not be submitted: {error}");
+
+                    self.wallet
+                        .db
+                        .update_payout_status(payout_id, true, Some(error.to_string()))
+                        .await?;
+                }
+            }
+
+            self.sync_sender
+                .send(SyncEvent::PayoutUpdated { payout_id })
+                .await
+                .ok();
+        }
+
+        Ok(())
+    }
+
+    async fn batch_status(
+        &self,
+        peer: &WalletPeer,
+        batch: PayoutBatchRow,
+    ) -> Result<PayoutBatchStatus, WalletError> {
+        if self.wallet.db.is_payout_batch_pending(batch).await? {
+            return Ok(PayoutBatchStatus::Pending);
+        }
+
+        // The inputs may have been spent by a conflicting transaction instead, in which case
+        // the coin for the first payment of the batch is never created.
+        let is_created = peer
+            .fetch_coins(vec![batch.output_coin_id], self.wallet.genesis_challenge)
+            .await?
+            .into_iter()
+            .any(|coin_state| coin_state.created_height.is_some());
+
+        Ok(if is_created {
+            PayoutBatchStatus::Confirmed
+        } else {
+            PayoutBatchStatus::Failed
+        })
+    }
+
+    async fn submit(
+        &self,
+        peer: &WalletPeer,
+        payout: &PayoutRow,
+    ) -> Result<Option<Bytes32>, WalletError> {
+        let rows = self
+            .wallet
+            .db
+            .unsubmitted_payout_payments(payout.id)
+            .await?;
+
+        if rows.is_empty() {
+            return Ok(None);
+        }
+
+        let payments: Vec<MultiSendPayment> = rows
+            .iter()
+            .map(|row| MultiSendPayment {
+                asset_id: row.asset_id,
+                amount: row.amount,
+                puzzle_hash: row.puzzle_hash,
+                memos: row.memos.clone(),
+            })
+            .collect();
+
+        let (coin_spends, count) = self
+            .wallet
+            .multi_send_within_cost(&payments, payout.fee, payout.max_cost)
+            .await?;
+
+        let spend_bundle = self
+            .wallet
+            .sign_transaction(
+                SpendBundle::new(coin_spends, Signature::default()),
+                &self.wallet.agg_sig_constants,
+                self.master_sk.clone(),
+                false,
+            )
+            .await?;
+
+        let transaction_id = spend_bundle.name();
+        let transaction = Transaction::from_coin_spends(spend_bundle.coin_spends)?;
+
+        let first = &rows[0];
+
+        let output_coin_id = transaction
+            .inputs
+            .iter()
+            .flat_map(|input| &input.outputs)
+            .find(|output| {
+                output.coin.amount == first.amount
+                    && (output.coin.puzzle_hash == first.puzzle_hash
+                        || output.kind.receiver_custody_p2_puzzle_hash() == Some(first.puzzle_hash))
+            })
+            .map(|output| output.coin.coin_id())
+            .ok_or(WalletError::MissingChild(
+                transaction.inputs[0].coin_spend.coin.coin_id(),
+            ))?;
+
+        // The batch is recorded before the transaction, so that if the wallet stops in
+        // between, the batch is treated as failed rather than the payments being sent twice.
+        let mut tx = self.wallet.db.tx().await?;
+
+        let batch_id = tx
+            .insert_payout_batch(payout.id, transaction_id, output_coin_id)
+            .await?;
+
+        for row in &rows[..count] {
+            tx.set_payout_payment_batch(payout.id, row.row_index, batch_id)
+                .await?;
+        }
+
+        tx.commit().await?;
+
+        let subscriptions = insert_transaction(
+            &self.wallet.db,
+            peer,
+            self.wallet.genesis_challenge,
+            transaction_id,
+            transaction,
+            spend_bundle.aggregated_signature,
+        )
+        .await?;
+
+        self.command_sender
+            .send(SyncCommand::SubscribeCoins {
+                coin_ids: subscriptions,
+            })
+            .await
+            .ok();
+
+        Ok(Some(transaction_id))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use chia::protocol::{Bytes32, Coin};
+    use sage_database::{PayoutPaymentRow, SpendingLimitRow};
+    use test_log::test;
+
+    use crate::{
+        spend_cost, MultiSendPayment, SyncCommand, SyncEvent, TestWallet, MAX_TRANSACTION_COST,
+    };
+
+    #[test(tokio::test)]
+    async fn test_payout_batches() -> anyhow::Result<()> {
+        let mut alice = TestWallet::new(1000).await?;
+        let bob = alice.next(0).await?;
+
+        // Limit the cost to what two payments need, so that five payments take three batches.
+        // The amounts are distinct, since identical outputs would need to be split up.
+        let max_cost = spend_cost(
+            &alice
+                .wallet
+                .multi_send(
+                    vec![
+                        MultiSendPayment::xch(bob.puzzle_hash, 10),
+                        MultiSendPayment::xch(bob.puzzle_hash, 20),
+                    ],
+                    0,
+                )
+                .await?,
+        )?;
+
+        let mut tx = alice.wallet.db.tx().await?;
+        let id = tx.insert_payout("Test".to_string(), 0, max_cost).await?;
+        for row_index in 0..5 {
+            tx.insert_payout_payment(
+                id,
+                PayoutPaymentRow {
+                    row_index,
+                    asset_id: None,
+                    puzzle_hash: bob.puzzle_hash,
+                    amount: 100 + u64::from(row_index),
+                    memos: vec![],
+                },
+            )
+            .await?;
+        }
+        tx.commit().await?;
+
+        alice
+            .sender
+            .send(SyncCommand::SetSigningKey(Some(alice.master_sk.clone())))
+            .await?;
+
+        loop {
+            alice
+                .consume_until(|event| matches!(event, SyncEvent::PayoutUpdated { .. }))
+                .await;
+
+            let payout = alice.wallet.db.payout(id).await?.expect("missing payout");
+            assert_eq!(payout.error, None);
+
+            if payout.confirmed_count == 5 {
+                break;
+            }
+        }
+
+        assert!(alice.wallet.db.active_payout_ids().await?.is_empty());
+
+        let payments = alice.wallet.db.payout_payments(id).await?;
+        assert!(payments.iter().all(|payment| payment.is_confirmed));
+
+        let transaction_ids: HashSet<_> = payments
+            .iter()
+            .filter_map(|payment| payment.transaction_id)
+            .collect();
+        assert_eq!(transaction_ids.len(), 3);
+
+        // Batches are only confirmed once the wallet has synced the spent coins.
+        assert_eq!(alice.wallet.db.xch_balance().await?, 490);
+
+        Ok(())
+    }
+
+    #[test(tokio::test)]
+    async fn test_payout_conflicting_spend() -> anyhow::Result<()> {
+        let mut alice = TestWallet::new(1000).await?;
+        let bob = alice.next(0).await?;
+
+        let coin = alice.wallet.db.selectable_xch_coins().await?[0];
+
+        // Record a batch as though it had been submitted, but spend its input coin with an
+        // unrelated transaction instead.
+        let mut tx = alice.wallet.db.tx().await?;
+        let id = tx
+            .insert_payout("Test".to_string(), 0, MAX_TRANSACTION_COST)
+            .await?;
+        tx.insert_payout_payment(
+            id,
+            PayoutPaymentRow {
+                row_index: 0,
+                asset_id: None,
+                puzzle_hash: bob.puzzle_hash,
+                amount: 100,
+                memos: vec![],
+            },
+        )
+        .await?;
+        let batch_id = tx
+            .insert_payout_batch(
+                id,
+                Bytes32::default(),
+                Coin::new(coin.coin_id(), bob.puzzle_hash, 100).coin_id(),
+            )
+            .await?;
+        tx.set_payout_payment_batch(id, 0, batch_id).await?;
+        tx.commit().await?;
+
+        let coin_spends = alice
+            .wallet
+            .send_xch(vec![(bob.puzzle_hash, 50)], 0, vec![], None)
+            .await?;
+        assert_eq!(coin_spends[0].coin, coin);
+        alice.transact(coin_spends).await?;
+        alice.wait_for_coins().await;
+
+        alice
+            .sender
+            .send(SyncCommand::SetSigningKey(Some(alice.master_sk.clone())))
+            .await?;
+
+        alice
+            .consume_until(|event| matches!(event, SyncEvent::PayoutUpdated { .. }))
+            .await;
+
+        // The input coin was spent, but the payment was never made, so the batch failed and
+        // the payment is released to be submitted again.
+        let payout = alice.wallet.db.payout(id).await?.expect("missing payout");
+        assert!(payout.is_paused);
+        assert!(payout.error.is_some());
+        assert_eq!(payout.submitted_count, 0);
+        assert_eq!(payout.confirmed_count, 0);
+
+        let payments = alice.wallet.db.payout_payments(id).await?;
+        assert_eq!(payments[0].transaction_id, None);
+        assert!(!payments[0].is_confirmed);
+
+        assert_eq!(alice.wallet.db.xch_balance().await?, 950);
+
+        Ok(())
+    }
+
+    #[test(tokio::test)]
+    async fn test_payout_insufficient_funds() -> anyhow::Result<()> {
+        let mut alice = TestWallet::new(1000).await?;
+
+        let mut tx = alice.wallet.db.tx().await?;
+        let id = tx
+            .insert_payout("Test".to_string(), 0, MAX_TRANSACTION_COST)
+            .await?;
+        tx.insert_payout_payment(
+            id,
+            PayoutPaymentRow {
+                row_index: 0,
+                asset_id: None,
+                puzzle_hash: alice.puzzle_hash,
+                amount: 5000,
+                memos: vec![],
+            },
+        )
+        .await?;
+        tx.commit().await?;
+
+        alice
+            .sender
+            .send(SyncCommand::SetSigningKey(Some(alice.master_sk.clone())))
+            .await?;
+
+        alice
+            .consume_until(|event| matches!(event, SyncEvent::PayoutUpdated { .. }))
+            .await;
+
+        let payout = alice.wallet.db.payout(id).await?.expect("missing payout");
+        assert!(payout.is_paused);
+        assert!(payout.error.is_some());
+        assert_eq!(payout.submitted_count, 0);
+
+        assert_eq!(alice.wallet.db.xch_balance().await?, 1000);
+
+        Ok(())
+    }
+
+    #[test(tokio::test)]
+    async fn test_payout_policy_violation() -> anyhow::Result<()> {
+        let mut alice = TestWallet::new(1000).await?;
+        let bob = alice.next(0).await?;
+
+        alice
+            .set_policy(
+                None,
+                vec![SpendingLimitRow {
+                    asset_hash: Bytes32::default(),
+                    max_per_transaction: None,
+                    max_per_day: Some(150),
+                }],
+                vec![],
+            )
+            .await?;
+
+        let mut tx = alice.wallet.db.tx().await?;
+        let id = tx
+            .insert_payout("Test".to_string(), 0, MAX_TRANSACTION_COST)
+            .await?;
+        for row_index in 0..2 {
+            tx.insert_payout_payment(
+                id,
+                PayoutPaymentRow {
+                    row_index,
+                    asset_id: None,
+                    puzzle_hash: bob.puzzle_hash,
+                    amount: 100 + u64::from(row_index),
+                    memos: vec![],
+                },
+            )
+            .await?;
+        }
+        tx.commit().await?;
+
+        alice
+            .sender
+            .send(SyncCommand::SetSigningKey(Some(alice.master_sk.clone())))
+            .await?;
+
+        alice
+            .consume_until(|event| matches!(event, SyncEvent::PayoutUpdated { .. }))
+            .await;
+
+        // The whole batch exceeds the daily limit, so nothing is signed and the payout is
+        // paused rather than retried.
+        let payout = alice.wallet.db.payout(id).await?.expect("missing payout");
+        assert!(payout.is_paused);
+        assert!(payout
+            .error
+            .as_deref()
+            .is_some_and(|error| error.contains("policy")));
+        assert_eq!(payout.submitted_count, 0);
+
+        assert_eq!(alice.wallet.db.xch_balance().await?, 1000);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,329 @@
+use std::{
+    sync::Arc,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use chia::{
+    bls::{SecretKey, Signature},
+    protocol::{Bytes32, SpendBundle},
+};
+use sage_database::ScheduledPaymentRow;
+use tokio::{
+    sync::{mpsc, Mutex},
+    time::sleep,
+};
+use tracing::{info, warn};
+
+use crate::{
+    insert_transaction, MultiSendPayment, PeerState, Schedule, SyncCommand, SyncEvent, Transaction,
+    Wallet, WalletError, WalletPeer,
+};
+
+#[derive(Debug)]
+pub struct ScheduledPaymentQueue {
+    wallet: Arc<Wallet>,
+    master_sk: SecretKey,
+    state: Arc<Mutex<PeerState>>,
+    sync_sender: mpsc::Sender<SyncEvent>,
+    command_sender: mpsc::Sender<SyncCommand>,
+}
+
+impl ScheduledPaymentQueue {
+    pub fn new(
+        wallet: Arc<Wallet>,
+        master_sk: SecretKey,
+        state: Arc<Mutex<PeerState>>,
+        sync_sender: mpsc::Sender<SyncEvent>,
+        command_sender: mpsc::Sender<SyncCommand>,
+    ) -> Self {
+        Self {
+            wallet,
+            master_sk,
+            state,
+            sync_sender,
+            command_sender,
+        }
+    }
+
+    pub async fn start(self, delay: Duration) -> Result<(), WalletError> {
+        loop {
+            self.process_batch().await?;
+            sleep(delay).await;
+        }
+    }
+
+    async fn process_batch(&self) -> Result<(), WalletError> {
+        let timestamp = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+
+        let payments = self.wallet.db.due_scheduled_payments(timestamp).await?;
+
+        if payments.is_empty() {
+            return Ok(());
+        }
+
+        let Some(peer) = self.state.lock().await.acquire_peer() else {
+            return Ok(());
+        };
+
+        for payment in payments {
+            // The schedule is advanced before anything is submitted, so that a run which is
+            // interrupted part way through can never be repeated.
+            let next_run_timestamp = payment
+                .schedule
+                .parse::<Schedule>()
+                .ok()
+                .and_then(|schedule| schedule.next_after(timestamp));
+
+            if let Some(next_run_timestamp) = next_run_timestamp {
+                self.wallet
+                    .db
+                    .set_next_run_timestamp(payment.id, next_run_timestamp)
+                    .await?;
+            } else {
+                self.wallet
+                    .db
+                    .update_scheduled_payment_status(payment.id, true, payment.next_run_timestamp)
+                    .await?;
+            }
+
+            let (transaction_id, error) = match self.run(&peer, &payment).await {
+                Ok(transaction_id) => {
+                    info!(
+                        "Submitted scheduled payment {} with transaction id {transaction_id}",
+                        payment.id
+                    );
+                    (Some(transaction_id), None)
+                }
+                Err(error) => {
+                    warn!("Scheduled payment {} failed: {error}", payment.id);
+                    (None, Some(error.to_string()))
+                }
+            };
+
+            self.wallet
+                .db
+                .insert_scheduled_payment_run(payment.id, transaction_id, error)
+                .await?;
+
+            self.sync_sender
+                .send(SyncEvent::ScheduledPaymentRun {
+                    scheduled_payment_id: payment.id,
+                    transaction_id,
+                })
+                .await
+                .ok();
+        }
+
+        Ok(())
+    }
+
+    async fn run(
+        &self,
+        peer: &WalletPeer,
+        payment: &ScheduledPaymentRow,
+    ) -> Result<Bytes32, WalletError> {
+        payment.schedule.parse::<Schedule>()?;
+
+        let payments = self
+            .wallet
+            .db
+            .scheduled_payment_recipients(payment.id)
+            .await?
+            .into_iter()
+            .map(|recipient| MultiSendPayment {
+                asset_id: recipient.asset_id,
+                amount: recipient.amount,
+                puzzle_hash: recipient.puzzle_hash,
+                memos: recipient.memos,
+            })
+            .collect();
+
+        let coin_spends = self.wallet.multi_send(payments, payment.fee).await?;
+
+        let spend_bundle = self
+            .wallet
+            .sign_transaction(
+                SpendBundle::new(coin_spends, Signature::default()),
+                &self.wallet.agg_sig_constants,
+                self.master_sk.clone(),
+                false,
+            )
+            .await?;
+
+        let transaction_id = spend_bundle.name();
+
+        let subscriptions = insert_transaction(
+            &self.wallet.db,
+            peer,
+            self.wallet.genesis_challenge,
+            transaction_id,
+            Transaction::from_coin_spends(spend_bundle.coin_spends)?,
+            spend_bundle.aggregated_signature,
+        )
+        .await?;
+
+        self.command_sender
+            .send(SyncCommand::SubscribeCoins {
+                coin_ids: subscriptions,
+            })
+            .await
+            .ok();
+
+        Ok(transaction_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chia::protocol::Bytes32;
+    use sage_database::{ScheduledPaymentRecipientRow, SpendingLimitRow};
+    use test_log::test;
+
+    use crate::{SyncCommand, SyncEvent, TestWallet};
+
+    #[test(tokio::test)]
+    async fn test_scheduled_payment() -> anyhow::Result<()> {
+        let mut alice = TestWallet::new(1000).await?;
+        let mut bob = alice.next(0).await?;
+
+        let mut tx = alice.wallet.db.tx().await?;
+        let id = tx
+            .insert_scheduled_payment("Test".to_string(), "* * * * *".to_string(), 100, 0)
+            .await?;
+        tx.insert_scheduled_payment_recipient(
+            id,
+            ScheduledPaymentRecipientRow {
+                asset_id: None,
+                puzzle_hash: bob.puzzle_hash,
+                amount: 250,
+                memos: vec![],
+            },
+        )
+        .await?;
+        tx.commit().await?;
+
+        alice
+            .sender
+            .send(SyncCommand::SetSigningKey(Some(alice.master_sk.clone())))
+            .await?;
+
+        alice
+            .consume_until(|event| matches!(event, SyncEvent::ScheduledPaymentRun { .. }))
+            .await;
+
+        let runs = alice.wallet.db.scheduled_payment_runs(id, 10).await?;
+        assert_eq!(runs.len(), 1);
+        assert!(runs[0].transaction_id.is_some());
+        assert_eq!(runs[0].error, None);
+
+        let row = alice
+            .wallet
+            .db
+            .scheduled_payment(id)
+            .await?
+            .expect("missing scheduled payment");
+        assert!(row.next_run_timestamp > 0);
+
+        alice.wait_for_coins().await;
+        bob.wait_for_coins().await;
+
+        assert_eq!(alice.wallet.db.xch_balance().await?, 650);
+        assert_eq!(bob.wallet.db.xch_balance().await?, 250);
+
+        Ok(())
+    }
+
+    #[test(tokio::test)]
+    async fn test_scheduled_payment_failure() -> anyhow::Result<()> {
+        let mut alice = TestWallet::new(1000).await?;
+
+        let mut tx = alice.wallet.db.tx().await?;
+        let id = tx
+            .insert_scheduled_payment("Test".to_string(), "* * * * *".to_string(), 0, 0)
+            .await?;
+        tx.insert_scheduled_payment_recipient(
+            id,
+            ScheduledPaymentRecipientRow {
+                asset_id: None,
+                puzzle_hash: alice.puzzle_hash,
+                amount: 5000,
+                memos: vec![],
+            },
+        )
+        .await?;
+        tx.commit().await?;
+
+        alice
+            .sender
+            .send(SyncCommand::SetSigningKey(Some(alice.master_sk.clone())))
+            .await?;
+
+        alice
+            .consume_until(|event| matches!(event, SyncEvent::ScheduledPaymentRun { .. }))
+            .await;
+
+        let runs = alice.wallet.db.scheduled_payment_runs(id, 10).await?;
+        assert_eq!(runs.len(), 1);
+        assert_eq!(runs[0].transaction_id, None);
+        assert!(runs[0].error.is_some());
+
+        assert_eq!(alice.wallet.db.xch_balance().await?, 1000);
+
+        Ok(())
+    }
+
+    #[test(tokio::test)]
+    async fn test_scheduled_payment_policy_violation() -> anyhow::Result<()> {
+        let mut alice = TestWallet::new(1000).await?;
+        let bob = alice.next(0).await?;
+
+        alice
+            .set_policy(
+                None,
+                vec![SpendingLimitRow {
+                    asset_hash: Bytes32::default(),
+                    max_per_transaction: Some(100),
+                    max_per_day: None,
+                }],
+                vec![],
+            )
+            .await?;
+
+        let mut tx = alice.wallet.db.tx().await?;
+        let id = tx
+            .insert_scheduled_payment("Test".to_string(), "* * * * *".to_string(), 0, 0)
+            .await?;
+        tx.insert_scheduled_payment_recipient(
+            id,
+            ScheduledPaymentRecipientRow {
+                asset_id: None,
+                puzzle_hash: bob.puzzle_hash,
+                amount: 250,
+                memos: vec![],
+            },
+        )
+        .await?;
+        tx.commit().await?;
+
+        alice
+            .sender
+            .send(SyncCommand::SetSigningKey(Some(alice.master_sk.clone())))
+            .await?;
+
+        alice
+            .consume_until(|event| matches!(event, SyncEvent::ScheduledPaymentRun { .. }))
+            .await;
+
+        let runs = alice.wallet.db.scheduled_payment_runs(id, 10).await?;
+        assert_eq!(runs.len(), 1);
+        assert_eq!(runs[0].transaction_id, None);
+        assert!(runs[0]
+            .error
+            .as_deref()
+            .is_some_and(|error| error.contains("policy")));
+
+        assert_eq!(alice.wallet.db.xch_balance().await?, 1000);
+
+        Ok(())
+    }
+}
@@ -97,6 +97,7 @@ impl TransactionQueue {
 
                     tx.set_transaction_children_unsynced(transaction_id).await?;
                     tx.remove_mempool_item(transaction_id).await?;
+                    tx.remove_policy_spends(transaction_id).await?;
 
                     tx.commit().await?;
 
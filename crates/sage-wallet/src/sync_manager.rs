@@ -6,6 +6,7 @@ use std::{
 };
 
 use chia::{
+    bls::SecretKey,
     protocol::{Bytes32, CoinStateUpdate, Message, NewPeakWallet, ProtocolMessageTypes},
     traits::Streamable,
 };
@@ -15,6 +16,7 @@ use chia_wallet_sdk::{
 };
 use futures_lite::future::poll_once;
 use itertools::Itertools;
+use sage_assets::UriResolver;
 use sage_config::Network;
 use tokio::{
     sync::{mpsc, Mutex},
@@ -25,7 +27,8 @@ use tracing::{debug, info, warn};
 use wallet_sync::{add_new_subscriptions, incremental_sync, sync_wallet};
 
 use crate::{
-    BlockTimeQueue, CatQueue, NftUriQueue, OfferQueue, PuzzleQueue, TransactionQueue, Wallet,
+    BlockTimeQueue, CatQueue, MintJobQueue, NftUriQueue, OfferGroupQueue, OfferQueue,
+    OfferRecreateQueue, PayoutQueue, PuzzleQueue, ScheduledPaymentQueue, TransactionQueue, Wallet,
     WalletError,
 };
 
@@ -46,7 +49,9 @@ pub struct SyncManager {
     options: SyncOptions,
     state: Arc<Mutex<PeerState>>,
     wallet: Option<Arc<Wallet>>,
+    signing_key: Option<SecretKey>,
     network: Network,
+    uri_resolver: UriResolver,
     connector: Connector,
     event_sender: mpsc::Sender<SyncEvent>,
     command_sender: mpsc::Sender<SyncCommand>,
@@ -58,6 +63,11 @@ pub struct SyncManager {
     transaction_queue_task: Option<JoinHandle<Result<(), WalletError>>>,
     offer_queue_task: Option<JoinHandle<Result<(), WalletError>>>,
     blocktime_queue_task: Option<JoinHandle<Result<(), WalletError>>>,
+    scheduled_payment_task: Option<JoinHandle<Result<(), WalletError>>>,
+    payout_task: Option<JoinHandle<Result<(), WalletError>>>,
+    mint_job_task: Option<JoinHandle<Result<(), WalletError>>>,
+    offer_group_task: Option<JoinHandle<Result<(), WalletError>>>,
+    offer_recreate_task: Option<JoinHandle<Result<(), WalletError>>>,
     pending_coin_subscriptions: Vec<Bytes32>,
     pending_puzzle_subscriptions: Vec<Bytes32>,
 }
@@ -102,6 +112,21 @@ impl Drop for SyncManager {
         if let Some(task) = &mut self.blocktime_queue_task {
             task.abort();
         }
+        if let Some(task) = &mut self.scheduled_payment_task {
+            task.abort();
+        }
+        if let Some(task) = &mut self.payout_task {
+            task.abort();
+        }
+        if let Some(task) = &mut self.mint_job_task {
+            task.abort();
+        }
+        if let Some(task) = &mut self.offer_group_task {
+            task.abort();
+        }
+        if let Some(task) = &mut self.offer_recreate_task {
+            task.abort();
+        }
     }
 }
 
@@ -111,6 +136,7 @@ impl SyncManager {
         state: Arc<Mutex<PeerState>>,
         wallet: Option<Arc<Wallet>>,
         network: Network,
+        uri_resolver: UriResolver,
         connector: Connector,
     ) -> (Self, mpsc::Sender<SyncCommand>, mpsc::Receiver<SyncEvent>) {
         let (command_sender, command_receiver) = mpsc::channel(100);
@@ -120,7 +146,9 @@ impl SyncManager {
             options,
             state,
             wallet,
+            signing_key: None,
             network,
+            uri_resolver,
             connector,
             event_sender,
             command_sender: command_sender.clone(),
@@ -132,6 +160,11 @@ impl SyncManager {
             transaction_queue_task: None,
             offer_queue_task: None,
             blocktime_queue_task: None,
+            scheduled_payment_task: None,
+            payout_task: None,
+            mint_job_task: None,
+            offer_group_task: None,
+            offer_recreate_task: None,
             pending_coin_subscriptions: Vec::new(),
             pending_puzzle_subscriptions: Vec::new(),
         };
@@ -155,8 +188,27 @@ impl SyncManager {
                     self.clear_subscriptions().await;
                     self.abort_wallet_tasks();
                     self.wallet = wallet;
+                    self.signing_key = None;
                     self.options.delta_sync = delta_sync;
                 }
+                SyncCommand::SetSigningKey(signing_key) => {
+                    if let Some(task) = self.scheduled_payment_task.take() {
+                        task.abort();
+                    }
+                    if let Some(task) = self.payout_task.take() {
+                        task.abort();
+                    }
+                    if let Some(task) = self.mint_job_task.take() {
+                        task.abort();
+                    }
+                    if let Some(task) = self.offer_group_task.take() {
+                        task.abort();
+                    }
+                    if let Some(task) = self.offer_recreate_task.take() {
+                        task.abort();
+                    }
+                    self.signing_key = signing_key;
+                }
                 SyncCommand::SwitchNetwork(network) => {
                     if self.network.network_id() != network.network_id()
                         || self.network.genesis_challenge != network.genesis_challenge
@@ -276,6 +328,21 @@ impl SyncManager {
         if let Some(task) = &mut self.blocktime_queue_task.take() {
             task.abort();
         }
+        if let Some(task) = &mut self.scheduled_payment_task.take() {
+            task.abort();
+        }
+        if let Some(task) = &mut self.payout_task.take() {
+            task.abort();
+        }
+        if let Some(task) = &mut self.mint_job_task.take() {
+            task.abort();
+        }
+        if let Some(task) = &mut self.offer_group_task.take() {
+            task.abort();
+        }
+        if let Some(task) = &mut self.offer_recreate_task.take() {
+            task.abort();
+        }
     }
 
     async fn handle_message(&self, ip: IpAddr, message: Message) -> Result<(), WalletError> {
@@ -437,6 +504,7 @@ impl SyncManager {
                         wallet.db.clone(),
                         self.event_sender.clone(),
                         self.network.clone(),
+                        self.uri_resolver.clone(),
                     )
                     .start(self.options.timeouts.nft_uri_delay),
                 );
@@ -487,6 +555,92 @@ impl SyncManager {
             self.offer_queue_task = None;
             self.blocktime_queue_task = None;
         }
+
+        // Scheduled payments, payouts, mint jobs, offer reposts, and recreated offers are only
+        // made once the wallet is fully synced.
+        if let (Some(wallet), Some(signing_key), InitialWalletSync::Subscribed(_)) = (
+            self.wallet.clone(),
+            self.signing_key.clone(),
+            &self.initial_wallet_sync,
+        ) {
+            if self.scheduled_payment_task.is_none() {
+                let task = tokio::spawn(
+                    ScheduledPaymentQueue::new(
+                        wallet.clone(),
+                        signing_key.clone(),
+                        self.state.clone(),
+                        self.event_sender.clone(),
+                        self.command_sender.clone(),
+                    )
+                    .start(self.options.timeouts.scheduled_payment_delay),
+                );
+                self.scheduled_payment_task = Some(task);
+            }
+
+            if self.payout_task.is_none() {
+                let task = tokio::spawn(
+                    PayoutQueue::new(
+                        wallet.clone(),
+                        signing_key.clone(),
+                        self.state.clone(),
+                        self.event_sender.clone(),
+                        self.command_sender.clone(),
+                    )
+                    .start(self.options.timeouts.payout_delay),
+                );
+                self.payout_task = Some(task);
+            }
+
+            if self.mint_job_task.is_none() {
+                let task = tokio::spawn(
+                    MintJobQueue::new(
+                        wallet.clone(),
+                        signing_key.clone(),
+                        self.state.clone(),
+                        self.event_sender.clone(),
+                        self.command_sender.clone(),
+                    )
+                    .start(self.options.timeouts.mint_job_delay),
+                );
+                self.mint_job_task = Some(task);
+            }
+
+            if self.offer_group_task.is_none() {
+                let task = tokio::spawn(
+                    OfferGroupQueue::new(
+                        wallet.clone(),
+                        signing_key.clone(),
+                        self.event_sender.clone(),
+                    )
+                    .start(self.options.timeouts.offer_group_delay),
+                );
+                self.offer_group_task = Some(task);
+            }
+
+            if self.offer_recreate_task.is_none() {
+                let task = tokio::spawn(
+                    OfferRecreateQueue::new(wallet, signing_key, self.event_sender.clone())
+                        .start(self.options.timeouts.offer_recreate_delay),
+                );
+                self.offer_recreate_task = Some(task);
+            }
+        } else {
+            if let Some(task) = self.scheduled_payment_task.take() {
+                task.abort();
+            }
+            if let Some(task) = self.payout_task.take() {
+                task.abort();
+            }
+            if let Some(task) = self.mint_job_task.take() {
+                task.abort();
+            }
+            if let Some(task) = self.offer_group_task.take() {
+                task.abort();
+            }
+            if let Some(task) = self.offer_recreate_task.take() {
+                task.abort();
+            }
+        }
     }
 
     async fn poll_tasks(&mut self) {
@@ -622,5 +776,90 @@ impl SyncManager {
                 None => {}
             }
         }
+
+        if let Some(task) = &mut self.scheduled_payment_task {
+            match poll_once(task).await {
+                Some(Err(error)) => {
+                    warn!("Scheduled payment queue failed with panic: {error}");
+                    self.scheduled_payment_task = None;
+                }
+                Some(Ok(Err(error))) => {
+                    warn!("Scheduled payment queue failed with error: {error}");
+                    self.scheduled_payment_task = None;
+                }
+                Some(Ok(Ok(()))) => {
+                    self.scheduled_payment_task = None;
+                }
+                None => {}
+            }
+        }
+
+        if let Some(task) = &mut self.payout_task {
+            match poll_once(task).await {
+                Some(Err(error)) => {
+                    warn!("Payout queue failed with panic: {error}");
+                    self.payout_task = None;
+                }
+                Some(Ok(Err(error))) => {
+                    warn!("Payout queue failed with error: {error}");
+                    self.payout_task = None;
+                }
+                Some(Ok(Ok(()))) => {
+                    self.payout_task = None;
+                }
+                None => {}
+            }
+        }
+
+        if let Some(task) = &mut self.mint_job_task {
+            match poll_once(task).await {
+                Some(Err(error)) => {
+                    warn!("Mint job queue failed with panic: {error}");
+                    self.mint_job_task = None;
+                }
+                Some(Ok(Err(error))) => {
+                    warn!("Mint job queue failed with error: {error}");
+                    self.mint_job_task = None;
+                }
+                Some(Ok(Ok(()))) => {
+                    self.mint_job_task = None;
+                }
+                None => {}
+            }
+        }
+
+        if let Some(task) = &mut self.offer_group_task {
+            match poll_once(task).await {
+                Some(Err(error)) => {
+                    warn!("Offer group queue failed with panic: {error}");
+                    self.offer_group_task = None;
+                }
+                Some(Ok(Err(error))) => {
+                    warn!("Offer group queue failed with error: {error}");
+                    self.offer_group_task = None;
+                }
+                Some(Ok(Ok(()))) => {
+                    self.offer_group_task = None;
+                }
+                None => {}
+            }
+        }
+
+        if let Some(task) = &mut self.offer_recreate_task {
+            match poll_once(task).await {
+                Some(Err(error)) => {
+                    warn!("Offer recreate queue failed with panic: {error}");
+                    self.offer_recreate_task = None;
+                }
+                Some(Ok(Err(error))) => {
+                    warn!("Offer recreate queue failed with error: {error}");
+                    self.offer_recreate_task = None;
+                }
+                Some(Ok(Ok(()))) => {
+                    self.offer_recreate_task = None;
+                }
+                None => {}
+            }
+        }
     }
 }
@@ -22,6 +22,11 @@ pub struct Timeouts {
     pub transaction_delay: Duration,
     pub offer_delay: Duration,
     pub blocktime_delay: Duration,
+    pub scheduled_payment_delay: Duration,
+    pub payout_delay: Duration,
+    pub mint_job_delay: Duration,
+    pub offer_group_delay: Duration,
+    pub offer_recreate_delay: Duration,
     pub connection: Duration,
     pub initial_peak: Duration,
     pub dns: Duration,
@@ -38,6 +43,11 @@ impl Default for Timeouts {
             transaction_delay: Duration::from_secs(1),
             offer_delay: Duration::from_secs(5),
             blocktime_delay: Duration::from_secs(1),
+            scheduled_payment_delay: Duration::from_secs(10),
+            payout_delay: Duration::from_secs(5),
+            mint_job_delay: Duration::from_secs(5),
+            offer_group_delay: Duration::from_secs(10),
+            offer_recreate_delay: Duration::from_secs(10),
             connection: Duration::from_secs(3),
             initial_peak: Duration::from_secs(2),
             dns: Duration::from_secs(3),
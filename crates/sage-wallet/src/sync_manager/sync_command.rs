@@ -1,6 +1,9 @@
 use std::{net::IpAddr, sync::Arc};
 
-use chia::protocol::{Bytes32, Message};
+use chia::{
+    bls::SecretKey,
+    protocol::{Bytes32, Message},
+};
 use sage_config::Network;
 
 use crate::Wallet;
@@ -11,6 +14,11 @@ pub enum SyncCommand {
         wallet: Option<Arc<Wallet>>,
         delta_sync: bool,
     },
+    /// Provides the secret key used by the queues that sign transactions in the background for
+    /// the current wallet, which are those for scheduled payments, payouts, offer group reposts,
+    /// recreated offers and mint jobs. Setting it restarts these queues, and it's cleared
+    /// whenever the wallet is switched.
+    SetSigningKey(Option<SecretKey>),
     SwitchNetwork(Network),
     HandleMessage {
         ip: IpAddr,
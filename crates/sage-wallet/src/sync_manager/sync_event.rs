@@ -22,6 +22,19 @@ pub enum SyncEvent {
         offer_id: Bytes32,
         status: OfferStatus,
     },
+    ScheduledPaymentRun {
+        scheduled_payment_id: u32,
+        transaction_id: Option<Bytes32>,
+    },
+    PayoutUpdated {
+        payout_id: u32,
+    },
+    MintJobUpdated {
+        mint_job_id: u32,
+    },
+    OfferGroupUpdated {
+        offer_group_id: u32,
+    },
     PuzzleBatchSynced,
     CatInfo,
     DidInfo,
@@ -1,14 +1,16 @@
 use std::{collections::HashSet, sync::Arc, time::Duration};
 
 use chia::protocol::{Bytes32, CoinState, CoinStateFilters};
-use sage_database::DatabaseTx;
+use chia_wallet_sdk::driver::{decode_offer, Offer};
+use clvmr::Allocator;
+use sage_database::{DatabaseTx, OfferStatus};
 use tokio::{
     sync::{mpsc, Mutex},
     time::sleep,
 };
 use tracing::{info, warn};
 
-use crate::{SyncCommand, Wallet, WalletError, WalletPeer};
+use crate::{SyncCommand, Transaction, Wallet, WalletError, WalletPeer};
 
 use super::{PeerState, SyncEvent};
 
@@ -208,6 +210,86 @@ async fn sync_puzzle_hashes(
     Ok(())
 }
 
+/// Marks active offers as invalid when a coin locked in them has been spent by another
+/// transaction. A spend is only known to conflict with the offer if it created a coin which the
+/// offer's own spend wouldn't have, so offers which have been taken are left for the offer queue
+/// to complete. Spends by the wallet's pending transactions are cancellations, and are left for
+/// the offer queue as well.
+///
+/// Only confirmed spends are considered. The wallet doesn't watch the mempool, so a conflicting
+/// spend by someone else is only detected once it's included in a block, and a pending spend by
+/// the wallet itself can't be told apart from a cancellation.
+async fn invalidate_conflicting_offers(
+    tx: &mut DatabaseTx<'_>,
+    coin_states: &[CoinState],
+) -> Result<Vec<Bytes32>, WalletError> {
+    let mut invalid_offers = Vec::new();
+
+    for coin_state in coin_states {
+        // Coin states from peers are only ever spent once the spend has been confirmed.
+        if coin_state.spent_height.is_none() {
+            continue;
+        }
+
+        let coin_id = coin_state.coin.coin_id();
+        let offers = tx.active_offers_for_coin(coin_id).await?;
+
+        if offers.is_empty() || !tx.mempool_items_for_input(coin_id).await?.is_empty() {
+            continue;
+        }
+
+        let children = tx.coin_children(coin_id).await?;
+
+        for row in offers {
+            if invalid_offers.contains(&row.offer_id) {
+                continue;
+            }
+
+            let mut allocator = Allocator::new();
+
+            let spend_bundle = decode_offer(&row.encoded_offer)?;
+            let offer = Offer::from_spend_bundle(&mut allocator, &spend_bundle)?;
+
+            let Some(coin_spend) = offer
+                .cancellable_coin_spends()?
+                .into_iter()
+                .find(|coin_spend| coin_spend.coin.coin_id() == coin_id)
+            else {
+                continue;
+            };
+
+            let expected_children = Transaction::from_coin_spends(vec![coin_spend])?
+                .inputs
+                .into_iter()
+                .flat_map(|input| input.outputs)
+                .map(|output| output.coin.coin_id())
+                .collect::<HashSet<_>>();
+
+            if children
+                .iter()
+                .all(|child_id| expected_children.contains(child_id))
+            {
+                continue;
+            }
+
+            warn!(
+                "Offer {} is no longer valid, since coin {coin_id} was spent by another transaction",
+                row.offer_id
+            );
+
+            tx.invalidate_offer(
+                row.offer_id,
+                format!("Coin {coin_id} was spent by another transaction"),
+            )
+            .await?;
+
+            invalid_offers.push(row.offer_id);
+        }
+    }
+
+    Ok(invalid_offers)
+}
+
 pub async fn incremental_sync(
     wallet: &Wallet,
     coin_states: Vec<CoinState>,
@@ -254,6 +336,8 @@ pub async fn incremental_sync(
         }
     }
 
+    let invalid_offers = invalidate_conflicting_offers(&mut tx, &coin_states).await?;
+
     for mempool_item_id in confirmed_transactions {
         tx.remove_mempool_item(mempool_item_id).await?;
     }
@@ -272,6 +356,16 @@ pub async fn incremental_sync(
         sync_sender.send(SyncEvent::CoinsUpdated).await.ok();
     }
 
+    for offer_id in invalid_offers {
+        sync_sender
+            .send(SyncEvent::OfferUpdated {
+                offer_id,
+                status: OfferStatus::Invalid,
+            })
+            .await
+            .ok();
+    }
+
     if !new_derivations.is_empty() {
         sync_sender
             .send(SyncEvent::DerivationIndex { next_index })
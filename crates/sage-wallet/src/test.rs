@@ -17,6 +17,7 @@ use chia_wallet_sdk::{
     test::{BlsPair, PeerSimulator},
     types::TESTNET11_CONSTANTS,
 };
+use sage_assets::UriResolver;
 use sage_config::TESTNET11;
 use sage_database::{Database, Derivation};
 use sqlx::{migrate, SqlitePool};
@@ -64,7 +65,7 @@ impl TestWallet {
 
     pub async fn new_with_options(balance: u64, options: SyncOptions) -> anyhow::Result<Self> {
         let sim = PeerSimulator::new().await?;
-        Self::with_sim(Arc::new(sim), balance, 0, options).await
+        Self::with_sim(Arc::new(sim), balance, 0, &options).await
     }
 
     pub async fn next_with_options(
@@ -72,14 +73,14 @@ impl TestWallet {
         balance: u64,
         options: SyncOptions,
     ) -> anyhow::Result<Self> {
-        Self::with_sim(self.sim.clone(), balance, self.index + 1, options).await
+        Self::with_sim(self.sim.clone(), balance, self.index + 1, &options).await
     }
 
     async fn with_sim(
         sim: Arc<PeerSimulator>,
         balance: u64,
         key_index: u32,
-        options: SyncOptions,
+        options: &SyncOptions,
     ) -> anyhow::Result<Self> {
         let db_index = {
             let mut lock = INDEX.lock().await;
@@ -146,10 +147,11 @@ impl TestWallet {
         ));
 
         let (mut sync_manager, sender, events) = SyncManager::new(
-            options,
+            *options,
             state.clone(),
             Some(wallet.clone()),
             TESTNET11.clone(),
+            UriResolver::default(),
             Connector::Plain,
         );
 
@@ -175,7 +177,7 @@ impl TestWallet {
             events,
             index: key_index,
             state,
-            options,
+            options: *options,
         };
 
         test.consume_until(|event| matches!(event, SyncEvent::Subscribed))
@@ -186,7 +188,7 @@ impl TestWallet {
     }
 
     pub async fn resync(&mut self) -> anyhow::Result<()> {
-        *self = Self::with_sim(self.sim.clone(), 0, self.index, self.options).await?;
+        *self = Self::with_sim(self.sim.clone(), 0, self.index, &self.options).await?;
         Ok(())
     }
 
@@ -278,6 +280,11 @@ pub fn default_test_options() -> SyncOptions {
             puzzle_delay: Duration::from_millis(100),
             transaction_delay: Duration::from_millis(100),
             offer_delay: Duration::from_millis(100),
+            scheduled_payment_delay: Duration::from_millis(100),
+            payout_delay: Duration::from_millis(100),
+            mint_job_delay: Duration::from_millis(100),
+            offer_group_delay: Duration::from_millis(100),
+            offer_recreate_delay: Duration::from_millis(100),
             ..Default::default()
         },
         testing: true,
@@ -1,9 +1,13 @@
+use std::collections::HashSet;
+
 use chia::{
     clvm_traits::{FromClvm, ToClvm},
-    protocol::{Coin, CoinSpend, Program},
+    protocol::{Bytes, Bytes32, Coin, CoinSpend, Program},
+    puzzles::{nft::NftMetadata, Memos},
 };
 use chia_wallet_sdk::types::{run_puzzle, Condition, Conditions};
 use clvmr::{Allocator, NodePtr};
+use indexmap::IndexMap;
 
 use crate::{ChildKind, CoinKind, WalletError};
 
@@ -24,6 +28,7 @@ pub struct TransactionInput {
 pub struct TransactionOutput {
     pub coin: Coin,
     pub kind: ChildKind,
+    pub memos: Vec<Bytes>,
 }
 
 impl Transaction {
@@ -34,9 +39,14 @@ impl Transaction {
         let mut fee = 0;
 
         for coin_spend in coin_spends {
+            let mut allocator = Allocator::new();
             let mut outputs = Vec::new();
 
-            for condition in run_conditions(&coin_spend.puzzle_reveal, &coin_spend.solution)? {
+            for condition in run_conditions(
+                &mut allocator,
+                &coin_spend.puzzle_reveal,
+                &coin_spend.solution,
+            )? {
                 match condition {
                     Condition::CreateCoin(create_coin) => {
                         let child_coin = Coin::new(
@@ -53,6 +63,7 @@ impl Transaction {
                                 &coin_spend.solution,
                                 child_coin,
                             )?,
+                            memos: parse_memos(&allocator, create_coin.memos),
                         });
                     }
                     Condition::ReserveFee(cond) => {
@@ -73,15 +84,77 @@ impl Transaction {
 
         Ok(Self { fee, inputs })
     }
-}
 
-fn run_conditions(puzzle_reveal: &Program, solution: &Program) -> Result<Conditions, WalletError> {
-    let mut allocator = Allocator::new();
+    /// The metadata of each NFT before and after the transaction, if it was changed. NFTs which
+    /// are spent more than once are compared from their first to their last coin.
+    pub fn nft_metadata_updates(&self) -> Vec<(Bytes32, NftMetadata, NftMetadata)> {
+        let spent_coin_ids: HashSet<Bytes32> = self
+            .inputs
+            .iter()
+            .map(|input| input.coin_spend.coin.coin_id())
+            .collect();
+
+        let mut old_metadata = IndexMap::new();
+        let mut new_metadata = IndexMap::new();
 
-    let puzzle = puzzle_reveal.to_clvm(&mut allocator)?;
-    let solution = solution.to_clvm(&mut allocator)?;
-    let output = run_puzzle(&mut allocator, puzzle, solution)?;
-    let conditions = Conditions::<NodePtr>::from_clvm(&allocator, output)?;
+        for input in &self.inputs {
+            if let CoinKind::Nft {
+                info,
+                metadata: Some(metadata),
+            } = &input.kind
+            {
+                if !spent_coin_ids.contains(&input.coin_spend.coin.parent_coin_info) {
+                    old_metadata.insert(info.launcher_id, metadata.clone());
+                }
+            }
+
+            for output in &input.outputs {
+                if let ChildKind::Nft {
+                    info,
+                    metadata: Some(metadata),
+                    ..
+                } = &output.kind
+                {
+                    if !spent_coin_ids.contains(&output.coin.coin_id()) {
+                        new_metadata.insert(info.launcher_id, metadata.clone());
+                    }
+                }
+            }
+        }
+
+        old_metadata
+            .into_iter()
+            .filter_map(|(launcher_id, old)| {
+                let new = new_metadata.swap_remove(&launcher_id)?;
+                (old != new).then_some((launcher_id, old, new))
+            })
+            .collect()
+    }
+}
+
+fn run_conditions(
+    allocator: &mut Allocator,
+    puzzle_reveal: &Program,
+    solution: &Program,
+) -> Result<Conditions, WalletError> {
+    let puzzle = puzzle_reveal.to_clvm(allocator)?;
+    let solution = solution.to_clvm(allocator)?;
+    let output = run_puzzle(allocator, puzzle, solution)?;
+    let conditions = Conditions::<NodePtr>::from_clvm(allocator, output)?;
 
     Ok(conditions)
 }
+
+/// The memos which are atoms, including the hint if there is one. Structured memos
+/// such as the clawback memo are skipped.
+fn parse_memos(allocator: &Allocator, memos: Memos<NodePtr>) -> Vec<Bytes> {
+    let Memos::Some(memos) = memos else {
+        return Vec::new();
+    };
+
+    Vec::<NodePtr>::from_clvm(allocator, memos)
+        .unwrap_or_default()
+        .into_iter()
+        .filter_map(|memo| Bytes::from_clvm(allocator, memo).ok())
+        .collect()
+}
@@ -1,5 +1,11 @@
+mod cost;
 mod offchain_metadata;
+mod price;
+mod schedule;
 mod submit;
 
+pub use cost::*;
 pub use offchain_metadata::*;
+pub use price::*;
+pub use schedule::*;
 pub use submit::*;
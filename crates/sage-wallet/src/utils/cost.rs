@@ -0,0 +1,30 @@
+use chia::{
+    bls::Signature,
+    consensus::spendbundle_conditions::get_conditions_from_spendbundle,
+    protocol::{CoinSpend, SpendBundle},
+};
+use chia_wallet_sdk::types::MAINNET_CONSTANTS;
+use clvmr::Allocator;
+
+use crate::WalletError;
+
+/// The maximum cost of a transaction that will be accepted into the mempool, which is half
+/// of the maximum block cost.
+pub const MAX_TRANSACTION_COST: u64 = 5_500_000_000;
+
+/// Calculates the cost of the coin spends, as it would be charged by the mempool. The
+/// signature isn't validated, so this can be used before a transaction is signed.
+pub fn spend_cost(coin_spends: &[CoinSpend]) -> Result<u64, WalletError> {
+    let mut allocator = Allocator::new();
+
+    let conditions = get_conditions_from_spendbundle(
+        &mut allocator,
+        &SpendBundle::new(coin_spends.to_vec(), Signature::default()),
+        MAINNET_CONSTANTS.max_block_cost_clvm,
+        MAINNET_CONSTANTS.hard_fork_height,
+        &MAINNET_CONSTANTS,
+    )
+    .map_err(|error| WalletError::Validation(error.1))?;
+
+    Ok(conditions.cost)
+}
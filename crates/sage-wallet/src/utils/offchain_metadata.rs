@@ -1,7 +1,8 @@
 use chia::protocol::Bytes32;
 use chia_sha2::Sha256;
-use sage_assets::{Chip0007Metadata, Collection};
-use sage_database::CollectionRow;
+use sage_assets::{Chip0007Metadata, Collection, NftAttribute};
+use sage_database::{CollectionRow, NftTrait};
+use serde_json::Value;
 use tracing::debug;
 
 #[derive(Debug, Default, Clone)]
@@ -10,6 +11,7 @@ pub struct ComputedNftInfo {
     pub description: Option<String>,
     pub sensitive_content: bool,
     pub collection: Option<CollectionRow>,
+    pub traits: Vec<NftTrait>,
 }
 
 pub fn compute_nft_info(did_id: Option<Bytes32>, blob: &[u8]) -> ComputedNftInfo {
@@ -27,6 +29,13 @@ pub fn compute_nft_info(did_id: Option<Bytes32>, blob: &[u8]) -> ComputedNftInfo
     };
 
     let sensitive_content = json.is_sensitive();
+    let traits = json
+        .attributes
+        .as_deref()
+        .unwrap_or_default()
+        .iter()
+        .filter_map(nft_trait)
+        .collect();
 
     let collection = if let (
         Some(did_id),
@@ -67,9 +76,32 @@ pub fn compute_nft_info(did_id: Option<Bytes32>, blob: &[u8]) -> ComputedNftInfo
         description: Some(json.description.clone()),
         sensitive_content,
         collection,
+        traits,
     }
 }
 
+/// Attributes with a trait type and value which aren't strings, numbers, or booleans are skipped.
+fn nft_trait(attribute: &NftAttribute) -> Option<NftTrait> {
+    let trait_type = match &attribute.trait_type {
+        Value::String(trait_type) => trait_type.clone(),
+        Value::Number(trait_type) => trait_type.to_string(),
+        _ => return None,
+    };
+
+    let (value, numeric_value) = match &attribute.value {
+        Value::String(value) => (value.clone(), None),
+        Value::Number(value) => (value.to_string(), value.as_f64()),
+        Value::Bool(value) => (value.to_string(), None),
+        _ => return None,
+    };
+
+    Some(NftTrait {
+        trait_type,
+        value,
+        numeric_value,
+    })
+}
+
 fn calculate_collection_id(did_id: Bytes32, json_collection_id: &str) -> Bytes32 {
     let mut hasher = Sha256::new();
     hasher.update(hex::encode(did_id));
@@ -97,4 +129,42 @@ mod tests {
             ))
         );
     }
+
+    #[test]
+    fn test_compute_nft_traits() {
+        let blob = br#"{
+            "format": "CHIP-0007",
+            "name": "Test",
+            "description": "",
+            "attributes": [
+                { "trait_type": "Background", "value": "Blue" },
+                { "trait_type": "Level", "value": 5 },
+                { "trait_type": 2, "value": true },
+                { "trait_type": "Skipped", "value": null }
+            ]
+        }"#;
+
+        let info = compute_nft_info(None, blob);
+
+        assert_eq!(
+            info.traits,
+            [
+                NftTrait {
+                    trait_type: "Background".to_string(),
+                    value: "Blue".to_string(),
+                    numeric_value: None,
+                },
+                NftTrait {
+                    trait_type: "Level".to_string(),
+                    value: "5".to_string(),
+                    numeric_value: Some(5.0),
+                },
+                NftTrait {
+                    trait_type: "2".to_string(),
+                    value: "true".to_string(),
+                    numeric_value: None,
+                },
+            ]
+        );
+    }
 }
@@ -0,0 +1,299 @@
+use std::{cmp::Ordering, fmt, str::FromStr};
+
+use crate::WalletError;
+
+/// A non-negative decimal price, such as the amount of one asset per whole unit of another.
+/// It's kept as a number of units at a given scale, so that it's never rounded.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Price {
+    units: u128,
+    scale: u32,
+}
+
+impl Price {
+    /// The most decimal places a price can have.
+    pub const MAX_DECIMALS: u32 = 18;
+
+    pub fn from_units(units: u128, scale: u32) -> Self {
+        Self { units, scale }
+    }
+
+    /// The price of each whole unit of the base asset, given the amount of the quote asset that
+    /// an amount of it was traded for. It's rounded down to as many decimals as can be kept.
+    pub fn from_amounts(
+        base_amount: u64,
+        base_precision: u8,
+        quote_amount: u64,
+        quote_precision: u8,
+    ) -> Option<Self> {
+        if base_amount == 0 {
+            return None;
+        }
+
+        (0..=Self::MAX_DECIMALS).rev().find_map(|scale| {
+            let numerator_exponent = scale + u32::from(base_precision);
+            let denominator_exponent = u32::from(quote_precision);
+
+            let (numerator, denominator) = if numerator_exponent >= denominator_exponent {
+                (
+                    u128::from(quote_amount).checked_mul(
+                        10u128.checked_pow(numerator_exponent - denominator_exponent)?,
+                    )?,
+                    u128::from(base_amount),
+                )
+            } else {
+                (
+                    u128::from(quote_amount),
+                    u128::from(base_amount).checked_mul(
+                        10u128.checked_pow(denominator_exponent - numerator_exponent)?,
+                    )?,
+                )
+            };
+
+            Some(Self::from_units(numerator / denominator, scale))
+        })
+    }
+
+    pub fn units(self) -> u128 {
+        self.units
+    }
+
+    pub fn scale(self) -> u32 {
+        self.scale
+    }
+
+    pub fn is_zero(self) -> bool {
+        self.units == 0
+    }
+
+    /// Returns the number of units at a scale which is at least as large as this one.
+    pub fn units_at(self, scale: u32) -> Option<u128> {
+        10u128
+            .checked_pow(scale.checked_sub(self.scale)?)
+            .and_then(|factor| self.units.checked_mul(factor))
+    }
+
+    pub fn checked_add(self, other: Self) -> Option<Self> {
+        let scale = self.scale.max(other.scale);
+
+        Some(Self::from_units(
+            self.units_at(scale)?.checked_add(other.units_at(scale)?)?,
+            scale,
+        ))
+    }
+
+    pub fn checked_sub(self, other: Self) -> Option<Self> {
+        let scale = self.scale.max(other.scale);
+
+        Some(Self::from_units(
+            self.units_at(scale)?.checked_sub(other.units_at(scale)?)?,
+            scale,
+        ))
+    }
+
+    /// Returns the amount of the quote asset for an amount of the base asset at this price,
+    /// rounded down. The precisions are the number of decimal places of each asset.
+    pub fn quote_amount(
+        self,
+        base_amount: u64,
+        base_precision: u8,
+        quote_precision: u8,
+    ) -> Option<u64> {
+        let value = u128::from(base_amount).checked_mul(self.units)?;
+        let numerator_exponent = u32::from(quote_precision);
+        let denominator_exponent = self.scale + u32::from(base_precision);
+
+        let value = if numerator_exponent >= denominator_exponent {
+            value.checked_mul(10u128.checked_pow(numerator_exponent - denominator_exponent)?)?
+        } else {
+            value / 10u128.checked_pow(denominator_exponent - numerator_exponent)?
+        };
+
+        value.try_into().ok()
+    }
+
+    /// Returns the amount of the base asset that an amount of the quote asset is worth at this
+    /// price, rounded down.
+    pub fn base_amount(
+        self,
+        quote_amount: u64,
+        base_precision: u8,
+        quote_precision: u8,
+    ) -> Option<u64> {
+        if self.is_zero() {
+            return None;
+        }
+
+        let numerator_exponent = self.scale + u32::from(base_precision);
+        let denominator_exponent = u32::from(quote_precision);
+
+        let (numerator, denominator) = if numerator_exponent >= denominator_exponent {
+            (
+                u128::from(quote_amount)
+                    .checked_mul(10u128.checked_pow(numerator_exponent - denominator_exponent)?)?,
+                self.units,
+            )
+        } else {
+            (
+                u128::from(quote_amount),
+                self.units
+                    .checked_mul(10u128.checked_pow(denominator_exponent - numerator_exponent)?)?,
+            )
+        };
+
+        (numerator / denominator).try_into().ok()
+    }
+}
+
+impl PartialEq for Price {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Price {}
+
+impl PartialOrd for Price {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Price {
+    fn cmp(&self, other: &Self) -> Ordering {
+        let scale = self.scale.max(other.scale);
+
+        match (self.units_at(scale), other.units_at(scale)) {
+            (Some(a), Some(b)) => a.cmp(&b),
+            // Only a price which is too large to fit at the larger scale can overflow.
+            (None, Some(_)) => Ordering::Greater,
+            (Some(_), None) => Ordering::Less,
+            (None, None) => {
+                (self.units / 10u128.pow(self.scale)).cmp(&(other.units / 10u128.pow(other.scale)))
+            }
+        }
+    }
+}
+
+impl FromStr for Price {
+    type Err = WalletError;
+
+    fn from_str(source: &str) -> Result<Self, Self::Err> {
+        let invalid = || WalletError::InvalidPrice(source.to_string());
+
+        let trimmed = source.trim();
+        let (whole, fraction) = trimmed.split_once('.').unwrap_or((trimmed, ""));
+
+        if (whole.is_empty() && fraction.is_empty())
+            || !whole
+                .chars()
+                .chain(fraction.chars())
+                .all(|c| c.is_ascii_digit())
+            || fraction.len() > Self::MAX_DECIMALS as usize
+        {
+            return Err(invalid());
+        }
+
+        let units = format!("{whole}{fraction}")
+            .parse::<u128>()
+            .map_err(|_| invalid())?;
+
+        Ok(Self::from_units(units, fraction.len().try_into()?))
+    }
+}
+
+impl fmt::Display for Price {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let scale = self.scale as usize;
+        let digits = format!("{:0>width$}", self.units, width = scale + 1);
+        let (whole, fraction) = digits.split_at(digits.len() - scale);
+        let fraction = fraction.trim_end_matches('0');
+
+        if fraction.is_empty() {
+            write!(f, "{whole}")
+        } else {
+            write!(f, "{whole}.{fraction}")
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn price(source: &str) -> Price {
+        source.parse().expect("invalid price")
+    }
+
+    #[test]
+    fn test_parse_price() {
+        assert_eq!(price("1.25").units(), 125);
+        assert_eq!(price("1.25").scale(), 2);
+        assert_eq!(price(".5"), price("0.5"));
+        assert_eq!(price("3"), price("3.000"));
+        assert!("".parse::<Price>().is_err());
+        assert!(".".parse::<Price>().is_err());
+        assert!("-1".parse::<Price>().is_err());
+        assert!("1.2.3".parse::<Price>().is_err());
+        assert!("0.0000000000000000001".parse::<Price>().is_err());
+    }
+
+    #[test]
+    fn test_format_price() {
+        assert_eq!(price("1.25").to_string(), "1.25");
+        assert_eq!(Price::from_units(5, 3).to_string(), "0.005");
+        assert_eq!(Price::from_units(1500, 3).to_string(), "1.5");
+        assert_eq!(Price::from_units(0, 2).to_string(), "0");
+    }
+
+    #[test]
+    fn test_price_arithmetic() {
+        assert_eq!(price("1.5").checked_add(price("0.25")), Some(price("1.75")));
+        assert_eq!(price("1.5").checked_sub(price("0.25")), Some(price("1.25")));
+        assert_eq!(price("0.25").checked_sub(price("1.5")), None);
+        assert!(price("0.3") > price("0.25"));
+        assert!(price("2") < price("10.5"));
+    }
+
+    #[test]
+    fn test_price_amounts() {
+        // 1 CAT at 0.5 XCH each is worth 0.5 XCH.
+        assert_eq!(
+            price("0.5").quote_amount(1000, 3, 12),
+            Some(500_000_000_000)
+        );
+
+        // 1 XCH at 200 CAT each is worth 200 CAT.
+        assert_eq!(
+            price("200").quote_amount(1_000_000_000_000, 12, 3),
+            Some(200_000)
+        );
+
+        // 0.5 XCH buys 1 CAT at 0.5 XCH each.
+        assert_eq!(price("0.5").base_amount(500_000_000_000, 3, 12), Some(1000));
+
+        // Amounts are rounded down.
+        assert_eq!(price("3").base_amount(1000, 3, 3), Some(333));
+        assert_eq!(price("0").base_amount(1000, 3, 3), None);
+    }
+
+    #[test]
+    fn test_price_from_amounts() {
+        // 2 CAT for 1 XCH is 0.5 XCH each.
+        assert_eq!(
+            Price::from_amounts(2000, 3, 1_000_000_000_000, 12),
+            Some(price("0.5"))
+        );
+
+        // 1 NFT for 250 CAT.
+        assert_eq!(Price::from_amounts(1, 0, 250_000, 3), Some(price("250")));
+
+        // Prices which don't divide evenly are rounded down.
+        assert_eq!(
+            Price::from_amounts(3000, 3, 1000, 3).map(|price| price.to_string()),
+            Some("0.333333333333333333".to_string())
+        );
+
+        assert_eq!(Price::from_amounts(0, 3, 1000, 3), None);
+    }
+}
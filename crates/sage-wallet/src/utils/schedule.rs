@@ -0,0 +1,278 @@
+use std::{fmt, str::FromStr};
+
+use crate::WalletError;
+
+const MINUTE: u64 = 60;
+const HOUR: u64 = 60 * MINUTE;
+const DAY: u64 = 24 * HOUR;
+
+/// How far ahead to search for the next matching time before giving up, in days.
+/// This covers every leap year cycle, so a schedule that never matches within it
+/// (such as February 30th) never will.
+const SEARCH_DAYS: u64 = 366 * 8;
+
+/// A five field cron expression (minute, hour, day of month, month, and day of week),
+/// evaluated in UTC. Each field supports `*`, values, ranges (`1-5`), lists (`1,15`),
+/// and steps (`*/15` or `0-30/10`). Sunday is both `0` and `7` in the day of week field.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Schedule {
+    source: String,
+    minutes: u64,
+    hours: u64,
+    days: u64,
+    months: u64,
+    weekdays: u64,
+    any_day: bool,
+    any_weekday: bool,
+}
+
+impl Schedule {
+    /// Returns the first matching UNIX timestamp that is strictly after the given one.
+    pub fn next_after(&self, timestamp: u64) -> Option<u64> {
+        let start = (timestamp / MINUTE + 1) * MINUTE;
+        let first_day = start / DAY;
+
+        for day in first_day..first_day + SEARCH_DAYS {
+            if !self.matches_day(day) {
+                continue;
+            }
+
+            let day_start = day * DAY;
+            let from = if day == first_day {
+                start - day_start
+            } else {
+                0
+            };
+
+            for hour in from / HOUR..24 {
+                if self.hours & (1 << hour) == 0 {
+                    continue;
+                }
+
+                for minute in 0..60 {
+                    let time = day_start + hour * HOUR + minute * MINUTE;
+
+                    if time >= start && self.minutes & (1 << minute) != 0 {
+                        return Some(time);
+                    }
+                }
+            }
+        }
+
+        None
+    }
+
+    fn matches_day(&self, day: u64) -> bool {
+        let (_year, month, day_of_month) = civil_from_days(day);
+        let weekday = (day + 4) % 7;
+
+        if self.months & (1 << month) == 0 {
+            return false;
+        }
+
+        let day_matches = self.days & (1 << day_of_month) != 0;
+        let weekday_matches = self.weekdays & (1 << weekday) != 0;
+
+        // Like cron, if both fields are restricted either one of them can match.
+        match (self.any_day, self.any_weekday) {
+            (true, true) => true,
+            (true, false) => weekday_matches,
+            (false, true) => day_matches,
+            (false, false) => day_matches || weekday_matches,
+        }
+    }
+}
+
+impl FromStr for Schedule {
+    type Err = WalletError;
+
+    fn from_str(source: &str) -> Result<Self, Self::Err> {
+        let invalid = || WalletError::InvalidSchedule(source.to_string());
+
+        let fields: Vec<&str> = source.split_whitespace().collect();
+
+        let [minutes, hours, days, months, weekdays] = fields.as_slice() else {
+            return Err(invalid());
+        };
+
+        let mut weekdays_mask = parse_field(weekdays, 0, 7).ok_or_else(invalid)?;
+
+        if weekdays_mask & (1 << 7) != 0 {
+            weekdays_mask = (weekdays_mask | 1) & !(1 << 7);
+        }
+
+        Ok(Self {
+            source: fields.join(" "),
+            minutes: parse_field(minutes, 0, 59).ok_or_else(invalid)?,
+            hours: parse_field(hours, 0, 23).ok_or_else(invalid)?,
+            days: parse_field(days, 1, 31).ok_or_else(invalid)?,
+            months: parse_field(months, 1, 12).ok_or_else(invalid)?,
+            weekdays: weekdays_mask,
+            any_day: *days == "*",
+            any_weekday: *weekdays == "*",
+        })
+    }
+}
+
+impl fmt::Display for Schedule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.source)
+    }
+}
+
+fn parse_field(field: &str, min: u64, max: u64) -> Option<u64> {
+    let mut mask = 0;
+
+    for part in field.split(',') {
+        let (range, step) = match part.split_once('/') {
+            Some((range, step)) => (range, step.parse::<u64>().ok()?),
+            None => (part, 1),
+        };
+
+        if step == 0 {
+            return None;
+        }
+
+        let (start, end) = if range == "*" {
+            (min, max)
+        } else if let Some((start, end)) = range.split_once('-') {
+            (start.parse().ok()?, end.parse().ok()?)
+        } else {
+            let value = range.parse().ok()?;
+            // A single value with a step runs from that value to the end of the range.
+            (value, if part.contains('/') { max } else { value })
+        };
+
+        if start < min || end > max || start > end {
+            return None;
+        }
+
+        for value in (start..=end).step_by(step.try_into().ok()?) {
+            mask |= 1 << value;
+        }
+    }
+
+    Some(mask)
+}
+
+/// Converts a number of days since the UNIX epoch into a (year, month, day) date.
+fn civil_from_days(days: u64) -> (u64, u64, u64) {
+    let z = days + 719_468;
+    let era = z / 146_097;
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + u64::from(month <= 2);
+    (year, month, day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 2024-01-01 00:00:00 UTC, a Monday.
+    const NEW_YEAR: u64 = 1_704_067_200;
+
+    fn schedule(source: &str) -> Schedule {
+        source.parse().expect("invalid schedule")
+    }
+
+    #[test]
+    fn test_civil_from_days() {
+        assert_eq!(civil_from_days(0), (1970, 1, 1));
+        assert_eq!(civil_from_days(NEW_YEAR / DAY), (2024, 1, 1));
+        assert_eq!(civil_from_days(NEW_YEAR / DAY + 59), (2024, 2, 29));
+    }
+
+    #[test]
+    fn test_every_minute() {
+        let schedule = schedule("* * * * *");
+        assert_eq!(schedule.next_after(NEW_YEAR), Some(NEW_YEAR + MINUTE));
+        assert_eq!(schedule.next_after(NEW_YEAR + 30), Some(NEW_YEAR + MINUTE));
+    }
+
+    #[test]
+    fn test_daily() {
+        let schedule = schedule("30 9 * * *");
+        assert_eq!(
+            schedule.next_after(NEW_YEAR),
+            Some(NEW_YEAR + 9 * HOUR + 30 * MINUTE)
+        );
+        assert_eq!(
+            schedule.next_after(NEW_YEAR + 10 * HOUR),
+            Some(NEW_YEAR + DAY + 9 * HOUR + 30 * MINUTE)
+        );
+    }
+
+    #[test]
+    fn test_steps_and_lists() {
+        let schedule = schedule("*/15 0,12 * * *");
+        assert_eq!(schedule.next_after(NEW_YEAR), Some(NEW_YEAR + 15 * MINUTE));
+        assert_eq!(
+            schedule.next_after(NEW_YEAR + 45 * MINUTE),
+            Some(NEW_YEAR + 12 * HOUR)
+        );
+    }
+
+    #[test]
+    fn test_monthly() {
+        let schedule = schedule("0 0 1 * *");
+        assert_eq!(schedule.next_after(NEW_YEAR), Some(NEW_YEAR + 31 * DAY));
+    }
+
+    #[test]
+    fn test_weekdays() {
+        // Fridays, where 2024-01-05 is the first.
+        assert_eq!(
+            schedule("0 0 * * 5").next_after(NEW_YEAR),
+            Some(NEW_YEAR + 4 * DAY)
+        );
+
+        // Sundays can be written as either 0 or 7.
+        assert_eq!(
+            schedule("0 0 * * 7").next_after(NEW_YEAR),
+            Some(NEW_YEAR + 6 * DAY)
+        );
+        assert_eq!(
+            schedule("0 0 * * 0").next_after(NEW_YEAR),
+            Some(NEW_YEAR + 6 * DAY)
+        );
+
+        // When both the day and weekday are restricted, either can match.
+        assert_eq!(
+            schedule("0 0 15 * 5").next_after(NEW_YEAR),
+            Some(NEW_YEAR + 4 * DAY)
+        );
+    }
+
+    #[test]
+    fn test_leap_day() {
+        assert_eq!(
+            schedule("0 0 29 2 *").next_after(NEW_YEAR + 60 * DAY),
+            Some(1_835_395_200)
+        );
+        assert_eq!(schedule("0 0 30 2 *").next_after(NEW_YEAR), None);
+    }
+
+    #[test]
+    fn test_invalid() {
+        for source in [
+            "",
+            "* * * *",
+            "* * * * * *",
+            "60 * * * *",
+            "* 24 * * *",
+            "* * 0 * *",
+            "* * * 13 *",
+            "* * * * 8",
+            "*/0 * * * *",
+            "5-1 * * * *",
+            "a * * * *",
+        ] {
+            assert!(source.parse::<Schedule>().is_err(), "{source}");
+        }
+    }
+}
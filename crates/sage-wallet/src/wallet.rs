@@ -22,10 +22,12 @@ use chia_wallet_sdk::{
 };
 use indexmap::IndexMap;
 use sage_database::{AssetKind, CoinKind, Database, DeserializePrimitive, P2Puzzle};
+use tokio::sync::Mutex;
 
 mod cats;
 mod coin_management;
 mod derivations;
+mod did_recovery;
 mod dids;
 mod memos;
 mod multi_send;
@@ -33,12 +35,16 @@ mod nfts;
 mod offer;
 mod options;
 mod signing;
+mod spending_policy;
 mod xch;
 
+pub use did_recovery::*;
+pub use dids::*;
 pub use multi_send::*;
 pub use nfts::*;
 pub use offer::*;
 pub use options::*;
+pub use spending_policy::*;
 
 use crate::WalletError;
 
@@ -50,6 +56,7 @@ pub struct Wallet {
     pub genesis_challenge: Bytes32,
     pub agg_sig_constants: AggSigConstants,
     pub change_p2_puzzle_hash: Option<Bytes32>,
+    policy_lock: Mutex<()>,
 }
 
 impl Wallet {
@@ -68,6 +75,7 @@ impl Wallet {
             genesis_challenge,
             agg_sig_constants,
             change_p2_puzzle_hash,
+            policy_lock: Mutex::new(()),
         }
     }
 
@@ -215,7 +223,16 @@ impl Wallet {
             let delta = deltas.get(&id).copied().unwrap_or_default();
             let required_amount = delta.output.saturating_sub(delta.input);
 
-            if required_amount == 0 && !deltas.is_needed(&id) {
+            // A fungible asset which is needed but already covered doesn't need another coin.
+            let is_selected = match id {
+                Id::Xch => !spends.xch.items.is_empty(),
+                _ => spends
+                    .cats
+                    .get(&id)
+                    .is_some_and(|cat| !cat.items.is_empty()),
+            };
+
+            if required_amount == 0 && (!deltas.is_needed(&id) || is_selected) {
                 continue;
             }
 
@@ -0,0 +1,437 @@
+use std::collections::HashSet;
+
+use chia::{
+    bls::{PublicKey, Signature},
+    clvm_traits::{clvm_list, clvm_quote, FromClvm},
+    protocol::{Bytes32, Coin, CoinSpend, SpendBundle},
+    puzzles::{singleton::SingletonSolution, standard::StandardArgs, CoinProof, Memos},
+};
+use chia_wallet_sdk::{
+    driver::{
+        Action, Did, DidInfo, Id, Layer, Puzzle, SingletonInfo, Spend, SpendContext, SpendKind,
+        StandardLayer,
+    },
+    prelude::{AssertConcurrentSpend, Condition, Conditions},
+};
+use clvmr::{Allocator, NodePtr};
+
+use crate::{WalletError, WalletPeer};
+
+use super::{dids::fetch_latest_did, recovery_list_hash, Wallet};
+
+/// The current state of a DID which is relevant for recovering it to a new public key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DidRecoveryInfo {
+    pub coin_id: Bytes32,
+    pub recovery_list_hash: Option<Bytes32>,
+    pub num_verifications_required: u64,
+    pub new_inner_puzzle_hash: Bytes32,
+}
+
+impl Wallet {
+    /// Looks up the latest coin of a DID, and the inner puzzle hash it would have once it has
+    /// been recovered to the given public key. Recovery DIDs attest to both of these.
+    pub async fn did_recovery_info(
+        &self,
+        peer: &WalletPeer,
+        did_id: Bytes32,
+        public_key: PublicKey,
+    ) -> Result<Option<DidRecoveryInfo>, WalletError> {
+        let mut allocator = Allocator::new();
+
+        let Some((did, _p2_puzzle)) = fetch_latest_did(&mut allocator, peer, did_id).await? else {
+            return Ok(None);
+        };
+
+        Ok(Some(DidRecoveryInfo {
+            coin_id: did.coin.coin_id(),
+            recovery_list_hash: did.info.recovery_list_hash,
+            num_verifications_required: did.info.num_verifications_required,
+            new_inner_puzzle_hash: recovered_info(did.info, public_key)
+                .inner_puzzle_hash()
+                .into(),
+        }))
+    }
+
+    /// Spends one of the wallet's DIDs to create a message coin, which approves the recovery of
+    /// another DID's coin to a new inner puzzle hash and public key.
+    pub async fn create_did_attestation(
+        &self,
+        did_id: Bytes32,
+        recovering_coin_id: Bytes32,
+        new_inner_puzzle_hash: Bytes32,
+        public_key: PublicKey,
+    ) -> Result<Vec<CoinSpend>, WalletError> {
+        let mut ctx = SpendContext::new();
+
+        let (_message_puzzle, message_puzzle_hash) = attestation_puzzle(
+            &mut ctx,
+            recovering_coin_id,
+            new_inner_puzzle_hash,
+            public_key,
+        )?;
+
+        let actions = [Action::update_did(Id::Existing(did_id), None, None, None)];
+
+        let mut spends = self.prepare_spends(&mut ctx, vec![], &actions).await?;
+        let deltas = spends.apply(&mut ctx, &actions)?;
+
+        let did_spend = spends
+            .dids
+            .get_mut(&Id::Existing(did_id))
+            .ok_or(WalletError::MissingDid(did_id))?
+            .last_mut()?;
+
+        let SpendKind::Conditions(spend) = &mut did_spend.kind else {
+            return Err(WalletError::MissingDid(did_id));
+        };
+
+        spend.add_conditions(Conditions::new().create_coin(message_puzzle_hash, 0, Memos::None));
+
+        self.complete_spends(&mut ctx, &deltas, spends).await?;
+
+        Ok(ctx.take())
+    }
+
+    /// Recovers a DID to a new public key, with an attestation from each DID in its recovery
+    /// list. The attestations are included in the spend bundle, which still has to be signed by
+    /// the new public key and the wallet's fee coins.
+    ///
+    /// The recovered DID is immediately spent again, so that it's hinted to the new owner.
+    pub async fn recover_did(
+        &self,
+        peer: &WalletPeer,
+        did_id: Bytes32,
+        recovery_list: &[Bytes32],
+        attestations: Vec<SpendBundle>,
+        public_key: PublicKey,
+        fee: u64,
+    ) -> Result<SpendBundle, WalletError> {
+        let mut ctx = SpendContext::new();
+
+        let Some((did, p2_puzzle)) = fetch_latest_did(&mut ctx, peer, did_id).await? else {
+            return Err(WalletError::MissingDid(did_id));
+        };
+
+        let Some(list_hash) = did.info.recovery_list_hash else {
+            return Err(WalletError::RecoveryDisabled(did_id));
+        };
+
+        if did.info.num_verifications_required == 0 || recovery_list.is_empty() {
+            return Err(WalletError::RecoveryDisabled(did_id));
+        }
+
+        if recovery_list_hash(recovery_list) != list_hash {
+            return Err(WalletError::RecoveryListMismatch);
+        }
+
+        let Some(p2_puzzle) = p2_puzzle else {
+            return Err(WalletError::UnknownDidPuzzle(did_id));
+        };
+
+        let coin_id = did.coin.coin_id();
+        let recovered_info = recovered_info(did.info, public_key);
+        let new_inner_puzzle_hash: Bytes32 = recovered_info.inner_puzzle_hash().into();
+
+        let (message_puzzle, message_puzzle_hash) =
+            attestation_puzzle(&mut ctx, coin_id, new_inner_puzzle_hash, public_key)?;
+
+        let mut coin_spends = Vec::new();
+        let mut aggregated_signature = Signature::default();
+        let mut recovery_coins = Vec::new();
+        let mut attesters = HashSet::new();
+
+        for attestation in attestations {
+            let (attester_id, attester_coin) =
+                parse_attestation(&mut ctx, &attestation.coin_spends, message_puzzle_hash)?;
+
+            if !recovery_list.contains(&attester_id) {
+                return Err(WalletError::InvalidAttestation(format!(
+                    "DID {attester_id} isn't in the recovery list"
+                )));
+            }
+
+            if !attesters.insert(attester_id) {
+                return Err(WalletError::InvalidAttestation(format!(
+                    "DID {attester_id} attested more than once"
+                )));
+            }
+
+            recovery_coins.push((attester_id, attester_coin));
+
+            ctx.spend(
+                Coin::new(attester_coin.coin_id(), message_puzzle_hash, 0),
+                Spend::new(message_puzzle, NodePtr::NIL),
+            )?;
+
+            coin_spends.extend(attestation.coin_spends);
+            aggregated_signature += &attestation.aggregated_signature;
+        }
+
+        // The DID puzzle checks the recovery coins against the recovery list in order, and doesn't
+        // skip over DIDs which haven't attested correctly. So every DID in the list must attest.
+        let recovery_coins = recovery_list
+            .iter()
+            .map(|&recovery_id| {
+                let (_, attester_coin) = recovery_coins
+                    .iter()
+                    .find(|(attester_id, _)| *attester_id == recovery_id)
+                    .ok_or(WalletError::MissingAttestation(recovery_id))?;
+
+                Ok(CoinProof {
+                    parent_coin_info: attester_coin.coin.parent_coin_info,
+                    inner_puzzle_hash: attester_coin.inner_puzzle_hash,
+                    amount: attester_coin.coin.amount,
+                })
+            })
+            .collect::<Result<Vec<_>, WalletError>>()?;
+
+        let puzzle = did
+            .info
+            .into_layers(p2_puzzle.ptr())
+            .construct_puzzle(&mut ctx)?;
+
+        let inner_solution = ctx.alloc(&clvm_list!(
+            0,
+            did.coin.amount,
+            new_inner_puzzle_hash,
+            recovery_coins,
+            public_key,
+            recovery_list.to_vec(),
+            coin_id
+        ))?;
+
+        let solution = ctx.alloc(&SingletonSolution {
+            lineage_proof: did.proof,
+            amount: did.coin.amount,
+            inner_solution,
+        })?;
+
+        ctx.spend(did.coin, Spend::new(puzzle, solution))?;
+
+        let _recovered_did = did.child_with(recovered_info, did.coin.amount).update(
+            &mut ctx,
+            &StandardLayer::new(public_key),
+            Conditions::new(),
+        )?;
+
+        if fee > 0 {
+            let actions = [Action::fee(fee)];
+
+            let mut spends = self.prepare_spends(&mut ctx, vec![], &actions).await?;
+
+            spends
+                .conditions
+                .required
+                .push(AssertConcurrentSpend::new(coin_id));
+
+            let deltas = spends.apply(&mut ctx, &actions)?;
+            self.complete_spends(&mut ctx, &deltas, spends).await?;
+        }
+
+        coin_spends.extend(ctx.take());
+
+        Ok(SpendBundle::new(coin_spends, aggregated_signature))
+    }
+}
+
+/// A DID coin which created the message coin of an attestation.
+#[derive(Debug, Clone, Copy)]
+struct AttesterCoin {
+    coin: Coin,
+    inner_puzzle_hash: Bytes32,
+}
+
+impl AttesterCoin {
+    fn coin_id(&self) -> Bytes32 {
+        self.coin.coin_id()
+    }
+}
+
+/// The DID info once it has been recovered, which is the same apart from the p2 puzzle.
+fn recovered_info(info: DidInfo, public_key: PublicKey) -> DidInfo {
+    DidInfo {
+        p2_puzzle_hash: StandardArgs::curry_tree_hash(public_key).into(),
+        ..info
+    }
+}
+
+/// Each DID which attests to a recovery creates a coin with this puzzle. When it's spent, it
+/// announces the coin being recovered and requires a signature of the new inner puzzle hash.
+fn attestation_puzzle(
+    ctx: &mut SpendContext,
+    recovering_coin_id: Bytes32,
+    new_inner_puzzle_hash: Bytes32,
+    public_key: PublicKey,
+) -> Result<(NodePtr, Bytes32), WalletError> {
+    let puzzle = ctx.alloc(&clvm_quote!(Conditions::new()
+        .create_coin_announcement(recovering_coin_id.to_vec().into())
+        .agg_sig_unsafe(public_key, new_inner_puzzle_hash.to_vec().into())))?;
+
+    let puzzle_hash = ctx.tree_hash(puzzle).into();
+
+    Ok((puzzle, puzzle_hash))
+}
+
+/// Finds the DID spend in an attestation which creates the expected message coin.
+fn parse_attestation(
+    ctx: &mut SpendContext,
+    coin_spends: &[CoinSpend],
+    message_puzzle_hash: Bytes32,
+) -> Result<(Bytes32, AttesterCoin), WalletError> {
+    for coin_spend in coin_spends {
+        let puzzle = ctx.alloc(&coin_spend.puzzle_reveal)?;
+        let puzzle = Puzzle::parse(ctx, puzzle);
+        let solution = ctx.alloc(&coin_spend.solution)?;
+
+        let Some((did, Some((p2_puzzle, p2_solution)))) =
+            Did::parse(ctx, coin_spend.coin, puzzle, solution)?
+        else {
+            continue;
+        };
+
+        let output = ctx.run(p2_puzzle.ptr(), p2_solution)?;
+        let conditions = Vec::<Condition>::from_clvm(ctx, output)?;
+
+        let creates_message = conditions.into_iter().any(|condition| {
+            condition.into_create_coin().is_some_and(|create_coin| {
+                create_coin.puzzle_hash == message_puzzle_hash && create_coin.amount == 0
+            })
+        });
+
+        if creates_message {
+            return Ok((
+                did.info.launcher_id,
+                AttesterCoin {
+                    coin: did.coin,
+                    inner_puzzle_hash: did.info.inner_puzzle_hash().into(),
+                },
+            ));
+        }
+    }
+
+    Err(WalletError::InvalidAttestation(
+        "No DID spend approves this recovery".to_string(),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use chia::protocol::SpendBundle;
+
+    use crate::{TestWallet, WalletPeer};
+
+    use super::*;
+
+    use test_log::test;
+
+    #[test(tokio::test)]
+    async fn test_recover_did() -> anyhow::Result<()> {
+        let mut alice = TestWallet::new(1).await?;
+        let mut bob = alice.next(2).await?;
+        let mut carol = bob.next(0).await?;
+
+        let (coin_spends, did) = alice.wallet.create_did(0).await?;
+        alice.transact(coin_spends).await?;
+        alice.wait_for_coins().await;
+
+        let did_id = did.info.launcher_id;
+
+        let mut recovery_list = Vec::new();
+
+        for _ in 0..2 {
+            let (coin_spends, did) = bob.wallet.create_did(0).await?;
+            bob.transact(coin_spends).await?;
+            bob.wait_for_coins().await;
+            recovery_list.push(did.info.launcher_id);
+        }
+
+        let coin_spends = alice
+            .wallet
+            .update_did(did_id, None, Some((recovery_list.clone(), 2)), 0)
+            .await?;
+        alice.transact(coin_spends).await?;
+        alice.wait_for_coins().await;
+
+        let public_key = carol
+            .wallet
+            .db
+            .public_key(carol.puzzle_hash)
+            .await?
+            .expect("missing public key");
+
+        let peer = WalletPeer::new(carol.peer.clone());
+
+        let info = carol
+            .wallet
+            .did_recovery_info(&peer, did_id, public_key)
+            .await?
+            .expect("missing DID");
+
+        assert_eq!(
+            info.recovery_list_hash,
+            Some(recovery_list_hash(&recovery_list))
+        );
+        assert_eq!(info.num_verifications_required, 2);
+
+        let mut attestations = Vec::new();
+
+        for &attester_id in &recovery_list {
+            let coin_spends = bob
+                .wallet
+                .create_did_attestation(
+                    attester_id,
+                    info.coin_id,
+                    info.new_inner_puzzle_hash,
+                    public_key,
+                )
+                .await?;
+
+            attestations.push(
+                bob.wallet
+                    .sign_transaction(
+                        SpendBundle::new(coin_spends, Signature::default()),
+                        &bob.agg_sig,
+                        bob.master_sk.clone(),
+                        false,
+                    )
+                    .await?,
+            );
+        }
+
+        let missing = carol
+            .wallet
+            .recover_did(
+                &peer,
+                did_id,
+                &recovery_list,
+                attestations[..1].to_vec(),
+                public_key,
+                0,
+            )
+            .await;
+
+        assert!(matches!(
+            missing,
+            Err(WalletError::MissingAttestation(id)) if id == recovery_list[1]
+        ));
+
+        let spend_bundle = carol
+            .wallet
+            .recover_did(&peer, did_id, &recovery_list, attestations, public_key, 0)
+            .await?;
+
+        let spend_bundle = carol
+            .wallet
+            .sign_transaction(spend_bundle, &carol.agg_sig, carol.master_sk.clone(), true)
+            .await?;
+
+        carol.push_bundle(spend_bundle).await?;
+        carol.wait_for_coins().await;
+
+        let did = carol.wallet.db.did(did_id).await?.expect("missing DID");
+        assert_eq!(did.info.p2_puzzle_hash, carol.puzzle_hash);
+
+        Ok(())
+    }
+}
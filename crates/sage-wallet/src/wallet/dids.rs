@@ -1,13 +1,18 @@
+use std::time::Duration;
+
 use chia::{
+    clvm_traits::ToClvm,
     clvm_utils::{tree_hash_atom, ToTreeHash},
-    protocol::{Bytes32, CoinSpend},
+    protocol::{Bytes32, CoinSpend, Program},
 };
-use chia_wallet_sdk::driver::{Action, ClawbackV2, Id, SpendContext};
+use chia_wallet_sdk::driver::{Action, ClawbackV2, Did, Id, Puzzle, SpendContext};
+use clvmr::Allocator;
 use sage_database::{SerializePrimitive, SerializedDid};
+use tokio::time::sleep;
 
 use crate::{
     wallet::memos::{calculate_memos, Hint},
-    WalletError,
+    WalletError, WalletPeer,
 };
 
 use super::Wallet;
@@ -107,13 +112,140 @@ impl Wallet {
 
         Ok(ctx.take())
     }
+
+    /// Updates the metadata of a DID on-chain, and optionally replaces its recovery list and the
+    /// number of verifications required to recover it.
+    pub async fn update_did(
+        &self,
+        did_id: Bytes32,
+        metadata: Option<Program>,
+        recovery: Option<(Vec<Bytes32>, u64)>,
+        fee: u64,
+    ) -> Result<Vec<CoinSpend>, WalletError> {
+        let mut ctx = SpendContext::new();
+
+        let metadata = metadata
+            .map(|metadata| ctx.alloc_hashed(&metadata))
+            .transpose()?;
+
+        let (recovery_list_hash, num_verifications_required) = match recovery {
+            Some((recovery_list, num_verifications_required)) => (
+                Some(Some(recovery_list_hash(&recovery_list))),
+                Some(num_verifications_required),
+            ),
+            None => (None, None),
+        };
+
+        self.spend(
+            &mut ctx,
+            vec![],
+            &[
+                Action::fee(fee),
+                Action::update_did(
+                    Id::Existing(did_id),
+                    recovery_list_hash,
+                    num_verifications_required,
+                    metadata,
+                ),
+            ],
+        )
+        .await?;
+
+        Ok(ctx.take())
+    }
+
+    /// Looks up the latest state of a DID which may not belong to the wallet.
+    pub async fn fetch_did(
+        &self,
+        peer: &WalletPeer,
+        launcher_id: Bytes32,
+    ) -> Result<Option<SerializedDid>, WalletError> {
+        let mut allocator = Allocator::new();
+
+        let Some((did, _p2_puzzle)) = fetch_latest_did(&mut allocator, peer, launcher_id).await?
+        else {
+            return Ok(None);
+        };
+
+        Ok(Some(did.serialize(&allocator)?))
+    }
+}
+
+/// Follows the lineage of a DID to its latest coin, and parses it from the parent spend. The p2
+/// puzzle is also returned if the parent spend revealed it, and it hasn't been changed since.
+pub(crate) async fn fetch_latest_did(
+    allocator: &mut Allocator,
+    peer: &WalletPeer,
+    launcher_id: Bytes32,
+) -> Result<Option<(Did, Option<Puzzle>)>, WalletError> {
+    let mut current_id = launcher_id;
+    let mut parent = None;
+
+    loop {
+        let Some(child) = peer.try_fetch_singleton_child(current_id).await? else {
+            return Ok(None);
+        };
+
+        if child.spent_height.is_some() {
+            parent = Some(child);
+            current_id = child.coin.coin_id();
+            sleep(Duration::from_secs(1)).await;
+            continue;
+        }
+
+        let Some(parent) = parent else {
+            return Ok(None);
+        };
+
+        let (parent_puzzle_reveal, parent_solution) = peer
+            .fetch_puzzle_solution(
+                parent.coin.coin_id(),
+                parent.spent_height.ok_or(WalletError::PeerMisbehaved)?,
+            )
+            .await?;
+
+        let parent_puzzle = parent_puzzle_reveal.to_clvm(allocator)?;
+        let parent_puzzle = Puzzle::parse(allocator, parent_puzzle);
+        let parent_solution = parent_solution.to_clvm(allocator)?;
+
+        let Some(did) = Did::parse_child(
+            allocator,
+            parent.coin,
+            parent_puzzle,
+            parent_solution,
+            child.coin,
+        )
+        .ok()
+        .flatten() else {
+            return Ok(None);
+        };
+
+        let p2_puzzle = Did::parse(allocator, parent.coin, parent_puzzle, parent_solution)
+            .ok()
+            .flatten()
+            .and_then(|(_parent_did, p2_spend)| p2_spend)
+            .map(|(p2_puzzle, _p2_solution)| p2_puzzle)
+            .filter(|p2_puzzle| p2_puzzle.curried_puzzle_hash() == did.info.p2_puzzle_hash.into());
+
+        return Ok(Some((did, p2_puzzle)));
+    }
+}
+
+/// The recovery list hash is the tree hash of the list of DIDs which can recover a DID.
+pub fn recovery_list_hash(recovery_list: &[Bytes32]) -> Bytes32 {
+    recovery_list.tree_hash().into()
 }
 
 #[cfg(test)]
 mod tests {
     use std::time::Duration;
 
-    use crate::TestWallet;
+    use chia::{clvm_traits::FromClvm, protocol::Bytes32};
+    use clvmr::Allocator;
+
+    use crate::{TestWallet, WalletPeer};
+
+    use super::*;
 
     use test_log::test;
     use tokio::time::sleep;
@@ -149,6 +281,52 @@ mod tests {
         Ok(())
     }
 
+    #[test(tokio::test)]
+    async fn test_update_did() -> anyhow::Result<()> {
+        let mut test = TestWallet::new(1).await?;
+
+        let (coin_spends, did) = test.wallet.create_did(0).await?;
+        test.transact(coin_spends).await?;
+        test.wait_for_coins().await;
+
+        let did_id = did.info.launcher_id;
+        let recovery_list = vec![Bytes32::new([1; 32]), Bytes32::new([2; 32])];
+
+        let mut allocator = Allocator::new();
+        let metadata = vec![("name".to_string(), "Alice".to_string())].to_clvm(&mut allocator)?;
+        let metadata = Program::from_clvm(&allocator, metadata)?;
+
+        let coin_spends = test
+            .wallet
+            .update_did(
+                did_id,
+                Some(metadata.clone()),
+                Some((recovery_list.clone(), 2)),
+                0,
+            )
+            .await?;
+        test.transact(coin_spends).await?;
+        test.wait_for_coins().await;
+
+        let did = test.wallet.db.did(did_id).await?.expect("missing DID");
+        assert_eq!(did.info.metadata, metadata);
+        assert_eq!(
+            did.info.recovery_list_hash,
+            Some(recovery_list_hash(&recovery_list))
+        );
+        assert_eq!(did.info.num_verifications_required, 2);
+
+        let fetched = test
+            .wallet
+            .fetch_did(&WalletPeer::new(test.peer.clone()), did_id)
+            .await?
+            .expect("could not fetch DID");
+        assert_eq!(fetched.coin, did.coin);
+        assert_eq!(fetched.info, did.info);
+
+        Ok(())
+    }
+
     #[test(tokio::test)]
     async fn test_transfer_did_external() -> anyhow::Result<()> {
         let mut alice = TestWallet::new(1000).await?;
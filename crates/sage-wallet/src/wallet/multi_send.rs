@@ -1,7 +1,7 @@
 use chia::protocol::{Bytes, Bytes32, CoinSpend};
 use chia_wallet_sdk::driver::{Action, Id, SpendContext};
 
-use crate::{wallet::memos::Hint, WalletError};
+use crate::{spend_cost, wallet::memos::Hint, WalletError};
 
 use super::{memos::calculate_memos, Wallet};
 
@@ -73,6 +73,35 @@ impl Wallet {
 
         Ok(ctx.take())
     }
+
+    /// Sends as many of the payments as fit within the maximum cost, in order, and returns
+    /// the number of payments that were included along with the coin spends.
+    pub async fn multi_send_within_cost(
+        &self,
+        payments: &[MultiSendPayment],
+        fee: u64,
+        max_cost: u64,
+    ) -> Result<(Vec<CoinSpend>, usize), WalletError> {
+        let mut count = payments.len();
+
+        loop {
+            let coin_spends = self.multi_send(payments[..count].to_vec(), fee).await?;
+            let cost = spend_cost(&coin_spends)?;
+
+            if cost <= max_cost {
+                return Ok((coin_spends, count));
+            }
+
+            if count <= 1 {
+                return Err(WalletError::CostExceeded(max_cost));
+            }
+
+            // The cost is roughly proportional to the number of payments, so this converges
+            // quickly, but it always has to make progress in case the estimate is too high.
+            let estimate = (count as u128 * u128::from(max_cost) / u128::from(cost)) as usize;
+            count = estimate.clamp(1, count - 1);
+        }
+    }
 }
 
 #[cfg(test)]
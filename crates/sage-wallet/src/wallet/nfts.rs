@@ -1,4 +1,5 @@
 use chia::{
+    clvm_traits::{FromClvm, ToClvm},
     clvm_utils::ToTreeHash,
     protocol::{Bytes32, CoinSpend},
     puzzles::nft::NftMetadata,
@@ -7,9 +8,11 @@ use chia_puzzles::NFT_METADATA_UPDATER_DEFAULT_HASH;
 use chia_wallet_sdk::driver::{
     Action, ClawbackV2, DriverError, Id, MetadataUpdate, SpendContext, TransferNftById,
 };
+use clvmr::{Allocator, NodePtr};
 use sage_database::{SerializePrimitive, SerializedNft};
 
 use crate::{
+    spend_cost,
     wallet::memos::{calculate_memos, Hint},
     WalletError,
 };
@@ -75,6 +78,36 @@ impl Wallet {
         ))
     }
 
+    /// Mints as many of the NFTs as fit within the maximum cost, in order, and returns the
+    /// number of NFTs that were included along with the coin spends and minted NFTs.
+    pub async fn bulk_mint_nfts_within_cost(
+        &self,
+        fee: u64,
+        did_id: Bytes32,
+        mints: &[WalletNftMint],
+        max_cost: u64,
+    ) -> Result<(Vec<CoinSpend>, Vec<SerializedNft>, usize), WalletError> {
+        let mut count = mints.len();
+
+        loop {
+            let (coin_spends, nfts) = self
+                .bulk_mint_nfts(fee, did_id, mints[..count].to_vec())
+                .await?;
+            let cost = spend_cost(&coin_spends)?;
+
+            if cost <= max_cost {
+                return Ok((coin_spends, nfts, count));
+            }
+
+            if count <= 1 {
+                return Err(WalletError::CostExceeded(max_cost));
+            }
+
+            let estimate = (count as u128 * u128::from(max_cost) / u128::from(cost)) as usize;
+            count = estimate.clamp(1, count - 1);
+        }
+    }
+
     pub async fn transfer_nfts(
         &self,
         nft_ids: Vec<Bytes32>,
@@ -143,20 +176,62 @@ impl Wallet {
         nft_id: Bytes32,
         fee: u64,
         uri: MetadataUpdate,
+    ) -> Result<Vec<CoinSpend>, WalletError> {
+        self.update_nft_metadata(vec![(nft_id, vec![uri])], fee)
+            .await
+    }
+
+    /// Updates the metadata of many NFTs in a single transaction. The default metadata updater
+    /// only allows URIs to be added, which end up at the start of each list in the order given.
+    pub async fn update_nft_metadata(
+        &self,
+        updates: Vec<(Bytes32, Vec<MetadataUpdate>)>,
+        fee: u64,
     ) -> Result<Vec<CoinSpend>, WalletError> {
         let mut ctx = SpendContext::new();
+        let mut actions = vec![Action::fee(fee)];
+
+        for (nft_id, updates) in updates {
+            let nft = self
+                .db
+                .nft(nft_id)
+                .await?
+                .ok_or(WalletError::MissingNft(nft_id))?;
 
-        let spend = uri.spend(&mut ctx)?;
+            if nft.info.metadata_updater_puzzle_hash != NFT_METADATA_UPDATER_DEFAULT_HASH.into() {
+                return Err(WalletError::UnsupportedMetadataUpdater(nft_id));
+            }
+
+            // The default metadata updater ignores URIs for lists which aren't in the metadata.
+            let mut allocator = Allocator::new();
+            let metadata = nft.info.metadata.to_clvm(&mut allocator)?;
+            let keys = Vec::<(String, NodePtr)>::from_clvm(&allocator, metadata)
+                .unwrap_or_default()
+                .into_iter()
+                .map(|(key, _value)| key)
+                .collect::<Vec<_>>();
+
+            for update in &updates {
+                let (key, name) = match update {
+                    MetadataUpdate::NewDataUri(_) => ("u", "data"),
+                    MetadataUpdate::NewMetadataUri(_) => ("mu", "metadata"),
+                    MetadataUpdate::NewLicenseUri(_) => ("lu", "license"),
+                };
+
+                if !keys.iter().any(|item| item == key) {
+                    return Err(WalletError::MissingNftUriList(nft_id, name));
+                }
+            }
+
+            let spends = updates
+                .into_iter()
+                .map(|update| update.spend(&mut ctx))
+                .collect::<Result<Vec<_>, DriverError>>()?;
 
-        self.spend(
-            &mut ctx,
-            vec![],
-            &[
-                Action::fee(fee),
-                Action::update_nft(Id::Existing(nft_id), vec![spend], None),
-            ],
-        )
-        .await?;
+            actions.push(Action::update_nft(Id::Existing(nft_id), spends, None));
+        }
+
+        self.spend(&mut ctx, vec![], &actions).await?;
 
         Ok(ctx.take())
     }
@@ -191,7 +266,7 @@ mod tests {
     use test_log::test;
     use tokio::time::sleep;
 
-    use crate::TestWallet;
+    use crate::{TestWallet, Transaction};
 
     use super::*;
 
@@ -209,7 +284,12 @@ mod tests {
                 0,
                 did.info.launcher_id,
                 vec![WalletNftMint {
-                    metadata: NftMetadata::default(),
+                    metadata: NftMetadata {
+                        data_uris: vec!["data".to_string()],
+                        metadata_uris: vec!["metadata".to_string()],
+                        license_uris: vec!["license".to_string()],
+                        ..Default::default()
+                    },
                     p2_puzzle_hash: None,
                     royalty_puzzle_hash: Some(Bytes32::default()),
                     royalty_basis_points: 300,
@@ -256,6 +336,99 @@ mod tests {
         Ok(())
     }
 
+    #[test(tokio::test)]
+    async fn test_update_nft_metadata() -> anyhow::Result<()> {
+        let mut test = TestWallet::new(3).await?;
+
+        let (coin_spends, did) = test.wallet.create_did(0).await?;
+        test.transact(coin_spends).await?;
+        test.wait_for_coins().await;
+
+        let mint = WalletNftMint {
+            metadata: NftMetadata {
+                data_uris: vec!["data".to_string()],
+                license_uris: vec!["license".to_string()],
+                ..Default::default()
+            },
+            p2_puzzle_hash: None,
+            royalty_puzzle_hash: Some(Bytes32::default()),
+            royalty_basis_points: 300,
+        };
+
+        let (coin_spends, nfts) = test
+            .wallet
+            .bulk_mint_nfts(0, did.info.launcher_id, vec![mint.clone(), mint])
+            .await?;
+        test.transact(coin_spends).await?;
+        test.wait_for_coins().await;
+
+        let first = nfts[0].info.launcher_id;
+        let second = nfts[1].info.launcher_id;
+
+        let coin_spends = test
+            .wallet
+            .update_nft_metadata(
+                vec![
+                    (
+                        first,
+                        vec![
+                            MetadataUpdate::NewDataUri("abc".to_string()),
+                            MetadataUpdate::NewDataUri("def".to_string()),
+                        ],
+                    ),
+                    (
+                        second,
+                        vec![MetadataUpdate::NewLicenseUri("xyz".to_string())],
+                    ),
+                ],
+                0,
+            )
+            .await?;
+
+        let updates = Transaction::from_coin_spends(coin_spends.clone())?.nft_metadata_updates();
+
+        assert_eq!(updates.len(), 2);
+
+        let (_, old, new) = updates
+            .iter()
+            .find(|(launcher_id, ..)| *launcher_id == first)
+            .expect("missing update");
+        assert_eq!(old.data_uris, vec!["data"]);
+        assert_eq!(new.data_uris, vec!["abc", "def", "data"]);
+
+        let (_, _, new) = updates
+            .iter()
+            .find(|(launcher_id, ..)| *launcher_id == second)
+            .expect("missing update");
+        assert_eq!(new.license_uris, vec!["xyz", "license"]);
+
+        assert!(matches!(
+            test.wallet
+                .update_nft_metadata(
+                    vec![(
+                        first,
+                        vec![MetadataUpdate::NewMetadataUri("xyz".to_string())]
+                    )],
+                    0,
+                )
+                .await,
+            Err(WalletError::MissingNftUriList(nft_id, "metadata")) if nft_id == first
+        ));
+
+        test.transact(coin_spends).await?;
+        test.wait_for_coins().await;
+
+        for (launcher_id, _old, new) in updates {
+            let nft = test.wallet.db.nft(launcher_id).await?.expect("missing nft");
+
+            let mut allocator = Allocator::new();
+            let metadata = nft.info.metadata.to_clvm(&mut allocator)?;
+            assert_eq!(NftMetadata::from_clvm(&allocator, metadata)?, new);
+        }
+
+        Ok(())
+    }
+
     #[test(tokio::test)]
     async fn test_transfer_nft_internal() -> anyhow::Result<()> {
         let mut test = TestWallet::new(2).await?;
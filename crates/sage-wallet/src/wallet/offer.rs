@@ -1,25 +1,43 @@
 mod aggregate_offer;
 mod cancel_offer;
 mod make_offer;
+mod make_offer_ladder;
 mod offer_assets;
+mod offer_royalties;
+mod preflight_offer;
 mod take_offer;
 
 pub use aggregate_offer::*;
 pub use make_offer::*;
+pub use make_offer_ladder::*;
+pub use offer_royalties::*;
+pub use preflight_offer::*;
 
 #[cfg(test)]
 mod tests {
+    use std::collections::HashSet;
+
     use chia::{
         clvm_traits::{FromClvm, ToClvm},
         protocol::{Bytes32, Program},
         puzzles::nft::NftMetadata,
     };
+    use chia_wallet_sdk::{
+        driver::{
+            calculate_nft_royalty, calculate_trade_price_amounts, calculate_trade_prices,
+            AssetInfo, CatAssetInfo, OfferAmounts,
+        },
+        types::{run_puzzle, Condition},
+    };
     use clvmr::Allocator;
     use indexmap::indexmap;
     use sage_database::NftOfferInfo;
     use test_log::test;
 
-    use crate::{Offered, Requested, RequestedCat, TestWallet, WalletNftMint};
+    use crate::{
+        LadderRung, NftRoyaltyPayment, OfferExpiry, OfferLadder, Offered, Requested, RequestedCat,
+        TestWallet, WalletError, WalletNftMint, WalletPeer, MAX_TRANSACTION_COST,
+    };
 
     use super::aggregate_offers;
 
@@ -46,7 +64,7 @@ mod tests {
                     cats: indexmap! { asset_id => RequestedCat { amount: 1000, hidden_puzzle_hash: None } },
                     ..Default::default()
                 },
-                None,
+                OfferExpiry::default(),
             )
             .await?;
         let offer = alice
@@ -73,6 +91,325 @@ mod tests {
         Ok(())
     }
 
+    #[test(tokio::test)]
+    async fn test_offer_expiry() -> anyhow::Result<()> {
+        let mut alice = TestWallet::new(1000).await?;
+        let mut bob = alice.next(1000).await?;
+
+        // Issue CAT
+        let (coin_spends, asset_id) = bob.wallet.issue_cat(1000, 0, None).await?;
+        bob.transact(coin_spends).await?;
+        bob.wait_for_coins().await;
+
+        // Create offer, which expires by both height and timestamp
+        let offer = alice
+            .wallet
+            .make_offer(
+                Offered {
+                    xch: 1000,
+                    ..Default::default()
+                },
+                Requested {
+                    cats: indexmap! { asset_id => RequestedCat { amount: 1000, hidden_puzzle_hash: None } },
+                    ..Default::default()
+                },
+                OfferExpiry {
+                    height: Some(1000),
+                    seconds: Some(4_000_000_000),
+                },
+            )
+            .await?;
+        let offer = alice
+            .wallet
+            .sign_transaction(offer, &alice.agg_sig, alice.master_sk.clone(), true)
+            .await?;
+
+        let mut allocator = Allocator::new();
+        let mut conditions = Vec::new();
+
+        for coin_spend in &offer.coin_spends {
+            if coin_spend.coin.parent_coin_info == Bytes32::default() {
+                continue;
+            }
+
+            let puzzle = coin_spend.puzzle_reveal.to_clvm(&mut allocator)?;
+            let solution = coin_spend.solution.to_clvm(&mut allocator)?;
+            let output = run_puzzle(&mut allocator, puzzle, solution)?;
+            conditions.extend(Vec::<Condition>::from_clvm(&allocator, output)?);
+        }
+
+        assert!(conditions.iter().any(|condition| matches!(
+            condition,
+            Condition::AssertBeforeHeightAbsolute(condition) if condition.height == 1000
+        )));
+        assert!(conditions.iter().any(|condition| matches!(
+            condition,
+            Condition::AssertBeforeSecondsAbsolute(condition) if condition.seconds == 4_000_000_000
+        )));
+
+        // It can still be taken before it expires
+        let offer = bob.wallet.take_offer(offer, 0).await?;
+        let spend_bundle = bob
+            .wallet
+            .sign_transaction(offer, &bob.agg_sig, bob.master_sk.clone(), true)
+            .await?;
+        bob.push_bundle(spend_bundle).await?;
+
+        bob.wait_for_coins().await;
+        alice.wait_for_puzzles().await;
+
+        assert_eq!(alice.wallet.db.cat_balance(asset_id).await?, 1000);
+        assert_eq!(bob.wallet.db.xch_balance().await?, 1000);
+
+        Ok(())
+    }
+
+    #[test(tokio::test)]
+    async fn test_offer_ladder_xch_for_cat() -> anyhow::Result<()> {
+        let mut alice = TestWallet::new(1000).await?;
+        let mut bob = alice.next(1000).await?;
+
+        // Issue CAT
+        let (coin_spends, asset_id) = bob.wallet.issue_cat(1000, 0, None).await?;
+        bob.transact(coin_spends).await?;
+        bob.wait_for_coins().await;
+
+        // Create the ladder, with two rungs of the same size
+        let ladder = alice
+            .wallet
+            .make_offer_ladder(
+                OfferLadder {
+                    offered_asset_id: None,
+                    requested_asset_id: Some(asset_id),
+                    requested_hidden_puzzle_hash: None,
+                    rungs: vec![
+                        LadderRung {
+                            offered_amount: 100,
+                            requested_amount: 10,
+                        },
+                        LadderRung {
+                            offered_amount: 100,
+                            requested_amount: 20,
+                        },
+                        LadderRung {
+                            offered_amount: 200,
+                            requested_amount: 60,
+                        },
+                    ],
+                    fee: 0,
+                    p2_puzzle_hash: None,
+                    expiry: OfferExpiry::default(),
+                },
+                vec![],
+            )
+            .await?;
+        assert_eq!(ladder.offers.len(), 3);
+
+        let mut offers = Vec::new();
+
+        for offer in ladder.offers {
+            offers.push(
+                alice
+                    .wallet
+                    .sign_transaction(offer, &alice.agg_sig, alice.master_sk.clone(), true)
+                    .await?,
+            );
+        }
+
+        // Each offer spends a different coin, apart from the requested payments
+        let coin_ids: HashSet<_> = offers
+            .iter()
+            .flat_map(|offer| &offer.coin_spends)
+            .filter(|coin_spend| coin_spend.coin.parent_coin_info != Bytes32::default())
+            .map(|coin_spend| coin_spend.coin.coin_id())
+            .collect();
+        assert_eq!(coin_ids.len(), 3);
+
+        alice.transact(ladder.split).await?;
+        alice.wait_for_coins().await;
+
+        // Take the middle rung
+        let offer = bob.wallet.take_offer(offers.remove(1), 0).await?;
+        let spend_bundle = bob
+            .wallet
+            .sign_transaction(offer, &bob.agg_sig, bob.master_sk.clone(), true)
+            .await?;
+        bob.push_bundle(spend_bundle).await?;
+
+        bob.wait_for_coins().await;
+        alice.wait_for_puzzles().await;
+
+        // Check balances
+        assert_eq!(alice.wallet.db.xch_balance().await?, 900);
+        assert_eq!(alice.wallet.db.cat_balance(asset_id).await?, 20);
+        assert_eq!(bob.wallet.db.xch_balance().await?, 100);
+
+        Ok(())
+    }
+
+    #[test(tokio::test)]
+    async fn test_preflight_offer() -> anyhow::Result<()> {
+        let mut alice = TestWallet::new(1000).await?;
+        let mut bob = alice.next(1000).await?;
+        let peer = WalletPeer::new(bob.peer.clone());
+
+        // Issue CAT
+        let (coin_spends, asset_id) = bob.wallet.issue_cat(1000, 0, None).await?;
+        bob.transact(coin_spends).await?;
+        bob.wait_for_coins().await;
+
+        // The offer spends a coin created by the split, which hasn't been submitted yet
+        let ladder = alice
+            .wallet
+            .make_offer_ladder(
+                OfferLadder {
+                    offered_asset_id: None,
+                    requested_asset_id: Some(asset_id),
+                    requested_hidden_puzzle_hash: None,
+                    rungs: vec![LadderRung {
+                        offered_amount: 100,
+                        requested_amount: 10,
+                    }],
+                    fee: 0,
+                    p2_puzzle_hash: None,
+                    expiry: OfferExpiry::default(),
+                },
+                vec![],
+            )
+            .await?;
+        let offer = alice
+            .wallet
+            .sign_transaction(
+                ladder.offers[0].clone(),
+                &alice.agg_sig,
+                alice.master_sk.clone(),
+                true,
+            )
+            .await?;
+
+        let preflight = bob.wallet.preflight_offer(&peer, offer.clone(), 0).await?;
+        assert_eq!(preflight.coins.len(), 1);
+        assert_eq!(preflight.coins[0].0.amount, 100);
+        assert!(preflight.coins[0].1.is_none());
+        assert!(!preflight.is_takeable(false));
+
+        alice.transact(ladder.split).await?;
+        alice.wait_for_coins().await;
+
+        let preflight = bob.wallet.preflight_offer(&peer, offer.clone(), 0).await?;
+        assert!(preflight.coins[0]
+            .1
+            .is_some_and(|coin_state| coin_state.spent_height.is_none()));
+        assert!(preflight
+            .cost
+            .is_some_and(|cost| cost > 0 && cost <= MAX_TRANSACTION_COST));
+        assert_eq!(preflight.error, None);
+        assert!(preflight.is_takeable(false));
+        assert!(!preflight.is_takeable(true));
+
+        // The fee is more than the offer and Bob's coins can pay, so the spend can't be built
+        let preflight = bob
+            .wallet
+            .preflight_offer(&peer, offer.clone(), 1000)
+            .await?;
+        assert_eq!(preflight.cost, None);
+        assert!(preflight.error.is_some());
+        assert!(!preflight.is_takeable(false));
+
+        let spend_bundle = bob.wallet.take_offer(offer.clone(), 0).await?;
+        let spend_bundle = bob
+            .wallet
+            .sign_transaction(spend_bundle, &bob.agg_sig, bob.master_sk.clone(), true)
+            .await?;
+        bob.push_bundle(spend_bundle).await?;
+        bob.wait_for_coins().await;
+
+        let preflight = bob.wallet.preflight_offer(&peer, offer, 0).await?;
+        assert!(preflight.coins[0]
+            .1
+            .is_some_and(|coin_state| coin_state.spent_height.is_some()));
+        assert!(!preflight.is_takeable(false));
+
+        Ok(())
+    }
+
+    #[test(tokio::test)]
+    async fn test_take_offers_xch_for_cat() -> anyhow::Result<()> {
+        let mut alice = TestWallet::new(1000).await?;
+        let mut bob = alice.next(1000).await?;
+
+        // Issue CAT
+        let (coin_spends, asset_id) = bob.wallet.issue_cat(1000, 0, None).await?;
+        bob.transact(coin_spends).await?;
+        bob.wait_for_coins().await;
+
+        let ladder = alice
+            .wallet
+            .make_offer_ladder(
+                OfferLadder {
+                    offered_asset_id: None,
+                    requested_asset_id: Some(asset_id),
+                    requested_hidden_puzzle_hash: None,
+                    rungs: vec![
+                        LadderRung {
+                            offered_amount: 100,
+                            requested_amount: 10,
+                        },
+                        LadderRung {
+                            offered_amount: 200,
+                            requested_amount: 30,
+                        },
+                    ],
+                    fee: 0,
+                    p2_puzzle_hash: None,
+                    expiry: OfferExpiry::default(),
+                },
+                vec![],
+            )
+            .await?;
+
+        let mut offers = Vec::new();
+
+        for offer in ladder.offers {
+            offers.push(
+                alice
+                    .wallet
+                    .sign_transaction(offer, &alice.agg_sig, alice.master_sk.clone(), true)
+                    .await?,
+            );
+        }
+
+        alice.transact(ladder.split).await?;
+        alice.wait_for_coins().await;
+
+        // The same offer can't be taken twice
+        assert!(matches!(
+            bob.wallet
+                .take_offers(vec![offers[0].clone(), offers[0].clone()], 0)
+                .await,
+            Err(WalletError::ConflictingOffers)
+        ));
+
+        // Take both rungs at once
+        let offer = bob.wallet.take_offers(offers, 0).await?;
+        let spend_bundle = bob
+            .wallet
+            .sign_transaction(offer, &bob.agg_sig, bob.master_sk.clone(), true)
+            .await?;
+        bob.push_bundle(spend_bundle).await?;
+
+        bob.wait_for_coins().await;
+        alice.wait_for_puzzles().await;
+
+        // Check balances
+        assert_eq!(alice.wallet.db.xch_balance().await?, 700);
+        assert_eq!(alice.wallet.db.cat_balance(asset_id).await?, 40);
+        assert_eq!(bob.wallet.db.xch_balance().await?, 300);
+        assert_eq!(bob.wallet.db.cat_balance(asset_id).await?, 960);
+
+        Ok(())
+    }
+
     #[test(tokio::test)]
     async fn test_offer_xch_for_nft() -> anyhow::Result<()> {
         let mut alice = TestWallet::new(1030).await?;
@@ -123,7 +460,7 @@ mod tests {
                     },
                     ..Default::default()
                 },
-                None,
+                OfferExpiry::default(),
             )
             .await?;
         let offer = alice
@@ -189,7 +526,7 @@ mod tests {
                     xch: 1000,
                     ..Default::default()
                 },
-                None,
+                OfferExpiry::default(),
             )
             .await?;
         let offer = alice
@@ -267,7 +604,7 @@ mod tests {
                     xch: 1000,
                     ..Default::default()
                 },
-                None,
+                OfferExpiry::default(),
             )
             .await?;
         let offer = alice
@@ -301,6 +638,224 @@ mod tests {
         Ok(())
     }
 
+    #[test(tokio::test)]
+    async fn test_offer_royalties_nft_for_xch() -> anyhow::Result<()> {
+        let mut alice = TestWallet::new(3).await?;
+        let mut bob = alice.next(1030).await?;
+
+        let (coin_spends, did) = alice.wallet.create_did(0).await?;
+        alice.transact(coin_spends).await?;
+        alice.wait_for_coins().await;
+
+        let (coin_spends, nfts) = alice
+            .wallet
+            .bulk_mint_nfts(
+                0,
+                did.info.launcher_id,
+                vec![
+                    WalletNftMint {
+                        metadata: NftMetadata::default(),
+                        p2_puzzle_hash: None,
+                        royalty_puzzle_hash: Some(Bytes32::default()),
+                        royalty_basis_points: 300,
+                    },
+                    WalletNftMint {
+                        metadata: NftMetadata::default(),
+                        p2_puzzle_hash: None,
+                        royalty_puzzle_hash: Some(Bytes32::default()),
+                        royalty_basis_points: 0,
+                    },
+                ],
+            )
+            .await?;
+        alice.transact(coin_spends).await?;
+        alice.wait_for_coins().await;
+
+        let offered = Offered {
+            nfts: nfts.iter().map(|nft| nft.info.launcher_id).collect(),
+            ..Default::default()
+        };
+        let requested = Requested {
+            xch: 1000,
+            ..Default::default()
+        };
+
+        // Only the NFT with royalties is paid for, and it gets the whole trade price
+        let royalties = alice.wallet.offer_royalties(&offered, &requested).await?;
+        assert_eq!(royalties.payments.len(), 1);
+        assert_eq!(royalties.payments[0].launcher_id, nfts[0].info.launcher_id);
+        assert_eq!(royalties.payments[0].asset_id, None);
+        assert_eq!(royalties.payments[0].trade_price, 1000);
+        assert_eq!(royalties.payments[0].amount, 30);
+        assert!(!royalties.payments[0].is_requested);
+        assert_eq!(royalties.trade_prices.len(), 1);
+        assert_eq!(royalties.trade_prices[0].0, None);
+        assert_eq!(royalties.trade_prices[0].1.amount, 1000);
+
+        // Create offer
+        let offer = alice
+            .wallet
+            .make_offer(offered, requested, OfferExpiry::default())
+            .await?;
+        let offer = alice
+            .wallet
+            .sign_transaction(offer, &alice.agg_sig, alice.master_sk.clone(), true)
+            .await?;
+
+        // Take offer
+        let offer = bob.wallet.take_offer(offer, 0).await?;
+        let spend_bundle = bob
+            .wallet
+            .sign_transaction(offer, &bob.agg_sig, bob.master_sk.clone(), true)
+            .await?;
+        bob.push_bundle(spend_bundle).await?;
+
+        // We need to wait for both wallets to sync in this case
+        alice.wait_for_coins().await;
+        bob.wait_for_coins().await;
+
+        // The taker paid exactly the previewed royalty
+        assert_eq!(alice.wallet.db.xch_balance().await?, 1000);
+        assert_eq!(bob.wallet.db.xch_balance().await?, 0);
+
+        Ok(())
+    }
+
+    #[test(tokio::test)]
+    async fn test_offer_royalties_nfts_for_xch_and_cat() -> anyhow::Result<()> {
+        let mut alice = TestWallet::new(4).await?;
+        let mut bob = alice.next(2500).await?;
+
+        // Issue CAT
+        let (coin_spends, asset_id) = bob.wallet.issue_cat(1000, 0, None).await?;
+        bob.transact(coin_spends).await?;
+        bob.wait_for_coins().await;
+
+        let (coin_spends, did) = alice.wallet.create_did(0).await?;
+        alice.transact(coin_spends).await?;
+        alice.wait_for_coins().await;
+
+        let mint = |royalty_puzzle_hash, royalty_basis_points| WalletNftMint {
+            metadata: NftMetadata::default(),
+            p2_puzzle_hash: None,
+            royalty_puzzle_hash: Some(royalty_puzzle_hash),
+            royalty_basis_points,
+        };
+
+        let (coin_spends, nfts) = alice
+            .wallet
+            .bulk_mint_nfts(
+                0,
+                did.info.launcher_id,
+                vec![
+                    mint(Bytes32::new([1; 32]), 300),
+                    mint(Bytes32::new([2; 32]), 0),
+                    mint(Bytes32::new([3; 32]), 150),
+                ],
+            )
+            .await?;
+        alice.transact(coin_spends).await?;
+        alice.wait_for_coins().await;
+
+        let offered = Offered {
+            nfts: nfts.iter().map(|nft| nft.info.launcher_id).collect(),
+            ..Default::default()
+        };
+        let requested = Requested {
+            xch: 1001,
+            cats: indexmap! { asset_id => RequestedCat { amount: 333, hidden_puzzle_hash: None } },
+            ..Default::default()
+        };
+
+        let royalties = alice.wallet.offer_royalties(&offered, &requested).await?;
+
+        // Each NFT with royalties gets an equal share of each requested asset
+        let requested_amounts = OfferAmounts {
+            xch: 1001,
+            cats: indexmap! { asset_id => 333 },
+        };
+        let trade_price_amounts = calculate_trade_price_amounts(&requested_amounts, 2);
+
+        let mut expected = Vec::new();
+
+        for (nft, basis_points) in [(&nfts[0], 300), (&nfts[2], 150)] {
+            for (asset_id, trade_price) in [
+                (None, trade_price_amounts.xch),
+                (Some(asset_id), trade_price_amounts.cats[&asset_id]),
+            ] {
+                expected.push(NftRoyaltyPayment {
+                    launcher_id: nft.info.launcher_id,
+                    royalty_puzzle_hash: nft.info.royalty_puzzle_hash,
+                    royalty_basis_points: basis_points,
+                    asset_id,
+                    trade_price,
+                    amount: calculate_nft_royalty(trade_price, basis_points),
+                    is_requested: false,
+                });
+            }
+        }
+
+        assert_eq!(royalties.payments, expected);
+
+        let mut asset_info = AssetInfo::new();
+        asset_info.insert_cat(asset_id, CatAssetInfo::new(None))?;
+
+        assert_eq!(
+            royalties.trade_prices,
+            [None, Some(asset_id)]
+                .into_iter()
+                .zip(calculate_trade_prices(&trade_price_amounts, &asset_info))
+                .collect::<Vec<_>>()
+        );
+
+        let royalty_total = |asset_id| -> u64 {
+            royalties
+                .payments
+                .iter()
+                .filter(|payment| payment.asset_id == asset_id)
+                .map(|payment| payment.amount)
+                .sum()
+        };
+        let xch_royalties = royalty_total(None);
+        let cat_royalties = royalty_total(Some(asset_id));
+
+        // Create offer
+        let offer = alice
+            .wallet
+            .make_offer(offered, requested, OfferExpiry::default())
+            .await?;
+        let offer = alice
+            .wallet
+            .sign_transaction(offer, &alice.agg_sig, alice.master_sk.clone(), true)
+            .await?;
+
+        // Take offer
+        let offer = bob.wallet.take_offer(offer, 0).await?;
+        let spend_bundle = bob
+            .wallet
+            .sign_transaction(offer, &bob.agg_sig, bob.master_sk.clone(), true)
+            .await?;
+        bob.push_bundle(spend_bundle).await?;
+
+        // We need to wait for both wallets to sync in this case
+        alice.wait_for_puzzles().await;
+        bob.wait_for_coins().await;
+
+        // The taker paid exactly the previewed royalties
+        assert_eq!(alice.wallet.db.xch_balance().await?, 1001);
+        assert_eq!(alice.wallet.db.cat_balance(asset_id).await?, 333);
+        assert_eq!(
+            bob.wallet.db.xch_balance().await?,
+            u128::from(1500 - 1001 - xch_royalties)
+        );
+        assert_eq!(
+            bob.wallet.db.cat_balance(asset_id).await?,
+            u128::from(1000 - 333 - cat_royalties)
+        );
+
+        Ok(())
+    }
+
     #[test(tokio::test)]
     async fn test_offer_nft_same_royalties_for_cat() -> anyhow::Result<()> {
         let mut alice = TestWallet::new(3).await?;
@@ -357,7 +912,7 @@ mod tests {
                     cats: indexmap! { asset_id => RequestedCat { amount: 1000, hidden_puzzle_hash: None } },
                     ..Default::default()
                 },
-                None,
+                OfferExpiry::default(),
             )
             .await?;
         let offer = alice
@@ -442,7 +997,7 @@ mod tests {
                     xch: 1000,
                     ..Default::default()
                 },
-                None,
+                OfferExpiry::default(),
             )
             .await?;
         let offer = alice
@@ -524,7 +1079,7 @@ mod tests {
                     xch: 500,
                     ..Default::default()
                 },
-                None,
+                OfferExpiry::default(),
             )
             .await?;
         let first_offer = alice
@@ -544,7 +1099,7 @@ mod tests {
                     xch: 500,
                     ..Default::default()
                 },
-                None,
+                OfferExpiry::default(),
             )
             .await?;
         let second_offer = alice
@@ -589,7 +1144,7 @@ mod tests {
                     ..Default::default()
                 },
                 Requested::default(),
-                None,
+                OfferExpiry::default(),
             )
             .await?;
         let offer = alice
@@ -632,7 +1187,7 @@ mod tests {
                     ..Default::default()
                 },
                 Requested::default(),
-                None,
+                OfferExpiry::default(),
             )
             .await?;
         let offer = alice
@@ -693,7 +1248,7 @@ mod tests {
                     ..Default::default()
                 },
                 Requested::default(),
-                None,
+                OfferExpiry::default(),
             )
             .await?;
         let offer = alice
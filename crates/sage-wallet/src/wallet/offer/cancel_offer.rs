@@ -8,31 +8,47 @@ impl Wallet {
         &self,
         spend_bundle: SpendBundle,
         fee: u64,
+    ) -> Result<Vec<CoinSpend>, WalletError> {
+        self.cancel_offers(vec![spend_bundle], fee).await
+    }
+
+    /// Cancels each of the offers by spending one of its coins, all in a single transaction.
+    pub async fn cancel_offers(
+        &self,
+        spend_bundles: Vec<SpendBundle>,
+        fee: u64,
     ) -> Result<Vec<CoinSpend>, WalletError> {
         let mut ctx = SpendContext::new();
 
-        let offer = Offer::from_spend_bundle(&mut ctx, &spend_bundle)?;
+        let mut coin_ids = Vec::new();
 
-        let mut coins = Vec::new();
+        for spend_bundle in spend_bundles {
+            let offer = Offer::from_spend_bundle(&mut ctx, &spend_bundle)?;
 
-        for coin_spend in offer.cancellable_coin_spends()? {
-            let coin_id = coin_spend.coin.coin_id();
+            let mut coins = Vec::new();
 
-            let Some(kind) = self.db.coin_kind(coin_id).await? else {
-                continue;
-            };
+            for coin_spend in offer.cancellable_coin_spends()? {
+                let coin_id = coin_spend.coin.coin_id();
 
-            coins.push((kind, coin_id));
-        }
+                let Some(kind) = self.db.coin_kind(coin_id).await? else {
+                    continue;
+                };
+
+                coins.push((kind, coin_id));
+            }
 
-        coins.sort();
+            coins.sort();
 
-        let Some((_, coin_id)) = coins.first().copied() else {
-            return Err(WalletError::UncancellableOffer);
-        };
+            let Some((_, coin_id)) = coins.first().copied() else {
+                return Err(WalletError::UncancellableOffer);
+            };
+
+            if !coin_ids.contains(&coin_id) {
+                coin_ids.push(coin_id);
+            }
+        }
 
-        self.spend(&mut ctx, vec![coin_id], &[Action::fee(fee)])
-            .await?;
+        self.spend(&mut ctx, coin_ids, &[Action::fee(fee)]).await?;
 
         Ok(ctx.take())
     }
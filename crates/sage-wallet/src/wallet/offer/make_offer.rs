@@ -1,6 +1,6 @@
 use chia::{
     bls::Signature,
-    protocol::{Bytes32, SpendBundle},
+    protocol::{Bytes32, Coin, SpendBundle},
     puzzles::{
         offer::{NotarizedPayment, Payment},
         Memos,
@@ -9,7 +9,7 @@ use chia::{
 use chia_puzzles::SETTLEMENT_PAYMENT_HASH;
 use chia_wallet_sdk::driver::{
     calculate_royalty_payments, calculate_trade_price_amounts, calculate_trade_prices, Action,
-    AssetInfo, CatAssetInfo, Id, NftAssetInfo, Offer, OfferAmounts, OptionAssetInfo,
+    AssetInfo, Cat, CatAssetInfo, Id, NftAssetInfo, Offer, OfferAmounts, OptionAssetInfo,
     RequestedPayments, RoyaltyInfo, SpendContext, Spends, TransferNftById,
 };
 use indexmap::IndexMap;
@@ -42,12 +42,38 @@ pub struct RequestedCat {
     pub hidden_puzzle_hash: Option<Bytes32>,
 }
 
+/// When an offer expires, if at all. It can no longer be taken once either the block height or
+/// the timestamp has been reached.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct OfferExpiry {
+    pub height: Option<u32>,
+    pub seconds: Option<u64>,
+}
+
+/// A coin which has been set aside for an offer, rather than being selected from the wallet.
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum OfferInput {
+    Xch(Coin),
+    Cat(Cat),
+}
+
 impl Wallet {
     pub async fn make_offer(
         &self,
         offered: Offered,
         requested: Requested,
-        expires_at: Option<u64>,
+        expiry: OfferExpiry,
+    ) -> Result<SpendBundle, WalletError> {
+        self.make_offer_with_inputs(offered, requested, expiry, Vec::new())
+            .await
+    }
+
+    pub(crate) async fn make_offer_with_inputs(
+        &self,
+        offered: Offered,
+        requested: Requested,
+        expiry: OfferExpiry,
+        inputs: Vec<OfferInput>,
     ) -> Result<SpendBundle, WalletError> {
         let mut ctx = SpendContext::new();
         let mut asset_info = AssetInfo::new();
@@ -137,6 +163,14 @@ impl Wallet {
 
         // Add requested payments
         let mut spends = Spends::new(change_puzzle_hash);
+
+        for input in inputs {
+            match input {
+                OfferInput::Xch(coin) => spends.add(coin),
+                OfferInput::Cat(cat) => spends.add(cat),
+            }
+        }
+
         self.select_spends(&mut ctx, &mut spends, &actions).await?;
 
         let nonce = Offer::nonce(spends.non_settlement_coin_ids());
@@ -253,11 +287,18 @@ impl Wallet {
             .required
             .extend(requested_payments.assertions(&mut ctx, &asset_info)?);
 
-        if let Some(expires_at) = expires_at {
+        if let Some(height) = expiry.height {
+            spends.conditions.required = spends
+                .conditions
+                .required
+                .assert_before_height_absolute(height);
+        }
+
+        if let Some(seconds) = expiry.seconds {
             spends.conditions.required = spends
                 .conditions
                 .required
-                .assert_before_seconds_absolute(expires_at);
+                .assert_before_seconds_absolute(seconds);
         }
 
         // Finish the spend
@@ -0,0 +1,177 @@
+use std::collections::{HashMap, HashSet};
+
+use chia::{
+    protocol::{Bytes32, CoinSpend, SpendBundle},
+    puzzles::Memos,
+};
+use chia_wallet_sdk::driver::{Action, Cat, Id, SpendContext};
+use indexmap::indexmap;
+
+use crate::{ChildKind, Transaction, Wallet, WalletError};
+
+use super::{OfferExpiry, OfferInput, Offered, Requested, RequestedCat};
+
+#[derive(Debug, Clone)]
+pub struct OfferLadder {
+    /// The CAT being offered, or XCH if this is `None`.
+    pub offered_asset_id: Option<Bytes32>,
+    /// The CAT being requested, or XCH if this is `None`.
+    pub requested_asset_id: Option<Bytes32>,
+    pub requested_hidden_puzzle_hash: Option<Bytes32>,
+    pub rungs: Vec<LadderRung>,
+    pub fee: u64,
+    pub p2_puzzle_hash: Option<Bytes32>,
+    pub expiry: OfferExpiry,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LadderRung {
+    pub offered_amount: u64,
+    pub requested_amount: u64,
+}
+
+#[derive(Debug, Clone)]
+pub struct OfferLadderSpends {
+    /// Splits the offered asset into a coin for each rung, and pays the fee.
+    pub split: Vec<CoinSpend>,
+    /// The unsigned offers, in the same order as the rungs.
+    pub offers: Vec<SpendBundle>,
+}
+
+impl Wallet {
+    /// Returns the number of decimal places of XCH or a CAT, which is used to convert prices
+    /// between whole units of each asset. CATs which haven't been seen yet use the default.
+    pub async fn asset_precision(&self, asset_id: Option<Bytes32>) -> Result<u8, WalletError> {
+        let precision = self
+            .db
+            .asset(asset_id.unwrap_or_default())
+            .await?
+            .map(|asset| asset.precision);
+
+        Ok(precision.unwrap_or(if asset_id.is_some() { 3 } else { 12 }))
+    }
+
+    /// Makes an offer for each rung of the ladder. The offered asset is split into a separate
+    /// coin for each rung first, so that the offers can be taken independently of each other.
+    /// The selected coins are spent by the split along with any others that are needed, which
+    /// allows the coins locked in existing offers to be reused.
+    pub async fn make_offer_ladder(
+        &self,
+        ladder: OfferLadder,
+        selected_coin_ids: Vec<Bytes32>,
+    ) -> Result<OfferLadderSpends, WalletError> {
+        let mut ctx = SpendContext::new();
+
+        // Coins with the same parent, puzzle hash and amount would have the same coin id, so
+        // rungs of the same size are sent to different addresses.
+        let mut occurrences = HashMap::<u64, usize>::new();
+
+        let address_indices: Vec<usize> = ladder
+            .rungs
+            .iter()
+            .map(|rung| {
+                let count = occurrences.entry(rung.offered_amount).or_default();
+                *count += 1;
+                *count - 1
+            })
+            .collect();
+
+        let address_count = occurrences.values().max().copied().unwrap_or(1);
+        let puzzle_hashes = self
+            .p2_puzzle_hashes(address_count.try_into()?, false, true)
+            .await?;
+
+        let mut actions = vec![Action::fee(ladder.fee)];
+
+        for (rung, &index) in ladder.rungs.iter().zip(&address_indices) {
+            let puzzle_hash = puzzle_hashes[index];
+
+            actions.push(Action::send(
+                ladder.offered_asset_id.map_or(Id::Xch, Id::Existing),
+                puzzle_hash,
+                rung.offered_amount,
+                if ladder.offered_asset_id.is_some() {
+                    ctx.hint(puzzle_hash)?
+                } else {
+                    Memos::None
+                },
+            ));
+        }
+
+        self.spend(&mut ctx, selected_coin_ids, &actions).await?;
+
+        let split = ctx.take();
+        let transaction = Transaction::from_coin_spends(split.clone())?;
+
+        let mut used_coin_ids = HashSet::new();
+        let mut offers = Vec::with_capacity(ladder.rungs.len());
+
+        for (rung_index, (rung, &index)) in ladder.rungs.iter().zip(&address_indices).enumerate() {
+            let puzzle_hash = puzzle_hashes[index];
+
+            let input = transaction
+                .inputs
+                .iter()
+                .flat_map(|input| &input.outputs)
+                .filter(|output| output.coin.amount == rung.offered_amount)
+                .filter(|output| !used_coin_ids.contains(&output.coin.coin_id()))
+                .find_map(|output| match (ladder.offered_asset_id, &output.kind) {
+                    (None, _) if output.coin.puzzle_hash == puzzle_hash => {
+                        Some(OfferInput::Xch(output.coin))
+                    }
+                    (
+                        Some(asset_id),
+                        ChildKind::Cat {
+                            info,
+                            lineage_proof,
+                            ..
+                        },
+                    ) if info.asset_id == asset_id && info.p2_puzzle_hash == puzzle_hash => Some(
+                        OfferInput::Cat(Cat::new(output.coin, Some(*lineage_proof), *info)),
+                    ),
+                    _ => None,
+                })
+                .ok_or(WalletError::MissingLadderCoin(rung_index))?;
+
+            used_coin_ids.insert(match input {
+                OfferInput::Xch(coin) => coin.coin_id(),
+                OfferInput::Cat(cat) => cat.coin.coin_id(),
+            });
+
+            let mut offered = Offered {
+                p2_puzzle_hash: ladder.p2_puzzle_hash,
+                ..Default::default()
+            };
+
+            match ladder.offered_asset_id {
+                Some(asset_id) => {
+                    offered.cats.insert(asset_id, rung.offered_amount);
+                }
+                None => offered.xch = rung.offered_amount,
+            }
+
+            let requested = match ladder.requested_asset_id {
+                Some(asset_id) => Requested {
+                    cats: indexmap! {
+                        asset_id => RequestedCat {
+                            amount: rung.requested_amount,
+                            hidden_puzzle_hash: ladder.requested_hidden_puzzle_hash,
+                        },
+                    },
+                    ..Default::default()
+                },
+                None => Requested {
+                    xch: rung.requested_amount,
+                    ..Default::default()
+                },
+            };
+
+            offers.push(
+                self.make_offer_with_inputs(offered, requested, ladder.expiry, vec![input])
+                    .await?,
+            );
+        }
+
+        Ok(OfferLadderSpends { split, offers })
+    }
+}
@@ -0,0 +1,154 @@
+use chia::protocol::Bytes32;
+use chia_wallet_sdk::{
+    driver::{
+        calculate_nft_royalty, calculate_trade_price_amounts, calculate_trade_prices, AssetInfo,
+        CatAssetInfo, OfferAmounts, RoyaltyInfo,
+    },
+    types::conditions::TradePrice,
+};
+use itertools::Itertools;
+
+use crate::{Offered, Requested, Wallet, WalletError};
+
+/// The royalty paid to the creator of an NFT in one of the assets it's traded for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NftRoyaltyPayment {
+    pub launcher_id: Bytes32,
+    pub royalty_puzzle_hash: Bytes32,
+    pub royalty_basis_points: u16,
+    /// The asset id of the CAT the royalty is paid in, or `None` for XCH.
+    pub asset_id: Option<Bytes32>,
+    /// The share of the asset which the NFT is traded for.
+    pub trade_price: u64,
+    pub amount: u64,
+    /// Royalties for requested NFTs are paid by the maker, and the rest by the taker.
+    pub is_requested: bool,
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct OfferRoyalties {
+    pub payments: Vec<NftRoyaltyPayment>,
+    /// The trade prices revealed by each offered NFT which has royalties, along with the asset
+    /// id of the CAT they are in, or `None` for XCH.
+    pub trade_prices: Vec<(Option<Bytes32>, TradePrice)>,
+}
+
+impl Wallet {
+    /// Calculates the royalties which would be paid when making and taking an offer, in the same
+    /// way as [`Wallet::make_offer`] does.
+    pub async fn offer_royalties(
+        &self,
+        offered: &Offered,
+        requested: &Requested,
+    ) -> Result<OfferRoyalties, WalletError> {
+        let mut asset_info = AssetInfo::new();
+
+        let offered_amounts = OfferAmounts {
+            xch: offered.xch,
+            cats: offered.cats.clone(),
+        };
+
+        let requested_amounts = OfferAmounts {
+            xch: requested.xch,
+            cats: requested
+                .cats
+                .iter()
+                .map(|(asset_id, cat)| {
+                    asset_info.insert_cat(*asset_id, CatAssetInfo::new(cat.hidden_puzzle_hash))?;
+                    Ok((*asset_id, cat.amount))
+                })
+                .collect::<Result<_, WalletError>>()?,
+        };
+
+        let mut offered_royalties = Vec::new();
+
+        for &launcher_id in &offered.nfts {
+            let nft = self
+                .db
+                .offer_nft_info(launcher_id)
+                .await?
+                .ok_or(WalletError::MissingNft(launcher_id))?;
+
+            if nft.royalty_basis_points > 0 {
+                offered_royalties.push(RoyaltyInfo::new(
+                    launcher_id,
+                    nft.royalty_puzzle_hash,
+                    nft.royalty_basis_points,
+                ));
+            }
+        }
+
+        let requested_royalties = requested
+            .nfts
+            .iter()
+            .map(|(&launcher_id, nft)| {
+                RoyaltyInfo::new(
+                    launcher_id,
+                    nft.royalty_puzzle_hash,
+                    nft.royalty_basis_points,
+                )
+            })
+            .filter(|info| info.basis_points > 0)
+            .collect_vec();
+
+        // The maker pays royalties for requested NFTs out of what they offer, and the taker pays
+        // royalties for offered NFTs out of what was requested.
+        let maker_trade_prices =
+            calculate_trade_price_amounts(&offered_amounts, requested_royalties.len());
+        let taker_trade_prices =
+            calculate_trade_price_amounts(&requested_amounts, offered_royalties.len());
+
+        let mut payments = royalty_payments(&maker_trade_prices, &requested_royalties, true);
+        payments.extend(royalty_payments(
+            &taker_trade_prices,
+            &offered_royalties,
+            false,
+        ));
+
+        Ok(OfferRoyalties {
+            payments,
+            // Trade prices are calculated in the same order, skipping zero amounts as well
+            trade_prices: asset_amounts(&taker_trade_prices)
+                .map(|(asset_id, _)| asset_id)
+                .zip(calculate_trade_prices(&taker_trade_prices, &asset_info))
+                .collect(),
+        })
+    }
+}
+
+fn royalty_payments(
+    trade_prices: &OfferAmounts,
+    royalties: &[RoyaltyInfo],
+    is_requested: bool,
+) -> Vec<NftRoyaltyPayment> {
+    let mut payments = Vec::new();
+
+    for royalty in royalties {
+        for (asset_id, trade_price) in asset_amounts(trade_prices) {
+            payments.push(NftRoyaltyPayment {
+                launcher_id: royalty.launcher_id,
+                royalty_puzzle_hash: royalty.puzzle_hash,
+                royalty_basis_points: royalty.basis_points,
+                asset_id,
+                trade_price,
+                amount: calculate_nft_royalty(trade_price, royalty.basis_points),
+                is_requested,
+            });
+        }
+    }
+
+    payments
+}
+
+/// The non-zero amounts of XCH and then each CAT, where XCH has no asset id.
+fn asset_amounts(amounts: &OfferAmounts) -> impl Iterator<Item = (Option<Bytes32>, u64)> + '_ {
+    [(None, amounts.xch)]
+        .into_iter()
+        .chain(
+            amounts
+                .cats
+                .iter()
+                .map(|(&asset_id, &amount)| (Some(asset_id), amount)),
+        )
+        .filter(|(_, amount)| *amount > 0)
+}
@@ -0,0 +1,77 @@
+use std::collections::HashMap;
+
+use chia::protocol::{Bytes32, Coin, CoinState, SpendBundle};
+
+use crate::{spend_cost, Wallet, WalletError, WalletPeer, MAX_TRANSACTION_COST};
+
+/// The state of an offer's coins on chain, and what it would cost to take it.
+#[derive(Debug, Clone)]
+pub struct OfferPreflight {
+    /// The coins spent by the maker, along with their state if the peer knows of them.
+    pub coins: Vec<(Coin, Option<CoinState>)>,
+    /// The cost of the taker's spend, if it could be built and run.
+    pub cost: Option<u64>,
+    /// Why the taker's spend couldn't be built or run.
+    pub error: Option<String>,
+}
+
+impl OfferPreflight {
+    /// Whether every coin is still unspent and the taker's spend fits in a transaction.
+    pub fn is_takeable(&self, expired: bool) -> bool {
+        !expired
+            && self.coins.iter().all(|(_, coin_state)| {
+                coin_state.is_some_and(|coin_state| coin_state.spent_height.is_none())
+            })
+            && self.cost.is_some_and(|cost| cost <= MAX_TRANSACTION_COST)
+    }
+}
+
+impl Wallet {
+    /// Checks whether an offer could be taken without signing or submitting anything.
+    pub async fn preflight_offer(
+        &self,
+        peer: &WalletPeer,
+        spend_bundle: SpendBundle,
+        fee: u64,
+    ) -> Result<OfferPreflight, WalletError> {
+        let input_coins = offer_input_coins(&spend_bundle);
+
+        let coin_states: HashMap<Bytes32, CoinState> = peer
+            .fetch_coins(
+                input_coins.iter().map(Coin::coin_id).collect(),
+                self.genesis_challenge,
+            )
+            .await?
+            .into_iter()
+            .map(|coin_state| (coin_state.coin.coin_id(), coin_state))
+            .collect();
+
+        let coins = input_coins
+            .into_iter()
+            .map(|coin| (coin, coin_states.get(&coin.coin_id()).copied()))
+            .collect();
+
+        // The taker's spend is built but not signed, so it's only run to find its cost
+        let (cost, error) = match self
+            .take_offer(spend_bundle, fee)
+            .await
+            .and_then(|unsigned| spend_cost(&unsigned.coin_spends))
+        {
+            Ok(cost) => (Some(cost), None),
+            Err(error) => (None, Some(error.to_string())),
+        };
+
+        Ok(OfferPreflight { coins, cost, error })
+    }
+}
+
+/// The coins spent by the maker of an offer, excluding the placeholder spends for the requested
+/// payments.
+pub fn offer_input_coins(spend_bundle: &SpendBundle) -> Vec<Coin> {
+    spend_bundle
+        .coin_spends
+        .iter()
+        .filter(|coin_spend| coin_spend.coin.parent_coin_info != Bytes32::default())
+        .map(|coin_spend| coin_spend.coin)
+        .collect()
+}
@@ -1,7 +1,7 @@
 use chia::{bls::Signature, protocol::SpendBundle};
 use chia_wallet_sdk::driver::{
-    calculate_royalty_payments, calculate_trade_price_amounts, calculate_trade_prices, Action, Id,
-    Offer, OfferAmounts, RoyaltyInfo, SpendContext, Spends, TransferNftById,
+    calculate_royalty_payments, calculate_trade_price_amounts, calculate_trade_prices, Action,
+    DriverError, Id, Offer, OfferAmounts, RoyaltyInfo, SpendContext, Spends, TransferNftById,
 };
 use indexmap::IndexMap;
 use itertools::Itertools;
@@ -14,66 +14,107 @@ impl Wallet {
         &self,
         spend_bundle: SpendBundle,
         fee: u64,
+    ) -> Result<SpendBundle, WalletError> {
+        self.take_offers(vec![spend_bundle], fee).await
+    }
+
+    /// Takes each of the offers in a single transaction, so that the fee is only paid once.
+    /// The offers can't spend the same coins or request the same NFT from the taker.
+    pub async fn take_offers(
+        &self,
+        spend_bundles: Vec<SpendBundle>,
+        fee: u64,
     ) -> Result<SpendBundle, WalletError> {
         let mut ctx = SpendContext::new();
-        let offer = Offer::from_spend_bundle(&mut ctx, &spend_bundle)?;
 
-        let arbitrage = offer.arbitrage();
+        let mut combined: Option<Offer> = None;
 
-        let mut requested_nfts = IndexMap::new();
+        // Make payments
+        let mut actions = vec![Action::fee(fee)];
 
-        for launcher_id in arbitrage.requested.nfts {
-            let Some(nft) = offer.asset_info().nft(launcher_id) else {
-                return Err(WalletError::MissingNft(launcher_id));
-            };
+        // The NFTs requested from the taker by each offer, along with what the offer pays for them
+        let mut taker_nfts = Vec::new();
 
-            let metadata = ctx.serialize(&nft.metadata)?;
+        for spend_bundle in spend_bundles {
+            let offer = Offer::from_spend_bundle(&mut ctx, &spend_bundle)?;
 
-            requested_nfts.insert(
-                launcher_id,
-                NftOfferInfo {
-                    metadata,
-                    metadata_updater_puzzle_hash: nft.metadata_updater_puzzle_hash,
-                    royalty_puzzle_hash: nft.royalty_puzzle_hash,
-                    royalty_basis_points: nft.royalty_basis_points,
-                },
-            );
-        }
+            let arbitrage = offer.arbitrage();
 
-        let change_puzzle_hash = self.change_p2_puzzle_hash().await?;
+            let mut requested_nfts = IndexMap::new();
 
-        let offer_amounts = OfferAmounts {
-            xch: arbitrage.offered.xch,
-            cats: arbitrage.offered.cats.clone(),
-        };
+            for launcher_id in arbitrage.requested.nfts {
+                let Some(nft) = offer.asset_info().nft(launcher_id) else {
+                    return Err(WalletError::MissingNft(launcher_id));
+                };
 
-        let requested_amounts = OfferAmounts {
-            xch: arbitrage.requested.xch,
-            cats: arbitrage.requested.cats.clone(),
-        };
+                let metadata = ctx.serialize(&nft.metadata)?;
 
-        let offer_royalties = requested_nfts
-            .iter()
-            .map(|(&launcher_id, nft)| {
-                RoyaltyInfo::new(
+                requested_nfts.insert(
                     launcher_id,
-                    nft.royalty_puzzle_hash,
-                    nft.royalty_basis_points,
-                )
-            })
-            .filter(|info| info.basis_points > 0)
-            .collect_vec();
+                    NftOfferInfo {
+                        metadata,
+                        metadata_updater_puzzle_hash: nft.metadata_updater_puzzle_hash,
+                        royalty_puzzle_hash: nft.royalty_puzzle_hash,
+                        royalty_basis_points: nft.royalty_basis_points,
+                    },
+                );
+            }
+
+            let offer_amounts = OfferAmounts {
+                xch: arbitrage.offered.xch,
+                cats: arbitrage.offered.cats.clone(),
+            };
 
-        let offer_trade_price_amounts =
-            calculate_trade_price_amounts(&offer_amounts, offer_royalties.len());
+            let requested_amounts = OfferAmounts {
+                xch: arbitrage.requested.xch,
+                cats: arbitrage.requested.cats.clone(),
+            };
 
-        // Make payments
-        let mut actions = vec![Action::fee(fee)];
+            let offer_royalties = requested_nfts
+                .iter()
+                .map(|(&launcher_id, nft)| {
+                    RoyaltyInfo::new(
+                        launcher_id,
+                        nft.royalty_puzzle_hash,
+                        nft.royalty_basis_points,
+                    )
+                })
+                .filter(|info| info.basis_points > 0)
+                .collect_vec();
+
+            let offer_trade_price_amounts =
+                calculate_trade_price_amounts(&offer_amounts, offer_royalties.len());
+
+            // Pay royalties
+            let royalty_payments =
+                calculate_royalty_payments(&mut ctx, &offer_trade_price_amounts, &offer_royalties)?;
+            actions.extend(royalty_payments.actions());
+
+            if arbitrage.offered.nfts.iter().any(|launcher_id| {
+                taker_nfts
+                    .iter()
+                    .any(|(launcher_ids, _): &(Vec<_>, _)| launcher_ids.contains(launcher_id))
+            }) {
+                return Err(WalletError::ConflictingOffers);
+            }
 
-        // Pay royalties
-        let royalty_payments =
-            calculate_royalty_payments(&mut ctx, &offer_trade_price_amounts, &offer_royalties)?;
-        actions.extend(royalty_payments.actions());
+            taker_nfts.push((arbitrage.offered.nfts, requested_amounts));
+
+            if let Some(combined) = &mut combined {
+                combined.extend(offer).map_err(|error| match error {
+                    DriverError::ConflictingOfferInputs => WalletError::ConflictingOffers,
+                    error => error.into(),
+                })?;
+            } else {
+                combined = Some(offer);
+            }
+        }
+
+        let Some(offer) = combined else {
+            return Err(WalletError::NoOffers);
+        };
+
+        let change_puzzle_hash = self.change_p2_puzzle_hash().await?;
 
         // Pay requested payments
         let mut spends = Spends::new(change_puzzle_hash);
@@ -83,26 +124,35 @@ impl Wallet {
         // Add requested payments
         self.select_spends(&mut ctx, &mut spends, &actions).await?;
 
-        // Reset DIDs and reveal trade prices
-        let mut royalty_nft_count = 0;
+        // Each offer's trade prices are split between the royalty NFTs it requests
+        let mut trade_prices = IndexMap::new();
 
-        for nft in spends.nfts.values().rev() {
-            let nft = nft.last()?;
+        for (launcher_ids, requested_amounts) in taker_nfts {
+            let mut royalty_nft_count = 0;
 
-            if !nft.kind.is_conditions() {
-                continue;
+            for &launcher_id in &launcher_ids {
+                let Some(nft) = spends.nfts.get(&Id::Existing(launcher_id)) else {
+                    continue;
+                };
+
+                let nft = nft.last()?;
+
+                if nft.kind.is_conditions() && nft.asset.info.royalty_basis_points > 0 {
+                    royalty_nft_count += 1;
+                }
             }
 
-            if nft.asset.info.royalty_basis_points > 0 {
-                royalty_nft_count += 1;
+            let offer_trade_prices = calculate_trade_prices(
+                &calculate_trade_price_amounts(&requested_amounts, royalty_nft_count),
+                offer.asset_info(),
+            );
+
+            for launcher_id in launcher_ids {
+                trade_prices.insert(launcher_id, offer_trade_prices.clone());
             }
         }
 
-        let trade_prices = calculate_trade_prices(
-            &calculate_trade_price_amounts(&requested_amounts, royalty_nft_count),
-            offer.asset_info(),
-        );
-
+        // Reset DIDs and reveal trade prices
         for nft in spends.nfts.values().rev() {
             let nft = nft.last()?;
 
@@ -110,15 +160,17 @@ impl Wallet {
                 continue;
             }
 
+            let launcher_id = nft.asset.info.launcher_id;
+
             actions.insert(
                 0,
                 Action::update_nft(
-                    Id::Existing(nft.asset.info.launcher_id),
+                    Id::Existing(launcher_id),
                     vec![],
                     Some(TransferNftById::new(
                         None,
                         if nft.asset.info.royalty_basis_points > 0 {
-                            trade_prices.clone()
+                            trade_prices.get(&launcher_id).cloned().unwrap_or_default()
                         } else {
                             vec![]
                         },
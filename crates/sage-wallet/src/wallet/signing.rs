@@ -17,6 +17,10 @@ use crate::WalletError;
 use super::Wallet;
 
 impl Wallet {
+    /// Signs the coin spends owned by this wallet. Every signature goes through here, so this
+    /// is where the spending policy is enforced and the outflows are recorded. Signers are
+    /// serialized while doing so, so that concurrent transactions can't each pass the daily
+    /// limits and exceed them together.
     pub async fn sign_transaction(
         &self,
         spend_bundle: SpendBundle,
@@ -24,6 +28,12 @@ impl Wallet {
         master_sk: SecretKey,
         partial: bool,
     ) -> Result<SpendBundle, WalletError> {
+        let _policy_guard = self.policy_lock.lock().await;
+
+        let outflows = self
+            .check_spending_policy(&spend_bundle.coin_spends)
+            .await?;
+
         let input_coin_spends = spend_bundle
             .coin_spends
             .iter()
@@ -87,9 +97,10 @@ impl Wallet {
             aggregated_signature += &sign(&sk, required.message());
         }
 
-        Ok(SpendBundle::new(
-            spend_bundle.coin_spends,
-            aggregated_signature,
-        ))
+        let spend_bundle = SpendBundle::new(spend_bundle.coin_spends, aggregated_signature);
+
+        self.record_outflows(spend_bundle.name(), outflows).await?;
+
+        Ok(spend_bundle)
     }
 }
@@ -0,0 +1,624 @@
+use std::{
+    collections::{HashMap, HashSet},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use chia::protocol::{Bytes32, CoinSpend};
+use chia_puzzles::{SETTLEMENT_PAYMENT_HASH, SINGLETON_LAUNCHER_HASH};
+
+use crate::{CoinKind, Transaction, TransactionInput, WalletError};
+
+use super::Wallet;
+
+/// The window over which the daily limits apply, in seconds.
+const DAY: u64 = 24 * 60 * 60;
+
+/// The amount of each asset that leaves the wallet in a transaction, keyed by asset hash.
+pub type Outflows = HashMap<Bytes32, u64>;
+
+impl Wallet {
+    /// Checks coin spends against the wallet's spending policy before they are signed, and
+    /// returns the outflows that should be recorded once the signature succeeds. This is
+    /// called by [`Wallet::sign_transaction`] while holding the policy lock, so that background
+    /// jobs are held to the same limits as transactions made by the user.
+    pub async fn check_spending_policy(
+        &self,
+        coin_spends: &[CoinSpend],
+    ) -> Result<Outflows, WalletError> {
+        let policy = self.db.spending_policy().await?;
+        let limits = self.db.spending_limits().await?;
+        let allowed_destinations: HashSet<Bytes32> =
+            self.db.allowed_destinations().await?.into_iter().collect();
+
+        if policy.is_none() && limits.is_empty() && allowed_destinations.is_empty() {
+            return Ok(Outflows::new());
+        }
+
+        // Offers carry their requested payments as spends of placeholder coins without a parent,
+        // which aren't part of the transaction until the offer is taken.
+        let transaction = Transaction::from_coin_spends(
+            coin_spends
+                .iter()
+                .filter(|coin_spend| coin_spend.coin.parent_coin_info != Bytes32::default())
+                .cloned()
+                .collect(),
+        )?;
+
+        if let Some(max_fee) = policy.and_then(|policy| policy.max_fee) {
+            if transaction.fee > max_fee {
+                return Err(WalletError::PolicyViolation(format!(
+                    "fee of {} exceeds the maximum of {max_fee}",
+                    transaction.fee
+                )));
+            }
+        }
+
+        let mut outflows = Outflows::new();
+
+        if transaction.fee > 0 {
+            outflows.insert(Bytes32::default(), transaction.fee);
+        }
+
+        for input in &transaction.inputs {
+            // Only coins owned by the wallet are counted, since the other side of an offer is
+            // spent in the same transaction.
+            let Some(p2_puzzle_hash) = input_p2_puzzle_hash(input) else {
+                continue;
+            };
+
+            if !self.db.is_custody_p2_puzzle_hash(p2_puzzle_hash).await? {
+                continue;
+            }
+
+            let (asset_hash, is_blocked) = match &input.kind {
+                CoinKind::Launcher => continue,
+                CoinKind::Unknown => (Some(Bytes32::default()), false),
+                CoinKind::Cat { info } => (Some(info.asset_id), false),
+                CoinKind::Nft { .. } => (
+                    None,
+                    policy.is_some_and(|policy| policy.is_nft_transfer_blocked),
+                ),
+                CoinKind::Did { .. } => (
+                    None,
+                    policy.is_some_and(|policy| policy.is_did_transfer_blocked),
+                ),
+                CoinKind::Option { .. } => (None, false),
+            };
+
+            for output in &input.outputs {
+                let destination = output
+                    .kind
+                    .receiver_custody_p2_puzzle_hash()
+                    .unwrap_or(output.coin.puzzle_hash);
+
+                if self.db.is_custody_p2_puzzle_hash(destination).await? {
+                    continue;
+                }
+
+                let is_launcher = destination == SINGLETON_LAUNCHER_HASH.into();
+
+                if is_blocked && !is_launcher {
+                    return Err(WalletError::PolicyViolation(format!(
+                        "transferring {} is not allowed",
+                        input_asset_name(input)
+                    )));
+                }
+
+                // Launchers and offer settlements aren't payments to another party, so they're
+                // governed by the asset rules rather than the allow list.
+                if !allowed_destinations.is_empty()
+                    && !is_launcher
+                    && destination != SETTLEMENT_PAYMENT_HASH.into()
+                    && !allowed_destinations.contains(&destination)
+                {
+                    return Err(WalletError::PolicyViolation(format!(
+                        "{destination} is not an allowed destination"
+                    )));
+                }
+
+                if let Some(asset_hash) = asset_hash {
+                    let total = outflows.entry(asset_hash).or_default();
+                    *total = total.saturating_add(output.coin.amount);
+                }
+            }
+        }
+
+        let timestamp = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+
+        for limit in limits {
+            let Some(&amount) = outflows.get(&limit.asset_hash) else {
+                continue;
+            };
+
+            let name = if limit.asset_hash == Bytes32::default() {
+                "XCH".to_string()
+            } else {
+                hex::encode(limit.asset_hash)
+            };
+
+            if let Some(max) = limit.max_per_transaction {
+                if amount > max {
+                    return Err(WalletError::PolicyViolation(format!(
+                        "sending {amount} of {name} exceeds the per transaction limit of {max}"
+                    )));
+                }
+            }
+
+            if let Some(max) = limit.max_per_day {
+                let spent = self
+                    .db
+                    .policy_spent_since(limit.asset_hash, timestamp.saturating_sub(DAY))
+                    .await?;
+
+                if spent + u128::from(amount) > u128::from(max) {
+                    return Err(WalletError::PolicyViolation(format!(
+                        "sending {amount} of {name} exceeds the daily limit of {max}, of which {spent} has been used"
+                    )));
+                }
+            }
+        }
+
+        Ok(outflows)
+    }
+
+    /// Records the outflows of a transaction that has been signed, so that they count
+    /// towards the daily limits. They're released again if the transaction can't be
+    /// submitted or is rejected by the mempool.
+    pub async fn record_outflows(
+        &self,
+        transaction_id: Bytes32,
+        outflows: Outflows,
+    ) -> Result<(), WalletError> {
+        let mut tx = self.db.tx().await?;
+
+        for (asset_hash, amount) in outflows {
+            tx.insert_policy_spend(transaction_id, asset_hash, amount)
+                .await?;
+        }
+
+        tx.commit().await?;
+
+        Ok(())
+    }
+}
+
+/// The inner puzzle hash of a coin, which is what determines whether the wallet owns it.
+fn input_p2_puzzle_hash(input: &TransactionInput) -> Option<Bytes32> {
+    match &input.kind {
+        CoinKind::Launcher => None,
+        CoinKind::Unknown => Some(input.coin_spend.coin.puzzle_hash),
+        CoinKind::Cat { info } => Some(info.p2_puzzle_hash),
+        CoinKind::Nft { info, .. } => Some(info.p2_puzzle_hash),
+        CoinKind::Did { info } => Some(info.p2_puzzle_hash),
+        CoinKind::Option { info } => Some(info.p2_puzzle_hash),
+    }
+}
+
+fn input_asset_name(input: &TransactionInput) -> String {
+    match &input.kind {
+        CoinKind::Nft { info, .. } => format!("NFT {}", info.launcher_id),
+        CoinKind::Did { info } => format!("DID {}", info.launcher_id),
+        _ => "this asset".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use chia::{bls::Signature, protocol::SpendBundle, puzzles::nft::NftMetadata};
+    use futures_util::future::join_all;
+    use indexmap::indexmap;
+    use sage_database::{SpendingLimitRow, SpendingPolicyRow};
+    use test_log::test;
+    use tokio::time::timeout;
+
+    use crate::{
+        OfferExpiry, Offered, Requested, RequestedCat, SyncEvent, TestWallet, WalletNftMint,
+    };
+
+    use super::*;
+
+    impl TestWallet {
+        async fn sign(&self, coin_spends: Vec<CoinSpend>) -> Result<SpendBundle, WalletError> {
+            self.wallet
+                .sign_transaction(
+                    SpendBundle::new(coin_spends, Signature::default()),
+                    &self.agg_sig,
+                    self.master_sk.clone(),
+                    false,
+                )
+                .await
+        }
+
+        pub(crate) async fn set_policy(
+            &self,
+            policy: Option<SpendingPolicyRow>,
+            limits: Vec<SpendingLimitRow>,
+            allowed_destinations: Vec<Bytes32>,
+        ) -> anyhow::Result<()> {
+            let mut tx = self.wallet.db.tx().await?;
+
+            tx.clear_spending_policy().await?;
+
+            if let Some(policy) = policy {
+                tx.set_spending_policy(policy).await?;
+            }
+
+            for limit in limits {
+                tx.insert_spending_limit(limit).await?;
+            }
+
+            for puzzle_hash in allowed_destinations {
+                tx.insert_allowed_destination(puzzle_hash).await?;
+            }
+
+            tx.commit().await?;
+
+            Ok(())
+        }
+    }
+
+    fn is_violation<T>(result: &Result<T, WalletError>) -> bool {
+        matches!(result, Err(WalletError::PolicyViolation(..)))
+    }
+
+    #[test(tokio::test)]
+    async fn test_spending_limits() -> anyhow::Result<()> {
+        let test = TestWallet::new(1000).await?;
+        let external = Bytes32::new([1; 32]);
+
+        test.set_policy(
+            None,
+            vec![SpendingLimitRow {
+                asset_hash: Bytes32::default(),
+                max_per_transaction: Some(300),
+                max_per_day: Some(500),
+            }],
+            vec![],
+        )
+        .await?;
+
+        // The fee counts towards the limit along with the amount sent.
+        let coin_spends = test
+            .wallet
+            .send_xch(vec![(external, 250)], 51, vec![], None)
+            .await?;
+        assert!(is_violation(&test.sign(coin_spends).await));
+
+        let coin_spends = test
+            .wallet
+            .send_xch(vec![(external, 250)], 50, vec![], None)
+            .await?;
+        test.sign(coin_spends).await?;
+
+        assert_eq!(
+            test.wallet
+                .db
+                .policy_spent_since(Bytes32::default(), 0)
+                .await?,
+            300
+        );
+
+        // The first transaction used up most of the daily limit.
+        let coin_spends = test
+            .wallet
+            .send_xch(vec![(external, 201)], 0, vec![], None)
+            .await?;
+        assert!(is_violation(&test.sign(coin_spends).await));
+
+        let coin_spends = test
+            .wallet
+            .send_xch(vec![(external, 200)], 0, vec![], None)
+            .await?;
+        test.sign(coin_spends).await?;
+
+        // Sending to the wallet itself isn't an outflow.
+        let coin_spends = test
+            .wallet
+            .send_xch(vec![(test.puzzle_hash, 1000)], 0, vec![], None)
+            .await?;
+        test.sign(coin_spends).await?;
+
+        assert_eq!(
+            test.wallet
+                .db
+                .policy_spent_since(Bytes32::default(), 0)
+                .await?,
+            500
+        );
+
+        Ok(())
+    }
+
+    #[test(tokio::test)]
+    async fn test_offer_limits() -> anyhow::Result<()> {
+        let test = TestWallet::new(1000).await?;
+        let asset_id = Bytes32::new([2; 32]);
+
+        test.set_policy(
+            None,
+            vec![SpendingLimitRow {
+                asset_hash: Bytes32::default(),
+                max_per_transaction: Some(300),
+                max_per_day: None,
+            }],
+            vec![],
+        )
+        .await?;
+
+        let make_offer = |xch| {
+            test.wallet.make_offer(
+                Offered {
+                    xch,
+                    ..Default::default()
+                },
+                Requested {
+                    cats: indexmap! { asset_id => RequestedCat { amount: 10, hidden_puzzle_hash: None } },
+                    ..Default::default()
+                },
+                OfferExpiry::default(),
+            )
+        };
+
+        // The offered amount leaves the wallet once the offer is taken, so it counts towards
+        // the limit, whereas the requested payments don't.
+        let offer = make_offer(301).await?;
+        let result = test
+            .wallet
+            .sign_transaction(offer, &test.agg_sig, test.master_sk.clone(), true)
+            .await;
+        assert!(is_violation(&result));
+
+        let offer = make_offer(300).await?;
+        test.wallet
+            .sign_transaction(offer, &test.agg_sig, test.master_sk.clone(), true)
+            .await?;
+
+        Ok(())
+    }
+
+    #[test(tokio::test)]
+    async fn test_concurrent_limits() -> anyhow::Result<()> {
+        let test = TestWallet::new(1000).await?;
+        let external = Bytes32::new([1; 32]);
+
+        test.set_policy(
+            None,
+            vec![SpendingLimitRow {
+                asset_hash: Bytes32::default(),
+                max_per_transaction: None,
+                max_per_day: Some(500),
+            }],
+            vec![],
+        )
+        .await?;
+
+        // Signers wait for each other, so that the check and the record can't be interleaved.
+        let coin_spends = test
+            .wallet
+            .send_xch(vec![(external, 100)], 0, vec![], None)
+            .await?;
+
+        let guard = test.wallet.policy_lock.lock().await;
+        let sign = test.sign(coin_spends);
+        tokio::pin!(sign);
+        assert!(timeout(Duration::from_millis(100), &mut sign)
+            .await
+            .is_err());
+        drop(guard);
+        sign.await?;
+
+        let mut bundles = Vec::new();
+
+        for _ in 0..5 {
+            bundles.push(
+                test.wallet
+                    .send_xch(vec![(external, 200)], 0, vec![], None)
+                    .await?,
+            );
+        }
+
+        // Each transaction fits in the daily limit on its own, but only two more of them fit
+        // together.
+        let results = join_all(
+            bundles
+                .into_iter()
+                .map(|coin_spends| test.sign(coin_spends)),
+        )
+        .await;
+        assert_eq!(results.iter().filter(|result| result.is_ok()).count(), 2);
+        assert!(results
+            .iter()
+            .all(|result| result.is_ok() || is_violation(result)));
+
+        assert_eq!(
+            test.wallet
+                .db
+                .policy_spent_since(Bytes32::default(), 0)
+                .await?,
+            500
+        );
+
+        Ok(())
+    }
+
+    #[test(tokio::test)]
+    async fn test_unsubmitted_outflows() -> anyhow::Result<()> {
+        let mut test = TestWallet::new(1000).await?;
+        let external = Bytes32::new([1; 32]);
+
+        test.set_policy(
+            None,
+            vec![SpendingLimitRow {
+                asset_hash: Bytes32::default(),
+                max_per_transaction: None,
+                max_per_day: Some(500),
+            }],
+            vec![],
+        )
+        .await?;
+
+        let conflicting = test
+            .sign(
+                test.wallet
+                    .send_xch(vec![(external, 300)], 0, vec![], None)
+                    .await?,
+            )
+            .await?;
+
+        test.transact(
+            test.wallet
+                .send_xch(vec![(external, 100)], 0, vec![], None)
+                .await?,
+        )
+        .await?;
+        test.wait_for_coins().await;
+
+        // Both transactions count once they've been signed.
+        assert_eq!(
+            test.wallet
+                .db
+                .policy_spent_since(Bytes32::default(), 0)
+                .await?,
+            400
+        );
+
+        // The first transaction spends a coin that no longer exists, so it's rejected and its
+        // outflows no longer count towards the limit.
+        test.push_bundle(conflicting).await?;
+        test.consume_until(|event| matches!(event, SyncEvent::TransactionFailed { .. }))
+            .await;
+
+        assert_eq!(
+            test.wallet
+                .db
+                .policy_spent_since(Bytes32::default(), 0)
+                .await?,
+            100
+        );
+
+        Ok(())
+    }
+
+    #[test(tokio::test)]
+    async fn test_allowed_destinations() -> anyhow::Result<()> {
+        let test = TestWallet::new(1000).await?;
+        let allowed = Bytes32::new([1; 32]);
+        let other = Bytes32::new([2; 32]);
+
+        test.set_policy(
+            Some(SpendingPolicyRow {
+                max_fee: Some(10),
+                is_nft_transfer_blocked: false,
+                is_did_transfer_blocked: false,
+            }),
+            vec![],
+            vec![allowed],
+        )
+        .await?;
+
+        let coin_spends = test
+            .wallet
+            .send_xch(vec![(other, 100)], 0, vec![], None)
+            .await?;
+        assert!(is_violation(&test.sign(coin_spends).await));
+
+        let coin_spends = test
+            .wallet
+            .send_xch(
+                vec![(allowed, 100), (test.puzzle_hash, 100)],
+                10,
+                vec![],
+                None,
+            )
+            .await?;
+        test.sign(coin_spends).await?;
+
+        let coin_spends = test
+            .wallet
+            .send_xch(vec![(allowed, 100)], 11, vec![], None)
+            .await?;
+        assert!(is_violation(&test.sign(coin_spends).await));
+
+        // Without a policy, nothing is checked or recorded, so only the allowed transaction
+        // and its fee count.
+        test.set_policy(None, vec![], vec![]).await?;
+
+        let coin_spends = test
+            .wallet
+            .send_xch(vec![(other, 100)], 100, vec![], None)
+            .await?;
+        test.sign(coin_spends).await?;
+
+        assert_eq!(
+            test.wallet
+                .db
+                .policy_spent_since(Bytes32::default(), 0)
+                .await?,
+            110
+        );
+
+        Ok(())
+    }
+
+    #[test(tokio::test)]
+    async fn test_blocked_transfers() -> anyhow::Result<()> {
+        let mut test = TestWallet::new(2).await?;
+        let external = Bytes32::new([1; 32]);
+
+        let (coin_spends, did) = test.wallet.create_did(0).await?;
+        test.transact(coin_spends).await?;
+        test.wait_for_coins().await;
+
+        test.set_policy(
+            Some(SpendingPolicyRow {
+                max_fee: None,
+                is_nft_transfer_blocked: true,
+                is_did_transfer_blocked: true,
+            }),
+            vec![],
+            vec![],
+        )
+        .await?;
+
+        let coin_spends = test
+            .wallet
+            .transfer_dids(vec![did.info.launcher_id], external, 0, None)
+            .await?;
+        assert!(is_violation(&test.sign(coin_spends).await));
+
+        // Minting spends the DID, but it stays in the wallet.
+        let (coin_spends, nfts) = test
+            .wallet
+            .bulk_mint_nfts(
+                0,
+                did.info.launcher_id,
+                vec![WalletNftMint {
+                    metadata: NftMetadata::default(),
+                    p2_puzzle_hash: None,
+                    royalty_puzzle_hash: None,
+                    royalty_basis_points: 0,
+                }],
+            )
+            .await?;
+        test.transact(coin_spends).await?;
+        test.wait_for_coins().await;
+
+        let launcher_id = nfts[0].info.launcher_id;
+
+        let coin_spends = test
+            .wallet
+            .transfer_nfts(vec![launcher_id], external, 0, None)
+            .await?;
+        assert!(is_violation(&test.sign(coin_spends).await));
+
+        let coin_spends = test
+            .wallet
+            .transfer_nfts(vec![launcher_id], test.puzzle_hash, 0, None)
+            .await?;
+        test.sign(coin_spends).await?;
+
+        Ok(())
+    }
+}
@@ -143,6 +143,7 @@ impl Wallet {
 mod tests {
     use std::time::Duration;
 
+    use chia::protocol::{Bytes, Bytes32};
     use test_log::test;
     use tokio::time::sleep;
 
@@ -326,4 +327,52 @@ mod tests {
 
         Ok(())
     }
+
+    #[test(tokio::test)]
+    async fn test_send_xch_records_payments() -> anyhow::Result<()> {
+        let mut alice = TestWallet::new(1000).await?;
+        let bob = alice.next(0).await?;
+
+        let coin_spends = alice
+            .wallet
+            .send_xch(
+                vec![(bob.puzzle_hash, 250), (alice.puzzle_hash, 100)],
+                50,
+                vec![Bytes::from(b"invoice 42".to_vec())],
+                None,
+            )
+            .await?;
+
+        alice.transact(coin_spends).await?;
+        alice.wait_for_coins().await;
+
+        let transactions = alice
+            .wallet
+            .db
+            .confirmed_submitted_transactions(0, u32::MAX)
+            .await?;
+
+        assert_eq!(transactions.len(), 1);
+        assert_eq!(transactions[0].fee, 50);
+
+        // Only the payment to another wallet is kept, since our own coins are synced.
+        let payments = alice
+            .wallet
+            .db
+            .confirmed_submitted_payments(0, u32::MAX)
+            .await?;
+
+        assert_eq!(payments.len(), 1);
+        assert_eq!(payments[0].height, transactions[0].height);
+        assert_eq!(payments[0].transaction_id, transactions[0].hash);
+        assert_eq!(payments[0].payment.asset_hash, Bytes32::default());
+        assert_eq!(payments[0].payment.p2_puzzle_hash, bob.puzzle_hash);
+        assert_eq!(payments[0].payment.amount, 250);
+        assert_eq!(
+            payments[0].payment.memos,
+            vec![Bytes::from(b"invoice 42".to_vec())]
+        );
+
+        Ok(())
+    }
 }
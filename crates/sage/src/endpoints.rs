@@ -1,8 +1,20 @@
 mod actions;
+mod contacts;
 mod data;
+mod dids;
+mod exchanges;
 mod keys;
+mod mint_jobs;
+mod nft_integrity;
+mod offer_groups;
 mod offers;
+mod payouts;
+mod scheduled_payments;
+mod search;
 mod settings;
+mod spending_policy;
 mod themes;
+mod trades;
+mod transaction_history;
 mod transactions;
 mod wallet_connect;
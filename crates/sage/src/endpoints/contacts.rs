@@ -0,0 +1,173 @@
+use chia::protocol::Bytes32;
+use chia_wallet_sdk::utils::Address;
+use sage_api::{
+    AddContact, AddContactResponse, ContactAddress, ContactAddressRecord, ContactRecord,
+    DeleteContact, DeleteContactResponse, GetContact, GetContactResponse, GetContacts,
+    GetContactsResponse, UpdateContact, UpdateContactResponse,
+};
+use sage_database::{ContactAddressRow, ContactRow, Database};
+
+use crate::{parse_did_id, Error, Result, Sage};
+
+impl Sage {
+    pub async fn get_contacts(&self, _req: GetContacts) -> Result<GetContactsResponse> {
+        let wallet = self.wallet()?;
+
+        let addresses = wallet.db.all_contact_addresses().await?;
+
+        let contacts = wallet
+            .db
+            .contacts()
+            .await?
+            .into_iter()
+            .map(|row| {
+                let addresses = addresses
+                    .iter()
+                    .filter(|address| address.contact_id == row.id)
+                    .cloned()
+                    .collect();
+                self.contact_record(row, addresses)
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(GetContactsResponse { contacts })
+    }
+
+    pub async fn get_contact(&self, req: GetContact) -> Result<GetContactResponse> {
+        let wallet = self.wallet()?;
+
+        let Some(row) = wallet.db.contact(req.id).await? else {
+            return Ok(GetContactResponse { contact: None });
+        };
+
+        let contact = self.load_contact(&wallet.db, row).await?;
+
+        Ok(GetContactResponse {
+            contact: Some(contact),
+        })
+    }
+
+    pub async fn add_contact(&self, req: AddContact) -> Result<AddContactResponse> {
+        let wallet = self.wallet()?;
+
+        let name = parse_contact_name(req.name)?;
+        let did_id = req.did_id.map(parse_did_id).transpose()?;
+        let addresses = self.parse_contact_addresses(req.addresses)?;
+
+        let mut tx = wallet.db.tx().await?;
+
+        let id = tx.insert_contact(name, req.notes, did_id).await?;
+
+        for (puzzle_hash, label) in addresses {
+            tx.insert_contact_address(id, puzzle_hash, label).await?;
+        }
+
+        tx.commit().await?;
+
+        let row = wallet
+            .db
+            .contact(id)
+            .await?
+            .ok_or(Error::MissingContact(id))?;
+        let contact = self.load_contact(&wallet.db, row).await?;
+
+        Ok(AddContactResponse { contact })
+    }
+
+    pub async fn update_contact(&self, req: UpdateContact) -> Result<UpdateContactResponse> {
+        let wallet = self.wallet()?;
+
+        let name = parse_contact_name(req.name)?;
+        let did_id = req.did_id.map(parse_did_id).transpose()?;
+        let addresses = self.parse_contact_addresses(req.addresses)?;
+
+        let mut tx = wallet.db.tx().await?;
+
+        if tx.contact(req.id).await?.is_none() {
+            return Err(Error::MissingContact(req.id));
+        }
+
+        tx.update_contact(req.id, name, req.notes, did_id).await?;
+        tx.delete_contact_addresses(req.id).await?;
+
+        for (puzzle_hash, label) in addresses {
+            tx.insert_contact_address(req.id, puzzle_hash, label)
+                .await?;
+        }
+
+        tx.commit().await?;
+
+        let row = wallet
+            .db
+            .contact(req.id)
+            .await?
+            .ok_or(Error::MissingContact(req.id))?;
+        let contact = self.load_contact(&wallet.db, row).await?;
+
+        Ok(UpdateContactResponse { contact })
+    }
+
+    pub async fn delete_contact(&self, req: DeleteContact) -> Result<DeleteContactResponse> {
+        let wallet = self.wallet()?;
+        wallet.db.delete_contact(req.id).await?;
+        Ok(DeleteContactResponse {})
+    }
+
+    fn parse_contact_addresses(
+        &self,
+        addresses: Vec<ContactAddress>,
+    ) -> Result<Vec<(Bytes32, Option<String>)>> {
+        addresses
+            .into_iter()
+            .map(|address| {
+                let label = address
+                    .label
+                    .map(|label| label.trim().to_string())
+                    .filter(|label| !label.is_empty());
+                Ok((self.parse_address(address.address)?, label))
+            })
+            .collect()
+    }
+
+    async fn load_contact(&self, db: &Database, row: ContactRow) -> Result<ContactRecord> {
+        let addresses = db.contact_addresses(row.id).await?;
+        self.contact_record(row, addresses)
+    }
+
+    fn contact_record(
+        &self,
+        row: ContactRow,
+        addresses: Vec<ContactAddressRow>,
+    ) -> Result<ContactRecord> {
+        Ok(ContactRecord {
+            id: row.id,
+            name: row.name,
+            notes: row.notes,
+            did_id: row
+                .did_id
+                .map(|did_id| Address::new(did_id, "did:chia:".to_string()).encode())
+                .transpose()?,
+            addresses: addresses
+                .into_iter()
+                .map(|address| {
+                    Ok(ContactAddressRecord {
+                        address: Address::new(address.puzzle_hash, self.network().prefix())
+                            .encode()?,
+                        label: address.label,
+                    })
+                })
+                .collect::<Result<Vec<_>>>()?,
+            created_timestamp: row.created_timestamp,
+        })
+    }
+}
+
+fn parse_contact_name(name: String) -> Result<String> {
+    let name = name.trim();
+
+    if name.is_empty() {
+        return Err(Error::EmptyContactName);
+    }
+
+    Ok(name.to_string())
+}
@@ -1,3 +1,5 @@
+use std::collections::HashMap;
+
 use crate::{
     address_kind, parse_asset_id, parse_collection_id, parse_did_id, parse_nft_id, parse_option_id,
     Error, Result, Sage,
@@ -11,29 +13,36 @@ use chia::{
 use chia_wallet_sdk::{driver::BURN_PUZZLE_HASH, utils::Address};
 use clvmr::Allocator;
 use sage_api::{
-    Amount, CheckAddress, CheckAddressResponse, CoinFilterMode as ApiCoinFilterMode, CoinRecord,
-    CoinSortMode as ApiCoinSortMode, DerivationRecord, DidRecord, GetAllCats, GetAllCatsResponse,
-    GetAreCoinsSpendable, GetAreCoinsSpendableResponse, GetCats, GetCatsResponse, GetCoins,
-    GetCoinsByIds, GetCoinsByIdsResponse, GetCoinsResponse, GetDatabaseStats,
-    GetDatabaseStatsResponse, GetDerivations, GetDerivationsResponse, GetDids, GetDidsResponse,
-    GetMinterDidIds, GetMinterDidIdsResponse, GetNft, GetNftCollection, GetNftCollectionResponse,
-    GetNftCollections, GetNftCollectionsResponse, GetNftData, GetNftDataResponse, GetNftIcon,
-    GetNftIconResponse, GetNftResponse, GetNftThumbnail, GetNftThumbnailResponse, GetNfts,
-    GetNftsResponse, GetOption, GetOptionResponse, GetOptions, GetOptionsResponse,
+    Amount, CheckAddress, CheckAddressResponse, ClearMediaCache, ClearMediaCacheResponse,
+    CoinFilterMode as ApiCoinFilterMode, CoinRecord, CoinSortMode as ApiCoinSortMode,
+    DerivationRecord, DidRecord, GetAllCats, GetAllCatsResponse, GetAreCoinsSpendable,
+    GetAreCoinsSpendableResponse, GetCats, GetCatsResponse, GetCoins, GetCoinsByIds,
+    GetCoinsByIdsResponse, GetCoinsResponse, GetDatabaseStats, GetDatabaseStatsResponse,
+    GetDerivations, GetDerivationsResponse, GetDids, GetDidsResponse, GetMediaCacheStats,
+    GetMediaCacheStatsResponse, GetMediaPins, GetMediaPinsResponse, GetMinterDidIds,
+    GetMinterDidIdsResponse, GetNft, GetNftCollection, GetNftCollectionResponse,
+    GetNftCollectionTraits, GetNftCollectionTraitsResponse, GetNftCollections,
+    GetNftCollectionsResponse, GetNftData, GetNftDataResponse, GetNftIcon, GetNftIconResponse,
+    GetNftPreview, GetNftPreviewResponse, GetNftResponse, GetNftThumbnail, GetNftThumbnailResponse,
+    GetNfts, GetNftsResponse, GetOption, GetOptionResponse, GetOptions, GetOptionsResponse,
     GetPendingTransactions, GetPendingTransactionsResponse, GetSpendableCoinCount,
     GetSpendableCoinCountResponse, GetSyncStatus, GetSyncStatusResponse, GetToken,
     GetTokenResponse, GetTransaction, GetTransactionResponse, GetTransactions,
     GetTransactionsResponse, GetVersion, GetVersionResponse, IsAssetOwned, IsAssetOwnedResponse,
-    NftCollectionRecord, NftData, NftRecord, NftSortMode as ApiNftSortMode, NftSpecialUseType,
-    OptionRecord, OptionSortMode as ApiOptionSortMode, PendingTransactionRecord,
-    PerformDatabaseMaintenance, PerformDatabaseMaintenanceResponse, TokenRecord,
-    TransactionCoinRecord, TransactionRecord,
+    NftCollectionRecord, NftData, NftPreviewSource, NftRecord, NftSortMode as ApiNftSortMode,
+    NftSpecialUseType, NftTraitCount, OptionRecord, OptionSortMode as ApiOptionSortMode,
+    PendingTransactionRecord, PerformDatabaseMaintenance, PerformDatabaseMaintenanceResponse,
+    PinNftMedia, PinNftMediaResponse, SetMediaCachePolicy, SetMediaCachePolicyResponse,
+    TokenRecord, TransactionCoinRecord, TransactionRecord,
 };
 use sage_database::{
-    AssetFilter, CoinFilterMode, CoinSortMode, NftGroupSearch, NftRow, NftSortMode, OptionSortMode,
+    AssetFilter, CoinFilterMode, CoinSortMode, MediaCachePolicy, MediaPin, MediaPinKind,
+    NftGroupSearch, NftRow, NftSortMode, NftTraitFilter, OptionSortMode, ResizedImageSource,
     Transaction, TransactionCoin,
 };
 
+use super::dids::did_metadata_entries;
+
 impl Sage {
     pub fn get_version(&self, _req: GetVersion) -> Result<GetVersionResponse> {
         Ok(GetVersionResponse {
@@ -83,6 +92,133 @@ impl Sage {
         Ok(response)
     }
 
+    pub async fn get_media_cache_stats(
+        &self,
+        _req: GetMediaCacheStats,
+    ) -> Result<GetMediaCacheStatsResponse> {
+        let wallet = self.wallet()?;
+
+        let policy = wallet.db.media_cache_policy().await?;
+        let stats = wallet.db.media_cache_stats().await?;
+
+        Ok(GetMediaCacheStatsResponse {
+            max_bytes: policy.and_then(|policy| policy.max_bytes),
+            store_externally: policy.is_some_and(|policy| policy.is_external),
+            cached_files: stats.cached_files,
+            cached_bytes: stats.cached_bytes,
+            external_files: stats.external_files,
+            external_bytes: stats.external_bytes,
+            pinned_files: stats.pinned_files,
+            pinned_bytes: stats.pinned_bytes,
+            resized_image_bytes: stats.resized_image_bytes,
+        })
+    }
+
+    pub async fn set_media_cache_policy(
+        &self,
+        req: SetMediaCachePolicy,
+    ) -> Result<SetMediaCachePolicyResponse> {
+        let wallet = self.wallet()?;
+
+        let mut tx = wallet.db.tx().await?;
+
+        tx.set_media_cache_policy(MediaCachePolicy {
+            max_bytes: req.max_bytes,
+            is_external: req.store_externally,
+        })
+        .await?;
+
+        // Files are evicted first, so that they don't need to be moved.
+        let eviction = tx.enforce_media_cache_policy().await?;
+        let relocated_files = tx.relocate_file_data(req.store_externally).await?;
+
+        tx.commit().await?;
+
+        Ok(SetMediaCachePolicyResponse {
+            evicted_files: eviction.files,
+            evicted_bytes: eviction.bytes,
+            relocated_files,
+        })
+    }
+
+    pub async fn pin_nft_media(&self, req: PinNftMedia) -> Result<PinNftMediaResponse> {
+        let wallet = self.wallet()?;
+
+        let mut pins = Vec::new();
+
+        for nft_id in req.nft_ids {
+            pins.push(MediaPin {
+                kind: MediaPinKind::Nft,
+                hash: parse_nft_id(nft_id)?,
+            });
+        }
+
+        for collection_id in req.collection_ids {
+            pins.push(MediaPin {
+                kind: MediaPinKind::Collection,
+                hash: parse_collection_id(collection_id)?,
+            });
+        }
+
+        let mut tx = wallet.db.tx().await?;
+
+        for pin in pins {
+            if req.pinned {
+                tx.insert_media_pin(pin).await?;
+            } else {
+                tx.delete_media_pin(pin).await?;
+            }
+        }
+
+        // Unpinned files count towards the budget again, which may now be exceeded.
+        if !req.pinned {
+            tx.enforce_media_cache_policy().await?;
+        }
+
+        tx.commit().await?;
+
+        Ok(PinNftMediaResponse {})
+    }
+
+    pub async fn get_media_pins(&self, _req: GetMediaPins) -> Result<GetMediaPinsResponse> {
+        let wallet = self.wallet()?;
+
+        let mut nft_ids = Vec::new();
+        let mut collection_ids = Vec::new();
+
+        for pin in wallet.db.media_pins().await? {
+            match pin.kind {
+                MediaPinKind::Nft => {
+                    nft_ids.push(Address::new(pin.hash, "nft".to_string()).encode()?);
+                }
+                MediaPinKind::Collection => {
+                    collection_ids.push(Address::new(pin.hash, "col".to_string()).encode()?);
+                }
+            }
+        }
+
+        Ok(GetMediaPinsResponse {
+            nft_ids,
+            collection_ids,
+        })
+    }
+
+    pub async fn clear_media_cache(
+        &self,
+        _req: ClearMediaCache,
+    ) -> Result<ClearMediaCacheResponse> {
+        let wallet = self.wallet()?;
+
+        let mut tx = wallet.db.tx().await?;
+        let eviction = tx.evict_media(0).await?;
+        tx.commit().await?;
+
+        Ok(ClearMediaCacheResponse {
+            evicted_files: eviction.files,
+            evicted_bytes: eviction.bytes,
+        })
+    }
+
     pub async fn get_sync_status(&self, _req: GetSyncStatus) -> Result<GetSyncStatusResponse> {
         let wallet = self.wallet()?;
 
@@ -131,6 +267,10 @@ impl Sage {
             return Ok(CheckAddressResponse { valid: false });
         };
 
+        if address.prefix != self.network().prefix() {
+            return Ok(CheckAddressResponse { valid: false });
+        }
+
         let is_valid = wallet
             .db
             .is_custody_p2_puzzle_hash(address.puzzle_hash)
@@ -369,6 +509,11 @@ impl Sage {
                     .encode()?,
                 amount: Amount::u64(row.coin_row.coin.amount),
                 recovery_hash: row.did_info.recovery_list_hash.map(hex::encode),
+                recovery_did_ids: self
+                    .did_recovery_ids(&wallet, row.did_info.recovery_list_hash)
+                    .await?,
+                num_verifications_required: row.did_info.num_verifications_required,
+                metadata: did_metadata_entries(&row.did_info.metadata),
                 created_height: row.coin_row.created_height,
             });
         }
@@ -519,9 +664,10 @@ impl Sage {
         let wallet = self.wallet()?;
 
         let transaction = wallet.db.transaction(req.height).await?;
+        let contacts = wallet.db.contact_names().await?;
 
         let transaction = transaction
-            .map(|row| self.transaction_record(row))
+            .map(|row| self.transaction_record(row, &contacts))
             .transpose()?;
 
         Ok(GetTransactionResponse { transaction })
@@ -537,8 +683,10 @@ impl Sage {
             .transactions(req.find_value, req.ascending, req.limit, req.offset)
             .await?;
 
+        let contacts = wallet.db.contact_names().await?;
+
         for row in transaction_records {
-            let record = self.transaction_record(row)?;
+            let record = self.transaction_record(row, &contacts)?;
             transactions.push(record);
         }
 
@@ -579,6 +727,29 @@ impl Sage {
         })
     }
 
+    pub async fn get_nft_collection_traits(
+        &self,
+        req: GetNftCollectionTraits,
+    ) -> Result<GetNftCollectionTraitsResponse> {
+        let wallet = self.wallet()?;
+
+        let collection_id = parse_collection_id(req.collection_id)?;
+
+        let (traits, total) = wallet.db.collection_trait_counts(collection_id).await?;
+
+        Ok(GetNftCollectionTraitsResponse {
+            traits: traits
+                .into_iter()
+                .map(|row| NftTraitCount {
+                    trait_type: row.trait_type,
+                    value: row.value,
+                    count: row.count,
+                })
+                .collect(),
+            total,
+        })
+    }
+
     pub async fn get_nft_collection(
         &self,
         req: GetNftCollection,
@@ -663,11 +834,29 @@ impl Sage {
             ApiNftSortMode::Name => NftSortMode::Name,
         };
 
+        let traits = req
+            .traits
+            .into_iter()
+            .map(|filter| match (filter.value, filter.min, filter.max) {
+                (Some(value), None, None) => Ok(NftTraitFilter::Value {
+                    trait_type: filter.trait_type,
+                    value,
+                }),
+                (None, min, max) if min.is_some() || max.is_some() => Ok(NftTraitFilter::Range {
+                    trait_type: filter.trait_type,
+                    min,
+                    max,
+                }),
+                _ => Err(Error::InvalidTraitFilter(filter.trait_type)),
+            })
+            .collect::<Result<Vec<_>>>()?;
+
         let (nfts, total) = wallet
             .db
             .owned_nfts(
                 req.name,
                 group,
+                &traits,
                 sort_mode,
                 req.include_hidden,
                 req.limit,
@@ -716,7 +905,14 @@ impl Sage {
         let metadata_hash = metadata.as_ref().and_then(|m| m.metadata_hash);
 
         let data = if let Some(hash) = data_hash {
-            wallet.db.full_file_data(hash).await?
+            wallet.db.touch_file(hash).await?;
+
+            // Evicted data is downloaded again, so that it's available the next time it's viewed.
+            if wallet.db.requeue_evicted_file(hash).await? {
+                None
+            } else {
+                wallet.db.full_file_data(hash).await?
+            }
         } else {
             None
         };
@@ -796,6 +992,41 @@ impl Sage {
         })
     }
 
+    pub async fn get_nft_preview(&self, req: GetNftPreview) -> Result<GetNftPreviewResponse> {
+        let wallet = self.wallet()?;
+
+        let nft_id = parse_nft_id(req.nft_id)?;
+
+        let Some(nft) = wallet.db.nft(nft_id).await? else {
+            return Ok(GetNftPreviewResponse {
+                preview: None,
+                source: None,
+            });
+        };
+
+        let mut allocator = Allocator::new();
+        let metadata_ptr = nft.info.metadata.to_clvm(&mut allocator)?;
+        let metadata = NftMetadata::from_clvm(&allocator, metadata_ptr).ok();
+
+        let Some(data_hash) = metadata.as_ref().and_then(|m| m.data_hash) else {
+            return Ok(GetNftPreviewResponse {
+                preview: None,
+                source: None,
+            });
+        };
+
+        // Files downloaded before previews were added only have a thumbnail.
+        let image = match wallet.db.preview(data_hash).await? {
+            Some(preview) => Some(preview),
+            None => wallet.db.thumbnail(data_hash).await?,
+        };
+
+        Ok(GetNftPreviewResponse {
+            source: image.as_ref().map(|image| preview_source(image.source)),
+            preview: image.map(|image| BASE64_STANDARD.encode(image.data)),
+        })
+    }
+
     fn nft_record(&self, row: NftRow) -> Result<NftRecord> {
         let mut allocator = Allocator::new();
         let metadata_ptr = row.nft_info.metadata.to_clvm(&mut allocator)?;
@@ -869,7 +1100,17 @@ impl Sage {
         })
     }
 
-    fn transaction_coin(&self, transaction_coin: TransactionCoin) -> Result<TransactionCoinRecord> {
+    fn transaction_coin(
+        &self,
+        transaction_coin: TransactionCoin,
+        contacts: &HashMap<Bytes32, String>,
+    ) -> Result<TransactionCoinRecord> {
+        let contact = transaction_coin
+            .p2_puzzle_hash
+            .and_then(|p2_puzzle_hash| contacts.get(&p2_puzzle_hash))
+            .or_else(|| contacts.get(&transaction_coin.coin.puzzle_hash))
+            .cloned();
+
         Ok(TransactionCoinRecord {
             coin_id: hex::encode(transaction_coin.coin.coin_id()),
             address: transaction_coin
@@ -881,18 +1122,23 @@ impl Sage {
             address_kind: address_kind(transaction_coin.p2_puzzle_hash),
             amount: Amount::u64(transaction_coin.coin.amount),
             asset: self.encode_asset(transaction_coin.asset)?,
+            contact,
         })
     }
 
-    fn transaction_record(&self, transaction: Transaction) -> Result<TransactionRecord> {
+    fn transaction_record(
+        &self,
+        transaction: Transaction,
+        contacts: &HashMap<Bytes32, String>,
+    ) -> Result<TransactionRecord> {
         let mut spent = Vec::new();
         let mut created = Vec::new();
 
         for coin in transaction.created {
-            created.push(self.transaction_coin(coin)?);
+            created.push(self.transaction_coin(coin, contacts)?);
         }
         for coin in transaction.spent {
-            spent.push(self.transaction_coin(coin)?);
+            spent.push(self.transaction_coin(coin, contacts)?);
         }
 
         Ok(TransactionRecord {
@@ -903,3 +1149,13 @@ impl Sage {
         })
     }
 }
+
+fn preview_source(source: ResizedImageSource) -> NftPreviewSource {
+    match source {
+        ResizedImageSource::Image => NftPreviewSource::Image,
+        ResizedImageSource::Vector => NftPreviewSource::Vector,
+        ResizedImageSource::Animation => NftPreviewSource::Animation,
+        ResizedImageSource::CoverArt => NftPreviewSource::CoverArt,
+        ResizedImageSource::Placeholder => NftPreviewSource::Placeholder,
+    }
+}
@@ -0,0 +1,386 @@
+use chia::{
+    bls::{master_to_wallet_hardened, master_to_wallet_unhardened, sign, verify},
+    clvm_traits::{FromClvm, ToClvm},
+    clvm_utils::ToTreeHash,
+    protocol::{Bytes32, Program, SpendBundle},
+    puzzles::{standard::StandardArgs, DeriveSynthetic},
+    traits::Streamable,
+};
+use chia_wallet_sdk::{signer::AggSigConstants, utils::Address};
+use clvmr::Allocator;
+use sage_api::{
+    CreateDidAttestation, CreateDidAttestationResponse, DidMetadataEntry, GetDidRecoveryInfo,
+    GetDidRecoveryInfoResponse, RecoverDid, RecoverDidResponse, SetDidRecoveryList,
+    SignMessageByDid, SignMessageByDidResponse, TransactionResponse, UpdateDidMetadata,
+    VerifyDidSignature, VerifyDidSignatureResponse,
+};
+use sage_wallet::{insert_transaction, recovery_list_hash, SyncCommand, Transaction, Wallet};
+
+use crate::{
+    json_bundle, parse_amount, parse_coin_id, parse_did_id, parse_hash, parse_public_key,
+    parse_signature, parse_signature_message, ConfirmationInfo, Error, Result, Sage,
+};
+
+impl Sage {
+    pub async fn update_did_metadata(&self, req: UpdateDidMetadata) -> Result<TransactionResponse> {
+        let wallet = self.wallet()?;
+        let did_id = parse_did_id(req.did_id)?;
+        let metadata = did_metadata_program(req.metadata)?;
+        let fee = parse_amount(req.fee)?;
+
+        let coin_spends = wallet.update_did(did_id, Some(metadata), None, fee).await?;
+        self.transact(coin_spends, req.auto_submit).await
+    }
+
+    pub async fn set_did_recovery_list(
+        &self,
+        req: SetDidRecoveryList,
+    ) -> Result<TransactionResponse> {
+        let wallet = self.wallet()?;
+        let did_id = parse_did_id(req.did_id)?;
+        let recovery_list = req
+            .recovery_did_ids
+            .into_iter()
+            .map(parse_did_id)
+            .collect::<Result<Vec<_>>>()?;
+        let fee = parse_amount(req.fee)?;
+
+        // The DID puzzle doesn't allow recovery if no verifications are required, so that's only
+        // valid when recovery is disabled with an empty list.
+        let num_recovery_dids = recovery_list.len() as u64;
+
+        if num_recovery_dids > 0
+            && !(1..=num_recovery_dids).contains(&req.num_verifications_required)
+        {
+            return Err(Error::InvalidRecoveryList(format!(
+                "Between 1 and {num_recovery_dids} verifications must be required"
+            )));
+        }
+
+        let coin_spends = wallet
+            .update_did(
+                did_id,
+                None,
+                Some((recovery_list.clone(), req.num_verifications_required)),
+                fee,
+            )
+            .await?;
+
+        wallet
+            .db
+            .insert_did_recovery_list(recovery_list_hash(&recovery_list), &recovery_list)
+            .await?;
+
+        self.transact(coin_spends, req.auto_submit).await
+    }
+
+    pub async fn sign_message_by_did(
+        &self,
+        req: SignMessageByDid,
+    ) -> Result<SignMessageByDidResponse> {
+        let wallet = self.wallet()?;
+        let did_id = parse_did_id(req.did_id)?;
+
+        let Some(did) = wallet.db.did(did_id).await? else {
+            return Err(Error::MissingDid(did_id));
+        };
+
+        let Some(public_key) = wallet.db.public_key(did.info.p2_puzzle_hash).await? else {
+            return Err(Error::InvalidKey);
+        };
+
+        let Some(info) = wallet.db.derivation(public_key).await? else {
+            return Err(Error::InvalidKey);
+        };
+
+        let (_mnemonic, Some(master_sk)) =
+            self.keychain.extract_secrets(wallet.fingerprint, b"")?
+        else {
+            return Err(Error::NoSigningKey);
+        };
+
+        let secret_key = if info.is_hardened {
+            master_to_wallet_hardened(&master_sk, info.derivation_index)
+        } else {
+            master_to_wallet_unhardened(&master_sk, info.derivation_index)
+        }
+        .derive_synthetic();
+
+        let decoded_message = parse_signature_message(req.message)?;
+        let signature = sign(
+            &secret_key,
+            ("Chia Signed Message", decoded_message).tree_hash(),
+        );
+
+        Ok(SignMessageByDidResponse {
+            public_key: hex::encode(public_key.to_bytes()),
+            signature: hex::encode(signature.to_bytes()),
+        })
+    }
+
+    pub async fn verify_did_signature(
+        &self,
+        req: VerifyDidSignature,
+    ) -> Result<VerifyDidSignatureResponse> {
+        let wallet = self.wallet()?;
+        let did_id = parse_did_id(req.did_id)?;
+        let public_key = parse_public_key(req.public_key)?;
+        let signature = parse_signature(req.signature)?;
+        let decoded_message = parse_signature_message(req.message)?;
+
+        let did = if let Some(did) = wallet.db.did(did_id).await? {
+            did
+        } else {
+            let peer = self
+                .peer_state
+                .lock()
+                .await
+                .acquire_peer()
+                .ok_or(Error::NoPeers)?;
+
+            wallet
+                .fetch_did(&peer, did_id)
+                .await?
+                .ok_or(Error::MissingDid(did_id))?
+        };
+
+        let p2_puzzle_hash: Bytes32 = StandardArgs::curry_tree_hash(public_key).into();
+
+        let is_valid = p2_puzzle_hash == did.info.p2_puzzle_hash
+            && verify(
+                &signature,
+                &public_key,
+                ("Chia Signed Message", decoded_message).tree_hash(),
+            );
+
+        Ok(VerifyDidSignatureResponse { is_valid })
+    }
+
+    pub async fn get_did_recovery_info(
+        &self,
+        req: GetDidRecoveryInfo,
+    ) -> Result<GetDidRecoveryInfoResponse> {
+        let wallet = self.wallet()?;
+        let did_id = parse_did_id(req.did_id)?;
+
+        let public_key = if let Some(public_key) = req.public_key {
+            parse_public_key(public_key)?
+        } else {
+            wallet
+                .db
+                .public_key(wallet.change_p2_puzzle_hash().await?)
+                .await?
+                .ok_or(Error::InvalidKey)?
+        };
+
+        let peer = self
+            .peer_state
+            .lock()
+            .await
+            .acquire_peer()
+            .ok_or(Error::NoPeers)?;
+
+        let info = wallet
+            .did_recovery_info(&peer, did_id, public_key)
+            .await?
+            .ok_or(Error::MissingDid(did_id))?;
+
+        Ok(GetDidRecoveryInfoResponse {
+            coin_id: hex::encode(info.coin_id),
+            new_inner_puzzle_hash: hex::encode(info.new_inner_puzzle_hash),
+            public_key: hex::encode(public_key.to_bytes()),
+            recovery_list_hash: info.recovery_list_hash.map(hex::encode),
+            recovery_did_ids: self
+                .did_recovery_ids(&wallet, info.recovery_list_hash)
+                .await?,
+            num_verifications_required: info.num_verifications_required,
+        })
+    }
+
+    pub async fn create_did_attestation(
+        &self,
+        req: CreateDidAttestation,
+    ) -> Result<CreateDidAttestationResponse> {
+        let wallet = self.wallet()?;
+        let did_id = parse_did_id(req.did_id)?;
+        let coin_id = parse_coin_id(req.coin_id)?;
+        let new_inner_puzzle_hash = parse_hash(req.new_inner_puzzle_hash)?;
+        let public_key = parse_public_key(req.public_key)?;
+
+        let coin_spends = wallet
+            .create_did_attestation(did_id, coin_id, new_inner_puzzle_hash, public_key)
+            .await?;
+
+        let spend_bundle = self.sign(coin_spends, false).await?;
+
+        Ok(CreateDidAttestationResponse {
+            attestation: hex::encode(spend_bundle.to_bytes()?),
+        })
+    }
+
+    pub async fn recover_did(&self, req: RecoverDid) -> Result<RecoverDidResponse> {
+        let wallet = self.wallet()?;
+        let did_id = parse_did_id(req.did_id)?;
+        let recovery_list = req
+            .recovery_did_ids
+            .into_iter()
+            .map(parse_did_id)
+            .collect::<Result<Vec<_>>>()?;
+        let attestations = req
+            .attestations
+            .into_iter()
+            .map(parse_attestation)
+            .collect::<Result<Vec<_>>>()?;
+        let public_key = parse_public_key(req.public_key)?;
+        let fee = parse_amount(req.fee)?;
+
+        if wallet.db.derivation(public_key).await?.is_none() {
+            return Err(Error::InvalidKey);
+        }
+
+        let peer = self
+            .peer_state
+            .lock()
+            .await
+            .acquire_peer()
+            .ok_or(Error::NoPeers)?;
+
+        let unsigned = wallet
+            .recover_did(&peer, did_id, &recovery_list, attestations, public_key, fee)
+            .await?;
+
+        let (_mnemonic, Some(master_sk)) =
+            self.keychain.extract_secrets(wallet.fingerprint, b"")?
+        else {
+            return Err(Error::NoSigningKey);
+        };
+
+        let spend_bundle = wallet
+            .sign_transaction(
+                unsigned,
+                &AggSigConstants::new(self.network().agg_sig_me()),
+                master_sk,
+                true,
+            )
+            .await?;
+
+        if req.auto_submit {
+            let subscriptions = insert_transaction(
+                &wallet.db,
+                &peer,
+                wallet.genesis_challenge,
+                spend_bundle.name(),
+                Transaction::from_coin_spends(spend_bundle.coin_spends.clone())?,
+                spend_bundle.aggregated_signature.clone(),
+            )
+            .await?;
+
+            self.command_sender
+                .send(SyncCommand::SubscribeCoins {
+                    coin_ids: subscriptions,
+                })
+                .await?;
+        }
+
+        let json_bundle = json_bundle(&spend_bundle);
+        let transaction_id = hex::encode(spend_bundle.name());
+
+        Ok(RecoverDidResponse {
+            summary: self
+                .summarize(spend_bundle.coin_spends, ConfirmationInfo::default())
+                .await?,
+            spend_bundle: json_bundle,
+            transaction_id,
+        })
+    }
+
+    /// The DIDs in a recovery list, if the list is empty or was set by this wallet.
+    pub(crate) async fn did_recovery_ids(
+        &self,
+        wallet: &Wallet,
+        hash: Option<Bytes32>,
+    ) -> Result<Option<Vec<String>>> {
+        let Some(hash) = hash else {
+            return Ok(Some(Vec::new()));
+        };
+
+        if hash == recovery_list_hash(&[]) {
+            return Ok(Some(Vec::new()));
+        }
+
+        let recovery_list = wallet.db.did_recovery_list(hash).await?;
+
+        if recovery_list.is_empty() {
+            return Ok(None);
+        }
+
+        Ok(Some(
+            recovery_list
+                .into_iter()
+                .map(|did_id| Ok(Address::new(did_id, "did:chia:".to_string()).encode()?))
+                .collect::<Result<_>>()?,
+        ))
+    }
+}
+
+/// DID metadata is a list of key value pairs by convention, but it can be anything. Metadata
+/// which isn't in that format is treated as empty.
+pub(crate) fn did_metadata_entries(metadata: &Program) -> Vec<DidMetadataEntry> {
+    let mut allocator = Allocator::new();
+
+    let Ok(ptr) = metadata.to_clvm(&mut allocator) else {
+        return Vec::new();
+    };
+
+    Vec::<(String, String)>::from_clvm(&allocator, ptr)
+        .unwrap_or_default()
+        .into_iter()
+        .map(|(key, value)| DidMetadataEntry { key, value })
+        .collect()
+}
+
+/// Attestations are the hex encoded spend bundles which create their message coins.
+fn parse_attestation(input: String) -> Result<SpendBundle> {
+    let stripped = input.strip_prefix("0x").unwrap_or(&input);
+
+    SpendBundle::from_bytes(&hex::decode(stripped)?).map_err(|_| Error::InvalidAttestation(input))
+}
+
+fn did_metadata_program(entries: Vec<DidMetadataEntry>) -> Result<Program> {
+    let mut allocator = Allocator::new();
+
+    let ptr = entries
+        .into_iter()
+        .map(|entry| (entry.key, entry.value))
+        .collect::<Vec<_>>()
+        .to_clvm(&mut allocator)?;
+
+    Ok(Program::from_clvm(&allocator, ptr)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_did_metadata() -> Result<()> {
+        let entries = vec![
+            DidMetadataEntry {
+                key: "name".to_string(),
+                value: "Alice".to_string(),
+            },
+            DidMetadataEntry {
+                key: "website".to_string(),
+                value: "https://example.com".to_string(),
+            },
+        ];
+
+        let metadata = did_metadata_program(entries.clone())?;
+        assert_eq!(did_metadata_entries(&metadata), entries);
+
+        assert_eq!(did_metadata_entries(&did_metadata_program(vec![])?), vec![]);
+        assert_eq!(did_metadata_entries(&Program::from(vec![0x01])), vec![]);
+
+        Ok(())
+    }
+}
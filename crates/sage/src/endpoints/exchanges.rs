@@ -0,0 +1,99 @@
+use chia_wallet_sdk::driver::decode_offer;
+use sage_api::{
+    ExchangeOfferRecord, FetchOfferByCode, FetchOfferByCodeResponse, PublishOffer,
+    PublishOfferResponse, SearchExchangeOffers, SearchExchangeOffersResponse,
+};
+use sage_config::ExchangeKind;
+use tracing::debug;
+
+use crate::{parse_offer_id, Error, Exchange, OfferExchange, OfferSearch, Result, Sage};
+
+impl Sage {
+    pub async fn publish_offer(&self, req: PublishOffer) -> Result<PublishOfferResponse> {
+        let wallet = self.wallet()?;
+        let offer_id = parse_offer_id(req.offer_id)?;
+
+        let Some(offer) = wallet.db.offer(offer_id).await? else {
+            return Err(Error::MissingOffer(offer_id));
+        };
+
+        let code = self
+            .exchange(Some(&req.exchange))?
+            .publish_offer(&offer.encoded_offer)
+            .await?;
+
+        wallet
+            .db
+            .insert_offer_publication(offer_id, req.exchange, code.clone())
+            .await?;
+
+        Ok(PublishOfferResponse { code })
+    }
+
+    pub async fn fetch_offer_by_code(
+        &self,
+        req: FetchOfferByCode,
+    ) -> Result<FetchOfferByCodeResponse> {
+        let offer = self
+            .exchange(req.exchange.as_deref())?
+            .fetch_valid_offer(&req.code)
+            .await?;
+
+        Ok(FetchOfferByCodeResponse { offer })
+    }
+
+    pub async fn search_exchange_offers(
+        &self,
+        req: SearchExchangeOffers,
+    ) -> Result<SearchExchangeOffersResponse> {
+        let offers = self
+            .exchange(Some(&req.exchange))?
+            .search_offers(&OfferSearch {
+                offered: req.offered,
+                requested: req.requested,
+                page: req.page,
+                page_size: req.page_size,
+            })
+            .await?;
+
+        // Listings that aren't usable offers are skipped rather than failing the search
+        let offers = offers
+            .into_iter()
+            .filter(|offer| match decode_offer(&offer.offer) {
+                Ok(_) => true,
+                Err(error) => {
+                    debug!(
+                        "Skipping invalid offer {} from exchange: {error}",
+                        offer.code
+                    );
+                    false
+                }
+            })
+            .map(|offer| ExchangeOfferRecord {
+                code: offer.code,
+                offer: offer.offer,
+            })
+            .collect();
+
+        Ok(SearchExchangeOffersResponse { offers })
+    }
+
+    /// The exchange with the given name, or the first offer code service if there isn't one.
+    pub fn exchange(&self, name: Option<&str>) -> Result<Exchange> {
+        let config = if let Some(name) = name {
+            self.config
+                .exchanges
+                .iter()
+                .find(|exchange| exchange.name == name)
+                .ok_or_else(|| Error::UnknownExchange(name.to_string()))?
+        } else {
+            self.config
+                .exchanges
+                .iter()
+                .find(|exchange| exchange.kind == ExchangeKind::OfferCode)
+                .ok_or_else(|| Error::UnknownExchange("offer code".to_string()))?
+        };
+
+        Exchange::from_config(config)
+    }
+}
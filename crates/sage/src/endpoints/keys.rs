@@ -236,6 +236,11 @@ impl Sage {
             if db_file.try_exists()? {
                 fs::remove_file(&db_file)?;
             }
+
+            let media_dir = path.join(format!("{}.media", req.network));
+            if media_dir.try_exists()? {
+                fs::remove_dir_all(&media_dir)?;
+            }
         }
 
         Ok(DeleteDatabaseResponse {})
@@ -319,6 +324,7 @@ impl Sage {
                 has_secrets: self.keychain.has_secret_key(fingerprint),
                 network_id,
                 emoji: wallet_config.emoji,
+                background_signing: wallet_config.background_signing,
             }),
         })
     }
@@ -353,6 +359,7 @@ impl Sage {
                 has_secrets: self.keychain.has_secret_key(wallet.fingerprint),
                 network_id: wallet.network.clone().unwrap_or_else(|| self.network_id()),
                 emoji: wallet.emoji.clone(),
+                background_signing: wallet.background_signing,
             });
         }
 
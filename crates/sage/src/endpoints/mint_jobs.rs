@@ -0,0 +1,434 @@
+use std::time::Duration;
+
+use chia::{protocol::Bytes32, puzzles::nft::NftMetadata};
+use chia_wallet_sdk::utils::Address;
+use futures_util::{stream, StreamExt, TryStreamExt};
+use sage_api::{
+    Amount, CreateMintJob, CreateMintJobResponse, DeleteMintJob, DeleteMintJobResponse, GetMintJob,
+    GetMintJobResponse, GetMintJobs, GetMintJobsResponse, MintJobFormat, MintJobItemRecord,
+    MintJobRecord, PauseMintJob, PauseMintJobResponse,
+};
+use sage_assets::{verify_uris, UriResolver};
+use sage_database::{MintJobItemRow, MintJobRow};
+use sage_wallet::{WalletError, MAX_TRANSACTION_COST};
+use serde::{Deserialize, Deserializer};
+use tokio::time::timeout;
+
+use crate::{parse_amount, parse_did_id, parse_hash, Error, Result, Sage};
+
+/// How many rows of the manifest have their URIs fetched at the same time.
+const CONCURRENT_VERIFICATIONS: usize = 16;
+
+/// A single row of the manifest, before it has been validated.
+#[derive(Debug, Default, Clone, Deserialize)]
+#[serde(default)]
+struct MintJobInput {
+    address: Option<String>,
+    edition_number: Option<u64>,
+    edition_total: Option<u64>,
+    #[serde(deserialize_with = "deserialize_uris")]
+    data_uris: Vec<String>,
+    data_hash: Option<String>,
+    #[serde(deserialize_with = "deserialize_uris")]
+    metadata_uris: Vec<String>,
+    metadata_hash: Option<String>,
+    #[serde(deserialize_with = "deserialize_uris")]
+    license_uris: Vec<String>,
+    license_hash: Option<String>,
+    royalty_address: Option<String>,
+    royalty_ten_thousandths: Option<u16>,
+}
+
+impl Sage {
+    pub async fn get_mint_jobs(&self, _req: GetMintJobs) -> Result<GetMintJobsResponse> {
+        let wallet = self.wallet()?;
+
+        let mint_jobs = wallet
+            .db
+            .mint_jobs()
+            .await?
+            .into_iter()
+            .map(mint_job_record)
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(GetMintJobsResponse { mint_jobs })
+    }
+
+    pub async fn get_mint_job(&self, req: GetMintJob) -> Result<GetMintJobResponse> {
+        let wallet = self.wallet()?;
+
+        let row = wallet
+            .db
+            .mint_job(req.id)
+            .await?
+            .ok_or(Error::MissingMintJob(req.id))?;
+
+        let items = wallet
+            .db
+            .mint_job_items(req.id)
+            .await?
+            .into_iter()
+            .map(|row| {
+                let metadata = row.item.metadata;
+
+                Ok(MintJobItemRecord {
+                    row_index: row.item.row_index,
+                    nft_id: row
+                        .launcher_id
+                        .map(|launcher_id| Address::new(launcher_id, "nft".to_string()).encode())
+                        .transpose()?,
+                    address: row
+                        .item
+                        .p2_puzzle_hash
+                        .map(|p2_puzzle_hash| {
+                            Address::new(p2_puzzle_hash, self.network().prefix()).encode()
+                        })
+                        .transpose()?,
+                    edition_number: metadata.edition_number,
+                    edition_total: metadata.edition_total,
+                    data_uris: metadata.data_uris,
+                    data_hash: metadata.data_hash.map(hex::encode),
+                    metadata_uris: metadata.metadata_uris,
+                    metadata_hash: metadata.metadata_hash.map(hex::encode),
+                    license_uris: metadata.license_uris,
+                    license_hash: metadata.license_hash.map(hex::encode),
+                    royalty_address: row
+                        .item
+                        .royalty_puzzle_hash
+                        .map(|royalty_puzzle_hash| {
+                            Address::new(royalty_puzzle_hash, self.network().prefix()).encode()
+                        })
+                        .transpose()?,
+                    royalty_ten_thousandths: row.item.royalty_basis_points,
+                    transaction_id: row.transaction_id.map(|id| id.to_string()),
+                    is_confirmed: row.is_confirmed,
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(GetMintJobResponse {
+            mint_job: mint_job_record(row)?,
+            items,
+        })
+    }
+
+    pub async fn create_mint_job(&self, req: CreateMintJob) -> Result<CreateMintJobResponse> {
+        let wallet = self.wallet()?;
+
+        let name = req.name.trim().to_string();
+
+        if name.is_empty() {
+            return Err(Error::EmptyMintJobName);
+        }
+
+        let did_id = parse_did_id(req.did_id)?;
+
+        if wallet.db.did(did_id).await?.is_none() {
+            return Err(Error::MissingDid(did_id));
+        }
+
+        let fee = parse_amount(req.fee)?;
+        let max_cost = req.max_cost.unwrap_or(MAX_TRANSACTION_COST);
+        let inputs = parse_mint_job_data(req.format, &req.data)?;
+
+        if inputs.is_empty() {
+            return Err(Error::EmptyMintJob);
+        }
+
+        // Each NFT is minted with a single mojo, and the fee is only known to be paid at least
+        // once, so this is checked up front rather than finding out partway through.
+        let total = inputs.len() as u128 + u128::from(fee);
+
+        if total > wallet.db.selectable_xch_balance().await? {
+            return Err(WalletError::InsufficientFunds.into());
+        }
+
+        // The royalty address is resolved now, so that it's the same for every batch.
+        let default_royalty_puzzle_hash = wallet.change_p2_puzzle_hash().await?;
+
+        let mut rows = Vec::with_capacity(inputs.len());
+
+        for (index, input) in inputs.into_iter().enumerate() {
+            let row = self
+                .parse_mint_job_input(index, input, default_royalty_puzzle_hash)
+                .map_err(|error| Error::InvalidMintJobRow(index + 1, error.to_string()))?;
+
+            rows.push(row);
+        }
+
+        // Every URI is fetched before anything is minted, since hashes can't be changed later.
+        let verify_hashes = req.verify_hashes;
+        let resolver = &self.uri_resolver();
+
+        let items: Vec<MintJobItemRow> = stream::iter(rows.into_iter().enumerate())
+            .map(|(index, row)| async move {
+                verify_mint_job_item(resolver, row, verify_hashes)
+                    .await
+                    .map_err(|error| Error::InvalidMintJobRow(index + 1, error.to_string()))
+            })
+            .buffered(CONCURRENT_VERIFICATIONS)
+            .try_collect()
+            .await?;
+
+        let mut tx = wallet.db.tx().await?;
+
+        let id = tx.insert_mint_job(name, did_id, fee, max_cost).await?;
+
+        for item in items {
+            tx.insert_mint_job_item(id, item).await?;
+        }
+
+        tx.commit().await?;
+
+        let row = wallet
+            .db
+            .mint_job(id)
+            .await?
+            .ok_or(Error::MissingMintJob(id))?;
+
+        Ok(CreateMintJobResponse {
+            mint_job: mint_job_record(row)?,
+        })
+    }
+
+    pub async fn pause_mint_job(&self, req: PauseMintJob) -> Result<PauseMintJobResponse> {
+        let wallet = self.wallet()?;
+
+        let row = wallet
+            .db
+            .mint_job(req.id)
+            .await?
+            .ok_or(Error::MissingMintJob(req.id))?;
+
+        // The error is kept while paused so that it can be shown, and cleared on resume.
+        let error = if req.paused { row.error } else { None };
+
+        wallet
+            .db
+            .update_mint_job_status(req.id, req.paused, error)
+            .await?;
+
+        let row = wallet
+            .db
+            .mint_job(req.id)
+            .await?
+            .ok_or(Error::MissingMintJob(req.id))?;
+
+        Ok(PauseMintJobResponse {
+            mint_job: mint_job_record(row)?,
+        })
+    }
+
+    pub async fn delete_mint_job(&self, req: DeleteMintJob) -> Result<DeleteMintJobResponse> {
+        let wallet = self.wallet()?;
+        wallet.db.delete_mint_job(req.id).await?;
+        Ok(DeleteMintJobResponse {})
+    }
+
+    fn parse_mint_job_input(
+        &self,
+        index: usize,
+        input: MintJobInput,
+        default_royalty_puzzle_hash: Bytes32,
+    ) -> Result<MintJobItemRow> {
+        let p2_puzzle_hash = match input.address.as_deref().map(str::trim) {
+            None | Some("") => None,
+            Some(address) => Some(self.parse_address(address.to_string())?),
+        };
+
+        let royalty_puzzle_hash = match input.royalty_address.as_deref().map(str::trim) {
+            None | Some("") => default_royalty_puzzle_hash,
+            Some(address) => self.parse_address(address.to_string())?,
+        };
+
+        Ok(MintJobItemRow {
+            row_index: index.try_into()?,
+            metadata: NftMetadata {
+                edition_number: input.edition_number.unwrap_or(1),
+                edition_total: input.edition_total.unwrap_or(1),
+                data_uris: input.data_uris,
+                data_hash: parse_optional_hash(input.data_hash)?,
+                metadata_uris: input.metadata_uris,
+                metadata_hash: parse_optional_hash(input.metadata_hash)?,
+                license_uris: input.license_uris,
+                license_hash: parse_optional_hash(input.license_hash)?,
+            },
+            p2_puzzle_hash,
+            royalty_puzzle_hash: Some(royalty_puzzle_hash),
+            royalty_basis_points: input.royalty_ten_thousandths.unwrap_or(0),
+        })
+    }
+}
+
+fn parse_optional_hash(input: Option<String>) -> Result<Option<Bytes32>> {
+    match input.as_deref().map(str::trim) {
+        None | Some("") => Ok(None),
+        Some(hash) => Ok(Some(parse_hash(hash.to_string())?)),
+    }
+}
+
+/// Fetches the URIs of the item, filling in the hashes that weren't provided and checking the
+/// ones that were if requested.
+async fn verify_mint_job_item(
+    resolver: &UriResolver,
+    mut item: MintJobItemRow,
+    verify_hashes: bool,
+) -> Result<MintJobItemRow> {
+    let metadata = &mut item.metadata;
+
+    metadata.data_hash = verify_hash(
+        resolver,
+        &metadata.data_uris,
+        metadata.data_hash,
+        verify_hashes,
+    )
+    .await?;
+    metadata.metadata_hash = verify_hash(
+        resolver,
+        &metadata.metadata_uris,
+        metadata.metadata_hash,
+        verify_hashes,
+    )
+    .await?;
+    metadata.license_hash = verify_hash(
+        resolver,
+        &metadata.license_uris,
+        metadata.license_hash,
+        verify_hashes,
+    )
+    .await?;
+
+    Ok(item)
+}
+
+async fn verify_hash(
+    resolver: &UriResolver,
+    uris: &[String],
+    hash: Option<Bytes32>,
+    verify_hashes: bool,
+) -> Result<Option<Bytes32>> {
+    if uris.is_empty() || (hash.is_some() && !verify_hashes) {
+        return Ok(hash);
+    }
+
+    let hash = timeout(
+        Duration::from_secs(10),
+        verify_uris(resolver, uris.to_vec(), hash),
+    )
+    .await??;
+
+    Ok(Some(hash))
+}
+
+/// URIs are separated by whitespace, and can also be given as an array in JSON manifests.
+fn deserialize_uris<'de, D>(deserializer: D) -> std::result::Result<Vec<String>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Uris {
+        List(Vec<String>),
+        Text(String),
+    }
+
+    Ok(match Option::<Uris>::deserialize(deserializer)? {
+        None => Vec::new(),
+        Some(Uris::List(uris)) => uris
+            .into_iter()
+            .map(|uri| uri.trim().to_string())
+            .filter(|uri| !uri.is_empty())
+            .collect(),
+        Some(Uris::Text(text)) => text.split_whitespace().map(ToString::to_string).collect(),
+    })
+}
+
+fn mint_job_record(row: MintJobRow) -> Result<MintJobRecord> {
+    Ok(MintJobRecord {
+        id: row.id,
+        name: row.name,
+        did_id: Address::new(row.did_id, "did:chia:".to_string()).encode()?,
+        fee: Amount::u64(row.fee),
+        max_cost: row.max_cost,
+        is_paused: row.is_paused,
+        error: row.error,
+        created_timestamp: row.created_timestamp,
+        item_count: row.item_count,
+        submitted_count: row.submitted_count,
+        confirmed_count: row.confirmed_count,
+    })
+}
+
+fn parse_mint_job_data(format: MintJobFormat, data: &str) -> Result<Vec<MintJobInput>> {
+    match format {
+        MintJobFormat::Csv => {
+            let mut reader = csv::ReaderBuilder::new()
+                .trim(csv::Trim::All)
+                .from_reader(data.as_bytes());
+
+            reader
+                .deserialize()
+                .enumerate()
+                .map(|(index, row)| {
+                    row.map_err(|error| Error::InvalidMintJobRow(index + 1, error.to_string()))
+                })
+                .collect()
+        }
+        MintJobFormat::Json => serde_json::from_str(data)
+            .map_err(|error| Error::InvalidMintJobManifest(error.to_string())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+
+    #[test]
+    fn test_parse_mint_job_csv() {
+        let data = "data_uris,data_hash,edition_number,edition_total,royalty_ten_thousandths\n\
+            https://a.com/1.png https://b.com/1.png,,1,2,300\n\
+            https://a.com/2.png,cafe,2,2,\n";
+
+        let rows = parse_mint_job_data(MintJobFormat::Csv, data).unwrap();
+
+        assert_eq!(rows.len(), 2);
+        assert_eq!(
+            rows[0].data_uris,
+            vec!["https://a.com/1.png", "https://b.com/1.png"]
+        );
+        assert_eq!(rows[0].data_hash, None);
+        assert_eq!(rows[0].edition_number, Some(1));
+        assert_eq!(rows[0].royalty_ten_thousandths, Some(300));
+        assert!(rows[0].metadata_uris.is_empty());
+        assert_eq!(rows[1].data_hash.as_deref(), Some("cafe"));
+        assert_eq!(rows[1].royalty_ten_thousandths, None);
+    }
+
+    #[test]
+    fn test_parse_mint_job_csv_invalid_row() {
+        let data = "data_uris,edition_number\nhttps://a.com/1.png,1\nhttps://a.com/2.png,two\n";
+
+        assert!(matches!(
+            parse_mint_job_data(MintJobFormat::Csv, data),
+            Err(Error::InvalidMintJobRow(2, _))
+        ));
+    }
+
+    #[test]
+    fn test_parse_mint_job_json() {
+        let data = r#"[
+            { "data_uris": ["https://a.com/1.png", "https://b.com/1.png"], "edition_number": 1 },
+            { "data_uris": "https://a.com/2.png", "license_uris": "https://a.com/license" }
+        ]"#;
+
+        let rows = parse_mint_job_data(MintJobFormat::Json, data).unwrap();
+
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0].data_uris.len(), 2);
+        assert_eq!(rows[0].edition_number, Some(1));
+        assert_eq!(rows[1].data_uris, vec!["https://a.com/2.png"]);
+        assert_eq!(rows[1].license_uris, vec!["https://a.com/license"]);
+    }
+}
@@ -0,0 +1,470 @@
+use std::collections::{HashMap, HashSet};
+
+use chia::protocol::Bytes32;
+use chia_wallet_sdk::utils::Address;
+use sage_api::{
+    FileIntegrityRecord, GetNftIntegrity, GetNftIntegrityReport, GetNftIntegrityReportResponse,
+    GetNftIntegrityResponse, IntegrityStatus, NftIntegrityRecord, NftIntegritySummary,
+    ReverifyNfts, ReverifyNftsResponse, UriIntegrityRecord, UriIntegrityStatus,
+};
+use sage_database::{FileUriStatus, NftFileHashes};
+
+use crate::{parse_collection_id, parse_nft_id, Error, Result, Sage};
+
+impl Sage {
+    pub async fn get_nft_integrity(&self, req: GetNftIntegrity) -> Result<GetNftIntegrityResponse> {
+        let wallet = self.wallet()?;
+
+        let nft_id = parse_nft_id(req.nft_id)?;
+
+        let Some(nft) = wallet.db.nft_file_hashes(nft_id).await? else {
+            return Err(Error::MissingNft(nft_id));
+        };
+
+        let mut uris = HashMap::new();
+
+        for hash in [nft.data_hash, nft.metadata_hash, nft.license_hash]
+            .into_iter()
+            .flatten()
+        {
+            uris.insert(hash, wallet.db.file_uri_statuses(hash).await?);
+        }
+
+        Ok(GetNftIntegrityResponse {
+            integrity: nft_integrity(nft, &uris)?,
+        })
+    }
+
+    pub async fn get_nft_integrity_report(
+        &self,
+        req: GetNftIntegrityReport,
+    ) -> Result<GetNftIntegrityReportResponse> {
+        let wallet = self.wallet()?;
+
+        let collection_id = req.collection_id.map(parse_collection_id).transpose()?;
+
+        let nfts = wallet.db.owned_nft_file_hashes(collection_id).await?;
+
+        let mut uris: HashMap<Bytes32, Vec<FileUriStatus>> = HashMap::new();
+
+        for row in wallet.db.owned_nft_file_uri_statuses().await? {
+            uris.entry(row.hash).or_default().push(row);
+        }
+
+        let mut summary = NftIntegritySummary::default();
+        let mut records = Vec::new();
+
+        for nft in nfts {
+            let record = nft_integrity(nft, &uris)?;
+
+            let count = match record.status {
+                IntegrityStatus::Verified => &mut summary.verified,
+                IntegrityStatus::Pending => &mut summary.pending,
+                IntegrityStatus::Unhashed => &mut summary.unhashed,
+                IntegrityStatus::Partial => &mut summary.partial,
+                IntegrityStatus::Unavailable => &mut summary.unavailable,
+                IntegrityStatus::Mismatch => &mut summary.mismatch,
+            };
+
+            *count += 1;
+
+            if req.include_verified || record.status != IntegrityStatus::Verified {
+                records.push(record);
+            }
+        }
+
+        let total = records.len().try_into()?;
+
+        Ok(GetNftIntegrityReportResponse {
+            nfts: records
+                .into_iter()
+                .skip(req.offset as usize)
+                .take(req.limit as usize)
+                .collect(),
+            total,
+            summary,
+        })
+    }
+
+    pub async fn reverify_nfts(&self, req: ReverifyNfts) -> Result<ReverifyNftsResponse> {
+        let wallet = self.wallet()?;
+
+        let nfts = if req.nft_ids.is_empty() {
+            let collection_id = req.collection_id.map(parse_collection_id).transpose()?;
+            wallet.db.owned_nft_file_hashes(collection_id).await?
+        } else {
+            let mut nfts = Vec::new();
+
+            for nft_id in req.nft_ids {
+                let nft_id = parse_nft_id(nft_id)?;
+
+                let Some(nft) = wallet.db.nft_file_hashes(nft_id).await? else {
+                    return Err(Error::MissingNft(nft_id));
+                };
+
+                nfts.push(nft);
+            }
+
+            nfts
+        };
+
+        let hashes: HashSet<Bytes32> = nfts
+            .into_iter()
+            .flat_map(|nft| [nft.data_hash, nft.metadata_hash, nft.license_hash])
+            .flatten()
+            .collect();
+
+        let mut tx = wallet.db.tx().await?;
+
+        let mut uris = 0;
+
+        for hash in hashes {
+            uris += tx.set_failing_uris_unchecked(hash).await?;
+        }
+
+        tx.commit().await?;
+
+        Ok(ReverifyNftsResponse {
+            uris: uris.try_into()?,
+        })
+    }
+}
+
+fn nft_integrity(
+    nft: NftFileHashes,
+    uris: &HashMap<Bytes32, Vec<FileUriStatus>>,
+) -> Result<NftIntegrityRecord> {
+    let file = |hash: Option<Bytes32>| {
+        hash.map(|hash| file_integrity(hash, uris.get(&hash).map_or(&[], Vec::as_slice)))
+    };
+
+    let data = file(nft.data_hash);
+    let metadata = file(nft.metadata_hash);
+    let license = file(nft.license_hash);
+
+    // Without a data hash there's nothing to tell whether the content is what was minted.
+    let status = [&data, &metadata, &license]
+        .into_iter()
+        .flatten()
+        .map(|file| file.status)
+        .chain(data.is_none().then_some(IntegrityStatus::Unhashed))
+        .max()
+        .unwrap_or(IntegrityStatus::Unhashed);
+
+    Ok(NftIntegrityRecord {
+        nft_id: Address::new(nft.hash, "nft".to_string()).encode()?,
+        name: nft.name,
+        status,
+        data,
+        metadata,
+        license,
+    })
+}
+
+fn file_integrity(hash: Bytes32, uris: &[FileUriStatus]) -> FileIntegrityRecord {
+    let uris: Vec<UriIntegrityRecord> = uris
+        .iter()
+        .map(|row| UriIntegrityRecord {
+            uri: row.uri.clone(),
+            status: uri_status(row.is_hash_match, row.failed_attempts),
+            error: row.error.clone(),
+            last_checked_timestamp: row.last_checked_timestamp,
+            failed_attempts: row.failed_attempts,
+        })
+        .collect();
+
+    let statuses: Vec<UriIntegrityStatus> = uris.iter().map(|uri| uri.status).collect();
+
+    FileIntegrityRecord {
+        hash: hex::encode(hash),
+        status: file_status(&statuses),
+        uris,
+    }
+}
+
+fn uri_status(is_hash_match: Option<bool>, failed_attempts: u32) -> UriIntegrityStatus {
+    match is_hash_match {
+        Some(true) => UriIntegrityStatus::Verified,
+        Some(false) => UriIntegrityStatus::Mismatch,
+        None if failed_attempts > 0 => UriIntegrityStatus::Failed,
+        None => UriIntegrityStatus::Pending,
+    }
+}
+
+fn file_status(uris: &[UriIntegrityStatus]) -> IntegrityStatus {
+    if uris.contains(&UriIntegrityStatus::Pending) {
+        return IntegrityStatus::Pending;
+    }
+
+    let verified = uris
+        .iter()
+        .filter(|&&status| status == UriIntegrityStatus::Verified)
+        .count();
+
+    if verified > 0 && verified == uris.len() {
+        IntegrityStatus::Verified
+    } else if verified > 0 {
+        IntegrityStatus::Partial
+    } else if uris.contains(&UriIntegrityStatus::Mismatch) {
+        IntegrityStatus::Mismatch
+    } else {
+        IntegrityStatus::Unavailable
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        sync::{
+            atomic::{AtomicBool, Ordering},
+            Arc,
+        },
+        time::Duration,
+    };
+
+    use axum::{routing::get, Router};
+    use chia::{
+        bls::PublicKey,
+        protocol::{Coin, CoinState, Program},
+        sha2::Sha256,
+    };
+    use sage_api::IntegrityStatus;
+    use sage_database::{Asset, AssetKind, Derivation, NftCoinInfo};
+    use sage_wallet::NftUriQueue;
+    use tokio::{net::TcpListener, sync::mpsc, time::sleep};
+
+    use crate::test_sage;
+
+    use super::*;
+
+    use UriIntegrityStatus::{Failed, Mismatch, Pending, Verified};
+
+    const DATA: &[u8] = b"NFT data";
+
+    #[test]
+    fn test_uri_status() {
+        assert_eq!(uri_status(None, 0), Pending);
+        assert_eq!(uri_status(None, 2), Failed);
+        assert_eq!(uri_status(Some(true), 1), Verified);
+        assert_eq!(uri_status(Some(false), 0), Mismatch);
+    }
+
+    #[test]
+    fn test_file_status() {
+        assert_eq!(file_status(&[]), IntegrityStatus::Unavailable);
+        assert_eq!(
+            file_status(&[Verified, Verified]),
+            IntegrityStatus::Verified
+        );
+        assert_eq!(file_status(&[Verified, Pending]), IntegrityStatus::Pending);
+        assert_eq!(file_status(&[Verified, Mismatch]), IntegrityStatus::Partial);
+        assert_eq!(file_status(&[Failed, Verified]), IntegrityStatus::Partial);
+        assert_eq!(file_status(&[Failed, Mismatch]), IntegrityStatus::Mismatch);
+        assert_eq!(file_status(&[Failed, Failed]), IntegrityStatus::Unavailable);
+    }
+
+    /// Serves the NFT data from one path and other content from another, until the content of
+    /// that path is fixed.
+    async fn file_server(is_fixed: Arc<AtomicBool>) -> anyhow::Result<String> {
+        let app = Router::new().route("/match", get(|| async { DATA })).route(
+            "/mismatch",
+            get(move || async move {
+                if is_fixed.load(Ordering::SeqCst) {
+                    DATA
+                } else {
+                    b"Other data".as_slice()
+                }
+            }),
+        );
+
+        let listener = TcpListener::bind("127.0.0.1:0").await?;
+        let address = listener.local_addr()?;
+
+        tokio::spawn(async move { axum::serve(listener, app).await });
+
+        Ok(format!("http://{address}"))
+    }
+
+    /// Waits for the queue to check every pending URI of the only NFT in the wallet.
+    async fn checked_integrity(sage: &Sage) -> anyhow::Result<NftIntegrityRecord> {
+        for _ in 0..500 {
+            let mut report = sage
+                .get_nft_integrity_report(GetNftIntegrityReport {
+                    collection_id: None,
+                    include_verified: true,
+                    offset: 0,
+                    limit: 10,
+                })
+                .await?;
+
+            assert_eq!(report.nfts.len(), 1);
+
+            let nft = report.nfts.remove(0);
+
+            if nft.status != IntegrityStatus::Pending {
+                return Ok(nft);
+            }
+
+            sleep(Duration::from_millis(20)).await;
+        }
+
+        anyhow::bail!("URIs were never checked");
+    }
+
+    fn uri_statuses(record: &FileIntegrityRecord) -> Vec<UriIntegrityStatus> {
+        record.uris.iter().map(|uri| uri.status).collect()
+    }
+
+    #[tokio::test]
+    async fn test_verify_nft_uris() -> anyhow::Result<()> {
+        let dir = std::env::temp_dir().join(format!("sage-nft-integrity-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir)?;
+
+        let sage = test_sage(&dir).await?;
+        let wallet = sage.wallet()?;
+
+        let is_fixed = Arc::new(AtomicBool::new(false));
+        let base_url = file_server(is_fixed.clone()).await?;
+
+        let nft_id = Bytes32::new([1; 32]);
+        let p2_puzzle_hash = Bytes32::new([2; 32]);
+
+        let mut hasher = Sha256::new();
+        hasher.update(DATA);
+        let data_hash = Bytes32::new(hasher.finalize());
+
+        let uris = [
+            format!("{base_url}/match"),
+            format!("{base_url}/mismatch"),
+            "http://127.0.0.1:1/unreachable".to_string(),
+        ];
+
+        let mut tx = wallet.db.tx().await?;
+
+        tx.insert_custody_p2_puzzle(
+            p2_puzzle_hash,
+            PublicKey::default(),
+            Derivation {
+                derivation_index: 0,
+                is_hardened: false,
+            },
+        )
+        .await?;
+
+        tx.insert_asset(Asset {
+            hash: nft_id,
+            name: Some("Test NFT".to_string()),
+            ticker: None,
+            precision: 1,
+            icon_url: None,
+            description: None,
+            is_sensitive_content: false,
+            is_visible: true,
+            hidden_puzzle_hash: None,
+            kind: AssetKind::Nft,
+        })
+        .await?;
+
+        tx.insert_nft(
+            nft_id,
+            &NftCoinInfo {
+                collection_hash: Bytes32::default(),
+                collection_name: None,
+                minter_hash: None,
+                owner_hash: None,
+                metadata: Program::default(),
+                metadata_updater_puzzle_hash: Bytes32::default(),
+                royalty_puzzle_hash: Bytes32::default(),
+                royalty_basis_points: 0,
+                data_hash: Some(data_hash),
+                metadata_hash: None,
+                license_hash: None,
+                edition_number: None,
+                edition_total: None,
+            },
+        )
+        .await?;
+
+        let coin = Coin::new(nft_id, p2_puzzle_hash, 1);
+        tx.insert_coin(CoinState::new(coin, None, None)).await?;
+        tx.update_coin(coin.coin_id(), nft_id, p2_puzzle_hash)
+            .await?;
+
+        // The file has already been downloaded, so only the verification pass is left.
+        tx.insert_file(data_hash).await?;
+
+        for uri in &uris {
+            tx.insert_file_uri(data_hash, uri.clone()).await?;
+        }
+
+        tx.update_file(data_hash, DATA.to_vec(), "text/plain".to_string(), true)
+            .await?;
+
+        tx.commit().await?;
+
+        let (sync_sender, _sync_receiver) = mpsc::channel(16);
+
+        let queue = NftUriQueue::new(
+            wallet.db.clone(),
+            sync_sender,
+            sage.network().clone(),
+            sage.uri_resolver(),
+        );
+
+        let handle = tokio::spawn(queue.start(Duration::from_millis(20)));
+
+        let nft = checked_integrity(&sage).await?;
+        let data = nft.data.expect("missing data file");
+
+        assert_eq!(
+            nft.nft_id,
+            Address::new(nft_id, "nft".to_string()).encode()?
+        );
+        assert_eq!(nft.name.as_deref(), Some("Test NFT"));
+        assert_eq!(nft.status, IntegrityStatus::Partial);
+        assert_eq!(data.hash, hex::encode(data_hash));
+        assert_eq!(data.status, IntegrityStatus::Partial);
+        assert_eq!(
+            data.uris
+                .iter()
+                .map(|uri| uri.uri.clone())
+                .collect::<Vec<_>>(),
+            uris
+        );
+        assert_eq!(uri_statuses(&data), [Verified, Mismatch, Failed]);
+        assert!(data.uris[0].error.is_none());
+        assert!(data.uris[1].error.is_none());
+        assert!(data.uris[2].error.is_some());
+        assert_eq!(data.uris[2].failed_attempts, 1);
+        assert!(data
+            .uris
+            .iter()
+            .all(|uri| uri.last_checked_timestamp.is_some()));
+
+        // Only the URIs which didn't match are queued again, and the rest are left alone.
+        is_fixed.store(true, Ordering::SeqCst);
+
+        let response = sage
+            .reverify_nfts(ReverifyNfts {
+                nft_ids: Vec::new(),
+                collection_id: None,
+            })
+            .await?;
+
+        assert_eq!(response.uris, 2);
+
+        let nft = checked_integrity(&sage).await?;
+        let data = nft.data.expect("missing data file");
+
+        assert_eq!(nft.status, IntegrityStatus::Partial);
+        assert_eq!(uri_statuses(&data), [Verified, Verified, Failed]);
+        assert!(data.uris[1].error.is_none());
+        assert!(data.uris[2].error.is_some());
+        assert_eq!(data.uris[2].failed_attempts, 1);
+
+        handle.abort();
+
+        Ok(())
+    }
+}
@@ -0,0 +1,640 @@
+use chia::{
+    bls::Signature,
+    protocol::{Bytes32, SpendBundle},
+};
+use chia_wallet_sdk::{
+    driver::{decode_offer, encode_offer, Offer, SpendContext},
+    signer::AggSigConstants,
+};
+use sage_api::{
+    Amount, CancelOfferGroup, CancelOfferGroupResponse, GetOfferGroup, GetOfferGroupResponse,
+    GetOfferGroups, GetOfferGroupsResponse, LadderDistribution, MakeOfferLadder,
+    MakeOfferLadderResponse, OfferGroupOfferRecord, OfferGroupRecord, RepriceOfferGroup,
+    RepriceOfferGroupResponse, SetOfferGroupPolicy, SetOfferGroupPolicyResponse,
+};
+use sage_database::{OfferGroupPolicy, OfferGroupRow, OfferStatus};
+use sage_wallet::{LadderRung, OfferExpiry, OfferLadder, Price};
+
+use crate::{parse_amount, parse_asset_id, Error, Result, Sage};
+
+use super::offers::{insert_offer_record, insert_offered_coins};
+
+/// Extra decimal places used for prices which are spread evenly over a range, so that the
+/// rungs in between don't have to be rounded to the precision of the range.
+const EXTRA_PRICE_DECIMALS: u32 = 4;
+
+/// The most offers that can be created in a single ladder.
+const MAX_LADDER_RUNGS: u32 = 100;
+
+impl Sage {
+    pub async fn make_offer_ladder(&self, req: MakeOfferLadder) -> Result<MakeOfferLadderResponse> {
+        let wallet = self.wallet()?;
+
+        let offered_asset_id = req.offered_asset_id.map(parse_asset_id).transpose()?;
+        let requested_asset_id = req.requested_asset_id.map(parse_asset_id).transpose()?;
+
+        if offered_asset_id == requested_asset_id {
+            return Err(Error::InvalidOfferLadder(
+                "the offered and requested assets must be different".to_string(),
+            ));
+        }
+
+        let total_amount = parse_amount(req.total_amount)?;
+        let fee = parse_amount(req.fee)?;
+
+        let prices = ladder_prices(
+            parse_price(&req.min_price)?,
+            parse_price(&req.max_price)?,
+            req.step.as_deref().map(parse_price).transpose()?,
+            req.count,
+        )?;
+
+        let amounts = ladder_amounts(total_amount, prices.len(), req.distribution)?;
+
+        let ladder = OfferLadder {
+            offered_asset_id,
+            requested_asset_id,
+            requested_hidden_puzzle_hash: match requested_asset_id {
+                Some(asset_id) => wallet.fetch_offer_cat_hidden_puzzle_hash(asset_id).await?,
+                None => None,
+            },
+            rungs: ladder_rungs(
+                &amounts,
+                &prices,
+                wallet.asset_precision(offered_asset_id).await?,
+                wallet.asset_precision(requested_asset_id).await?,
+            )?,
+            fee,
+            p2_puzzle_hash: req
+                .receive_address
+                .map(|address| self.parse_address(address))
+                .transpose()?,
+            expiry: OfferExpiry {
+                height: None,
+                seconds: req.expires_at_second,
+            },
+        };
+
+        self.submit_offer_ladder(None, ladder, prices, Vec::new())
+            .await
+    }
+
+    pub async fn get_offer_groups(&self, _req: GetOfferGroups) -> Result<GetOfferGroupsResponse> {
+        let wallet = self.wallet()?;
+
+        let groups = wallet
+            .db
+            .offer_groups()
+            .await?
+            .into_iter()
+            .map(offer_group_record)
+            .collect();
+
+        Ok(GetOfferGroupsResponse { groups })
+    }
+
+    pub async fn get_offer_group(&self, req: GetOfferGroup) -> Result<GetOfferGroupResponse> {
+        let wallet = self.wallet()?;
+
+        let group = wallet
+            .db
+            .offer_group(req.id)
+            .await?
+            .ok_or(Error::MissingOfferGroup(req.id))?;
+
+        let mut offers = Vec::new();
+
+        for row in wallet.db.offer_group_offers(req.id).await? {
+            offers.push(OfferGroupOfferRecord {
+                price: row.price,
+                is_reversed: row.is_reversed,
+                offer: self.offer_record(&wallet, row.offer).await?,
+            });
+        }
+
+        Ok(GetOfferGroupResponse {
+            group: offer_group_record(group),
+            offers,
+        })
+    }
+
+    pub async fn cancel_offer_group(
+        &self,
+        req: CancelOfferGroup,
+    ) -> Result<CancelOfferGroupResponse> {
+        let wallet = self.wallet()?;
+        let fee = parse_amount(req.fee)?;
+
+        if wallet.db.offer_group(req.id).await?.is_none() {
+            return Err(Error::MissingOfferGroup(req.id));
+        }
+
+        let offers = wallet
+            .db
+            .offer_group_offers(req.id)
+            .await?
+            .into_iter()
+            .filter(|row| row.offer.status == OfferStatus::Active)
+            .map(|row| Ok(decode_offer(&row.offer.encoded_offer)?))
+            .collect::<Result<Vec<_>>>()?;
+
+        if offers.is_empty() {
+            return Err(Error::InvalidOfferLadder(
+                "the group has no active offers".to_string(),
+            ));
+        }
+
+        let coin_spends = wallet.cancel_offers(offers, fee).await?;
+
+        self.transact(coin_spends, req.auto_submit).await
+    }
+
+    pub async fn reprice_offer_group(
+        &self,
+        req: RepriceOfferGroup,
+    ) -> Result<RepriceOfferGroupResponse> {
+        let wallet = self.wallet()?;
+        let fee = parse_amount(req.fee)?;
+
+        let group = wallet
+            .db
+            .offer_group(req.id)
+            .await?
+            .ok_or(Error::MissingOfferGroup(req.id))?;
+
+        let mut rows = Vec::new();
+
+        // Only the offers selling the offered asset are replaced, and any reposted offers which
+        // are buying it back are left as they are.
+        for row in wallet.db.offer_group_offers(req.id).await? {
+            if row.is_reversed || row.offer.status != OfferStatus::Active {
+                continue;
+            }
+
+            rows.push((parse_price(&row.price)?, row.offer));
+        }
+
+        if rows.is_empty() {
+            return Err(Error::InvalidOfferLadder(
+                "the group has no active offers to re-price".to_string(),
+            ));
+        }
+
+        // The rungs keep their sizes and order, and only the prices change.
+        rows.sort_by_key(|(price, _)| *price);
+
+        let prices = ladder_prices(
+            parse_price(&req.min_price)?,
+            parse_price(&req.max_price)?,
+            None,
+            Some(rows.len().try_into()?),
+        )?;
+
+        let mut ctx = SpendContext::new();
+        let mut amounts = Vec::with_capacity(rows.len());
+        let mut coin_ids = Vec::new();
+
+        for (_, row) in &rows {
+            let offered_amount = wallet
+                .db
+                .offer_assets(row.offer_id)
+                .await?
+                .into_iter()
+                .find(|asset| !asset.is_requested)
+                .map_or(0, |asset| asset.amount);
+
+            amounts.push(offered_amount);
+
+            // Spending the coins of the old offers to create the new ones cancels them.
+            let offer = Offer::from_spend_bundle(&mut ctx, &decode_offer(&row.encoded_offer)?)?;
+
+            for coin_spend in offer.cancellable_coin_spends()? {
+                let coin_id = coin_spend.coin.coin_id();
+
+                if wallet.db.coin_kind(coin_id).await?.is_some() && !coin_ids.contains(&coin_id) {
+                    coin_ids.push(coin_id);
+                }
+            }
+        }
+
+        let ladder = OfferLadder {
+            offered_asset_id: group.offered_asset_id,
+            requested_asset_id: group.requested_asset_id,
+            requested_hidden_puzzle_hash: match group.requested_asset_id {
+                Some(asset_id) => wallet.fetch_offer_cat_hidden_puzzle_hash(asset_id).await?,
+                None => None,
+            },
+            rungs: ladder_rungs(
+                &amounts,
+                &prices,
+                wallet.asset_precision(group.offered_asset_id).await?,
+                wallet.asset_precision(group.requested_asset_id).await?,
+            )?,
+            fee,
+            p2_puzzle_hash: None,
+            expiry: OfferExpiry {
+                height: None,
+                seconds: req.expires_at_second,
+            },
+        };
+
+        self.submit_offer_ladder(Some(group.id), ladder, prices, coin_ids)
+            .await
+    }
+
+    pub async fn set_offer_group_policy(
+        &self,
+        req: SetOfferGroupPolicy,
+    ) -> Result<SetOfferGroupPolicyResponse> {
+        let wallet = self.wallet()?;
+
+        if wallet.db.offer_group(req.id).await?.is_none() {
+            return Err(Error::MissingOfferGroup(req.id));
+        }
+
+        let price_spread = req
+            .price_spread
+            .as_deref()
+            .map_or(Ok(Price::default()), parse_price)?;
+        let stop_price = req.stop_price.as_deref().map(parse_price).transpose()?;
+        let max_inventory = req.max_inventory.map(parse_amount).transpose()?;
+
+        let mut tx = wallet.db.tx().await?;
+
+        tx.set_offer_group_policy(
+            req.id,
+            OfferGroupPolicy {
+                auto_repost: req.auto_repost,
+                price_spread: price_spread.to_string(),
+                stop_price: stop_price.map(|price| price.to_string()),
+                max_inventory,
+            },
+        )
+        .await?;
+
+        tx.commit().await?;
+
+        Ok(SetOfferGroupPolicyResponse {})
+    }
+
+    /// Signs the ladder and submits the transaction which splits the coins, then imports all
+    /// of the offers into the group at once.
+    async fn submit_offer_ladder(
+        &self,
+        group_id: Option<u32>,
+        ladder: OfferLadder,
+        prices: Vec<Price>,
+        selected_coin_ids: Vec<Bytes32>,
+    ) -> Result<MakeOfferLadderResponse> {
+        let wallet = self.wallet()?;
+
+        let offered_asset_id = ladder.offered_asset_id;
+        let requested_asset_id = ladder.requested_asset_id;
+
+        let spends = wallet.make_offer_ladder(ladder, selected_coin_ids).await?;
+
+        let (_mnemonic, Some(master_sk)) =
+            self.keychain.extract_secrets(wallet.fingerprint, b"")?
+        else {
+            return Err(Error::NoSigningKey);
+        };
+
+        // Each transaction is checked against the spending policy when it's signed, but the
+        // per transaction limits apply to the ladder as a whole, so it's checked up front too.
+        let mut coin_spends = spends.split.clone();
+
+        for offer in &spends.offers {
+            coin_spends.extend(offer.coin_spends.iter().cloned());
+        }
+
+        wallet.check_spending_policy(&coin_spends).await?;
+
+        let constants = AggSigConstants::new(self.network().agg_sig_me());
+
+        let split = wallet
+            .sign_transaction(
+                SpendBundle::new(spends.split, Signature::default()),
+                &constants,
+                master_sk.clone(),
+                false,
+            )
+            .await?;
+
+        let mut imports = Vec::with_capacity(spends.offers.len());
+
+        for offer in spends.offers {
+            let offer = wallet
+                .sign_transaction(offer, &constants, master_sk.clone(), false)
+                .await?;
+
+            imports.push(self.prepare_offer_import(encode_offer(&offer)?).await?);
+        }
+
+        // The group and its offers are saved before the split is submitted, so that they can't be
+        // lost once it has been. They stay pending until the split has been added to the wallet,
+        // since the offers spend the coins it creates.
+        let mut tx = wallet.db.tx().await?;
+
+        let is_new_group = group_id.is_none();
+
+        let group_id = match group_id {
+            Some(group_id) => group_id,
+            None => {
+                tx.insert_offer_group(offered_asset_id, requested_asset_id)
+                    .await?
+            }
+        };
+
+        let mut offered_coins = Vec::with_capacity(imports.len());
+
+        for (mut import, price) in imports.into_iter().zip(prices) {
+            let offer_id = import.row.offer_id;
+
+            import.row.status = OfferStatus::Pending;
+
+            offered_coins.push((offer_id, insert_offer_record(&mut tx, import).await?));
+            tx.insert_offer_group_offer(group_id, offer_id, price.to_string(), false)
+                .await?;
+        }
+
+        tx.commit().await?;
+
+        if let Err(error) = self.submit(split).await {
+            let mut tx = wallet.db.tx().await?;
+
+            for (offer_id, _) in &offered_coins {
+                tx.delete_offer(*offer_id).await?;
+            }
+
+            if is_new_group {
+                tx.delete_offer_group(group_id).await?;
+            }
+
+            tx.commit().await?;
+
+            return Err(error);
+        }
+
+        let mut tx = wallet.db.tx().await?;
+        let mut offer_ids = Vec::with_capacity(offered_coins.len());
+
+        for (offer_id, coin_ids) in offered_coins {
+            insert_offered_coins(&mut tx, offer_id, coin_ids).await?;
+            tx.update_offer_status(offer_id, OfferStatus::Active)
+                .await?;
+
+            offer_ids.push(hex::encode(offer_id));
+        }
+
+        tx.commit().await?;
+
+        Ok(MakeOfferLadderResponse {
+            group_id,
+            offer_ids,
+        })
+    }
+}
+
+fn offer_group_record(row: OfferGroupRow) -> OfferGroupRecord {
+    OfferGroupRecord {
+        id: row.id,
+        offered_asset_id: row.offered_asset_id.map(hex::encode),
+        requested_asset_id: row.requested_asset_id.map(hex::encode),
+        created_timestamp: row.created_timestamp,
+        offer_count: row.offer_count,
+        active_count: row.active_count,
+        auto_repost: row.policy.auto_repost,
+        price_spread: row.policy.price_spread,
+        stop_price: row.policy.stop_price,
+        max_inventory: row.policy.max_inventory.map(Amount::u64),
+        stop_reason: row.stop_reason,
+    }
+}
+
+fn parse_price(input: &str) -> Result<Price> {
+    input
+        .parse()
+        .map_err(|_| Error::InvalidPrice(input.to_string()))
+}
+
+/// The price of each rung from lowest to highest.
+fn ladder_prices(
+    min: Price,
+    max: Price,
+    step: Option<Price>,
+    count: Option<u32>,
+) -> Result<Vec<Price>> {
+    let mut scale = min.scale().max(max.scale());
+
+    if let Some(step) = step {
+        scale = scale.max(step.scale());
+    } else {
+        scale = (scale + EXTRA_PRICE_DECIMALS).min(Price::MAX_DECIMALS);
+    }
+
+    let units_at = |price: Price| {
+        price
+            .units_at(scale)
+            .ok_or_else(|| Error::InvalidPrice(price.to_string()))
+    };
+
+    let min_units = units_at(min)?;
+    let max_units = units_at(max)?;
+
+    if min_units == 0 {
+        return Err(Error::InvalidOfferLadder(
+            "prices must be greater than zero".to_string(),
+        ));
+    }
+
+    if min_units > max_units {
+        return Err(Error::InvalidOfferLadder(
+            "the minimum price is greater than the maximum price".to_string(),
+        ));
+    }
+
+    let prices: Vec<u128> = match (step, count) {
+        (Some(step), None) => {
+            let step_units = units_at(step)?;
+
+            if step_units == 0 {
+                return Err(Error::InvalidOfferLadder(
+                    "the step must be greater than zero".to_string(),
+                ));
+            }
+
+            let count = (max_units - min_units) / step_units + 1;
+
+            if count > u128::from(MAX_LADDER_RUNGS) {
+                return Err(Error::InvalidOfferLadder(format!(
+                    "a ladder can have at most {MAX_LADDER_RUNGS} rungs"
+                )));
+            }
+
+            (0..count)
+                .map(|index| min_units + index * step_units)
+                .collect()
+        }
+        (None, Some(count)) => {
+            if count == 0 || count > MAX_LADDER_RUNGS {
+                return Err(Error::InvalidOfferLadder(format!(
+                    "the count must be between 1 and {MAX_LADDER_RUNGS}"
+                )));
+            }
+
+            if count == 1 {
+                vec![min_units]
+            } else {
+                let intervals = u128::from(count - 1);
+                let range = max_units - min_units;
+
+                // Each price is rounded to the nearest unit.
+                (0..=intervals)
+                    .map(|index| min_units + (2 * range * index + intervals) / (2 * intervals))
+                    .collect()
+            }
+        }
+        _ => {
+            return Err(Error::InvalidOfferLadder(
+                "either a step or a count must be provided".to_string(),
+            ))
+        }
+    };
+
+    Ok(prices
+        .into_iter()
+        .map(|units| Price::from_units(units, scale))
+        .collect())
+}
+
+/// Spreads the total amount over the rungs, with any remainder going to the last rung.
+fn ladder_amounts(total: u64, count: usize, distribution: LadderDistribution) -> Result<Vec<u64>> {
+    let weights: Vec<u128> = (1..=count as u128)
+        .map(|index| match distribution {
+            LadderDistribution::Uniform => 1,
+            LadderDistribution::Increasing => index,
+            LadderDistribution::Decreasing => count as u128 + 1 - index,
+        })
+        .collect();
+
+    let total_weight: u128 = weights.iter().sum();
+
+    let mut amounts = weights
+        .iter()
+        .map(|weight| Ok((u128::from(total) * weight / total_weight).try_into()?))
+        .collect::<Result<Vec<u64>>>()?;
+
+    let remainder = total - amounts.iter().sum::<u64>();
+
+    if let Some(last) = amounts.last_mut() {
+        *last += remainder;
+    }
+
+    if amounts.contains(&0) {
+        return Err(Error::InvalidOfferLadder(
+            "the total amount is too small for the number of rungs".to_string(),
+        ));
+    }
+
+    Ok(amounts)
+}
+
+/// Works out how much of the requested asset each rung asks for, rounded down to the nearest
+/// mojo. Prices are in whole units of each asset, so the precision of both is needed.
+fn ladder_rungs(
+    amounts: &[u64],
+    prices: &[Price],
+    offered_precision: u8,
+    requested_precision: u8,
+) -> Result<Vec<LadderRung>> {
+    amounts
+        .iter()
+        .zip(prices)
+        .map(|(&offered_amount, price)| {
+            let requested_amount = price
+                .quote_amount(offered_amount, offered_precision, requested_precision)
+                .ok_or_else(|| {
+                    Error::InvalidOfferLadder("the requested amount is too large".to_string())
+                })?;
+
+            if requested_amount == 0 {
+                return Err(Error::InvalidOfferLadder(
+                    "the price is too low for the size of a rung".to_string(),
+                ));
+            }
+
+            Ok(LadderRung {
+                offered_amount,
+                requested_amount,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+
+    fn prices(min: &str, max: &str, step: Option<&str>, count: Option<u32>) -> Result<Vec<String>> {
+        Ok(ladder_prices(
+            parse_price(min)?,
+            parse_price(max)?,
+            step.map(parse_price).transpose()?,
+            count,
+        )?
+        .into_iter()
+        .map(|price| price.to_string())
+        .collect())
+    }
+
+    #[test]
+    fn test_ladder_prices() {
+        assert_eq!(
+            prices("0.01", "0.02", None, Some(3)).unwrap(),
+            ["0.01", "0.015", "0.02"]
+        );
+        assert_eq!(
+            prices("1", "2", Some("0.3"), None).unwrap(),
+            ["1", "1.3", "1.6", "1.9"]
+        );
+        assert!(prices("2", "1", None, Some(2)).is_err());
+        assert!(prices("1", "2", None, None).is_err());
+        assert!(prices("0", "2", None, Some(2)).is_err());
+        assert!(prices("-1", "2", None, Some(2)).is_err());
+    }
+
+    #[test]
+    fn test_ladder_amounts() {
+        assert_eq!(
+            ladder_amounts(100, 3, LadderDistribution::Uniform).unwrap(),
+            [33, 33, 34]
+        );
+        assert_eq!(
+            ladder_amounts(60, 3, LadderDistribution::Increasing).unwrap(),
+            [10, 20, 30]
+        );
+        assert_eq!(
+            ladder_amounts(60, 3, LadderDistribution::Decreasing).unwrap(),
+            [30, 20, 10]
+        );
+        assert!(ladder_amounts(2, 3, LadderDistribution::Uniform).is_err());
+    }
+
+    #[test]
+    fn test_ladder_rungs() {
+        // Offering 1 CAT (1000 mojos) at 0.5 XCH each requests 0.5 XCH.
+        let rungs = ladder_rungs(&[1000], &[parse_price("0.5").unwrap()], 3, 12).unwrap();
+        assert_eq!(
+            rungs,
+            [LadderRung {
+                offered_amount: 1000,
+                requested_amount: 500_000_000_000
+            }]
+        );
+
+        // A price which rounds down to nothing is rejected.
+        assert!(ladder_rungs(&[1], &[parse_price("0.0001").unwrap()], 3, 3).is_err());
+    }
+}
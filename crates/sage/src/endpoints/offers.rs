@@ -1,4 +1,4 @@
-use chia::protocol::Bytes32;
+use chia::protocol::{Bytes32, Coin, SpendBundle};
 use chia::puzzles::nft::NftMetadata;
 use chia_wallet_sdk::{
     driver::{decode_offer, encode_offer, DriverError, Offer, SpendContext},
@@ -11,26 +11,44 @@ use sage_api::{
     Amount, CancelOffer, CancelOfferResponse, CancelOffers, CancelOffersResponse, CombineOffers,
     CombineOffersResponse, DeleteOffer, DeleteOfferResponse, GetOffer, GetOfferResponse, GetOffers,
     GetOffersForAsset, GetOffersForAssetResponse, GetOffersResponse, ImportOffer,
-    ImportOfferResponse, MakeOffer, MakeOfferResponse, NftRoyalty, OfferAmount, OfferAsset,
-    OfferRecord, OfferRecordStatus, OfferSummary, OptionAssets, TakeOffer, TakeOfferResponse,
+    ImportOfferResponse, ImportOfferResult, ImportOfferStatus, ImportOffers, ImportOffersFromUrl,
+    ImportOffersFromUrlResponse, ImportOffersResponse, MakeNftOffers, MakeNftOffersResponse,
+    MakeOffer, MakeOfferResponse, NftOffer, NftRoyalty, NftRoyaltyPayment, OfferAmount, OfferAsset,
+    OfferCoinState, OfferCoinStatus, OfferPublicationRecord, OfferRecord, OfferRecordStatus,
+    OfferSummary, OfferTradePrice, OptionAssets, PreflightOffer, PreflightOfferResponse,
+    PreviewOfferRoyalties, PreviewOfferRoyaltiesResponse, SetOfferAutoRecreate,
+    SetOfferAutoRecreateResponse, TakeOffer, TakeOfferResponse, TakeOffers, TakeOffersResponse,
     ViewOffer, ViewOfferResponse,
 };
 use sage_assets::fetch_uris_with_hash;
-use sage_database::{AssetKind, OfferRow, OfferStatus, OfferedAsset};
+use sage_database::{
+    AssetKind, DatabaseTx, NftGroupSearch, NftSortMode, OfferRow, OfferStatus, OfferedAsset,
+};
 use sage_wallet::{
-    aggregate_offers, insert_transaction, sort_offer, Offered, Requested, RequestedCat,
-    SyncCommand, Transaction, Wallet, WalletError,
+    aggregate_offers, insert_transaction, offer_input_coins, sort_offer, OfferExpiry, Offered,
+    Requested, RequestedCat, SyncCommand, Transaction, Wallet, WalletError,
+};
+use std::{
+    collections::HashSet,
+    time::{Duration, SystemTime, UNIX_EPOCH},
 };
-use std::time::{Duration, SystemTime, UNIX_EPOCH};
 use tokio::time::timeout;
-use tracing::debug;
+use tracing::{debug, warn};
 
 use crate::{
-    extract_nft_data, json_bundle, offer_expiration, parse_amount, parse_asset_id, parse_hash,
-    parse_nft_id, parse_offer_id, parse_option_id, ConfirmationInfo, Error, ExtractedNftData,
-    Result, Sage,
+    extract_nft_data, json_bundle, offer_expiration, parse_amount, parse_asset_id,
+    parse_collection_id, parse_hash, parse_nft_id, parse_offer_id, parse_option_id,
+    ConfirmationInfo, Error, ExtractedNftData, Result, Sage,
 };
 
+/// The rows for an offer which is ready to be inserted into the database.
+#[derive(Debug, Clone)]
+pub(crate) struct OfferImport {
+    pub row: OfferRow,
+    pub coin_ids: Vec<Bytes32>,
+    assets: Vec<AssetToOffer>,
+}
+
 #[derive(Debug, Clone)]
 struct AssetToOffer {
     offer_id: Bytes32,
@@ -40,24 +58,97 @@ struct AssetToOffer {
     is_requested: bool,
 }
 
+/// An asset given or received by taking an offer.
+#[derive(Debug, Clone, Copy)]
+struct TakenAsset {
+    asset_id: Bytes32,
+    is_received: bool,
+    amount: u64,
+    royalty: u64,
+}
+
+impl TakenAsset {
+    /// NFTs and options are traded one at a time, and royalties are paid in the other assets.
+    fn nft(launcher_id: Bytes32, is_received: bool) -> Self {
+        Self {
+            asset_id: launcher_id,
+            is_received,
+            amount: 1,
+            royalty: 0,
+        }
+    }
+}
+
 impl Sage {
     pub async fn make_offer(&self, req: MakeOffer) -> Result<MakeOfferResponse> {
         let wallet = self.wallet()?;
 
-        let mut offered = Offered {
-            fee: parse_amount(req.fee)?,
-            p2_puzzle_hash: req
-                .receive_address
-                .map(|address| self.parse_address(address))
-                .transpose()?,
-            ..Default::default()
+        let expiry = offer_expiry(
+            &req,
+            wallet.db.latest_peak().await?.map(|(height, _)| height),
+            SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .expect("system time is before the UNIX epoch")
+                .as_secs(),
+        )?;
+
+        let (mut offered, requested) = self
+            .offer_amounts(&wallet, req.offered_assets, req.requested_assets)
+            .await?;
+
+        offered.fee = parse_amount(req.fee)?;
+        offered.p2_puzzle_hash = req
+            .receive_address
+            .map(|address| self.parse_address(address))
+            .transpose()?;
+
+        let unsigned = wallet.make_offer(offered, requested, expiry).await?;
+
+        let (_mnemonic, Some(master_sk)) =
+            self.keychain.extract_secrets(wallet.fingerprint, b"")?
+        else {
+            return Err(Error::NoSigningKey);
         };
 
+        let offer = wallet
+            .sign_transaction(
+                unsigned,
+                &AggSigConstants::new(self.network().agg_sig_me()),
+                master_sk,
+                false,
+            )
+            .await?;
+
+        let encoded_offer = encode_offer(&offer)?;
+
+        if req.auto_import {
+            self.import_offer(ImportOffer {
+                offer: encoded_offer.clone(),
+            })
+            .await?;
+        }
+
+        Ok(MakeOfferResponse {
+            offer: encoded_offer,
+            offer_id: hex::encode(sort_offer(offer).name()),
+        })
+    }
+
+    /// Parses the assets on each side of an offer, fetching the info needed to request NFTs and
+    /// options from a peer if they aren't in the wallet.
+    async fn offer_amounts(
+        &self,
+        wallet: &Wallet,
+        offered_assets: Vec<OfferAmount>,
+        requested_assets: Vec<OfferAmount>,
+    ) -> Result<(Offered, Requested)> {
+        let mut offered = Offered::default();
+
         for OfferAmount {
             asset_id,
             amount: raw_amount,
             hidden_puzzle_hash: _, // We ignore this since we already have it
-        } in req.offered_assets
+        } in offered_assets
         {
             let amount = parse_amount(raw_amount.clone())?;
 
@@ -91,7 +182,7 @@ impl Sage {
             asset_id,
             hidden_puzzle_hash,
             amount: raw_amount,
-        } in req.requested_assets
+        } in requested_assets
         {
             let amount = parse_amount(raw_amount.clone())?;
 
@@ -151,38 +242,117 @@ impl Sage {
             }
         }
 
-        let unsigned = wallet
-            .make_offer(offered, requested, req.expires_at_second)
-            .await?;
+        Ok((offered, requested))
+    }
 
-        let (_mnemonic, Some(master_sk)) =
-            self.keychain.extract_secrets(wallet.fingerprint, b"")?
-        else {
-            return Err(Error::NoSigningKey);
-        };
+    pub async fn preview_offer_royalties(
+        &self,
+        req: PreviewOfferRoyalties,
+    ) -> Result<PreviewOfferRoyaltiesResponse> {
+        let wallet = self.wallet()?;
 
-        let offer = wallet
-            .sign_transaction(
-                unsigned,
-                &AggSigConstants::new(self.network().agg_sig_me()),
-                master_sk,
-                false,
-            )
+        let (offered, requested) = self
+            .offer_amounts(&wallet, req.offered_assets, req.requested_assets)
             .await?;
 
-        let encoded_offer = encode_offer(&offer)?;
+        let royalties = wallet.offer_royalties(&offered, &requested).await?;
 
-        if req.auto_import {
-            self.import_offer(ImportOffer {
-                offer: encoded_offer.clone(),
-            })
-            .await?;
+        Ok(PreviewOfferRoyaltiesResponse {
+            royalties: royalties
+                .payments
+                .into_iter()
+                .map(|payment| {
+                    Ok(NftRoyaltyPayment {
+                        nft_id: Address::new(payment.launcher_id, "nft".to_string()).encode()?,
+                        royalty_address: Address::new(
+                            payment.royalty_puzzle_hash,
+                            self.network().prefix(),
+                        )
+                        .encode()?,
+                        royalty_basis_points: payment.royalty_basis_points,
+                        asset_id: payment.asset_id.map(hex::encode),
+                        trade_price: Amount::u64(payment.trade_price),
+                        amount: Amount::u64(payment.amount),
+                        is_requested: payment.is_requested,
+                    })
+                })
+                .collect::<Result<_>>()?,
+            trade_prices: royalties
+                .trade_prices
+                .into_iter()
+                .map(|(asset_id, trade_price)| OfferTradePrice {
+                    asset_id: asset_id.map(hex::encode),
+                    amount: Amount::u64(trade_price.amount),
+                    puzzle_hash: hex::encode(trade_price.puzzle_hash),
+                })
+                .collect(),
+        })
+    }
+
+    pub async fn make_nft_offers(&self, req: MakeNftOffers) -> Result<MakeNftOffersResponse> {
+        let wallet = self.wallet()?;
+
+        let nft_ids = match (req.nft_ids.is_empty(), req.collection_id) {
+            (false, None) => req.nft_ids,
+            (true, Some(collection_id)) => {
+                let (nfts, _) = wallet
+                    .db
+                    .owned_nfts(
+                        None,
+                        Some(NftGroupSearch::Collection(parse_collection_id(
+                            collection_id,
+                        )?)),
+                        &[],
+                        NftSortMode::Name,
+                        true,
+                        u32::MAX,
+                        0,
+                    )
+                    .await?;
+
+                let mut nft_ids = Vec::new();
+
+                // NFTs which are already locked in an offer can't be offered again
+                for nft in nfts {
+                    if wallet.db.spendable_nft(nft.asset.hash).await?.is_some() {
+                        nft_ids.push(Address::new(nft.asset.hash, "nft".to_string()).encode()?);
+                    }
+                }
+
+                nft_ids
+            }
+            _ => return Err(Error::InvalidNftSelection),
+        };
+
+        let mut offers = Vec::new();
+
+        for nft_id in nft_ids {
+            let response = self
+                .make_offer(MakeOffer {
+                    requested_assets: req.requested_assets.clone(),
+                    offered_assets: vec![OfferAmount {
+                        asset_id: Some(nft_id.clone()),
+                        hidden_puzzle_hash: None,
+                        amount: Amount::u64(1),
+                    }],
+                    fee: req.fee.clone(),
+                    receive_address: req.receive_address.clone(),
+                    expires_at_second: req.expires_at_second,
+                    expires_at_height: req.expires_at_height,
+                    expires_in_blocks: req.expires_in_blocks,
+                    expires_in_seconds: req.expires_in_seconds,
+                    auto_import: true,
+                })
+                .await?;
+
+            offers.push(NftOffer {
+                nft_id,
+                offer: response.offer,
+                offer_id: response.offer_id,
+            });
         }
 
-        Ok(MakeOfferResponse {
-            offer: encoded_offer,
-            offer_id: hex::encode(sort_offer(offer).name()),
-        })
+        Ok(MakeNftOffersResponse { offers })
     }
 
     pub async fn take_offer(&self, req: TakeOffer) -> Result<TakeOfferResponse> {
@@ -191,8 +361,63 @@ impl Sage {
         let offer = decode_offer(&req.offer)?;
         let fee = parse_amount(req.fee)?;
 
-        let unsigned = wallet.take_offer(offer, fee).await?;
+        let unsigned = wallet.take_offer(offer.clone(), fee).await?;
+
+        self.complete_take(&wallet, unsigned, &[offer], fee, req.auto_submit)
+            .await
+    }
+
+    pub async fn take_offers(&self, req: TakeOffers) -> Result<TakeOffersResponse> {
+        let wallet = self.wallet()?;
+
+        let offers = req
+            .offers
+            .iter()
+            .map(|offer| Ok(decode_offer(offer)?))
+            .collect::<Result<Vec<_>>>()?;
+        let fee = parse_amount(req.fee)?;
+
+        // If any of the offers can no longer be taken, none of them are
+        let coin_ids = offers
+            .iter()
+            .flat_map(offer_input_coins)
+            .map(|coin| coin.coin_id())
+            .collect_vec();
+
+        let peer = self
+            .peer_state
+            .lock()
+            .await
+            .acquire_peer()
+            .ok_or(Error::NoPeers)?;
+
+        for coin_state in peer.fetch_coins(coin_ids, wallet.genesis_challenge).await? {
+            if coin_state.spent_height.is_some() {
+                return Err(Error::CoinSpent(coin_state.coin.coin_id()));
+            }
+        }
+
+        let unsigned = wallet.take_offers(offers.clone(), fee).await?;
+
+        let response = self
+            .complete_take(&wallet, unsigned, &offers, fee, req.auto_submit)
+            .await?;
+
+        Ok(TakeOffersResponse {
+            summary: response.summary,
+            spend_bundle: response.spend_bundle,
+            transaction_id: response.transaction_id,
+        })
+    }
 
+    async fn complete_take(
+        &self,
+        wallet: &Wallet,
+        unsigned: SpendBundle,
+        offers: &[SpendBundle],
+        fee: u64,
+        auto_submit: bool,
+    ) -> Result<TakeOfferResponse> {
         let (_mnemonic, Some(master_sk)) =
             self.keychain.extract_secrets(wallet.fingerprint, b"")?
         else {
@@ -213,7 +438,7 @@ impl Sage {
             serde_json::to_string(&json_bundle(&spend_bundle)).expect("msg")
         );
 
-        if req.auto_submit {
+        if auto_submit {
             let peer = self
                 .peer_state
                 .lock()
@@ -238,6 +463,11 @@ impl Sage {
                 .await?;
         }
 
+        // The trades are recorded even if the transaction is submitted later. If it never is,
+        // they're removed by the offer queue once they've gone stale.
+        self.insert_taker_trades(wallet, offers, spend_bundle.name(), fee)
+            .await?;
+
         let json_bundle = json_bundle(&spend_bundle);
         let transaction_id = hex::encode(spend_bundle.name());
 
@@ -250,6 +480,160 @@ impl Sage {
         })
     }
 
+    /// Records a trade for each of the offers taken in a transaction, which are filled once it
+    /// has been confirmed. The fee is counted against the first of them.
+    async fn insert_taker_trades(
+        &self,
+        wallet: &Wallet,
+        offers: &[SpendBundle],
+        transaction_id: Bytes32,
+        mut fee: u64,
+    ) -> Result<()> {
+        let mut trades = Vec::new();
+
+        for spend_bundle in offers {
+            let offer_id = sort_offer(spend_bundle.clone()).name();
+
+            let mut ctx = SpendContext::new();
+            let offer = Offer::from_spend_bundle(&mut ctx, spend_bundle)?;
+
+            let Some(settlement_coin) = offer.offered_coins().flatten().into_iter().next() else {
+                continue;
+            };
+
+            let assets = self.taken_assets(&ctx, &offer).await?;
+
+            trades.push((offer_id, settlement_coin.coin_id(), assets));
+        }
+
+        let mut tx = wallet.db.tx().await?;
+
+        for (offer_id, settlement_coin_id, assets) in trades {
+            let Some(trade_id) = tx
+                .insert_trade(
+                    offer_id,
+                    false,
+                    settlement_coin_id,
+                    Some(transaction_id),
+                    fee,
+                )
+                .await?
+            else {
+                continue;
+            };
+
+            fee = 0;
+
+            for asset in assets {
+                tx.insert_trade_asset(
+                    trade_id,
+                    asset.asset_id,
+                    asset.is_received,
+                    asset.amount,
+                    asset.royalty,
+                )
+                .await?;
+            }
+        }
+
+        tx.commit().await?;
+
+        Ok(())
+    }
+
+    /// The assets given and received by taking an offer, which are cached if they aren't known.
+    /// Royalties are paid by the side which gives the asset they're paid in.
+    async fn taken_assets(&self, ctx: &SpendContext, offer: &Offer) -> Result<Vec<TakenAsset>> {
+        let offered_amounts = offer.offered_coins().amounts();
+        let requested_amounts = offer.requested_payments().amounts();
+        let offered_royalties = offer.offered_royalty_amounts();
+        let requested_royalties = offer.requested_royalty_amounts();
+
+        let mut assets = Vec::new();
+
+        for (is_received, amounts, royalties) in [
+            (true, &offered_amounts, &offered_royalties),
+            (false, &requested_amounts, &requested_royalties),
+        ] {
+            if amounts.xch > 0 || royalties.xch > 0 {
+                assets.push(TakenAsset {
+                    asset_id: Bytes32::default(),
+                    is_received,
+                    amount: amounts.xch,
+                    royalty: royalties.xch,
+                });
+            }
+
+            for (&asset_id, &amount) in &amounts.cats {
+                let hidden_puzzle_hash = offer
+                    .asset_info()
+                    .cat(asset_id)
+                    .and_then(|cat| cat.hidden_puzzle_hash);
+
+                self.cache_cat(asset_id, hidden_puzzle_hash).await?;
+
+                assets.push(TakenAsset {
+                    asset_id,
+                    is_received,
+                    amount,
+                    royalty: royalties.cats.get(&asset_id).copied().unwrap_or(0),
+                });
+            }
+        }
+
+        for nft in offer.offered_coins().nfts.values() {
+            self.cache_nft(
+                ctx,
+                nft.info.launcher_id,
+                nft.info.metadata.ptr(),
+                &mut ConfirmationInfo::default(),
+            )
+            .await?;
+
+            assets.push(TakenAsset::nft(nft.info.launcher_id, true));
+        }
+
+        for &launcher_id in offer.requested_payments().nfts.keys() {
+            let nft = offer
+                .asset_info()
+                .nft(launcher_id)
+                .ok_or(DriverError::MissingAssetInfo)?;
+
+            self.cache_nft(
+                ctx,
+                launcher_id,
+                nft.metadata.ptr(),
+                &mut ConfirmationInfo::default(),
+            )
+            .await?;
+
+            assets.push(TakenAsset::nft(launcher_id, false));
+        }
+
+        for (launcher_ids, is_received) in [
+            (
+                offer.offered_coins().options.keys().copied().collect_vec(),
+                true,
+            ),
+            (
+                offer
+                    .requested_payments()
+                    .options
+                    .keys()
+                    .copied()
+                    .collect_vec(),
+                false,
+            ),
+        ] {
+            for launcher_id in launcher_ids {
+                self.cache_option(launcher_id).await?;
+                assets.push(TakenAsset::nft(launcher_id, is_received));
+            }
+        }
+
+        Ok(assets)
+    }
+
     pub async fn view_offer(&self, req: ViewOffer) -> Result<ViewOfferResponse> {
         let (offer, status) = self.summarize_offer(decode_offer(&req.offer)?).await?;
 
@@ -261,14 +645,69 @@ impl Sage {
                 OfferStatus::Completed => OfferRecordStatus::Completed,
                 OfferStatus::Cancelled => OfferRecordStatus::Cancelled,
                 OfferStatus::Expired => OfferRecordStatus::Expired,
+                OfferStatus::Invalid => OfferRecordStatus::Invalid,
             },
         })
     }
 
+    pub async fn preflight_offer(&self, req: PreflightOffer) -> Result<PreflightOfferResponse> {
+        let wallet = self.wallet()?;
+
+        let spend_bundle = decode_offer(&req.offer)?;
+        let fee = parse_amount(req.fee)?;
+
+        let mut ctx = SpendContext::new();
+        let offer = Offer::from_spend_bundle(&mut ctx, &spend_bundle)?;
+        let expiration = offer_expiration(&mut ctx, &offer)?;
+
+        let (peer, peak_height) = {
+            let peer_state = self.peer_state.lock().await;
+
+            (
+                peer_state.acquire_peer().ok_or(Error::NoPeers)?,
+                peer_state.peak().map(|(height, _)| height),
+            )
+        };
+
+        let preflight = wallet.preflight_offer(&peer, spend_bundle, fee).await?;
+
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+
+        let expired = expiration.is_expired(peak_height, now);
+
+        Ok(PreflightOfferResponse {
+            takeable: preflight.is_takeable(expired),
+            coins: preflight
+                .coins
+                .into_iter()
+                .map(|(coin, coin_state)| OfferCoinState {
+                    coin_id: hex::encode(coin.coin_id()),
+                    amount: Amount::u64(coin.amount),
+                    status: match coin_state {
+                        Some(coin_state) if coin_state.spent_height.is_some() => {
+                            OfferCoinStatus::Spent
+                        }
+                        Some(_) => OfferCoinStatus::Unspent,
+                        None => OfferCoinStatus::Missing,
+                    },
+                    spent_height: coin_state.and_then(|coin_state| coin_state.spent_height),
+                })
+                .collect(),
+            peak_height,
+            expired,
+            expiration_height: expiration.expiration_height,
+            expiration_timestamp: expiration.expiration_timestamp,
+            cost: preflight.cost,
+            error: preflight.error,
+        })
+    }
+
     pub async fn import_offer(&self, req: ImportOffer) -> Result<ImportOfferResponse> {
         let wallet = self.wallet()?;
-        let spend_bundle = sort_offer(decode_offer(&req.offer)?);
-        let offer_id = spend_bundle.name();
+        let offer_id = sort_offer(decode_offer(&req.offer)?).name();
 
         if wallet.db.offer(offer_id).await?.is_some() {
             return Ok(ImportOfferResponse {
@@ -276,6 +715,192 @@ impl Sage {
             });
         }
 
+        let import = self.prepare_offer_import(req.offer).await?;
+
+        let mut tx = wallet.db.tx().await?;
+        insert_offer_import(&mut tx, import).await?;
+        tx.commit().await?;
+
+        Ok(ImportOfferResponse {
+            offer_id: hex::encode(offer_id),
+        })
+    }
+
+    pub async fn import_offers(&self, req: ImportOffers) -> Result<ImportOffersResponse> {
+        Ok(ImportOffersResponse {
+            results: self.import_offer_batch(req.offers, req.verify).await?,
+        })
+    }
+
+    pub async fn import_offers_from_url(
+        &self,
+        req: ImportOffersFromUrl,
+    ) -> Result<ImportOffersFromUrlResponse> {
+        let content = self.uri_resolver().fetch(&req.url, None).await?;
+        let offers = offer_lines(&String::from_utf8_lossy(&content.blob));
+
+        Ok(ImportOffersFromUrlResponse {
+            results: self.import_offer_batch(offers, req.verify).await?,
+        })
+    }
+
+    /// Imports each offer on its own, so that an invalid offer is reported in its result rather
+    /// than failing the rest of the batch.
+    async fn import_offer_batch(
+        &self,
+        offers: Vec<String>,
+        verify: bool,
+    ) -> Result<Vec<ImportOfferResult>> {
+        let wallet = self.wallet()?;
+
+        let peer = if verify {
+            let peer_state = self.peer_state.lock().await;
+
+            Some((
+                peer_state.acquire_peer().ok_or(Error::NoPeers)?,
+                peer_state.peak().map(|(height, _)| height),
+            ))
+        } else {
+            None
+        };
+
+        let mut results = Vec::with_capacity(offers.len());
+        let mut offers_to_verify = Vec::new();
+
+        for encoded_offer in offers {
+            let spend_bundle = match decode_offer(&encoded_offer) {
+                Ok(spend_bundle) => spend_bundle,
+                Err(error) => {
+                    results.push(ImportOfferResult {
+                        offer_id: None,
+                        status: ImportOfferStatus::Invalid,
+                        error: Some(error.to_string()),
+                        live: None,
+                    });
+                    continue;
+                }
+            };
+
+            let offer_id = sort_offer(spend_bundle.clone()).name();
+
+            let status = if wallet.db.offer(offer_id).await?.is_some() {
+                ImportOfferStatus::Duplicate
+            } else {
+                let imported = async {
+                    let import = self.prepare_offer_import(encoded_offer).await?;
+
+                    let mut tx = wallet.db.tx().await?;
+                    insert_offer_import(&mut tx, import).await?;
+                    tx.commit().await?;
+
+                    Ok::<_, Error>(())
+                }
+                .await;
+
+                if let Err(error) = imported {
+                    results.push(ImportOfferResult {
+                        offer_id: Some(hex::encode(offer_id)),
+                        status: ImportOfferStatus::Invalid,
+                        error: Some(error.to_string()),
+                        live: None,
+                    });
+                    continue;
+                }
+
+                ImportOfferStatus::Imported
+            };
+
+            let mut error = None;
+
+            if verify {
+                let mut ctx = SpendContext::new();
+
+                match Offer::from_spend_bundle(&mut ctx, &spend_bundle)
+                    .map_err(Error::from)
+                    .and_then(|offer| offer_expiration(&mut ctx, &offer))
+                {
+                    Ok(expiration) => offers_to_verify.push((
+                        results.len(),
+                        offer_input_coins(&spend_bundle),
+                        expiration,
+                    )),
+                    Err(verify_error) => {
+                        error = Some(format!("The offer couldn't be verified: {verify_error}"));
+                    }
+                }
+            }
+
+            results.push(ImportOfferResult {
+                offer_id: Some(hex::encode(offer_id)),
+                status,
+                error,
+                live: None,
+            });
+        }
+
+        let Some((peer, peak_height)) = peer else {
+            return Ok(results);
+        };
+
+        let coin_ids = offers_to_verify
+            .iter()
+            .flat_map(|(_, coins, _)| coins.iter().map(Coin::coin_id))
+            .collect_vec();
+
+        let mut unspent_coin_ids = HashSet::new();
+        let mut unverified_coin_ids = HashSet::new();
+
+        // If a batch of coins can't be fetched, the offers spending them are left unverified
+        // rather than failing the import.
+        for batch in coin_ids.chunks(1000) {
+            match peer
+                .fetch_coins(batch.to_vec(), wallet.genesis_challenge)
+                .await
+            {
+                Ok(coin_states) => {
+                    for coin_state in coin_states {
+                        if coin_state.spent_height.is_none() {
+                            unspent_coin_ids.insert(coin_state.coin.coin_id());
+                        }
+                    }
+                }
+                Err(error) => {
+                    warn!("Failed to fetch the coins of imported offers: {error}");
+                    unverified_coin_ids.extend(batch.iter().copied());
+                }
+            }
+        }
+
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+
+        for (index, coins, expiration) in offers_to_verify {
+            if coins
+                .iter()
+                .any(|coin| unverified_coin_ids.contains(&coin.coin_id()))
+            {
+                continue;
+            }
+
+            results[index].live = Some(
+                !expiration.is_expired(peak_height, now)
+                    && coins
+                        .iter()
+                        .all(|coin| unspent_coin_ids.contains(&coin.coin_id())),
+            );
+        }
+
+        Ok(results)
+    }
+
+    /// Caches the assets in the offer and works out the rows to insert, without inserting them.
+    pub(crate) async fn prepare_offer_import(&self, encoded_offer: String) -> Result<OfferImport> {
+        let wallet = self.wallet()?;
+        let spend_bundle = sort_offer(decode_offer(&encoded_offer)?);
+        let offer_id = spend_bundle.name();
+
         let mut ctx = SpendContext::new();
         let offer = Offer::from_spend_bundle(&mut ctx, &spend_bundle)?;
         let coin_ids = offer
@@ -306,6 +931,7 @@ impl Sage {
         }
 
         let testnet = self.network().genesis_challenge == TESTNET11_CONSTANTS.genesis_challenge;
+        let resolver = self.uri_resolver();
 
         for nft in offer.offered_coins().nfts.values() {
             let _info = if let Ok(metadata) = ctx.extract::<NftMetadata>(nft.info.metadata.ptr()) {
@@ -314,7 +940,7 @@ impl Sage {
                 if let Some(hash) = metadata.data_hash {
                     if let Ok(Some(data)) = timeout(
                         Duration::from_secs(10),
-                        fetch_uris_with_hash(metadata.data_uris.clone(), hash, testnet),
+                        fetch_uris_with_hash(&resolver, metadata.data_uris.clone(), hash, testnet),
                     )
                     .await
                     {
@@ -325,7 +951,12 @@ impl Sage {
                 if let Some(hash) = metadata.metadata_hash {
                     if let Ok(Some(data)) = timeout(
                         Duration::from_secs(10),
-                        fetch_uris_with_hash(metadata.metadata_uris.clone(), hash, testnet),
+                        fetch_uris_with_hash(
+                            &resolver,
+                            metadata.metadata_uris.clone(),
+                            hash,
+                            testnet,
+                        ),
                     )
                     .await
                     {
@@ -398,7 +1029,7 @@ impl Sage {
                 if let Some(hash) = metadata.data_hash {
                     if let Ok(Some(data)) = timeout(
                         Duration::from_secs(10),
-                        fetch_uris_with_hash(metadata.data_uris.clone(), hash, testnet),
+                        fetch_uris_with_hash(&resolver, metadata.data_uris.clone(), hash, testnet),
                     )
                     .await
                     {
@@ -409,7 +1040,12 @@ impl Sage {
                 if let Some(hash) = metadata.metadata_hash {
                     if let Ok(Some(data)) = timeout(
                         Duration::from_secs(10),
-                        fetch_uris_with_hash(metadata.metadata_uris.clone(), hash, testnet),
+                        fetch_uris_with_hash(
+                            &resolver,
+                            metadata.metadata_uris.clone(),
+                            hash,
+                            testnet,
+                        ),
                     )
                     .await
                     {
@@ -443,91 +1079,53 @@ impl Sage {
             });
         }
 
-        let mut tx = wallet.db.tx().await?;
-
         let inserted_timestamp = SystemTime::now()
             .duration_since(UNIX_EPOCH)
             .expect("system time is before the UNIX epoch")
             .as_secs();
 
-        tx.insert_offer(OfferRow {
-            offer_id,
-            encoded_offer: req.offer,
-            expiration_height: status.expiration_height,
-            expiration_timestamp: status.expiration_timestamp,
-            fee: offer.offered_coins().fee,
-            status: OfferStatus::Active,
-            inserted_timestamp,
-        })
-        .await?;
-
-        for coin_id in coin_ids {
-            if !tx.is_known_coin(coin_id).await? {
-                return Err(Error::Wallet(WalletError::CannotImportOffer));
-            }
-
-            tx.insert_offered_coin(offer_id, coin_id).await?;
-        }
+        let mut assets = Vec::new();
 
         if offered_amounts.xch > 0 || offered_royalties.xch > 0 {
-            tx.insert_offer_asset(
+            assets.push(AssetToOffer {
                 offer_id,
-                Bytes32::default(),
-                offered_amounts.xch,
-                offered_royalties.xch,
-                false,
-            )
-            .await?;
+                is_requested: false,
+                asset_id: Bytes32::default(),
+                amount: offered_amounts.xch,
+                royalty: offered_royalties.xch,
+            });
         }
 
         if requested_amounts.xch > 0 || requested_royalties.xch > 0 {
-            tx.insert_offer_asset(
+            assets.push(AssetToOffer {
                 offer_id,
-                Bytes32::default(),
-                requested_amounts.xch,
-                requested_royalties.xch,
-                true,
-            )
-            .await?;
-        }
-
-        for row in cat_rows {
-            tx.insert_offer_asset(
-                row.offer_id,
-                row.asset_id,
-                row.amount,
-                row.royalty,
-                row.is_requested,
-            )
-            .await?;
-        }
-
-        for row in nft_rows {
-            tx.insert_offer_asset(
-                row.offer_id,
-                row.asset_id,
-                row.amount,
-                row.royalty,
-                row.is_requested,
-            )
-            .await?;
-        }
-
-        for row in option_rows {
-            tx.insert_offer_asset(
-                row.offer_id,
-                row.asset_id,
-                row.amount,
-                row.royalty,
-                row.is_requested,
-            )
-            .await?;
+                is_requested: true,
+                asset_id: Bytes32::default(),
+                amount: requested_amounts.xch,
+                royalty: requested_royalties.xch,
+            });
         }
 
-        tx.commit().await?;
+        assets.extend(cat_rows);
+        assets.extend(nft_rows);
+        assets.extend(option_rows);
 
-        Ok(ImportOfferResponse {
-            offer_id: hex::encode(offer_id),
+        Ok(OfferImport {
+            row: OfferRow {
+                offer_id,
+                encoded_offer,
+                expiration_height: status.expiration_height,
+                expiration_timestamp: status.expiration_timestamp,
+                fee: offer.offered_coins().fee,
+                status: OfferStatus::Active,
+                inserted_timestamp,
+                invalid_reason: None,
+                auto_recreate: false,
+                recreated_offer_id: None,
+                recreate_error: None,
+            },
+            coin_ids,
+            assets,
         })
     }
 
@@ -607,7 +1205,30 @@ impl Sage {
         Ok(DeleteOfferResponse {})
     }
 
-    async fn offer_record(&self, wallet: &Wallet, offer: OfferRow) -> Result<OfferRecord> {
+    pub async fn set_offer_auto_recreate(
+        &self,
+        req: SetOfferAutoRecreate,
+    ) -> Result<SetOfferAutoRecreateResponse> {
+        let wallet = self.wallet()?;
+        let offer_id = parse_offer_id(req.offer_id)?;
+
+        if wallet.db.offer(offer_id).await?.is_none() {
+            return Err(Error::MissingOffer(offer_id));
+        }
+
+        wallet
+            .db
+            .set_offer_auto_recreate(offer_id, req.auto_recreate)
+            .await?;
+
+        Ok(SetOfferAutoRecreateResponse {})
+    }
+
+    pub(crate) async fn offer_record(
+        &self,
+        wallet: &Wallet,
+        offer: OfferRow,
+    ) -> Result<OfferRecord> {
         let assets = wallet.db.offer_assets(offer.offer_id).await?;
 
         let mut maker = Vec::new();
@@ -670,6 +1291,25 @@ impl Sage {
             }
         }
 
+        let publications = wallet
+            .db
+            .offer_publications(offer.offer_id)
+            .await?
+            .into_iter()
+            .map(|row| OfferPublicationRecord {
+                exchange: row.exchange,
+                code: row.code,
+                published_timestamp: row.published_timestamp,
+            })
+            .collect();
+
+        let peak_height = wallet.db.latest_peak().await?.map(|(height, _)| height);
+
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("system time is before the UNIX epoch")
+            .as_secs();
+
         Ok(OfferRecord {
             offer_id: hex::encode(offer.offer_id),
             offer: offer.encoded_offer,
@@ -679,6 +1319,7 @@ impl Sage {
                 OfferStatus::Completed => OfferRecordStatus::Completed,
                 OfferStatus::Cancelled => OfferRecordStatus::Cancelled,
                 OfferStatus::Expired => OfferRecordStatus::Expired,
+                OfferStatus::Invalid => OfferRecordStatus::Invalid,
             },
             creation_timestamp: offer.inserted_timestamp,
             summary: OfferSummary {
@@ -688,6 +1329,17 @@ impl Sage {
                 expiration_height: offer.expiration_height,
                 expiration_timestamp: offer.expiration_timestamp,
             },
+            blocks_until_expiration: offer.expiration_height.and_then(|height| {
+                peak_height.map(|peak_height| height.saturating_sub(peak_height))
+            }),
+            seconds_until_expiration: offer
+                .expiration_timestamp
+                .map(|timestamp| timestamp.saturating_sub(now)),
+            publications,
+            invalid_reason: offer.invalid_reason,
+            auto_recreate: offer.auto_recreate,
+            recreated_offer_id: offer.recreated_offer_id.map(hex::encode),
+            recreate_error: offer.recreate_error,
         })
     }
 
@@ -731,3 +1383,351 @@ impl Sage {
         self.transact(coin_spends, req.auto_submit).await
     }
 }
+
+/// Resolves the expirations requested for a new offer against the current peak height and time.
+/// If an offer is given more than one expiration of the same kind, the earliest one is used.
+fn offer_expiry(req: &MakeOffer, peak_height: Option<u32>, now: u64) -> Result<OfferExpiry> {
+    let expires_in_height = req
+        .expires_in_blocks
+        .map(|blocks| {
+            peak_height
+                .map(|peak_height| peak_height.saturating_add(blocks))
+                .ok_or(Error::UnknownPeak)
+        })
+        .transpose()?;
+
+    let expires_in_second = req
+        .expires_in_seconds
+        .map(|seconds| now.saturating_add(seconds));
+
+    Ok(OfferExpiry {
+        height: req
+            .expires_at_height
+            .into_iter()
+            .chain(expires_in_height)
+            .min(),
+        seconds: req
+            .expires_at_second
+            .into_iter()
+            .chain(expires_in_second)
+            .min(),
+    })
+}
+
+/// Splits the contents of an offer file or download into the offers it contains, one per line.
+fn offer_lines(text: &str) -> Vec<String> {
+    text.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(ToString::to_string)
+        .collect()
+}
+
+pub(crate) async fn insert_offer_import(
+    tx: &mut DatabaseTx<'_>,
+    import: OfferImport,
+) -> Result<()> {
+    let offer_id = import.row.offer_id;
+    let coin_ids = insert_offer_record(tx, import).await?;
+    insert_offered_coins(tx, offer_id, coin_ids).await
+}
+
+/// Inserts an offer without linking it to the coins it spends, and returns their ids so that
+/// they can be linked with [`insert_offered_coins`] once they're known to the wallet.
+pub(crate) async fn insert_offer_record(
+    tx: &mut DatabaseTx<'_>,
+    import: OfferImport,
+) -> Result<Vec<Bytes32>> {
+    tx.insert_offer(import.row).await?;
+
+    for row in import.assets {
+        tx.insert_offer_asset(
+            row.offer_id,
+            row.asset_id,
+            row.amount,
+            row.royalty,
+            row.is_requested,
+        )
+        .await?;
+    }
+
+    Ok(import.coin_ids)
+}
+
+pub(crate) async fn insert_offered_coins(
+    tx: &mut DatabaseTx<'_>,
+    offer_id: Bytes32,
+    coin_ids: Vec<Bytes32>,
+) -> Result<()> {
+    for coin_id in coin_ids {
+        if !tx.is_known_coin(coin_id).await? {
+            return Err(Error::Wallet(WalletError::CannotImportOffer));
+        }
+
+        tx.insert_offered_coin(offer_id, coin_id).await?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::{routing::get, Router};
+    use chia::{
+        bls::{SecretKey, Signature},
+        protocol::CoinState,
+        puzzles::{
+            offer::{NotarizedPayment, Payment},
+            standard::StandardArgs,
+            Memos,
+        },
+    };
+    use chia_puzzles::SETTLEMENT_PAYMENT_HASH;
+    use chia_wallet_sdk::{
+        driver::{AssetInfo, RequestedPayments, StandardLayer},
+        types::Conditions,
+    };
+    use sage_config::GatewayConfig;
+    use tokio::net::TcpListener;
+
+    use crate::test_sage;
+
+    use super::*;
+
+    /// An offer of 1000 mojos from a distinct coin, requesting 500 mojos in return.
+    fn test_offer(seed: u8) -> anyhow::Result<(Coin, String)> {
+        let mut ctx = SpendContext::new();
+
+        let public_key = SecretKey::from_seed(&[seed; 32]).public_key();
+        let puzzle_hash = StandardArgs::curry_tree_hash(public_key).into();
+        let coin = Coin::new(Bytes32::new([seed; 32]), puzzle_hash, 1000);
+
+        let requested = RequestedPayments {
+            xch: vec![NotarizedPayment::new(
+                Offer::nonce(vec![coin.coin_id()]),
+                vec![Payment::new(puzzle_hash, 500, Memos::None)],
+            )],
+            ..Default::default()
+        };
+
+        let assertions = requested.assertions(&mut ctx, &AssetInfo::new())?;
+
+        StandardLayer::new(public_key).spend(
+            &mut ctx,
+            coin,
+            Conditions::new()
+                .create_coin(SETTLEMENT_PAYMENT_HASH.into(), 1000, Memos::None)
+                .extend(assertions),
+        )?;
+
+        let coin_spends = ctx.take();
+
+        let offer = Offer::from_input_spend_bundle(
+            &mut ctx,
+            SpendBundle::new(coin_spends, Signature::default()),
+            requested,
+            AssetInfo::new(),
+        )?;
+
+        Ok((coin, encode_offer(&offer.to_spend_bundle(&mut ctx)?)?))
+    }
+
+    /// Serves offers on a local port, both directly and through an IPFS path gateway.
+    async fn offer_server(offers: String) -> anyhow::Result<String> {
+        let app = Router::new()
+            .route("/offers.txt", get(offers.clone()))
+            .route("/ipfs/*path", get(offers));
+
+        let listener = TcpListener::bind("127.0.0.1:0").await?;
+        let address = listener.local_addr()?;
+
+        tokio::spawn(async move { axum::serve(listener, app).await });
+
+        Ok(format!("http://{address}"))
+    }
+
+    fn statuses(results: &[ImportOfferResult]) -> Vec<ImportOfferStatus> {
+        results.iter().map(|result| result.status).collect()
+    }
+
+    fn make_offer(
+        expires_at_height: Option<u32>,
+        expires_in_blocks: Option<u32>,
+        expires_at_second: Option<u64>,
+        expires_in_seconds: Option<u64>,
+    ) -> MakeOffer {
+        MakeOffer {
+            requested_assets: Vec::new(),
+            offered_assets: Vec::new(),
+            fee: Amount::u64(0),
+            receive_address: None,
+            expires_at_second,
+            expires_at_height,
+            expires_in_blocks,
+            expires_in_seconds,
+            auto_import: false,
+        }
+    }
+
+    #[test]
+    fn test_offer_expiry() -> Result<()> {
+        assert_eq!(
+            offer_expiry(&make_offer(None, None, None, None), None, 1000)?,
+            OfferExpiry::default()
+        );
+
+        // Relative expirations are resolved against the peak and the current time
+        assert_eq!(
+            offer_expiry(&make_offer(None, Some(10), None, Some(60)), Some(100), 1000)?,
+            OfferExpiry {
+                height: Some(110),
+                seconds: Some(1060),
+            }
+        );
+
+        // The earliest expiration of each kind is used
+        assert_eq!(
+            offer_expiry(
+                &make_offer(Some(105), Some(10), Some(2000), Some(60)),
+                Some(100),
+                1000
+            )?,
+            OfferExpiry {
+                height: Some(105),
+                seconds: Some(1060),
+            }
+        );
+
+        assert!(matches!(
+            offer_expiry(&make_offer(None, Some(10), None, None), None, 1000),
+            Err(Error::UnknownPeak)
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_offer_lines() {
+        assert_eq!(offer_lines("offer1abc"), vec!["offer1abc"]);
+        assert_eq!(
+            offer_lines("offer1abc\r\n\n  offer1def  \n"),
+            vec!["offer1abc", "offer1def"]
+        );
+        assert!(offer_lines(" \n").is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_import_offers() -> anyhow::Result<()> {
+        let dir = std::env::temp_dir().join(format!("sage-import-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        let mut sage = test_sage(&dir).await?;
+
+        let (coins, offers): (Vec<Coin>, Vec<String>) = (1..=5)
+            .map(test_offer)
+            .collect::<anyhow::Result<Vec<_>>>()?
+            .into_iter()
+            .unzip();
+
+        // Only offers spending the wallet's own coins can be imported
+        let wallet = sage.wallet()?;
+        let mut tx = wallet.db.tx().await?;
+        for coin in coins {
+            tx.insert_coin(CoinState::new(coin, None, None)).await?;
+        }
+        tx.commit().await?;
+
+        // The offers in a file
+        let response = sage
+            .import_offers(ImportOffers {
+                offers: offer_lines(&format!("{}\r\n\n{}\n", offers[0], offers[1])),
+                verify: false,
+            })
+            .await?;
+
+        assert_eq!(
+            statuses(&response.results),
+            vec![ImportOfferStatus::Imported, ImportOfferStatus::Imported]
+        );
+        assert!(response.results.iter().all(|result| result.error.is_none()));
+
+        // The offers in a directory, where one of them is corrupted and one was already imported
+        let response = sage
+            .import_offers(ImportOffers {
+                offers: vec![
+                    offers[2].clone(),
+                    "offer1bad".to_string(),
+                    offers[0].clone(),
+                ],
+                verify: false,
+            })
+            .await?;
+
+        assert_eq!(
+            statuses(&response.results),
+            vec![
+                ImportOfferStatus::Imported,
+                ImportOfferStatus::Invalid,
+                ImportOfferStatus::Duplicate,
+            ]
+        );
+        assert_eq!(response.results[1].offer_id, None);
+        assert!(response.results[1].error.is_some());
+        assert_eq!(
+            response.results[2].offer_id,
+            Some(hex::encode(sort_offer(decode_offer(&offers[0])?).name()))
+        );
+
+        // A truncated offer is reported on its own
+        let truncated = &offers[3][..offers[3].len() - 10];
+        let url = offer_server(format!("{}\n{truncated}\n", offers[3])).await?;
+
+        let response = sage
+            .import_offers_from_url(ImportOffersFromUrl {
+                url: format!("{url}/offers.txt"),
+                verify: false,
+            })
+            .await?;
+
+        assert_eq!(
+            statuses(&response.results),
+            vec![ImportOfferStatus::Imported, ImportOfferStatus::Invalid]
+        );
+        assert!(response.results[1].error.is_some());
+
+        // Content-addressed URLs fall back to the next gateway if one is unreachable
+        let url = offer_server(format!("{}\n{}", offers[4], offers[1])).await?;
+
+        sage.config.gateways = GatewayConfig {
+            ipfs: vec!["http://127.0.0.1:1".to_string(), url.clone()],
+            ipns: Vec::new(),
+            arweave: Vec::new(),
+        };
+
+        let response = sage
+            .import_offers_from_url(ImportOffersFromUrl {
+                url: "ipfs://bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi/offers"
+                    .to_string(),
+                verify: false,
+            })
+            .await?;
+
+        assert_eq!(
+            statuses(&response.results),
+            vec![ImportOfferStatus::Imported, ImportOfferStatus::Duplicate]
+        );
+
+        assert!(sage
+            .import_offers_from_url(ImportOffersFromUrl {
+                url: format!("{url}/missing.txt"),
+                verify: false,
+            })
+            .await
+            .is_err());
+
+        assert_eq!(wallet.db.offers(None).await?.len(), 5);
+
+        std::fs::remove_dir_all(&dir)?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,293 @@
+use std::collections::HashMap;
+
+use chia::protocol::Bytes32;
+use chia_wallet_sdk::utils::Address;
+use sage_api::{
+    Amount, CreatePayout, CreatePayoutResponse, DeletePayout, DeletePayoutResponse, GetPayout,
+    GetPayoutResponse, GetPayouts, GetPayoutsResponse, PausePayout, PausePayoutResponse,
+    PayoutFormat, PayoutPaymentRecord, PayoutRecord,
+};
+use sage_database::{PayoutPaymentRow, PayoutRow};
+use sage_wallet::{WalletError, MAX_TRANSACTION_COST};
+use serde::Deserialize;
+
+use crate::{parse_amount, parse_asset_id, parse_memos, Error, Result, Sage};
+
+/// A single row of the payment data, before it has been validated.
+#[derive(Debug, Clone, Deserialize)]
+struct PayoutInput {
+    address: String,
+    #[serde(default)]
+    asset: Option<String>,
+    amount: Amount,
+    #[serde(default)]
+    memo: Option<String>,
+}
+
+impl Sage {
+    pub async fn get_payouts(&self, _req: GetPayouts) -> Result<GetPayoutsResponse> {
+        let wallet = self.wallet()?;
+
+        let payouts = wallet
+            .db
+            .payouts()
+            .await?
+            .into_iter()
+            .map(payout_record)
+            .collect();
+
+        Ok(GetPayoutsResponse { payouts })
+    }
+
+    pub async fn get_payout(&self, req: GetPayout) -> Result<GetPayoutResponse> {
+        let wallet = self.wallet()?;
+
+        let row = wallet
+            .db
+            .payout(req.id)
+            .await?
+            .ok_or(Error::MissingPayout(req.id))?;
+
+        let payments = wallet
+            .db
+            .payout_payments(req.id)
+            .await?
+            .into_iter()
+            .map(|row| {
+                Ok(PayoutPaymentRecord {
+                    row_index: row.payment.row_index,
+                    asset_id: row.payment.asset_id.map(hex::encode),
+                    address: Address::new(row.payment.puzzle_hash, self.network().prefix())
+                        .encode()?,
+                    amount: Amount::u64(row.payment.amount),
+                    memos: row.payment.memos.into_iter().map(hex::encode).collect(),
+                    transaction_id: row.transaction_id.map(|id| id.to_string()),
+                    is_confirmed: row.is_confirmed,
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(GetPayoutResponse {
+            payout: payout_record(row),
+            payments,
+        })
+    }
+
+    pub async fn create_payout(&self, req: CreatePayout) -> Result<CreatePayoutResponse> {
+        let wallet = self.wallet()?;
+
+        let name = req.name.trim().to_string();
+
+        if name.is_empty() {
+            return Err(Error::EmptyPayoutName);
+        }
+
+        let fee = parse_amount(req.fee)?;
+        let max_cost = req.max_cost.unwrap_or(MAX_TRANSACTION_COST);
+        let inputs = parse_payout_data(req.format, &req.data)?;
+
+        if inputs.is_empty() {
+            return Err(Error::EmptyPayout);
+        }
+
+        let mut payments = Vec::with_capacity(inputs.len());
+        let mut totals = HashMap::<Option<Bytes32>, u128>::new();
+
+        for (index, input) in inputs.into_iter().enumerate() {
+            let payment = self
+                .parse_payout_input(index, input)
+                .map_err(|error| Error::InvalidPayoutRow(index + 1, error.to_string()))?;
+
+            *totals.entry(payment.asset_id).or_default() += u128::from(payment.amount);
+
+            payments.push(payment);
+        }
+
+        // The whole payout is checked against the balance up front, rather than finding out
+        // partway through. The fee is only known to be paid at least once.
+        *totals.entry(None).or_default() += u128::from(fee);
+
+        for (asset_id, total) in totals {
+            let balance = match asset_id {
+                Some(asset_id) => wallet.db.selectable_cat_balance(asset_id).await?,
+                None => wallet.db.selectable_xch_balance().await?,
+            };
+
+            if total > balance {
+                return Err(WalletError::InsufficientFunds.into());
+            }
+        }
+
+        let mut tx = wallet.db.tx().await?;
+
+        let id = tx.insert_payout(name, fee, max_cost).await?;
+
+        for payment in payments {
+            tx.insert_payout_payment(id, payment).await?;
+        }
+
+        tx.commit().await?;
+
+        let row = wallet
+            .db
+            .payout(id)
+            .await?
+            .ok_or(Error::MissingPayout(id))?;
+
+        Ok(CreatePayoutResponse {
+            payout: payout_record(row),
+        })
+    }
+
+    pub async fn pause_payout(&self, req: PausePayout) -> Result<PausePayoutResponse> {
+        let wallet = self.wallet()?;
+
+        let row = wallet
+            .db
+            .payout(req.id)
+            .await?
+            .ok_or(Error::MissingPayout(req.id))?;
+
+        // The error is kept while paused so that it can be shown, and cleared on resume.
+        let error = if req.paused { row.error } else { None };
+
+        wallet
+            .db
+            .update_payout_status(req.id, req.paused, error)
+            .await?;
+
+        let row = wallet
+            .db
+            .payout(req.id)
+            .await?
+            .ok_or(Error::MissingPayout(req.id))?;
+
+        Ok(PausePayoutResponse {
+            payout: payout_record(row),
+        })
+    }
+
+    pub async fn delete_payout(&self, req: DeletePayout) -> Result<DeletePayoutResponse> {
+        let wallet = self.wallet()?;
+        wallet.db.delete_payout(req.id).await?;
+        Ok(DeletePayoutResponse {})
+    }
+
+    fn parse_payout_input(&self, index: usize, input: PayoutInput) -> Result<PayoutPaymentRow> {
+        let asset_id = match input.asset.as_deref().map(str::trim) {
+            None | Some("") => None,
+            Some(asset) if asset.eq_ignore_ascii_case("xch") => None,
+            Some(asset) => Some(parse_asset_id(asset.to_string())?),
+        };
+
+        let amount = parse_amount(input.amount)?;
+
+        if amount == 0 {
+            return Err(Error::InvalidAmount(amount.to_string()));
+        }
+
+        Ok(PayoutPaymentRow {
+            row_index: index.try_into()?,
+            asset_id,
+            puzzle_hash: self.parse_address(input.address.trim().to_string())?,
+            amount,
+            memos: parse_memos(input.memo.into_iter().collect())?,
+        })
+    }
+}
+
+fn payout_record(row: PayoutRow) -> PayoutRecord {
+    PayoutRecord {
+        id: row.id,
+        name: row.name,
+        fee: Amount::u64(row.fee),
+        max_cost: row.max_cost,
+        is_paused: row.is_paused,
+        error: row.error,
+        created_timestamp: row.created_timestamp,
+        payment_count: row.payment_count,
+        submitted_count: row.submitted_count,
+        confirmed_count: row.confirmed_count,
+    }
+}
+
+fn parse_payout_data(format: PayoutFormat, data: &str) -> Result<Vec<PayoutInput>> {
+    match format {
+        PayoutFormat::Csv => {
+            let mut reader = csv::ReaderBuilder::new()
+                .trim(csv::Trim::All)
+                .from_reader(data.as_bytes());
+
+            reader
+                .deserialize()
+                .enumerate()
+                .map(|(index, row)| {
+                    row.map_err(|error| Error::InvalidPayoutRow(index + 1, error.to_string()))
+                })
+                .collect()
+        }
+        PayoutFormat::Json => {
+            serde_json::from_str(data).map_err(|error| Error::InvalidPayoutFile(error.to_string()))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+
+    #[test]
+    fn test_parse_payout_csv() {
+        let data = "address,asset,amount,memo\n\
+            xch1a,,100,\n\
+            xch1b, xch ,200,cafe\n\
+            xch1c,a628c1c2c6fcb74d53746157e438e108eab5c0bb3e5c80ff9b1910b3e4832913,300,\n";
+
+        let rows = parse_payout_data(PayoutFormat::Csv, data).unwrap();
+
+        assert_eq!(rows.len(), 3);
+        assert_eq!(rows[0].address, "xch1a");
+        assert_eq!(rows[0].asset, None);
+        assert_eq!(rows[0].amount.to_u64(), Some(100));
+        assert_eq!(rows[0].memo, None);
+        assert_eq!(rows[1].asset.as_deref(), Some("xch"));
+        assert_eq!(rows[1].memo.as_deref(), Some("cafe"));
+        assert_eq!(rows[2].amount.to_u64(), Some(300));
+    }
+
+    #[test]
+    fn test_parse_payout_csv_without_optional_columns() {
+        let rows = parse_payout_data(PayoutFormat::Csv, "address,amount\nxch1a,100\n").unwrap();
+
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].asset, None);
+        assert_eq!(rows[0].memo, None);
+    }
+
+    #[test]
+    fn test_parse_payout_csv_invalid_row() {
+        let data = "address,asset,amount,memo\nxch1a,,100,\nxch1b,,\n";
+
+        assert!(matches!(
+            parse_payout_data(PayoutFormat::Csv, data),
+            Err(Error::InvalidPayoutRow(2, _))
+        ));
+    }
+
+    #[test]
+    fn test_parse_payout_json() {
+        let data = r#"[
+            { "address": "xch1a", "amount": 100 },
+            { "address": "xch1b", "asset": "xch", "amount": "200", "memo": "cafe" }
+        ]"#;
+
+        let rows = parse_payout_data(PayoutFormat::Json, data).unwrap();
+
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0].amount.to_u64(), Some(100));
+        assert_eq!(rows[1].amount.to_u64(), Some(200));
+        assert_eq!(rows[1].memo.as_deref(), Some("cafe"));
+    }
+}
@@ -0,0 +1,192 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use chia_wallet_sdk::utils::Address;
+use sage_api::{
+    Amount, CreateScheduledPayment, CreateScheduledPaymentResponse, DeleteScheduledPayment,
+    DeleteScheduledPaymentResponse, GetScheduledPayments, GetScheduledPaymentsResponse,
+    PauseScheduledPayment, PauseScheduledPaymentResponse, Payment, ScheduledPaymentRecord,
+    ScheduledPaymentRunRecord,
+};
+use sage_database::{Database, ScheduledPaymentRecipientRow, ScheduledPaymentRow};
+use sage_wallet::Schedule;
+
+use crate::{parse_amount, parse_asset_id, parse_memos, Error, Result, Sage};
+
+/// The number of most recent runs included with each scheduled payment.
+const RECENT_RUNS: u32 = 10;
+
+impl Sage {
+    pub async fn get_scheduled_payments(
+        &self,
+        _req: GetScheduledPayments,
+    ) -> Result<GetScheduledPaymentsResponse> {
+        let wallet = self.wallet()?;
+
+        let mut scheduled_payments = Vec::new();
+
+        for row in wallet.db.scheduled_payments().await? {
+            scheduled_payments.push(self.scheduled_payment_record(&wallet.db, row).await?);
+        }
+
+        Ok(GetScheduledPaymentsResponse { scheduled_payments })
+    }
+
+    pub async fn create_scheduled_payment(
+        &self,
+        req: CreateScheduledPayment,
+    ) -> Result<CreateScheduledPaymentResponse> {
+        let wallet = self.wallet()?;
+
+        let name = req.name.trim().to_string();
+
+        if name.is_empty() {
+            return Err(Error::EmptyScheduledPaymentName);
+        }
+
+        if req.payments.is_empty() {
+            return Err(Error::EmptyScheduledPayment);
+        }
+
+        let schedule = parse_schedule(&req.schedule)?;
+        let next_run_timestamp = next_run_timestamp(&schedule)?;
+        let fee = parse_amount(req.fee)?;
+
+        let mut recipients = Vec::with_capacity(req.payments.len());
+
+        for payment in req.payments {
+            recipients.push(ScheduledPaymentRecipientRow {
+                asset_id: payment.asset_id.map(parse_asset_id).transpose()?,
+                puzzle_hash: self.parse_address(payment.address)?,
+                amount: parse_amount(payment.amount)?,
+                memos: parse_memos(payment.memos)?,
+            });
+        }
+
+        let mut tx = wallet.db.tx().await?;
+
+        let id = tx
+            .insert_scheduled_payment(name, schedule.to_string(), fee, next_run_timestamp)
+            .await?;
+
+        for recipient in recipients {
+            tx.insert_scheduled_payment_recipient(id, recipient).await?;
+        }
+
+        tx.commit().await?;
+
+        let row = wallet
+            .db
+            .scheduled_payment(id)
+            .await?
+            .ok_or(Error::MissingScheduledPayment(id))?;
+
+        Ok(CreateScheduledPaymentResponse {
+            scheduled_payment: self.scheduled_payment_record(&wallet.db, row).await?,
+        })
+    }
+
+    pub async fn pause_scheduled_payment(
+        &self,
+        req: PauseScheduledPayment,
+    ) -> Result<PauseScheduledPaymentResponse> {
+        let wallet = self.wallet()?;
+
+        let row = wallet
+            .db
+            .scheduled_payment(req.id)
+            .await?
+            .ok_or(Error::MissingScheduledPayment(req.id))?;
+
+        // Runs that were missed while paused are skipped rather than made all at once.
+        let next_run_timestamp = if req.paused {
+            row.next_run_timestamp
+        } else {
+            next_run_timestamp(&parse_schedule(&row.schedule)?)?
+        };
+
+        wallet
+            .db
+            .update_scheduled_payment_status(req.id, req.paused, next_run_timestamp)
+            .await?;
+
+        let row = wallet
+            .db
+            .scheduled_payment(req.id)
+            .await?
+            .ok_or(Error::MissingScheduledPayment(req.id))?;
+
+        Ok(PauseScheduledPaymentResponse {
+            scheduled_payment: self.scheduled_payment_record(&wallet.db, row).await?,
+        })
+    }
+
+    pub async fn delete_scheduled_payment(
+        &self,
+        req: DeleteScheduledPayment,
+    ) -> Result<DeleteScheduledPaymentResponse> {
+        let wallet = self.wallet()?;
+        wallet.db.delete_scheduled_payment(req.id).await?;
+        Ok(DeleteScheduledPaymentResponse {})
+    }
+
+    async fn scheduled_payment_record(
+        &self,
+        db: &Database,
+        row: ScheduledPaymentRow,
+    ) -> Result<ScheduledPaymentRecord> {
+        let payments = db
+            .scheduled_payment_recipients(row.id)
+            .await?
+            .into_iter()
+            .map(|recipient| {
+                Ok(Payment {
+                    asset_id: recipient.asset_id.map(hex::encode),
+                    address: Address::new(recipient.puzzle_hash, self.network().prefix())
+                        .encode()?,
+                    amount: Amount::u64(recipient.amount),
+                    memos: recipient.memos.into_iter().map(hex::encode).collect(),
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let runs = db
+            .scheduled_payment_runs(row.id, RECENT_RUNS)
+            .await?
+            .into_iter()
+            .map(|run| ScheduledPaymentRunRecord {
+                transaction_id: run.transaction_id.map(|id| id.to_string()),
+                error: run.error,
+                run_timestamp: run.run_timestamp,
+            })
+            .collect();
+
+        Ok(ScheduledPaymentRecord {
+            id: row.id,
+            name: row.name,
+            schedule: row.schedule,
+            payments,
+            fee: Amount::u64(row.fee),
+            is_paused: row.is_paused,
+            next_run_timestamp: row.next_run_timestamp,
+            created_timestamp: row.created_timestamp,
+            runs,
+        })
+    }
+}
+
+fn parse_schedule(input: &str) -> Result<Schedule> {
+    input
+        .parse()
+        .map_err(|_| Error::InvalidSchedule(input.to_string()))
+}
+
+fn next_run_timestamp(schedule: &Schedule) -> Result<u64> {
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("system time is before the UNIX epoch")
+        .as_secs();
+
+    schedule
+        .next_after(timestamp)
+        .ok_or_else(|| Error::InvalidSchedule(schedule.to_string()))
+}
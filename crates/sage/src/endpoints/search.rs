@@ -0,0 +1,129 @@
+use chia::protocol::Bytes32;
+use chia_wallet_sdk::utils::Address;
+use sage_api::{Search, SearchKind as ApiSearchKind, SearchResponse, SearchResult};
+use sage_database::SearchKind;
+
+use crate::{Result, Sage};
+
+impl Sage {
+    pub async fn search(&self, req: Search) -> Result<SearchResponse> {
+        let wallet = self.wallet()?;
+
+        let kinds: Vec<SearchKind> = req
+            .kinds
+            .into_iter()
+            .map(|kind| match kind {
+                ApiSearchKind::Token => SearchKind::Token,
+                ApiSearchKind::Nft => SearchKind::Nft,
+                ApiSearchKind::Did => SearchKind::Did,
+                ApiSearchKind::Option => SearchKind::Option,
+                ApiSearchKind::Collection => SearchKind::Collection,
+                ApiSearchKind::Offer => SearchKind::Offer,
+                ApiSearchKind::Transaction => SearchKind::Transaction,
+                ApiSearchKind::Contact => SearchKind::Contact,
+            })
+            .collect();
+
+        let (hits, total) = wallet
+            .db
+            .search(&search_terms(&req.query), &kinds, req.limit, req.offset)
+            .await?;
+
+        let mut results = Vec::new();
+
+        for hit in hits {
+            let hash = hit.hash.unwrap_or_default();
+
+            let (kind, id) = match hit.kind {
+                SearchKind::Token if hash == Bytes32::default() => {
+                    (ApiSearchKind::Token, "xch".to_string())
+                }
+                SearchKind::Token => (ApiSearchKind::Token, hex::encode(hash)),
+                SearchKind::Nft => (
+                    ApiSearchKind::Nft,
+                    Address::new(hash, "nft".to_string()).encode()?,
+                ),
+                SearchKind::Did => (
+                    ApiSearchKind::Did,
+                    Address::new(hash, "did:chia:".to_string()).encode()?,
+                ),
+                SearchKind::Option => (
+                    ApiSearchKind::Option,
+                    Address::new(hash, "option".to_string()).encode()?,
+                ),
+                SearchKind::Collection => (
+                    ApiSearchKind::Collection,
+                    Address::new(hash, "col".to_string()).encode()?,
+                ),
+                SearchKind::Offer => (ApiSearchKind::Offer, hex::encode(hash)),
+                SearchKind::Transaction => (ApiSearchKind::Transaction, hex::encode(hash)),
+                SearchKind::Contact => (ApiSearchKind::Contact, hit.row_id.to_string()),
+            };
+
+            results.push(SearchResult {
+                kind,
+                id,
+                name: hit.name,
+                ticker: hit.ticker,
+                score: -hit.rank,
+            });
+        }
+
+        Ok(SearchResponse { results, total })
+    }
+}
+
+/// Splits a query into the words which are indexed. Addresses and bech32m ids are replaced with
+/// the hex of the puzzle hash or id they encode, since that's how they are indexed.
+fn search_terms(query: &str) -> Vec<String> {
+    let mut terms = Vec::new();
+
+    for word in query.split_whitespace() {
+        if let Ok(address) = Address::decode(word) {
+            terms.push(hex::encode(address.puzzle_hash));
+            continue;
+        }
+
+        if let Some(hex) = word
+            .strip_prefix("0x")
+            .filter(|hex| !hex.is_empty() && hex.chars().all(|c| c.is_ascii_hexdigit()))
+        {
+            terms.push(hex.to_lowercase());
+            continue;
+        }
+
+        terms.extend(
+            word.split(|c: char| !c.is_alphanumeric())
+                .filter(|term| !term.is_empty())
+                .map(str::to_lowercase),
+        );
+    }
+
+    terms
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_search_terms() -> Result<()> {
+        let puzzle_hash = Bytes32::new([7; 32]);
+        let address = Address::new(puzzle_hash, "xch".to_string()).encode()?;
+
+        assert_eq!(
+            search_terms(&format!("  Cool-Monkey #5 0xABcd {address}")),
+            [
+                "cool".to_string(),
+                "monkey".to_string(),
+                "5".to_string(),
+                "abcd".to_string(),
+                hex::encode(puzzle_hash),
+            ]
+        );
+
+        assert!(search_terms(" - ").is_empty());
+
+        Ok(())
+    }
+}
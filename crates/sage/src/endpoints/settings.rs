@@ -4,10 +4,10 @@ use itertools::Itertools;
 use sage_api::{
     AddPeer, AddPeerResponse, GetNetwork, GetNetworkResponse, GetNetworks, GetNetworksResponse,
     GetPeers, GetPeersResponse, NetworkKind, PeerRecord, RemovePeer, RemovePeerResponse,
-    SetChangeAddress, SetChangeAddressResponse, SetDeltaSync, SetDeltaSyncOverride,
-    SetDeltaSyncOverrideResponse, SetDeltaSyncResponse, SetDiscoverPeers, SetDiscoverPeersResponse,
-    SetNetwork, SetNetworkOverride, SetNetworkOverrideResponse, SetNetworkResponse, SetTargetPeers,
-    SetTargetPeersResponse,
+    SetBackgroundSigning, SetBackgroundSigningResponse, SetChangeAddress, SetChangeAddressResponse,
+    SetDeltaSync, SetDeltaSyncOverride, SetDeltaSyncOverrideResponse, SetDeltaSyncResponse,
+    SetDiscoverPeers, SetDiscoverPeersResponse, SetNetwork, SetNetworkOverride,
+    SetNetworkOverrideResponse, SetNetworkResponse, SetTargetPeers, SetTargetPeersResponse,
 };
 use sage_config::{MAINNET, TESTNET11};
 use sage_wallet::SyncCommand;
@@ -176,4 +176,30 @@ impl Sage {
         self.switch_wallet().await?;
         Ok(SetChangeAddressResponse {})
     }
+
+    pub async fn set_background_signing(
+        &mut self,
+        req: SetBackgroundSigning,
+    ) -> Result<SetBackgroundSigningResponse> {
+        let Some(wallet_config) = self
+            .wallet_config
+            .wallets
+            .iter_mut()
+            .find(|w| w.fingerprint == req.fingerprint)
+        else {
+            return Err(Error::UnknownFingerprint);
+        };
+        wallet_config.background_signing = req.enabled;
+        self.save_config()?;
+
+        if self.config.global.fingerprint == Some(req.fingerprint) {
+            self.command_sender
+                .send(SyncCommand::SetSigningKey(
+                    self.background_signing_key(req.fingerprint, req.enabled),
+                ))
+                .await?;
+        }
+
+        Ok(SetBackgroundSigningResponse {})
+    }
 }
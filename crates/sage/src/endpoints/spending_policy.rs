@@ -0,0 +1,114 @@
+use chia::protocol::Bytes32;
+use chia_wallet_sdk::utils::Address;
+use sage_api::{
+    Amount, GetSpendingPolicy, GetSpendingPolicyResponse, SetSpendingPolicy,
+    SetSpendingPolicyResponse, SpendingLimit, SpendingPolicy,
+};
+use sage_database::{Database, SpendingLimitRow, SpendingPolicyRow};
+
+use crate::{parse_amount, parse_asset_id, Error, Result, Sage};
+
+impl Sage {
+    pub async fn get_spending_policy(
+        &self,
+        _req: GetSpendingPolicy,
+    ) -> Result<GetSpendingPolicyResponse> {
+        let wallet = self.wallet()?;
+
+        Ok(GetSpendingPolicyResponse {
+            policy: self.spending_policy(&wallet.db).await?,
+        })
+    }
+
+    pub async fn set_spending_policy(
+        &self,
+        req: SetSpendingPolicy,
+    ) -> Result<SetSpendingPolicyResponse> {
+        let wallet = self.wallet()?;
+
+        // Changing the policy requires the same password as signing, otherwise it could
+        // simply be turned off by whoever it's meant to restrict.
+        let (_mnemonic, Some(_master_sk)) = self
+            .keychain
+            .extract_secrets(wallet.fingerprint, req.password.as_bytes())?
+        else {
+            return Err(Error::NoSigningKey);
+        };
+
+        let policy = SpendingPolicyRow {
+            max_fee: req.policy.max_fee.map(parse_amount).transpose()?,
+            is_nft_transfer_blocked: req.policy.block_nft_transfers,
+            is_did_transfer_blocked: req.policy.block_did_transfers,
+        };
+
+        let mut limits = Vec::with_capacity(req.policy.limits.len());
+
+        for limit in req.policy.limits {
+            limits.push(SpendingLimitRow {
+                asset_hash: limit
+                    .asset_id
+                    .map(parse_asset_id)
+                    .transpose()?
+                    .unwrap_or_default(),
+                max_per_transaction: limit.max_per_transaction.map(parse_amount).transpose()?,
+                max_per_day: limit.max_per_day.map(parse_amount).transpose()?,
+            });
+        }
+
+        let mut allowed_destinations = Vec::with_capacity(req.policy.allowed_addresses.len());
+
+        for address in req.policy.allowed_addresses {
+            allowed_destinations.push(self.parse_address(address)?);
+        }
+
+        let mut tx = wallet.db.tx().await?;
+
+        tx.clear_spending_policy().await?;
+        tx.set_spending_policy(policy).await?;
+
+        for limit in limits {
+            tx.insert_spending_limit(limit).await?;
+        }
+
+        for puzzle_hash in allowed_destinations {
+            tx.insert_allowed_destination(puzzle_hash).await?;
+        }
+
+        tx.commit().await?;
+
+        Ok(SetSpendingPolicyResponse {
+            policy: self.spending_policy(&wallet.db).await?,
+        })
+    }
+
+    async fn spending_policy(&self, db: &Database) -> Result<SpendingPolicy> {
+        let policy = db.spending_policy().await?;
+
+        let limits = db
+            .spending_limits()
+            .await?
+            .into_iter()
+            .map(|limit| SpendingLimit {
+                asset_id: (limit.asset_hash != Bytes32::default())
+                    .then(|| hex::encode(limit.asset_hash)),
+                max_per_transaction: limit.max_per_transaction.map(Amount::u64),
+                max_per_day: limit.max_per_day.map(Amount::u64),
+            })
+            .collect();
+
+        let allowed_addresses = db
+            .allowed_destinations()
+            .await?
+            .into_iter()
+            .map(|puzzle_hash| Ok(Address::new(puzzle_hash, self.network().prefix()).encode()?))
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(SpendingPolicy {
+            limits,
+            allowed_addresses,
+            max_fee: policy.and_then(|policy| policy.max_fee).map(Amount::u64),
+            block_nft_transfers: policy.is_some_and(|policy| policy.is_nft_transfer_blocked),
+            block_did_transfers: policy.is_some_and(|policy| policy.is_did_transfer_blocked),
+        })
+    }
+}
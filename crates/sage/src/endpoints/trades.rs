@@ -0,0 +1,383 @@
+use chia::protocol::Bytes32;
+use indexmap::IndexMap;
+use sage_api::{
+    Amount, GetTrades, GetTradesResponse, TradeAmount, TradeAssetPnl, TradePrice, TradeRecord,
+};
+use sage_database::{Asset, TradeAssetRow, TradeRow};
+use sage_wallet::Price;
+
+use crate::{encode_asset_id, parse_asset_id, parse_nft_id, parse_option_id, Error, Result, Sage};
+
+/// The precision of XCH, which realized profit and loss is measured in.
+const XCH_PRECISION: u8 = 12;
+
+impl Sage {
+    pub async fn get_trades(&self, req: GetTrades) -> Result<GetTradesResponse> {
+        let wallet = self.wallet()?;
+
+        let asset_id = req
+            .asset_id
+            .map(|asset_id| {
+                parse_asset_id(asset_id.clone())
+                    .or_else(|_| parse_nft_id(asset_id.clone()))
+                    .or_else(|_| parse_option_id(asset_id.clone()))
+                    .map_err(|_| Error::InvalidAssetId(asset_id))
+            })
+            .transpose()?;
+
+        let mut assets = IndexMap::<u32, Vec<TradeAssetRow>>::new();
+
+        for row in wallet.db.trade_assets().await? {
+            assets.entry(row.trade_id).or_default().push(row);
+        }
+
+        let trades = wallet
+            .db
+            .trades()
+            .await?
+            .into_iter()
+            .map(|trade| {
+                let assets = assets.swap_remove(&trade.id).unwrap_or_default();
+                (trade, assets)
+            })
+            .filter(|(trade, assets)| {
+                (req.include_unfilled || trade.fill_height.is_some())
+                    && asset_id
+                        .is_none_or(|asset_id| assets.iter().any(|row| row.asset.hash == asset_id))
+            })
+            .collect::<Vec<_>>();
+
+        let filled = trades
+            .iter()
+            .filter(|(trade, _)| trade.fill_height.is_some())
+            .collect::<Vec<_>>();
+
+        let fees_paid = filled
+            .iter()
+            .map(|(trade, _)| u128::from(trade.fee))
+            .sum::<u128>();
+
+        let mut asset_pnls = Vec::new();
+
+        for pnl in asset_pnl(&filled) {
+            if asset_id.is_some_and(|asset_id| pnl.asset.hash != asset_id) {
+                continue;
+            }
+
+            asset_pnls.push(TradeAssetPnl {
+                received: Amount::u128(pnl.received),
+                given: Amount::u128(pnl.given),
+                royalties_paid: Amount::u128(pnl.royalties_paid),
+                net: pnl.net().to_string(),
+                bought: Amount::u128(pnl.bought),
+                sold: Amount::u128(pnl.sold),
+                average_buy_price: pnl.average_buy_price().map(|price| price.to_string()),
+                average_sell_price: pnl.average_sell_price().map(|price| price.to_string()),
+                realized_pnl: pnl.realized_pnl().map(|pnl| pnl.to_string()),
+                asset: self.encode_asset(pnl.asset)?,
+            });
+        }
+
+        let mut records = Vec::new();
+
+        for (trade, assets) in trades {
+            records.push(self.trade_record(trade, assets)?);
+        }
+
+        Ok(GetTradesResponse {
+            trades: records,
+            assets: asset_pnls,
+            fees_paid: Amount::u128(fees_paid),
+        })
+    }
+
+    fn trade_record(&self, trade: TradeRow, assets: Vec<TradeAssetRow>) -> Result<TradeRecord> {
+        let mut prices = Vec::new();
+
+        for given in assets.iter().filter(|row| !row.is_received) {
+            for received in assets.iter().filter(|row| row.is_received) {
+                let Some((base, quote, price)) = trade_price(given, received) else {
+                    continue;
+                };
+
+                prices.push(TradePrice {
+                    base_asset_id: encode_asset_id(base.hash, base.kind)?,
+                    quote_asset_id: encode_asset_id(quote.hash, quote.kind)?,
+                    price: price.to_string(),
+                });
+            }
+        }
+
+        let mut given = Vec::new();
+        let mut received = Vec::new();
+
+        for row in assets {
+            let amount = TradeAmount {
+                asset: self.encode_asset(row.asset)?,
+                amount: Amount::u64(row.amount),
+                royalty: Amount::u64(row.royalty),
+            };
+
+            if row.is_received {
+                received.push(amount);
+            } else {
+                given.push(amount);
+            }
+        }
+
+        Ok(TradeRecord {
+            offer_id: hex::encode(trade.offer_id),
+            is_maker: trade.is_maker,
+            transaction_id: trade.transaction_id.map(hex::encode),
+            fee: Amount::u64(trade.fee),
+            fill_height: trade.fill_height,
+            fill_timestamp: trade.fill_timestamp,
+            inserted_timestamp: trade.inserted_timestamp,
+            given,
+            received,
+            prices,
+        })
+    }
+}
+
+/// The price of a pair of assets in a trade. XCH is the quote asset when it's part of the pair,
+/// and otherwise the given asset is priced in the received asset.
+fn trade_price<'a>(
+    given: &'a TradeAssetRow,
+    received: &'a TradeAssetRow,
+) -> Option<(&'a Asset, &'a Asset, Price)> {
+    let (base, quote) = if given.asset.hash == Bytes32::default() {
+        (received, given)
+    } else {
+        (given, received)
+    };
+
+    let price = Price::from_amounts(
+        base.amount,
+        base.asset.precision,
+        quote.amount,
+        quote.asset.precision,
+    )?;
+
+    Some((&base.asset, &quote.asset, price))
+}
+
+/// The totals of an asset over a set of trades.
+#[derive(Debug, Clone)]
+struct AssetPnl {
+    asset: Asset,
+    received: u128,
+    given: u128,
+    royalties_paid: u128,
+    /// The amount received in trades for only XCH, and the XCH paid for it including royalties.
+    bought: u128,
+    buy_cost: u128,
+    /// The amount given in trades for only XCH, and the XCH received for it.
+    sold: u128,
+    sell_proceeds: u128,
+}
+
+impl AssetPnl {
+    fn new(asset: Asset) -> Self {
+        Self {
+            asset,
+            received: 0,
+            given: 0,
+            royalties_paid: 0,
+            bought: 0,
+            buy_cost: 0,
+            sold: 0,
+            sell_proceeds: 0,
+        }
+    }
+
+    fn net(&self) -> i128 {
+        self.received.cast_signed() - self.given.cast_signed() - self.royalties_paid.cast_signed()
+    }
+
+    fn average_buy_price(&self) -> Option<Price> {
+        Price::from_amounts(
+            self.bought.try_into().ok()?,
+            self.asset.precision,
+            self.buy_cost.try_into().ok()?,
+            XCH_PRECISION,
+        )
+    }
+
+    fn average_sell_price(&self) -> Option<Price> {
+        Price::from_amounts(
+            self.sold.try_into().ok()?,
+            self.asset.precision,
+            self.sell_proceeds.try_into().ok()?,
+            XCH_PRECISION,
+        )
+    }
+
+    /// The XCH made or lost on the amount which has been both bought and sold, at the average
+    /// prices it was bought and sold for.
+    fn realized_pnl(&self) -> Option<i128> {
+        if self.bought == 0 || self.sold == 0 {
+            return None;
+        }
+
+        let matched = self.bought.min(self.sold);
+        let proceeds = self.sell_proceeds.checked_mul(matched)? / self.sold;
+        let cost = self.buy_cost.checked_mul(matched)? / self.bought;
+
+        Some(proceeds.cast_signed() - cost.cast_signed())
+    }
+}
+
+/// Totals each asset over the trades, in the order they were first traded.
+fn asset_pnl(trades: &[&(TradeRow, Vec<TradeAssetRow>)]) -> Vec<AssetPnl> {
+    let mut assets = IndexMap::<Bytes32, AssetPnl>::new();
+
+    for (_, rows) in trades.iter().rev() {
+        for row in rows {
+            let pnl = assets
+                .entry(row.asset.hash)
+                .or_insert_with(|| AssetPnl::new(row.asset.clone()));
+
+            if row.is_received {
+                pnl.received += u128::from(row.amount);
+            } else {
+                pnl.given += u128::from(row.amount);
+                pnl.royalties_paid += u128::from(row.royalty);
+            }
+        }
+
+        // Only trades of a single asset for XCH are counted as buying or selling it
+        let [first, second] = rows.as_slice() else {
+            continue;
+        };
+
+        let (xch, other) = if first.asset.hash == Bytes32::default() {
+            (first, second)
+        } else if second.asset.hash == Bytes32::default() {
+            (second, first)
+        } else {
+            continue;
+        };
+
+        if xch.is_received == other.is_received {
+            continue;
+        }
+
+        let pnl = &mut assets[&other.asset.hash];
+
+        if other.is_received {
+            pnl.bought += u128::from(other.amount);
+            pnl.buy_cost += u128::from(xch.amount) + u128::from(xch.royalty);
+        } else {
+            pnl.sold += u128::from(other.amount);
+            pnl.sell_proceeds += u128::from(xch.amount);
+        }
+    }
+
+    assets.into_values().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use sage_database::AssetKind;
+
+    use super::*;
+
+    fn asset(hash: Bytes32, precision: u8) -> Asset {
+        Asset {
+            hash,
+            name: None,
+            ticker: None,
+            precision,
+            icon_url: None,
+            description: None,
+            is_sensitive_content: false,
+            is_visible: true,
+            hidden_puzzle_hash: None,
+            kind: AssetKind::Token,
+        }
+    }
+
+    /// A filled trade of a CAT for XCH, with amounts in whole units.
+    fn trade(
+        id: u32,
+        cat_amount: u64,
+        xch_amount: u64,
+        is_buy: bool,
+    ) -> (TradeRow, Vec<TradeAssetRow>) {
+        let row = |asset, is_received, amount| TradeAssetRow {
+            trade_id: id,
+            asset,
+            is_received,
+            amount,
+            royalty: 0,
+        };
+
+        (
+            TradeRow {
+                id,
+                offer_id: Bytes32::new([id.try_into().expect("id is too large"); 32]),
+                is_maker: true,
+                transaction_id: None,
+                fee: 0,
+                fill_height: Some(id),
+                fill_timestamp: None,
+                inserted_timestamp: 0,
+            },
+            vec![
+                row(
+                    asset(Bytes32::default(), 12),
+                    !is_buy,
+                    xch_amount * 1_000_000_000_000,
+                ),
+                row(asset(Bytes32::new([1; 32]), 3), is_buy, cat_amount * 1000),
+            ],
+        )
+    }
+
+    #[test]
+    fn test_asset_pnl() {
+        // Trades are ordered from the most recent, so this buys 100 CAT for 1 XCH, another
+        // 100 CAT for 3 XCH, then sells 100 CAT for 4 XCH.
+        let trades = [
+            trade(3, 100, 4, false),
+            trade(2, 100, 3, true),
+            trade(1, 100, 1, true),
+        ];
+        let trades = trades.iter().collect::<Vec<_>>();
+
+        let pnl = asset_pnl(&trades);
+        assert_eq!(pnl.len(), 2);
+
+        let xch = &pnl[0];
+        assert_eq!(xch.asset.hash, Bytes32::default());
+        assert_eq!(xch.net(), 0);
+        assert_eq!(xch.bought, 0);
+
+        let cat = &pnl[1];
+        assert_eq!(cat.net(), 100_000);
+        assert_eq!(cat.bought, 200_000);
+        assert_eq!(cat.sold, 100_000);
+        assert_eq!(
+            cat.average_buy_price().map(|p| p.to_string()),
+            Some("0.02".to_string())
+        );
+        assert_eq!(
+            cat.average_sell_price().map(|p| p.to_string()),
+            Some("0.04".to_string())
+        );
+
+        // 100 CAT sold for 4 XCH, which cost 2 XCH at the average price.
+        assert_eq!(cat.realized_pnl(), Some(2_000_000_000_000));
+    }
+
+    #[test]
+    fn test_trade_price() {
+        let (_, assets) = trade(1, 100, 2, false);
+
+        let (base, quote, price) = trade_price(&assets[1], &assets[0]).expect("no price");
+        assert_eq!(base.hash, Bytes32::new([1; 32]));
+        assert_eq!(quote.hash, Bytes32::default());
+        assert_eq!(price.to_string(), "0.02");
+    }
+}
@@ -0,0 +1,300 @@
+use std::collections::HashMap;
+
+use chia::protocol::{Bytes, Bytes32};
+use chia_wallet_sdk::utils::Address;
+use indexmap::IndexMap;
+use sage_api::{
+    Amount, ExportFormat, ExportTransactions, ExportTransactionsResponse, TransactionExportRecord,
+};
+use sage_database::{Asset, AssetKind, ConfirmedPaymentRow, SubmittedTransactionRow};
+
+use crate::{encode_asset_id, encode_asset_kind, Error, Result, Sage};
+
+impl Sage {
+    pub async fn export_transactions(
+        &self,
+        req: ExportTransactions,
+    ) -> Result<ExportTransactionsResponse> {
+        let wallet = self.wallet()?;
+
+        if req
+            .start_height
+            .zip(req.end_height)
+            .is_some_and(|(start, end)| start > end)
+            || req
+                .start_timestamp
+                .zip(req.end_timestamp)
+                .is_some_and(|(start, end)| start > end)
+        {
+            return Err(Error::InvalidHistoryRange);
+        }
+
+        let transactions = wallet
+            .db
+            .transactions_in_range(
+                req.start_height,
+                req.end_height,
+                req.start_timestamp,
+                req.end_timestamp,
+            )
+            .await?;
+
+        let mut fees = HashMap::<u32, Vec<SubmittedTransactionRow>>::new();
+        let mut payments = HashMap::<u32, Vec<ConfirmedPaymentRow>>::new();
+        let mut trade_assets = HashMap::<(u32, Bytes32), Bytes32>::new();
+        let mut trade_transactions = HashMap::<Bytes32, Bytes32>::new();
+
+        if let (Some(first), Some(last)) = (transactions.first(), transactions.last()) {
+            for row in wallet
+                .db
+                .confirmed_submitted_transactions(first.height, last.height)
+                .await?
+            {
+                fees.entry(row.height).or_default().push(row);
+            }
+
+            for row in wallet
+                .db
+                .confirmed_submitted_payments(first.height, last.height)
+                .await?
+            {
+                payments.entry(row.height).or_default().push(row);
+            }
+
+            // Movements are linked to an offer if they're part of the transaction which took it,
+            // or if the offer traded that asset in the block it was filled in.
+            for row in wallet
+                .db
+                .filled_trade_assets(first.height, last.height)
+                .await?
+            {
+                trade_assets
+                    .entry((row.fill_height, row.asset_hash))
+                    .or_insert(row.offer_id);
+
+                if let Some(transaction_id) = row.transaction_id {
+                    trade_transactions.insert(transaction_id, row.offer_id);
+                }
+            }
+        }
+
+        let contacts = wallet.db.contact_names().await?;
+
+        let mut records = Vec::new();
+
+        for transaction in transactions {
+            let submitted = fees.remove(&transaction.height).unwrap_or_default();
+            let fee: u64 = submitted.iter().map(|row| row.fee).sum();
+
+            // Only coins with one of our own p2 puzzles are counted, so that coins which
+            // are merely tracked by the wallet don't show up as movements.
+            let mut movements = IndexMap::<Bytes32, (Asset, i128)>::new();
+
+            for (coins, sign) in [(transaction.spent, -1), (transaction.created, 1)] {
+                for coin in coins {
+                    if coin.p2_puzzle_hash.is_none() {
+                        continue;
+                    }
+
+                    let value = movement_value(coin.asset.kind, coin.coin.amount);
+
+                    movements
+                        .entry(coin.asset.hash)
+                        .or_insert_with(|| (coin.asset, 0))
+                        .1 += sign * value;
+                }
+            }
+
+            // The fee is reported separately, rather than as part of the XCH that was sent.
+            if fee > 0 {
+                if let Some((_, amount)) = movements.get_mut(&Bytes32::default()) {
+                    *amount += i128::from(fee);
+                }
+            }
+
+            let transaction_id = match submitted.as_slice() {
+                [row] => Some(row.hash),
+                _ => None,
+            };
+
+            let block_payments = payments.remove(&transaction.height).unwrap_or_default();
+            let xch = movements
+                .get(&Bytes32::default())
+                .map(|(asset, _)| asset.clone());
+            let mut rows = Vec::new();
+
+            for (asset_hash, (asset, amount)) in movements {
+                let mut remaining = amount;
+
+                if amount < 0 {
+                    for row in block_payments
+                        .iter()
+                        .filter(|row| row.payment.asset_hash == asset_hash)
+                    {
+                        let value = movement_value(asset.kind, row.payment.amount);
+                        remaining += value;
+
+                        rows.push(HistoryRow {
+                            asset: asset.clone(),
+                            amount: -value,
+                            transaction_id: Some(row.transaction_id),
+                            counterparty: Some(row.payment.p2_puzzle_hash),
+                            memos: row.payment.memos.clone(),
+                            offer_id: trade_transactions.get(&row.transaction_id).copied(),
+                        });
+                    }
+                }
+
+                if remaining != 0 {
+                    let offer_id = transaction_id
+                        .and_then(|transaction_id| trade_transactions.get(&transaction_id))
+                        .or_else(|| trade_assets.get(&(transaction.height, asset_hash)))
+                        .copied();
+
+                    rows.push(HistoryRow {
+                        asset,
+                        amount: remaining,
+                        transaction_id,
+                        counterparty: None,
+                        memos: Vec::new(),
+                        offer_id,
+                    });
+                }
+            }
+
+            // A transaction that only moved coins between our own addresses still paid a fee.
+            if let Some(asset) = xch.filter(|_| rows.is_empty() && fee > 0) {
+                rows.push(HistoryRow {
+                    asset,
+                    amount: 0,
+                    transaction_id,
+                    counterparty: None,
+                    memos: Vec::new(),
+                    offer_id: transaction_id
+                        .and_then(|transaction_id| trade_transactions.get(&transaction_id))
+                        .copied(),
+                });
+            }
+
+            if rows.is_empty() {
+                continue;
+            }
+
+            // The fee is split evenly between the rows, with the remainder on the first one.
+            let row_count = rows.len() as u64;
+            let fee_share = fee / row_count;
+            let fee_remainder = fee % row_count;
+
+            for (index, row) in rows.into_iter().enumerate() {
+                let fee = if index == 0 {
+                    fee_share + fee_remainder
+                } else {
+                    fee_share
+                };
+
+                records.push(TransactionExportRecord {
+                    height: transaction.height,
+                    timestamp: transaction.timestamp,
+                    transaction_id: row.transaction_id.map(hex::encode),
+                    asset_kind: encode_asset_kind(row.asset.kind),
+                    asset_id: encode_asset_id(row.asset.hash, row.asset.kind)?,
+                    ticker: row.asset.ticker,
+                    name: row.asset.name,
+                    precision: row.asset.precision,
+                    amount: row.amount.to_string(),
+                    fee: Amount::u64(fee),
+                    counterparty: row
+                        .counterparty
+                        .map(|puzzle_hash| {
+                            Address::new(puzzle_hash, self.network().prefix()).encode()
+                        })
+                        .transpose()?,
+                    memo: encode_memos(&row.memos),
+                    offer_id: row.offer_id.as_ref().map(ToString::to_string),
+                    label: row
+                        .counterparty
+                        .and_then(|puzzle_hash| contacts.get(&puzzle_hash))
+                        .cloned(),
+                });
+            }
+        }
+
+        let count = records.len().try_into()?;
+
+        let data = match req.format {
+            ExportFormat::Csv => {
+                let mut writer = csv::Writer::from_writer(Vec::new());
+
+                for record in records {
+                    writer.serialize(record)?;
+                }
+
+                String::from_utf8(
+                    writer
+                        .into_inner()
+                        .map_err(csv::IntoInnerError::into_error)?,
+                )
+                .expect("CSV output is valid UTF-8")
+            }
+            ExportFormat::Json => serde_json::to_string_pretty(&records)?,
+        };
+
+        Ok(ExportTransactionsResponse { data, count })
+    }
+}
+
+struct HistoryRow {
+    asset: Asset,
+    amount: i128,
+    transaction_id: Option<Bytes32>,
+    counterparty: Option<Bytes32>,
+    memos: Vec<Bytes>,
+    offer_id: Option<Bytes32>,
+}
+
+/// Tokens move by their amount, whereas singletons such as NFTs move one at a time.
+fn movement_value(kind: AssetKind, amount: u64) -> i128 {
+    match kind {
+        AssetKind::Token => i128::from(amount),
+        AssetKind::Nft | AssetKind::Did | AssetKind::Option => 1,
+    }
+}
+
+/// Memos are shown as text if possible, and hex encoded otherwise.
+fn encode_memos(memos: &[Bytes]) -> Option<String> {
+    if memos.is_empty() {
+        return None;
+    }
+
+    Some(
+        memos
+            .iter()
+            .map(|memo| match std::str::from_utf8(memo) {
+                Ok(text) if !text.chars().any(char::is_control) => text.to_string(),
+                _ => hex::encode(memo),
+            })
+            .collect::<Vec<_>>()
+            .join("; "),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encode_memos() {
+        assert_eq!(encode_memos(&[]), None);
+        assert_eq!(
+            encode_memos(&[Bytes::from(b"invoice 42".to_vec())]),
+            Some("invoice 42".to_string())
+        );
+        assert_eq!(
+            encode_memos(&[
+                Bytes::from(b"hello".to_vec()),
+                Bytes::from(vec![0x00, 0xff])
+            ]),
+            Some("hello; 00ff".to_string())
+        );
+    }
+}
@@ -16,8 +16,8 @@ use sage_api::{
     CreateDid, ExerciseOptions, FinalizeClawback, IssueCat, MintOption, MintOptionResponse,
     MultiSend, NftUriKind, NormalizeDids, OptionAsset, SendCat, SendXch, SignCoinSpends,
     SignCoinSpendsResponse, Split, SubmitTransaction, SubmitTransactionResponse,
-    TransactionResponse, TransferDids, TransferNfts, TransferOptions, ViewCoinSpends,
-    ViewCoinSpendsResponse,
+    TransactionResponse, TransferDids, TransferNfts, TransferOptions, UpdateNftMetadata,
+    ViewCoinSpends, ViewCoinSpendsResponse,
 };
 use sage_assets::fetch_uris_without_hash;
 use sage_database::{Asset, AssetKind};
@@ -288,6 +288,7 @@ impl Sage {
         let mut mints = Vec::with_capacity(req.mints.len());
         let mut info = ConfirmationInfo::default();
         let testnet = self.network().genesis_challenge == TESTNET11_CONSTANTS.genesis_challenge;
+        let resolver = self.uri_resolver();
 
         for item in req.mints {
             let royalty_puzzle_hash = item
@@ -304,7 +305,7 @@ impl Sage {
             } else {
                 let data = timeout(
                     Duration::from_secs(10),
-                    fetch_uris_without_hash(item.data_uris.clone(), testnet),
+                    fetch_uris_without_hash(&resolver, item.data_uris.clone(), testnet),
                 )
                 .await??;
 
@@ -321,7 +322,7 @@ impl Sage {
             } else {
                 let metadata = timeout(
                     Duration::from_secs(10),
-                    fetch_uris_without_hash(item.metadata_uris.clone(), testnet),
+                    fetch_uris_without_hash(&resolver, item.metadata_uris.clone(), testnet),
                 )
                 .await??;
 
@@ -338,7 +339,7 @@ impl Sage {
             } else {
                 let data = timeout(
                     Duration::from_secs(10),
-                    fetch_uris_without_hash(item.license_uris.clone(), testnet),
+                    fetch_uris_without_hash(&resolver, item.license_uris.clone(), testnet),
                 )
                 .await??;
 
@@ -421,6 +422,40 @@ impl Sage {
         self.transact(coin_spends, req.auto_submit).await
     }
 
+    pub async fn update_nft_metadata(&self, req: UpdateNftMetadata) -> Result<TransactionResponse> {
+        let wallet = self.wallet()?;
+        let fee = parse_amount(req.fee)?;
+
+        let mut updates = Vec::with_capacity(req.updates.len());
+
+        for update in req.updates {
+            let nft_id = parse_nft_id(update.nft_id)?;
+
+            let uris = update
+                .data_uris
+                .into_iter()
+                .map(MetadataUpdate::NewDataUri)
+                .chain(
+                    update
+                        .metadata_uris
+                        .into_iter()
+                        .map(MetadataUpdate::NewMetadataUri),
+                )
+                .chain(
+                    update
+                        .license_uris
+                        .into_iter()
+                        .map(MetadataUpdate::NewLicenseUri),
+                )
+                .collect();
+
+            updates.push((nft_id, uris));
+        }
+
+        let coin_spends = wallet.update_nft_metadata(updates, fee).await?;
+        self.transact(coin_spends, req.auto_submit).await
+    }
+
     pub async fn assign_nfts_to_did(&self, req: AssignNftsToDid) -> Result<TransactionResponse> {
         let wallet = self.wallet()?;
         let nft_ids = req
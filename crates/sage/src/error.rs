@@ -58,6 +58,9 @@ pub enum Error {
     #[error("BLS error: {0}")]
     Bls(#[from] chia::bls::Error),
 
+    #[error("Streamable error: {0}")]
+    Streamable(#[from] chia::traits::Error),
+
     #[error("BIP39 error: {0}")]
     Bip39(#[from] bip39::Error),
 
@@ -79,6 +82,15 @@ pub enum Error {
     #[error("Bincode error: {0}")]
     Bincode(#[from] bincode::Error),
 
+    #[error("CSV error: {0}")]
+    Csv(#[from] csv::Error),
+
+    #[error("JSON error: {0}")]
+    Json(#[from] serde_json::Error),
+
+    #[error("HTTP error: {0}")]
+    Reqwest(#[from] reqwest::Error),
+
     #[error("Logging initialization error: {0}")]
     LogSubscriber(#[from] TryInitError),
 
@@ -166,6 +178,9 @@ pub enum Error {
     #[error("Cannot specify both collection and DID")]
     InvalidGroup,
 
+    #[error("Trait filter for {0} must have either a value or a range")]
+    InvalidTraitFilter(String),
+
     #[error("Wallet is cold and cannot be used for signing")]
     NoSigningKey,
 
@@ -196,6 +211,75 @@ pub enum Error {
     #[error("Missing offer: {0}")]
     MissingOffer(Bytes32),
 
+    #[error("Missing contact: {0}")]
+    MissingContact(u32),
+
+    #[error("Contact name cannot be empty")]
+    EmptyContactName,
+
+    #[error("Missing scheduled payment: {0}")]
+    MissingScheduledPayment(u32),
+
+    #[error("Scheduled payment name cannot be empty")]
+    EmptyScheduledPaymentName,
+
+    #[error("Scheduled payments must have at least one payment")]
+    EmptyScheduledPayment,
+
+    #[error("Invalid schedule: {0}")]
+    InvalidSchedule(String),
+
+    #[error("Missing payout: {0}")]
+    MissingPayout(u32),
+
+    #[error("Payout name cannot be empty")]
+    EmptyPayoutName,
+
+    #[error("Payouts must have at least one payment")]
+    EmptyPayout,
+
+    #[error("Invalid payout file: {0}")]
+    InvalidPayoutFile(String),
+
+    #[error("Invalid payout row {0}: {1}")]
+    InvalidPayoutRow(usize, String),
+
+    #[error("Missing mint job: {0}")]
+    MissingMintJob(u32),
+
+    #[error("Mint job name cannot be empty")]
+    EmptyMintJobName,
+
+    #[error("Mint jobs must have at least one NFT")]
+    EmptyMintJob,
+
+    #[error("Invalid mint job manifest: {0}")]
+    InvalidMintJobManifest(String),
+
+    #[error("Invalid mint job row {0}: {1}")]
+    InvalidMintJobRow(usize, String),
+
+    #[error("The start of the transaction history range is after the end")]
+    InvalidHistoryRange,
+
+    #[error("Missing offer group: {0}")]
+    MissingOfferGroup(u32),
+
+    #[error("Invalid price: {0}")]
+    InvalidPrice(String),
+
+    #[error("Invalid offer ladder: {0}")]
+    InvalidOfferLadder(String),
+
+    #[error("Either a list of NFT ids or a collection id must be provided")]
+    InvalidNftSelection,
+
+    #[error("Invalid recovery list: {0}")]
+    InvalidRecoveryList(String),
+
+    #[error("Invalid attestation: {0}")]
+    InvalidAttestation(String),
+
     #[error("Coin already spent: {0}")]
     CoinSpent(Bytes32),
 
@@ -205,6 +289,15 @@ pub enum Error {
     #[error("No peers are currently available")]
     NoPeers,
 
+    #[error("The current peak height isn't known yet")]
+    UnknownPeak,
+
+    #[error("Unknown exchange: {0}")]
+    UnknownExchange(String),
+
+    #[error("Exchange error: {0}")]
+    Exchange(String),
+
     #[error("Could not fetch NFT with id: {0}")]
     CouldNotFetchNft(Bytes32),
 
@@ -233,6 +326,7 @@ pub enum Error {
 impl Error {
     pub fn kind(&self) -> ErrorKind {
         match self {
+            Self::Wallet(WalletError::PolicyViolation(..)) => ErrorKind::Policy,
             Self::Wallet(..) => ErrorKind::Wallet,
             Self::NotLoggedIn | Self::NoSigningKey => ErrorKind::Unauthorized,
             Self::Keychain(error) => match error {
@@ -261,6 +355,9 @@ impl Error {
             | Self::ToClvm(..)
             | Self::FromClvm(..)
             | Self::Bincode(..)
+            | Self::Csv(..)
+            | Self::Json(..)
+            | Self::Reqwest(..)
             | Self::Eval(..)
             | Self::Driver(..)
             | Self::Timeout(..) => ErrorKind::Internal,
@@ -274,8 +371,15 @@ impl Error {
             | Self::MissingDid(..)
             | Self::MissingNft(..)
             | Self::MissingOption(..)
-            | Self::MissingOffer(..) => ErrorKind::NotFound,
+            | Self::MissingOffer(..)
+            | Self::MissingContact(..)
+            | Self::MissingScheduledPayment(..)
+            | Self::MissingPayout(..)
+            | Self::MissingMintJob(..)
+            | Self::MissingOfferGroup(..)
+            | Self::UnknownExchange(..) => ErrorKind::NotFound,
             Self::Bls(..)
+            | Self::Streamable(..)
             | Self::Hex(..)
             | Self::InvalidKey
             | Self::TryFromSlice(..)
@@ -300,12 +404,33 @@ impl Error {
             | Self::Uri(..)
             | Self::IpAddrParse(..)
             | Self::NoPeers
+            | Self::UnknownPeak
+            | Self::Exchange(..)
             | Self::CouldNotFetchNft(..)
             | Self::CouldNotFetchOption(..)
             | Self::MissingAssetId
             | Self::InvalidGroup
+            | Self::InvalidTraitFilter(..)
             | Self::InvalidThemeJson
-            | Self::MissingThemeData => ErrorKind::Api,
+            | Self::MissingThemeData
+            | Self::EmptyContactName
+            | Self::EmptyScheduledPaymentName
+            | Self::EmptyScheduledPayment
+            | Self::InvalidSchedule(..)
+            | Self::EmptyPayoutName
+            | Self::EmptyPayout
+            | Self::InvalidPayoutFile(..)
+            | Self::InvalidPayoutRow(..)
+            | Self::EmptyMintJobName
+            | Self::EmptyMintJob
+            | Self::InvalidMintJobManifest(..)
+            | Self::InvalidMintJobRow(..)
+            | Self::InvalidHistoryRange
+            | Self::InvalidPrice(..)
+            | Self::InvalidOfferLadder(..)
+            | Self::InvalidNftSelection
+            | Self::InvalidRecoveryList(..)
+            | Self::InvalidAttestation(..) => ErrorKind::Api,
         }
     }
 }
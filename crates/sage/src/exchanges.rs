@@ -0,0 +1,518 @@
+use std::{future::Future, time::Duration};
+
+use chia_wallet_sdk::driver::decode_offer;
+use reqwest::{Client, Response};
+use sage_config::{ExchangeConfig, ExchangeKind};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+use crate::{Error, Result};
+
+/// How long an exchange has to respond to a request.
+const EXCHANGE_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// A service that offers can be published to, and later fetched from by the code it gives them.
+pub trait OfferExchange {
+    /// Publishes an offer, and returns the code it can be fetched by.
+    fn publish_offer(&self, offer: &str) -> impl Future<Output = Result<String>> + Send;
+
+    /// Fetches a published offer by its code.
+    fn fetch_offer(&self, code: &str) -> impl Future<Output = Result<String>> + Send;
+
+    /// Searches the offers listed on the exchange for a pair of assets, a page at a time.
+    fn search_offers(
+        &self,
+        search: &OfferSearch,
+    ) -> impl Future<Output = Result<Vec<ExchangeOffer>>> + Send;
+}
+
+/// The assets to search for offers of, where each asset is identified the same way as by the
+/// exchange, such as by its asset id or `xch`. Pages start from 1.
+#[derive(Debug, Clone, Serialize)]
+pub struct OfferSearch {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub offered: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub requested: Option<String>,
+    pub page: u32,
+    pub page_size: u32,
+}
+
+/// An offer listed on an exchange, along with the code it can be fetched by.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct ExchangeOffer {
+    #[serde(alias = "id")]
+    pub code: String,
+    pub offer: String,
+}
+
+/// One of the exchanges which can be configured, chosen by its kind.
+#[derive(Debug, Clone)]
+pub enum Exchange {
+    OfferCode(OfferCodeExchange),
+    Dexie(DexieExchange),
+    Http(HttpExchange),
+}
+
+impl Exchange {
+    /// Connects to an exchange with a client that gives up on requests which take too long.
+    pub fn from_config(config: &ExchangeConfig) -> Result<Self> {
+        let client = Client::builder().timeout(EXCHANGE_TIMEOUT).build()?;
+
+        Ok(Self::new(client, config))
+    }
+
+    pub fn new(client: Client, config: &ExchangeConfig) -> Self {
+        let base_url = config.base_url.trim_end_matches('/').to_string();
+
+        match config.kind {
+            ExchangeKind::OfferCode => Self::OfferCode(OfferCodeExchange { client, base_url }),
+            ExchangeKind::Dexie => Self::Dexie(DexieExchange { client, base_url }),
+            ExchangeKind::Http => Self::Http(HttpExchange { client, base_url }),
+        }
+    }
+
+    /// Fetches a published offer by its code, and makes sure that it's an offer that can be used.
+    pub async fn fetch_valid_offer(&self, code: &str) -> Result<String> {
+        let offer = self.fetch_offer(code.trim()).await?;
+
+        decode_offer(&offer)?;
+
+        Ok(offer)
+    }
+}
+
+impl OfferExchange for Exchange {
+    async fn publish_offer(&self, offer: &str) -> Result<String> {
+        match self {
+            Self::OfferCode(exchange) => exchange.publish_offer(offer).await,
+            Self::Dexie(exchange) => exchange.publish_offer(offer).await,
+            Self::Http(exchange) => exchange.publish_offer(offer).await,
+        }
+    }
+
+    async fn fetch_offer(&self, code: &str) -> Result<String> {
+        match self {
+            Self::OfferCode(exchange) => exchange.fetch_offer(code).await,
+            Self::Dexie(exchange) => exchange.fetch_offer(code).await,
+            Self::Http(exchange) => exchange.fetch_offer(code).await,
+        }
+    }
+
+    async fn search_offers(&self, search: &OfferSearch) -> Result<Vec<ExchangeOffer>> {
+        match self {
+            Self::OfferCode(exchange) => exchange.search_offers(search).await,
+            Self::Dexie(exchange) => exchange.search_offers(search).await,
+            Self::Http(exchange) => exchange.search_offers(search).await,
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct OfferBody<'a> {
+    offer: &'a str,
+}
+
+#[derive(Serialize)]
+struct CodeBody<'a> {
+    code: &'a str,
+}
+
+#[derive(Deserialize)]
+struct OfferResponse {
+    offer: String,
+}
+
+#[derive(Deserialize)]
+struct CodeResponse {
+    code: String,
+}
+
+#[derive(Deserialize)]
+struct SearchResponse {
+    offers: Vec<ExchangeOffer>,
+}
+
+/// Shares offers by short codes, such as with offercodes.chia.net.
+#[derive(Debug, Clone)]
+pub struct OfferCodeExchange {
+    client: Client,
+    base_url: String,
+}
+
+impl OfferExchange for OfferCodeExchange {
+    async fn publish_offer(&self, offer: &str) -> Result<String> {
+        let response = self
+            .client
+            .post(format!("{}/upload_offer", self.base_url))
+            .json(&OfferBody { offer })
+            .send()
+            .await?;
+
+        Ok(parse_response::<CodeResponse>(response).await?.code)
+    }
+
+    async fn fetch_offer(&self, code: &str) -> Result<String> {
+        let response = self
+            .client
+            .post(format!("{}/download_offer", self.base_url))
+            .json(&CodeBody { code })
+            .send()
+            .await?;
+
+        Ok(parse_response::<OfferResponse>(response).await?.offer)
+    }
+
+    /// Offer codes are private to whoever they're shared with, so there's nothing to search.
+    async fn search_offers(&self, _search: &OfferSearch) -> Result<Vec<ExchangeOffer>> {
+        Err(Error::Exchange(
+            "Offer code services can't be searched".to_string(),
+        ))
+    }
+}
+
+/// Posts offers to a Dexie compatible API, which identifies them by offer id.
+#[derive(Debug, Clone)]
+pub struct DexieExchange {
+    client: Client,
+    base_url: String,
+}
+
+#[derive(Deserialize)]
+struct DexiePostResponse {
+    success: bool,
+    id: Option<String>,
+    error_message: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct DexieGetResponse {
+    success: bool,
+    offer: Option<OfferResponse>,
+    error_message: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct DexieSearchResponse {
+    success: bool,
+    offers: Option<Vec<ExchangeOffer>>,
+    error_message: Option<String>,
+}
+
+/// The status Dexie lists offers which can still be taken under.
+const DEXIE_OPEN_STATUS: u8 = 0;
+
+impl OfferExchange for DexieExchange {
+    async fn publish_offer(&self, offer: &str) -> Result<String> {
+        let response = self
+            .client
+            .post(format!("{}/v1/offers", self.base_url))
+            .json(&OfferBody { offer })
+            .send()
+            .await?;
+
+        let response = parse_response::<DexiePostResponse>(response).await?;
+
+        match response.id {
+            Some(id) if response.success => Ok(id),
+            _ => {
+                Err(Error::Exchange(response.error_message.unwrap_or_else(
+                    || "The offer was not accepted".to_string(),
+                )))
+            }
+        }
+    }
+
+    async fn fetch_offer(&self, code: &str) -> Result<String> {
+        let response = self
+            .client
+            .get(format!("{}/v1/offers/{code}", self.base_url))
+            .send()
+            .await?;
+
+        let response = parse_response::<DexieGetResponse>(response).await?;
+
+        match response.offer {
+            Some(offer) if response.success => Ok(offer.offer),
+            _ => Err(Error::Exchange(
+                response
+                    .error_message
+                    .unwrap_or_else(|| format!("Offer {code} was not found")),
+            )),
+        }
+    }
+
+    async fn search_offers(&self, search: &OfferSearch) -> Result<Vec<ExchangeOffer>> {
+        let response = self
+            .client
+            .get(format!("{}/v1/offers", self.base_url))
+            .query(search)
+            .query(&[("status", DEXIE_OPEN_STATUS)])
+            .send()
+            .await?;
+
+        let response = parse_response::<DexieSearchResponse>(response).await?;
+
+        match response.offers {
+            Some(offers) if response.success => Ok(offers),
+            _ => Err(Error::Exchange(response.error_message.unwrap_or_else(
+                || "The offers couldn't be searched".to_string(),
+            ))),
+        }
+    }
+}
+
+/// A minimal HTTP API, where offers are posted to `/offers` and fetched from `/offers/{code}`.
+/// Listed offers are searched with a `GET` request to `/offers`, with the same query parameters
+/// as Dexie.
+#[derive(Debug, Clone)]
+pub struct HttpExchange {
+    client: Client,
+    base_url: String,
+}
+
+impl OfferExchange for HttpExchange {
+    async fn publish_offer(&self, offer: &str) -> Result<String> {
+        let response = self
+            .client
+            .post(format!("{}/offers", self.base_url))
+            .json(&OfferBody { offer })
+            .send()
+            .await?;
+
+        Ok(parse_response::<CodeResponse>(response).await?.code)
+    }
+
+    async fn fetch_offer(&self, code: &str) -> Result<String> {
+        let response = self
+            .client
+            .get(format!("{}/offers/{code}", self.base_url))
+            .send()
+            .await?;
+
+        Ok(parse_response::<OfferResponse>(response).await?.offer)
+    }
+
+    async fn search_offers(&self, search: &OfferSearch) -> Result<Vec<ExchangeOffer>> {
+        let response = self
+            .client
+            .get(format!("{}/offers", self.base_url))
+            .query(search)
+            .send()
+            .await?;
+
+        Ok(parse_response::<SearchResponse>(response).await?.offers)
+    }
+}
+
+async fn parse_response<T: DeserializeOwned>(response: Response) -> Result<T> {
+    let status = response.status();
+
+    if !status.is_success() {
+        return Err(Error::Exchange(format!(
+            "The server responded with status {status}"
+        )));
+    }
+
+    Ok(response.json().await?)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        collections::HashMap,
+        sync::{Arc, Mutex},
+    };
+
+    use axum::{
+        extract::{Path, Query, State},
+        routing::get,
+        Json, Router,
+    };
+    use serde_json::{json, Value};
+    use tokio::net::TcpListener;
+
+    use super::*;
+
+    type Offers = Arc<Mutex<HashMap<String, String>>>;
+
+    /// Serves a router on a local port, and returns the config of an exchange using it.
+    async fn serve(app: Router, kind: ExchangeKind) -> anyhow::Result<ExchangeConfig> {
+        let listener = TcpListener::bind("127.0.0.1:0").await?;
+        let address = listener.local_addr()?;
+
+        tokio::spawn(async move { axum::serve(listener, app).await });
+
+        Ok(ExchangeConfig {
+            name: "mock".to_string(),
+            kind,
+            base_url: format!("http://{address}/"),
+        })
+    }
+
+    /// Offers whose text contains the offered asset match a search for it.
+    fn search(offers: &Offers, query: &HashMap<String, String>) -> Vec<Value> {
+        let offers = offers.lock().expect("poisoned");
+        let mut codes = offers.keys().cloned().collect::<Vec<_>>();
+        codes.sort();
+
+        codes
+            .into_iter()
+            .filter(|code| {
+                query
+                    .get("offered")
+                    .is_none_or(|offered| offers[code].contains(offered.as_str()))
+            })
+            .map(|code| json!({ "code": code, "offer": offers[&code] }))
+            .collect()
+    }
+
+    /// Serves the HTTP exchange API on a local port, storing offers in memory.
+    async fn mock_exchange() -> anyhow::Result<ExchangeConfig> {
+        async fn post_offer(State(offers): State<Offers>, Json(body): Json<Value>) -> Json<Value> {
+            let mut offers = offers.lock().expect("poisoned");
+            let code = format!("code{}", offers.len());
+            offers.insert(
+                code.clone(),
+                body["offer"].as_str().unwrap_or_default().to_string(),
+            );
+            Json(json!({ "code": code }))
+        }
+
+        async fn get_offer(State(offers): State<Offers>, Path(code): Path<String>) -> Json<Value> {
+            let offers = offers.lock().expect("poisoned");
+            Json(json!({ "offer": offers.get(&code) }))
+        }
+
+        async fn search_offers(
+            State(offers): State<Offers>,
+            Query(query): Query<HashMap<String, String>>,
+        ) -> Json<Value> {
+            Json(json!({ "offers": search(&offers, &query) }))
+        }
+
+        let app = Router::new()
+            .route("/offers", get(search_offers).post(post_offer))
+            .route("/offers/:code", get(get_offer))
+            .with_state(Offers::default());
+
+        serve(app, ExchangeKind::Http).await
+    }
+
+    /// Serves the parts of the Dexie API that are used on a local port, storing offers in memory.
+    async fn mock_dexie() -> anyhow::Result<ExchangeConfig> {
+        async fn post_offer(State(offers): State<Offers>, Json(body): Json<Value>) -> Json<Value> {
+            let mut offers = offers.lock().expect("poisoned");
+            let id = format!("id{}", offers.len());
+            offers.insert(
+                id.clone(),
+                body["offer"].as_str().unwrap_or_default().to_string(),
+            );
+            Json(json!({ "success": true, "id": id }))
+        }
+
+        async fn get_offer(State(offers): State<Offers>, Path(id): Path<String>) -> Json<Value> {
+            let offers = offers.lock().expect("poisoned");
+
+            match offers.get(&id) {
+                Some(offer) => Json(json!({ "success": true, "offer": { "offer": offer } })),
+                None => Json(json!({ "success": false, "error_message": "Offer not found" })),
+            }
+        }
+
+        async fn search_offers(
+            State(offers): State<Offers>,
+            Query(query): Query<HashMap<String, String>>,
+        ) -> Json<Value> {
+            // Only open offers are searched for
+            if query.get("status").map(String::as_str) != Some("0") {
+                return Json(json!({ "success": false, "error_message": "Missing status" }));
+            }
+
+            let offers = search(&offers, &query)
+                .into_iter()
+                .map(|offer| json!({ "id": offer["code"], "offer": offer["offer"] }))
+                .collect::<Vec<_>>();
+
+            Json(json!({ "success": true, "offers": offers }))
+        }
+
+        let app = Router::new()
+            .route("/v1/offers", get(search_offers).post(post_offer))
+            .route("/v1/offers/:id", get(get_offer))
+            .with_state(Offers::default());
+
+        serve(app, ExchangeKind::Dexie).await
+    }
+
+    fn offered(asset: &str) -> OfferSearch {
+        OfferSearch {
+            offered: Some(asset.to_string()),
+            requested: None,
+            page: 1,
+            page_size: 10,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_http_exchange() -> anyhow::Result<()> {
+        let exchange = Exchange::from_config(&mock_exchange().await?)?;
+
+        let code = exchange.publish_offer("offer1test").await?;
+        assert_eq!(code, "code0");
+        assert_eq!(exchange.fetch_offer(&code).await?, "offer1test");
+
+        // Unknown codes don't have an offer to deserialize
+        assert!(exchange.fetch_offer("missing").await.is_err());
+
+        exchange.publish_offer("offer1other").await?;
+
+        assert_eq!(
+            exchange.search_offers(&offered("test")).await?,
+            vec![ExchangeOffer {
+                code: "code0".to_string(),
+                offer: "offer1test".to_string(),
+            }]
+        );
+        assert!(exchange.search_offers(&offered("none")).await?.is_empty());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_dexie_exchange() -> anyhow::Result<()> {
+        let exchange = Exchange::from_config(&mock_dexie().await?)?;
+
+        let id = exchange.publish_offer("offer1test").await?;
+        assert_eq!(id, "id0");
+        assert_eq!(exchange.fetch_offer(&id).await?, "offer1test");
+        assert!(exchange.fetch_offer("missing").await.is_err());
+
+        exchange.publish_offer("offer1other").await?;
+
+        assert_eq!(
+            exchange.search_offers(&offered("other")).await?,
+            vec![ExchangeOffer {
+                code: "id1".to_string(),
+                offer: "offer1other".to_string(),
+            }]
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_offer_code_search() -> anyhow::Result<()> {
+        let exchange = Exchange::from_config(&ExchangeConfig {
+            name: "offercodes".to_string(),
+            kind: ExchangeKind::OfferCode,
+            base_url: "http://127.0.0.1:1".to_string(),
+        })?;
+
+        assert!(matches!(
+            exchange.search_offers(&offered("xch")).await,
+            Err(Error::Exchange(..))
+        ));
+
+        Ok(())
+    }
+}
@@ -2,11 +2,19 @@
 
 mod endpoints;
 mod error;
+mod exchanges;
 mod peers;
 mod sage;
 mod utils;
 
+#[cfg(test)]
+mod test;
+
 pub use error::*;
+pub use exchanges::*;
 pub use sage::*;
 
 pub(crate) use utils::*;
+
+#[cfg(test)]
+pub(crate) use test::*;
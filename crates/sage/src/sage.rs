@@ -6,7 +6,10 @@ use std::{
     time::{Duration, SystemTime, UNIX_EPOCH},
 };
 
-use chia::{bls::master_to_wallet_unhardened_intermediate, protocol::Bytes32};
+use chia::{
+    bls::{master_to_wallet_unhardened_intermediate, SecretKey},
+    protocol::Bytes32,
+};
 use chia_wallet_sdk::{
     client::{create_rustls_connector, load_ssl_cert, Connector},
     signer::AggSigConstants,
@@ -14,6 +17,7 @@ use chia_wallet_sdk::{
 };
 use indexmap::IndexMap;
 use sage_api::{Unit, XCH};
+use sage_assets::UriResolver;
 use sage_config::{
     migrate_config, migrate_networks, Config, Network, NetworkList, OldConfig, OldNetwork,
     WalletConfig,
@@ -26,7 +30,7 @@ use sqlx::{
     ConnectOptions, SqlitePool,
 };
 use tokio::sync::{mpsc, Mutex};
-use tracing::{error, info, Level};
+use tracing::{error, info, warn, Level};
 use tracing_appender::rolling::{Builder, Rotation};
 use tracing_subscriber::{
     filter::filter_fn, fmt, layer::SubscriberExt, EnvFilter, Layer, Registry,
@@ -232,6 +236,7 @@ impl Sage {
             self.peer_state.clone(),
             self.wallet.clone(),
             self.network().clone(),
+            self.uri_resolver(),
             connector,
         );
 
@@ -272,7 +277,7 @@ impl Sage {
         let intermediate_pk = master_to_wallet_unhardened_intermediate(&master_pk);
 
         let pool = self.connect_to_database(fingerprint).await?;
-        let db = Database::new(pool);
+        let db = Database::new(pool).with_media_dir(self.wallet_media_path(fingerprint)?);
 
         db.run_rust_migrations(self.network().ticker.clone())
             .await?;
@@ -306,9 +311,33 @@ impl Sage {
             })
             .await?;
 
+        self.command_sender
+            .send(SyncCommand::SetSigningKey(self.background_signing_key(
+                fingerprint,
+                wallet_config.background_signing,
+            )))
+            .await?;
+
         Ok(())
     }
 
+    /// The key used by background jobs, which is only handed over if the user has opted in
+    /// to unattended signing for this wallet. A watch-only wallet or a key that can't be
+    /// extracted without a password just means that nothing is signed in the background.
+    pub fn background_signing_key(&self, fingerprint: u32, enabled: bool) -> Option<SecretKey> {
+        if !enabled {
+            return None;
+        }
+
+        match self.keychain.extract_secrets(fingerprint, b"") {
+            Ok((_mnemonic, master_sk)) => master_sk,
+            Err(error) => {
+                warn!("Background signing is unavailable for wallet {fingerprint}: {error}");
+                None
+            }
+        }
+    }
+
     pub async fn setup_peers(&mut self) -> Result<()> {
         let peer_dir = self.path.join("peers");
 
@@ -448,6 +477,12 @@ impl Sage {
         Ok(path)
     }
 
+    /// NFT files which are stored outside of the database are kept next to it, in a directory
+    /// named after the network.
+    pub fn wallet_media_path(&self, fingerprint: u32) -> Result<PathBuf> {
+        Ok(self.wallet_db_path(fingerprint)?.with_extension("media"))
+    }
+
     pub fn wallet_config(&self) -> Option<&sage_config::Wallet> {
         self.config.global.fingerprint.and_then(|fingerprint| {
             self.wallet_config
@@ -476,6 +511,10 @@ impl Sage {
         self.network().network_id()
     }
 
+    pub fn uri_resolver(&self) -> UriResolver {
+        UriResolver::new(self.config.gateways.clone())
+    }
+
     pub fn wallet(&self) -> Result<Arc<Wallet>> {
         let Some(fingerprint) = self.config.global.fingerprint else {
             return Err(Error::NotLoggedIn);
@@ -0,0 +1,30 @@
+use std::{path::Path, sync::Arc};
+
+use chia::bls::{master_to_wallet_unhardened_intermediate, SecretKey};
+use chia_wallet_sdk::signer::AggSigConstants;
+use sage_database::Database;
+use sage_wallet::Wallet;
+
+use crate::Sage;
+
+/// A logged in wallet with a database in the given directory, which isn't synced.
+pub(crate) async fn test_sage(path: &Path) -> anyhow::Result<Sage> {
+    let mut sage = Sage::new(path);
+
+    let master_sk = SecretKey::from_seed(&[1; 32]);
+    let fingerprint = sage.keychain.add_secret_key(&master_sk, b"")?;
+    sage.config.global.fingerprint = Some(fingerprint);
+
+    let pool = sage.connect_to_database(fingerprint).await?;
+
+    sage.wallet = Some(Arc::new(Wallet::new(
+        Database::new(pool),
+        fingerprint,
+        master_to_wallet_unhardened_intermediate(&master_sk.public_key()),
+        sage.network().genesis_challenge,
+        AggSigConstants::new(sage.network().agg_sig_me()),
+        None,
+    )));
+
+    Ok(sage)
+}
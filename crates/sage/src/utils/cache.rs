@@ -82,12 +82,13 @@ impl Sage {
 
         let info = if let Ok(metadata) = NftMetadata::from_clvm(allocator, nft_metadata) {
             let testnet = self.network().genesis_challenge == TESTNET11_CONSTANTS.genesis_challenge;
+            let resolver = self.uri_resolver();
 
             if let Some(hash) = metadata.data_hash {
                 if let Entry::Vacant(entry) = confirmation_info.nft_data.entry(hash) {
                     if let Ok(Some(data)) = timeout(
                         Duration::from_secs(10),
-                        fetch_uris_with_hash(metadata.data_uris.clone(), hash, testnet),
+                        fetch_uris_with_hash(&resolver, metadata.data_uris.clone(), hash, testnet),
                     )
                     .await
                     {
@@ -100,7 +101,12 @@ impl Sage {
                 if let Entry::Vacant(entry) = confirmation_info.nft_data.entry(hash) {
                     if let Ok(Some(data)) = timeout(
                         Duration::from_secs(10),
-                        fetch_uris_with_hash(metadata.metadata_uris.clone(), hash, testnet),
+                        fetch_uris_with_hash(
+                            &resolver,
+                            metadata.metadata_uris.clone(),
+                            hash,
+                            testnet,
+                        ),
                     )
                     .await
                     {
@@ -5,11 +5,12 @@ use chia::{
     protocol::{Bytes32, Coin, CoinSpend, SpendBundle},
     puzzles::nft::NftMetadata,
 };
+use chia_puzzles::{SETTLEMENT_PAYMENT_HASH, SINGLETON_LAUNCHER_HASH};
 use chia_wallet_sdk::{driver::BURN_PUZZLE_HASH, utils::Address};
 use clvmr::Allocator;
 use sage_api::{
-    Amount, CoinJson, CoinSpendJson, SpendBundleJson, TransactionInput, TransactionOutput,
-    TransactionSummary,
+    Amount, CoinJson, CoinSpendJson, NftMetadataDiff, NftMetadataJson, SpendBundleJson,
+    TransactionInput, TransactionOutput, TransactionSummary,
 };
 use sage_assets::{base64_data_uri, Data};
 use sage_database::{Asset, AssetKind, Database};
@@ -19,6 +20,17 @@ use crate::{Error, Result, Sage};
 
 use super::{parse_coin_id, parse_hash, parse_program, parse_signature};
 
+/// Sending to an address that we've never seen before is worth a warning, since it's likely to be
+/// a typo or a substituted address. Our own addresses, and the well-known puzzles used for burns,
+/// singleton launches and offer settlements, are never considered new.
+fn is_new_address(p2_puzzle_hash: Bytes32, receiving: bool, known: bool) -> bool {
+    !receiving
+        && !known
+        && p2_puzzle_hash != BURN_PUZZLE_HASH
+        && p2_puzzle_hash != SINGLETON_LAUNCHER_HASH.into()
+        && p2_puzzle_hash != SETTLEMENT_PAYMENT_HASH.into()
+}
+
 #[derive(Debug, Default)]
 pub struct ConfirmationInfo {
     pub nft_data: HashMap<Bytes32, Data>,
@@ -33,6 +45,19 @@ impl Sage {
         let wallet = self.wallet()?;
 
         let transaction = Transaction::from_coin_spends(coin_spends)?;
+        let contacts = wallet.db.contact_names().await?;
+
+        let nft_metadata_diffs = transaction
+            .nft_metadata_updates()
+            .into_iter()
+            .map(|(launcher_id, old, new)| {
+                Ok(NftMetadataDiff {
+                    nft_id: Address::new(launcher_id, "nft".to_string()).encode()?,
+                    old: nft_metadata_json(old),
+                    new: nft_metadata_json(new),
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
 
         let mut inputs = Vec::with_capacity(transaction.inputs.len());
 
@@ -108,13 +133,23 @@ impl Sage {
                     .unwrap_or(output.coin.puzzle_hash);
 
                 let address = Address::new(p2_puzzle_hash, self.network().prefix()).encode()?;
+                let receiving = wallet.db.is_custody_p2_puzzle_hash(p2_puzzle_hash).await?;
+
+                let known = wallet.db.is_known_puzzle_hash(p2_puzzle_hash).await?
+                    || wallet
+                        .db
+                        .is_known_puzzle_hash(output.coin.puzzle_hash)
+                        .await?;
+                let is_new_address = is_new_address(p2_puzzle_hash, receiving, known);
 
                 outputs.push(TransactionOutput {
                     coin_id: hex::encode(output.coin.coin_id()),
                     amount: Amount::u64(output.coin.amount),
                     address,
-                    receiving: wallet.db.is_custody_p2_puzzle_hash(p2_puzzle_hash).await?,
+                    receiving,
                     burning: p2_puzzle_hash == BURN_PUZZLE_HASH,
+                    contact: contacts.get(&p2_puzzle_hash).cloned(),
+                    is_new_address,
                 });
             }
 
@@ -130,10 +165,24 @@ impl Sage {
         Ok(TransactionSummary {
             fee: Amount::u64(transaction.fee),
             inputs,
+            nft_metadata_diffs,
         })
     }
 }
 
+fn nft_metadata_json(metadata: NftMetadata) -> NftMetadataJson {
+    NftMetadataJson {
+        edition_number: metadata.edition_number,
+        edition_total: metadata.edition_total,
+        data_uris: metadata.data_uris,
+        data_hash: metadata.data_hash.map(hex::encode),
+        metadata_uris: metadata.metadata_uris,
+        metadata_hash: metadata.metadata_hash.map(hex::encode),
+        license_uris: metadata.license_uris,
+        license_hash: metadata.license_hash.map(hex::encode),
+    }
+}
+
 #[derive(Debug, Default)]
 pub struct ExtractedNftData {
     pub name: Option<String>,
@@ -239,3 +288,29 @@ pub fn rust_coin(coin: CoinJson) -> Result<Coin> {
             .ok_or(Error::InvalidCoinAmount(coin.amount.to_string()))?,
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_new_address() {
+        let p2_puzzle_hash = Bytes32::new([1; 32]);
+
+        assert!(is_new_address(p2_puzzle_hash, false, false));
+        assert!(!is_new_address(p2_puzzle_hash, true, false));
+        assert!(!is_new_address(p2_puzzle_hash, false, true));
+
+        assert!(!is_new_address(BURN_PUZZLE_HASH, false, false));
+        assert!(!is_new_address(
+            SINGLETON_LAUNCHER_HASH.into(),
+            false,
+            false
+        ));
+        assert!(!is_new_address(
+            SETTLEMENT_PAYMENT_HASH.into(),
+            false,
+            false
+        ));
+    }
+}
@@ -20,6 +20,17 @@ pub struct OfferExpiration {
     pub coins: IndexMap<Bytes32, StatusCoinType>,
 }
 
+impl OfferExpiration {
+    /// Whether the offer can no longer be taken at the given peak height or time.
+    pub fn is_expired(&self, peak_height: Option<u32>, now: u64) -> bool {
+        self.expiration_height
+            .is_some_and(|height| peak_height.is_some_and(|peak_height| peak_height >= height))
+            || self
+                .expiration_timestamp
+                .is_some_and(|timestamp| now >= timestamp)
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum StatusCoinType {
     Settle,
@@ -195,3 +206,29 @@ fn parse_message_flags(mode: u8, side: MessageSide) -> MessageFlags {
 
     flags
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_offer_expired() {
+        let expiration = |expiration_height, expiration_timestamp| OfferExpiration {
+            expiration_height,
+            expiration_timestamp,
+            coins: IndexMap::new(),
+        };
+
+        assert!(!expiration(None, None).is_expired(Some(100), 1000));
+
+        assert!(!expiration(Some(100), None).is_expired(Some(99), 1000));
+        assert!(expiration(Some(100), None).is_expired(Some(100), 1000));
+        assert!(!expiration(Some(100), None).is_expired(None, 1000));
+
+        assert!(!expiration(None, Some(1000)).is_expired(Some(100), 999));
+        assert!(expiration(None, Some(1000)).is_expired(None, 1000));
+
+        assert!(expiration(Some(200), Some(1000)).is_expired(Some(100), 1000));
+        assert!(expiration(Some(100), Some(2000)).is_expired(Some(100), 1000));
+    }
+}
@@ -0,0 +1,27 @@
+/*
+ * An address book of external contacts. Each contact can have any number of
+ * addresses (stored as puzzle hashes) and optionally be associated with a DID.
+ */
+CREATE TABLE contacts (
+  id INTEGER NOT NULL PRIMARY KEY,
+  name TEXT NOT NULL,
+  notes TEXT,
+  did_hash BLOB,
+  created_timestamp INTEGER NOT NULL DEFAULT (unixepoch())
+);
+
+CREATE TABLE contact_addresses (
+  id INTEGER NOT NULL PRIMARY KEY,
+  contact_id INTEGER NOT NULL,
+  puzzle_hash BLOB NOT NULL,
+  label TEXT,
+  FOREIGN KEY (contact_id) REFERENCES contacts(id) ON DELETE CASCADE,
+  UNIQUE(contact_id, puzzle_hash)
+);
+
+CREATE INDEX idx_contact_addresses_contact_id ON contact_addresses(contact_id);
+CREATE INDEX idx_contact_addresses_puzzle_hash ON contact_addresses(puzzle_hash);
+CREATE INDEX idx_contacts_name ON contacts(name ASC);
+
+-- used to determine whether an address has been sent to before
+CREATE INDEX idx_coins_puzzle_hash ON coins(puzzle_hash);
@@ -0,0 +1,45 @@
+/*
+ * Recurring payments that are submitted by the wallet on a schedule while it's synced.
+ * The schedule is a five field cron expression (minute, hour, day of month, month, and
+ * day of week) which is evaluated in UTC. The fee is paid on each run.
+ */
+CREATE TABLE scheduled_payments (
+  id INTEGER NOT NULL PRIMARY KEY,
+  name TEXT NOT NULL,
+  schedule TEXT NOT NULL,
+  fee BLOB NOT NULL,
+  is_paused BOOLEAN NOT NULL DEFAULT FALSE,
+  next_run_timestamp INTEGER NOT NULL,
+  created_timestamp INTEGER NOT NULL DEFAULT (unixepoch())
+);
+
+/*
+ * The payments made on each run, in the same shape as a multi send. The asset hash is
+ * NULL for XCH, and memos are a serialized list of byte strings.
+ */
+CREATE TABLE scheduled_payment_recipients (
+  id INTEGER NOT NULL PRIMARY KEY,
+  scheduled_payment_id INTEGER NOT NULL,
+  asset_hash BLOB,
+  puzzle_hash BLOB NOT NULL,
+  amount BLOB NOT NULL,
+  memos BLOB NOT NULL,
+  FOREIGN KEY (scheduled_payment_id) REFERENCES scheduled_payments(id) ON DELETE CASCADE
+);
+
+/*
+ * The outcome of each run, which either has the id of the submitted transaction or the
+ * reason it couldn't be created.
+ */
+CREATE TABLE scheduled_payment_runs (
+  id INTEGER NOT NULL PRIMARY KEY,
+  scheduled_payment_id INTEGER NOT NULL,
+  transaction_id BLOB,
+  error TEXT,
+  run_timestamp INTEGER NOT NULL DEFAULT (unixepoch()),
+  FOREIGN KEY (scheduled_payment_id) REFERENCES scheduled_payments(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_scheduled_payments_next_run ON scheduled_payments(is_paused, next_run_timestamp);
+CREATE INDEX idx_scheduled_payment_recipients_payment ON scheduled_payment_recipients(scheduled_payment_id);
+CREATE INDEX idx_scheduled_payment_runs_payment ON scheduled_payment_runs(scheduled_payment_id, run_timestamp DESC);
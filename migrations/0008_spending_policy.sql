@@ -0,0 +1,47 @@
+/*
+ * Rules that every transaction must satisfy before the wallet will sign it. There is at
+ * most one row, which holds the rules that aren't specific to an asset.
+ */
+CREATE TABLE spending_policy (
+  id INTEGER NOT NULL PRIMARY KEY CHECK (id = 1),
+  max_fee BLOB,
+  is_nft_transfer_blocked BOOLEAN NOT NULL,
+  is_did_transfer_blocked BOOLEAN NOT NULL
+);
+
+/*
+ * Outflow limits for individual assets. The asset hash matches the assets table, so XCH
+ * is represented by 32 zero bytes.
+ */
+CREATE TABLE spending_limits (
+  id INTEGER NOT NULL PRIMARY KEY,
+  asset_hash BLOB NOT NULL UNIQUE,
+  max_per_transaction BLOB,
+  max_per_day BLOB
+);
+
+/*
+ * If there are any allowed destinations, coins can only be sent to these puzzle hashes
+ * (other than back to the wallet itself).
+ */
+CREATE TABLE allowed_destinations (
+  id INTEGER NOT NULL PRIMARY KEY,
+  puzzle_hash BLOB NOT NULL UNIQUE
+);
+
+/*
+ * The amount of each asset that has left the wallet in signed transactions, which is
+ * used to enforce the daily limits. They're recorded when the transaction is signed, and
+ * removed again if it couldn't be submitted or was rejected by the mempool.
+ */
+CREATE TABLE policy_spends (
+  id INTEGER NOT NULL PRIMARY KEY,
+  transaction_hash BLOB NOT NULL,
+  asset_hash BLOB NOT NULL,
+  amount BLOB NOT NULL,
+  is_submitted BOOLEAN NOT NULL DEFAULT FALSE,
+  spent_timestamp INTEGER NOT NULL DEFAULT (unixepoch())
+);
+
+CREATE INDEX idx_policy_spends_asset_timestamp ON policy_spends(asset_hash, spent_timestamp);
+CREATE INDEX idx_policy_spends_transaction ON policy_spends(transaction_hash);
@@ -0,0 +1,53 @@
+/*
+ * Large lists of payments which are split into multiple transactions, each of which stays
+ * below the maximum cost, and submitted one after another. The fee is paid on each of the
+ * transactions. A payout is paused along with the reason if a transaction can't be created
+ * or fails to make it on-chain.
+ */
+CREATE TABLE payouts (
+  id INTEGER NOT NULL PRIMARY KEY,
+  name TEXT NOT NULL,
+  fee BLOB NOT NULL,
+  max_cost INTEGER NOT NULL,
+  is_paused BOOLEAN NOT NULL DEFAULT FALSE,
+  error TEXT,
+  created_timestamp INTEGER NOT NULL DEFAULT (unixepoch())
+);
+
+/*
+ * Each transaction that has been submitted for a payout. The output coin is the one created
+ * for the first payment of the batch, which only exists on-chain if the transaction was
+ * confirmed, rather than its inputs being spent by a conflicting transaction.
+ */
+CREATE TABLE payout_batches (
+  id INTEGER NOT NULL PRIMARY KEY,
+  payout_id INTEGER NOT NULL,
+  transaction_id BLOB NOT NULL,
+  output_coin_hash BLOB NOT NULL,
+  is_confirmed BOOLEAN NOT NULL DEFAULT FALSE,
+  is_failed BOOLEAN NOT NULL DEFAULT FALSE,
+  submitted_timestamp INTEGER NOT NULL DEFAULT (unixepoch()),
+  FOREIGN KEY (payout_id) REFERENCES payouts(id) ON DELETE CASCADE
+);
+
+/*
+ * The individual payments in the order they were provided. Payments without a batch are yet
+ * to be submitted, and are released from their batch if it fails. The asset hash is NULL for
+ * XCH, and memos are a serialized list of byte strings.
+ */
+CREATE TABLE payout_payments (
+  id INTEGER NOT NULL PRIMARY KEY,
+  payout_id INTEGER NOT NULL,
+  row_index INTEGER NOT NULL,
+  asset_hash BLOB,
+  puzzle_hash BLOB NOT NULL,
+  amount BLOB NOT NULL,
+  memos BLOB NOT NULL,
+  batch_id INTEGER,
+  FOREIGN KEY (payout_id) REFERENCES payouts(id) ON DELETE CASCADE,
+  FOREIGN KEY (batch_id) REFERENCES payout_batches(id) ON DELETE SET NULL,
+  UNIQUE(payout_id, row_index)
+);
+
+CREATE INDEX idx_payout_batches_payout ON payout_batches(payout_id);
+CREATE INDEX idx_payout_payments_batch ON payout_payments(batch_id);
@@ -0,0 +1,31 @@
+/*
+ * Transactions submitted by this wallet, which are kept after they are confirmed so that the
+ * fee and the payments made to other wallets can be included in the transaction history. The
+ * input coin is one of the coins spent by the transaction, which links it to the block it was
+ * confirmed in.
+ */
+CREATE TABLE submitted_transactions (
+  id INTEGER NOT NULL PRIMARY KEY,
+  hash BLOB NOT NULL UNIQUE,
+  input_coin_hash BLOB NOT NULL,
+  fee BLOB NOT NULL,
+  submitted_timestamp INTEGER NOT NULL DEFAULT (unixepoch())
+);
+
+/*
+ * Coins created by a submitted transaction for other wallets, since they aren't synced. The
+ * asset hash matches the assets table, and the memos are a serialized list of byte strings
+ * without the hint.
+ */
+CREATE TABLE submitted_payments (
+  id INTEGER NOT NULL PRIMARY KEY,
+  submitted_transaction_id INTEGER NOT NULL,
+  asset_hash BLOB NOT NULL,
+  p2_puzzle_hash BLOB NOT NULL,
+  amount BLOB NOT NULL,
+  memos BLOB NOT NULL,
+  FOREIGN KEY (submitted_transaction_id) REFERENCES submitted_transactions(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_submitted_transactions_input ON submitted_transactions(input_coin_hash);
+CREATE INDEX idx_submitted_payments_transaction ON submitted_payments(submitted_transaction_id);
@@ -0,0 +1,25 @@
+/*
+ * Offers which were created together, such as the rungs of a price ladder, so that they can
+ * be cancelled or re-priced as a whole. The asset hashes are NULL for XCH.
+ */
+CREATE TABLE offer_groups (
+  id INTEGER NOT NULL PRIMARY KEY,
+  offered_asset_hash BLOB,
+  requested_asset_hash BLOB,
+  created_timestamp INTEGER NOT NULL DEFAULT (unixepoch())
+);
+
+/*
+ * The offers in each group, along with the price they were made at. The price is the amount
+ * of the requested asset per unit of the offered asset, as a decimal string.
+ */
+CREATE TABLE offer_group_offers (
+  id INTEGER NOT NULL PRIMARY KEY,
+  offer_group_id INTEGER NOT NULL,
+  offer_id INTEGER NOT NULL UNIQUE,
+  price TEXT NOT NULL,
+  FOREIGN KEY (offer_group_id) REFERENCES offer_groups(id) ON DELETE CASCADE,
+  FOREIGN KEY (offer_id) REFERENCES offers(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_offer_group_offers_group ON offer_group_offers(offer_group_id);
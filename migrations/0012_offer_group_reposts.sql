@@ -0,0 +1,21 @@
+/*
+ * The policy for replacing offers in a group once they are taken. The replacement offers the
+ * proceeds back the other way, priced the spread below the taken offer if it sold the group's
+ * offered asset, or the spread above it if it bought it back. The stop price is the lowest price
+ * a replacement can be made at, and the max inventory is the most of the offered asset that the
+ * group's active offers can hold at once. Reposting is turned off when either limit is reached
+ * or a replacement can't be made, and the reason is kept until the policy is set again.
+ */
+ALTER TABLE offer_groups ADD COLUMN auto_repost BOOLEAN NOT NULL DEFAULT FALSE;
+ALTER TABLE offer_groups ADD COLUMN price_spread TEXT NOT NULL DEFAULT '0';
+ALTER TABLE offer_groups ADD COLUMN stop_price TEXT;
+ALTER TABLE offer_groups ADD COLUMN max_inventory BLOB;
+ALTER TABLE offer_groups ADD COLUMN stop_reason TEXT;
+
+/*
+ * Reversed offers trade the group's requested asset for its offered asset. Once an offer has
+ * been taken and handled by the repost policy it's marked as reposted, whether or not a
+ * replacement was made.
+ */
+ALTER TABLE offer_group_offers ADD COLUMN is_reversed BOOLEAN NOT NULL DEFAULT FALSE;
+ALTER TABLE offer_group_offers ADD COLUMN is_reposted BOOLEAN NOT NULL DEFAULT FALSE;
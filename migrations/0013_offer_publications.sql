@@ -0,0 +1,13 @@
+/*
+ * The exchanges each offer has been published to, and the code it can be fetched by from each
+ * of them. Publishing an offer to the same exchange again replaces the code.
+ */
+CREATE TABLE offer_publications (
+  id INTEGER NOT NULL PRIMARY KEY,
+  offer_id INTEGER NOT NULL,
+  exchange TEXT NOT NULL,
+  code TEXT NOT NULL,
+  published_timestamp INTEGER NOT NULL DEFAULT (unixepoch()),
+  FOREIGN KEY (offer_id) REFERENCES offers(id) ON DELETE CASCADE,
+  UNIQUE(offer_id, exchange)
+);
@@ -0,0 +1,38 @@
+/*
+ * Offers which have been filled, both those made by the wallet and those it took. One of the
+ * coins created when the offer settles is kept so that the fill can be found, and the fill
+ * height is NULL until it has been, and the fill timestamp comes from the block at that height.
+ * The transaction is only known for offers that were taken. A taker trade which hasn't been
+ * filled is replaced if the offer is taken again, and removed once its transaction is no longer
+ * pending.
+ */
+CREATE TABLE trades (
+  id INTEGER NOT NULL PRIMARY KEY,
+  offer_hash BLOB NOT NULL,
+  is_maker BOOLEAN NOT NULL,
+  settlement_coin_hash BLOB NOT NULL,
+  transaction_hash BLOB,
+  fee BLOB NOT NULL,
+  fill_height INTEGER,
+  inserted_timestamp INTEGER NOT NULL DEFAULT (unixepoch()),
+  UNIQUE(offer_hash, is_maker)
+);
+
+/*
+ * The amounts of each asset given and received in a trade, as seen by the counterparty. The
+ * royalty is paid on top of the amount, by the side which gives the asset.
+ */
+CREATE TABLE trade_assets (
+  id INTEGER NOT NULL PRIMARY KEY,
+  trade_id INTEGER NOT NULL,
+  asset_id INTEGER NOT NULL,
+  is_received BOOLEAN NOT NULL,
+  amount BLOB NOT NULL,
+  royalty BLOB NOT NULL,
+  FOREIGN KEY (trade_id) REFERENCES trades(id) ON DELETE CASCADE,
+  FOREIGN KEY (asset_id) REFERENCES assets(id) ON DELETE CASCADE,
+  UNIQUE(trade_id, asset_id, is_received)
+);
+
+CREATE INDEX idx_trades_fill_height ON trades(fill_height);
+CREATE INDEX idx_trade_assets_trade_id ON trade_assets(trade_id);
@@ -0,0 +1,17 @@
+/*
+ * Offers become invalid when a coin locked in them is spent by another transaction, such as one
+ * made from another device with the same key, and the reason is kept for display. This adds an
+ * offer status:
+ *
+ * Invalid = 5
+ *
+ * Offers with auto recreate turned on are made again from other coins once they become invalid,
+ * and are linked to the offer which replaced them. If the offer can't be made again, auto
+ * recreate is turned off and the error is kept instead.
+ */
+ALTER TABLE offers ADD COLUMN invalid_reason TEXT;
+ALTER TABLE offers ADD COLUMN auto_recreate BOOLEAN NOT NULL DEFAULT FALSE;
+ALTER TABLE offers ADD COLUMN recreated_offer_id INTEGER REFERENCES offers(id) ON DELETE SET NULL;
+ALTER TABLE offers ADD COLUMN recreate_error TEXT;
+
+CREATE INDEX idx_coins_parent_coin_hash ON coins(parent_coin_hash);
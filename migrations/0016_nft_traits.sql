@@ -0,0 +1,53 @@
+/*
+ * The CHIP-0007 attributes of NFTs, parsed from their off-chain metadata. Values are kept as text
+ * so they can be compared for equality, and numbers are also kept as a real so they can be
+ * filtered by range.
+ */
+CREATE TABLE nft_traits (
+  id INTEGER NOT NULL PRIMARY KEY,
+  asset_id INTEGER NOT NULL,
+  trait_type TEXT NOT NULL,
+  value TEXT NOT NULL,
+  numeric_value REAL,
+  FOREIGN KEY (asset_id) REFERENCES assets(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_nft_traits_asset_id ON nft_traits(asset_id);
+CREATE INDEX idx_nft_traits_value ON nft_traits(trait_type, value);
+CREATE INDEX idx_nft_traits_numeric_value ON nft_traits(trait_type, numeric_value);
+
+/*
+ * Metadata which has already been downloaded isn't fetched again, so the traits of existing NFTs
+ * are filled in from it here.
+ */
+WITH attributes AS (
+  SELECT
+    nfts.asset_id,
+    CASE WHEN item.type = 'object' THEN item.value ELSE '{}' END AS attribute
+  FROM nfts
+  INNER JOIN files ON files.hash = nfts.metadata_hash
+  INNER JOIN json_each(
+    CASE
+      WHEN NOT json_valid(CAST(files.data AS TEXT)) THEN '[]'
+      WHEN json_type(CAST(files.data AS TEXT), '$.attributes') = 'array'
+      THEN json_extract(CAST(files.data AS TEXT), '$.attributes')
+      ELSE '[]'
+    END
+  ) AS item
+)
+INSERT INTO nft_traits (asset_id, trait_type, value, numeric_value)
+SELECT
+  asset_id,
+  CAST(json_extract(attribute, '$.trait_type') AS TEXT),
+  CASE json_type(attribute, '$.value')
+    WHEN 'true' THEN 'true'
+    WHEN 'false' THEN 'false'
+    ELSE CAST(json_extract(attribute, '$.value') AS TEXT)
+  END,
+  CASE
+    WHEN json_type(attribute, '$.value') IN ('integer', 'real')
+    THEN CAST(json_extract(attribute, '$.value') AS REAL)
+  END
+FROM attributes
+WHERE json_type(attribute, '$.trait_type') IN ('text', 'integer', 'real')
+  AND json_type(attribute, '$.value') IN ('text', 'integer', 'real', 'true', 'false');
@@ -0,0 +1,203 @@
+/*
+ * A full-text index of the things which can be searched for in the wallet. Each row is kept in
+ * sync with the row it was made from by the triggers below, and has a rowid made from the id of
+ * that row and its kind, so that it can be found again:
+ *
+ * Token = 0
+ * NFT = 1
+ * DID = 2
+ * Option = 3
+ * Collection = 4
+ * Offer = 5
+ * Transaction = 6
+ * Contact = 7
+ *
+ * Hashes are indexed as lowercase hex, and addresses as the hex of their puzzle hash. Memos are
+ * indexed when the payments of a submitted transaction are inserted, since they are serialized.
+ * Contacts have no hash, so they're found again by their rowid.
+ * Only transactions submitted by the wallet are indexed, since the memos of incoming coins and
+ * the addresses they were sent from aren't stored.
+ */
+CREATE VIRTUAL TABLE search_index USING fts5(
+  kind UNINDEXED,
+  hash UNINDEXED,
+  name,
+  ticker,
+  description,
+  traits,
+  identifiers,
+  memos,
+  prefix = '2 3',
+  tokenize = 'unicode61 remove_diacritics 2'
+);
+
+CREATE TRIGGER trigger_search_index_insert_asset
+    AFTER INSERT ON assets
+BEGIN
+    INSERT OR REPLACE INTO search_index (rowid, kind, hash, name, ticker, description, identifiers)
+    VALUES (NEW.id * 8 + NEW.kind, NEW.kind, NEW.hash, NEW.name, NEW.ticker, NEW.description, lower(hex(NEW.hash)));
+END;
+
+CREATE TRIGGER trigger_search_index_update_asset
+    AFTER UPDATE OF name, ticker, description ON assets
+BEGIN
+    UPDATE search_index SET name = NEW.name, ticker = NEW.ticker, description = NEW.description
+    WHERE rowid = NEW.id * 8 + NEW.kind;
+END;
+
+CREATE TRIGGER trigger_search_index_delete_asset
+    AFTER DELETE ON assets
+BEGIN
+    DELETE FROM search_index WHERE rowid = OLD.id * 8 + OLD.kind;
+END;
+
+CREATE TRIGGER trigger_search_index_insert_nft_trait
+    AFTER INSERT ON nft_traits
+BEGIN
+    UPDATE search_index SET traits = ltrim(coalesce(traits, '') || ' ' || NEW.trait_type || ' ' || NEW.value)
+    WHERE rowid = NEW.asset_id * 8 + 1;
+END;
+
+CREATE TRIGGER trigger_search_index_delete_nft_trait
+    AFTER DELETE ON nft_traits
+BEGIN
+    UPDATE search_index SET traits = (
+        SELECT group_concat(trait_type || ' ' || value, ' ') FROM nft_traits WHERE asset_id = OLD.asset_id
+    )
+    WHERE rowid = OLD.asset_id * 8 + 1;
+END;
+
+CREATE TRIGGER trigger_search_index_insert_collection
+    AFTER INSERT ON collections
+    WHEN NEW.id != 0
+BEGIN
+    INSERT OR REPLACE INTO search_index (rowid, kind, hash, name, description, identifiers)
+    VALUES (NEW.id * 8 + 4, 4, NEW.hash, NEW.name, NEW.description, lower(hex(NEW.hash)));
+END;
+
+CREATE TRIGGER trigger_search_index_update_collection
+    AFTER UPDATE OF name, description ON collections
+BEGIN
+    UPDATE search_index SET name = NEW.name, description = NEW.description
+    WHERE rowid = NEW.id * 8 + 4;
+END;
+
+CREATE TRIGGER trigger_search_index_delete_collection
+    AFTER DELETE ON collections
+BEGIN
+    DELETE FROM search_index WHERE rowid = OLD.id * 8 + 4;
+END;
+
+CREATE TRIGGER trigger_search_index_insert_offer
+    AFTER INSERT ON offers
+BEGIN
+    INSERT OR REPLACE INTO search_index (rowid, kind, hash, identifiers)
+    VALUES (NEW.id * 8 + 5, 5, NEW.hash, lower(hex(NEW.hash)));
+END;
+
+CREATE TRIGGER trigger_search_index_delete_offer
+    AFTER DELETE ON offers
+BEGIN
+    DELETE FROM search_index WHERE rowid = OLD.id * 8 + 5;
+END;
+
+CREATE TRIGGER trigger_search_index_insert_submitted_transaction
+    AFTER INSERT ON submitted_transactions
+BEGIN
+    INSERT OR REPLACE INTO search_index (rowid, kind, hash, identifiers)
+    VALUES (NEW.id * 8 + 6, 6, NEW.hash, lower(hex(NEW.hash)));
+END;
+
+CREATE TRIGGER trigger_search_index_delete_submitted_transaction
+    AFTER DELETE ON submitted_transactions
+BEGIN
+    DELETE FROM search_index WHERE rowid = OLD.id * 8 + 6;
+END;
+
+CREATE TRIGGER trigger_search_index_insert_submitted_payment
+    AFTER INSERT ON submitted_payments
+BEGIN
+    UPDATE search_index SET identifiers = identifiers || ' ' || lower(hex(NEW.p2_puzzle_hash))
+    WHERE rowid = NEW.submitted_transaction_id * 8 + 6;
+END;
+
+CREATE TRIGGER trigger_search_index_insert_contact
+    AFTER INSERT ON contacts
+BEGIN
+    INSERT OR REPLACE INTO search_index (rowid, kind, name, description, identifiers)
+    VALUES (NEW.id * 8 + 7, 7, NEW.name, NEW.notes, lower(hex(NEW.did_hash)));
+END;
+
+CREATE TRIGGER trigger_search_index_update_contact
+    AFTER UPDATE OF name, notes, did_hash ON contacts
+BEGIN
+    UPDATE search_index SET name = NEW.name, description = NEW.notes, identifiers = ltrim(
+        coalesce(lower(hex(NEW.did_hash)), '') || coalesce((
+            SELECT ' ' || group_concat(lower(hex(puzzle_hash)), ' ')
+            FROM contact_addresses WHERE contact_id = NEW.id
+        ), '')
+    )
+    WHERE rowid = NEW.id * 8 + 7;
+END;
+
+CREATE TRIGGER trigger_search_index_delete_contact
+    AFTER DELETE ON contacts
+BEGIN
+    DELETE FROM search_index WHERE rowid = OLD.id * 8 + 7;
+END;
+
+CREATE TRIGGER trigger_search_index_insert_contact_address
+    AFTER INSERT ON contact_addresses
+BEGIN
+    UPDATE search_index SET identifiers = ltrim(coalesce(identifiers, '') || ' ' || lower(hex(NEW.puzzle_hash)))
+    WHERE rowid = NEW.contact_id * 8 + 7;
+END;
+
+CREATE TRIGGER trigger_search_index_delete_contact_address
+    AFTER DELETE ON contact_addresses
+BEGIN
+    UPDATE search_index SET identifiers = ltrim(
+        coalesce((SELECT lower(hex(did_hash)) FROM contacts WHERE id = OLD.contact_id), '') || coalesce((
+            SELECT ' ' || group_concat(lower(hex(puzzle_hash)), ' ')
+            FROM contact_addresses WHERE contact_id = OLD.contact_id
+        ), '')
+    )
+    WHERE rowid = OLD.contact_id * 8 + 7;
+END;
+
+INSERT INTO search_index (rowid, kind, hash, name, ticker, description, traits, identifiers)
+SELECT
+  assets.id * 8 + assets.kind, assets.kind, assets.hash, assets.name, assets.ticker,
+  assets.description,
+  (SELECT group_concat(trait_type || ' ' || value, ' ') FROM nft_traits WHERE asset_id = assets.id),
+  lower(hex(assets.hash))
+FROM assets;
+
+INSERT INTO search_index (rowid, kind, hash, name, description, identifiers)
+SELECT id * 8 + 4, 4, hash, name, description, lower(hex(hash))
+FROM collections
+WHERE id != 0;
+
+INSERT INTO search_index (rowid, kind, hash, identifiers)
+SELECT id * 8 + 5, 5, hash, lower(hex(hash))
+FROM offers;
+
+INSERT INTO search_index (rowid, kind, hash, identifiers)
+SELECT
+  submitted_transactions.id * 8 + 6, 6, submitted_transactions.hash,
+  lower(hex(submitted_transactions.hash)) || coalesce((
+    SELECT ' ' || group_concat(lower(hex(p2_puzzle_hash)), ' ')
+    FROM submitted_payments
+    WHERE submitted_transaction_id = submitted_transactions.id
+  ), '')
+FROM submitted_transactions;
+
+INSERT INTO search_index (rowid, kind, name, description, identifiers)
+SELECT
+  contacts.id * 8 + 7, 7, contacts.name, contacts.notes,
+  ltrim(coalesce(lower(hex(contacts.did_hash)), '') || coalesce((
+    SELECT ' ' || group_concat(lower(hex(puzzle_hash)), ' ')
+    FROM contact_addresses
+    WHERE contact_id = contacts.id
+  ), ''))
+FROM contacts;
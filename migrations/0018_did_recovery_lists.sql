@@ -0,0 +1,12 @@
+/*
+ * The DIDs in recovery lists which were set by the wallet, in order, since only the tree hash of
+ * the list is kept on-chain. They are looked up by that hash, so the list of a DID is only known
+ * if it matches what's on-chain.
+ */
+CREATE TABLE did_recovery_lists (
+  id INTEGER NOT NULL PRIMARY KEY,
+  hash BLOB NOT NULL,
+  position INTEGER NOT NULL,
+  did_hash BLOB NOT NULL,
+  UNIQUE(hash, position)
+);
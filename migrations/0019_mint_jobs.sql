@@ -0,0 +1,63 @@
+/*
+ * Large NFT mints from a manifest, which are split into multiple transactions that each stay
+ * below the maximum cost and are minted from the same DID one after another. The fee is paid
+ * on each of the transactions. A mint job is paused along with the reason if a transaction
+ * can't be created or fails to make it on-chain.
+ */
+CREATE TABLE mint_jobs (
+  id INTEGER NOT NULL PRIMARY KEY,
+  name TEXT NOT NULL,
+  did_hash BLOB NOT NULL,
+  fee BLOB NOT NULL,
+  max_cost INTEGER NOT NULL,
+  is_paused BOOLEAN NOT NULL DEFAULT FALSE,
+  error TEXT,
+  created_timestamp INTEGER NOT NULL DEFAULT (unixepoch())
+);
+
+/*
+ * Each transaction that has been submitted for a mint job. The input coin is one of the coins
+ * spent by the transaction, which tells whether it was confirmed or failed once it's no
+ * longer in the mempool.
+ */
+CREATE TABLE mint_job_batches (
+  id INTEGER NOT NULL PRIMARY KEY,
+  mint_job_id INTEGER NOT NULL,
+  transaction_id BLOB NOT NULL,
+  input_coin_hash BLOB NOT NULL,
+  is_confirmed BOOLEAN NOT NULL DEFAULT FALSE,
+  is_failed BOOLEAN NOT NULL DEFAULT FALSE,
+  submitted_timestamp INTEGER NOT NULL DEFAULT (unixepoch()),
+  FOREIGN KEY (mint_job_id) REFERENCES mint_jobs(id) ON DELETE CASCADE
+);
+
+/*
+ * The individual NFTs in the order they were provided in the manifest. Items without a batch
+ * are yet to be minted, and are released from their batch if it fails, along with the launcher
+ * id they would have had. URI lists are serialized lists of strings, and the hashes have
+ * already been verified against the content of the URIs.
+ */
+CREATE TABLE mint_job_items (
+  id INTEGER NOT NULL PRIMARY KEY,
+  mint_job_id INTEGER NOT NULL,
+  row_index INTEGER NOT NULL,
+  edition_number INTEGER NOT NULL,
+  edition_total INTEGER NOT NULL,
+  data_uris BLOB NOT NULL,
+  data_hash BLOB,
+  metadata_uris BLOB NOT NULL,
+  metadata_hash BLOB,
+  license_uris BLOB NOT NULL,
+  license_hash BLOB,
+  p2_puzzle_hash BLOB,
+  royalty_puzzle_hash BLOB,
+  royalty_basis_points INTEGER NOT NULL,
+  batch_id INTEGER,
+  launcher_hash BLOB,
+  FOREIGN KEY (mint_job_id) REFERENCES mint_jobs(id) ON DELETE CASCADE,
+  FOREIGN KEY (batch_id) REFERENCES mint_job_batches(id) ON DELETE SET NULL,
+  UNIQUE(mint_job_id, row_index)
+);
+
+CREATE INDEX idx_mint_job_batches_job ON mint_job_batches(mint_job_id);
+CREATE INDEX idx_mint_job_items_batch ON mint_job_items(batch_id);
@@ -0,0 +1,7 @@
+/*
+ * The result of the last check of each URI, so that every URI of a file is verified on its own
+ * rather than only the one its data was downloaded from. The hash match is NULL until the URI
+ * has been fetched successfully, and the error is the reason the last attempt failed.
+ */
+ALTER TABLE file_uris ADD COLUMN is_hash_match BOOLEAN;
+ALTER TABLE file_uris ADD COLUMN error TEXT;
@@ -0,0 +1,22 @@
+/*
+ * Resized images now include a size-limited preview (kind = 2) alongside the icon and thumbnail,
+ * and record how they were made from the file, since not every file is an image.
+ *
+ * Image = 0
+ * Vector = 1
+ * Animation = 2
+ * CoverArt = 3
+ * Placeholder = 4
+ */
+ALTER TABLE resized_images ADD COLUMN source INTEGER NOT NULL DEFAULT 0;
+
+/*
+ * Files of types which previously had no thumbnail are flagged so that their resized images are
+ * created from the data that's already stored, rather than downloading them again.
+ */
+ALTER TABLE files ADD COLUMN needs_resized_images BOOLEAN NOT NULL DEFAULT FALSE;
+
+UPDATE files SET needs_resized_images = TRUE
+WHERE data IS NOT NULL
+AND NOT EXISTS (SELECT 1 FROM resized_images WHERE resized_images.file_id = files.id)
+AND (mime_type = 'image/svg+xml' OR mime_type = 'text/html' OR mime_type LIKE 'video/%' OR mime_type LIKE 'audio/%' OR mime_type LIKE 'model/%');
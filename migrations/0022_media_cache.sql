@@ -0,0 +1,35 @@
+/*
+ * How much space downloaded NFT files are allowed to take up, and whether new files are stored
+ * in a content-addressed directory instead of the database. There is at most one row, and the
+ * cache isn't limited without it.
+ */
+CREATE TABLE media_cache_policy (
+  id INTEGER NOT NULL PRIMARY KEY CHECK (id = 1),
+  max_bytes INTEGER,
+  is_external BOOLEAN NOT NULL
+);
+
+/*
+ * NFTs and collections whose files are never evicted from the cache.
+ *
+ * Nft = 0
+ * Collection = 1
+ */
+CREATE TABLE media_pins (
+  id INTEGER NOT NULL PRIMARY KEY,
+  kind INTEGER NOT NULL,
+  hash BLOB NOT NULL,
+  UNIQUE(kind, hash)
+);
+
+/*
+ * The size of the data is tracked separately, since it isn't in the database if it's stored
+ * externally. Files are evicted in order of when they were last accessed.
+ */
+ALTER TABLE files ADD COLUMN size INTEGER;
+ALTER TABLE files ADD COLUMN is_external BOOLEAN NOT NULL DEFAULT FALSE;
+ALTER TABLE files ADD COLUMN last_accessed_timestamp INTEGER;
+
+UPDATE files SET size = LENGTH(data), last_accessed_timestamp = unixepoch() WHERE data IS NOT NULL;
+
+CREATE INDEX idx_files_last_accessed_timestamp ON files(last_accessed_timestamp);
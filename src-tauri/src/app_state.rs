@@ -32,11 +32,19 @@ pub async fn initialize(app_handle: AppHandle, sage: &mut Sage) -> Result<()> {
                 // TODO: New event?
                 SyncEvent::CoinsUpdated
                 | SyncEvent::TransactionUpdated { .. }
-                | SyncEvent::OfferUpdated { .. } => ApiEvent::CoinState,
+                | SyncEvent::OfferUpdated { .. }
+                | SyncEvent::ScheduledPaymentRun { .. }
+                | SyncEvent::PayoutUpdated { .. } => ApiEvent::CoinState,
                 SyncEvent::PuzzleBatchSynced => ApiEvent::PuzzleBatchSynced,
                 SyncEvent::CatInfo => ApiEvent::CatInfo,
                 SyncEvent::DidInfo => ApiEvent::DidInfo,
                 SyncEvent::NftData => ApiEvent::NftData,
+                SyncEvent::OfferGroupUpdated { offer_group_id } => {
+                    ApiEvent::OfferGroupUpdated { offer_group_id }
+                }
+                SyncEvent::MintJobUpdated { mint_job_id } => {
+                    ApiEvent::MintJobUpdated { mint_job_id }
+                }
             };
             if app_handle.emit("sync-event", event).is_err() {
                 break;
@@ -63,8 +63,29 @@ pub fn run() {
             commands::get_version,
             commands::get_database_stats,
             commands::perform_database_maintenance,
+            commands::get_media_cache_stats,
+            commands::set_media_cache_policy,
+            commands::pin_nft_media,
+            commands::get_media_pins,
+            commands::clear_media_cache,
             commands::check_address,
             commands::get_derivations,
+            commands::get_contacts,
+            commands::get_contact,
+            commands::add_contact,
+            commands::update_contact,
+            commands::delete_contact,
+            commands::get_scheduled_payments,
+            commands::create_scheduled_payment,
+            commands::pause_scheduled_payment,
+            commands::delete_scheduled_payment,
+            commands::get_spending_policy,
+            commands::set_spending_policy,
+            commands::get_payouts,
+            commands::get_payout,
+            commands::create_payout,
+            commands::pause_payout,
+            commands::delete_payout,
             commands::get_are_coins_spendable,
             commands::get_spendable_coin_count,
             commands::get_coins_by_ids,
@@ -78,26 +99,46 @@ pub fn run() {
             commands::get_option,
             commands::get_nft_collections,
             commands::get_nft_collection,
+            commands::get_nft_collection_traits,
             commands::get_nfts,
             commands::get_nft,
             commands::get_nft_data,
             commands::get_nft_icon,
             commands::get_nft_thumbnail,
+            commands::get_nft_preview,
             commands::get_pending_transactions,
             commands::get_transaction,
             commands::get_transactions,
+            commands::export_transactions,
             commands::validate_address,
             commands::make_offer,
+            commands::preview_offer_royalties,
+            commands::make_nft_offers,
             commands::take_offer,
+            commands::take_offers,
             commands::combine_offers,
             commands::view_offer,
+            commands::preflight_offer,
+            commands::publish_offer,
+            commands::fetch_offer_by_code,
+            commands::search_exchange_offers,
+            commands::get_trades,
             commands::import_offer,
+            commands::import_offers,
+            commands::import_offers_from_url,
             commands::get_offers,
             commands::get_offers_for_asset,
             commands::get_offer,
             commands::delete_offer,
+            commands::set_offer_auto_recreate,
             commands::cancel_offer,
             commands::cancel_offers,
+            commands::make_offer_ladder,
+            commands::get_offer_groups,
+            commands::get_offer_group,
+            commands::cancel_offer_group,
+            commands::reprice_offer_group,
+            commands::set_offer_group_policy,
             commands::network_config,
             commands::set_discover_peers,
             commands::set_target_peers,
@@ -110,6 +151,7 @@ pub fn run() {
             commands::set_delta_sync,
             commands::set_delta_sync_override,
             commands::set_change_address,
+            commands::set_background_signing,
             commands::update_cat,
             commands::resync_cat,
             commands::update_did,
@@ -140,6 +182,23 @@ pub fn run() {
             commands::download_cni_offercode,
             commands::get_logs,
             commands::is_asset_owned,
+            commands::search,
+            commands::update_did_metadata,
+            commands::set_did_recovery_list,
+            commands::sign_message_by_did,
+            commands::verify_did_signature,
+            commands::get_did_recovery_info,
+            commands::create_did_attestation,
+            commands::recover_did,
+            commands::update_nft_metadata,
+            commands::get_mint_jobs,
+            commands::get_mint_job,
+            commands::create_mint_job,
+            commands::pause_mint_job,
+            commands::delete_mint_job,
+            commands::get_nft_integrity,
+            commands::get_nft_integrity_report,
+            commands::reverify_nfts,
         ])
         .events(collect_events![SyncEvent]);
 
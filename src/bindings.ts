@@ -125,12 +125,75 @@ async getDatabaseStats(req: GetDatabaseStats) : Promise<GetDatabaseStatsResponse
 async performDatabaseMaintenance(req: PerformDatabaseMaintenance) : Promise<PerformDatabaseMaintenanceResponse> {
     return await TAURI_INVOKE("perform_database_maintenance", { req });
 },
+async getMediaCacheStats(req: GetMediaCacheStats) : Promise<GetMediaCacheStatsResponse> {
+    return await TAURI_INVOKE("get_media_cache_stats", { req });
+},
+async setMediaCachePolicy(req: SetMediaCachePolicy) : Promise<SetMediaCachePolicyResponse> {
+    return await TAURI_INVOKE("set_media_cache_policy", { req });
+},
+async pinNftMedia(req: PinNftMedia) : Promise<PinNftMediaResponse> {
+    return await TAURI_INVOKE("pin_nft_media", { req });
+},
+async getMediaPins(req: GetMediaPins) : Promise<GetMediaPinsResponse> {
+    return await TAURI_INVOKE("get_media_pins", { req });
+},
+async clearMediaCache(req: ClearMediaCache) : Promise<ClearMediaCacheResponse> {
+    return await TAURI_INVOKE("clear_media_cache", { req });
+},
 async checkAddress(req: CheckAddress) : Promise<CheckAddressResponse> {
     return await TAURI_INVOKE("check_address", { req });
 },
 async getDerivations(req: GetDerivations) : Promise<GetDerivationsResponse> {
     return await TAURI_INVOKE("get_derivations", { req });
 },
+async getContacts(req: GetContacts) : Promise<GetContactsResponse> {
+    return await TAURI_INVOKE("get_contacts", { req });
+},
+async getContact(req: GetContact) : Promise<GetContactResponse> {
+    return await TAURI_INVOKE("get_contact", { req });
+},
+async addContact(req: AddContact) : Promise<AddContactResponse> {
+    return await TAURI_INVOKE("add_contact", { req });
+},
+async updateContact(req: UpdateContact) : Promise<UpdateContactResponse> {
+    return await TAURI_INVOKE("update_contact", { req });
+},
+async deleteContact(req: DeleteContact) : Promise<DeleteContactResponse> {
+    return await TAURI_INVOKE("delete_contact", { req });
+},
+async getScheduledPayments(req: GetScheduledPayments) : Promise<GetScheduledPaymentsResponse> {
+    return await TAURI_INVOKE("get_scheduled_payments", { req });
+},
+async createScheduledPayment(req: CreateScheduledPayment) : Promise<CreateScheduledPaymentResponse> {
+    return await TAURI_INVOKE("create_scheduled_payment", { req });
+},
+async pauseScheduledPayment(req: PauseScheduledPayment) : Promise<PauseScheduledPaymentResponse> {
+    return await TAURI_INVOKE("pause_scheduled_payment", { req });
+},
+async deleteScheduledPayment(req: DeleteScheduledPayment) : Promise<DeleteScheduledPaymentResponse> {
+    return await TAURI_INVOKE("delete_scheduled_payment", { req });
+},
+async getSpendingPolicy(req: GetSpendingPolicy) : Promise<GetSpendingPolicyResponse> {
+    return await TAURI_INVOKE("get_spending_policy", { req });
+},
+async setSpendingPolicy(req: SetSpendingPolicy) : Promise<SetSpendingPolicyResponse> {
+    return await TAURI_INVOKE("set_spending_policy", { req });
+},
+async getPayouts(req: GetPayouts) : Promise<GetPayoutsResponse> {
+    return await TAURI_INVOKE("get_payouts", { req });
+},
+async getPayout(req: GetPayout) : Promise<GetPayoutResponse> {
+    return await TAURI_INVOKE("get_payout", { req });
+},
+async createPayout(req: CreatePayout) : Promise<CreatePayoutResponse> {
+    return await TAURI_INVOKE("create_payout", { req });
+},
+async pausePayout(req: PausePayout) : Promise<PausePayoutResponse> {
+    return await TAURI_INVOKE("pause_payout", { req });
+},
+async deletePayout(req: DeletePayout) : Promise<DeletePayoutResponse> {
+    return await TAURI_INVOKE("delete_payout", { req });
+},
 async getAreCoinsSpendable(req: GetAreCoinsSpendable) : Promise<GetAreCoinsSpendableResponse> {
     return await TAURI_INVOKE("get_are_coins_spendable", { req });
 },
@@ -170,6 +233,9 @@ async getNftCollections(req: GetNftCollections) : Promise<GetNftCollectionsRespo
 async getNftCollection(req: GetNftCollection) : Promise<GetNftCollectionResponse> {
     return await TAURI_INVOKE("get_nft_collection", { req });
 },
+async getNftCollectionTraits(req: GetNftCollectionTraits) : Promise<GetNftCollectionTraitsResponse> {
+    return await TAURI_INVOKE("get_nft_collection_traits", { req });
+},
 async getNfts(req: GetNfts) : Promise<GetNftsResponse> {
     return await TAURI_INVOKE("get_nfts", { req });
 },
@@ -185,6 +251,9 @@ async getNftIcon(req: GetNftIcon) : Promise<GetNftIconResponse> {
 async getNftThumbnail(req: GetNftThumbnail) : Promise<GetNftThumbnailResponse> {
     return await TAURI_INVOKE("get_nft_thumbnail", { req });
 },
+async getNftPreview(req: GetNftPreview) : Promise<GetNftPreviewResponse> {
+    return await TAURI_INVOKE("get_nft_preview", { req });
+},
 async getPendingTransactions(req: GetPendingTransactions) : Promise<GetPendingTransactionsResponse> {
     return await TAURI_INVOKE("get_pending_transactions", { req });
 },
@@ -194,24 +263,57 @@ async getTransaction(req: GetTransaction) : Promise<GetTransactionResponse> {
 async getTransactions(req: GetTransactions) : Promise<GetTransactionsResponse> {
     return await TAURI_INVOKE("get_transactions", { req });
 },
+async exportTransactions(req: ExportTransactions) : Promise<ExportTransactionsResponse> {
+    return await TAURI_INVOKE("export_transactions", { req });
+},
 async validateAddress(address: string) : Promise<boolean> {
     return await TAURI_INVOKE("validate_address", { address });
 },
 async makeOffer(req: MakeOffer) : Promise<MakeOfferResponse> {
     return await TAURI_INVOKE("make_offer", { req });
 },
+async previewOfferRoyalties(req: PreviewOfferRoyalties) : Promise<PreviewOfferRoyaltiesResponse> {
+    return await TAURI_INVOKE("preview_offer_royalties", { req });
+},
+async makeNftOffers(req: MakeNftOffers) : Promise<MakeNftOffersResponse> {
+    return await TAURI_INVOKE("make_nft_offers", { req });
+},
 async takeOffer(req: TakeOffer) : Promise<TakeOfferResponse> {
     return await TAURI_INVOKE("take_offer", { req });
 },
+async takeOffers(req: TakeOffers) : Promise<TakeOffersResponse> {
+    return await TAURI_INVOKE("take_offers", { req });
+},
 async combineOffers(req: CombineOffers) : Promise<CombineOffersResponse> {
     return await TAURI_INVOKE("combine_offers", { req });
 },
 async viewOffer(req: ViewOffer) : Promise<ViewOfferResponse> {
     return await TAURI_INVOKE("view_offer", { req });
 },
+async preflightOffer(req: PreflightOffer) : Promise<PreflightOfferResponse> {
+    return await TAURI_INVOKE("preflight_offer", { req });
+},
+async publishOffer(req: PublishOffer) : Promise<PublishOfferResponse> {
+    return await TAURI_INVOKE("publish_offer", { req });
+},
+async fetchOfferByCode(req: FetchOfferByCode) : Promise<FetchOfferByCodeResponse> {
+    return await TAURI_INVOKE("fetch_offer_by_code", { req });
+},
+async searchExchangeOffers(req: SearchExchangeOffers) : Promise<SearchExchangeOffersResponse> {
+    return await TAURI_INVOKE("search_exchange_offers", { req });
+},
+async getTrades(req: GetTrades) : Promise<GetTradesResponse> {
+    return await TAURI_INVOKE("get_trades", { req });
+},
 async importOffer(req: ImportOffer) : Promise<ImportOfferResponse> {
     return await TAURI_INVOKE("import_offer", { req });
 },
+async importOffers(req: ImportOffers) : Promise<ImportOffersResponse> {
+    return await TAURI_INVOKE("import_offers", { req });
+},
+async importOffersFromUrl(req: ImportOffersFromUrl) : Promise<ImportOffersFromUrlResponse> {
+    return await TAURI_INVOKE("import_offers_from_url", { req });
+},
 async getOffers(req: GetOffers) : Promise<GetOffersResponse> {
     return await TAURI_INVOKE("get_offers", { req });
 },
@@ -224,12 +326,33 @@ async getOffer(req: GetOffer) : Promise<GetOfferResponse> {
 async deleteOffer(req: DeleteOffer) : Promise<DeleteOfferResponse> {
     return await TAURI_INVOKE("delete_offer", { req });
 },
+async setOfferAutoRecreate(req: SetOfferAutoRecreate) : Promise<SetOfferAutoRecreateResponse> {
+    return await TAURI_INVOKE("set_offer_auto_recreate", { req });
+},
 async cancelOffer(req: CancelOffer) : Promise<TransactionResponse> {
     return await TAURI_INVOKE("cancel_offer", { req });
 },
 async cancelOffers(req: CancelOffers) : Promise<TransactionResponse> {
     return await TAURI_INVOKE("cancel_offers", { req });
 },
+async makeOfferLadder(req: MakeOfferLadder) : Promise<MakeOfferLadderResponse> {
+    return await TAURI_INVOKE("make_offer_ladder", { req });
+},
+async getOfferGroups(req: GetOfferGroups) : Promise<GetOfferGroupsResponse> {
+    return await TAURI_INVOKE("get_offer_groups", { req });
+},
+async getOfferGroup(req: GetOfferGroup) : Promise<GetOfferGroupResponse> {
+    return await TAURI_INVOKE("get_offer_group", { req });
+},
+async cancelOfferGroup(req: CancelOfferGroup) : Promise<TransactionResponse> {
+    return await TAURI_INVOKE("cancel_offer_group", { req });
+},
+async repriceOfferGroup(req: RepriceOfferGroup) : Promise<MakeOfferLadderResponse> {
+    return await TAURI_INVOKE("reprice_offer_group", { req });
+},
+async setOfferGroupPolicy(req: SetOfferGroupPolicy) : Promise<SetOfferGroupPolicyResponse> {
+    return await TAURI_INVOKE("set_offer_group_policy", { req });
+},
 async networkConfig() : Promise<NetworkConfig> {
     return await TAURI_INVOKE("network_config");
 },
@@ -266,6 +389,9 @@ async setDeltaSyncOverride(req: SetDeltaSyncOverride) : Promise<EmptyResponse> {
 async setChangeAddress(req: SetChangeAddress) : Promise<EmptyResponse> {
     return await TAURI_INVOKE("set_change_address", { req });
 },
+async setBackgroundSigning(req: SetBackgroundSigning) : Promise<EmptyResponse> {
+    return await TAURI_INVOKE("set_background_signing", { req });
+},
 async updateCat(req: UpdateCat) : Promise<UpdateCatResponse> {
     return await TAURI_INVOKE("update_cat", { req });
 },
@@ -355,6 +481,57 @@ async getLogs() : Promise<LogFile[]> {
 },
 async isAssetOwned(req: IsAssetOwned) : Promise<IsAssetOwnedResponse> {
     return await TAURI_INVOKE("is_asset_owned", { req });
+},
+async search(req: Search) : Promise<SearchResponse> {
+    return await TAURI_INVOKE("search", { req });
+},
+async updateDidMetadata(req: UpdateDidMetadata) : Promise<TransactionResponse> {
+    return await TAURI_INVOKE("update_did_metadata", { req });
+},
+async setDidRecoveryList(req: SetDidRecoveryList) : Promise<TransactionResponse> {
+    return await TAURI_INVOKE("set_did_recovery_list", { req });
+},
+async signMessageByDid(req: SignMessageByDid) : Promise<SignMessageByDidResponse> {
+    return await TAURI_INVOKE("sign_message_by_did", { req });
+},
+async verifyDidSignature(req: VerifyDidSignature) : Promise<VerifyDidSignatureResponse> {
+    return await TAURI_INVOKE("verify_did_signature", { req });
+},
+async getDidRecoveryInfo(req: GetDidRecoveryInfo) : Promise<GetDidRecoveryInfoResponse> {
+    return await TAURI_INVOKE("get_did_recovery_info", { req });
+},
+async createDidAttestation(req: CreateDidAttestation) : Promise<CreateDidAttestationResponse> {
+    return await TAURI_INVOKE("create_did_attestation", { req });
+},
+async recoverDid(req: RecoverDid) : Promise<RecoverDidResponse> {
+    return await TAURI_INVOKE("recover_did", { req });
+},
+async updateNftMetadata(req: UpdateNftMetadata) : Promise<UpdateNftMetadataResponse> {
+    return await TAURI_INVOKE("update_nft_metadata", { req });
+},
+async getMintJobs(req: GetMintJobs) : Promise<GetMintJobsResponse> {
+    return await TAURI_INVOKE("get_mint_jobs", { req });
+},
+async getMintJob(req: GetMintJob) : Promise<GetMintJobResponse> {
+    return await TAURI_INVOKE("get_mint_job", { req });
+},
+async createMintJob(req: CreateMintJob) : Promise<CreateMintJobResponse> {
+    return await TAURI_INVOKE("create_mint_job", { req });
+},
+async pauseMintJob(req: PauseMintJob) : Promise<PauseMintJobResponse> {
+    return await TAURI_INVOKE("pause_mint_job", { req });
+},
+async deleteMintJob(req: DeleteMintJob) : Promise<DeleteMintJobResponse> {
+    return await TAURI_INVOKE("delete_mint_job", { req });
+},
+async getNftIntegrity(req: GetNftIntegrity) : Promise<GetNftIntegrityResponse> {
+    return await TAURI_INVOKE("get_nft_integrity", { req });
+},
+async getNftIntegrityReport(req: GetNftIntegrityReport) : Promise<GetNftIntegrityReportResponse> {
+    return await TAURI_INVOKE("get_nft_integrity_report", { req });
+},
+async reverifyNfts(req: ReverifyNfts) : Promise<ReverifyNftsResponse> {
+    return await TAURI_INVOKE("reverify_nfts", { req });
 }
 }
 
@@ -373,6 +550,34 @@ syncEvent: "sync-event"
 
 /** user-defined types **/
 
+/**
+ * Add a contact to the address book
+ */
+export type AddContact = { 
+/**
+ * Display name of the contact
+ */
+name: string; 
+/**
+ * Addresses belonging to the contact
+ */
+addresses: ContactAddress[]; 
+/**
+ * Optional notes
+ */
+notes?: string | null; 
+/**
+ * Optional DID ID associated with the contact
+ */
+did_id?: string | null }
+/**
+ * Response with the created contact
+ */
+export type AddContactResponse = { 
+/**
+ * Created contact
+ */
+contact: ContactRecord }
 /**
  * Add a URI to an NFT
  */
@@ -617,6 +822,22 @@ fee: Amount;
  * Whether to automatically submit the transaction
  */
 auto_submit?: boolean }
+/**
+ * Cancel an offer group
+ */
+export type CancelOfferGroup = { 
+/**
+ * Offer group ID
+ */
+id: number; 
+/**
+ * Transaction fee
+ */
+fee: Amount; 
+/**
+ * Whether to automatically submit the transaction
+ */
+auto_submit?: boolean }
 /**
  * Cancel multiple offers
  */
@@ -646,9 +867,25 @@ address: string }
  */
 export type CheckAddressResponse = { 
 /**
- * Whether the address is valid and belongs to this wallet
+ * Whether the address is valid for the current network and belongs to this wallet
  */
 valid: boolean }
+/**
+ * Clear the media cache
+ */
+export type ClearMediaCache = Record<string, never>
+/**
+ * Response after clearing the media cache
+ */
+export type ClearMediaCacheResponse = { 
+/**
+ * Number of files evicted
+ */
+evicted_files: number; 
+/**
+ * Number of bytes evicted
+ */
+evicted_bytes: number }
 /**
  * Coin structure
  */
@@ -718,6 +955,20 @@ export type CombineOffersResponse = {
  * Combined offer string
  */
 offer: string }
+/**
+ * Address to store for a contact
+ */
+export type ContactAddress = { 
+/**
+ * Address on the current network
+ */
+address: string; 
+/**
+ * Optional label such as "Deposit" or "Cold storage"
+ */
+label?: string | null }
+export type ContactAddressRecord = { address: string; label: string | null }
+export type ContactRecord = { id: number; name: string; notes: string | null; did_id: string | null; addresses: ContactAddressRecord[]; created_timestamp: number }
 /**
  * Create a new DID
  */
@@ -734,6 +985,146 @@ fee: Amount;
  * Whether to automatically submit the transaction
  */
 auto_submit?: boolean }
+/**
+ * Attest to the recovery of a DID
+ */
+export type CreateDidAttestation = { 
+/**
+ * DID ID of the wallet which attests to the recovery
+ */
+did_id: string; 
+/**
+ * Coin ID of the DID being recovered
+ */
+coin_id: string; 
+/**
+ * Inner puzzle hash of the DID once recovered
+ */
+new_inner_puzzle_hash: string; 
+/**
+ * Public key the DID will be recovered to
+ */
+public_key: string }
+/**
+ * Response with a signed attestation
+ */
+export type CreateDidAttestationResponse = { 
+/**
+ * Hex encoded spend bundle of the attestation
+ */
+attestation: string }
+/**
+ * Create a mint job
+ */
+export type CreateMintJob = { 
+/**
+ * Display name for the mint job
+ */
+name: string; 
+/**
+ * DID ID to mint the NFTs from
+ */
+did_id: string; 
+/**
+ * Format of the manifest
+ */
+format?: MintJobFormat; 
+/**
+ * The manifest data
+ */
+data: string; 
+/**
+ * Fee paid on each transaction
+ */
+fee: Amount; 
+/**
+ * Maximum cost of each transaction, defaults to the maximum cost allowed by the mempool
+ */
+max_cost?: number | null; 
+/**
+ * Whether to check the hashes in the manifest against the content of the URIs
+ */
+verify_hashes?: boolean }
+/**
+ * Response with the created mint job
+ */
+export type CreateMintJobResponse = { 
+/**
+ * The created mint job
+ */
+mint_job: MintJobRecord }
+/**
+ * Create a payout
+ */
+export type CreatePayout = { 
+/**
+ * Display name for the payout
+ */
+name: string; 
+/**
+ * Format of the payment data
+ */
+format?: PayoutFormat; 
+/**
+ * The payment data
+ */
+data: string; 
+/**
+ * Fee paid on each transaction
+ */
+fee: Amount; 
+/**
+ * Maximum cost of each transaction, defaults to the maximum cost allowed by the mempool
+ */
+max_cost?: number | null }
+/**
+ * Response with the created payout
+ */
+export type CreatePayoutResponse = { 
+/**
+ * The created payout
+ */
+payout: PayoutRecord }
+/**
+ * Create a scheduled payment
+ */
+export type CreateScheduledPayment = { 
+/**
+ * Display name for the scheduled payment
+ */
+name: string; 
+/**
+ * Cron expression (minute, hour, day of month, month, day of week)
+ */
+schedule: string; 
+/**
+ * Payments to make on each run
+ */
+payments: Payment[]; 
+/**
+ * Fee paid on each run
+ */
+fee: Amount }
+/**
+ * Response with the created scheduled payment
+ */
+export type CreateScheduledPaymentResponse = { 
+/**
+ * The created scheduled payment
+ */
+scheduled_payment: ScheduledPaymentRecord }
+/**
+ * Delete a contact
+ */
+export type DeleteContact = { 
+/**
+ * Contact ID to delete
+ */
+id: number }
+/**
+ * Response for contact deletion
+ */
+export type DeleteContactResponse = Record<string, never>
 /**
  * Delete a wallet database
  */
@@ -762,6 +1153,18 @@ fingerprint: number }
  * Response for key deletion
  */
 export type DeleteKeyResponse = Record<string, never>
+/**
+ * Delete a mint job
+ */
+export type DeleteMintJob = { 
+/**
+ * Mint job ID
+ */
+id: number }
+/**
+ * Response for deleting a mint job
+ */
+export type DeleteMintJobResponse = Record<string, never>
 /**
  * Delete an offer
  */
@@ -774,6 +1177,30 @@ offer_id: string }
  * Response for offer deletion
  */
 export type DeleteOfferResponse = Record<string, never>
+/**
+ * Delete a payout
+ */
+export type DeletePayout = { 
+/**
+ * Payout ID
+ */
+id: number }
+/**
+ * Response for deleting a payout
+ */
+export type DeletePayoutResponse = Record<string, never>
+/**
+ * Delete a scheduled payment
+ */
+export type DeleteScheduledPayment = { 
+/**
+ * Scheduled payment ID
+ */
+id: number }
+/**
+ * Response for deleting a scheduled payment
+ */
+export type DeleteScheduledPaymentResponse = Record<string, never>
 /**
  * Delete a theme NFT from the wallet
  */
@@ -784,10 +1211,27 @@ export type DeleteUserTheme = {
 nft_id: string }
 export type DeleteUserThemeResponse = Record<string, never>
 export type DerivationRecord = { index: number; public_key: string; address: string }
-export type DidRecord = { launcher_id: string; name: string | null; visible: boolean; coin_id: string; address: string; amount: Amount; recovery_hash: string | null; created_height: number | null }
+export type DidMetadataEntry = { key: string; value: string }
+export type DidRecord = { launcher_id: string; name: string | null; visible: boolean; coin_id: string; address: string; amount: Amount; recovery_hash: string | null; 
+/**
+ * The DIDs which can recover the DID, if the recovery list is known
+ */
+recovery_did_ids: string[] | null; num_verifications_required: number; metadata: DidMetadataEntry[]; created_height: number | null }
 export type EmptyResponse = Record<string, never>
 export type Error = { kind: ErrorKind; reason: string }
-export type ErrorKind = "wallet" | "api" | "not_found" | "unauthorized" | "internal" | "database_migration" | "nfc"
+export type ErrorKind = "wallet" | "api" | "not_found" | "unauthorized" | "internal" | "database_migration" | "nfc" | "policy"
+/**
+ * An offer listed on an exchange
+ */
+export type ExchangeOfferRecord = { 
+/**
+ * Code the offer can be fetched by from the exchange
+ */
+code: string; 
+/**
+ * Offer string
+ */
+offer: string }
 /**
  * Exercise options
  */
@@ -804,6 +1248,64 @@ fee: Amount;
  * Whether to automatically submit the transaction
  */
 auto_submit?: boolean }
+export type ExportFormat = "csv" | "json"
+/**
+ * Export the transaction history
+ */
+export type ExportTransactions = { 
+/**
+ * Format of the exported data
+ */
+format?: ExportFormat; 
+/**
+ * First block height to include
+ */
+start_height?: number | null; 
+/**
+ * Last block height to include
+ */
+end_height?: number | null; 
+/**
+ * Earliest block timestamp to include, in seconds since the UNIX epoch
+ */
+start_timestamp?: number | null; 
+/**
+ * Latest block timestamp to include, in seconds since the UNIX epoch
+ */
+end_timestamp?: number | null }
+/**
+ * Response with the exported transaction history
+ */
+export type ExportTransactionsResponse = { 
+/**
+ * The exported rows in the requested format
+ */
+data: string; 
+/**
+ * Number of rows exported
+ */
+count: number }
+/**
+ * Fetch an offer from an exchange
+ */
+export type FetchOfferByCode = { 
+/**
+ * Code of the offer
+ */
+code: string; 
+/**
+ * Name of the exchange, which defaults to the first offer code service
+ */
+exchange?: string | null }
+/**
+ * Response with the fetched offer
+ */
+export type FetchOfferByCodeResponse = { 
+/**
+ * Offer string
+ */
+offer: string }
+export type FileIntegrityRecord = { hash: string; status: IntegrityStatus; uris: UriIntegrityRecord[] }
 /**
  * Filter unlocked coins from a list
  */
@@ -968,6 +1470,34 @@ coins: CoinRecord[];
  * Total number of coins available
  */
 total: number }
+/**
+ * Get a specific contact
+ */
+export type GetContact = { 
+/**
+ * Contact ID
+ */
+id: number }
+/**
+ * Response with contact details
+ */
+export type GetContactResponse = { 
+/**
+ * Contact details
+ */
+contact: ContactRecord | null }
+/**
+ * List contacts in the address book
+ */
+export type GetContacts = Record<string, never>
+/**
+ * Response with address book contacts
+ */
+export type GetContactsResponse = { 
+/**
+ * List of contacts
+ */
+contacts: ContactRecord[] }
 /**
  * Retrieve database statistics
  */
@@ -1033,17 +1563,57 @@ derivations: DerivationRecord[];
  */
 total: number }
 /**
- * List all DIDs in the wallet
+ * Get the information needed to recover a DID
  */
-export type GetDids = Record<string, never>
+export type GetDidRecoveryInfo = { 
 /**
- * Response with DID list
+ * DID ID to recover
  */
-export type GetDidsResponse = { 
+did_id: string; 
 /**
- * List of DIDs
+ * Public key to recover the DID to, defaults to one of the wallet's keys
  */
-dids: DidRecord[] }
+public_key?: string | null }
+/**
+ * Response with the information needed to recover a DID
+ */
+export type GetDidRecoveryInfoResponse = { 
+/**
+ * Coin ID of the latest DID coin
+ */
+coin_id: string; 
+/**
+ * Inner puzzle hash of the DID once recovered
+ */
+new_inner_puzzle_hash: string; 
+/**
+ * Public key the DID will be recovered to
+ */
+public_key: string; 
+/**
+ * Hash of the recovery list, if there is one
+ */
+recovery_list_hash: string | null; 
+/**
+ * Recovery DIDs, if the recovery list was set by this wallet
+ */
+recovery_did_ids: string[] | null; 
+/**
+ * Number of recovery DIDs which must attest to a recovery
+ */
+num_verifications_required: number }
+/**
+ * List all DIDs in the wallet
+ */
+export type GetDids = Record<string, never>
+/**
+ * Response with DID list
+ */
+export type GetDidsResponse = { 
+/**
+ * List of DIDs
+ */
+dids: DidRecord[] }
 /**
  * Get a specific wallet key
  */
@@ -1072,6 +1642,95 @@ export type GetKeysResponse = {
  * List of wallet keys
  */
 keys: KeyInfo[] }
+export type GetMediaCacheStats = Record<string, never>
+/**
+ * Response with media cache statistics
+ */
+export type GetMediaCacheStatsResponse = { 
+/**
+ * Maximum size of the cache in bytes, or unlimited if not set
+ */
+max_bytes: number | null; 
+/**
+ * Whether new files are stored in a directory next to the database
+ */
+store_externally: boolean; 
+/**
+ * Number of files whose data is cached
+ */
+cached_files: number; 
+/**
+ * Total size of cached files in bytes
+ */
+cached_bytes: number; 
+/**
+ * Number of cached files stored outside of the database
+ */
+external_files: number; 
+/**
+ * Size of cached files stored outside of the database in bytes
+ */
+external_bytes: number; 
+/**
+ * Number of cached files belonging to pinned NFTs or collections
+ */
+pinned_files: number; 
+/**
+ * Size of pinned files in bytes
+ */
+pinned_bytes: number; 
+/**
+ * Size of icons, thumbnails and previews in bytes, which are never evicted
+ */
+resized_image_bytes: number }
+/**
+ * List pinned NFT media
+ */
+export type GetMediaPins = Record<string, never>
+/**
+ * Response with pinned NFT media
+ */
+export type GetMediaPinsResponse = { 
+/**
+ * Pinned NFT IDs
+ */
+nft_ids: string[]; 
+/**
+ * Pinned collection IDs
+ */
+collection_ids: string[] }
+/**
+ * Get a mint job
+ */
+export type GetMintJob = { 
+/**
+ * Mint job ID
+ */
+id: number }
+/**
+ * Response with a mint job and its items
+ */
+export type GetMintJobResponse = { 
+/**
+ * The mint job
+ */
+mint_job: MintJobRecord; 
+/**
+ * Items in the order they were provided
+ */
+items: MintJobItemRecord[] }
+/**
+ * List mint jobs
+ */
+export type GetMintJobs = Record<string, never>
+/**
+ * Response with mint jobs
+ */
+export type GetMintJobsResponse = { 
+/**
+ * List of mint jobs
+ */
+mint_jobs: MintJobRecord[] }
 /**
  * Get minter DIDs with pagination
  */
@@ -1140,6 +1799,26 @@ export type GetNftCollectionResponse = {
  * Collection if found
  */
 collection: NftCollectionRecord | null }
+/**
+ * Count the traits of owned NFTs in a collection
+ */
+export type GetNftCollectionTraits = { 
+/**
+ * Collection ID
+ */
+collection_id: string }
+/**
+ * Response with trait counts for an NFT collection
+ */
+export type GetNftCollectionTraitsResponse = { 
+/**
+ * Number of owned NFTs with each trait
+ */
+traits: NftTraitCount[]; 
+/**
+ * Total number of owned NFTs in the collection
+ */
+total: number }
 /**
  * List NFT collections
  */
@@ -1200,6 +1879,78 @@ export type GetNftIconResponse = {
  * Base64-encoded icon image
  */
 icon: string | null }
+/**
+ * Get the integrity of an NFT's files
+ */
+export type GetNftIntegrity = { 
+/**
+ * NFT ID
+ */
+nft_id: string }
+/**
+ * Get an integrity report for owned NFTs
+ */
+export type GetNftIntegrityReport = { 
+/**
+ * Only include NFTs in this collection
+ */
+collection_id?: string | null; 
+/**
+ * Whether to include NFTs that have been fully verified
+ */
+include_verified?: boolean; 
+/**
+ * Starting offset for pagination
+ */
+offset: number; 
+/**
+ * Number of NFTs to return
+ */
+limit: number }
+/**
+ * Response with an integrity report for owned NFTs
+ */
+export type GetNftIntegrityReportResponse = { 
+/**
+ * NFTs in the report
+ */
+nfts: NftIntegrityRecord[]; 
+/**
+ * Total number of NFTs in the report
+ */
+total: number; 
+/**
+ * Number of owned NFTs with each status, including verified ones
+ */
+summary: NftIntegritySummary }
+/**
+ * Response with the integrity of an NFT's files
+ */
+export type GetNftIntegrityResponse = { 
+/**
+ * Integrity of the NFT
+ */
+integrity: NftIntegrityRecord }
+/**
+ * Get NFT preview image
+ */
+export type GetNftPreview = { 
+/**
+ * NFT coin ID
+ */
+nft_id: string }
+/**
+ * Response with NFT preview
+ */
+export type GetNftPreviewResponse = { 
+/**
+ * Base64-encoded PNG preview image
+ */
+preview: string | null; 
+/**
+ * How the preview was made from the NFT data
+ */
+source: NftPreviewSource | null }
 /**
  * Response with NFT details
  */
@@ -1259,7 +2010,11 @@ sort_mode: NftSortMode;
 /**
  * Include hidden NFTs
  */
-include_hidden: boolean }
+include_hidden: boolean; 
+/**
+ * Only include NFTs which match these traits, where NFTs need one of the traits given for each trait type
+ */
+traits?: NftTraitFilter[] }
 /**
  * Response with NFTs list
  */
@@ -1280,6 +2035,38 @@ export type GetOffer = {
  * Offer ID
  */
 offer_id: string }
+/**
+ * Get an offer group
+ */
+export type GetOfferGroup = { 
+/**
+ * Offer group ID
+ */
+id: number }
+/**
+ * Response with an offer group and its offers
+ */
+export type GetOfferGroupResponse = { 
+/**
+ * The offer group
+ */
+group: OfferGroupRecord; 
+/**
+ * Offers in the order they were created
+ */
+offers: OfferGroupOfferRecord[] }
+/**
+ * List offer groups
+ */
+export type GetOfferGroups = Record<string, never>
+/**
+ * Response with offer groups
+ */
+export type GetOfferGroupsResponse = { 
+/**
+ * List of offer groups
+ */
+groups: OfferGroupRecord[] }
 /**
  * Response with offer details
  */
@@ -1372,6 +2159,38 @@ options: OptionRecord[];
  * Total number of options
  */
 total: number }
+/**
+ * Get a payout
+ */
+export type GetPayout = { 
+/**
+ * Payout ID
+ */
+id: number }
+/**
+ * Response with a payout and its payments
+ */
+export type GetPayoutResponse = { 
+/**
+ * The payout
+ */
+payout: PayoutRecord; 
+/**
+ * Payments in the order they were provided
+ */
+payments: PayoutPaymentRecord[] }
+/**
+ * List payouts
+ */
+export type GetPayouts = Record<string, never>
+/**
+ * Response with payouts
+ */
+export type GetPayoutsResponse = { 
+/**
+ * List of payouts
+ */
+payouts: PayoutRecord[] }
 /**
  * List all network peers
  */
@@ -1396,6 +2215,18 @@ export type GetPendingTransactionsResponse = {
  * List of pending transactions
  */
 transactions: PendingTransactionRecord[] }
+/**
+ * List scheduled payments
+ */
+export type GetScheduledPayments = Record<string, never>
+/**
+ * Response with scheduled payments
+ */
+export type GetScheduledPaymentsResponse = { 
+/**
+ * List of scheduled payments
+ */
+scheduled_payments: ScheduledPaymentRecord[] }
 /**
  * Get wallet secret key
  */
@@ -1428,6 +2259,18 @@ export type GetSpendableCoinCountResponse = {
  * Number of spendable coins
  */
 count: number }
+/**
+ * Get the spending policy
+ */
+export type GetSpendingPolicy = Record<string, never>
+/**
+ * Response with the spending policy
+ */
+export type GetSpendingPolicyResponse = { 
+/**
+ * The current spending policy
+ */
+policy: SpendingPolicy }
 /**
  * Get the current synchronization status
  */
@@ -1496,6 +2339,34 @@ export type GetTokenResponse = {
  * Token information if found
  */
 token: TokenRecord | null }
+/**
+ * List the trade history
+ */
+export type GetTrades = { 
+/**
+ * Only include trades of this asset (null for all trades)
+ */
+asset_id?: string | null; 
+/**
+ * Whether to include taken offers which haven't been filled yet
+ */
+include_unfilled?: boolean }
+/**
+ * Response with the trade history
+ */
+export type GetTradesResponse = { 
+/**
+ * Trades, the most recently filled first
+ */
+trades: TradeRecord[]; 
+/**
+ * Profit and loss of each traded asset
+ */
+assets: TradeAssetPnl[]; 
+/**
+ * Total fees paid for the filled trades
+ */
+fees_paid: Amount }
 /**
  * Get a specific transaction by height
  */
@@ -1638,6 +2509,71 @@ export type ImportOfferResponse = {
  * ID of the imported offer
  */
 offer_id: string }
+/**
+ * Result of importing a single offer
+ */
+export type ImportOfferResult = { 
+/**
+ * ID of the offer, if it could be decoded
+ */
+offer_id: string | null; 
+/**
+ * Whether the offer was imported
+ */
+status: ImportOfferStatus; 
+/**
+ * Why the offer is invalid or couldn't be verified
+ */
+error: string | null; 
+/**
+ * Whether the offered coins are unspent and the offer hasn't expired, if it was verified.
+ * This is left empty if its coins couldn't be fetched.
+ */
+live: boolean | null }
+/**
+ * Outcome of importing an offer
+ */
+export type ImportOfferStatus = "imported" | "duplicate" | "invalid"
+/**
+ * Import several offers at once
+ */
+export type ImportOffers = { 
+/**
+ * Offer strings to import
+ */
+offers: string[]; 
+/**
+ * Whether to check that the offered coins are unspent and the offers haven't expired
+ */
+verify?: boolean }
+/**
+ * Import offers from a URL
+ */
+export type ImportOffersFromUrl = { 
+/**
+ * HTTP(S), IPFS, IPNS or Arweave URL to download the offers from
+ */
+url: string; 
+/**
+ * Whether to check that the offered coins are unspent and the offers haven't expired
+ */
+verify?: boolean }
+/**
+ * Response with the result of importing each downloaded offer
+ */
+export type ImportOffersFromUrlResponse = { 
+/**
+ * Result for each offer, in the order they were listed
+ */
+results: ImportOfferResult[] }
+/**
+ * Response with the result of importing each offer
+ */
+export type ImportOffersResponse = { 
+/**
+ * Result for each offer, in the same order they were provided
+ */
+results: ImportOfferResult[] }
 /**
  * Increase the derivation index to generate more addresses
  */
@@ -1659,6 +2595,12 @@ index: number }
  */
 export type IncreaseDerivationIndexResponse = Record<string, never>
 export type InheritedNetwork = "mainnet" | "testnet11"
+/**
+ * Whether the content of an NFT file matches its on-chain hash. A file is partially verified if
+ * only some of its URIs match, and unhashed NFTs can't be verified at all. The variants are
+ * ordered from best to worst, so the status of an NFT is the worst status of its files.
+ */
+export type IntegrityStatus = "verified" | "pending" | "unhashed" | "partial" | "unavailable" | "mismatch"
 /**
  * Check if an asset is owned
  */
@@ -1699,8 +2641,12 @@ fee: Amount;
  * Whether to automatically submit the transaction
  */
 auto_submit?: boolean }
-export type KeyInfo = { name: string; fingerprint: number; public_key: string; kind: KeyKind; has_secrets: boolean; network_id: string; emoji: string | null }
+export type KeyInfo = { name: string; fingerprint: number; public_key: string; kind: KeyKind; has_secrets: boolean; network_id: string; emoji: string | null; background_signing: boolean }
 export type KeyKind = "bls"
+/**
+ * How the total size of an offer ladder is spread over its rungs
+ */
+export type LadderDistribution = "uniform" | "increasing" | "decreasing"
 /**
  * Lineage proof for CAT coins
  */
@@ -1739,19 +2685,23 @@ export type Logout = Record<string, never>
  */
 export type LogoutResponse = Record<string, never>
 /**
- * Create a new offer
+ * Create a separate offer for each of several NFTs
  */
-export type MakeOffer = { 
+export type MakeNftOffers = { 
 /**
- * Assets requested in the offer
+ * NFT IDs to offer
  */
-requested_assets: OfferAmount[]; 
+nft_ids?: string[]; 
 /**
- * Assets offered in exchange
+ * Collection ID to offer every owned NFT from
  */
-offered_assets: OfferAmount[]; 
+collection_id?: string | null; 
 /**
- * Transaction fee
+ * Assets requested for each NFT
+ */
+requested_assets: OfferAmount[]; 
+/**
+ * Transaction fee for each offer
  */
 fee: Amount; 
 /**
@@ -1763,35 +2713,154 @@ receive_address?: string | null;
  */
 expires_at_second?: number | null; 
 /**
- * Whether to automatically import the offer
+ * Optional block height at which the offers expire
  */
-auto_import?: boolean }
+expires_at_height?: number | null; 
 /**
- * Response with created offer
+ * Optional number of blocks from the current peak after which the offers expire
  */
-export type MakeOfferResponse = { 
+expires_in_blocks?: number | null; 
 /**
- * Offer string (bech32 encoded)
+ * Optional number of seconds from now after which the offers expire
  */
-offer: string; 
+expires_in_seconds?: number | null }
 /**
- * Offer ID
+ * Response with the created offers
  */
-offer_id: string }
+export type MakeNftOffersResponse = { 
 /**
- * Mint a new option
+ * Created offers, in the same order as the NFTs
  */
-export type MintOption = { 
+offers: NftOffer[] }
 /**
- * Expiration time in seconds
+ * Create a new offer
  */
-expiration_seconds: number; 
+export type MakeOffer = { 
 /**
- * Underlying asset
+ * Assets requested in the offer
  */
-underlying: OptionAsset; 
+requested_assets: OfferAmount[]; 
 /**
- * Strike price asset
+ * Assets offered in exchange
+ */
+offered_assets: OfferAmount[]; 
+/**
+ * Transaction fee
+ */
+fee: Amount; 
+/**
+ * Optional receive address
+ */
+receive_address?: string | null; 
+/**
+ * Optional expiration timestamp
+ */
+expires_at_second?: number | null; 
+/**
+ * Optional block height at which the offer expires
+ */
+expires_at_height?: number | null; 
+/**
+ * Optional number of blocks from the current peak after which the offer expires
+ */
+expires_in_blocks?: number | null; 
+/**
+ * Optional number of seconds from now after which the offer expires
+ */
+expires_in_seconds?: number | null; 
+/**
+ * Whether to automatically import the offer
+ */
+auto_import?: boolean }
+/**
+ * Create an offer ladder
+ */
+export type MakeOfferLadder = { 
+/**
+ * Asset ID being offered (null for XCH)
+ */
+offered_asset_id?: string | null; 
+/**
+ * Asset ID being requested (null for XCH)
+ */
+requested_asset_id?: string | null; 
+/**
+ * Price of the first rung
+ */
+min_price: string; 
+/**
+ * Price of the last rung
+ */
+max_price: string; 
+/**
+ * Price difference between rungs
+ */
+step?: string | null; 
+/**
+ * Number of rungs, spread evenly over the price range
+ */
+count?: number | null; 
+/**
+ * Total amount of the offered asset
+ */
+total_amount: Amount; 
+/**
+ * How the total amount is spread over the rungs
+ */
+distribution?: LadderDistribution; 
+/**
+ * Fee for the transaction which splits the offered asset
+ */
+fee: Amount; 
+/**
+ * Optional receive address
+ */
+receive_address?: string | null; 
+/**
+ * Optional expiration timestamp
+ */
+expires_at_second?: number | null }
+/**
+ * Response with the created offer group
+ */
+export type MakeOfferLadderResponse = { 
+/**
+ * Offer group ID
+ */
+group_id: number; 
+/**
+ * IDs of the offers, from the lowest price to the highest
+ */
+offer_ids: string[] }
+/**
+ * Response with created offer
+ */
+export type MakeOfferResponse = { 
+/**
+ * Offer string (bech32 encoded)
+ */
+offer: string; 
+/**
+ * Offer ID
+ */
+offer_id: string }
+export type MintJobFormat = "csv" | "json"
+export type MintJobItemRecord = { row_index: number; nft_id: string | null; address: string | null; edition_number: number; edition_total: number; data_uris: string[]; data_hash: string | null; metadata_uris: string[]; metadata_hash: string | null; license_uris: string[]; license_hash: string | null; royalty_address: string | null; royalty_ten_thousandths: number; transaction_id: string | null; is_confirmed: boolean }
+export type MintJobRecord = { id: number; name: string; did_id: string; fee: Amount; max_cost: number; is_paused: boolean; error: string | null; created_timestamp: number; item_count: number; submitted_count: number; confirmed_count: number }
+/**
+ * Mint a new option
+ */
+export type MintOption = { 
+/**
+ * Expiration time in seconds
+ */
+expiration_seconds: number; 
+/**
+ * Underlying asset
+ */
+underlying: OptionAsset; 
+/**
+ * Strike price asset
  */
 strike: OptionAsset; 
 /**
@@ -1824,6 +2893,33 @@ export type NetworkKind = "mainnet" | "testnet" | "unknown"
 export type NetworkList = { networks: Network[] }
 export type NftCollectionRecord = { collection_id: string; did_id: string; metadata_collection_id: string; visible: boolean; name: string | null; icon: string | null }
 export type NftData = { blob: string | null; mime_type: string | null; hash_matches: boolean; metadata_json: string | null; metadata_hash_matches: boolean }
+export type NftIntegrityRecord = { nft_id: string; name: string | null; status: IntegrityStatus; data: FileIntegrityRecord | null; metadata: FileIntegrityRecord | null; license: FileIntegrityRecord | null }
+/**
+ * The number of NFTs with each integrity status
+ */
+export type NftIntegritySummary = { verified: number; pending: number; unhashed: number; partial: number; unavailable: number; mismatch: number }
+export type NftMetadataDiff = { nft_id: string; old: NftMetadataJson; new: NftMetadataJson }
+export type NftMetadataJson = { edition_number: number; edition_total: number; data_uris: string[]; data_hash: string | null; metadata_uris: string[]; metadata_hash: string | null; license_uris: string[]; license_hash: string | null }
+/**
+ * Metadata changes for a single NFT
+ */
+export type NftMetadataUpdate = { 
+/**
+ * NFT ID
+ */
+nft_id: string; 
+/**
+ * Data URIs to add
+ */
+data_uris?: string[]; 
+/**
+ * Metadata URIs to add
+ */
+metadata_uris?: string[]; 
+/**
+ * License URIs to add
+ */
+license_uris?: string[] }
 /**
  * Individual NFT to mint
  */
@@ -1872,10 +2968,101 @@ royalty_address?: string | null;
  * Royalty percentage in ten-thousandths (e.g., 300 = 3%)
  */
 royalty_ten_thousandths?: number }
+/**
+ * Offer created for a single NFT
+ */
+export type NftOffer = { 
+/**
+ * NFT ID
+ */
+nft_id: string; 
+/**
+ * Offer string (bech32 encoded)
+ */
+offer: string; 
+/**
+ * Offer ID
+ */
+offer_id: string }
+/**
+ * How the preview images of an NFT were made from its data. Vector images are rasterized,
+ * animations are previewed as a strip of frames, and placeholders are generated with a badge for
+ * file types that can't be rendered. Videos aren't decoded, so they're previewed by the cover art
+ * embedded in the file, and videos without any get a placeholder instead.
+ */
+export type NftPreviewSource = "image" | "vector" | "animation" | "cover_art" | "placeholder"
 export type NftRecord = { launcher_id: string; collection_id: string | null; collection_name: string | null; minter_did: string | null; owner_did: string | null; visible: boolean; sensitive_content: boolean; name: string | null; created_height: number | null; coin_id: string; address: string; royalty_address: string; royalty_ten_thousandths: number; data_uris: string[]; data_hash: string | null; metadata_uris: string[]; metadata_hash: string | null; license_uris: string[]; license_hash: string | null; edition_number: number | null; edition_total: number | null; icon_url: string | null; created_timestamp: number | null; special_use_type: NftSpecialUseType | null }
 export type NftRoyalty = { royalty_address: string; royalty_basis_points: number }
+/**
+ * Royalty paid to the creator of an NFT in one asset
+ */
+export type NftRoyaltyPayment = { 
+/**
+ * NFT ID
+ */
+nft_id: string; 
+/**
+ * Address the royalty is paid to
+ */
+royalty_address: string; 
+/**
+ * Royalty percentage in basis points
+ */
+royalty_basis_points: number; 
+/**
+ * Asset ID the royalty is paid in (null for XCH)
+ */
+asset_id: string | null; 
+/**
+ * Share of the asset the NFT is traded for
+ */
+trade_price: Amount; 
+/**
+ * Royalty amount
+ */
+amount: Amount; 
+/**
+ * Whether the NFT is requested, in which case the maker pays the royalty
+ */
+is_requested: boolean }
 export type NftSortMode = "name" | "recent"
 export type NftSpecialUseType = "none" | "theme"
+/**
+ * The number of owned NFTs in a collection with a trait
+ */
+export type NftTraitCount = { 
+/**
+ * Trait type
+ */
+trait_type: string; 
+/**
+ * Value of the trait
+ */
+value: string; 
+/**
+ * Number of NFTs with this trait
+ */
+count: number }
+/**
+ * A trait which NFTs must have, either with an exact value or a numeric value within a range
+ */
+export type NftTraitFilter = { 
+/**
+ * Trait type
+ */
+trait_type: string; 
+/**
+ * Exact value of the trait
+ */
+value?: string | null; 
+/**
+ * Minimum numeric value of the trait, inclusive
+ */
+min?: number | null; 
+/**
+ * Maximum numeric value of the trait, inclusive
+ */
+max?: number | null }
 /**
  * Type of NFT URI
  */
@@ -1913,9 +3100,30 @@ hidden_puzzle_hash?: string | null;
  */
 amount: Amount }
 export type OfferAsset = { asset: Asset; amount: Amount; royalty: Amount; nft_royalty: NftRoyalty | null; option_assets: OptionAssets | null }
-export type OfferRecord = { offer_id: string; offer: string; status: OfferRecordStatus; creation_timestamp: number; summary: OfferSummary }
-export type OfferRecordStatus = "pending" | "active" | "completed" | "cancelled" | "expired"
+export type OfferCoinState = { coin_id: string; amount: Amount; status: OfferCoinStatus; spent_height: number | null }
+export type OfferCoinStatus = "unspent" | "spent" | "missing"
+export type OfferGroupOfferRecord = { price: string; is_reversed: boolean; offer: OfferRecord }
+export type OfferGroupRecord = { id: number; offered_asset_id: string | null; requested_asset_id: string | null; created_timestamp: number; offer_count: number; active_count: number; auto_repost: boolean; price_spread: string; stop_price: string | null; max_inventory: Amount | null; stop_reason: string | null }
+export type OfferPublicationRecord = { exchange: string; code: string; published_timestamp: number }
+export type OfferRecord = { offer_id: string; offer: string; status: OfferRecordStatus; creation_timestamp: number; summary: OfferSummary; blocks_until_expiration: number | null; seconds_until_expiration: number | null; publications: OfferPublicationRecord[]; invalid_reason: string | null; auto_recreate: boolean; recreated_offer_id: string | null; recreate_error: string | null }
+export type OfferRecordStatus = "pending" | "active" | "completed" | "cancelled" | "expired" | "invalid"
 export type OfferSummary = { fee: Amount; maker: OfferAsset[]; taker: OfferAsset[]; expiration_height: number | null; expiration_timestamp: number | null }
+/**
+ * Trade price revealed when an NFT is transferred
+ */
+export type OfferTradePrice = { 
+/**
+ * Asset ID (null for XCH)
+ */
+asset_id: string | null; 
+/**
+ * Amount of the asset
+ */
+amount: Amount; 
+/**
+ * Settlement puzzle hash of the asset
+ */
+puzzle_hash: string }
 /**
  * Asset specification for options
  */
@@ -1931,6 +3139,89 @@ amount: Amount }
 export type OptionAssets = { underlying_asset: Asset; underlying_amount: Amount; strike_asset: Asset; strike_amount: Amount; expiration_seconds: number }
 export type OptionRecord = { launcher_id: string; name: string | null; visible: boolean; coin_id: string; address: string; amount: Amount; underlying_asset: Asset; underlying_amount: Amount; underlying_coin_id: string; strike_asset: Asset; strike_amount: Amount; expiration_seconds: number; created_height: number | null; created_timestamp: number | null }
 export type OptionSortMode = "name" | "created_height" | "expiration_seconds"
+/**
+ * Pause or resume a mint job
+ */
+export type PauseMintJob = { 
+/**
+ * Mint job ID
+ */
+id: number; 
+/**
+ * Whether the mint job should be paused
+ */
+paused: boolean }
+/**
+ * Response with the updated mint job
+ */
+export type PauseMintJobResponse = { 
+/**
+ * The updated mint job
+ */
+mint_job: MintJobRecord }
+/**
+ * Pause or resume a payout
+ */
+export type PausePayout = { 
+/**
+ * Payout ID
+ */
+id: number; 
+/**
+ * Whether the payout should be paused
+ */
+paused: boolean }
+/**
+ * Response with the updated payout
+ */
+export type PausePayoutResponse = { 
+/**
+ * The updated payout
+ */
+payout: PayoutRecord }
+/**
+ * Pause or resume a scheduled payment
+ */
+export type PauseScheduledPayment = { 
+/**
+ * Scheduled payment ID
+ */
+id: number; 
+/**
+ * Whether the scheduled payment should be paused
+ */
+paused: boolean }
+/**
+ * Response with the updated scheduled payment
+ */
+export type PauseScheduledPaymentResponse = { 
+/**
+ * The updated scheduled payment
+ */
+scheduled_payment: ScheduledPaymentRecord }
+/**
+ * Individual payment in a multi-send transaction
+ */
+export type Payment = { 
+/**
+ * Optional asset ID (null for XCH)
+ */
+asset_id?: string | null; 
+/**
+ * Recipient address
+ */
+address: string; 
+/**
+ * Amount to send
+ */
+amount: Amount; 
+/**
+ * Optional memos
+ */
+memos?: string[] }
+export type PayoutFormat = "csv" | "json"
+export type PayoutPaymentRecord = { row_index: number; asset_id: string | null; address: string; amount: Amount; memos: string[]; transaction_id: string | null; is_confirmed: boolean }
+export type PayoutRecord = { id: number; name: string; fee: Amount; max_cost: number; is_paused: boolean; error: string | null; created_timestamp: number; payment_count: number; submitted_count: number; confirmed_count: number }
 export type PeerRecord = { ip_addr: string; port: number; peak_height: number; user_managed: boolean }
 export type PendingTransactionRecord = { transaction_id: string; fee: Amount; submitted_at: number | null }
 /**
@@ -1969,103 +3260,394 @@ pages_vacuumed: number;
  * Number of WAL pages checkpointed
  */
 wal_pages_checkpointed: number }
+/**
+ * Pin or unpin NFT media
+ */
+export type PinNftMedia = { 
+/**
+ * NFT IDs to pin or unpin
+ */
+nft_ids?: string[]; 
+/**
+ * Collection IDs to pin or unpin
+ */
+collection_ids?: string[]; 
+/**
+ * Whether to pin or unpin them
+ */
+pinned: boolean }
+/**
+ * Response after pinning or unpinning NFT media
+ */
+export type PinNftMediaResponse = Record<string, never>
+/**
+ * Check whether an offer can be taken
+ */
+export type PreflightOffer = { 
+/**
+ * Offer string to check
+ */
+offer: string; 
+/**
+ * Transaction fee the offer would be taken with
+ */
+fee: Amount }
+/**
+ * Response with the preflight verdict for an offer
+ */
+export type PreflightOfferResponse = { 
+/**
+ * Whether the offer can currently be taken
+ */
+takeable: boolean; 
+/**
+ * The state of each of the offered coins on chain
+ */
+coins: OfferCoinState[]; 
+/**
+ * Peak height of the peer the coins were fetched from
+ */
+peak_height: number | null; 
+/**
+ * Whether the offer has expired
+ */
+expired: boolean; 
+/**
+ * Block height at which the offer expires
+ */
+expiration_height: number | null; 
+/**
+ * Timestamp at which the offer expires
+ */
+expiration_timestamp: number | null; 
+/**
+ * Cost of the taker's spend, if it could be built and run
+ */
+cost: number | null; 
+/**
+ * Why the taker's spend couldn't be built or run
+ */
+error: string | null }
+/**
+ * Preview the royalties for an offer
+ */
+export type PreviewOfferRoyalties = { 
+/**
+ * Assets requested in the offer
+ */
+requested_assets: OfferAmount[]; 
+/**
+ * Assets offered in exchange
+ */
+offered_assets: OfferAmount[] }
+/**
+ * Response with the royalty breakdown of an offer
+ */
+export type PreviewOfferRoyaltiesResponse = { 
+/**
+ * Royalty owed for each NFT in each asset it's traded for
+ */
+royalties: NftRoyaltyPayment[]; 
+/**
+ * Trade prices revealed by each offered NFT which has royalties
+ */
+trade_prices: OfferTradePrice[] }
+/**
+ * Publish an offer to an exchange
+ */
+export type PublishOffer = { 
+/**
+ * Offer ID
+ */
+offer_id: string; 
+/**
+ * Name of the exchange to publish to
+ */
+exchange: string }
+/**
+ * Response with the code of a published offer
+ */
+export type PublishOfferResponse = { 
+/**
+ * Code the offer can be fetched by from the exchange
+ */
+code: string }
+/**
+ * Recover a DID with attestations
+ */
+export type RecoverDid = { 
+/**
+ * DID ID to recover
+ */
+did_id: string; 
+/**
+ * Recovery DIDs, in the order they were set
+ */
+recovery_did_ids: string[]; 
+/**
+ * Hex encoded attestations from the recovery DIDs
+ */
+attestations: string[]; 
+/**
+ * Public key the attestations approve, which must belong to the wallet
+ */
+public_key: string; 
+/**
+ * Transaction fee
+ */
+fee: Amount; 
+/**
+ * Whether to automatically submit the transaction
+ */
+auto_submit?: boolean }
+/**
+ * Response with the recovery transaction
+ */
+export type RecoverDidResponse = { 
+/**
+ * Transaction summary
+ */
+summary: TransactionSummary; 
+/**
+ * Spend bundle, including the attestations
+ */
+spend_bundle: SpendBundleJson; 
+/**
+ * Transaction ID
+ */
+transaction_id: string }
 /**
  * Re-download an `NFT`'s data and metadata from its URIs
  */
-export type RedownloadNft = { 
+export type RedownloadNft = { 
+/**
+ * The `NFT` ID to re-download
+ */
+nft_id: string }
+/**
+ * Response after re-downloading an `NFT`
+ */
+export type RedownloadNftResponse = Record<string, never>
+/**
+ * Remove a peer from the connection list
+ */
+export type RemovePeer = { 
+/**
+ * IP address or hostname of the peer
+ */
+ip: string; 
+/**
+ * Whether to ban the peer from reconnecting
+ */
+ban: boolean }
+/**
+ * Rename a wallet key
+ */
+export type RenameKey = { 
+/**
+ * Wallet fingerprint
+ */
+fingerprint: number; 
+/**
+ * New display name
+ */
+name: string }
+/**
+ * Response for key rename
+ */
+export type RenameKeyResponse = Record<string, never>
+/**
+ * Re-price an offer group
+ */
+export type RepriceOfferGroup = { 
+/**
+ * Offer group ID
+ */
+id: number; 
+/**
+ * Price of the first rung
+ */
+min_price: string; 
+/**
+ * Price of the last rung
+ */
+max_price: string; 
+/**
+ * Transaction fee
+ */
+fee: Amount; 
+/**
+ * Optional expiration timestamp
+ */
+expires_at_second?: number | null }
+/**
+ * Resynchronize wallet data with the blockchain
+ */
+export type Resync = { 
+/**
+ * The fingerprint of the wallet to resync
+ */
+fingerprint: number; 
+/**
+ * Delete all coin records during resync
+ */
+delete_coins?: boolean; 
+/**
+ * Delete all asset records during resync
+ */
+delete_assets?: boolean; 
+/**
+ * Delete all file records during resync
+ */
+delete_files?: boolean; 
+/**
+ * Delete all offer records during resync
+ */
+delete_offers?: boolean; 
+/**
+ * Delete all address records during resync
+ */
+delete_addresses?: boolean; 
+/**
+ * Delete all block records during resync
+ */
+delete_blocks?: boolean }
+/**
+ * Resynchronize a `CAT` token's metadata from an external source
+ */
+export type ResyncCat = { 
+/**
+ * The asset ID of the `CAT` token to resynchronize
+ */
+asset_id: string }
+/**
+ * Response after resynchronizing a `CAT` token
+ */
+export type ResyncCatResponse = Record<string, never>
+/**
+ * Response from resynchronizing the wallet
+ */
+export type ResyncResponse = Record<string, never>
+/**
+ * Check the failing URIs of NFTs again
+ */
+export type ReverifyNfts = { 
+/**
+ * NFT IDs to check again, or every owned NFT if empty
+ */
+nft_ids?: string[]; 
+/**
+ * Only check owned NFTs in this collection, if no NFT IDs are provided
+ */
+collection_id?: string | null }
+/**
+ * Response after queueing URIs to be checked again
+ */
+export type ReverifyNftsResponse = { 
+/**
+ * Number of URIs that will be checked again
+ */
+uris: number }
+/**
+ * Save a theme NFT to the wallet
+ */
+export type SaveUserTheme = { 
 /**
- * The `NFT` ID to re-download
+ * NFT ID of the theme
  */
 nft_id: string }
+export type SaveUserThemeResponse = Record<string, never>
+export type ScheduledPaymentRecord = { id: number; name: string; schedule: string; payments: Payment[]; fee: Amount; is_paused: boolean; next_run_timestamp: number; created_timestamp: number; runs: ScheduledPaymentRunRecord[] }
+export type ScheduledPaymentRunRecord = { transaction_id: string | null; error: string | null; run_timestamp: number }
 /**
- * Response after re-downloading an `NFT`
+ * Search the wallet
  */
-export type RedownloadNftResponse = Record<string, never>
+export type Search = { 
 /**
- * Remove a peer from the connection list
+ * Search query
  */
-export type RemovePeer = { 
+query: string; 
 /**
- * IP address or hostname of the peer
+ * Only include results of these kinds (all kinds if empty)
  */
-ip: string; 
+kinds?: SearchKind[]; 
 /**
- * Whether to ban the peer from reconnecting
+ * Starting offset for pagination
  */
-ban: boolean }
+offset: number; 
 /**
- * Rename a wallet key
+ * Number of results to return
  */
-export type RenameKey = { 
+limit: number }
 /**
- * Wallet fingerprint
+ * Search the offers listed on an exchange
  */
-fingerprint: number; 
+export type SearchExchangeOffers = { 
 /**
- * New display name
+ * Name of the exchange to search
  */
-name: string }
+exchange: string; 
 /**
- * Response for key rename
+ * Offered asset, as identified by the exchange (such as an asset ID or "xch")
  */
-export type RenameKeyResponse = Record<string, never>
+offered?: string | null; 
 /**
- * Resynchronize wallet data with the blockchain
+ * Requested asset, as identified by the exchange (such as an asset ID or "xch")
  */
-export type Resync = { 
+requested?: string | null; 
 /**
- * The fingerprint of the wallet to resync
+ * Page of results, starting from 1
  */
-fingerprint: number; 
+page: number; 
 /**
- * Delete all coin records during resync
+ * Number of offers per page
  */
-delete_coins?: boolean; 
+page_size: number }
 /**
- * Delete all asset records during resync
+ * Response with the offers found on an exchange
  */
-delete_assets?: boolean; 
+export type SearchExchangeOffersResponse = { 
 /**
- * Delete all file records during resync
+ * Offers in the order they were listed
  */
-delete_files?: boolean; 
+offers: ExchangeOfferRecord[] }
+export type SearchKind = "token" | "nft" | "did" | "option" | "collection" | "offer" | "transaction" | "contact"
 /**
- * Delete all offer records during resync
+ * Response with search results
  */
-delete_offers?: boolean; 
+export type SearchResponse = { 
 /**
- * Delete all address records during resync
+ * Results, the most relevant first
  */
-delete_addresses?: boolean; 
+results: SearchResult[]; 
 /**
- * Delete all block records during resync
+ * Total number of results
  */
-delete_blocks?: boolean }
+total: number }
 /**
- * Resynchronize a `CAT` token's metadata from an external source
+ * Something in the wallet which matched a search
  */
-export type ResyncCat = { 
+export type SearchResult = { 
 /**
- * The asset ID of the `CAT` token to resynchronize
+ * Kind of result
  */
-asset_id: string }
+kind: SearchKind; 
 /**
- * Response after resynchronizing a `CAT` token
+ * Asset ID, NFT ID, DID ID, option ID, collection ID, offer ID, transaction ID or contact ID
  */
-export type ResyncCatResponse = Record<string, never>
+id: string; 
 /**
- * Response from resynchronizing the wallet
+ * Name, if it has one
  */
-export type ResyncResponse = Record<string, never>
+name: string | null; 
 /**
- * Save a theme NFT to the wallet
+ * Ticker of a token
  */
-export type SaveUserTheme = { 
+ticker: string | null; 
 /**
- * NFT ID of the theme
+ * Relevance of the result, where higher is more relevant
  */
-nft_id: string }
-export type SaveUserThemeResponse = Record<string, never>
+score: number }
 export type SecretKeyInfo = { mnemonic: string | null; secret_key: string }
 /**
  * Send CAT tokens to an address
@@ -2151,6 +3733,19 @@ clawback?: number | null;
  * Whether to automatically submit the transaction
  */
 auto_submit?: boolean }
+/**
+ * Allow background jobs to sign transactions for a wallet
+ */
+export type SetBackgroundSigning = { 
+/**
+ * Wallet fingerprint
+ */
+fingerprint: number; 
+/**
+ * Whether scheduled payments, payouts, mint jobs, offer reposts and recreated offers are
+ * signed without the wallet being unlocked
+ */
+enabled: boolean }
 /**
  * Set the change address for transactions
  */
@@ -2183,6 +3778,30 @@ fingerprint: number;
  * Delta sync setting (null to use default)
  */
 delta_sync: boolean | null }
+/**
+ * Set the recovery list of a DID
+ */
+export type SetDidRecoveryList = { 
+/**
+ * DID ID to update
+ */
+did_id: string; 
+/**
+ * DID IDs which can recover the DID
+ */
+recovery_did_ids: string[]; 
+/**
+ * Number of recovery DIDs which must attest to a recovery
+ */
+num_verifications_required: number; 
+/**
+ * Transaction fee
+ */
+fee: Amount; 
+/**
+ * Whether to automatically submit the transaction
+ */
+auto_submit?: boolean }
 /**
  * Enable or disable automatic peer discovery
  */
@@ -2191,6 +3810,34 @@ export type SetDiscoverPeers = {
  * Whether to enable peer discovery
  */
 discover_peers: boolean }
+/**
+ * Configure the media cache
+ */
+export type SetMediaCachePolicy = { 
+/**
+ * Maximum size of the cache in bytes, or unlimited if not set
+ */
+max_bytes?: number | null; 
+/**
+ * Store files in a content-addressed directory next to the database
+ */
+store_externally?: boolean }
+/**
+ * Response after configuring the media cache
+ */
+export type SetMediaCachePolicyResponse = { 
+/**
+ * Number of files evicted to fit in the budget
+ */
+evicted_files: number; 
+/**
+ * Number of bytes evicted to fit in the budget
+ */
+evicted_bytes: number; 
+/**
+ * Number of files moved into or out of the database
+ */
+relocated_files: number }
 /**
  * Set the active network
  */
@@ -2211,6 +3858,70 @@ fingerprint: number;
  * Network name (null to reset to default)
  */
 name: string | null }
+/**
+ * Set whether an offer is made again if it becomes invalid
+ */
+export type SetOfferAutoRecreate = { 
+/**
+ * Offer ID to update
+ */
+offer_id: string; 
+/**
+ * Whether to make the offer again if it becomes invalid
+ */
+auto_recreate: boolean }
+/**
+ * Response for setting whether an offer is made again
+ */
+export type SetOfferAutoRecreateResponse = Record<string, never>
+/**
+ * Set the repost policy of an offer group
+ */
+export type SetOfferGroupPolicy = { 
+/**
+ * Offer group ID
+ */
+id: number; 
+/**
+ * Whether to replace offers once they are taken
+ */
+auto_repost: boolean; 
+/**
+ * Price difference between a taken offer and its replacement
+ */
+price_spread?: string | null; 
+/**
+ * Lowest price a replacement can be made at
+ */
+stop_price?: string | null; 
+/**
+ * Most of the offered asset that the group's active offers can hold
+ */
+max_inventory?: Amount | null }
+/**
+ * Response for setting the repost policy of an offer group
+ */
+export type SetOfferGroupPolicyResponse = Record<string, never>
+/**
+ * Set the spending policy
+ */
+export type SetSpendingPolicy = { 
+/**
+ * Password used to unlock the wallet's keys
+ */
+password: string; 
+/**
+ * The new spending policy
+ */
+policy: SpendingPolicy }
+/**
+ * Response with the updated spending policy
+ */
+export type SetSpendingPolicyResponse = { 
+/**
+ * The updated spending policy
+ */
+policy: SpendingPolicy }
 /**
  * Set target number of peers to maintain
  */
@@ -2283,6 +3994,30 @@ publicKey: string;
  * Signature
  */
 signature: string }
+/**
+ * Sign a message with a DID
+ */
+export type SignMessageByDid = { 
+/**
+ * DID ID whose key to use
+ */
+did_id: string; 
+/**
+ * Message to sign
+ */
+message: string }
+/**
+ * Response with signed message
+ */
+export type SignMessageByDidResponse = { 
+/**
+ * Public key used
+ */
+public_key: string; 
+/**
+ * Signature
+ */
+signature: string }
 /**
  * Sign a message with a public key
  */
@@ -2344,6 +4079,46 @@ locked: boolean;
  * Optional lineage proof for CAT coins
  */
 lineageProof: LineageProof | null }
+/**
+ * Outflow limits for an asset
+ */
+export type SpendingLimit = { 
+/**
+ * Asset ID of the CAT, or null for XCH
+ */
+asset_id?: string | null; 
+/**
+ * Maximum amount sent in a single transaction
+ */
+max_per_transaction?: Amount | null; 
+/**
+ * Maximum amount sent in any 24 hour period
+ */
+max_per_day?: Amount | null }
+/**
+ * Rules that every transaction must satisfy before it is signed
+ */
+export type SpendingPolicy = { 
+/**
+ * Outflow limits for individual assets
+ */
+limits?: SpendingLimit[]; 
+/**
+ * If not empty, the only addresses that funds can be sent to
+ */
+allowed_addresses?: string[]; 
+/**
+ * Maximum fee for a single transaction
+ */
+max_fee?: Amount | null; 
+/**
+ * Whether NFTs can be transferred out of the wallet
+ */
+block_nft_transfers?: boolean; 
+/**
+ * Whether DIDs can be transferred out of the wallet
+ */
+block_did_transfers?: boolean }
 /**
  * Split coins into multiple smaller coins
  */
@@ -2376,7 +4151,7 @@ spend_bundle: SpendBundleJson }
  * Response for transaction submission
  */
 export type SubmitTransactionResponse = Record<string, never>
-export type SyncEvent = { type: "start"; ip: string } | { type: "stop" } | { type: "subscribed" } | { type: "derivation" } | { type: "coin_state" } | { type: "transaction_failed"; transaction_id: string; error: string | null } | { type: "puzzle_batch_synced" } | { type: "cat_info" } | { type: "did_info" } | { type: "nft_data" }
+export type SyncEvent = { type: "start"; ip: string } | { type: "stop" } | { type: "subscribed" } | { type: "derivation" } | { type: "coin_state" } | { type: "transaction_failed"; transaction_id: string; error: string | null } | { type: "puzzle_batch_synced" } | { type: "cat_info" } | { type: "did_info" } | { type: "nft_data" } | { type: "offer_group_updated"; offer_group_id: number } | { type: "mint_job_updated"; mint_job_id: number }
 /**
  * Accept an offer
  */
@@ -2409,10 +4184,47 @@ spend_bundle: SpendBundleJson;
  * Transaction ID
  */
 transaction_id: string }
+/**
+ * Accept multiple offers at once
+ */
+export type TakeOffers = { 
+/**
+ * Offer strings to accept
+ */
+offers: string[]; 
+/**
+ * Transaction fee
+ */
+fee: Amount; 
+/**
+ * Whether to automatically submit the transaction
+ */
+auto_submit?: boolean }
+/**
+ * Response with accepted offers details
+ */
+export type TakeOffersResponse = { 
+/**
+ * Summary of the combined transaction
+ */
+summary: TransactionSummary; 
+/**
+ * Spend bundle
+ */
+spend_bundle: SpendBundleJson; 
+/**
+ * Transaction ID
+ */
+transaction_id: string }
 export type TokenRecord = { asset_id: string | null; name: string | null; ticker: string | null; precision: number; description: string | null; icon_url: string | null; visible: boolean; balance: Amount; revocation_address: string | null }
-export type TransactionCoinRecord = { coin_id: string; amount: Amount; address: string | null; address_kind: AddressKind; asset: Asset }
+export type TradeAmount = { asset: Asset; amount: Amount; royalty: Amount }
+export type TradeAssetPnl = { asset: Asset; received: Amount; given: Amount; royalties_paid: Amount; net: string; bought: Amount; sold: Amount; average_buy_price: string | null; average_sell_price: string | null; realized_pnl: string | null }
+export type TradePrice = { base_asset_id: string | null; quote_asset_id: string | null; price: string }
+export type TradeRecord = { offer_id: string; is_maker: boolean; transaction_id: string | null; fee: Amount; fill_height: number | null; fill_timestamp: number | null; inserted_timestamp: number; given: TradeAmount[]; received: TradeAmount[]; prices: TradePrice[] }
+export type TransactionCoinRecord = { coin_id: string; amount: Amount; address: string | null; address_kind: AddressKind; asset: Asset; contact: string | null }
+export type TransactionExportRecord = { height: number; timestamp: number | null; transaction_id: string | null; asset_kind: AssetKind; asset_id: string | null; ticker: string | null; name: string | null; precision: number; amount: string; fee: Amount; counterparty: string | null; memo: string | null; offer_id: string | null; label: string | null }
 export type TransactionInput = { coin_id: string; amount: Amount; address: string; asset: Asset | null; outputs: TransactionOutput[] }
-export type TransactionOutput = { coin_id: string; amount: Amount; address: string; receiving: boolean; burning: boolean }
+export type TransactionOutput = { coin_id: string; amount: Amount; address: string; receiving: boolean; burning: boolean; contact: string | null; is_new_address: boolean }
 export type TransactionRecord = { height: number; timestamp: number | null; spent: TransactionCoinRecord[]; created: TransactionCoinRecord[] }
 /**
  * Standard transaction response
@@ -2426,7 +4238,7 @@ summary: TransactionSummary;
  * Coin spends in the transaction
  */
 coin_spends: CoinSpendJson[] }
-export type TransactionSummary = { fee: Amount; inputs: TransactionInput[] }
+export type TransactionSummary = { fee: Amount; inputs: TransactionInput[]; nft_metadata_diffs: NftMetadataDiff[] }
 /**
  * Transfer DIDs to a new address
  */
@@ -2512,6 +4324,38 @@ record: TokenRecord }
  * Response after updating a `CAT` token
  */
 export type UpdateCatResponse = Record<string, never>
+/**
+ * Update an existing contact
+ */
+export type UpdateContact = { 
+/**
+ * Contact ID
+ */
+id: number; 
+/**
+ * Display name of the contact
+ */
+name: string; 
+/**
+ * Addresses belonging to the contact
+ */
+addresses: ContactAddress[]; 
+/**
+ * Optional notes
+ */
+notes?: string | null; 
+/**
+ * Optional DID ID associated with the contact
+ */
+did_id?: string | null }
+/**
+ * Response with the updated contact
+ */
+export type UpdateContactResponse = { 
+/**
+ * Updated contact
+ */
+contact: ContactRecord }
 /**
  * Update a `DID`'s name and visibility settings
  */
@@ -2528,6 +4372,26 @@ name: string | null;
  * Whether the `DID` should be visible in the UI
  */
 visible: boolean }
+/**
+ * Update the metadata of a DID
+ */
+export type UpdateDidMetadata = { 
+/**
+ * DID ID to update
+ */
+did_id: string; 
+/**
+ * New metadata, replacing the existing metadata
+ */
+metadata: DidMetadataEntry[]; 
+/**
+ * Transaction fee
+ */
+fee: Amount; 
+/**
+ * Whether to automatically submit the transaction
+ */
+auto_submit?: boolean }
 /**
  * Response after updating a `DID`
  */
@@ -2560,6 +4424,23 @@ visible: boolean }
  * Response after updating an `NFT` collection
  */
 export type UpdateNftCollectionResponse = Record<string, never>
+/**
+ * Update the metadata of NFTs
+ */
+export type UpdateNftMetadata = { 
+/**
+ * Metadata updates to apply
+ */
+updates: NftMetadataUpdate[]; 
+/**
+ * Transaction fee
+ */
+fee: Amount; 
+/**
+ * Whether to automatically submit the transaction
+ */
+auto_submit?: boolean }
+export type UpdateNftMetadataResponse = TransactionResponse
 /**
  * Response after updating an `NFT`
  */
@@ -2580,6 +4461,39 @@ visible: boolean }
  * Response after updating an option
  */
 export type UpdateOptionResponse = Record<string, never>
+export type UriIntegrityRecord = { uri: string; status: UriIntegrityStatus; error: string | null; last_checked_timestamp: number | null; failed_attempts: number }
+/**
+ * The result of the last check of a single URI
+ */
+export type UriIntegrityStatus = "verified" | "pending" | "failed" | "mismatch"
+/**
+ * Verify a message signed with a DID
+ */
+export type VerifyDidSignature = { 
+/**
+ * DID ID which signed the message
+ */
+did_id: string; 
+/**
+ * Message which was signed
+ */
+message: string; 
+/**
+ * Public key which signed the message
+ */
+public_key: string; 
+/**
+ * Signature
+ */
+signature: string }
+/**
+ * Response with the verification result
+ */
+export type VerifyDidSignatureResponse = { 
+/**
+ * Whether the signature is valid and the public key owns the DID
+ */
+is_valid: boolean }
 /**
  * View coin spends without signing
  */
@@ -86,6 +86,10 @@ export default function ErrorDialog({ error, setError }: ErrorDialogProps) {
       kind = 'NFC';
       break;
 
+    case 'policy':
+      kind = 'Spending Policy';
+      break;
+
     case 'database_migration':
       kind = 'Database Migration';
       break;
@@ -38,6 +38,9 @@ function createDefaultDidRecord(name: string, launcherId: string): DidRecord {
     amount: 0,
     created_height: 0,
     recovery_hash: '',
+    recovery_did_ids: null,
+    num_verifications_required: 0,
+    metadata: [],
   };
 }
 
@@ -300,6 +300,7 @@ export function Offers() {
                         <SelectItem value='completed'>Completed</SelectItem>
                         <SelectItem value='cancelled'>Cancelled</SelectItem>
                         <SelectItem value='expired'>Expired</SelectItem>
+                        <SelectItem value='invalid'>Invalid</SelectItem>
                       </SelectContent>
                     </Select>
                   </div>